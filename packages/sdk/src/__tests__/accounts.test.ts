@@ -0,0 +1,53 @@
+/**
+ * Account Kinds Module Tests
+ *
+ * Tests for classifying program accounts by discriminator
+ */
+
+import { describe, it, expect } from 'vitest';
+import { utils } from '@coral-xyz/anchor';
+import { sha256, bytesToHex, hexToBytes } from '../crypto';
+import {
+  accountKindFilter,
+  classifyAccount,
+  subsystemAccountKinds,
+  VEIL_ACCOUNT_KINDS,
+  VeilAccountName,
+} from '../accounts';
+
+const CUSTOM_DISCRIMINATORS = ['VoterWeightRecord', 'RealmsVoterWeightRecord', 'RealmsMaxVoterWeightRecord'];
+
+describe('Account Kinds Module', () => {
+  it('should use Anchor account discriminators', async () => {
+    for (const name of Object.keys(VEIL_ACCOUNT_KINDS) as VeilAccountName[]) {
+      if (CUSTOM_DISCRIMINATORS.includes(name)) continue;
+      const digest = await sha256(new TextEncoder().encode(`account:${name}`));
+      expect(VEIL_ACCOUNT_KINDS[name].discriminator).toBe(bytesToHex(digest.slice(0, 8)));
+    }
+  });
+
+  it('should give every kind a distinct discriminator', () => {
+    const discriminators = Object.values(VEIL_ACCOUNT_KINDS).map((kind) => kind.discriminator);
+    expect(new Set(discriminators).size).toBe(discriminators.length);
+  });
+
+  it('should classify account data by its first eight bytes', () => {
+    const data = new Uint8Array(64);
+    data.set(hexToBytes(VEIL_ACCOUNT_KINDS.ShieldedPool.discriminator));
+    expect(classifyAccount(data)).toEqual({ name: 'ShieldedPool', subsystem: 'shielded' });
+
+    expect(classifyAccount(new Uint8Array(64))).toBeNull();
+    expect(classifyAccount(new Uint8Array(4))).toBeNull();
+  });
+
+  it('should filter on the discriminator at offset 0', () => {
+    const filter = accountKindFilter('VoteRecord') as { memcmp: { offset: number; bytes: string } };
+    expect(filter.memcmp.offset).toBe(0);
+    expect(bytesToHex(utils.bytes.bs58.decode(filter.memcmp.bytes))).toBe(VEIL_ACCOUNT_KINDS.VoteRecord.discriminator);
+  });
+
+  it('should list the kinds of a subsystem', () => {
+    expect(subsystemAccountKinds('relayer')).toEqual(['RelayerRegistry', 'RelayerRecord']);
+    expect(subsystemAccountKinds('voting')).toContain('QuadraticVoteRecord');
+  });
+});
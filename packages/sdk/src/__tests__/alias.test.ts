@@ -0,0 +1,83 @@
+/**
+ * Alias Module Tests
+ *
+ * Tests for private alias hashing, meta-address encryption and resolution
+ */
+
+import { describe, it, expect, vi } from 'vitest';
+import { Connection, Keypair } from '@solana/web3.js';
+import {
+  aliasHash,
+  createAliasSalt,
+  decryptMetaAddress,
+  encryptMetaAddress,
+  getAliasPDA,
+  normalizeAliasName,
+  resolveAlias,
+  ALIAS_SALT_LENGTH,
+} from '../alias';
+
+const meta = {
+  spendPublicKey: new Uint8Array(32).fill(7),
+  viewPublicKey: new Uint8Array(32).fill(9),
+};
+
+describe('Alias Module', () => {
+  describe('aliasHash', () => {
+    it('should be deterministic for the same normalised name and salt', async () => {
+      const salt = createAliasSalt();
+      expect(salt.length).toBe(ALIAS_SALT_LENGTH);
+      expect(normalizeAliasName('  Alice ')).toBe('alice');
+      expect(await aliasHash('Alice', salt)).toEqual(await aliasHash('alice ', salt));
+    });
+
+    it('should differ per salt', async () => {
+      expect(await aliasHash('alice', createAliasSalt())).not.toEqual(await aliasHash('alice', createAliasSalt()));
+    });
+
+    it('should reject empty names and short salts', async () => {
+      await expect(aliasHash('  ', createAliasSalt())).rejects.toThrow();
+      await expect(aliasHash('alice', new Uint8Array(16))).rejects.toThrow();
+    });
+  });
+
+  describe('meta-address encryption', () => {
+    it('should round-trip with the right name and salt', async () => {
+      const salt = createAliasSalt();
+      const encrypted = await encryptMetaAddress(meta, 'alice', salt);
+      expect(encrypted.length).toBe(64);
+      expect(encrypted.slice(0, 32)).not.toEqual(meta.spendPublicKey);
+      expect(await decryptMetaAddress(encrypted, 'Alice', salt)).toEqual(meta);
+    });
+
+    it('should not reveal the meta-address to the wrong name', async () => {
+      const salt = createAliasSalt();
+      const encrypted = await encryptMetaAddress(meta, 'alice', salt);
+      expect(await decryptMetaAddress(encrypted, 'bob', salt)).not.toEqual(meta);
+    });
+  });
+
+  describe('resolveAlias', () => {
+    it('should decrypt the registered record', async () => {
+      const salt = createAliasSalt();
+      const hash = await aliasHash('alice', salt);
+      const wallet = Keypair.generate().publicKey;
+
+      const data = new Uint8Array(8 + 32 + 32 + 64 + 8 + 8 + 2);
+      data.set(hash, 8);
+      data.set(wallet.toBytes(), 40);
+      data.set(await encryptMetaAddress(meta, 'alice', salt), 72);
+
+      const getAccountInfo = vi.fn().mockResolvedValue({ data: Buffer.from(data) });
+      const connection = { getAccountInfo } as unknown as Connection;
+
+      expect(await resolveAlias(connection, 'alice', salt)).toEqual(meta);
+      expect(getAccountInfo).toHaveBeenCalledWith(getAliasPDA(hash)[0]);
+    });
+
+    it('should return null for unregistered aliases', async () => {
+      const connection = { getAccountInfo: vi.fn().mockResolvedValue(null) } as unknown as Connection;
+      expect(await resolveAlias(connection, 'nobody', createAliasSalt())).toBeNull();
+    });
+  });
+});
@@ -0,0 +1,101 @@
+/**
+ * Coin Selection Module Tests
+ *
+ * Tests for picking notes to spend over synthetic note sets
+ */
+
+import { describe, it, expect } from 'vitest';
+import { selectNotes, SelectableNote, SelectionStrategy } from '../coinselect';
+
+const SOL = 1_000_000_000n;
+
+function notes(...values: bigint[]): SelectableNote[] {
+  return values.map((value, i) => ({ value, pool: 'pool-a', createdAt: 1_700_000_000 + i }));
+}
+
+function values(selected: SelectableNote[]): bigint[] {
+  return selected.map((note) => note.value);
+}
+
+describe('Coin Selection Module', () => {
+  it('should spend the largest notes first', () => {
+    const result = selectNotes(notes(1n * SOL, 5n * SOL, 2n * SOL, 3n * SOL), 6n * SOL, {
+      strategy: SelectionStrategy.LargestFirst,
+    });
+
+    expect(values(result.selected)).toEqual([5n * SOL, 3n * SOL]);
+    expect(result.change).toBe(2n * SOL);
+  });
+
+  it('should find a changeless set with branch and bound', () => {
+    const result = selectNotes(notes(7n * SOL, 5n * SOL, 4n * SOL, 3n * SOL), 8n * SOL, {
+      strategy: SelectionStrategy.BranchAndBound,
+    });
+
+    expect(values(result.selected)).toEqual([5n * SOL, 3n * SOL]);
+    expect(result.change).toBe(0n);
+  });
+
+  it('should avoid dust change', () => {
+    const dustThreshold = SOL / 10n;
+    const set = notes(3n * SOL + 1_000n, 2n * SOL, 1n * SOL + SOL / 2n);
+
+    const result = selectNotes(set, 3n * SOL, { strategy: SelectionStrategy.BranchAndBound, dustThreshold });
+
+    expect(result.isDust).toBe(false);
+    expect(values(result.selected)).toEqual([2n * SOL, 1n * SOL + SOL / 2n]);
+  });
+
+  it('should prefer a single note for privacy', () => {
+    const result = selectNotes(notes(1n * SOL, 2n * SOL, 10n * SOL, 2n * SOL), 4n * SOL);
+
+    expect(values(result.selected)).toEqual([10n * SOL]);
+    expect(result.change).toBe(6n * SOL);
+  });
+
+  it('should prefer older notes among equal choices', () => {
+    const result = selectNotes(notes(5n * SOL, 5n * SOL, 5n * SOL), 4n * SOL);
+
+    expect(result.selected[0].createdAt).toBe(1_700_000_000);
+  });
+
+  it('should never combine notes of different pools', () => {
+    const set = [
+      { value: 3n * SOL, pool: 'pool-a' },
+      { value: 3n * SOL, pool: 'pool-b' },
+      { value: 4n * SOL, pool: 'pool-b' },
+    ];
+
+    const result = selectNotes(set, 6n * SOL);
+
+    expect(result.selected.every((note) => note.pool === 'pool-b')).toBe(true);
+    expect(() => selectNotes(set, 8n * SOL)).toThrow();
+  });
+
+  it('should skip spent and locked notes', () => {
+    const set: SelectableNote[] = [
+      { value: 9n * SOL, isSpent: true },
+      { value: 8n * SOL, unlockAt: 2_000 },
+      { value: 2n * SOL, unlockAt: 500 },
+    ];
+
+    expect(values(selectNotes(set, 2n * SOL, { now: 1_000 }).selected)).toEqual([2n * SOL]);
+    expect(() => selectNotes(set, 3n * SOL, { now: 1_000 })).toThrow();
+  });
+
+  it('should respect the input limit', () => {
+    const set = notes(...Array.from({ length: 10 }, () => 1n * SOL));
+
+    expect(selectNotes(set, 3n * SOL, { maxInputs: 3 }).selected).toHaveLength(3);
+    expect(() => selectNotes(set, 5n * SOL, { maxInputs: 4 })).toThrow();
+  });
+
+  it('should fall back to largest first when the search budget runs out', () => {
+    const set = notes(...Array.from({ length: 64 }, (_, i) => BigInt(i + 1) * 1_000_003n));
+    const target = 120_000_000n;
+
+    const result = selectNotes(set, target, { strategy: SelectionStrategy.BranchAndBound, maxTries: 1 });
+
+    expect(result.total >= target).toBe(true);
+  });
+});
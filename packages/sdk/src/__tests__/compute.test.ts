@@ -0,0 +1,82 @@
+/**
+ * Compute Budget Module Tests
+ *
+ * Tests for compute unit limits and priority fees on SDK transactions
+ */
+
+import { describe, it, expect, vi } from 'vitest';
+import { ComputeBudgetProgram, Connection, Keypair, SystemProgram, Transaction } from '@solana/web3.js';
+import {
+  computeBudgetInstructions,
+  estimateComputeUnits,
+  recommendedComputeUnitPrice,
+  withComputeBudget,
+  DEFAULT_INSTRUCTION_COMPUTE_UNITS,
+  INSTRUCTION_COMPUTE_UNITS,
+  MAX_TRANSACTION_COMPUTE_UNITS,
+} from '../compute';
+
+describe('Compute Budget Module', () => {
+  describe('estimateComputeUnits', () => {
+    it('should size the limit from the benchmark table', () => {
+      expect(estimateComputeUnits(['shield_deposit'])).toBe(INSTRUCTION_COMPUTE_UNITS.shield_deposit + 150);
+    });
+
+    it('should fall back to the runtime default for unbenchmarked instructions', () => {
+      expect(estimateComputeUnits(['shield_withdraw'])).toBe(DEFAULT_INSTRUCTION_COMPUTE_UNITS + 150);
+    });
+
+    it('should apply per-instruction and whole-transaction overrides', () => {
+      expect(estimateComputeUnits(['shield_withdraw'], { instructionUnits: { shield_withdraw: 400_000 } }))
+        .toBe(400_150);
+      expect(estimateComputeUnits(['shield_deposit'], { computeUnitLimit: 77_000 })).toBe(77_000);
+    });
+
+    it('should cap the limit at the transaction maximum', () => {
+      const names = Array.from({ length: 10 }, () => 'shield_withdraw');
+      expect(estimateComputeUnits(names)).toBe(MAX_TRANSACTION_COMPUTE_UNITS);
+    });
+  });
+
+  describe('withComputeBudget', () => {
+    const transfer = SystemProgram.transfer({
+      fromPubkey: Keypair.generate().publicKey,
+      toPubkey: Keypair.generate().publicKey,
+      lamports: 1,
+    });
+
+    it('should prepend the limit and, when priced, the price', () => {
+      const tx = withComputeBudget(new Transaction().add(transfer), ['shield_deposit'], { computeUnitPrice: 5_000 });
+
+      expect(tx.instructions).toHaveLength(3);
+      expect(tx.instructions[0].programId.equals(ComputeBudgetProgram.programId)).toBe(true);
+      expect(tx.instructions[1].programId.equals(ComputeBudgetProgram.programId)).toBe(true);
+      expect(tx.instructions[2]).toBe(transfer);
+    });
+
+    it('should leave the transaction untouched when disabled', () => {
+      expect(computeBudgetInstructions(['shield_deposit'], { disabled: true })).toEqual([]);
+      expect(computeBudgetInstructions(['shield_deposit'])).toHaveLength(1);
+    });
+  });
+
+  describe('recommendedComputeUnitPrice', () => {
+    it('should return the median of the non-zero recent fees', async () => {
+      const connection = {
+        getRecentPrioritizationFees: vi.fn().mockResolvedValue(
+          [0, 300, 100, 0, 200].map((prioritizationFee, slot) => ({ slot, prioritizationFee }))
+        ),
+      } as unknown as Connection;
+
+      expect(await recommendedComputeUnitPrice(connection)).toBe(200);
+    });
+
+    it('should return zero when nobody pays for priority', async () => {
+      const connection = {
+        getRecentPrioritizationFees: vi.fn().mockResolvedValue([{ slot: 1, prioritizationFee: 0 }]),
+      } as unknown as Connection;
+
+      expect(await recommendedComputeUnitPrice(connection)).toBe(0);
+    });
+  });
+});
@@ -0,0 +1,286 @@
+/**
+ * Ledger Module Tests
+ *
+ * Tests for event replay and the double-entry accounting export
+ */
+
+import { describe, it, expect } from 'vitest';
+import { Keypair } from '@solana/web3.js';
+import {
+  buildLedger,
+  parseCpiEvents,
+  parseProgramEvents,
+  ledgerBalances,
+  ledgerToCsv,
+  LEDGER_ACCOUNTS,
+  LedgerViewingKey,
+  NoteFetcher,
+  VeilEventRecord
+} from '../ledger';
+import { VEIL_PROGRAM_ID } from '../shielded';
+import { bytesToHex } from '../crypto';
+
+const pool = Keypair.generate().publicKey;
+const shielded = LEDGER_ACCOUNTS.shielded(pool.toBase58());
+
+/** 32-byte value tagged by its first byte */
+const tagged = (tag: number) => {
+  const value = new Uint8Array(32);
+  value[0] = tag;
+  return value;
+};
+
+/** Note ciphertexts on-chain: ours start with 1 and carry the amount */
+const notes = new Map<string, Uint8Array>();
+const note = (commitment: Uint8Array, amount: bigint, ours = true) => {
+  const encrypted = new Uint8Array(64);
+  encrypted[0] = ours ? 1 : 0;
+  new DataView(encrypted.buffer).setBigUint64(8, amount, true);
+  notes.set(bytesToHex(commitment), encrypted);
+  return commitment;
+};
+const fetchNote: NoteFetcher = async (_pool, commitment) => notes.get(bytesToHex(commitment)) ?? null;
+
+/** Nullifier = commitment with the tag moved to the last byte */
+const nullifierOf = (commitment: Uint8Array) => {
+  const nullifier = new Uint8Array(32);
+  nullifier[31] = commitment[0];
+  return nullifier;
+};
+
+const viewingKey: LedgerViewingKey = {
+  decryptNote: (encrypted) => encrypted[0] === 1
+    ? {
+      amount: new DataView(encrypted.buffer, encrypted.byteOffset).getBigUint64(8, true),
+      blindingFactor: new Uint8Array(32),
+      ownerCommitment: new Uint8Array(32),
+      unlockAt: 0,
+    }
+    : null,
+  nullifierFor: async (commitment) => nullifierOf(commitment),
+  relayerFee: (feeCommitment) => feeCommitment[0] === 99 ? 5_000n : null,
+};
+
+let slot = 100;
+const event = (name: string, data: Record<string, unknown>, eventIndex = 0): VeilEventRecord => ({
+  name,
+  data: { pool, ...data },
+  signature: `sig${slot}`,
+  slot: slot++,
+  blockTime: 1_700_000_000 + slot,
+  eventIndex,
+});
+
+describe('Ledger Module', () => {
+  describe('buildLedger', () => {
+    it('should record a deposit of our note', async () => {
+      const commitment = note(tagged(1), 1_000_000n);
+      const entries = await buildLedger(
+        [event('ShieldedDeposit', { note_commitment: [...commitment] })],
+        viewingKey,
+        fetchNote
+      );
+
+      expect(entries).toHaveLength(1);
+      expect(entries[0].kind).toBe('deposit');
+      expect(entries[0].postings).toEqual([
+        { account: shielded, amount: 1_000_000n },
+        { account: LEDGER_ACCOUNTS.external, amount: -1_000_000n },
+      ]);
+    });
+
+    it('should decrypt deposits from the ciphertext in the event', async () => {
+      const commitment = tagged(14);
+      const encrypted = new Uint8Array(64);
+      encrypted[0] = 1;
+      new DataView(encrypted.buffer).setBigUint64(8, 250_000n, true);
+
+      const entries = await buildLedger(
+        [event('ShieldedDeposit', { note_commitment: commitment, encrypted_note: [...encrypted] })],
+        viewingKey,
+        async () => null
+      );
+
+      expect(entries).toHaveLength(1);
+      expect(entries[0].postings[0]).toEqual({ account: shielded, amount: 250_000n });
+    });
+
+    it('should ignore notes the viewing key cannot open', async () => {
+      const commitment = note(tagged(2), 1_000_000n, false);
+      const entries = await buildLedger(
+        [
+          event('ShieldedDeposit', { note_commitment: commitment }),
+          event('ShieldedWithdraw', { nullifier: nullifierOf(commitment), output_commitment: new Uint8Array(32) }),
+        ],
+        viewingKey,
+        fetchNote
+      );
+
+      expect(entries).toHaveLength(0);
+    });
+
+    it('should net change notes out of withdrawals', async () => {
+      const input = note(tagged(3), 1_000_000n);
+      const change = note(tagged(4), 300_000n);
+      const entries = await buildLedger(
+        [
+          event('ShieldedDeposit', { note_commitment: input }),
+          event('ShieldedWithdraw', { nullifier: nullifierOf(input), output_commitment: change }),
+          event('ShieldedWithdraw', { nullifier: nullifierOf(change), output_commitment: new Uint8Array(32) }),
+        ],
+        viewingKey,
+        fetchNote
+      );
+
+      expect(entries.map((entry) => entry.kind)).toEqual(['deposit', 'withdrawal', 'withdrawal']);
+      expect(entries[1].postings[0]).toEqual({ account: shielded, amount: -700_000n });
+      expect(entries[2].postings[0]).toEqual({ account: shielded, amount: -300_000n });
+      expect(ledgerBalances(entries).get(shielded)).toBe(0n);
+    });
+
+    it('should split relayer fees from relayed withdrawals', async () => {
+      const input = note(tagged(5), 1_000_000n);
+      const entries = await buildLedger(
+        [
+          event('ShieldedDeposit', { noteCommitment: input }),
+          event('ShieldedWithdrawRelayed', {
+            nullifier: nullifierOf(input),
+            outputCommitment: new Uint8Array(32),
+            feeCommitment: tagged(99),
+          }),
+        ],
+        viewingKey,
+        fetchNote
+      );
+
+      const balances = ledgerBalances(entries);
+      expect(balances.get(LEDGER_ACCOUNTS.external)).toBe(-1_000_000n + 995_000n);
+      expect(balances.get(LEDGER_ACCOUNTS.fees)).toBe(5_000n);
+      expect(balances.get(shielded)).toBe(0n);
+    });
+
+    it('should record each leg of an aggregated withdrawal', async () => {
+      const first = note(tagged(6), 400_000n);
+      const second = note(tagged(7), 600_000n);
+      const entries = await buildLedger(
+        [
+          event('ShieldedDeposit', { note_commitment: first }),
+          event('ShieldedDeposit', { note_commitment: second }),
+          event('ShieldedWithdrawAggregated', {
+            nullifiers: [nullifierOf(first), nullifierOf(second)],
+            output_commitments: [new Uint8Array(32), new Uint8Array(32)],
+          }),
+        ],
+        viewingKey,
+        fetchNote
+      );
+
+      const legs = entries.filter((entry) => entry.kind === 'withdrawal');
+      expect(legs).toHaveLength(2);
+      expect(new Set(legs.map((entry) => entry.id)).size).toBe(2);
+    });
+
+    it('should record rewards as income', async () => {
+      const stake = note(tagged(8), 1_000_000n);
+      const rewarded = note(tagged(9), 1_050_000n);
+      const entries = await buildLedger(
+        [
+          event('ShieldedDeposit', { note_commitment: stake }),
+          event('ShieldedRewardsClaimed', { stake_nullifier: nullifierOf(stake), new_note_commitment: rewarded }),
+        ],
+        viewingKey,
+        fetchNote
+      );
+
+      expect(entries[1].kind).toBe('reward');
+      expect(ledgerBalances(entries).get(LEDGER_ACCOUNTS.rewards)).toBe(-50_000n);
+    });
+
+    it('should be deterministic regardless of input order and duplicates', async () => {
+      const input = note(tagged(10), 800_000n);
+      const change = note(tagged(11), 200_000n);
+      const events = [
+        event('ShieldedDeposit', { note_commitment: input }),
+        event('ShieldedWithdraw', { nullifier: nullifierOf(input), output_commitment: change }),
+      ];
+
+      const forward = await buildLedger(events, viewingKey, fetchNote);
+      const shuffled = await buildLedger([events[1], events[0], events[1]], viewingKey, fetchNote);
+
+      expect(shuffled).toEqual(forward);
+      expect(forward.map((entry) => entry.id)).toEqual([
+        `${events[0].signature}/0/0/deposit`,
+        `${events[1].signature}/0/0/withdrawal`,
+      ]);
+    });
+
+    it('should produce balanced entries', async () => {
+      const input = note(tagged(12), 500_000n);
+      const entries = await buildLedger(
+        [
+          event('ShieldedDeposit', { note_commitment: input }),
+          event('ShieldedWithdraw', { nullifier: nullifierOf(input), output_commitment: new Uint8Array(32) }),
+        ],
+        viewingKey,
+        fetchNote
+      );
+
+      for (const entry of entries) {
+        expect(entry.postings.reduce((sum, posting) => sum + posting.amount, 0n)).toBe(0n);
+      }
+    });
+  });
+
+  describe('parseProgramEvents', () => {
+    it('should only decode events emitted by the Veil program', () => {
+      const other = Keypair.generate().publicKey.toBase58();
+      const veil = VEIL_PROGRAM_ID.toBase58();
+      const logs = [
+        `Program ${veil} invoke [1]`,
+        'Program data: ours-1',
+        `Program ${other} invoke [2]`,
+        'Program data: theirs',
+        `Program ${other} success`,
+        'Program data: ours-2',
+        `Program ${veil} success`,
+      ];
+
+      const events = parseProgramEvents(logs, VEIL_PROGRAM_ID, (data) => ({ name: data, data: {} }));
+      expect(events.map((e) => e.name)).toEqual(['ours-1', 'ours-2']);
+    });
+  });
+
+  describe('parseCpiEvents', () => {
+    const tag = [0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d];
+    const decode = (data: string) => ({ name: Buffer.from(data, 'base64').toString(), data: {} });
+
+    it('should decode tagged self-invocations of the Veil program', () => {
+      const other = Keypair.generate().publicKey;
+      const instructions = [
+        { programId: VEIL_PROGRAM_ID, data: Uint8Array.from([...tag, ...Buffer.from('ours-1')]) },
+        { programId: other, data: Uint8Array.from([...tag, ...Buffer.from('theirs')]) },
+        { programId: VEIL_PROGRAM_ID, data: Uint8Array.from(Buffer.from('not-an-event')) },
+        { programId: VEIL_PROGRAM_ID, data: Uint8Array.from([...tag, ...Buffer.from('ours-2')]) },
+      ];
+
+      const events = parseCpiEvents(instructions, VEIL_PROGRAM_ID, decode);
+      expect(events.map((e) => e.name)).toEqual(['ours-1', 'ours-2']);
+    });
+  });
+
+  describe('ledgerToCsv', () => {
+    it('should write one row per posting', async () => {
+      const commitment = note(tagged(13), 42n);
+      const entries = await buildLedger(
+        [event('ShieldedDeposit', { note_commitment: commitment })],
+        viewingKey,
+        fetchNote
+      );
+
+      const rows = ledgerToCsv(entries).trim().split('\n');
+      expect(rows[0]).toBe('id,kind,pool,signature,slot,block_time,account,amount_lamports');
+      expect(rows).toHaveLength(3);
+      expect(rows[1].endsWith(`${shielded},42`)).toBe(true);
+    });
+  });
+});
@@ -0,0 +1,119 @@
+/**
+ * Offline Signing Module Tests
+ *
+ * Tests for durable-nonce transactions signed by cold-storage signers
+ */
+
+import { describe, it, expect } from 'vitest';
+import { Keypair, SystemProgram, Transaction } from '@solana/web3.js';
+import {
+  buildDurableNonceTransaction,
+  buildStealthSignOfflineInstruction,
+  computeInstructionDiscriminator,
+  exportOfflineTransaction,
+  importOfflineTransaction,
+  isDurableNonceTransaction,
+  mergeOfflineSignatures,
+  signOffline,
+  DurableNonce,
+} from '../offline';
+
+function durableNonce(authority: Keypair): DurableNonce {
+  return {
+    nonceAccount: Keypair.generate().publicKey,
+    authority: authority.publicKey,
+    nonce: Keypair.generate().publicKey.toBase58(),
+  };
+}
+
+describe('Offline Signing Module', () => {
+  const feePayer = Keypair.generate();
+  const coldSigner = Keypair.generate();
+
+  function transfer() {
+    return SystemProgram.transfer({ fromPubkey: coldSigner.publicKey, toPubkey: feePayer.publicKey, lamports: 1 });
+  }
+
+  describe('buildDurableNonceTransaction', () => {
+    it('should advance the nonce first and use it as the blockhash', () => {
+      const nonce = durableNonce(feePayer);
+      const tx = buildDurableNonceTransaction(nonce, feePayer.publicKey, [transfer()]);
+
+      expect(tx.recentBlockhash).toBe(nonce.nonce);
+      expect(isDurableNonceTransaction(tx)).toBe(true);
+
+      const message = tx.compileMessage();
+      expect(message.instructions).toHaveLength(2);
+      expect(message.accountKeys[message.instructions[0].programIdIndex].equals(SystemProgram.programId)).toBe(true);
+    });
+
+    it('should not treat blockhash transactions as durable', () => {
+      const tx = new Transaction().add(transfer());
+      expect(isDurableNonceTransaction(tx)).toBe(false);
+    });
+  });
+
+  describe('signOffline', () => {
+    it('should collect signatures from separate offline round trips', () => {
+      const nonce = durableNonce(feePayer);
+      const unsigned = exportOfflineTransaction(
+        buildDurableNonceTransaction(nonce, feePayer.publicKey, [transfer()])
+      );
+
+      const cold = exportOfflineTransaction(signOffline(importOfflineTransaction(unsigned), [coldSigner]));
+      const hot = exportOfflineTransaction(signOffline(importOfflineTransaction(unsigned), [feePayer]));
+
+      const merged = mergeOfflineSignatures([cold, hot].map(importOfflineTransaction));
+      expect(merged.verifySignatures()).toBe(true);
+      expect(() => merged.serialize()).not.toThrow();
+    });
+
+    it('should refuse transactions that expire with their blockhash', () => {
+      const tx = new Transaction().add(transfer());
+      tx.recentBlockhash = Keypair.generate().publicKey.toBase58();
+      tx.feePayer = feePayer.publicKey;
+      expect(() => signOffline(tx, [coldSigner])).toThrow();
+    });
+
+    it('should reject copies signed over a different message', () => {
+      const a = signOffline(
+        buildDurableNonceTransaction(durableNonce(feePayer), feePayer.publicKey, [transfer()]),
+        [coldSigner]
+      );
+      const b = signOffline(
+        buildDurableNonceTransaction(durableNonce(feePayer), feePayer.publicKey, [transfer()]),
+        [feePayer]
+      );
+      expect(() => mergeOfflineSignatures([a, b])).toThrow();
+    });
+  });
+
+  describe('buildStealthSignOfflineInstruction', () => {
+    it('should use the Anchor discriminator', async () => {
+      expect(Array.from(await computeInstructionDiscriminator('stealth_sign_offline')))
+        .toEqual([160, 70, 223, 32, 18, 30, 48, 38]);
+    });
+
+    it('should encode the pinned action hash and expiry', async () => {
+      const ix = await buildStealthSignOfflineInstruction({
+        multisig: Keypair.generate().publicKey,
+        proposal: Keypair.generate().publicKey,
+        signer: coldSigner.publicKey,
+        circuitRegistry: Keypair.generate().publicKey,
+        signerProof: new Uint8Array(256).fill(1),
+        approvalCommitment: new Uint8Array(32).fill(2),
+        instructionHash: new Uint8Array(32).fill(3),
+        validUntil: 1_700_000_000n,
+        circuitId: new Uint8Array(32).fill(4),
+      });
+
+      expect(ix.data).toHaveLength(8 + 4 + 256 + 32 + 32 + 8 + 32);
+      expect(ix.data.readUInt32LE(8)).toBe(256);
+      expect(ix.data[300]).toBe(3);
+      expect(ix.data.readBigInt64LE(332)).toBe(1_700_000_000n);
+      expect(ix.data[340]).toBe(4);
+      expect(ix.keys[2]).toMatchObject({ pubkey: coldSigner.publicKey, isSigner: true, isWritable: true });
+      expect(ix.keys).toHaveLength(7);
+    });
+  });
+});
@@ -0,0 +1,149 @@
+/**
+ * Transaction Planner Module Tests
+ *
+ * Tests for planning multi-step flows and resuming them after interruptions
+ */
+
+import { describe, it, expect } from 'vitest';
+import { mkdtempSync } from 'fs';
+import { tmpdir } from 'os';
+import { join } from 'path';
+import {
+  createPlan,
+  FilePlanStore,
+  MemoryPlanStore,
+  PlanGoal,
+  PlanStep,
+  readySteps,
+  runPlan,
+  SignatureStatus,
+  StepExecutor,
+} from '../planner';
+
+const SOL = 1_000_000_000n;
+const UNLOCK = 1_700_100_000;
+
+const GOALS: PlanGoal[] = [
+  { kind: 'unstake', stakeCommitment: 'aa'.repeat(32), unlockAt: UNLOCK, claimRewards: true },
+  {
+    kind: 'unshield',
+    recipient: 'recipient',
+    amount: 3n * SOL,
+    notes: [
+      { commitment: '01'.repeat(32), value: 2n * SOL, pool: 'pool-a', createdAt: 1 },
+      { commitment: '02'.repeat(32), value: 2n * SOL, pool: 'pool-a', createdAt: 2, unlockAt: UNLOCK + 100 },
+    ],
+  },
+];
+
+/** Executor over a fake chain: sent transactions confirm on the next status check unless told otherwise */
+class FakeExecutor implements StepExecutor {
+  sent: PlanStep[] = [];
+  statuses = new Map<string, SignatureStatus>();
+  failNext = false;
+
+  async execute(step: PlanStep): Promise<string> {
+    if (this.failNext) throw new Error('rejected by wallet');
+    this.sent.push(step);
+    const signature = `sig-${this.sent.length}`;
+    this.statuses.set(signature, 'confirmed');
+    return signature;
+  }
+
+  async status(signature: string): Promise<SignatureStatus> {
+    return this.statuses.get(signature) ?? 'pending';
+  }
+}
+
+describe('Transaction Planner Module', () => {
+  it('should plan steps in order with their preconditions', async () => {
+    const plan = await createPlan(GOALS);
+
+    expect(plan.steps.map((step) => step.action.type)).toEqual([
+      'claim-rewards',
+      'unstake',
+      'shield-withdraw',
+      'shield-withdraw',
+    ]);
+    expect(plan.steps[1].after).toEqual([plan.steps[0].id]);
+    expect(plan.steps[1].notBefore).toBe(UNLOCK);
+    expect(plan.steps[2].after).toEqual([plan.steps[1].id]);
+    expect(plan.steps[3].notBefore).toBe(UNLOCK + 100);
+
+    const amounts = plan.steps.slice(2).map((step) => (step.action.type === 'shield-withdraw' ? step.action.amount : 0n));
+    expect(amounts.reduce((sum, amount) => sum + amount, 0n)).toBe(3n * SOL);
+  });
+
+  it('should give the same goals the same plan', async () => {
+    const a = await createPlan(GOALS);
+    const b = await createPlan(GOALS);
+    const c = await createPlan([GOALS[0]]);
+
+    expect(b).toEqual(a);
+    expect(c.id).not.toBe(a.id);
+  });
+
+  it('should wait for cooldowns and resume', async () => {
+    const plan = await createPlan(GOALS);
+    const store = new MemoryPlanStore();
+    const executor = new FakeExecutor();
+    let now = UNLOCK - 10;
+
+    const first = await runPlan(plan, executor, store, () => now);
+    expect(first).toEqual({ done: false, waitUntil: UNLOCK });
+    expect(executor.sent.map((step) => step.action.type)).toEqual(['claim-rewards']);
+
+    now = UNLOCK;
+    const second = await runPlan((await store.load(plan.id))!, executor, store, () => now);
+    expect(second.waitUntil).toBe(UNLOCK + 100);
+    expect(executor.sent).toHaveLength(3);
+
+    now = UNLOCK + 100;
+    expect(await runPlan((await store.load(plan.id))!, executor, store, () => now)).toEqual({ done: true });
+    expect(executor.sent).toHaveLength(4);
+  });
+
+  it('should not resend a step that was sent before an interruption', async () => {
+    const plan = await createPlan([GOALS[0]]);
+    const store = new MemoryPlanStore();
+    const executor = new FakeExecutor();
+    executor.execute = async (step) => {
+      executor.sent.push(step);
+      return 'sig-in-flight';
+    };
+
+    expect(await runPlan(plan, executor, store, () => UNLOCK)).toEqual({ done: false });
+    const saved = (await store.load(plan.id))!;
+    expect(saved.steps[0].status).toBe('submitted');
+
+    executor.statuses.set('sig-in-flight', 'confirmed');
+    const resumed = new FakeExecutor();
+    resumed.statuses = executor.statuses;
+    await runPlan(saved, resumed, store, () => UNLOCK);
+
+    expect(resumed.sent.map((step) => step.action.type)).toEqual(['unstake']);
+  });
+
+  it('should stop on a failed step', async () => {
+    const plan = await createPlan([GOALS[0]]);
+    const executor = new FakeExecutor();
+    executor.failNext = true;
+
+    const result = await runPlan(plan, executor, new MemoryPlanStore(), () => UNLOCK);
+
+    expect(result.done).toBe(false);
+    expect(result.failed?.error).toBe('rejected by wallet');
+    expect(readySteps(plan, UNLOCK).ready).toHaveLength(0);
+  });
+
+  it('should persist plans to disk with exact amounts', async () => {
+    const store = new FilePlanStore(join(mkdtempSync(join(tmpdir(), 'veil-plans-')), 'plans'));
+    const plan = await createPlan(GOALS);
+
+    await store.save(plan);
+    expect(await store.load(plan.id)).toEqual(plan);
+
+    await store.remove(plan.id);
+    expect(await store.load(plan.id)).toBeNull();
+  });
+});
@@ -0,0 +1,174 @@
+/**
+ * Background Prover Module Tests
+ *
+ * Tests for queueing, progress and cancellation of proof jobs
+ */
+
+import { describe, it, expect } from 'vitest';
+import {
+  BackgroundProver,
+  InlineBackend,
+  ProofCancelledError,
+  ProofOutput,
+  ProofProgress,
+  ProverRequest,
+  ProverResponse,
+  ProverWorker,
+  serveProofRequests,
+  WorkerPoolBackend,
+} from '../prover';
+
+const OUTPUT: ProofOutput = {
+  proof: { pi_a: ['1', '2', '1'], pi_b: [['3', '4'], ['5', '6'], ['1', '0']], pi_c: ['7', '8', '1'], protocol: 'groth16', curve: 'bn128' },
+  publicSignals: ['42'],
+};
+
+function tick(): Promise<void> {
+  return new Promise((resolve) => setTimeout(resolve, 0));
+}
+
+/** A worker answering in-process through `serveProofRequests`, proving only when released */
+class FakeWorker implements ProverWorker {
+  onmessage: ((event: { data: ProverResponse }) => void) | null = null;
+  terminated = false;
+  release: () => void = () => {};
+  private scope = {
+    onmessage: null as ((event: { data: ProverRequest }) => void) | null,
+    postMessage: (message: ProverResponse) => this.onmessage?.({ data: message }),
+  };
+
+  constructor(failWith?: string) {
+    serveProofRequests(this.scope, async (_job, report) => {
+      if (failWith) throw new Error(failWith);
+      report({ stage: 'witness', fraction: 0.2 });
+      await new Promise<void>((resolve) => (this.release = resolve));
+      report({ stage: 'proving', fraction: 0.9 });
+      return OUTPUT;
+    });
+  }
+
+  postMessage(message: ProverRequest): void {
+    this.scope.onmessage?.({ data: message });
+  }
+
+  terminate(): void {
+    this.terminated = true;
+  }
+}
+
+describe('Background Prover Module', () => {
+  it('should report progress through to the proof', async () => {
+    const stages: ProofProgress[] = [];
+    const prover = new BackgroundProver(
+      new InlineBackend(async (_job, report) => {
+        report({ stage: 'witness', fraction: 0.3 });
+        report({ stage: 'proving', fraction: 0.8 });
+        return OUTPUT;
+      })
+    );
+
+    const output = await prover.prove({ circuit: 'withdrawal', inputs: {} }, { onProgress: (p) => stages.push(p) }).result;
+
+    expect(output.publicSignals).toEqual(['42']);
+    expect(stages.map((p) => p.stage)).toEqual(['queued', 'witness', 'proving', 'done']);
+  });
+
+  it('should run jobs one at a time on an inline backend', async () => {
+    let active = 0;
+    let peak = 0;
+    const prover = new BackgroundProver(
+      new InlineBackend(async () => {
+        peak = Math.max(peak, ++active);
+        await tick();
+        active--;
+        return OUTPUT;
+      })
+    );
+
+    const tasks = [1, 2, 3].map(() => prover.prove({ circuit: 'withdrawal', inputs: {} }));
+    expect(prover.pending()).toBe(3);
+    await Promise.all(tasks.map((task) => task.result));
+
+    expect(peak).toBe(1);
+    expect(prover.pending()).toBe(0);
+  });
+
+  it('should cancel a queued job without running it', async () => {
+    let runs = 0;
+    const prover = new BackgroundProver(
+      new InlineBackend(async () => {
+        runs++;
+        await tick();
+        return OUTPUT;
+      })
+    );
+
+    const first = prover.prove({ circuit: 'withdrawal', inputs: {} });
+    const second = prover.prove({ circuit: 'withdrawal', inputs: {} });
+    second.cancel();
+
+    await expect(second.result).rejects.toBeInstanceOf(ProofCancelledError);
+    await first.result;
+    expect(runs).toBe(1);
+  });
+
+  it('should honour an abort signal', async () => {
+    const prover = new BackgroundProver(new InlineBackend(async () => OUTPUT));
+    const controller = new AbortController();
+    controller.abort();
+
+    await expect(
+      prover.prove({ circuit: 'withdrawal', inputs: {} }, { signal: controller.signal }).result
+    ).rejects.toBeInstanceOf(ProofCancelledError);
+  });
+
+  it('should prove on workers and reuse them', async () => {
+    const workers: FakeWorker[] = [];
+    const prover = new BackgroundProver(new WorkerPoolBackend(() => {
+      const worker = new FakeWorker();
+      workers.push(worker);
+      return worker;
+    }, 2));
+
+    const stages: string[] = [];
+    const task = prover.prove({ circuit: 'withdrawal', inputs: {} }, { onProgress: (p) => stages.push(p.stage) });
+    await tick();
+    workers[0].release();
+    await task.result;
+
+    const again = prover.prove({ circuit: 'withdrawal', inputs: {} });
+    await tick();
+    workers[0].release();
+    await again.result;
+
+    expect(workers).toHaveLength(1);
+    expect(stages).toEqual(['queued', 'witness', 'proving', 'done']);
+  });
+
+  it('should terminate the worker of a cancelled job', async () => {
+    const workers: FakeWorker[] = [];
+    const prover = new BackgroundProver(new WorkerPoolBackend(() => {
+      const worker = new FakeWorker();
+      workers.push(worker);
+      return worker;
+    }, 1));
+
+    const task = prover.prove({ circuit: 'withdrawal', inputs: {} });
+    await tick();
+    task.cancel();
+
+    await expect(task.result).rejects.toBeInstanceOf(ProofCancelledError);
+    expect(workers[0].terminated).toBe(true);
+
+    const next = prover.prove({ circuit: 'withdrawal', inputs: {} });
+    await tick();
+    workers[1].release();
+    await expect(next.result).resolves.toEqual(OUTPUT);
+  });
+
+  it('should pass worker errors on', async () => {
+    const prover = new BackgroundProver(new WorkerPoolBackend(() => new FakeWorker('witness generation failed')));
+
+    await expect(prover.prove({ circuit: 'withdrawal', inputs: {} }).result).rejects.toThrow('witness generation failed');
+  });
+});
@@ -0,0 +1,32 @@
+/**
+ * Recovery Module Tests
+ *
+ * Tests for anchoring and verifying encrypted wallet backups
+ */
+
+import { describe, it, expect } from 'vitest';
+import { PublicKey } from '@solana/web3.js';
+import { computeBackupHashes, getWalletBackupPDA, verifyRestoredBackup } from '../recovery';
+import { randomBytes } from '../crypto';
+
+describe('Recovery Module', () => {
+  describe('backup anchoring', () => {
+    it('should verify only the anchored backup version', async () => {
+      const v1 = randomBytes(256);
+      const v2 = randomBytes(256);
+      const { backupHash } = await computeBackupHashes(v1, 'ipfs://backup-v1');
+
+      expect(await verifyRestoredBackup(v1, backupHash)).toBe(true);
+      expect(await verifyRestoredBackup(v2, backupHash)).toBe(false);
+    });
+
+    it('should derive one record per backup version', () => {
+      const wallet = new PublicKey(randomBytes(32));
+      const [first] = getWalletBackupPDA(wallet, 1n);
+      const [second] = getWalletBackupPDA(wallet, 2n);
+
+      expect(first.equals(second)).toBe(false);
+      expect(getWalletBackupPDA(wallet, 1n)[0].equals(first)).toBe(true);
+    });
+  });
+});
@@ -0,0 +1,120 @@
+/**
+ * Remote Proving Module Tests
+ *
+ * Tests for attestation checks and sealed requests against the reference server
+ */
+
+import { describe, it, expect, vi, afterEach } from 'vitest';
+import nacl from 'tweetnacl';
+import { bytesToHex } from '../crypto';
+import { ProofJob, ProofOutput } from '../prover';
+import {
+  openProofResponse,
+  RemoteProverClient,
+  RemoteProverError,
+  RemoteProverServer,
+  sealProofRequest,
+  SignedAttestationVerifier,
+} from '../remote';
+
+const MEASUREMENT = 'ab'.repeat(32);
+const JOB: ProofJob = { circuit: 'withdrawal', inputs: { secret: 12345n, pathIndices: [0, 1] } };
+
+function setup(now = { time: 1_700_000_000 }) {
+  const signer = nacl.sign.keyPair();
+  const seen: ProofJob[] = [];
+  const server = new RemoteProverServer({
+    signer,
+    measurement: MEASUREMENT,
+    ttl: 60,
+    now: () => now.time,
+    proveFn: async (job) => {
+      seen.push(job);
+      const output: ProofOutput = {
+        proof: { pi_a: ['1'], pi_b: [['2']], pi_c: ['3'], protocol: 'groth16', curve: 'bn128' },
+        publicSignals: [String(job.inputs.secret)],
+      };
+      return output;
+    },
+  });
+  const verifier = new SignedAttestationVerifier([bytesToHex(signer.publicKey)], [MEASUREMENT]);
+  return { server, verifier, seen, now };
+}
+
+describe('Remote Proving Module', () => {
+  afterEach(() => {
+    vi.unstubAllGlobals();
+  });
+
+  it('should prove a sealed job and seal the output back', async () => {
+    const { server, seen } = setup();
+    const attestation = await server.attestation();
+
+    const { request, ephemeralSecret } = sealProofRequest(JOB, attestation);
+    expect(request.ciphertext).not.toContain(bytesToHex(new TextEncoder().encode('12345')));
+
+    const output = openProofResponse(await server.handle(request), attestation, ephemeralSecret);
+    expect(output.publicSignals).toEqual(['12345']);
+    expect(seen[0].inputs.secret).toBe('12345');
+  });
+
+  it('should accept only trusted, current attestations', async () => {
+    const { server, verifier, now } = setup();
+    const attestation = await server.attestation();
+
+    expect(await verifier.verify(attestation, now.time)).toBe(true);
+    expect(await verifier.verify(attestation, now.time + 60)).toBe(false);
+    expect(await verifier.verify({ ...attestation, measurement: 'cd'.repeat(32) }, now.time)).toBe(false);
+    expect(await verifier.verify({ ...attestation, enclaveKey: 'ee'.repeat(32) }, now.time)).toBe(false);
+
+    const other = setup();
+    expect(await other.verifier.verify(attestation, now.time)).toBe(false);
+  });
+
+  it('should refuse requests sealed to a rotated key', async () => {
+    const { server, now } = setup();
+    const stale = await server.attestation();
+    now.time += 60;
+    const fresh = await server.attestation();
+
+    expect(fresh.enclaveKey).not.toBe(stale.enclaveKey);
+    await expect(server.handle(sealProofRequest(JOB, stale).request)).rejects.toMatchObject({ status: 409 });
+  });
+
+  it('should refuse tampered requests', async () => {
+    const { server } = setup();
+    const { request } = sealProofRequest(JOB, await server.attestation());
+    const tampered = { ...request, ciphertext: '00' + request.ciphertext.slice(2) };
+
+    await expect(server.handle(tampered)).rejects.toBeInstanceOf(RemoteProverError);
+  });
+
+  it('should prove through the HTTP client', async () => {
+    const { server, verifier, now } = setup();
+    vi.stubGlobal('fetch', async (url: string, init?: { body?: string }) => {
+      const body = url.endsWith('/attestation')
+        ? await server.attestation()
+        : await server.handle(JSON.parse(init!.body!));
+      return { ok: true, status: 200, json: async () => body };
+    });
+
+    const client = new RemoteProverClient('https://prover.test', verifier, () => now.time);
+    const output = await client.prove(JOB);
+
+    expect(output.publicSignals).toEqual(['12345']);
+  });
+
+  it('should not send a witness to an untrusted prover', async () => {
+    const { server, now } = setup();
+    const posted: string[] = [];
+    vi.stubGlobal('fetch', async (url: string) => {
+      posted.push(url);
+      return { ok: true, status: 200, json: async () => server.attestation() };
+    });
+
+    const client = new RemoteProverClient('https://prover.test', setup().verifier, () => now.time);
+
+    await expect(client.prove(JOB)).rejects.toThrow('not trusted');
+    expect(posted).toEqual(['https://prover.test/attestation']);
+  });
+});
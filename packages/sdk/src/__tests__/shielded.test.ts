@@ -21,7 +21,21 @@ import {
   NULLIFIER_SEED,
   MERKLE_TREE_DEPTH,
   MAX_SHIELDED_NOTES,
-  isBalancePublic
+  NULLIFIER_FILTER_BYTES,
+  isBalancePublic,
+  checkNullifierFilter,
+  computePoolBinding,
+  computeHoldingTag,
+  computeHoldingClaimTag,
+  computeClaimKeyHash,
+  getHoldingPDA,
+  getDepositCallerPDA,
+  getCpiDepositorPDA,
+  getNullifierFilterPDA,
+  getProtocolStatsPDA,
+  PROTOCOL_STATS_SEED,
+  nullifierFilterBits,
+  nullifierFilterMayContain
 } from '../shielded';
 import { Connection, PublicKey, Keypair, SystemProgram, Transaction } from '@solana/web3.js';
 import { bytesToHex, createPedersenCommitment, verifyPedersenCommitment, randomBytes, poseidonHash, bytesToBigInt } from '../crypto';
@@ -144,6 +158,19 @@ describe('Shielded Balance Module', () => {
       });
     });
 
+    describe('buildDecoyDeposit', () => {
+      it('should pad the real note with distinct decoys', async () => {
+        const { noteCommitments, encryptedNotes, realIndex } = await client.buildDecoyDeposit(wallet.publicKey, 1.0, 3);
+
+        expect(noteCommitments).toHaveLength(4);
+        expect(encryptedNotes).toHaveLength(4);
+        expect(realIndex).toBeGreaterThanOrEqual(0);
+        expect(realIndex).toBeLessThan(4);
+        expect(new Set(noteCommitments.map(bytesToHex)).size).toBe(4);
+        encryptedNotes.forEach((note) => expect(note).toHaveLength(64));
+      });
+    });
+
     describe('withdraw', () => {
       it('should have withdraw method', () => {
         expect(client.withdraw).toBeDefined();
@@ -484,5 +511,109 @@ describe('Shielded Balance Module', () => {
       expect(result).toBeNull();
     });
   });
+
+  describe('getProtocolStatsPDA', () => {
+    it('should derive the single stats account from its seed', () => {
+      const [pda, bump] = getProtocolStatsPDA();
+      const [expected] = PublicKey.findProgramAddressSync([Buffer.from(PROTOCOL_STATS_SEED)], VEIL_PROGRAM_ID);
+      expect(pda.equals(expected)).toBe(true);
+      expect(bump).toBeLessThanOrEqual(255);
+    });
+  });
+
+  describe('Holding accounts', () => {
+    const pool = new PublicKey(new Uint8Array(32).fill(7));
+
+    it('should match the on-chain holding tags', async () => {
+      const tag = await computeHoldingTag(pool, new Uint8Array(32).fill(8), 1000n);
+      expect(bytesToHex(tag)).toBe('bb0f50e3bf7349394d85f2faef49f978e6f1771ae7af50a4a80b251cbfe9aebe');
+
+      const claimTag = await computeHoldingClaimTag(pool, new PublicKey(new Uint8Array(32).fill(9)));
+      expect(bytesToHex(claimTag)).toBe('186ac30cde9ce148afe158e39360cb906b3650ad3a7026274a35ab60645eb1fe');
+    });
+
+    it('should derive one holding account per claim key', async () => {
+      const first = await computeClaimKeyHash(randomBytes(32));
+      const second = await computeClaimKeyHash(randomBytes(32));
+      expect(getHoldingPDA(pool, first)[0].equals(getHoldingPDA(pool, second)[0])).toBe(false);
+    });
+  });
+
+  describe('CPI deposits', () => {
+    const pool = new PublicKey(new Uint8Array(32).fill(7));
+    const caller = new PublicKey(new Uint8Array(32).fill(3));
+
+    it('should derive the depositor under the caller program', () => {
+      const [depositor] = getCpiDepositorPDA(caller);
+      const [expected] = PublicKey.findProgramAddressSync([Buffer.from('veil_depositor')], caller);
+      expect(depositor.equals(expected)).toBe(true);
+      expect(depositor.equals(getCpiDepositorPDA(VEIL_PROGRAM_ID)[0])).toBe(false);
+    });
+
+    it('should derive one allowlist entry per caller program', () => {
+      const other = new PublicKey(new Uint8Array(32).fill(4));
+      expect(getDepositCallerPDA(pool, caller)[0].equals(getDepositCallerPDA(pool, other)[0])).toBe(false);
+    });
+  });
+
+  describe('computePoolBinding', () => {
+    it('should match the on-chain pool binding', async () => {
+      const pool = new PublicKey(new Uint8Array(32).fill(7));
+      expect(bytesToHex(await computePoolBinding(pool))).toBe(
+        '8839235e862df1dd8694248ec853354d5d27944d15ad43746dccd44c6fc7e510'
+      );
+    });
+
+    it('should differ between pools', async () => {
+      const a = await computePoolBinding(Keypair.generate().publicKey);
+      const b = await computePoolBinding(Keypair.generate().publicKey);
+      expect(bytesToHex(a)).not.toBe(bytesToHex(b));
+    });
+  });
+
+  // ==========================================================================
+  // NULLIFIER FILTER TESTS
+  // ==========================================================================
+
+  describe('Nullifier Filter', () => {
+    const pool = Keypair.generate().publicKey;
+
+    /** NullifierFilter account data with `nullifiers` inserted */
+    const filterData = async (nullifiers: Uint8Array[], trackedFrom = 0) => {
+      const data = new Uint8Array(8 + 32 + NULLIFIER_FILTER_BYTES + 4 + 4 + 8);
+      for (const nullifier of nullifiers) {
+        for (const bit of await nullifierFilterBits(nullifier)) {
+          data[40 + (bit >> 3)] |= 1 << (bit & 7);
+        }
+      }
+      new DataView(data.buffer).setUint32(40 + NULLIFIER_FILTER_BYTES, trackedFrom, true);
+      return data;
+    };
+
+    it('should find inserted nullifiers and rule out others', async () => {
+      const spent = randomBytes(32);
+      const data = await filterData([spent]);
+
+      expect(await nullifierFilterMayContain(data, spent)).toBe(true);
+      expect(await nullifierFilterMayContain(data, randomBytes(32))).toBe(false);
+    });
+
+    it('should not rule out nullifiers spent before the filter existed', async () => {
+      const data = await filterData([], 3);
+      expect(await nullifierFilterMayContain(data, randomBytes(32))).toBe(true);
+    });
+
+    it('should read the filter PDA and report pools without one', async () => {
+      const spent = randomBytes(32);
+      const getAccountInfo = vi.fn()
+        .mockResolvedValueOnce({ data: Buffer.from(await filterData([spent])) })
+        .mockResolvedValueOnce(null);
+      const connection = { getAccountInfo } as unknown as Connection;
+
+      expect(await checkNullifierFilter(connection, pool, spent)).toBe(true);
+      expect(getAccountInfo).toHaveBeenCalledWith(getNullifierFilterPDA(pool)[0]);
+      expect(await checkNullifierFilter(connection, pool, spent)).toBeNull();
+    });
+  });
 });
 
@@ -0,0 +1,208 @@
+/**
+ * Shared Test Vector Tests
+ *
+ * Checks the SDK against test-vectors/vectors.json, the same file the
+ * on-chain unit tests load, so hashes computed here cannot drift from
+ * the program's.
+ */
+
+import { readFileSync } from 'fs';
+import { describe, it, expect } from 'vitest';
+import { PublicKey } from '@solana/web3.js';
+import { sha256, hashDomain, hexToBytes, bytesToHex } from '../crypto';
+import {
+  computePoolBinding,
+  computeClaimKeyHash,
+  computeHoldingTag,
+  computeHoldingClaimTag,
+  computeCircuitId,
+  computeSplitTag,
+  computeDepositCancelTag,
+  computeRelayerSolvencyTag,
+  computeRewardCurveHash,
+  computeEarlyExitTag,
+  nullifierFilterBits
+} from '../shielded';
+import {
+  computeTreasuryBandTag,
+  computeSmallSpendTag,
+  computeHeartbeatTag,
+  computeSignerRotationTag,
+  computeStealthApprovalTag
+} from '../multisig';
+import { computeTallyCommitment, computeConvictionTag, computeQuadraticVoteTag } from '../voting';
+
+interface Vector {
+  name: string;
+  inputs: Record<string, any>;
+  expected: any;
+}
+
+const vectors: Record<string, Vector[]> = JSON.parse(
+  readFileSync(new URL('../../../../test-vectors/vectors.json', import.meta.url), 'utf8')
+);
+
+function vector(group: string, name: string): Vector {
+  const found = vectors[group].find(v => v.name === name);
+  if (!found) throw new Error(`no vector named ${name}`);
+  return found;
+}
+
+const key = (hex: string) => new PublicKey(hexToBytes(hex));
+
+describe('Shared test vectors', () => {
+  it('should match the hash vectors', async () => {
+    const abc = vector('hash', 'sha256_abc');
+    expect(bytesToHex(await sha256(hexToBytes(abc.inputs.data)))).toBe(abc.expected);
+
+    for (const name of ['hash_domain_empty', 'hash_domain_parts']) {
+      const { inputs, expected } = vector('hash', name);
+      const digest = await hashDomain(inputs.tag, ...inputs.parts.map(hexToBytes));
+      expect(bytesToHex(digest)).toBe(expected);
+    }
+  });
+
+  it('should match the commitment vectors', async () => {
+    const binding = vector('commitments', 'pool_binding');
+    expect(bytesToHex(await computePoolBinding(key(binding.inputs.pool)))).toBe(binding.expected);
+
+    const claimKey = vector('commitments', 'claim_key_hash');
+    expect(bytesToHex(await computeClaimKeyHash(hexToBytes(claimKey.inputs.claim_secret)))).toBe(claimKey.expected);
+
+    const holding = vector('commitments', 'holding_tag');
+    const tag = await computeHoldingTag(
+      key(holding.inputs.pool),
+      hexToBytes(holding.inputs.claim_key_hash),
+      BigInt(holding.inputs.lamports)
+    );
+    expect(bytesToHex(tag)).toBe(holding.expected);
+
+    const claim = vector('commitments', 'holding_claim_tag');
+    const claimTag = await computeHoldingClaimTag(key(claim.inputs.holding), key(claim.inputs.recipient));
+    expect(bytesToHex(claimTag)).toBe(claim.expected);
+
+    const band = vector('commitments', 'treasury_band_tag');
+    const bandTag = await computeTreasuryBandTag(
+      key(band.inputs.multisig),
+      key(band.inputs.pool),
+      BigInt(band.inputs.min_balance),
+      BigInt(band.inputs.max_balance)
+    );
+    expect(bytesToHex(bandTag)).toBe(band.expected);
+
+    const spend = vector('commitments', 'small_spend_tag');
+    const spendTag = await computeSmallSpendTag(
+      key(spend.inputs.proposal),
+      key(spend.inputs.mint),
+      BigInt(spend.inputs.amount)
+    );
+    expect(bytesToHex(spendTag)).toBe(spend.expected);
+
+    const heartbeat = vector('commitments', 'heartbeat_tag');
+    const heartbeatTag = await computeHeartbeatTag(
+      key(heartbeat.inputs.multisig),
+      BigInt(heartbeat.inputs.epoch),
+      heartbeat.inputs.signers.map(hexToBytes)
+    );
+    expect(bytesToHex(heartbeatTag)).toBe(heartbeat.expected);
+
+    const rotation = vector('commitments', 'signer_rotation_tag');
+    const rotationTag = await computeSignerRotationTag(
+      key(rotation.inputs.multisig),
+      hexToBytes(rotation.inputs.new_commitment)
+    );
+    expect(bytesToHex(rotationTag)).toBe(rotation.expected);
+
+    const approval = vector('commitments', 'stealth_approval_tag');
+    const approvalTag = await computeStealthApprovalTag(
+      key(approval.inputs.proposal),
+      approval.inputs.signers.map(hexToBytes)
+    );
+    expect(bytesToHex(approvalTag)).toBe(approval.expected);
+
+    const tally = vector('commitments', 'tally_commitment');
+    const tallyCommitment = await computeTallyCommitment(
+      key(tally.inputs.proposal),
+      {
+        yesCount: BigInt(tally.inputs.yes_count),
+        noCount: BigInt(tally.inputs.no_count),
+        yesWeight: BigInt(tally.inputs.yes_weight),
+        noWeight: BigInt(tally.inputs.no_weight)
+      },
+      tally.inputs.quorum_failed,
+      BigInt(tally.inputs.salt)
+    );
+    expect(bytesToHex(tallyCommitment)).toBe(tally.expected);
+
+    const conviction = vector('commitments', 'conviction_tag');
+    const convictionTag = await computeConvictionTag(
+      key(conviction.inputs.stake),
+      BigInt(conviction.inputs.conviction),
+      BigInt(conviction.inputs.elapsed),
+      BigInt(conviction.inputs.ramp_period),
+      BigInt(conviction.inputs.weight_unit)
+    );
+    expect(bytesToHex(convictionTag)).toBe(conviction.expected);
+
+    const quadratic = vector('commitments', 'quadratic_vote_tag');
+    const quadraticTag = await computeQuadraticVoteTag(
+      key(quadratic.inputs.proposal),
+      quadratic.inputs.votes.map(BigInt)
+    );
+    expect(bytesToHex(quadraticTag)).toBe(quadratic.expected);
+
+    const circuit = vector('commitments', 'circuit_id');
+    expect(bytesToHex(await computeCircuitId(circuit.inputs.circuit))).toBe(circuit.expected);
+
+    const split = vector('commitments', 'split_tag');
+    const splitTag = await computeSplitTag(
+      key(split.inputs.pool),
+      split.inputs.recipients.map((recipient: string, i: number) => ({
+        recipient: key(recipient),
+        lamports: BigInt(split.inputs.lamports[i])
+      }))
+    );
+    expect(bytesToHex(splitTag)).toBe(split.expected);
+
+    const cancel = vector('commitments', 'deposit_cancel_tag');
+    const cancelTag = await computeDepositCancelTag(
+      key(cancel.inputs.pool),
+      cancel.inputs.note_index,
+      key(cancel.inputs.recipient),
+      BigInt(cancel.inputs.lamports)
+    );
+    expect(bytesToHex(cancelTag)).toBe(cancel.expected);
+
+    const solvency = vector('commitments', 'relayer_solvency_tag');
+    const solvencyTag = await computeRelayerSolvencyTag(
+      key(solvency.inputs.relayer_record),
+      BigInt(solvency.inputs.stake_lamports),
+      BigInt(solvency.inputs.period_index)
+    );
+    expect(bytesToHex(solvencyTag)).toBe(solvency.expected);
+
+    const curve = vector('commitments', 'reward_curve_hash');
+    const curveHash = await computeRewardCurveHash(
+      curve.inputs.base_rate_bps,
+      curve.inputs.starts_at.map((startsAt: number, i: number) => ({
+        startsAt: BigInt(startsAt),
+        rateBps: curve.inputs.rate_bps[i]
+      }))
+    );
+    expect(bytesToHex(curveHash)).toBe(curve.expected);
+
+    const exit = vector('commitments', 'early_exit_tag');
+    const exitTag = await computeEarlyExitTag(
+      key(exit.inputs.pool),
+      key(exit.inputs.recipient),
+      BigInt(exit.inputs.gross_lamports),
+      exit.inputs.penalty_bps
+    );
+    expect(bytesToHex(exitTag)).toBe(exit.expected);
+  });
+
+  it('should match the nullifier vectors', async () => {
+    const { inputs, expected } = vector('nullifiers', 'nullifier_filter_bits');
+    expect(await nullifierFilterBits(hexToBytes(inputs.nullifier))).toEqual(expected);
+  });
+});
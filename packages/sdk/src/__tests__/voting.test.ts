@@ -9,9 +9,25 @@ import { Connection, PublicKey } from '@solana/web3.js';
 import {
   PrivateVotingClient,
   VoteChoice,
-  verifyVoteCommitment
+  verifyVoteCommitment,
+  getVoterWeightRecordPDA,
+  computeLockupWeight,
+  MAX_GOVERNANCE_LOCKUP_SECONDS,
+  VOTING_PROGRAM_ID,
+  VOTER_WEIGHT_SEED,
+  getRealmRegistrarPDA,
+  getRealmVoterWeightRecordPDA,
+  getRealmMaxVoterWeightRecordPDA,
+  REALM_VOTER_WEIGHT_SEED,
+  createRevealToken,
+  computeRevealTokenHash,
+  getProposalTemplatePDA,
+  computeEligibilityLeaf,
+  PROPOSAL_TEMPLATE_SEED,
+  computeConviction,
+  computeQuadraticCost
 } from '../voting';
-import { bytesToHex, hexToBytes } from '../crypto';
+import { bytesToHex, hexToBytes, hashDomain } from '../crypto';
 
 // Mock connection
 const mockConnection = {
@@ -121,5 +137,92 @@ describe('Private Voting Module', () => {
       expect(VoteChoice.ABSTAIN).toBe(2);
     });
   });
-});
 
+  describe('Governance token weight', () => {
+    it('should derive one voter weight record per mint and owner', () => {
+      const mint = new PublicKey(new Uint8Array(32).fill(1));
+      const owner = new PublicKey(new Uint8Array(32).fill(2));
+      const [pda] = getVoterWeightRecordPDA(mint, owner);
+      const [expected] = PublicKey.findProgramAddressSync(
+        [Buffer.from(VOTER_WEIGHT_SEED), mint.toBuffer(), owner.toBuffer()],
+        VOTING_PROGRAM_ID
+      );
+      expect(pda.equals(expected)).toBe(true);
+      expect(pda.equals(getVoterWeightRecordPDA(owner, mint)[0])).toBe(false);
+    });
+
+    it('should weigh lockups up to 4x, decaying linearly', () => {
+      expect(computeLockupWeight(1000n, 0)).toBe(1000n);
+      expect(computeLockupWeight(1000n, -5)).toBe(1000n);
+      expect(computeLockupWeight(1000n, MAX_GOVERNANCE_LOCKUP_SECONDS)).toBe(4000n);
+      expect(computeLockupWeight(1000n, MAX_GOVERNANCE_LOCKUP_SECONDS * 2)).toBe(4000n);
+      expect(computeLockupWeight(1000n, MAX_GOVERNANCE_LOCKUP_SECONDS / 2)).toBe(2500n);
+    });
+  });
+
+  describe('Realms addin', () => {
+    it('should derive addin records per registrar', () => {
+      const realm = new PublicKey(new Uint8Array(32).fill(3));
+      const mint = new PublicKey(new Uint8Array(32).fill(4));
+      const owner = new PublicKey(new Uint8Array(32).fill(5));
+      const [registrar] = getRealmRegistrarPDA(realm, mint);
+      const [record] = getRealmVoterWeightRecordPDA(registrar, owner);
+      const [expected] = PublicKey.findProgramAddressSync(
+        [Buffer.from(REALM_VOTER_WEIGHT_SEED), registrar.toBuffer(), owner.toBuffer()],
+        VOTING_PROGRAM_ID
+      );
+      expect(record.equals(expected)).toBe(true);
+      expect(getRealmMaxVoterWeightRecordPDA(registrar)[0].equals(record)).toBe(false);
+      expect(registrar.equals(getRealmRegistrarPDA(mint, realm)[0])).toBe(false);
+    });
+  });
+
+  describe('Delegated reveal', () => {
+    it('should hash reveal tokens under their own domain', async () => {
+      const { token, tokenHash } = await createRevealToken();
+      expect(token.length).toBe(32);
+      expect(bytesToHex(tokenHash)).toBe(bytesToHex(await hashDomain('veil:reveal_token:v2', token)));
+      expect(bytesToHex(await computeRevealTokenHash(token))).toBe(bytesToHex(tokenHash));
+      expect(bytesToHex(tokenHash)).not.toBe(bytesToHex(await computeRevealTokenHash(new Uint8Array(32))));
+    });
+  });
+
+  describe('Proposal templates', () => {
+    it('should derive templates per creator and id', () => {
+      const creator = new PublicKey(new Uint8Array(32).fill(6));
+      const templateId = new Uint8Array(32).fill(7);
+      const [template] = getProposalTemplatePDA(creator, templateId);
+      const [expected] = PublicKey.findProgramAddressSync(
+        [Buffer.from(PROPOSAL_TEMPLATE_SEED), creator.toBuffer(), Buffer.from(templateId)],
+        VOTING_PROGRAM_ID
+      );
+      expect(template.equals(expected)).toBe(true);
+      expect(getProposalTemplatePDA(creator, new Uint8Array(32))[0].equals(template)).toBe(false);
+    });
+
+    it('should hash eligibility leaves under their own domain', async () => {
+      const voter = new PublicKey(new Uint8Array(32).fill(8));
+      const leaf = await computeEligibilityLeaf(voter);
+      expect(bytesToHex(leaf)).toBe(bytesToHex(await hashDomain('veil:eligibility_leaf:v2', voter.toBytes())));
+    });
+  });
+
+  describe('Conviction voting', () => {
+    it('should accrue conviction linearly up to the full weight', () => {
+      const ramp = 7 * 86_400;
+      expect(computeConviction(1000n, 0, ramp)).toBe(0n);
+      expect(computeConviction(1000n, -10, ramp)).toBe(0n);
+      expect(computeConviction(1000n, ramp / 2, ramp)).toBe(500n);
+      expect(computeConviction(1000n, ramp, ramp)).toBe(1000n);
+      expect(computeConviction(1000n, ramp * 3, ramp)).toBe(1000n);
+    });
+  });
+
+  describe('Quadratic voting', () => {
+    it('should charge the sum of the squared votes', () => {
+      expect(computeQuadraticCost([])).toBe(0n);
+      expect(computeQuadraticCost([3n, 0n, 5n, 1n])).toBe(35n);
+      expect(computeQuadraticCost([10n])).toBe(100n);
+    });
+  });
+});
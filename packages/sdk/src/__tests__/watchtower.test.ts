@@ -0,0 +1,82 @@
+/**
+ * Watchtower Module Tests
+ *
+ * Tests for decoding and polling watchtower alert feeds
+ */
+
+import { describe, it, expect } from 'vitest';
+import { PublicKey } from '@solana/web3.js';
+import { randomBytes } from '../crypto';
+import {
+  alertsSince,
+  decodeWatchtower,
+  encodeWatchtower,
+  getWatchtowerPDA,
+  WatchtowerAlert,
+  WatchtowerAlertKind,
+  WatchtowerState,
+  WATCHTOWER_ALERT_SLOTS,
+} from '../watchtower';
+
+function alert(sequence: number): WatchtowerAlert {
+  return {
+    sequence: BigInt(sequence),
+    kind: WatchtowerAlertKind.LargeProposal,
+    reference: randomBytes(32),
+    value: 5_000_000_000n,
+    timestamp: 1_700_000_000n + BigInt(sequence),
+  };
+}
+
+function watchtower(alertCount: number): WatchtowerState {
+  const first = Math.max(1, alertCount - WATCHTOWER_ALERT_SLOTS + 1);
+  return {
+    subject: new PublicKey(randomBytes(32)),
+    owner: new PublicKey(randomBytes(32)),
+    alertKey: new PublicKey(randomBytes(32)),
+    largeAmount: 1_000_000_000n,
+    alertCount: BigInt(alertCount),
+    alerts: Array.from({ length: alertCount - first + 1 }, (_, i) => alert(first + i)),
+    createdAt: 1_700_000_000n,
+  };
+}
+
+describe('Watchtower Module', () => {
+  it('should decode the account layout', () => {
+    const state = watchtower(3);
+    const decoded = decodeWatchtower(encodeWatchtower(state));
+
+    expect(decoded.subject.equals(state.subject)).toBe(true);
+    expect(decoded.alertKey.equals(state.alertKey)).toBe(true);
+    expect(decoded.largeAmount).toBe(state.largeAmount);
+    expect(decoded.alerts.map((a) => a.sequence)).toEqual([1n, 2n, 3n]);
+    expect(decoded.alerts[2].reference).toEqual(state.alerts[2].reference);
+    expect(decoded.alerts[2].timestamp).toBe(1_700_000_003n);
+  });
+
+  it('should reject other accounts', () => {
+    expect(() => decodeWatchtower(new Uint8Array(2048))).toThrow();
+  });
+
+  it('should return alerts after the cursor, oldest first', () => {
+    const poll = alertsSince(decodeWatchtower(encodeWatchtower(watchtower(20))), 10n);
+
+    expect(poll.alerts.map((a) => a.sequence)).toEqual([11n, 12n, 13n, 14n, 15n, 16n, 17n, 18n, 19n, 20n]);
+    expect(poll.latest).toBe(20n);
+    expect(poll.missed).toBe(0n);
+  });
+
+  it('should count alerts the ring overwrote', () => {
+    const poll = alertsSince(decodeWatchtower(encodeWatchtower(watchtower(40))), 2n);
+
+    expect(poll.alerts).toHaveLength(WATCHTOWER_ALERT_SLOTS);
+    expect(poll.alerts[0].sequence).toBe(25n);
+    expect(poll.missed).toBe(22n);
+  });
+
+  it('should derive one watchtower per subject', () => {
+    const subject = new PublicKey(randomBytes(32));
+    expect(getWatchtowerPDA(subject)[0].equals(getWatchtowerPDA(subject)[0])).toBe(true);
+    expect(getWatchtowerPDA(subject)[0].equals(getWatchtowerPDA(new PublicKey(randomBytes(32)))[0])).toBe(false);
+  });
+});
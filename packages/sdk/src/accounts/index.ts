@@ -0,0 +1,170 @@
+/**
+ * Veil Account Kinds Module
+ *
+ * Classifies the program's accounts without deserializing them. Every
+ * account starts with its 8-byte Anchor discriminator, which already names
+ * its type, so this module maps discriminators to account names and the
+ * subsystem (state module) they belong to, and builds the `memcmp` filters
+ * getProgramAccounts needs to fetch one kind.
+ *
+ * Layouts are append-only (see `migrate_account`), so a tag field cannot
+ * be put in front of existing fields without moving every offset indexers
+ * already filter on; the discriminator is the kind tag.
+ *
+ * @example
+ * ```typescript
+ * import { accountKindFilter, classifyAccount } from '@veil-protocol/sdk/accounts';
+ *
+ * const pools = await connection.getProgramAccounts(VEIL_PROGRAM_ID, {
+ *   filters: [accountKindFilter('ShieldedPool')],
+ * });
+ * const kind = classifyAccount(info.data); // { name: 'ShieldedPool', subsystem: 'shielded' }
+ * ```
+ */
+
+import { utils } from '@coral-xyz/anchor';
+import { GetProgramAccountsFilter } from '@solana/web3.js';
+import { bytesToHex, hexToBytes } from '../crypto';
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+export type VeilSubsystem =
+  | 'audit'
+  | 'beacon'
+  | 'circuits'
+  | 'commerce'
+  | 'crank'
+  | 'governance'
+  | 'holding'
+  | 'multisig'
+  | 'referral'
+  | 'relayer'
+  | 'rln'
+  | 'shielded'
+  | 'staking'
+  | 'voting'
+  | 'wallet'
+  | 'watchtower';
+
+export interface VeilAccountKind {
+  /** Subsystem (program state module) the account belongs to */
+  subsystem: VeilSubsystem;
+  /** Anchor discriminator, hex */
+  discriminator: string;
+}
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+/**
+ * Every program account type by name. Discriminators are
+ * sha256("account:<Name>")[..8] except for the three governance accounts
+ * that set their own
+ */
+export const VEIL_ACCOUNT_KINDS = {
+  AuditLog: { subsystem: 'audit', discriminator: 'e6cfb0e9aa8265f4' },
+  BeaconRound: { subsystem: 'beacon', discriminator: '3ea29a2a6a546fea' },
+  BeaconEntry: { subsystem: 'beacon', discriminator: 'b267bc63338006a7' },
+  CircuitRegistry: { subsystem: 'circuits', discriminator: 'f83d6a378a985825' },
+  Escrow: { subsystem: 'commerce', discriminator: '1fd57bbbba16da9b' },
+  Subscription: { subsystem: 'commerce', discriminator: '40071a8766846221' },
+  Campaign: { subsystem: 'commerce', discriminator: '3228310b9ddce5c0' },
+  CrankTask: { subsystem: 'crank', discriminator: '74f5465ddbd99e28' },
+  VoterWeightRecord: { subsystem: 'governance', discriminator: '87241d9180f5d912' },
+  RealmRegistrar: { subsystem: 'governance', discriminator: '3bcd551b8f31dff0' },
+  RealmWeightSnapshot: { subsystem: 'governance', discriminator: 'af591909927f1742' },
+  RealmsVoterWeightRecord: { subsystem: 'governance', discriminator: '2ef99b4b99f87409' },
+  RealmsMaxVoterWeightRecord: { subsystem: 'governance', discriminator: '9d5ff29710621a76' },
+  HoldingAccount: { subsystem: 'holding', discriminator: 'ad88748472f12f13' },
+  StealthMultisig: { subsystem: 'multisig', discriminator: 'd8196bcd79f70173' },
+  MultisigProposal: { subsystem: 'multisig', discriminator: '0d0f9037fca453d0' },
+  MultisigProposalAction: { subsystem: 'multisig', discriminator: 'ed3a922a41b44161' },
+  EncryptedMultisigAction: { subsystem: 'multisig', discriminator: '6f5d86f3ec85800a' },
+  SignerHeartbeats: { subsystem: 'multisig', discriminator: '18f58e4a0aee2e5a' },
+  TreasuryAttestation: { subsystem: 'multisig', discriminator: '621aeba5a604c1a5' },
+  ReferralProgram: { subsystem: 'referral', discriminator: 'b2e2eb07991d9941' },
+  ReferralRecord: { subsystem: 'referral', discriminator: 'e1d7d35d32a8fcb9' },
+  RelayerRegistry: { subsystem: 'relayer', discriminator: '5475b409691a2795' },
+  RelayerRecord: { subsystem: 'relayer', discriminator: '8a84acd794c37249' },
+  RlnGroup: { subsystem: 'rln', discriminator: 'c63430afafec76d2' },
+  RlnMember: { subsystem: 'rln', discriminator: '99bbfaa82c06e9e3' },
+  RlnNullifierRecord: { subsystem: 'rln', discriminator: '08f159e2e611c16a' },
+  ShieldedPool: { subsystem: 'shielded', discriminator: '682fd0003ffaaa67' },
+  ShieldedNote: { subsystem: 'shielded', discriminator: '64f17ceaa64dc438' },
+  NullifierRecord: { subsystem: 'shielded', discriminator: '381239af45cabd46' },
+  ReserveAttestation: { subsystem: 'shielded', discriminator: '69d45fd88c2acd4b' },
+  DepositReceiptConfig: { subsystem: 'shielded', discriminator: 'a887bcc3059771f1' },
+  DepositCaller: { subsystem: 'shielded', discriminator: 'c185334ea90e7347' },
+  ClaimableNote: { subsystem: 'shielded', discriminator: 'aaaba51924fb0cf2' },
+  TreeCheckpoint: { subsystem: 'shielded', discriminator: 'a31b8f1e39d55b0a' },
+  NullifierFilter: { subsystem: 'shielded', discriminator: '08f41197ed4b951b' },
+  ProtocolStats: { subsystem: 'shielded', discriminator: '85e3d8f1ebac231f' },
+  PrivateStakePool: { subsystem: 'staking', discriminator: 'b15a5f2b86bcb06a' },
+  PrivateStakeRecord: { subsystem: 'staking', discriminator: '58b3c80715e4fa72' },
+  Proposal: { subsystem: 'voting', discriminator: '1a5ebdbb74883521' },
+  ProposalTemplate: { subsystem: 'voting', discriminator: '76c06c1e84c0006f' },
+  ConvictionProposal: { subsystem: 'voting', discriminator: '726e9fed3a40819e' },
+  ConvictionStake: { subsystem: 'voting', discriminator: 'aebf2326951e8526' },
+  QuadraticProposal: { subsystem: 'voting', discriminator: '61a5e415d43de9be' },
+  QuadraticVoteRecord: { subsystem: 'voting', discriminator: '6c0a36c9c5604de5' },
+  PoolGovernance: { subsystem: 'voting', discriminator: 'a07c01719cb66955' },
+  PoolUpdateProposal: { subsystem: 'voting', discriminator: 'b2d21c0d7e9e2373' },
+  VoteRecord: { subsystem: 'voting', discriminator: '70097ba5ea099da7' },
+  WalletAccount: { subsystem: 'wallet', discriminator: '9e62ab99d440f2d5' },
+  AliasRecord: { subsystem: 'wallet', discriminator: '6d26324c1e2f9743' },
+  WalletBackup: { subsystem: 'wallet', discriminator: '5f9365556dfdcce0' },
+  WalletAttestation: { subsystem: 'wallet', discriminator: 'e9e992afade38e8f' },
+  WalletRegistry: { subsystem: 'wallet', discriminator: '53af466217bcf523' },
+  WalletEnrollment: { subsystem: 'wallet', discriminator: '2d79dc186940b086' },
+  Attestation: { subsystem: 'wallet', discriminator: '987db75624927949' },
+  Watchtower: { subsystem: 'watchtower', discriminator: '84a502cb1f9a4eed' },
+} as const satisfies Record<string, VeilAccountKind>;
+
+export type VeilAccountName = keyof typeof VEIL_ACCOUNT_KINDS;
+
+/** Bytes of the discriminator every account starts with */
+export const ACCOUNT_DISCRIMINATOR_LEN = 8;
+
+const KINDS_BY_DISCRIMINATOR = new Map<string, VeilAccountName>(
+  (Object.keys(VEIL_ACCOUNT_KINDS) as VeilAccountName[]).map((name) => [VEIL_ACCOUNT_KINDS[name].discriminator, name])
+);
+
+// ============================================================================
+// CLASSIFICATION
+// ============================================================================
+
+/**
+ * Name and subsystem of the account whose data is `data`, read from its
+ * discriminator alone; null for data the program did not write
+ */
+export function classifyAccount(
+  data: Uint8Array
+): { name: VeilAccountName; subsystem: VeilSubsystem } | null {
+  if (data.length < ACCOUNT_DISCRIMINATOR_LEN) return null;
+
+  const name = KINDS_BY_DISCRIMINATOR.get(bytesToHex(data.subarray(0, ACCOUNT_DISCRIMINATOR_LEN)));
+  if (!name) return null;
+  return { name, subsystem: VEIL_ACCOUNT_KINDS[name].subsystem };
+}
+
+/**
+ * getProgramAccounts filter matching accounts of one kind
+ */
+export function accountKindFilter(name: VeilAccountName): GetProgramAccountsFilter {
+  return {
+    memcmp: { offset: 0, bytes: utils.bytes.bs58.encode(hexToBytes(VEIL_ACCOUNT_KINDS[name].discriminator)) },
+  };
+}
+
+/**
+ * Names of the account kinds in `subsystem`. getProgramAccounts filters
+ * cannot be OR-ed, so fetch a subsystem with one query per kind
+ */
+export function subsystemAccountKinds(subsystem: VeilSubsystem): VeilAccountName[] {
+  return (Object.keys(VEIL_ACCOUNT_KINDS) as VeilAccountName[]).filter(
+    (name) => VEIL_ACCOUNT_KINDS[name].subsystem === subsystem
+  );
+}
@@ -0,0 +1,200 @@
+/**
+ * Veil Alias Module
+ *
+ * Private payment names. A wallet registers `alias_hash = H(name || salt)`
+ * on-chain together with its stealth meta-address (spend and view public
+ * keys) encrypted under a key derived from the same name and salt. Anyone
+ * told the name and salt can find the record and recover the meta-address;
+ * the chain only ever sees the hash and the ciphertext, so there is no public
+ * name -> address directory to scrape.
+ *
+ * Hashes use the program's domain-separated SHA-256 (`hash_domain` with
+ * DOMAIN_ALIAS). Names are normalised (NFKC, trimmed, lowercased) before
+ * hashing so "Alice" and "alice " resolve to the same record.
+ *
+ * @example
+ * ```typescript
+ * import { createAliasSalt, aliasHash, encryptMetaAddress, resolveAlias } from '@veil-protocol/sdk/alias';
+ *
+ * // Owner: register 'alice' and share the name + salt with payers
+ * const salt = createAliasSalt();
+ * const hash = await aliasHash('alice', salt);
+ * const encrypted = await encryptMetaAddress({ spendPublicKey, viewPublicKey }, 'alice', salt);
+ *
+ * // Payer: look the meta-address up
+ * const meta = await resolveAlias(connection, 'alice', salt);
+ * ```
+ */
+
+import { Connection, PublicKey } from '@solana/web3.js';
+import { hashDomain, sha256 } from '../crypto';
+import { VEIL_PROGRAM_ID } from '../shielded';
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/** Stealth meta-address an alias points to */
+export interface StealthMetaAddress {
+  /** Spend public key (32 bytes) */
+  spendPublicKey: Uint8Array;
+  /** View public key (32 bytes) */
+  viewPublicKey: Uint8Array;
+}
+
+/** On-chain AliasRecord */
+export interface AliasRecord {
+  aliasHash: Uint8Array;
+  /** Commitment wallet that controls the alias */
+  wallet: PublicKey;
+  encryptedMetaAddress: Uint8Array;
+  createdAt: number;
+  updatedAt: number;
+}
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+/** PDA seed of alias records (pda.rs ALIAS_SEED) */
+export const ALIAS_SEED = 'alias';
+
+/** Domain tag of alias hashes (crypto/hash.rs DOMAIN_ALIAS) */
+export const DOMAIN_ALIAS = 'veil:alias:v2';
+
+/** Domain tag of the meta-address encryption key; never used on-chain */
+export const DOMAIN_ALIAS_KEY = 'veil:alias_key:v1';
+
+/** Salt length in bytes */
+export const ALIAS_SALT_LENGTH = 32;
+
+/** Encrypted meta-address length in bytes (spend || view) */
+export const ENCRYPTED_META_ADDRESS_LENGTH = 64;
+
+/** Discriminator + alias_hash + wallet */
+const ENCRYPTED_META_ADDRESS_OFFSET = 8 + 32 + 32;
+
+// ============================================================================
+// HASHING
+// ============================================================================
+
+/** Canonical form of an alias name */
+export function normalizeAliasName(name: string): string {
+  const normalized = name.normalize('NFKC').trim().toLowerCase();
+  if (normalized.length === 0) {
+    throw new Error('Alias name is empty');
+  }
+  return normalized;
+}
+
+/** Random salt for a new alias */
+export function createAliasSalt(): Uint8Array {
+  return crypto.getRandomValues(new Uint8Array(ALIAS_SALT_LENGTH));
+}
+
+function aliasPreimage(name: string, salt: Uint8Array): Uint8Array[] {
+  if (salt.length !== ALIAS_SALT_LENGTH) {
+    throw new Error(`Alias salt must be ${ALIAS_SALT_LENGTH} bytes`);
+  }
+  // The fixed-length salt goes last, so name || salt is unambiguous
+  return [new TextEncoder().encode(normalizeAliasName(name)), salt];
+}
+
+/** alias_hash = H(DOMAIN_ALIAS, name || salt), the record's PDA seed */
+export async function aliasHash(name: string, salt: Uint8Array): Promise<Uint8Array> {
+  return hashDomain(DOMAIN_ALIAS, ...aliasPreimage(name, salt));
+}
+
+/** PDA of the alias record for `hash` */
+export function getAliasPDA(hash: Uint8Array, programId: PublicKey = VEIL_PROGRAM_ID): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from(ALIAS_SEED), Buffer.from(hash)], programId);
+}
+
+// ============================================================================
+// ENCRYPTION
+// ============================================================================
+
+/** 64-byte keystream from H(DOMAIN_ALIAS_KEY, name || salt) */
+async function aliasKeystream(name: string, salt: Uint8Array): Promise<Uint8Array> {
+  const key = await hashDomain(DOMAIN_ALIAS_KEY, ...aliasPreimage(name, salt));
+  const keystream = new Uint8Array(ENCRYPTED_META_ADDRESS_LENGTH);
+  for (let block = 0; block < ENCRYPTED_META_ADDRESS_LENGTH / 32; block++) {
+    keystream.set(await sha256(new Uint8Array([...key, block])), block * 32);
+  }
+  return keystream;
+}
+
+function xor(data: Uint8Array, keystream: Uint8Array): Uint8Array {
+  return data.map((byte, i) => byte ^ keystream[i]);
+}
+
+/**
+ * Encrypt a meta-address for `register_alias` / `update_alias`.
+ * The key depends only on the name and salt, so rotate the salt (a new
+ * alias) rather than updating in place when the old meta-address must
+ * stay unlinkable to the new one
+ */
+export async function encryptMetaAddress(
+  meta: StealthMetaAddress,
+  name: string,
+  salt: Uint8Array
+): Promise<Uint8Array> {
+  if (meta.spendPublicKey.length !== 32 || meta.viewPublicKey.length !== 32) {
+    throw new Error('Meta-address keys must be 32 bytes');
+  }
+  const plaintext = new Uint8Array(ENCRYPTED_META_ADDRESS_LENGTH);
+  plaintext.set(meta.spendPublicKey, 0);
+  plaintext.set(meta.viewPublicKey, 32);
+  return xor(plaintext, await aliasKeystream(name, salt));
+}
+
+/** Decrypt a record's meta-address with the alias name and salt */
+export async function decryptMetaAddress(
+  encrypted: Uint8Array,
+  name: string,
+  salt: Uint8Array
+): Promise<StealthMetaAddress> {
+  if (encrypted.length !== ENCRYPTED_META_ADDRESS_LENGTH) {
+    throw new Error(`Encrypted meta-address must be ${ENCRYPTED_META_ADDRESS_LENGTH} bytes`);
+  }
+  const plaintext = xor(encrypted, await aliasKeystream(name, salt));
+  return {
+    spendPublicKey: plaintext.slice(0, 32),
+    viewPublicKey: plaintext.slice(32, 64),
+  };
+}
+
+// ============================================================================
+// RESOLUTION
+// ============================================================================
+
+/** Decode an AliasRecord account */
+export function decodeAliasRecord(data: Uint8Array): AliasRecord {
+  const view = new DataView(data.buffer, data.byteOffset, data.byteLength);
+  const timestamps = ENCRYPTED_META_ADDRESS_OFFSET + ENCRYPTED_META_ADDRESS_LENGTH;
+  return {
+    aliasHash: data.slice(8, 40),
+    wallet: new PublicKey(data.slice(40, 72)),
+    encryptedMetaAddress: data.slice(ENCRYPTED_META_ADDRESS_OFFSET, timestamps),
+    createdAt: Number(view.getBigInt64(timestamps, true)),
+    updatedAt: Number(view.getBigInt64(timestamps + 8, true)),
+  };
+}
+
+/**
+ * Resolve an alias to its stealth meta-address, or `null` when no wallet
+ * has registered it
+ */
+export async function resolveAlias(
+  connection: Connection,
+  name: string,
+  salt: Uint8Array,
+  programId: PublicKey = VEIL_PROGRAM_ID
+): Promise<StealthMetaAddress | null> {
+  const [address] = getAliasPDA(await aliasHash(name, salt), programId);
+  const account = await connection.getAccountInfo(address);
+  if (!account) return null;
+
+  const record = decodeAliasRecord(account.data);
+  return decryptMetaAddress(record.encryptedMetaAddress, name, salt);
+}
@@ -0,0 +1,219 @@
+/**
+ * Veil Coin Selection Module
+ *
+ * Picks which shielded notes to spend for a withdrawal of `target`
+ * lamports. Every note spent in one transaction is linked to the others,
+ * and a tiny change note is hard to ever spend without linking it too, so
+ * the strategies trade off the number of inputs against change dust:
+ *
+ * - `LargestFirst`: spend the biggest notes until the target is covered
+ * - `BranchAndBound`: search for a set with no change, or the least change
+ *   that is not dust
+ * - `PrivacyPreferred`: the fewest notes possible, then no dust, then the
+ *   oldest notes (deposits with the most activity after them)
+ *
+ * Notes of different pools are never combined: a withdrawal proof is made
+ * against one pool's tree.
+ *
+ * @example
+ * ```typescript
+ * import { selectNotes, SelectionStrategy } from '@veil-protocol/sdk/coinselect';
+ *
+ * const { selected, change } = selectNotes(myNotes, 2_500_000_000n, {
+ *   strategy: SelectionStrategy.PrivacyPreferred,
+ *   dustThreshold: 10_000_000n,
+ * });
+ * ```
+ */
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+export enum SelectionStrategy {
+  LargestFirst = 'largest-first',
+  BranchAndBound = 'branch-and-bound',
+  PrivacyPreferred = 'privacy-preferred',
+}
+
+/** A decrypted note the wallet could spend */
+export interface SelectableNote {
+  /** Value in lamports */
+  value: bigint;
+  /** Pool the note is in; notes of different pools are never combined */
+  pool?: string;
+  /** Unix time (seconds) of the deposit; older notes are preferred for privacy */
+  createdAt?: number;
+  /** Unix time (seconds) the note unlocks; later notes are skipped */
+  unlockAt?: number;
+  /** Spent notes are skipped */
+  isSpent?: boolean;
+}
+
+export interface SelectionOptions {
+  strategy?: SelectionStrategy;
+  /** Change below this (but above zero) counts as dust; default 0 */
+  dustThreshold?: bigint;
+  /** Most notes to spend together; default 4 */
+  maxInputs?: number;
+  /** Current unix time (seconds), to skip locked notes */
+  now?: number;
+  /** Search steps before branch and bound gives up; default 100000 */
+  maxTries?: number;
+}
+
+export interface Selection<T extends SelectableNote> {
+  /** Notes to spend, largest first */
+  selected: T[];
+  /** Sum of the selected notes */
+  total: bigint;
+  /** total - target, returned as a change note */
+  change: bigint;
+  /** The change is above zero but below the dust threshold */
+  isDust: boolean;
+}
+
+export const DEFAULT_MAX_INPUTS = 4;
+export const DEFAULT_MAX_TRIES = 100_000;
+
+// ============================================================================
+// SELECTION
+// ============================================================================
+
+/**
+ * Choose notes covering `target` lamports with `options.strategy`
+ * (privacy-preferred by default). Throws if no pool holds enough
+ * spendable value within `maxInputs` notes
+ */
+export function selectNotes<T extends SelectableNote>(
+  notes: T[],
+  target: bigint,
+  options: SelectionOptions = {}
+): Selection<T> {
+  if (target <= 0n) throw new Error('target must be positive');
+
+  const strategy = options.strategy ?? SelectionStrategy.PrivacyPreferred;
+  const settings = {
+    dust: options.dustThreshold ?? 0n,
+    maxInputs: options.maxInputs ?? DEFAULT_MAX_INPUTS,
+    maxTries: options.maxTries ?? DEFAULT_MAX_TRIES,
+  };
+
+  let best: Selection<T> | null = null;
+  for (const group of spendableByPool(notes, options.now)) {
+    const candidate =
+      strategy === SelectionStrategy.LargestFirst
+        ? largestFirst(group, target, settings.maxInputs, settings.dust)
+        : search(group, target, settings, strategy);
+    if (candidate && (!best || compare(candidate, best, strategy) < 0)) {
+      best = candidate;
+    }
+  }
+
+  if (!best) throw new Error(`no pool holds ${target} lamports in at most ${settings.maxInputs} notes`);
+  return best;
+}
+
+/** Unspent, unlocked notes grouped by pool, each sorted largest first */
+function spendableByPool<T extends SelectableNote>(notes: T[], now?: number): T[][] {
+  const pools = new Map<string, T[]>();
+  for (const note of notes) {
+    if (note.isSpent || note.value <= 0n) continue;
+    if (now !== undefined && note.unlockAt !== undefined && note.unlockAt > now) continue;
+    const key = note.pool ?? '';
+    pools.set(key, [...(pools.get(key) ?? []), note]);
+  }
+  return [...pools.values()].map((group) => group.sort((a, b) => (a.value > b.value ? -1 : a.value < b.value ? 1 : 0)));
+}
+
+function selection<T extends SelectableNote>(selected: T[], target: bigint, dust: bigint): Selection<T> {
+  const total = selected.reduce((sum, note) => sum + note.value, 0n);
+  const change = total - target;
+  return { selected, total, change, isDust: change > 0n && change < dust };
+}
+
+function largestFirst<T extends SelectableNote>(
+  sorted: T[],
+  target: bigint,
+  maxInputs: number,
+  dust: bigint
+): Selection<T> | null {
+  let total = 0n;
+  const selected: T[] = [];
+  for (const note of sorted) {
+    if (total >= target || selected.length === maxInputs) break;
+    selected.push(note);
+    total += note.value;
+  }
+  return total >= target ? selection(selected, target, dust) : null;
+}
+
+/**
+ * Depth-first search over include/exclude decisions, largest notes first
+ * A branch stops as soon as it covers the target (more notes only add
+ * change) or can no longer reach it
+ */
+function search<T extends SelectableNote>(
+  sorted: T[],
+  target: bigint,
+  settings: { dust: bigint; maxInputs: number; maxTries: number },
+  strategy: SelectionStrategy
+): Selection<T> | null {
+  const remaining: bigint[] = new Array(sorted.length + 1).fill(0n);
+  for (let i = sorted.length - 1; i >= 0; i--) remaining[i] = remaining[i + 1] + sorted[i].value;
+
+  const found: { best: Selection<T> | null; exact: boolean } = { best: null, exact: false };
+  let tries = 0;
+  const chosen: T[] = [];
+
+  const visit = (index: number, total: bigint): void => {
+    // Branch and bound cannot beat a changeless set
+    if (found.exact || ++tries > settings.maxTries) return;
+    if (total >= target) {
+      const candidate = selection([...chosen], target, settings.dust);
+      if (!found.best || compare(candidate, found.best, strategy) < 0) found.best = candidate;
+      found.exact = strategy === SelectionStrategy.BranchAndBound && candidate.change === 0n;
+      return;
+    }
+    if (index === sorted.length || chosen.length === settings.maxInputs) return;
+    if (total + remaining[index] < target) return;
+
+    chosen.push(sorted[index]);
+    visit(index + 1, total + sorted[index].value);
+    chosen.pop();
+    visit(index + 1, total);
+  };
+  visit(0, 0n);
+
+  return found.best ?? largestFirst(sorted, target, settings.maxInputs, settings.dust);
+}
+
+/** 0 for no change, 1 for change worth keeping, 2 for dust */
+function changeClass(candidate: Selection<SelectableNote>): number {
+  if (candidate.change === 0n) return 0;
+  return candidate.isDust ? 2 : 1;
+}
+
+/** Deposit time of the newest selected note (unknown counts as newest) */
+function newestInput(candidate: Selection<SelectableNote>): number {
+  return Math.max(...candidate.selected.map((note) => note.createdAt ?? Number.MAX_SAFE_INTEGER));
+}
+
+/** Negative when `a` is the better selection under `strategy` */
+function compare(a: Selection<SelectableNote>, b: Selection<SelectableNote>, strategy: SelectionStrategy): number {
+  const keys = (s: Selection<SelectableNote>): (number | bigint)[] => {
+    switch (strategy) {
+      case SelectionStrategy.LargestFirst:
+        return [s.selected.length, changeClass(s), s.change];
+      case SelectionStrategy.BranchAndBound:
+        return [changeClass(s), s.change, s.selected.length];
+      case SelectionStrategy.PrivacyPreferred:
+        return [s.selected.length, changeClass(s), newestInput(s), s.change];
+    }
+  };
+  const [ka, kb] = [keys(a), keys(b)];
+  for (let i = 0; i < ka.length; i++) {
+    if (ka[i] !== kb[i]) return ka[i] < kb[i] ? -1 : 1;
+  }
+  return 0;
+}
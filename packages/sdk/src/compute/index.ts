@@ -0,0 +1,148 @@
+/**
+ * Veil Compute Budget Module
+ *
+ * Sizes the compute budget of Veil transactions. SDK transaction builders
+ * prepend `SetComputeUnitLimit` (and, when a price is set,
+ * `SetComputeUnitPrice`) instructions sized from the per-instruction
+ * benchmark table, so proof-verifying instructions get the limit they need
+ * and priority fees are only paid on units the transaction can use.
+ *
+ * The table mirrors tests/compute-budget.json, which a benchmark run
+ * writes as measured units + 20% headroom; instructions it does not list
+ * get the runtime's default per-instruction allowance. The figures checked
+ * in are estimates that no benchmark run has produced yet.
+ *
+ * @example
+ * ```typescript
+ * import { withComputeBudget, recommendedComputeUnitPrice } from '@veil-protocol/sdk/compute';
+ *
+ * const tx = new Transaction().add(depositIx);
+ * withComputeBudget(tx, ['shield_deposit'], {
+ *   computeUnitPrice: await recommendedComputeUnitPrice(connection, [pool]),
+ * });
+ * ```
+ */
+
+import { ComputeBudgetProgram, Connection, PublicKey, Transaction, TransactionInstruction } from '@solana/web3.js';
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+export interface ComputeBudgetOptions {
+  /** Compute unit limit for the whole transaction, replacing the estimate */
+  computeUnitLimit?: number;
+  /** Per-instruction units, replacing the benchmark table entry */
+  instructionUnits?: Record<string, number>;
+  /** Priority fee in micro-lamports per compute unit (default: none) */
+  computeUnitPrice?: number;
+  /** Leave the transaction's budget untouched */
+  disabled?: boolean;
+}
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+/** Estimated compute units per instruction (tests/compute-budget.json) */
+export const INSTRUCTION_COMPUTE_UNITS: Readonly<Record<string, number>> = {
+  initialize_commitment: 20_000,
+  submit_proof: 30_000,
+  initiate_recovery: 10_000,
+  cancel_recovery: 8_000,
+  create_proposal: 20_000,
+  cast_vote: 25_000,
+  create_multisig: 30_000,
+  create_multisig_proposal: 30_000,
+  stealth_sign: 30_000,
+  execute_multisig_proposal: 12_000,
+  create_rln_group: 20_000,
+  register_rln_member: 35_000,
+  create_shielded_pool: 20_000,
+  shield_deposit: 30_000,
+  create_tree_checkpoint: 15_000,
+  checkpoint_tree: 40_000,
+};
+
+/** Units the runtime allows an instruction without an explicit limit */
+export const DEFAULT_INSTRUCTION_COMPUTE_UNITS = 200_000;
+
+/** Most compute units one transaction may request */
+export const MAX_TRANSACTION_COMPUTE_UNITS = 1_400_000;
+
+/** Units consumed by each compute budget instruction itself */
+const COMPUTE_BUDGET_INSTRUCTION_UNITS = 150;
+
+// ============================================================================
+// BUDGET
+// ============================================================================
+
+/**
+ * Compute unit limit for a transaction running `instructions` (program
+ * instruction names, e.g. 'shield_deposit'), capped at the protocol maximum
+ */
+export function estimateComputeUnits(instructions: string[], options: ComputeBudgetOptions = {}): number {
+  if (options.computeUnitLimit !== undefined) {
+    return Math.min(options.computeUnitLimit, MAX_TRANSACTION_COMPUTE_UNITS);
+  }
+
+  const budgetInstructions = options.computeUnitPrice ? 2 : 1;
+  const units = instructions.reduce(
+    (total, name) => total + (
+      options.instructionUnits?.[name] ??
+      INSTRUCTION_COMPUTE_UNITS[name] ??
+      DEFAULT_INSTRUCTION_COMPUTE_UNITS
+    ),
+    budgetInstructions * COMPUTE_BUDGET_INSTRUCTION_UNITS
+  );
+  return Math.min(units, MAX_TRANSACTION_COMPUTE_UNITS);
+}
+
+/**
+ * Compute budget instructions for a transaction running `instructions`;
+ * place them before any other instruction
+ */
+export function computeBudgetInstructions(
+  instructions: string[],
+  options: ComputeBudgetOptions = {}
+): TransactionInstruction[] {
+  if (options.disabled) return [];
+
+  const budget = [
+    ComputeBudgetProgram.setComputeUnitLimit({ units: estimateComputeUnits(instructions, options) }),
+  ];
+  if (options.computeUnitPrice) {
+    budget.push(ComputeBudgetProgram.setComputeUnitPrice({ microLamports: options.computeUnitPrice }));
+  }
+  return budget;
+}
+
+/**
+ * Prepend compute budget instructions to `tx`, which runs `instructions`
+ * @returns `tx`, for chaining
+ */
+export function withComputeBudget(
+  tx: Transaction,
+  instructions: string[],
+  options: ComputeBudgetOptions = {}
+): Transaction {
+  tx.instructions.unshift(...computeBudgetInstructions(instructions, options));
+  return tx;
+}
+
+/**
+ * Priority fee (micro-lamports per unit) paid by the median recent
+ * transaction that paid one, optionally restricted to transactions
+ * writing `accounts`. Returns 0 when nobody is paying for priority
+ */
+export async function recommendedComputeUnitPrice(
+  connection: Connection,
+  accounts: PublicKey[] = []
+): Promise<number> {
+  const fees = (await connection.getRecentPrioritizationFees({ lockedWritableAccounts: accounts }))
+    .map((fee) => fee.prioritizationFee)
+    .filter((fee) => fee > 0)
+    .sort((a, b) => a - b);
+  if (fees.length === 0) return 0;
+  return fees[Math.floor(fees.length / 2)];
+}
@@ -47,6 +47,24 @@ export async function sha256String(input: string): Promise<Uint8Array> {
   return sha256(encoder.encode(input));
 }
 
+/**
+ * Domain-separated SHA-256, matching the program's `hash_domain`:
+ * sha256(len(tag) || tag || parts...)
+ */
+export async function hashDomain(tag: string, ...parts: Uint8Array[]): Promise<Uint8Array> {
+  const tagBytes = new TextEncoder().encode(tag);
+  const length = 1 + tagBytes.length + parts.reduce((total, part) => total + part.length, 0);
+  const data = new Uint8Array(length);
+  data[0] = tagBytes.length;
+  data.set(tagBytes, 1);
+  let offset = 1 + tagBytes.length;
+  for (const part of parts) {
+    data.set(part, offset);
+    offset += part.length;
+  }
+  return sha256(data);
+}
+
 /**
  * Create commitment hash from identity
  */
@@ -17,6 +17,16 @@
  * - 🔒 Confidential: SPL Token-2022 confidential transfers
  * - 📋 Compliance: Audit keys, ZK-KYC, institutional privacy
  * - 💱 Ramps: Anonymous on/off ramps for fiat
+ * - 📒 Ledger: Deterministic accounting export from event replay
+ * - ⛽ Compute: Compute unit limits and priority fees for Veil transactions
+ * - 🏷️ Alias: Private payment names resolving to stealth meta-addresses
+ * - 🧊 Offline: Durable-nonce transactions for cold-storage signers
+ * - 🗂️ Accounts: Classify program accounts by discriminator for indexers
+ * - 🔔 Watchtower: Poll wallet, multisig and pool alert feeds for notifiers
+ * - 🪙 Coin Selection: Pick which notes to spend with the least linkability and dust
+ * - ⏳ Prover: Background proof generation with progress and cancellation
+ * - 🛰️ Remote Proving: Delegate proofs to attested enclaves without revealing the witness
+ * - 🗺️ Planner: Resumable plans for multi-transaction flows
  *
  * @example
  * ```typescript
@@ -80,6 +90,16 @@ export * from './compression';
 export * from './confidential';
 export * from './compliance';
 export * from './ramps';
+export * from './ledger';
+export * from './compute';
+export * from './alias';
+export * from './offline';
+export * from './accounts';
+export * from './watchtower';
+export * from './coinselect';
+export * from './prover';
+export * from './remote';
+export * from './planner';
 
 // Import for VeilClient
 import { generateIdentityProof, createIdentityCommitment, deriveWallet } from './identity';
@@ -96,6 +116,7 @@ import { CompressedAccountClient, CompressedTokenClient, CompressedShieldedPool
 import { ConfidentialTransferClient, createConfidentialClient } from './confidential';
 import { ComplianceClient, createComplianceClient } from './compliance';
 import { RampClient, createRampClient } from './ramps';
+import { ComputeBudgetOptions } from './compute';
 import { IdentityInput, RecoveryConfig, ShamirShare } from './types';
 
 // ============================================================================
@@ -105,6 +126,8 @@ import { IdentityInput, RecoveryConfig, ShamirShare } from './types';
 export interface VeilClientConfig {
   connection: Connection;
   cluster?: 'mainnet-beta' | 'devnet' | 'testnet';
+  /** Compute budget attached to program transactions (see `compute`) */
+  computeBudget?: ComputeBudgetOptions;
 }
 
 /**
@@ -126,6 +149,7 @@ export class VeilClient {
   public connected: boolean = false;
 
   private encryptionKey: string = '';
+  private computeBudget: ComputeBudgetOptions;
   private commitment: Uint8Array | null = null;
 
   // Core privacy modules
@@ -155,6 +179,7 @@ export class VeilClient {
 
   constructor(config: VeilClientConfig) {
     this.connection = config.connection;
+    this.computeBudget = config.computeBudget ?? {};
   }
 
   /**
@@ -173,7 +198,7 @@ export class VeilClient {
       this.encryptionKey = Buffer.from(result.commitment).toString('hex');
 
       // Initialize core privacy modules
-      this.shielded = createShieldedClient(this.connection, this.encryptionKey);
+      this.shielded = createShieldedClient(this.connection, this.encryptionKey, this.computeBudget);
       this.transfer = createTransferClient(this.connection);
       this.tokens = createTokenClient(this.connection, this.encryptionKey);
       this.dex = createDexClient(this.connection);
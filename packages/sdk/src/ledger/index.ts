@@ -0,0 +1,532 @@
+/**
+ * Veil Ledger Module
+ *
+ * Accounting export for shielded pool activity. Replays Veil program events,
+ * keeps the ones a viewing key can open and turns them into a deterministic
+ * double-entry ledger (deposits, withdrawals, rewards, fees) for tax and
+ * audit reports - no third-party indexer required.
+ *
+ * Events never carry amounts, so amounts are recovered by decrypting the
+ * notes each transaction spent and created:
+ * - ShieldedDeposit: a new note of ours → deposit
+ * - ShieldedWithdraw / ShieldedWithdrawRelayed / ShieldedWithdrawAggregated:
+ *   spent note minus change note (minus relayer fee) → withdrawal (+ fee)
+ * - ShieldedRewardsClaimed: new note minus stake note → reward
+ * - ShieldedConsolidate: inputs minus output → fee
+ *
+ * ShieldedDeposit events carry the note ciphertext, so deposits decrypt
+ * straight from the event; other notes (and deposits from before the event
+ * carried it) are read through a `NoteFetcher`.
+ *
+ * The program emits these events with `emit_cpi!`, so they are read from its
+ * self-invocations (inner instructions) rather than from logs, which RPC
+ * nodes truncate in busy transactions. Transactions from before that change
+ * are still picked up from their logs.
+ *
+ * The same events and viewing key always produce the same entries, in the
+ * same order, with the same IDs.
+ *
+ * @example
+ * ```typescript
+ * import { BorshCoder } from '@coral-xyz/anchor';
+ * import { replayLedger, createViewingKey, ledgerToCsv } from '@veil-protocol/sdk/ledger';
+ *
+ * const coder = new BorshCoder(idl);
+ * const entries = await replayLedger(
+ *   connection,
+ *   createViewingKey(encryptionKey, wallet),
+ *   (data) => coder.events.decode(data),
+ * );
+ * fs.writeFileSync('veil-2025.csv', ledgerToCsv(entries));
+ * ```
+ */
+
+import { utils } from '@coral-xyz/anchor';
+import { Connection, PublicKey } from '@solana/web3.js';
+import { DecryptedNote } from '../types';
+import { bytesToHex } from '../crypto';
+import { VEIL_PROGRAM_ID, decryptNoteData, computeNoteNullifier } from '../shielded';
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/** A decoded Veil event and where it was emitted */
+export interface VeilEventRecord {
+  /** Event name, e.g. 'ShieldedDeposit' */
+  name: string;
+  /** Decoded fields (snake_case or camelCase keys) */
+  data: Record<string, unknown>;
+  /** Transaction signature */
+  signature: string;
+  /** Slot the transaction landed in */
+  slot: number;
+  /** Block time (unix seconds), if the RPC node knows it */
+  blockTime: number | null;
+  /** Position among the Veil events of its transaction */
+  eventIndex: number;
+}
+
+/**
+ * Decodes one event payload (base64 discriminator + data), e.g.
+ * `coder.events.decode`. Used for both `Program data:` log lines and
+ * `emit_cpi!` instruction data
+ */
+export type EventDecoder = (data: string) => { name: string; data: Record<string, unknown> } | null;
+
+/** An instruction invoked inside a transaction */
+export interface InnerInstruction {
+  programId: PublicKey;
+  data: Uint8Array;
+}
+
+/** Returns the encrypted data of the note with `commitment` in `pool` */
+export type NoteFetcher = (pool: PublicKey, commitment: Uint8Array) => Promise<Uint8Array | null>;
+
+/** What the ledger needs to recognise a user's notes */
+export interface LedgerViewingKey {
+  /** Decrypt note data, or null if the note is not ours */
+  decryptNote(encryptedData: Uint8Array): DecryptedNote | null;
+  /** Nullifier our note with `noteCommitment` is spent with */
+  nullifierFor(noteCommitment: Uint8Array): Promise<Uint8Array>;
+  /** Fee paid to a relayer, if we know the opening of `feeCommitment` */
+  relayerFee?(feeCommitment: Uint8Array): bigint | null;
+}
+
+export type LedgerEntryKind = 'deposit' | 'withdrawal' | 'reward' | 'fee';
+
+/** One leg of an entry. Positive amounts are debits, negative are credits */
+export interface LedgerPosting {
+  account: string;
+  /** Lamports */
+  amount: bigint;
+}
+
+/** A balanced journal entry: its postings always sum to zero */
+export interface LedgerEntry {
+  /** Stable ID: signature/eventIndex/leg/kind */
+  id: string;
+  kind: LedgerEntryKind;
+  /** Pool address (base58) */
+  pool: string;
+  signature: string;
+  slot: number;
+  blockTime: number | null;
+  postings: LedgerPosting[];
+}
+
+export interface ReplayOptions {
+  /** Program to replay (default: VEIL_PROGRAM_ID) */
+  programId?: PublicKey;
+  /** Replay transactions before this signature */
+  before?: string;
+  /** Replay transactions after this signature */
+  until?: string;
+  /** Maximum number of transactions to fetch */
+  limit?: number;
+  /** Where to read note ciphertexts (default: getProgramAccounts) */
+  fetchNote?: NoteFetcher;
+}
+
+// ============================================================================
+// CONSTANTS
+// ============================================================================
+
+/** Ledger account names */
+export const LEDGER_ACCOUNTS = {
+  /** Shielded balance in one pool */
+  shielded: (pool: string) => `assets:shielded:${pool}`,
+  /** Funds outside the pool (the depositor's or recipient's public wallet) */
+  external: 'assets:external',
+  /** Staking rewards earned inside the pool */
+  rewards: 'income:rewards',
+  /** Relayer and consolidation fees */
+  fees: 'expenses:fees',
+} as const;
+
+/** Byte offsets in a ShieldedNote account: after the discriminator and pool */
+const NOTE_POOL_OFFSET = 8;
+const NOTE_COMMITMENT_OFFSET = 40;
+const NOTE_ENCRYPTED_DATA_OFFSET = 72;
+const NOTE_ENCRYPTED_DATA_LEN = 64;
+
+/** Anchor's EVENT_IX_TAG (little-endian), the prefix of `emit_cpi!` instruction data */
+const EVENT_IX_TAG = Uint8Array.from([0xe4, 0x45, 0xa5, 0x2e, 0x51, 0xcb, 0x9a, 0x1d]);
+
+// ============================================================================
+// EVENT REPLAY
+// ============================================================================
+
+/**
+ * Extract the events `programId` emitted in one transaction's logs
+ * Tracks the invocation stack so events of other programs (including ones
+ * Veil calls into) are ignored
+ */
+export function parseProgramEvents(
+  logs: string[],
+  programId: PublicKey,
+  decodeEvent: EventDecoder
+): { name: string; data: Record<string, unknown> }[] {
+  const program = programId.toBase58();
+  const stack: string[] = [];
+  const events: { name: string; data: Record<string, unknown> }[] = [];
+
+  for (const line of logs) {
+    const invoke = /^Program (\S+) invoke \[\d+\]$/.exec(line);
+    if (invoke) {
+      stack.push(invoke[1]);
+      continue;
+    }
+    if (/^Program \S+ (success|failed)/.test(line)) {
+      stack.pop();
+      continue;
+    }
+    if (line.startsWith('Program data: ') && stack[stack.length - 1] === program) {
+      const event = decodeEvent(line.slice('Program data: '.length));
+      if (event) events.push(event);
+    }
+  }
+  return events;
+}
+
+/**
+ * Extract the events `programId` emitted with `emit_cpi!` from a
+ * transaction's inner instructions, in emission order
+ * Only the program itself can sign for its event authority, so a
+ * successful self-invocation carrying the tag is always a genuine event
+ */
+export function parseCpiEvents(
+  instructions: InnerInstruction[],
+  programId: PublicKey,
+  decodeEvent: EventDecoder
+): { name: string; data: Record<string, unknown> }[] {
+  const events: { name: string; data: Record<string, unknown> }[] = [];
+
+  for (const { programId: invoked, data } of instructions) {
+    if (!invoked.equals(programId) || data.length < EVENT_IX_TAG.length) continue;
+    if (!EVENT_IX_TAG.every((byte, i) => data[i] === byte)) continue;
+
+    const event = decodeEvent(Buffer.from(data.subarray(EVENT_IX_TAG.length)).toString('base64'));
+    if (event) events.push(event);
+  }
+  return events;
+}
+
+/**
+ * Fetch and decode the Veil events of successful transactions
+ * `emit_cpi!` events come first in each transaction, followed by any
+ * events found in its logs
+ * Returned in ledger order (see `compareEvents`)
+ */
+export async function fetchVeilEvents(
+  connection: Connection,
+  decodeEvent: EventDecoder,
+  options: ReplayOptions = {}
+): Promise<VeilEventRecord[]> {
+  const programId = options.programId ?? VEIL_PROGRAM_ID;
+  const signatures = await connection.getSignaturesForAddress(programId, {
+    before: options.before,
+    until: options.until,
+    limit: options.limit,
+  });
+
+  const records: VeilEventRecord[] = [];
+  for (const { signature, err } of signatures) {
+    if (err) continue;
+
+    const tx = await connection.getTransaction(signature, {
+      commitment: 'confirmed',
+      maxSupportedTransactionVersion: 0,
+    });
+    if (!tx?.meta) continue;
+
+    const accountKeys = tx.transaction.message.getAccountKeys({
+      accountsFromLookupTables: tx.meta.loadedAddresses ?? undefined,
+    });
+    const inner = (tx.meta.innerInstructions ?? []).flatMap(({ instructions }) =>
+      instructions.map((ix) => ({
+        programId: accountKeys.get(ix.programIdIndex) as PublicKey,
+        data: utils.bytes.bs58.decode(ix.data),
+      }))
+    );
+
+    const events = [
+      ...parseCpiEvents(inner, programId, decodeEvent),
+      ...parseProgramEvents(tx.meta.logMessages ?? [], programId, decodeEvent),
+    ];
+    events.forEach((event, eventIndex) => {
+      records.push({
+        ...event,
+        signature,
+        slot: tx.slot,
+        blockTime: tx.blockTime ?? null,
+        eventIndex,
+      });
+    });
+  }
+
+  return records.sort(compareEvents);
+}
+
+/**
+ * Total order on events: slot, then signature, then position in the
+ * transaction. RPC nodes do not expose the order of transactions within a
+ * slot, so ties are broken by signature to keep replays deterministic
+ */
+export function compareEvents(a: VeilEventRecord, b: VeilEventRecord): number {
+  if (a.slot !== b.slot) return a.slot - b.slot;
+  if (a.signature !== b.signature) return a.signature < b.signature ? -1 : 1;
+  return a.eventIndex - b.eventIndex;
+}
+
+/**
+ * Note fetcher backed by getProgramAccounts, filtering ShieldedNote
+ * accounts by pool and commitment
+ */
+export function createNoteFetcher(
+  connection: Connection,
+  programId: PublicKey = VEIL_PROGRAM_ID
+): NoteFetcher {
+  return async (pool, commitment) => {
+    const accounts = await connection.getProgramAccounts(programId, {
+      filters: [
+        { memcmp: { offset: NOTE_POOL_OFFSET, bytes: pool.toBase58() } },
+        { memcmp: { offset: NOTE_COMMITMENT_OFFSET, bytes: new PublicKey(commitment).toBase58() } },
+      ],
+    });
+    if (accounts.length === 0) return null;
+
+    const data = accounts[0].account.data;
+    return new Uint8Array(
+      data.subarray(NOTE_ENCRYPTED_DATA_OFFSET, NOTE_ENCRYPTED_DATA_OFFSET + NOTE_ENCRYPTED_DATA_LEN)
+    );
+  };
+}
+
+/**
+ * Viewing key for notes created by the shielded client with `encryptionKey`
+ * for `wallet`
+ */
+export function createViewingKey(
+  encryptionKey: string,
+  wallet: PublicKey,
+  relayerFees: Map<string, bigint> = new Map()
+): LedgerViewingKey {
+  return {
+    decryptNote: (encryptedData) => decryptNoteData(encryptedData, encryptionKey),
+    nullifierFor: (noteCommitment) => computeNoteNullifier(noteCommitment, wallet, encryptionKey),
+    relayerFee: (feeCommitment) => relayerFees.get(bytesToHex(feeCommitment)) ?? null,
+  };
+}
+
+// ============================================================================
+// LEDGER
+// ============================================================================
+
+/**
+ * Build the double-entry ledger of the notes `viewingKey` can open
+ * Events are replayed in `compareEvents` order whatever order they are
+ * passed in, and duplicates (same signature and index) are dropped
+ */
+export async function buildLedger(
+  events: VeilEventRecord[],
+  viewingKey: LedgerViewingKey,
+  fetchNote: NoteFetcher
+): Promise<LedgerEntry[]> {
+  const seen = new Set<string>();
+  const ordered = [...events].sort(compareEvents).filter((event) => {
+    const key = `${event.signature}/${event.eventIndex}`;
+    if (seen.has(key)) return false;
+    seen.add(key);
+    return true;
+  });
+
+  // Our unspent notes, keyed by the nullifier that spends them
+  const owned = new Map<string, bigint>();
+
+  const own = async (
+    pool: PublicKey,
+    commitment: Uint8Array,
+    encryptedNote: Uint8Array | null = null
+  ): Promise<bigint | null> => {
+    if (commitment.every((byte) => byte === 0)) return null;
+    const encryptedData = encryptedNote ?? await fetchNote(pool, commitment);
+    const note = encryptedData && viewingKey.decryptNote(encryptedData);
+    if (!note) return null;
+
+    owned.set(bytesToHex(await viewingKey.nullifierFor(commitment)), note.amount);
+    return note.amount;
+  };
+
+  const spend = (nullifier: Uint8Array): bigint | null => {
+    const key = bytesToHex(nullifier);
+    const amount = owned.get(key);
+    if (amount === undefined) return null;
+    owned.delete(key);
+    return amount;
+  };
+
+  const entries: LedgerEntry[] = [];
+  for (const event of ordered) {
+    const pool = new PublicKey(field(event.data, 'pool') as PublicKey | string);
+    const shielded = LEDGER_ACCOUNTS.shielded(pool.toBase58());
+    const post = (leg: number, kind: LedgerEntryKind, amount: bigint, counterAccount: string) => {
+      if (amount <= 0n) return;
+      const shieldedAmount = kind === 'deposit' || kind === 'reward' ? amount : -amount;
+      entries.push({
+        id: `${event.signature}/${event.eventIndex}/${leg}/${kind}`,
+        kind,
+        pool: pool.toBase58(),
+        signature: event.signature,
+        slot: event.slot,
+        blockTime: event.blockTime,
+        postings: [
+          { account: shielded, amount: shieldedAmount },
+          { account: counterAccount, amount: -shieldedAmount },
+        ],
+      });
+    };
+
+    // A withdrawal leg: spent note minus change, minus any relayer fee
+    const withdrawal = async (leg: number, nullifier: Uint8Array, output: Uint8Array, fee: bigint) => {
+      const input = spend(nullifier);
+      if (input === null) return;
+      const change = (await own(pool, output)) ?? 0n;
+      post(leg, 'withdrawal', input - change - fee, LEDGER_ACCOUNTS.external);
+      post(leg, 'fee', fee, LEDGER_ACCOUNTS.fees);
+    };
+
+    switch (event.name) {
+      case 'ShieldedDeposit': {
+        const amount = await own(
+          pool,
+          bytes(event.data, 'note_commitment'),
+          optionalBytes(event.data, 'encrypted_note')
+        );
+        if (amount !== null) post(0, 'deposit', amount, LEDGER_ACCOUNTS.external);
+        break;
+      }
+      case 'ShieldedWithdraw':
+        await withdrawal(0, bytes(event.data, 'nullifier'), bytes(event.data, 'output_commitment'), 0n);
+        break;
+      case 'ShieldedWithdrawRelayed': {
+        const fee = viewingKey.relayerFee?.(bytes(event.data, 'fee_commitment')) ?? 0n;
+        await withdrawal(0, bytes(event.data, 'nullifier'), bytes(event.data, 'output_commitment'), fee);
+        break;
+      }
+      case 'ShieldedWithdrawAggregated': {
+        const nullifiers = bytesList(event.data, 'nullifiers');
+        const outputs = bytesList(event.data, 'output_commitments');
+        for (let leg = 0; leg < nullifiers.length; leg++) {
+          await withdrawal(leg, nullifiers[leg], outputs[leg], 0n);
+        }
+        break;
+      }
+      case 'ShieldedRewardsClaimed': {
+        const stake = spend(bytes(event.data, 'stake_nullifier'));
+        if (stake === null) break;
+        const claimed = (await own(pool, bytes(event.data, 'new_note_commitment'))) ?? 0n;
+        post(0, 'reward', claimed - stake, LEDGER_ACCOUNTS.rewards);
+        break;
+      }
+      case 'ShieldedConsolidate': {
+        let inputs = 0n;
+        let ours = false;
+        for (const nullifier of bytesList(event.data, 'nullifiers')) {
+          const amount = spend(nullifier);
+          if (amount === null) continue;
+          inputs += amount;
+          ours = true;
+        }
+        if (!ours) break;
+        const output = (await own(pool, bytes(event.data, 'output_commitment'))) ?? 0n;
+        post(0, 'fee', inputs - output, LEDGER_ACCOUNTS.fees);
+        break;
+      }
+      default:
+        break;
+    }
+  }
+
+  return entries;
+}
+
+/**
+ * Fetch every Veil event and build the ledger for `viewingKey`
+ */
+export async function replayLedger(
+  connection: Connection,
+  viewingKey: LedgerViewingKey,
+  decodeEvent: EventDecoder,
+  options: ReplayOptions = {}
+): Promise<LedgerEntry[]> {
+  const events = await fetchVeilEvents(connection, decodeEvent, options);
+  const fetchNote = options.fetchNote ?? createNoteFetcher(connection, options.programId);
+  return buildLedger(events, viewingKey, fetchNote);
+}
+
+/**
+ * Balance of every ledger account (debits positive)
+ * Sums to zero across accounts for any well-formed ledger
+ */
+export function ledgerBalances(entries: LedgerEntry[]): Map<string, bigint> {
+  const balances = new Map<string, bigint>();
+  for (const entry of entries) {
+    for (const { account, amount } of entry.postings) {
+      balances.set(account, (balances.get(account) ?? 0n) + amount);
+    }
+  }
+  return balances;
+}
+
+/**
+ * Render the ledger as CSV, one row per posting
+ */
+export function ledgerToCsv(entries: LedgerEntry[]): string {
+  const rows = ['id,kind,pool,signature,slot,block_time,account,amount_lamports'];
+  for (const entry of entries) {
+    for (const { account, amount } of entry.postings) {
+      rows.push([
+        entry.id,
+        entry.kind,
+        entry.pool,
+        entry.signature,
+        entry.slot,
+        entry.blockTime ?? '',
+        account,
+        amount.toString(),
+      ].join(','));
+    }
+  }
+  return rows.join('\n') + '\n';
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+/** Read an event field by its snake_case name, accepting camelCase too */
+function field(data: Record<string, unknown>, name: string): unknown {
+  if (name in data) return data[name];
+  return data[name.replace(/_([a-z])/g, (_, c: string) => c.toUpperCase())];
+}
+
+function toBytes(value: unknown): Uint8Array {
+  if (value instanceof Uint8Array) return value;
+  if (Array.isArray(value)) return new Uint8Array(value as number[]);
+  throw new Error('Expected a byte array event field');
+}
+
+function bytes(data: Record<string, unknown>, name: string): Uint8Array {
+  return toBytes(field(data, name));
+}
+
+function optionalBytes(data: Record<string, unknown>, name: string): Uint8Array | null {
+  const value = field(data, name);
+  return value === undefined ? null : toBytes(value);
+}
+
+function bytesList(data: Record<string, unknown>, name: string): Uint8Array[] {
+  const value = field(data, name);
+  return Array.isArray(value) ? value.map(toBytes) : [];
+}
@@ -6,7 +6,7 @@
  */
 
 import { Connection, PublicKey, Transaction, SystemProgram } from '@solana/web3.js';
-import { sha256String, poseidonHash, bytesToHex, bytesToBigInt, randomBytes } from '../crypto';
+import { sha256String, poseidonHash, bytesToHex, bytesToBigInt, randomBytes, hashDomain } from '../crypto';
 import { ProofData, Commitment } from '../types';
 
 // Program ID for stealth multisig
@@ -14,6 +14,14 @@ export const MULTISIG_PROGRAM_ID = new PublicKey('5C1VaebPdHZYETnTL18cLJK2RexXmV
 
 export const MAX_SIGNERS = 10;
 
+export const TREASURY_ATTESTATION_SEED = 'treasury_attestation';
+export const SIGNER_HEARTBEATS_SEED = 'signer_heartbeats';
+const DOMAIN_TREASURY_BAND = 'veil:treasury_band:v2';
+const DOMAIN_SMALL_SPEND = 'veil:small_spend:v2';
+const DOMAIN_SIGNER_HEARTBEAT = 'veil:signer_heartbeat:v2';
+const DOMAIN_SIGNER_ROTATION = 'veil:signer_rotation:v2';
+const DOMAIN_STEALTH_APPROVAL = 'veil:stealth_approval:v2';
+
 // ============================================================================
 // TYPES
 // ============================================================================
@@ -237,3 +245,107 @@ export function isThresholdReached(proposal: MultisigProposal, threshold: number
   return proposal.approvalCount >= threshold;
 }
 
+/**
+ * Public input a treasury attestation proof commits to, fixing the stated band:
+ * H(DOMAIN_TREASURY_BAND, multisig, pool, minBalance, maxBalance), bounds as
+ * u64 little-endian
+ */
+export async function computeTreasuryBandTag(
+  multisig: PublicKey,
+  pool: PublicKey,
+  minBalance: bigint,
+  maxBalance: bigint
+): Promise<Uint8Array> {
+  const bounds = new Uint8Array(16);
+  const view = new DataView(bounds.buffer);
+  view.setBigUint64(0, minBalance, true);
+  view.setBigUint64(8, maxBalance, true);
+  return hashDomain(DOMAIN_TREASURY_BAND, multisig.toBytes(), pool.toBytes(), bounds);
+}
+
+/**
+ * Treasury attestation of `multisig` over one pool state
+ */
+export function getTreasuryAttestationPDA(
+  multisig: PublicKey,
+  pool: PublicKey,
+  merkleRoot: Uint8Array,
+  nullifierCount: number,
+  programId: PublicKey = MULTISIG_PROGRAM_ID
+): [PublicKey, number] {
+  const count = Buffer.alloc(4);
+  count.writeUInt32LE(nullifierCount);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(TREASURY_ATTESTATION_SEED), multisig.toBuffer(), pool.toBuffer(), Buffer.from(merkleRoot), count],
+    programId
+  );
+}
+
+/**
+ * Public input a small-spend proof commits to, fixing the transfer:
+ * H(DOMAIN_SMALL_SPEND, proposal, mint, amount as u64 little-endian).
+ * SOL transfers use the default (all-zero) mint
+ */
+export async function computeSmallSpendTag(
+  proposal: PublicKey,
+  mint: PublicKey,
+  amount: bigint
+): Promise<Uint8Array> {
+  const encoded = new Uint8Array(8);
+  new DataView(encoded.buffer).setBigUint64(0, amount, true);
+  return hashDomain(DOMAIN_SMALL_SPEND, proposal.toBytes(), mint.toBytes(), encoded);
+}
+
+/**
+ * Public input a heartbeat proof commits to, fixing the epoch and the
+ * signer set: H(DOMAIN_SIGNER_HEARTBEAT, multisig, epoch as u64
+ * little-endian, ...signerCommitments)
+ */
+export async function computeHeartbeatTag(
+  multisig: PublicKey,
+  epoch: bigint,
+  signerCommitments: Uint8Array[]
+): Promise<Uint8Array> {
+  const encoded = new Uint8Array(8);
+  new DataView(encoded.buffer).setBigUint64(0, epoch, true);
+  return hashDomain(DOMAIN_SIGNER_HEARTBEAT, multisig.toBytes(), encoded, ...signerCommitments);
+}
+
+/**
+ * Heartbeats posted for `multisig` in one liveness epoch
+ */
+export function getSignerHeartbeatsPDA(
+  multisig: PublicKey,
+  epoch: bigint,
+  programId: PublicKey = MULTISIG_PROGRAM_ID
+): [PublicKey, number] {
+  const encoded = Buffer.alloc(8);
+  encoded.writeBigUInt64LE(epoch);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(SIGNER_HEARTBEATS_SEED), multisig.toBuffer(), encoded],
+    programId
+  );
+}
+
+/**
+ * Public input a stealth approval proof commits to, binding it to one
+ * proposal and the multisig's signer set:
+ * H(DOMAIN_STEALTH_APPROVAL, proposal, ...signerCommitments)
+ */
+export async function computeStealthApprovalTag(
+  proposal: PublicKey,
+  signerCommitments: Uint8Array[]
+): Promise<Uint8Array> {
+  return hashDomain(DOMAIN_STEALTH_APPROVAL, proposal.toBytes(), ...signerCommitments);
+}
+
+/**
+ * Public input a signer rotation proof commits to, fixing the commitment
+ * that replaces the old one: H(DOMAIN_SIGNER_ROTATION, multisig, newCommitment)
+ */
+export async function computeSignerRotationTag(
+  multisig: PublicKey,
+  newCommitment: Uint8Array
+): Promise<Uint8Array> {
+  return hashDomain(DOMAIN_SIGNER_ROTATION, multisig.toBytes(), newCommitment);
+}
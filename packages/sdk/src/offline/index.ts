@@ -0,0 +1,241 @@
+/**
+ * Veil Offline Signing Module
+ *
+ * Durable-nonce transactions for cold-storage signers. A transaction built
+ * on a nonce account instead of a recent blockhash never expires, so it
+ * can be carried to an air-gapped machine, signed there and brought back
+ * for submission whenever convenient. Each signer signs the same message
+ * bytes; the signed copies are merged and sent once all are collected.
+ *
+ * The program reads time when the transaction executes, not when it was
+ * signed, so stealth approvals signed offline go through
+ * `stealth_sign_offline`, which pins the action the signer reviewed and a
+ * `valid_until` time past which the approval no longer counts.
+ *
+ * @example
+ * ```typescript
+ * import { fetchDurableNonce, buildDurableNonceTransaction, signOffline,
+ *   exportOfflineTransaction, importOfflineTransaction, mergeOfflineSignatures } from '@veil-protocol/sdk/offline';
+ *
+ * // Online: prepare
+ * const nonce = await fetchDurableNonce(connection, nonceAccount);
+ * const tx = buildDurableNonceTransaction(nonce, feePayer, [approveIx]);
+ * const unsigned = exportOfflineTransaction(tx);
+ *
+ * // Offline: each cold signer
+ * const signed = exportOfflineTransaction(signOffline(importOfflineTransaction(unsigned), [coldKey]));
+ *
+ * // Online: collect and submit
+ * const ready = mergeOfflineSignatures(signedCopies.map(importOfflineTransaction));
+ * await connection.sendRawTransaction(ready.serialize());
+ * ```
+ */
+
+import {
+  Connection,
+  NonceAccount,
+  PublicKey,
+  Signer,
+  SystemInstruction,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from '@solana/web3.js';
+import { sha256 } from '../crypto';
+
+const VEIL_PROGRAM_ID = new PublicKey('5C1VaebPdHZYETnTL18cLJK2RexXmVVhkkYpnYHD5P4h');
+
+const EVENT_AUTHORITY_SEED = '__event_authority';
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+export interface DurableNonce {
+  /** Nonce account the transaction advances */
+  nonceAccount: PublicKey;
+  /** Key allowed to advance the nonce; must sign the transaction */
+  authority: PublicKey;
+  /** Current nonce value, used in place of a recent blockhash */
+  nonce: string;
+}
+
+export interface StealthSignOfflineParams {
+  multisig: PublicKey;
+  proposal: PublicKey;
+  /** Cold signer; pays for the approval slot */
+  signer: PublicKey;
+  /** Circuit registry the stealth approval verifying key is checked against */
+  circuitRegistry: PublicKey;
+  /** Stealth approval proof over [approvalCommitment, computeStealthApprovalTag(...)] */
+  signerProof: Uint8Array;
+  approvalCommitment: Uint8Array;
+  /** Proposal `instruction_hash` the signer reviewed */
+  instructionHash: Uint8Array;
+  /** Unix time after which the approval no longer counts */
+  validUntil: bigint;
+  /** `Circuit::StealthApproval` id the proof was generated for */
+  circuitId: Uint8Array;
+}
+
+// ============================================================================
+// DURABLE NONCES
+// ============================================================================
+
+/**
+ * Read the current value and authority of a nonce account
+ */
+export async function fetchDurableNonce(connection: Connection, nonceAccount: PublicKey): Promise<DurableNonce> {
+  const info = await connection.getAccountInfo(nonceAccount);
+  if (!info) throw new Error(`nonce account ${nonceAccount.toBase58()} not found`);
+
+  const { authorizedPubkey, nonce } = NonceAccount.fromAccountData(info.data);
+  return { nonceAccount, authority: authorizedPubkey, nonce };
+}
+
+/**
+ * Transaction running `instructions` on a durable nonce: the nonce
+ * advance comes first, as the runtime requires, and the nonce stands in
+ * for the recent blockhash. Compute budget instructions belong in
+ * `instructions`, after the advance
+ */
+export function buildDurableNonceTransaction(
+  nonce: DurableNonce,
+  feePayer: PublicKey,
+  instructions: TransactionInstruction[]
+): Transaction {
+  const nonceInstruction = SystemProgram.nonceAdvance({
+    noncePubkey: nonce.nonceAccount,
+    authorizedPubkey: nonce.authority,
+  });
+
+  const tx = new Transaction();
+  tx.feePayer = feePayer;
+  tx.recentBlockhash = nonce.nonce;
+  tx.nonceInfo = { nonce: nonce.nonce, nonceInstruction };
+  tx.add(nonceInstruction, ...instructions);
+  return tx;
+}
+
+/**
+ * Whether `tx` starts with a nonce advance, i.e. will not expire with
+ * its blockhash
+ */
+export function isDurableNonceTransaction(tx: Transaction): boolean {
+  const first = tx.instructions[0];
+  if (!first || !first.programId.equals(SystemProgram.programId)) return false;
+  try {
+    return SystemInstruction.decodeInstructionType(first) === 'AdvanceNonceAccount';
+  } catch {
+    return false;
+  }
+}
+
+// ============================================================================
+// OFFLINE SIGNING
+// ============================================================================
+
+/**
+ * Add `signers`' signatures to a durable-nonce transaction without any
+ * network access. Refuses blockhash transactions, which would likely
+ * expire before an offline round trip completes
+ * @returns `tx`, for chaining
+ */
+export function signOffline(tx: Transaction, signers: Signer[]): Transaction {
+  if (!isDurableNonceTransaction(tx)) {
+    throw new Error('offline signing needs a durable-nonce transaction');
+  }
+  tx.partialSign(...signers);
+  return tx;
+}
+
+/**
+ * Base64 wire form of a partially signed transaction, for moving it to
+ * and from an offline signer
+ */
+export function exportOfflineTransaction(tx: Transaction): string {
+  return tx.serialize({ requireAllSignatures: false, verifySignatures: false }).toString('base64');
+}
+
+/**
+ * Transaction from `exportOfflineTransaction`'s output
+ */
+export function importOfflineTransaction(encoded: string): Transaction {
+  return Transaction.from(Buffer.from(encoded, 'base64'));
+}
+
+/**
+ * Combine copies of one transaction signed by different offline signers.
+ * Every copy must carry the same message; a copy signed over anything
+ * else (another nonce, another instruction) is rejected
+ */
+export function mergeOfflineSignatures(copies: Transaction[]): Transaction {
+  if (copies.length === 0) throw new Error('no transactions to merge');
+
+  const [merged, ...rest] = copies;
+  const message = merged.serializeMessage();
+  for (const copy of rest) {
+    if (!copy.serializeMessage().equals(message)) {
+      throw new Error('signed copies do not share one message');
+    }
+    for (const { publicKey, signature } of copy.signatures) {
+      if (signature) merged.addSignature(publicKey, signature);
+    }
+  }
+  return merged;
+}
+
+// ============================================================================
+// INSTRUCTIONS
+// ============================================================================
+
+/**
+ * Anchor instruction discriminator: sha256("global:" + name)[..8]
+ */
+export async function computeInstructionDiscriminator(name: string): Promise<Uint8Array> {
+  return (await sha256(new TextEncoder().encode(`global:${name}`))).slice(0, 8);
+}
+
+/**
+ * PDA Anchor's `emit_cpi!` signs event self-CPIs with
+ */
+export function getEventAuthorityPDA(programId: PublicKey = VEIL_PROGRAM_ID): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from(EVENT_AUTHORITY_SEED)], programId);
+}
+
+/**
+ * `stealth_sign_offline` instruction, for a cold signer's durable-nonce
+ * transaction
+ */
+export async function buildStealthSignOfflineInstruction(
+  params: StealthSignOfflineParams,
+  programId: PublicKey = VEIL_PROGRAM_ID
+): Promise<TransactionInstruction> {
+  const proofLen = params.signerProof.length;
+  const data = Buffer.alloc(8 + 4 + proofLen + 32 + 32 + 8 + 32);
+  data.set(await computeInstructionDiscriminator('stealth_sign_offline'), 0);
+  data.writeUInt32LE(proofLen, 8);
+  data.set(params.signerProof, 12);
+  let offset = 12 + proofLen;
+  data.set(params.approvalCommitment, offset);
+  offset += 32;
+  data.set(params.instructionHash, offset);
+  offset += 32;
+  data.writeBigInt64LE(params.validUntil, offset);
+  offset += 8;
+  data.set(params.circuitId, offset);
+
+  return new TransactionInstruction({
+    programId,
+    keys: [
+      { pubkey: params.multisig, isSigner: false, isWritable: false },
+      { pubkey: params.proposal, isSigner: false, isWritable: true },
+      { pubkey: params.signer, isSigner: true, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: params.circuitRegistry, isSigner: false, isWritable: false },
+      { pubkey: getEventAuthorityPDA(programId)[0], isSigner: false, isWritable: false },
+      { pubkey: programId, isSigner: false, isWritable: false },
+    ],
+    data,
+  });
+}
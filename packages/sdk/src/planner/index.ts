@@ -0,0 +1,314 @@
+/**
+ * Veil Transaction Planner Module
+ *
+ * Flows like "unstake, wait for the lockup, withdraw, unshield" span
+ * several transactions over hours or days. `createPlan` turns goals into
+ * an ordered list of steps, each with the steps it waits for and the time
+ * it may run from. The same goals always give the same plan and step ids,
+ * so an interrupted flow can be matched with its saved plan.
+ *
+ * `runPlan` sends every step that is ready. It saves the plan after each
+ * send and before waiting for confirmation. On resume, a step that was
+ * already sent is checked by its signature instead of being sent again, so
+ * a crash between sending and confirming never spends a note twice.
+ *
+ * @example
+ * ```typescript
+ * import { createPlan, runPlan, FilePlanStore } from '@veil-protocol/sdk/planner';
+ *
+ * const store = new FilePlanStore('./veil-plans');
+ * const fresh = await createPlan([
+ *   { kind: 'unstake', stakeCommitment, unlockAt, claimRewards: true },
+ *   { kind: 'unshield', recipient, amount: 2_000_000_000n, notes },
+ * ]);
+ * // Same goals, same id: pick up where an earlier run stopped
+ * const plan = (await store.load(fresh.id)) ?? fresh;
+ * const { done, waitUntil } = await runPlan(plan, executor, store);
+ * if (!done && waitUntil) scheduleResume(plan.id, waitUntil);
+ * ```
+ */
+
+import { Connection } from '@solana/web3.js';
+import { bytesToHex, sha256 } from '../crypto';
+import { selectNotes, SelectableNote, SelectionStrategy } from '../coinselect';
+
+/** Version of the saved plan format */
+export const PLAN_FORMAT_VERSION = 1;
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/** A note the planner may spend; `commitment` is hex */
+export interface PlannableNote extends SelectableNote {
+  commitment: string;
+}
+
+export type PlanGoal =
+  /** Claim rewards (optionally) and unstake once the lockup ends */
+  | { kind: 'unstake'; stakeCommitment: string; unlockAt: number; claimRewards?: boolean }
+  /** Deposit `amount` lamports into a shielded pool */
+  | { kind: 'shield'; pool: string; amount: bigint }
+  /** Withdraw `amount` lamports to `recipient`, waiting for locked notes to unlock */
+  | { kind: 'unshield'; recipient: string; amount: bigint; notes: PlannableNote[]; strategy?: SelectionStrategy };
+
+export type PlanAction =
+  | { type: 'claim-rewards'; stakeCommitment: string }
+  | { type: 'unstake'; stakeCommitment: string }
+  | { type: 'shield-deposit'; pool: string; amount: bigint }
+  | { type: 'shield-withdraw'; pool: string; noteCommitment: string; amount: bigint; recipient: string };
+
+export type StepStatus = 'pending' | 'submitted' | 'confirmed' | 'failed';
+
+export interface PlanStep {
+  /** `<plan id>:<index>` */
+  id: string;
+  action: PlanAction;
+  /** Steps that must be confirmed first */
+  after: string[];
+  /** Unix time (seconds) the step may run from, e.g. a lockup end */
+  notBefore?: number;
+  status: StepStatus;
+  /** Transaction signature once submitted */
+  signature?: string;
+  error?: string;
+}
+
+export interface Plan {
+  version: number;
+  /** Hash of the goals */
+  id: string;
+  goals: PlanGoal[];
+  steps: PlanStep[];
+}
+
+export type SignatureStatus = 'pending' | 'confirmed' | 'failed';
+
+/** Sends plan steps and reports on sent transactions */
+export interface StepExecutor {
+  /** Build, sign and send the step's transaction; returns its signature */
+  execute(step: PlanStep, plan: Plan): Promise<string>;
+  status(signature: string): Promise<SignatureStatus>;
+}
+
+/** Where plans live between runs */
+export interface PlanStore {
+  load(id: string): Promise<Plan | null>;
+  save(plan: Plan): Promise<void>;
+  remove(id: string): Promise<void>;
+}
+
+/** Where `runPlan` stopped; run it again later unless `done` or `failed` */
+export interface RunResult {
+  /** Every step is confirmed */
+  done: boolean;
+  /** Earliest time a waiting step may run, when nothing else can */
+  waitUntil?: number;
+  /** The step that failed, which stops the plan */
+  failed?: PlanStep;
+}
+
+// ============================================================================
+// PLANNING
+// ============================================================================
+
+/**
+ * Plan the steps for `goals`, in order. Each goal starts after the last
+ * step of the goal before it. Throws if an unshield goal cannot be covered
+ */
+export async function createPlan(goals: PlanGoal[]): Promise<Plan> {
+  const id = bytesToHex(await sha256(new TextEncoder().encode(toJson(goals)))).slice(0, 32);
+  const steps: PlanStep[] = [];
+  let previous: string[] = [];
+
+  const add = (action: PlanAction, after: string[], notBefore?: number): string => {
+    const step: PlanStep = { id: `${id}:${steps.length}`, action, after, status: 'pending' };
+    if (notBefore !== undefined) step.notBefore = notBefore;
+    steps.push(step);
+    return step.id;
+  };
+
+  for (const goal of goals) {
+    switch (goal.kind) {
+      case 'unstake': {
+        const after = goal.claimRewards
+          ? [add({ type: 'claim-rewards', stakeCommitment: goal.stakeCommitment }, previous)]
+          : previous;
+        previous = [add({ type: 'unstake', stakeCommitment: goal.stakeCommitment }, after, goal.unlockAt)];
+        break;
+      }
+      case 'shield':
+        previous = [add({ type: 'shield-deposit', pool: goal.pool, amount: goal.amount }, previous)];
+        break;
+      case 'unshield': {
+        // Locked notes are fine: their withdrawals wait for the unlock
+        const { selected, change } = selectNotes(goal.notes, goal.amount, { strategy: goal.strategy });
+        // Withdrawals of one goal are independent; the change comes off the last note
+        previous = selected.map((note, i) =>
+          add(
+            {
+              type: 'shield-withdraw',
+              pool: note.pool ?? '',
+              noteCommitment: note.commitment,
+              amount: i === selected.length - 1 ? note.value - change : note.value,
+              recipient: goal.recipient,
+            },
+            previous,
+            note.unlockAt
+          )
+        );
+        break;
+      }
+    }
+  }
+
+  return { version: PLAN_FORMAT_VERSION, id, goals, steps };
+}
+
+/**
+ * Steps that can be sent at `now`, and the earliest time a step still
+ * waiting on its `notBefore` may run
+ */
+export function readySteps(plan: Plan, now: number): { ready: PlanStep[]; waitUntil?: number } {
+  const confirmed = new Set(plan.steps.filter((step) => step.status === 'confirmed').map((step) => step.id));
+  const ready: PlanStep[] = [];
+  let waitUntil: number | undefined;
+
+  for (const step of plan.steps) {
+    if (step.status !== 'pending' || !step.after.every((id) => confirmed.has(id))) continue;
+    if (step.notBefore !== undefined && step.notBefore > now) {
+      waitUntil = Math.min(waitUntil ?? step.notBefore, step.notBefore);
+    } else {
+      ready.push(step);
+    }
+  }
+  return { ready, waitUntil };
+}
+
+// ============================================================================
+// EXECUTION
+// ============================================================================
+
+/**
+ * Advance `plan` as far as it can go now: settle steps sent in an earlier
+ * run, then send ready steps one at a time, saving after every change.
+ * Returns while a sent transaction is unconfirmed; a failed step stops
+ * the plan
+ */
+export async function runPlan(
+  plan: Plan,
+  executor: StepExecutor,
+  store: PlanStore,
+  now: () => number = () => Math.floor(Date.now() / 1000)
+): Promise<RunResult> {
+  for (;;) {
+    for (const step of plan.steps.filter((s) => s.status === 'submitted')) {
+      const status = await executor.status(step.signature!);
+      if (status === 'pending') return { done: false };
+      step.status = status;
+      if (status === 'failed') step.error = 'transaction failed';
+      await store.save(plan);
+    }
+
+    const failed = plan.steps.find((step) => step.status === 'failed');
+    if (failed) return { done: false, failed };
+    if (plan.steps.every((step) => step.status === 'confirmed')) return { done: true };
+
+    const { ready, waitUntil } = readySteps(plan, now());
+    if (ready.length === 0) return { done: false, waitUntil };
+
+    const step = ready[0];
+    try {
+      step.signature = await executor.execute(step, plan);
+      step.status = 'submitted';
+    } catch (error) {
+      step.status = 'failed';
+      step.error = error instanceof Error ? error.message : String(error);
+    }
+    await store.save(plan);
+  }
+}
+
+/** `StepExecutor.status` from a connection's signature statuses */
+export async function signatureStatus(connection: Connection, signature: string): Promise<SignatureStatus> {
+  const { value } = await connection.getSignatureStatuses([signature], { searchTransactionHistory: true });
+  const status = value[0];
+  if (!status) return 'pending';
+  if (status.err) return 'failed';
+  return status.confirmationStatus === 'confirmed' || status.confirmationStatus === 'finalized'
+    ? 'confirmed'
+    : 'pending';
+}
+
+// ============================================================================
+// PERSISTENCE
+// ============================================================================
+
+/** JSON with bigints kept exact */
+function toJson(value: unknown): string {
+  return JSON.stringify(value, (_key, item) => (typeof item === 'bigint' ? { $bigint: item.toString() } : item));
+}
+
+export function serializePlan(plan: Plan): string {
+  return toJson(plan);
+}
+
+export function deserializePlan(json: string): Plan {
+  const plan = JSON.parse(json, (_key, item) =>
+    item && typeof item === 'object' && typeof item.$bigint === 'string' ? BigInt(item.$bigint) : item
+  ) as Plan;
+  if (plan.version !== PLAN_FORMAT_VERSION) throw new Error(`unsupported plan version ${plan.version}`);
+  return plan;
+}
+
+export class MemoryPlanStore implements PlanStore {
+  private plans = new Map<string, string>();
+
+  async load(id: string): Promise<Plan | null> {
+    const json = this.plans.get(id);
+    return json ? deserializePlan(json) : null;
+  }
+
+  async save(plan: Plan): Promise<void> {
+    this.plans.set(plan.id, serializePlan(plan));
+  }
+
+  async remove(id: string): Promise<void> {
+    this.plans.delete(id);
+  }
+}
+
+/**
+ * One JSON file per plan in `directory` (Node only). Saves write a
+ * temporary file and rename it, so a crash never leaves half a plan
+ */
+export class FilePlanStore implements PlanStore {
+  constructor(private directory: string) {}
+
+  async load(id: string): Promise<Plan | null> {
+    const fs = await import('fs/promises');
+    try {
+      return deserializePlan(await fs.readFile(this.path(id), 'utf8'));
+    } catch (error) {
+      if ((error as { code?: string }).code === 'ENOENT') return null;
+      throw error;
+    }
+  }
+
+  async save(plan: Plan): Promise<void> {
+    const fs = await import('fs/promises');
+    await fs.mkdir(this.directory, { recursive: true });
+    const temporary = `${this.path(plan.id)}.tmp`;
+    await fs.writeFile(temporary, serializePlan(plan));
+    await fs.rename(temporary, this.path(plan.id));
+  }
+
+  async remove(id: string): Promise<void> {
+    const fs = await import('fs/promises');
+    await fs.rm(this.path(id), { force: true });
+  }
+
+  private path(id: string): string {
+    return `${this.directory}/${id}.json`;
+  }
+}
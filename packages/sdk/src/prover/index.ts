@@ -0,0 +1,299 @@
+/**
+ * Veil Background Prover Module
+ *
+ * Client-side Groth16 proving takes seconds, long enough to freeze a
+ * wallet UI if it runs on the main thread. `BackgroundProver` queues proof
+ * jobs on a backend, reports progress through callbacks and can cancel a
+ * job while it waits or runs:
+ *
+ * - `WorkerPoolBackend`: proves on a pool of web workers (or Node
+ *   `worker_threads` behind the same interface); the worker script calls
+ *   `serveProofRequests` with its prover, e.g. snarkjs with the circuit's
+ *   wasm and zkey
+ * - `InlineBackend`: proves on the calling thread, for tests and
+ *   environments without workers
+ *
+ * A running WASM prover cannot be interrupted, so cancelling a job on a
+ * worker terminates that worker; the pool starts a fresh one for the next
+ * job.
+ *
+ * @example
+ * ```typescript
+ * import { BackgroundProver, WorkerPoolBackend } from '@veil-protocol/sdk/prover';
+ *
+ * const prover = new BackgroundProver(
+ *   new WorkerPoolBackend(() => new Worker(new URL('./prove.worker.js', import.meta.url)), 2)
+ * );
+ * const task = prover.prove(
+ *   { circuit: 'withdrawal', inputs },
+ *   { onProgress: ({ stage, fraction }) => setProgress(stage, fraction) }
+ * );
+ * cancelButton.onclick = () => task.cancel();
+ * const { proof, publicSignals } = await task.result;
+ * ```
+ */
+
+import { ZKProof } from '../types';
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+export type ProofStage = 'queued' | 'witness' | 'proving' | 'done';
+
+export interface ProofProgress {
+  stage: ProofStage;
+  /** Overall completion, 0 to 1 */
+  fraction: number;
+}
+
+export interface ProofJob {
+  /** Circuit name, as in `Circuit::name` (e.g. "withdrawal") */
+  circuit: string;
+  /** Witness inputs for the circuit */
+  inputs: Record<string, unknown>;
+}
+
+export interface ProofOutput {
+  proof: ZKProof;
+  publicSignals: string[];
+}
+
+export interface ProveOptions {
+  onProgress?: (progress: ProofProgress) => void;
+  /** Cancels the job when aborted, like `ProofTask.cancel` */
+  signal?: AbortSignal;
+}
+
+export interface ProofTask {
+  /** Resolves with the proof, or rejects with `ProofCancelledError` */
+  result: Promise<ProofOutput>;
+  cancel(): void;
+}
+
+/** Where proofs run; one `run` call per job */
+export interface ProverBackend {
+  /** Jobs the backend can prove at the same time */
+  readonly concurrency: number;
+  run(job: ProofJob, onProgress: (progress: ProofProgress) => void, signal: AbortSignal): Promise<ProofOutput>;
+  /** Release workers */
+  close?(): void;
+}
+
+/** Prover on the calling thread; `report` may be called any number of times */
+export type ProveFunction = (
+  job: ProofJob,
+  report: (progress: ProofProgress) => void
+) => Promise<ProofOutput>;
+
+export class ProofCancelledError extends Error {
+  constructor() {
+    super('proof generation cancelled');
+    this.name = 'ProofCancelledError';
+  }
+}
+
+// ============================================================================
+// PROVER
+// ============================================================================
+
+interface QueuedJob {
+  job: ProofJob;
+  onProgress: (progress: ProofProgress) => void;
+  controller: AbortController;
+  resolve: (output: ProofOutput) => void;
+  reject: (error: unknown) => void;
+}
+
+/**
+ * Queue of proof jobs run on a backend, at most `backend.concurrency` at
+ * a time, in submission order
+ */
+export class BackgroundProver {
+  private queue: QueuedJob[] = [];
+  private running = 0;
+
+  constructor(private backend: ProverBackend) {}
+
+  /** Submit a job; progress starts at `queued` */
+  prove(job: ProofJob, options: ProveOptions = {}): ProofTask {
+    const controller = new AbortController();
+    const onProgress = options.onProgress ?? (() => {});
+
+    const result = new Promise<ProofOutput>((resolve, reject) => {
+      const queued: QueuedJob = { job, onProgress, controller, resolve, reject };
+      controller.signal.addEventListener('abort', () => {
+        const index = this.queue.indexOf(queued);
+        if (index >= 0) {
+          this.queue.splice(index, 1);
+          reject(new ProofCancelledError());
+        }
+      });
+      this.queue.push(queued);
+      onProgress({ stage: 'queued', fraction: 0 });
+      this.drain();
+    });
+
+    if (options.signal) {
+      if (options.signal.aborted) controller.abort();
+      else options.signal.addEventListener('abort', () => controller.abort());
+    }
+
+    return { result, cancel: () => controller.abort() };
+  }
+
+  /** Jobs waiting or running */
+  pending(): number {
+    return this.queue.length + this.running;
+  }
+
+  /** Cancel every job and release the backend */
+  close(): void {
+    for (const queued of [...this.queue]) queued.controller.abort();
+    this.backend.close?.();
+  }
+
+  private drain(): void {
+    while (this.running < this.backend.concurrency && this.queue.length > 0) {
+      const queued = this.queue.shift()!;
+      this.running++;
+      this.backend
+        .run(queued.job, queued.onProgress, queued.controller.signal)
+        .then((output) => {
+          queued.onProgress({ stage: 'done', fraction: 1 });
+          queued.resolve(output);
+        }, queued.reject)
+        .finally(() => {
+          this.running--;
+          this.drain();
+        });
+    }
+  }
+}
+
+// ============================================================================
+// BACKENDS
+// ============================================================================
+
+/**
+ * Proves on the calling thread, one job at a time. Cancellation takes
+ * effect at the prover's next `report`
+ */
+export class InlineBackend implements ProverBackend {
+  readonly concurrency = 1;
+
+  constructor(private proveFn: ProveFunction) {}
+
+  async run(job: ProofJob, onProgress: (progress: ProofProgress) => void, signal: AbortSignal): Promise<ProofOutput> {
+    const report = (progress: ProofProgress) => {
+      if (signal.aborted) throw new ProofCancelledError();
+      onProgress(progress);
+    };
+    if (signal.aborted) throw new ProofCancelledError();
+    const output = await this.proveFn(job, report);
+    if (signal.aborted) throw new ProofCancelledError();
+    return output;
+  }
+}
+
+/** Messages from the pool to a worker */
+export type ProverRequest = { type: 'prove'; id: number; job: ProofJob };
+
+/** Messages from a worker to the pool */
+export type ProverResponse =
+  | { type: 'progress'; id: number; progress: ProofProgress }
+  | { type: 'result'; id: number; output: ProofOutput }
+  | { type: 'error'; id: number; message: string };
+
+/** The part of a web worker the pool uses */
+export interface ProverWorker {
+  postMessage(message: ProverRequest): void;
+  onmessage: ((event: { data: ProverResponse }) => void) | null;
+  terminate(): void;
+}
+
+/**
+ * Proves on up to `size` workers made by `createWorker`, started on
+ * first use and reused between jobs
+ */
+export class WorkerPoolBackend implements ProverBackend {
+  readonly concurrency: number;
+  private idle: ProverWorker[] = [];
+  private nextId = 1;
+
+  constructor(private createWorker: () => ProverWorker, size = 2) {
+    this.concurrency = Math.max(1, size);
+  }
+
+  run(job: ProofJob, onProgress: (progress: ProofProgress) => void, signal: AbortSignal): Promise<ProofOutput> {
+    if (signal.aborted) return Promise.reject(new ProofCancelledError());
+
+    const worker = this.idle.pop() ?? this.createWorker();
+    const id = this.nextId++;
+
+    return new Promise<ProofOutput>((resolve, reject) => {
+      const finish = (keepWorker: boolean) => {
+        worker.onmessage = null;
+        signal.removeEventListener('abort', onAbort);
+        if (keepWorker) this.idle.push(worker);
+        else worker.terminate();
+      };
+      const onAbort = () => {
+        finish(false);
+        reject(new ProofCancelledError());
+      };
+
+      worker.onmessage = ({ data }) => {
+        if (data.id !== id) return;
+        if (data.type === 'progress') {
+          onProgress(data.progress);
+        } else if (data.type === 'result') {
+          finish(true);
+          resolve(data.output);
+        } else {
+          finish(false);
+          reject(new Error(data.message));
+        }
+      };
+      signal.addEventListener('abort', onAbort);
+      worker.postMessage({ type: 'prove', id, job });
+    });
+  }
+
+  close(): void {
+    for (const worker of this.idle) worker.terminate();
+    this.idle = [];
+  }
+}
+
+/** The part of a worker's global scope `serveProofRequests` uses */
+export interface ProverWorkerScope {
+  postMessage(message: ProverResponse): void;
+  onmessage: ((event: { data: ProverRequest }) => void) | null;
+}
+
+/**
+ * Answer the pool's requests from inside a worker script with `proveFn`
+ *
+ * @example
+ * ```typescript
+ * // prove.worker.ts
+ * serveProofRequests(self, async (job, report) => {
+ *   report({ stage: 'witness', fraction: 0.1 });
+ *   const { proof, publicSignals } = await snarkjs.groth16.fullProve(job.inputs, wasm, zkey);
+ *   return { proof, publicSignals };
+ * });
+ * ```
+ */
+export function serveProofRequests(scope: ProverWorkerScope, proveFn: ProveFunction): void {
+  scope.onmessage = async ({ data }) => {
+    if (data.type !== 'prove') return;
+    const { id, job } = data;
+    try {
+      const output = await proveFn(job, (progress) => scope.postMessage({ type: 'progress', id, progress }));
+      scope.postMessage({ type: 'result', id, output });
+    } catch (error) {
+      scope.postMessage({ type: 'error', id, message: error instanceof Error ? error.message : String(error) });
+    }
+  };
+}
@@ -9,7 +9,12 @@
 
 import { Keypair, PublicKey } from '@solana/web3.js';
 import { ShamirShare, RecoveryConfig, RecoveryKey, RecoveryMethod, ProofData } from '../types';
-import { sha256String, poseidonHash, bytesToBigInt, bytesToHex, randomBytes } from '../crypto';
+import { sha256, sha256String, poseidonHash, bytesToBigInt, bytesToHex, randomBytes } from '../crypto';
+
+const VEIL_PROGRAM_ID = new PublicKey('5C1VaebPdHZYETnTL18cLJK2RexXmVVhkkYpnYHD5P4h');
+
+/** PDA seed of anchored backup versions (pda.rs WALLET_BACKUP_SEED) */
+export const WALLET_BACKUP_SEED = 'wallet_backup';
 
 // ============================================================================
 // SHAMIR'S SECRET SHARING
@@ -181,6 +186,46 @@ export async function initiateRecovery(
   }
 }
 
+// ============================================================================
+// BACKUP ANCHORING
+// ============================================================================
+
+/**
+ * Hashes `register_backup` anchors for an encrypted backup blob stored at
+ * `uri`. Hash the ciphertext, not the plaintext, so the anchor reveals
+ * nothing about the notes and keys inside
+ */
+export async function computeBackupHashes(
+  encryptedBlob: Uint8Array,
+  uri: string
+): Promise<{ backupHash: Uint8Array; uriHash: Uint8Array }> {
+  return {
+    backupHash: await sha256(encryptedBlob),
+    uriHash: await sha256String(uri),
+  };
+}
+
+/**
+ * Whether a restored blob is the backup version anchored with
+ * `expectedHash` (a `WalletBackup` record's `backup_hash`)
+ */
+export async function verifyRestoredBackup(encryptedBlob: Uint8Array, expectedHash: Uint8Array): Promise<boolean> {
+  return bytesToHex(await sha256(encryptedBlob)) === bytesToHex(expectedHash);
+}
+
+/**
+ * Version `backupVersion` (counting from 1) of `wallet`'s anchored backup
+ */
+export function getWalletBackupPDA(
+  wallet: PublicKey,
+  backupVersion: bigint,
+  programId: PublicKey = VEIL_PROGRAM_ID
+): [PublicKey, number] {
+  const version = Buffer.alloc(8);
+  version.writeBigUInt64LE(backupVersion);
+  return PublicKey.findProgramAddressSync([Buffer.from(WALLET_BACKUP_SEED), wallet.toBuffer(), version], programId);
+}
+
 // ============================================================================
 // UTILITY FUNCTIONS
 // ============================================================================
@@ -0,0 +1,339 @@
+/**
+ * Veil Remote Proving Module
+ *
+ * Lets a low-power device hand proof generation to a remote prover
+ * without showing it the witness. The prover runs inside a trusted
+ * enclave and publishes an attestation: a short-lived X25519 key bound
+ * to the enclave's code measurement, signed by a key the wallet trusts
+ * (the enclave vendor's quote, or an operator key checked against it).
+ *
+ * 1. `GET {url}/attestation` returns a `ProverAttestation`
+ * 2. The client checks the signature, measurement and expiry, then seals
+ *    the job (circuit and witness) to the enclave key with a fresh
+ *    ephemeral key (NaCl box)
+ * 3. `POST {url}/prove` carries the `SealedProofRequest`; the enclave
+ *    opens it, proves and seals the `ProofOutput` back to the ephemeral
+ *    key, so the operator sees neither the witness nor the nullifier in
+ *    the public signals
+ *
+ * Splitting the witness into additive shares across several provers does
+ * not work on its own for Groth16: the quotient polynomial is quadratic in
+ * the witness, so the provers would have to run an MPC protocol together.
+ * Attested enclaves are what this protocol supports.
+ *
+ * `RemoteProverServer` is the reference server to run inside the enclave;
+ * it is transport-agnostic, so any HTTP framework can route the two
+ * endpoints to `attestation()` and `handle()`.
+ *
+ * @example
+ * ```typescript
+ * import { BackgroundProver } from '@veil-protocol/sdk/prover';
+ * import { RemoteProverClient, SignedAttestationVerifier } from '@veil-protocol/sdk/remote';
+ *
+ * const client = new RemoteProverClient('https://prover.example.com', new SignedAttestationVerifier(
+ *   [operatorKey],
+ *   [expectedMeasurement]
+ * ));
+ * const prover = new BackgroundProver(client.backend(2));
+ * const { proof, publicSignals } = await prover.prove({ circuit: 'withdrawal', inputs }).result;
+ * ```
+ */
+
+import nacl from 'tweetnacl';
+import { bigIntToBytes, bytesToHex, hashDomain, hexToBytes } from '../crypto';
+import { ProofCancelledError, ProofJob, ProofOutput, ProofProgress, ProveFunction, ProverBackend } from '../prover';
+
+/** Version of the sealed request and response format */
+export const REMOTE_PROVER_PROTOCOL_VERSION = 1;
+
+/** Default lifetime of an enclave key, in seconds */
+export const DEFAULT_ATTESTATION_TTL = 3600;
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/** An enclave key bound to the code it runs; byte fields are hex */
+export interface ProverAttestation {
+  version: number;
+  /** Hash of the enclave image (e.g. SGX MRENCLAVE, Nitro PCR0) */
+  measurement: string;
+  /** X25519 key witnesses are sealed to; its secret never leaves the enclave */
+  enclaveKey: string;
+  /** Ed25519 key that signed the attestation */
+  signer: string;
+  /** Unix time (seconds) */
+  issuedAt: number;
+  expiresAt: number;
+  signature: string;
+}
+
+/** Body of `POST /prove`; byte fields are hex */
+export interface SealedProofRequest {
+  version: number;
+  /** The attestation's enclave key, so a rotated enclave can refuse it */
+  enclaveKey: string;
+  /** Client key for this request only, which the response is sealed to */
+  ephemeralKey: string;
+  nonce: string;
+  /** NaCl box of the JSON `ProofJob` */
+  ciphertext: string;
+}
+
+/** Body of the `/prove` response; byte fields are hex */
+export interface SealedProofResponse {
+  nonce: string;
+  /** NaCl box of the JSON `ProofOutput` */
+  ciphertext: string;
+}
+
+/** Decides whether an attestation comes from an enclave the wallet trusts */
+export interface AttestationVerifier {
+  verify(attestation: ProverAttestation, now: number): Promise<boolean>;
+}
+
+export interface RemoteProverServerOptions {
+  /** Ed25519 key signing attestations */
+  signer: nacl.SignKeyPair;
+  /** Hex measurement of the running enclave image */
+  measurement: string;
+  /** Proves opened jobs, e.g. snarkjs inside the enclave */
+  proveFn: ProveFunction;
+  /** Enclave key lifetime in seconds; default `DEFAULT_ATTESTATION_TTL` */
+  ttl?: number;
+  /** Current unix time (seconds); defaults to the system clock */
+  now?: () => number;
+}
+
+// ============================================================================
+// ATTESTATION
+// ============================================================================
+
+/** Digest an attestation signature covers */
+export async function attestationDigest(
+  attestation: Omit<ProverAttestation, 'signature'>
+): Promise<Uint8Array> {
+  return hashDomain(
+    'veil-remote-prover-attestation',
+    new Uint8Array([attestation.version]),
+    hexToBytes(attestation.measurement),
+    hexToBytes(attestation.enclaveKey),
+    hexToBytes(attestation.signer),
+    bigIntToBytes(BigInt(attestation.issuedAt), 8),
+    bigIntToBytes(BigInt(attestation.expiresAt), 8)
+  );
+}
+
+/**
+ * Accepts attestations signed by one of `trustedSigners` for one of
+ * `measurements` (all hex), and not yet expired
+ */
+export class SignedAttestationVerifier implements AttestationVerifier {
+  constructor(private trustedSigners: string[], private measurements: string[]) {}
+
+  async verify(attestation: ProverAttestation, now: number): Promise<boolean> {
+    if (attestation.version !== REMOTE_PROVER_PROTOCOL_VERSION) return false;
+    if (!this.trustedSigners.includes(attestation.signer)) return false;
+    if (!this.measurements.includes(attestation.measurement)) return false;
+    if (now < attestation.issuedAt || now >= attestation.expiresAt) return false;
+
+    const digest = await attestationDigest(attestation);
+    return nacl.sign.detached.verify(digest, hexToBytes(attestation.signature), hexToBytes(attestation.signer));
+  }
+}
+
+// ============================================================================
+// SEALING
+// ============================================================================
+
+function encodeJson(value: unknown): Uint8Array {
+  return new TextEncoder().encode(
+    JSON.stringify(value, (_key, item) => (typeof item === 'bigint' ? item.toString() : item))
+  );
+}
+
+function decodeJson<T>(bytes: Uint8Array): T {
+  return JSON.parse(new TextDecoder().decode(bytes)) as T;
+}
+
+/**
+ * Seal `job` to an attested enclave key. Returns the request and the
+ * ephemeral secret needed to open the response
+ */
+export function sealProofRequest(
+  job: ProofJob,
+  attestation: ProverAttestation
+): { request: SealedProofRequest; ephemeralSecret: Uint8Array } {
+  const ephemeral = nacl.box.keyPair();
+  const nonce = nacl.randomBytes(nacl.box.nonceLength);
+  const ciphertext = nacl.box(encodeJson(job), nonce, hexToBytes(attestation.enclaveKey), ephemeral.secretKey);
+
+  return {
+    request: {
+      version: REMOTE_PROVER_PROTOCOL_VERSION,
+      enclaveKey: attestation.enclaveKey,
+      ephemeralKey: bytesToHex(ephemeral.publicKey),
+      nonce: bytesToHex(nonce),
+      ciphertext: bytesToHex(ciphertext),
+    },
+    ephemeralSecret: ephemeral.secretKey,
+  };
+}
+
+/** Open the enclave's response to a request sealed with `ephemeralSecret` */
+export function openProofResponse(
+  response: SealedProofResponse,
+  attestation: ProverAttestation,
+  ephemeralSecret: Uint8Array
+): ProofOutput {
+  const plaintext = nacl.box.open(
+    hexToBytes(response.ciphertext),
+    hexToBytes(response.nonce),
+    hexToBytes(attestation.enclaveKey),
+    ephemeralSecret
+  );
+  if (!plaintext) throw new Error('remote prover response does not open with the attested key');
+  return decodeJson<ProofOutput>(plaintext);
+}
+
+// ============================================================================
+// CLIENT
+// ============================================================================
+
+/** Talks to a remote prover at `url` over HTTP */
+export class RemoteProverClient {
+  private attestation: ProverAttestation | null = null;
+
+  constructor(
+    private url: string,
+    private verifier: AttestationVerifier,
+    private now: () => number = () => Math.floor(Date.now() / 1000)
+  ) {}
+
+  /** Fetch and verify the prover's attestation, reusing it until it expires */
+  async attest(signal?: AbortSignal): Promise<ProverAttestation> {
+    if (this.attestation && this.now() < this.attestation.expiresAt) return this.attestation;
+
+    const response = await fetch(`${this.url}/attestation`, { signal });
+    if (!response.ok) throw new Error(`remote prover attestation failed: ${response.status}`);
+    const attestation = (await response.json()) as ProverAttestation;
+    if (!(await this.verifier.verify(attestation, this.now()))) {
+      throw new Error('remote prover attestation is not trusted');
+    }
+    this.attestation = attestation;
+    return attestation;
+  }
+
+  /** Prove `job` remotely; the witness only leaves the device sealed */
+  async prove(job: ProofJob, signal?: AbortSignal): Promise<ProofOutput> {
+    const attestation = await this.attest(signal);
+    const { request, ephemeralSecret } = sealProofRequest(job, attestation);
+
+    const response = await fetch(`${this.url}/prove`, {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify(request),
+      signal,
+    });
+    if (response.status === 409) {
+      // The enclave rotated its key; attest again on the next call
+      this.attestation = null;
+    }
+    if (!response.ok) throw new Error(`remote prover failed: ${response.status}`);
+
+    return openProofResponse((await response.json()) as SealedProofResponse, attestation, ephemeralSecret);
+  }
+
+  /** A `BackgroundProver` backend running up to `concurrency` remote jobs */
+  backend(concurrency = 1): ProverBackend {
+    return {
+      concurrency,
+      run: async (job: ProofJob, onProgress: (progress: ProofProgress) => void, signal: AbortSignal) => {
+        onProgress({ stage: 'proving', fraction: 0.1 });
+        try {
+          return await this.prove(job, signal);
+        } catch (error) {
+          if (signal.aborted) throw new ProofCancelledError();
+          throw error;
+        }
+      },
+    };
+  }
+}
+
+// ============================================================================
+// SERVER (reference)
+// ============================================================================
+
+/** Thrown by `RemoteProverServer.handle`; `status` is the HTTP status to answer with */
+export class RemoteProverError extends Error {
+  constructor(public status: number, message: string) {
+    super(message);
+    this.name = 'RemoteProverError';
+  }
+}
+
+/**
+ * Reference prover to run inside the enclave. The enclave key is
+ * generated in memory and rotated when its attestation expires
+ */
+export class RemoteProverServer {
+  private keys: nacl.BoxKeyPair = nacl.box.keyPair();
+  private current: ProverAttestation | null = null;
+  private ttl: number;
+  private now: () => number;
+
+  constructor(private options: RemoteProverServerOptions) {
+    this.ttl = options.ttl ?? DEFAULT_ATTESTATION_TTL;
+    this.now = options.now ?? (() => Math.floor(Date.now() / 1000));
+  }
+
+  /** Answer `GET /attestation` */
+  async attestation(): Promise<ProverAttestation> {
+    const now = this.now();
+    if (this.current && now < this.current.expiresAt) return this.current;
+    if (this.current) this.keys = nacl.box.keyPair();
+
+    const unsigned = {
+      version: REMOTE_PROVER_PROTOCOL_VERSION,
+      measurement: this.options.measurement,
+      enclaveKey: bytesToHex(this.keys.publicKey),
+      signer: bytesToHex(this.options.signer.publicKey),
+      issuedAt: now,
+      expiresAt: now + this.ttl,
+    };
+    const digest = await attestationDigest(unsigned);
+    this.current = {
+      ...unsigned,
+      signature: bytesToHex(nacl.sign.detached(digest, this.options.signer.secretKey)),
+    };
+    return this.current;
+  }
+
+  /** Answer `POST /prove`; throws `RemoteProverError` for bad requests */
+  async handle(request: SealedProofRequest): Promise<SealedProofResponse> {
+    if (request.version !== REMOTE_PROVER_PROTOCOL_VERSION) {
+      throw new RemoteProverError(400, 'unsupported protocol version');
+    }
+    const current = await this.attestation();
+    if (request.enclaveKey !== current.enclaveKey) {
+      throw new RemoteProverError(409, 'enclave key rotated');
+    }
+
+    const clientKey = hexToBytes(request.ephemeralKey);
+    const plaintext = nacl.box.open(
+      hexToBytes(request.ciphertext),
+      hexToBytes(request.nonce),
+      clientKey,
+      this.keys.secretKey
+    );
+    if (!plaintext) throw new RemoteProverError(400, 'request does not open');
+
+    const output = await this.options.proveFn(decodeJson<ProofJob>(plaintext), () => {});
+    const nonce = nacl.randomBytes(nacl.box.nonceLength);
+    return {
+      nonce: bytesToHex(nonce),
+      ciphertext: bytesToHex(nacl.box(encodeJson(output), nonce, clientKey, this.keys.secretKey)),
+    };
+  }
+}
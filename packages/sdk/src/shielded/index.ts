@@ -9,6 +9,7 @@
  * - shield_deposit: Deposit with Pedersen commitment and Bulletproofs
  * - shield_withdraw: Withdraw with nullifier and ZK proof
  * - claim_shielded_rewards: Claim staking rewards privately
+ * - create_nullifier_filter: Bloom filter of spent nullifiers for pre-checks
  */
 
 import { Connection, PublicKey, Transaction, SystemProgram, LAMPORTS_PER_SOL } from '@solana/web3.js';
@@ -28,8 +29,10 @@ import {
   randomBytes,
   encrypt,
   decrypt,
-  bigIntToBytes
+  bigIntToBytes,
+  hashDomain
 } from '../crypto';
+import { ComputeBudgetOptions, withComputeBudget } from '../compute';
 
 // Veil Protocol Program ID (deployed on-chain)
 export const VEIL_PROGRAM_ID = new PublicKey('5C1VaebPdHZYETnTL18cLJK2RexXmVVhkkYpnYHD5P4h');
@@ -38,11 +41,47 @@ export const VEIL_PROGRAM_ID = new PublicKey('5C1VaebPdHZYETnTL18cLJK2RexXmVVhkk
 export const SHIELDED_POOL_SEED = 'shielded_pool';
 export const SHIELDED_NOTE_SEED = 'shielded_note';
 export const NULLIFIER_SEED = 'nullifier';
+export const NULLIFIER_FILTER_SEED = 'nullifier_filter';
+export const PROTOCOL_STATS_SEED = 'protocol_stats';
+export const HOLDING_SEED = 'holding';
+export const DEPOSIT_CALLER_SEED = 'deposit_caller';
+export const CPI_DEPOSITOR_SEED = 'veil_depositor';
+
+// Most public recipients one `shield_withdraw_split` can pay
+export const MAX_SPLIT_RECIPIENTS = 4;
+
+// How long after a deposit `cancel_deposit` still accepts it (1 hour)
+export const DEPOSIT_CANCEL_WINDOW_SECONDS = 3_600;
+
+// Most notes one `route_deposit` can spread across pools
+export const MAX_ROUTED_NOTES = 8;
+
+// Highest penalty `configure_early_exit` accepts
+export const MAX_EARLY_EXIT_PENALTY_BPS = 5_000;
+
+// Breakpoints `configure_reward_curve` accepts
+export const MAX_REWARD_CURVE_POINTS = 8;
 
 // Merkle tree constants
 export const MERKLE_TREE_DEPTH = 8;
 export const MAX_SHIELDED_NOTES = 256;
 
+// Nullifier filter layout (state/shielded.rs NullifierFilter)
+export const NULLIFIER_FILTER_BYTES = 1024;
+export const NULLIFIER_FILTER_HASHES = 4;
+const DOMAIN_NULLIFIER_FILTER = 'veil:nullifier_filter:v2';
+const DOMAIN_POOL_BINDING = 'veil:pool_binding:v2';
+const DOMAIN_CLAIM_KEY = 'veil:claim_key:v2';
+const DOMAIN_HOLDING = 'veil:holding:v2';
+const DOMAIN_HOLDING_CLAIM = 'veil:holding_claim:v2';
+const DOMAIN_CIRCUIT_ID = 'veil:circuit_id:v2';
+const DOMAIN_SPLIT_WITHDRAWAL = 'veil:split_withdrawal:v2';
+const DOMAIN_DEPOSIT_CANCEL = 'veil:deposit_cancel:v2';
+const DOMAIN_RELAYER_SOLVENCY = 'veil:relayer_solvency:v2';
+const DOMAIN_REWARD_CURVE = 'veil:reward_curve:v2';
+const DOMAIN_EARLY_EXIT = 'veil:early_exit:v2';
+const NULLIFIER_FILTER_BITS_OFFSET = 8 + 32;
+
 // ============================================================================
 // SHIELDED BALANCE CLIENT
 // ============================================================================
@@ -52,10 +91,12 @@ export class ShieldedBalanceClient {
   private encryptionKey: string;
   private balances: Map<string, ShieldedBalance> = new Map();
   private notes: Map<string, ShieldedNote[]> = new Map(); // Track user's notes per pool
+  private computeBudget: ComputeBudgetOptions;
 
-  constructor(connection: Connection, encryptionKey: string) {
+  constructor(connection: Connection, encryptionKey: string, computeBudget: ComputeBudgetOptions = {}) {
     this.connection = connection;
     this.encryptionKey = encryptionKey;
+    this.computeBudget = computeBudget;
   }
 
   // ==========================================================================
@@ -100,7 +141,7 @@ export class ShieldedBalanceClient {
         data: Buffer.from(instructionData),
       };
 
-      const tx = new Transaction().add(ix);
+      const tx = withComputeBudget(new Transaction().add(ix), ['create_shielded_pool'], this.computeBudget);
       tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
       tx.feePayer = creator;
 
@@ -240,7 +281,7 @@ export class ShieldedBalanceClient {
         data: Buffer.from(instructionData),
       };
 
-      const tx = new Transaction().add(ix);
+      const tx = withComputeBudget(new Transaction().add(ix), ['shield_deposit'], this.computeBudget);
       tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
       tx.feePayer = wallet;
 
@@ -275,6 +316,79 @@ export class ShieldedBalanceClient {
     }
   }
 
+  /**
+   * Outputs for `shield_deposit_with_decoys`: the real note plus
+   * `decoyCount` zero-value notes owned by `wallet`, in random order.
+   * `decoyCount` must equal the pool's configured `decoy_outputs`
+   */
+  async buildDecoyDeposit(
+    wallet: PublicKey,
+    amount: number,
+    decoyCount: number
+  ): Promise<{ noteCommitments: Uint8Array[]; encryptedNotes: Uint8Array[]; realIndex: number }> {
+    const ownerCommitment = await this.computeOwnerCommitment(wallet);
+    const amounts = [BigInt(Math.floor(amount * LAMPORTS_PER_SOL)), ...Array(decoyCount).fill(0n)];
+
+    const outputs = await Promise.all(amounts.map(async (value) => {
+      const blindingFactor = randomBytes(32);
+      return {
+        commitment: await this.computeNoteCommitment(value, blindingFactor, ownerCommitment),
+        encryptedNote: this.encryptNote({
+          amount: value,
+          blindingFactor,
+          ownerCommitment,
+          unlockAt: Date.now() + 86400000,
+        }),
+      };
+    }));
+
+    // Fisher-Yates with the real note tracked, so its leaf position is random
+    const order = outputs.map((_, i) => i);
+    for (let i = order.length - 1; i > 0; i--) {
+      const j = new DataView(randomBytes(4).buffer).getUint32(0) % (i + 1);
+      [order[i], order[j]] = [order[j], order[i]];
+    }
+
+    return {
+      noteCommitments: order.map((i) => outputs[i].commitment),
+      encryptedNotes: order.map((i) => outputs[i].encryptedNote),
+      realIndex: order.indexOf(0),
+    };
+  }
+
+  /**
+   * Plan for `route_deposit`: one note per leg, with each pool's legs
+   * moved together in order of first appearance, as the program requires.
+   * Pass the remaining accounts as each pool followed by the note PDAs at
+   * its next note indices
+   */
+  async buildRouteDeposit(
+    wallet: PublicKey,
+    legs: { pool: PublicKey; amount: number }[]
+  ): Promise<{ pool: PublicKey; noteCommitment: Uint8Array; encryptedNote: Uint8Array }[]> {
+    if (legs.length === 0 || legs.length > MAX_ROUTED_NOTES) {
+      throw new Error(`a route holds 1-${MAX_ROUTED_NOTES} notes`);
+    }
+    const ownerCommitment = await this.computeOwnerCommitment(wallet);
+    const pools = [...new Set(legs.map((leg) => leg.pool.toBase58()))];
+    const grouped = pools.flatMap((pool) => legs.filter((leg) => leg.pool.toBase58() === pool));
+
+    return Promise.all(grouped.map(async ({ pool, amount }) => {
+      const value = BigInt(Math.floor(amount * LAMPORTS_PER_SOL));
+      const blindingFactor = randomBytes(32);
+      return {
+        pool,
+        noteCommitment: await this.computeNoteCommitment(value, blindingFactor, ownerCommitment),
+        encryptedNote: this.encryptNote({
+          amount: value,
+          blindingFactor,
+          ownerCommitment,
+          unlockAt: Date.now() + 86400000,
+        }),
+      };
+    }));
+  }
+
   // ==========================================================================
   // WITHDRAW (shield_withdraw instruction)
   // ==========================================================================
@@ -338,7 +452,8 @@ export class ShieldedBalanceClient {
         merkleProof,
         pathIndices,
         withdrawalProof,
-        outputCommitment
+        outputCommitment,
+        await computeCircuitId('withdrawal')
       );
 
       const [nullifierPda] = PublicKey.findProgramAddressSync(
@@ -358,7 +473,7 @@ export class ShieldedBalanceClient {
         data: Buffer.from(instructionData),
       };
 
-      const tx = new Transaction().add(ix);
+      const tx = withComputeBudget(new Transaction().add(ix), ['shield_withdraw'], this.computeBudget);
       tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
       tx.feePayer = wallet;
 
@@ -466,18 +581,7 @@ export class ShieldedBalanceClient {
    * Decrypt note data
    */
   decryptNote(encryptedData: Uint8Array): DecryptedNote | null {
-    try {
-      const encStr = new TextDecoder().decode(encryptedData).replace(/\0+$/, '');
-      const noteData = JSON.parse(decrypt(encStr, this.encryptionKey));
-      return {
-        amount: BigInt(noteData.amount),
-        blindingFactor: new Uint8Array(Buffer.from(noteData.blindingFactor, 'hex')),
-        ownerCommitment: new Uint8Array(Buffer.from(noteData.ownerCommitment, 'hex')),
-        unlockAt: noteData.unlockAt,
-      };
-    } catch {
-      return null;
-    }
+    return decryptNoteData(encryptedData, this.encryptionKey);
   }
 
   /**
@@ -624,11 +728,12 @@ export class ShieldedBalanceClient {
     merkleProof: Uint8Array[],
     pathIndices: number,
     withdrawalProof: Uint8Array,
-    outputCommitment: Uint8Array
+    outputCommitment: Uint8Array,
+    circuitId: Uint8Array
   ): Uint8Array {
-    // Instruction format: [discriminator(1)] + [nullifier(32)] + [merkle_proof(8*32)] + [path_indices(1)] + [proof_len(2)] + [proof(var)] + [output(32)]
+    // Instruction format: [discriminator(1)] + [nullifier(32)] + [merkle_proof(8*32)] + [path_indices(1)] + [proof_len(2)] + [proof(var)] + [output(32)] + [circuit_id(32)]
     const proofLen = withdrawalProof.length;
-    const data = new Uint8Array(1 + 32 + (8 * 32) + 1 + 2 + proofLen + 32);
+    const data = new Uint8Array(1 + 32 + (8 * 32) + 1 + 2 + proofLen + 32 + 32);
     let offset = 0;
 
     data[offset] = 2; // shield_withdraw discriminator
@@ -655,6 +760,9 @@ export class ShieldedBalanceClient {
     offset += proofLen;
 
     data.set(outputCommitment, offset);
+    offset += 32;
+
+    data.set(circuitId, offset);
 
     return data;
   }
@@ -746,9 +854,10 @@ export class ShieldedBalanceClient {
  */
 export function createShieldedClient(
   connection: Connection,
-  encryptionKey: string
+  encryptionKey: string,
+  computeBudget: ComputeBudgetOptions = {}
 ): ShieldedBalanceClient {
-  return new ShieldedBalanceClient(connection, encryptionKey);
+  return new ShieldedBalanceClient(connection, encryptionKey, computeBudget);
 }
 
 /**
@@ -765,5 +874,317 @@ export async function isBalancePublic(
   return balance > 0;
 }
 
+/**
+ * Decrypt on-chain note data with the key it was encrypted under
+ * Returns null if the note belongs to someone else
+ */
+export function decryptNoteData(
+  encryptedData: Uint8Array,
+  encryptionKey: string
+): DecryptedNote | null {
+  try {
+    const encStr = new TextDecoder().decode(encryptedData).replace(/\0+$/, '');
+    const noteData = JSON.parse(decrypt(encStr, encryptionKey));
+    return {
+      amount: BigInt(noteData.amount),
+      blindingFactor: new Uint8Array(Buffer.from(noteData.blindingFactor, 'hex')),
+      ownerCommitment: new Uint8Array(Buffer.from(noteData.ownerCommitment, 'hex')),
+      unlockAt: noteData.unlockAt,
+    };
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Nullifier a note is spent with: H(note_commitment || owner_secret),
+ * where owner_secret = SHA-256(wallet || encryption key)
+ */
+export async function computeNoteNullifier(
+  noteCommitment: Uint8Array,
+  wallet: PublicKey,
+  encryptionKey: string
+): Promise<Uint8Array> {
+  const ownerSecret = await sha256String(wallet.toBase58() + encryptionKey);
+  const nullifier = await poseidonHash([
+    bytesToBigInt(noteCommitment),
+    bytesToBigInt(ownerSecret)
+  ]);
+  return bigIntToBytes(nullifier);
+}
+
+/**
+ * Pool binding every withdrawal and reward-claim proof carries as a public
+ * signal: H(DOMAIN_POOL_BINDING, pool). Proofs for one pool are rejected by
+ * another
+ */
+export async function computePoolBinding(pool: PublicKey): Promise<Uint8Array> {
+  return hashDomain(DOMAIN_POOL_BINDING, pool.toBytes());
+}
+
+/**
+ * Circuit a proof-verifying instruction is pinned to with its `circuit_id`
+ * argument: H(DOMAIN_CIRCUIT_ID, name), `name` as in the circuit registry
+ * (e.g. 'withdrawal', 'relayed_withdrawal', 'holding_claim')
+ */
+export async function computeCircuitId(name: string): Promise<Uint8Array> {
+  return hashDomain(DOMAIN_CIRCUIT_ID, new TextEncoder().encode(name));
+}
+
+/**
+ * Claim key of a holding account: H(DOMAIN_CLAIM_KEY, claimSecret).
+ * Keep `claimSecret` to claim the withdrawn lamports later
+ */
+export async function computeClaimKeyHash(claimSecret: Uint8Array): Promise<Uint8Array> {
+  return hashDomain(DOMAIN_CLAIM_KEY, claimSecret);
+}
+
+/**
+ * PDA holding lamports withdrawn with `shield_withdraw_to_holding`
+ */
+export function getHoldingPDA(
+  pool: PublicKey,
+  claimKeyHash: Uint8Array,
+  programId: PublicKey = VEIL_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(HOLDING_SEED), pool.toBuffer(), Buffer.from(claimKeyHash)],
+    programId
+  );
+}
+
+/**
+ * Public input a holding withdrawal proof commits to:
+ * H(DOMAIN_HOLDING, pool, claimKeyHash, lamports as u64 little-endian)
+ */
+export async function computeHoldingTag(
+  pool: PublicKey,
+  claimKeyHash: Uint8Array,
+  lamports: bigint
+): Promise<Uint8Array> {
+  const amount = new Uint8Array(8);
+  new DataView(amount.buffer).setBigUint64(0, lamports, true);
+  return hashDomain(DOMAIN_HOLDING, pool.toBytes(), claimKeyHash, amount);
+}
+
+/**
+ * Public input a holding claim proof commits to, fixing the recipient:
+ * H(DOMAIN_HOLDING_CLAIM, holding, recipient)
+ */
+export async function computeHoldingClaimTag(holding: PublicKey, recipient: PublicKey): Promise<Uint8Array> {
+  return hashDomain(DOMAIN_HOLDING_CLAIM, holding.toBytes(), recipient.toBytes());
+}
+
+/**
+ * Public input a split withdrawal proof commits to, fixing every recipient
+ * and share: H(DOMAIN_SPLIT_WITHDRAWAL, pool, (recipient, lamports)...),
+ * lamports as u64 little-endian. Pass the recipients to
+ * `shield_withdraw_split` in the same order
+ */
+export async function computeSplitTag(
+  pool: PublicKey,
+  payments: { recipient: PublicKey; lamports: bigint }[]
+): Promise<Uint8Array> {
+  const encoded = new Uint8Array(payments.length * 40);
+  const view = new DataView(encoded.buffer);
+  payments.forEach(({ recipient, lamports }, i) => {
+    encoded.set(recipient.toBytes(), i * 40);
+    view.setBigUint64(i * 40 + 32, lamports, true);
+  });
+  return hashDomain(DOMAIN_SPLIT_WITHDRAWAL, pool.toBytes(), encoded);
+}
+
+/**
+ * Public input a deposit cancellation proof commits to, fixing the note,
+ * refund recipient and amount: H(DOMAIN_DEPOSIT_CANCEL, pool, note_index,
+ * recipient, lamports), note index as u32 and lamports as u64 little-endian.
+ * `cancel_deposit` only accepts it until the pool's tree checkpoint
+ * includes the note, and at most `DEPOSIT_CANCEL_WINDOW_SECONDS` after
+ * the deposit
+ */
+export async function computeDepositCancelTag(
+  pool: PublicKey,
+  noteIndex: number,
+  recipient: PublicKey,
+  lamports: bigint
+): Promise<Uint8Array> {
+  const encoded = new Uint8Array(4 + 32 + 8);
+  const view = new DataView(encoded.buffer);
+  view.setUint32(0, noteIndex, true);
+  encoded.set(recipient.toBytes(), 4);
+  view.setBigUint64(36, lamports, true);
+  return hashDomain(DOMAIN_DEPOSIT_CANCEL, pool.toBytes(), encoded);
+}
+
+/**
+ * Public input a relayer solvency proof commits to, fixing the relayer's
+ * stake and the solvency period it is proven for:
+ * H(DOMAIN_RELAYER_SOLVENCY, relayer_record, stake_lamports, period_index),
+ * both numbers u64 little-endian. `period_index` is the submission time
+ * divided by the registry's `solvency_period`
+ */
+export async function computeRelayerSolvencyTag(
+  relayerRecord: PublicKey,
+  stakeLamports: bigint,
+  periodIndex: bigint
+): Promise<Uint8Array> {
+  const encoded = new Uint8Array(16);
+  const view = new DataView(encoded.buffer);
+  view.setBigUint64(0, stakeLamports, true);
+  view.setBigUint64(8, periodIndex, true);
+  return hashDomain(DOMAIN_RELAYER_SOLVENCY, relayerRecord.toBytes(), encoded);
+}
+
+/**
+ * Lamports `shield_withdraw_early` keeps as penalty when releasing
+ * `grossLamports`; the recipient receives the rest
+ */
+export function earlyExitPenalty(grossLamports: bigint, penaltyBps: number): bigint {
+  return (grossLamports * BigInt(penaltyBps)) / 10_000n;
+}
+
+/**
+ * Public input an early-exit proof commits to, fixing the payout and the
+ * pool's penalty rate: H(DOMAIN_EARLY_EXIT, pool, recipient,
+ * gross_lamports, penalty_bps), lamports as u64 and the penalty as u16
+ * little-endian. The transaction fails if the authority changes the
+ * penalty after the proof was made
+ */
+export async function computeEarlyExitTag(
+  pool: PublicKey,
+  recipient: PublicKey,
+  grossLamports: bigint,
+  penaltyBps: number
+): Promise<Uint8Array> {
+  const encoded = new Uint8Array(10);
+  const view = new DataView(encoded.buffer);
+  view.setBigUint64(0, grossLamports, true);
+  view.setUint16(8, penaltyBps, true);
+  return hashDomain(DOMAIN_EARLY_EXIT, pool.toBytes(), recipient.toBytes(), encoded);
+}
+
+/** Breakpoint of a pool's reward curve (state/shielded.rs RewardCurvePoint) */
+export interface RewardCurvePoint {
+  /** Deposits from this unix time on earn `rateBps` */
+  startsAt: bigint;
+  rateBps: number;
+}
+
+/**
+ * Reward rate a note deposited at `time` earns, the rate the program
+ * snapshots on the note: the last curve point started by then, the pool's
+ * flat `rewardRateBps` before the first one
+ */
+export function rewardRateAt(baseRateBps: number, curve: RewardCurvePoint[], time: bigint): number {
+  let rate = baseRateBps;
+  for (const point of curve) {
+    if (point.startsAt > time) break;
+    rate = point.rateBps;
+  }
+  return rate;
+}
+
+/**
+ * Public input a shielded reward proof commits to, fixing the pool's rate
+ * schedule: H(DOMAIN_REWARD_CURVE, base_rate_bps, (starts_at, rate_bps)...),
+ * starts_at as i64 and rates as u16 little-endian. The reward circuit
+ * evaluates it at the note's deposit time (`rewardRateAt`)
+ */
+export async function computeRewardCurveHash(
+  baseRateBps: number,
+  curve: RewardCurvePoint[]
+): Promise<Uint8Array> {
+  const base = new Uint8Array(2);
+  new DataView(base.buffer).setUint16(0, baseRateBps, true);
+  const encoded = new Uint8Array(curve.length * 10);
+  const view = new DataView(encoded.buffer);
+  curve.forEach(({ startsAt, rateBps }, i) => {
+    view.setBigInt64(i * 10, startsAt, true);
+    view.setUint16(i * 10 + 8, rateBps, true);
+  });
+  return hashDomain(DOMAIN_REWARD_CURVE, base, encoded);
+}
+
+/**
+ * Allowlist entry letting `callerProgram` deposit into `pool` through CPI
+ */
+export function getDepositCallerPDA(
+  pool: PublicKey,
+  callerProgram: PublicKey,
+  programId: PublicKey = VEIL_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(DEPOSIT_CALLER_SEED), pool.toBuffer(), callerProgram.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Depositor PDA a caller program signs `shield_deposit_cpi` with. Derived
+ * under the caller's program ID, so only that program can sign for it
+ */
+export function getCpiDepositorPDA(callerProgram: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from(CPI_DEPOSITOR_SEED)], callerProgram);
+}
+
+/**
+ * PDA of a pool's nullifier filter
+ */
+export function getNullifierFilterPDA(
+  pool: PublicKey,
+  programId: PublicKey = VEIL_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from(NULLIFIER_FILTER_SEED), pool.toBuffer()], programId);
+}
+
+/**
+ * PDA of the protocol-wide stats account (pool count, notes and nullifiers
+ * across counted pools); read it with the `get_protocol_stats` view
+ */
+export function getProtocolStatsPDA(programId: PublicKey = VEIL_PROGRAM_ID): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from(PROTOCOL_STATS_SEED)], programId);
+}
+
+/**
+ * Bits a nullifier sets in a pool's nullifier filter: little-endian u16
+ * words of H(DOMAIN_NULLIFIER_FILTER, nullifier), modulo the filter size
+ */
+export async function nullifierFilterBits(nullifier: Uint8Array): Promise<number[]> {
+  const digest = await hashDomain(DOMAIN_NULLIFIER_FILTER, nullifier);
+  return Array.from({ length: NULLIFIER_FILTER_HASHES }, (_, i) =>
+    (digest[2 * i] | (digest[2 * i + 1] << 8)) % (NULLIFIER_FILTER_BYTES * 8)
+  );
+}
+
+/**
+ * Whether NullifierFilter account `data` may contain `nullifier`.
+ * `false` proves the note unspent; `true` may be a false positive, so
+ * confirm with the nullifier PDA before treating the note as spent.
+ * Filters created after the pool's first spend cannot rule anything out
+ */
+export async function nullifierFilterMayContain(data: Uint8Array, nullifier: Uint8Array): Promise<boolean> {
+  const view = new DataView(data.buffer, data.byteOffset, data.byteLength);
+  const trackedFrom = view.getUint32(NULLIFIER_FILTER_BITS_OFFSET + NULLIFIER_FILTER_BYTES, true);
+  if (trackedFrom > 0) return true;
+
+  const bits = data.subarray(NULLIFIER_FILTER_BITS_OFFSET, NULLIFIER_FILTER_BITS_OFFSET + NULLIFIER_FILTER_BYTES);
+  return (await nullifierFilterBits(nullifier)).every((bit) => (bits[bit >> 3] & (1 << (bit & 7))) !== 0);
+}
+
+/**
+ * Check a nullifier against a pool's filter in one account read
+ * Returns null when the pool keeps no filter
+ */
+export async function checkNullifierFilter(
+  connection: Connection,
+  pool: PublicKey,
+  nullifier: Uint8Array,
+  programId: PublicKey = VEIL_PROGRAM_ID
+): Promise<boolean | null> {
+  const account = await connection.getAccountInfo(getNullifierFilterPDA(pool, programId)[0]);
+  if (!account) return null;
+  return nullifierFilterMayContain(account.data, nullifier);
+}
+
 export type { ShieldedBalance, ShieldDeposit, ShieldWithdraw } from '../types';
 
@@ -6,12 +6,39 @@
  */
 
 import { Connection, PublicKey, Transaction, SystemProgram } from '@solana/web3.js';
-import { sha256String, poseidonHash, bytesToHex, bytesToBigInt, randomBytes } from '../crypto';
+import { sha256String, poseidonHash, bytesToHex, bytesToBigInt, randomBytes, hashDomain } from '../crypto';
 import { ProofData, Commitment } from '../types';
 
 // Program ID for private voting
 export const VOTING_PROGRAM_ID = new PublicKey('5C1VaebPdHZYETnTL18cLJK2RexXmVVhkkYpnYHD5P4h');
 
+/** Seed of the voter weight record escrowing governance tokens */
+export const VOTER_WEIGHT_SEED = 'voter_weight';
+export const PROPOSAL_TEMPLATE_SEED = 'proposal_template';
+export const CONVICTION_PROPOSAL_SEED = 'conviction_proposal';
+export const CONVICTION_STAKE_SEED = 'conviction_stake';
+export const QUADRATIC_PROPOSAL_SEED = 'quadratic_proposal';
+export const QUADRATIC_VOTE_SEED = 'quadratic_vote';
+/** Lamports a proposal creator escrows for whoever finalizes it */
+export const FINALIZATION_BOUNTY_LAMPORTS = 1_000_000;
+/** Window a conviction proof counts for; stakes re-prove every window */
+export const CONVICTION_PROOF_WINDOW = 86_400;
+/** Longest governance token lockup (4 years) */
+export const MAX_GOVERNANCE_LOCKUP_SECONDS = 4 * 365 * 86_400;
+/** Extra weight of a maximal lockup, in basis points of the deposit */
+export const MAX_LOCKUP_BONUS_BPS = 30_000n;
+export const REALM_REGISTRAR_SEED = 'realm_registrar';
+export const REALM_SNAPSHOT_SEED = 'realm_snapshot';
+export const REALM_VOTER_WEIGHT_SEED = 'realm_voter_weight';
+export const REALM_MAX_VOTER_WEIGHT_SEED = 'realm_max_voter_weight';
+export const REALM_WEIGHT_NULLIFIER_SEED = 'realm_weight_nullifier';
+
+const DOMAIN_REVEAL_TOKEN = 'veil:reveal_token:v2';
+const DOMAIN_ELIGIBILITY_LEAF = 'veil:eligibility_leaf:v2';
+const DOMAIN_TALLY_COMMITMENT = 'veil:tally_commitment:v2';
+const DOMAIN_CONVICTION = 'veil:conviction:v2';
+const DOMAIN_QUADRATIC_VOTE = 'veil:quadratic_vote:v2';
+
 // ============================================================================
 // TYPES
 // ============================================================================
@@ -268,3 +295,267 @@ export async function verifyVoteCommitment(
   return bytesToHex(expected) === bytesToHex(commitment);
 }
 
+/**
+ * Voter weight record escrowing `owner`'s governance tokens of `mint`
+ */
+export function getVoterWeightRecordPDA(
+  mint: PublicKey,
+  owner: PublicKey,
+  programId: PublicKey = VOTING_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(VOTER_WEIGHT_SEED), mint.toBuffer(), owner.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Voting weight of `amount` escrowed tokens with `remainingSeconds` of
+ * lockup left, as `cast_token_weighted_vote` computes it: the deposit plus
+ * MAX_LOCKUP_BONUS_BPS of it scaled by the share of the maximum lockup
+ * still to run
+ */
+export function computeLockupWeight(amount: bigint, remainingSeconds: number): bigint {
+  const remaining = BigInt(Math.min(Math.max(Math.floor(remainingSeconds), 0), MAX_GOVERNANCE_LOCKUP_SECONDS));
+  const bonus = (amount * MAX_LOCKUP_BONUS_BPS * remaining) / (10_000n * BigInt(MAX_GOVERNANCE_LOCKUP_SECONDS));
+  const weight = amount + bonus;
+  return weight > 0xffffffffffffffffn ? 0xffffffffffffffffn : weight;
+}
+
+/**
+ * Hash a vote record stores for a delegated reveal:
+ * H(DOMAIN_REVEAL_TOKEN, revealToken)
+ */
+export async function computeRevealTokenHash(revealToken: Uint8Array): Promise<Uint8Array> {
+  return hashDomain(DOMAIN_REVEAL_TOKEN, revealToken);
+}
+
+/**
+ * Fresh reveal token for `cast_vote_with_reveal_token`. Pass `tokenHash`
+ * at cast time; hand `token` with the choice and secret to the reveal
+ * service, which calls `reveal_vote_with_token` without the voter's wallet
+ */
+export async function createRevealToken(): Promise<{ token: Uint8Array; tokenHash: Uint8Array }> {
+  const token = randomBytes(32);
+  return { token, tokenHash: await computeRevealTokenHash(token) };
+}
+
+/**
+ * Governance parameter template `creator` published under `templateId`
+ */
+export function getProposalTemplatePDA(
+  creator: PublicKey,
+  templateId: Uint8Array,
+  programId: PublicKey = VOTING_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(PROPOSAL_TEMPLATE_SEED), creator.toBuffer(), Buffer.from(templateId)],
+    programId
+  );
+}
+
+/**
+ * Leaf of a proposal eligibility tree: H(DOMAIN_ELIGIBILITY_LEAF, voter).
+ * The template's eligibility root is a depth-8 Merkle tree of these
+ */
+export async function computeEligibilityLeaf(voter: PublicKey): Promise<Uint8Array> {
+  return hashDomain(DOMAIN_ELIGIBILITY_LEAF, voter.toBytes());
+}
+
+/**
+ * Tally commitment a two-stage finalization publishes, for checking it
+ * against the reveals before the dispute window closes:
+ * H(DOMAIN_TALLY_COMMITMENT, proposal, yesCount, noCount, yesWeight,
+ * noWeight, quorumFailed, salt), counts u64 little-endian, salt the commit slot
+ */
+export async function computeTallyCommitment(
+  proposal: PublicKey,
+  counts: { yesCount: bigint; noCount: bigint; yesWeight: bigint; noWeight: bigint },
+  quorumFailed: boolean,
+  salt: bigint
+): Promise<Uint8Array> {
+  const encoded = new Uint8Array(33);
+  const view = new DataView(encoded.buffer);
+  view.setBigUint64(0, counts.yesCount, true);
+  view.setBigUint64(8, counts.noCount, true);
+  view.setBigUint64(16, counts.yesWeight, true);
+  view.setBigUint64(24, counts.noWeight, true);
+  encoded[32] = quorumFailed ? 1 : 0;
+  const slot = new Uint8Array(8);
+  new DataView(slot.buffer).setBigUint64(0, salt, true);
+  return hashDomain(DOMAIN_TALLY_COMMITMENT, proposal.toBytes(), encoded, slot);
+}
+
+/**
+ * Conviction voting proposal created by `creator`
+ */
+export function getConvictionProposalPDA(
+  creator: PublicKey,
+  proposalId: Uint8Array,
+  programId: PublicKey = VOTING_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(CONVICTION_PROPOSAL_SEED), creator.toBuffer(), Buffer.from(proposalId)],
+    programId
+  );
+}
+
+/**
+ * Shielded stake committed to a conviction proposal
+ */
+export function getConvictionStakePDA(
+  proposal: PublicKey,
+  stakeNullifier: Uint8Array,
+  programId: PublicKey = VOTING_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(CONVICTION_STAKE_SEED), proposal.toBuffer(), Buffer.from(stakeNullifier)],
+    programId
+  );
+}
+
+/**
+ * Most conviction a stake of `weight` can prove after `elapsedSeconds`:
+ * its weight scaled by min(elapsed, rampPeriod) / rampPeriod
+ */
+export function computeConviction(weight: bigint, elapsedSeconds: number, rampPeriod: number): bigint {
+  const elapsed = BigInt(Math.min(Math.max(Math.floor(elapsedSeconds), 0), rampPeriod));
+  return (weight * elapsed) / BigInt(rampPeriod);
+}
+
+/**
+ * Public input a conviction proof commits to: H(DOMAIN_CONVICTION, stake,
+ * conviction, elapsed, rampPeriod, weightUnit), all 8 bytes little-endian
+ */
+export async function computeConvictionTag(
+  stake: PublicKey,
+  conviction: bigint,
+  elapsed: bigint,
+  rampPeriod: bigint,
+  weightUnit: bigint
+): Promise<Uint8Array> {
+  const encoded = new Uint8Array(32);
+  const view = new DataView(encoded.buffer);
+  view.setBigUint64(0, conviction, true);
+  view.setBigInt64(8, elapsed, true);
+  view.setBigInt64(16, rampPeriod, true);
+  view.setBigUint64(24, weightUnit, true);
+  return hashDomain(DOMAIN_CONVICTION, stake.toBytes(), encoded);
+}
+
+/**
+ * Quadratic voting proposal
+ */
+export function getQuadraticProposalPDA(
+  creator: PublicKey,
+  proposalId: Uint8Array,
+  programId: PublicKey = VOTING_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(QUADRATIC_PROPOSAL_SEED), creator.toBuffer(), Buffer.from(proposalId)],
+    programId
+  );
+}
+
+/**
+ * Quadratic vote keyed by its vote nullifier
+ */
+export function getQuadraticVotePDA(
+  proposal: PublicKey,
+  voteNullifier: Uint8Array,
+  programId: PublicKey = VOTING_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(QUADRATIC_VOTE_SEED), proposal.toBuffer(), Buffer.from(voteNullifier)],
+    programId
+  );
+}
+
+/**
+ * Credits a quadratic vote spends: the sum of the squared votes, which
+ * the allocation proof shows is within the voter's hidden budget
+ */
+export function computeQuadraticCost(votes: bigint[]): bigint {
+  return votes.reduce((cost, count) => cost + count * count, 0n);
+}
+
+/**
+ * Public input a quadratic vote proof commits to: H(DOMAIN_QUADRATIC_VOTE,
+ * proposal, votes...), each vote count 8 bytes little-endian
+ */
+export async function computeQuadraticVoteTag(proposal: PublicKey, votes: bigint[]): Promise<Uint8Array> {
+  const encoded = new Uint8Array(votes.length * 8);
+  const view = new DataView(encoded.buffer);
+  votes.forEach((count, i) => view.setBigUint64(i * 8, count, true));
+  return hashDomain(DOMAIN_QUADRATIC_VOTE, proposal.toBytes(), encoded);
+}
+
+/**
+ * Registrar tying a Realms DAO's governing mint to a shielded pool
+ */
+export function getRealmRegistrarPDA(
+  realm: PublicKey,
+  governingTokenMint: PublicKey,
+  programId: PublicKey = VOTING_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(REALM_REGISTRAR_SEED), realm.toBuffer(), governingTokenMint.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Pool root snapshot that weight proofs for an spl-governance proposal use
+ */
+export function getRealmWeightSnapshotPDA(
+  registrar: PublicKey,
+  proposal: PublicKey,
+  programId: PublicKey = VOTING_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(REALM_SNAPSHOT_SEED), registrar.toBuffer(), proposal.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * spl-governance addin voter weight record of `owner`; pass it to Realms
+ * as the voter weight account when casting a vote
+ */
+export function getRealmVoterWeightRecordPDA(
+  registrar: PublicKey,
+  owner: PublicKey,
+  programId: PublicKey = VOTING_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(REALM_VOTER_WEIGHT_SEED), registrar.toBuffer(), owner.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * spl-governance addin max voter weight record of a registrar
+ */
+export function getRealmMaxVoterWeightRecordPDA(
+  registrar: PublicKey,
+  programId: PublicKey = VOTING_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(REALM_MAX_VOTER_WEIGHT_SEED), registrar.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * Spent weight nullifier of one proposal snapshot
+ */
+export function getRealmWeightNullifierPDA(
+  snapshot: PublicKey,
+  weightNullifier: Uint8Array,
+  programId: PublicKey = VOTING_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(REALM_WEIGHT_NULLIFIER_SEED), snapshot.toBuffer(), Buffer.from(weightNullifier)],
+    programId
+  );
+}
@@ -0,0 +1,189 @@
+/**
+ * Veil Watchtower Module
+ *
+ * Reads the alert feed a wallet owner, multisig creator or pool creator
+ * registers with `register_watchtower`. Recovery starts, large multisig
+ * proposals and pool pauses write compact alerts into a ring of
+ * `WATCHTOWER_ALERT_SLOTS` entries on the watchtower account, so a
+ * notification service polls one account per subject and forwards new
+ * alerts to the registered alert key.
+ *
+ * @example
+ * ```typescript
+ * import { pollWatchtower } from '@veil-protocol/sdk/watchtower';
+ *
+ * let cursor = 0n;
+ * const { alerts, latest, missed } = await pollWatchtower(connection, walletAccount, cursor);
+ * for (const alert of alerts) notify(alert);
+ * cursor = latest;
+ * ```
+ */
+
+import { Connection, PublicKey } from '@solana/web3.js';
+import { bytesToHex, hexToBytes } from '../crypto';
+import { VEIL_ACCOUNT_KINDS } from '../accounts';
+
+const VEIL_PROGRAM_ID = new PublicKey('5C1VaebPdHZYETnTL18cLJK2RexXmVVhkkYpnYHD5P4h');
+
+/** PDA seed of watchtowers (pda.rs WATCHTOWER_SEED) */
+export const WATCHTOWER_SEED = 'watchtower';
+
+/** Alerts a watchtower keeps before overwriting the oldest */
+export const WATCHTOWER_ALERT_SLOTS = 16;
+
+// Account layout (state/watchtower.rs Watchtower)
+const ALERTS_OFFSET = 8 + 32 + 32 + 32 + 8 + 8;
+const ALERT_LEN = 8 + 1 + 32 + 8 + 8;
+const WATCHTOWER_LEN = ALERTS_OFFSET + ALERT_LEN * WATCHTOWER_ALERT_SLOTS + 8 + 1 + 1;
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+export enum WatchtowerAlertKind {
+  /** A time-locked wallet recovery was started; value is the unlock time */
+  RecoveryInitiated = 0,
+  /** A multisig proposal at or above the threshold, or not a plain transfer; value is the amount */
+  LargeProposal = 1,
+  /** Governance deactivated the pool */
+  PoolPaused = 2,
+}
+
+export interface WatchtowerAlert {
+  /** Position in the feed, counting from 1 */
+  sequence: bigint;
+  kind: WatchtowerAlertKind;
+  /** Recovery commitment, multisig proposal or pool update proposal */
+  reference: Uint8Array;
+  value: bigint;
+  timestamp: bigint;
+}
+
+export interface WatchtowerState {
+  subject: PublicKey;
+  owner: PublicKey;
+  alertKey: PublicKey;
+  /** Smallest multisig transfer that raises an alert */
+  largeAmount: bigint;
+  /** Alerts written so far */
+  alertCount: bigint;
+  /** Alerts still in the ring, oldest first */
+  alerts: WatchtowerAlert[];
+  createdAt: bigint;
+}
+
+export interface WatchtowerPoll {
+  /** Alerts after the cursor still in the ring, oldest first */
+  alerts: WatchtowerAlert[];
+  /** Sequence to pass as the cursor next time */
+  latest: bigint;
+  /** Alerts after the cursor already overwritten; read the events for them */
+  missed: bigint;
+}
+
+// ============================================================================
+// ACCOUNTS
+// ============================================================================
+
+/**
+ * Watchtower PDA of a wallet, multisig or shielded pool
+ */
+export function getWatchtowerPDA(subject: PublicKey, programId: PublicKey = VEIL_PROGRAM_ID): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from(WATCHTOWER_SEED), subject.toBuffer()], programId);
+}
+
+/**
+ * Decode watchtower account data
+ */
+export function decodeWatchtower(data: Uint8Array): WatchtowerState {
+  if (data.length < WATCHTOWER_LEN || bytesToHex(data.slice(0, 8)) !== VEIL_ACCOUNT_KINDS.Watchtower.discriminator) {
+    throw new Error('not a watchtower account');
+  }
+
+  const view = new DataView(data.buffer, data.byteOffset, data.byteLength);
+  const key = (offset: number) => new PublicKey(data.slice(offset, offset + 32));
+
+  const alerts: WatchtowerAlert[] = [];
+  for (let slot = 0; slot < WATCHTOWER_ALERT_SLOTS; slot++) {
+    const offset = ALERTS_OFFSET + slot * ALERT_LEN;
+    const sequence = view.getBigUint64(offset, true);
+    if (sequence === 0n) continue;
+    alerts.push({
+      sequence,
+      kind: data[offset + 8] as WatchtowerAlertKind,
+      reference: data.slice(offset + 9, offset + 41),
+      value: view.getBigUint64(offset + 41, true),
+      timestamp: view.getBigInt64(offset + 49, true),
+    });
+  }
+  alerts.sort((a, b) => (a.sequence < b.sequence ? -1 : 1));
+
+  const createdAt = ALERTS_OFFSET + ALERT_LEN * WATCHTOWER_ALERT_SLOTS;
+  return {
+    subject: key(8),
+    owner: key(40),
+    alertKey: key(72),
+    largeAmount: view.getBigUint64(104, true),
+    alertCount: view.getBigUint64(112, true),
+    alerts,
+    createdAt: view.getBigInt64(createdAt, true),
+  };
+}
+
+// ============================================================================
+// POLLING
+// ============================================================================
+
+/**
+ * Alerts written after `cursor` (a sequence number, 0 for all), and how
+ * many of them the ring has already overwritten
+ */
+export function alertsSince(state: WatchtowerState, cursor: bigint): WatchtowerPoll {
+  const alerts = state.alerts.filter((alert) => alert.sequence > cursor);
+  const unseen = state.alertCount > cursor ? state.alertCount - cursor : 0n;
+  return {
+    alerts,
+    latest: state.alertCount > cursor ? state.alertCount : cursor,
+    missed: unseen - BigInt(alerts.length),
+  };
+}
+
+/**
+ * Fetch a subject's watchtower and return the alerts after `cursor`
+ */
+export async function pollWatchtower(
+  connection: Connection,
+  subject: PublicKey,
+  cursor: bigint,
+  programId: PublicKey = VEIL_PROGRAM_ID
+): Promise<WatchtowerPoll> {
+  const [address] = getWatchtowerPDA(subject, programId);
+  const info = await connection.getAccountInfo(address);
+  if (!info) throw new Error(`no watchtower registered for ${subject.toBase58()}`);
+  return alertsSince(decodeWatchtower(info.data), cursor);
+}
+
+/**
+ * Watchtower account bytes for `state`, laid out as the program stores
+ * them; used to test pollers without a validator
+ */
+export function encodeWatchtower(state: WatchtowerState): Uint8Array {
+  const data = new Uint8Array(WATCHTOWER_LEN);
+  const view = new DataView(data.buffer);
+  data.set(hexToBytes(VEIL_ACCOUNT_KINDS.Watchtower.discriminator), 0);
+  data.set(state.subject.toBytes(), 8);
+  data.set(state.owner.toBytes(), 40);
+  data.set(state.alertKey.toBytes(), 72);
+  view.setBigUint64(104, state.largeAmount, true);
+  view.setBigUint64(112, state.alertCount, true);
+  for (const alert of state.alerts) {
+    const offset = ALERTS_OFFSET + Number(alert.sequence % BigInt(WATCHTOWER_ALERT_SLOTS)) * ALERT_LEN;
+    view.setBigUint64(offset, alert.sequence, true);
+    data[offset + 8] = alert.kind;
+    data.set(alert.reference, offset + 9);
+    view.setBigUint64(offset + 41, alert.value, true);
+    view.setBigInt64(offset + 49, alert.timestamp, true);
+  }
+  view.setBigInt64(ALERTS_OFFSET + ALERT_LEN * WATCHTOWER_ALERT_SLOTS, state.createdAt, true);
+  return data;
+}
@@ -16,6 +16,16 @@ export default defineConfig({
     'confidential/index': 'src/confidential/index.ts',
     'compliance/index': 'src/compliance/index.ts',
     'ramps/index': 'src/ramps/index.ts',
+    'ledger/index': 'src/ledger/index.ts',
+    'compute/index': 'src/compute/index.ts',
+    'alias/index': 'src/alias/index.ts',
+    'offline/index': 'src/offline/index.ts',
+    'accounts/index': 'src/accounts/index.ts',
+    'watchtower/index': 'src/watchtower/index.ts',
+    'coinselect/index': 'src/coinselect/index.ts',
+    'prover/index': 'src/prover/index.ts',
+    'remote/index': 'src/remote/index.ts',
+    'planner/index': 'src/planner/index.ts',
   },
   format: ['cjs', 'esm'],
   dts: true,
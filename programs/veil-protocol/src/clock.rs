@@ -0,0 +1,64 @@
+//! Clock source for time-gated logic
+//!
+//! Handlers read the current time through `now` instead of `Clock::get()`.
+//! Built with the `test-clock` feature, a `MockClock` account (see
+//! `set_mock_clock`) passed as the last remaining account overrides the
+//! sysvar's `unix_timestamp` and `slot`, so integration tests can step
+//! through timelocks, voting windows and lockups deterministically. Without
+//! the feature the remaining accounts are ignored and `now` is the plain
+//! sysvar read.
+//!
+//! Time is always read when the transaction executes, never when it was
+//! signed. A durable-nonce transaction carries a nonce instead of a recent
+//! blockhash, so it stays valid until the nonce is advanced and may land
+//! days after its signers approved it. Every deadline, timelock and voting
+//! window is therefore checked against the execution time, and nothing in
+//! the program depends on the blockhash, slot hashes or the position of the
+//! `AdvanceNonceAccount` instruction. Signers who need their intent to
+//! expire say so explicitly, e.g. `stealth_sign_offline`'s `valid_until`.
+
+use anchor_lang::prelude::*;
+
+/// Current clock, overridden by a trailing `MockClock` under `test-clock`
+pub fn now(remaining_accounts: &[AccountInfo]) -> Result<Clock> {
+    #[allow(unused_mut)]
+    let mut clock = Clock::get()?;
+
+    #[cfg(feature = "test-clock")]
+    if let Some(mock) = mock_clock(remaining_accounts)? {
+        clock.unix_timestamp = mock.unix_timestamp;
+        clock.slot = mock.slot;
+    }
+    #[cfg(not(feature = "test-clock"))]
+    let _ = remaining_accounts;
+
+    Ok(clock)
+}
+
+/// `remaining_accounts` without a trailing `MockClock`, for handlers that
+/// consume their remaining accounts positionally
+pub fn strip<'a, 'info>(remaining_accounts: &'a [AccountInfo<'info>]) -> &'a [AccountInfo<'info>] {
+    #[cfg(feature = "test-clock")]
+    if let Some((last, rest)) = remaining_accounts.split_last() {
+        if is_mock_clock(last) {
+            return rest;
+        }
+    }
+    remaining_accounts
+}
+
+#[cfg(feature = "test-clock")]
+fn is_mock_clock(info: &AccountInfo) -> bool {
+    info.owner == &crate::ID && info.key() == crate::pda::mock_clock_address().0
+}
+
+#[cfg(feature = "test-clock")]
+fn mock_clock(remaining_accounts: &[AccountInfo]) -> Result<Option<crate::MockClock>> {
+    match remaining_accounts.last() {
+        Some(info) if is_mock_clock(info) => {
+            let data = info.try_borrow_data()?;
+            Ok(Some(crate::MockClock::try_deserialize(&mut &data[..])?))
+        }
+        _ => Ok(None),
+    }
+}
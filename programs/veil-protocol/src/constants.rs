@@ -0,0 +1,142 @@
+//! Protocol limits and parameters
+
+use crate::*;
+
+/// Largest proof any instruction accepts, in bytes. A Groth16 proof is
+/// 256 bytes; the rest leaves room for encodings with extra metadata
+#[constant]
+pub const MAX_PROOF_LEN: u32 = 512;
+/// Most public signals a caller-supplied signal vector may carry
+#[constant]
+pub const MAX_PUBLIC_SIGNALS: u32 = 16;
+/// Maximum number of signers for a multisig
+/// Signer and approval slots are allocated on demand, so small multisigs
+/// only pay rent for the commitments they actually store
+pub const MAX_MULTISIG_SIGNERS: usize = 64;
+/// Maximum number of notes one `shield_deposit_batch` can insert
+pub const MAX_DEPOSIT_BATCH: usize = 16;
+/// Maximum number of notes one `route_deposit` can spread across pools
+pub const MAX_ROUTED_NOTES: usize = 8;
+/// Maximum number of input notes one `shield_consolidate` can spend
+pub const MAX_CONSOLIDATE_INPUTS: usize = 8;
+/// Maximum number of withdrawals one aggregated proof can settle
+pub const MAX_AGGREGATE_WITHDRAWALS: usize = 32;
+/// Alerts a watchtower keeps before overwriting the oldest
+pub const WATCHTOWER_ALERT_SLOTS: usize = 16;
+/// Maximum number of public recipients one `shield_withdraw_split` can pay
+pub const MAX_SPLIT_RECIPIENTS: usize = 4;
+/// How long after a deposit its note can still be cancelled (1 hour)
+pub const DEPOSIT_CANCEL_WINDOW: i64 = 3_600;
+/// Maximum ciphertext size of a threshold-encrypted multisig proposal
+pub const MAX_ENCRYPTED_ACTION_LEN: usize = 1024;
+/// Longest veto window a multisig can configure (30 days)
+pub const MAX_VETO_WINDOW: i64 = 30 * 86_400;
+/// Longest execution timelock a multisig can configure (30 days)
+pub const MAX_MULTISIG_TIMELOCK: i64 = 30 * 86_400;
+/// Shortest heartbeat epoch a multisig can configure (1 day)
+pub const MIN_LIVENESS_PERIOD: i64 = 86_400;
+/// Longest heartbeat epoch a multisig can configure (1 year)
+pub const MAX_LIVENESS_PERIOD: i64 = 365 * 86_400;
+/// Maximum instruction data of a multisig `PoolAdmin` action
+pub const MAX_POOL_ADMIN_DATA_LEN: usize = 256;
+/// Most steps in a multisig `Batch` action
+pub const MAX_MULTISIG_BATCH_STEPS: usize = 8;
+/// SPL Token program
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+/// SPL Token-2022 program
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+/// SPL Associated Token Account program
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+/// Programs a commitment wallet may call through `execute_as_wallet`:
+/// SPL Token, Token-2022, Associated Token Account and Memo
+pub const WALLET_CPI_ALLOWLIST: [Pubkey; 4] = [
+    TOKEN_PROGRAM_ID,
+    TOKEN_2022_PROGRAM_ID,
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+    pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr"),
+];
+/// Size of a Token-2022 mint with only the NonTransferable extension:
+/// base mint padded to 165 bytes, account type byte, empty TLV entry
+pub const RECEIPT_MINT_LEN: usize = 165 + 1 + 4;
+/// Maximum number of votes per proposal
+pub const MAX_VOTES_PER_PROPOSAL: usize = 100;
+/// Lamports a proposal creator escrows in the proposal, paid to whoever
+/// finalizes it once the reveal phase has ended
+pub const FINALIZATION_BOUNTY_LAMPORTS: u64 = 1_000_000;
+/// Longest time a conviction stake may take to reach full weight (1 year)
+pub const MAX_CONVICTION_RAMP_PERIOD: i64 = 365 * 86_400;
+/// Window a conviction proof counts for (1 day); stakes re-prove against
+/// the current spend anchor each window, so a spent note stops counting
+pub const CONVICTION_PROOF_WINDOW: i64 = 86_400;
+/// Most options a quadratic voting proposal can offer
+pub const MAX_QUADRATIC_OPTIONS: usize = 8;
+/// Bounds on a proposal's tally dispute window (1 hour to 30 days)
+pub const MIN_TALLY_DISPUTE_WINDOW: i64 = 3_600;
+pub const MAX_TALLY_DISPUTE_WINDOW: i64 = 30 * 86_400;
+/// Shortest commit and reveal phases a governed pool may allow (1 day)
+pub const MIN_POOL_GOVERNANCE_PERIOD: i64 = 86_400;
+/// Longest delay a governed pool may put between a passing vote and the
+/// update taking effect (30 days)
+pub const MAX_POOL_GOVERNANCE_DELAY: i64 = 30 * 86_400;
+/// How many times `finalize_proposal` may extend a reveal phase that is
+/// below the proposal's minimum reveal ratio
+pub const MAX_REVEAL_EXTENSIONS: u8 = 3;
+/// Longest governance token lockup (4 years); locking this long earns the
+/// full `MAX_LOCKUP_BONUS_BPS` weight bonus
+pub const MAX_GOVERNANCE_LOCKUP_SECONDS: i64 = 4 * 365 * 86_400;
+/// Extra voting weight (basis points of the deposit) of a maximal lockup,
+/// decaying linearly as the lockup runs down: up to 4x the deposit
+pub const MAX_LOCKUP_BONUS_BPS: u64 = 30_000;
+/// Maximum number of notes in the shielded pool Merkle tree
+pub const MAX_SHIELDED_NOTES: usize = 256;
+/// Highest share of an early-exit withdrawal a pool may keep as penalty
+pub const MAX_EARLY_EXIT_PENALTY_BPS: u16 = 5_000;
+/// Breakpoints a shielded pool's reward curve may hold
+pub const MAX_REWARD_CURVE_POINTS: usize = 8;
+/// Shortest note expiry a pool may configure (1 year)
+pub const MIN_NOTE_EXPIRY_SECONDS: i64 = 365 * 86_400;
+/// Notice between announcing a sweep of expired notes and executing it
+/// (180 days), so owners of dormant notes can still withdraw them
+pub const NOTE_SWEEP_NOTICE_SECONDS: i64 = 180 * 86_400;
+/// Length of one anonymity-metrics epoch
+pub const ANONYMITY_EPOCH_SECONDS: i64 = 86_400;
+/// Number of recent epochs kept in a pool's deposit histogram
+pub const DEPOSIT_HISTOGRAM_BUCKETS: usize = 8;
+/// Merkle tree depth for shielded pool
+pub const MERKLE_TREE_DEPTH: usize = 8;
+/// Size of a pool's nullifier bloom filter (8192 bits): about 0.02% false
+/// positives once all MAX_SHIELDED_NOTES notes are spent
+pub const NULLIFIER_FILTER_BYTES: usize = 1024;
+/// Bits set per nullifier in the bloom filter
+pub const NULLIFIER_FILTER_HASHES: usize = 4;
+/// Number of public signals in a wallet attestation proof
+pub const ATTESTATION_PUBLIC_SIGNALS: usize = 5;
+/// How long a wallet attestation stays valid (30 days)
+pub const ATTESTATION_VALIDITY_SECONDS: i64 = 30 * 86400;
+/// Maximum age of the reference time an attestation proof was generated against
+pub const MAX_ATTESTATION_PROOF_AGE: i64 = 3600;
+/// Depth of the wallet registry's commitment tree (about a million wallets)
+pub const WALLET_TREE_DEPTH: usize = 20;
+/// Recent wallet registry roots an attestation proof may be generated against
+pub const WALLET_ROOT_HISTORY: usize = 8;
+/// Maximum actions per identity per epoch in an RLN group
+pub const MAX_RLN_MESSAGE_LIMIT: u32 = 100;
+/// Delay between a relayer requesting exit and reclaiming its stake, so
+/// misbehaviour can still be slashed (7 days outside localnet)
+pub const RELAYER_UNBONDING_SECONDS: i64 = network::RELAYER_UNBONDING_SECONDS;
+/// Bounds on how long a relayer solvency proof stays valid (1 hour to 30 days)
+pub const MIN_RELAYER_SOLVENCY_PERIOD: i64 = 3_600;
+pub const MAX_RELAYER_SOLVENCY_PERIOD: i64 = 30 * 86_400;
+/// Anchor discriminator of `on_proof_verified`, the instruction external
+/// programs implement to receive routed proof results: sha256("global:on_proof_verified")[..8]
+pub const PROOF_VERIFIED_CALLBACK_DISCRIMINATOR: [u8; 8] = [250, 216, 26, 22, 200, 169, 107, 127];
+/// Anchor discriminator of `screen_withdrawal`, the instruction a pool's
+/// screener program implements: sha256("global:screen_withdrawal")[..8]
+pub const SCREEN_WITHDRAWAL_DISCRIMINATOR: [u8; 8] = [126, 168, 137, 36, 118, 99, 89, 195];
+
+/// Votes cast before this time (the v1 hash upgrade) may still be revealed
+/// against an untagged v0 commitment
+pub const LEGACY_VOTE_COMMITMENT_CUTOFF: i64 = 1_792_108_800;
+/// Current layout version of program accounts; accounts created before
+/// versioning read as version 0 until `migrate_account` upgrades them
+pub const ACCOUNT_VERSION: u8 = 1;
@@ -0,0 +1,528 @@
+//! Commitments and the tags that bind proofs to one state transition
+
+use crate::*;
+
+/// Compute action hash: H(DOMAIN_MULTISIG_ACTION, borsh(action))
+pub(crate) fn compute_multisig_action_hash(action: &MultisigAction) -> Result<[u8; 32]> {
+    let mut data = Vec::with_capacity(action.serialized_len());
+    action.serialize(&mut data)?;
+    Ok(hash_domain(DOMAIN_MULTISIG_ACTION, &[&data]))
+}
+
+/// Compute key share commitment:
+/// H(DOMAIN_PROPOSAL_KEY_SHARE, proposal || signer_commitment || index || share)
+pub(crate) fn compute_key_share_commitment(
+    proposal: &Pubkey,
+    signer_commitment: &[u8; 32],
+    key_share: &ProposalKeyShare,
+) -> [u8; 32] {
+    hash_domain(
+        DOMAIN_PROPOSAL_KEY_SHARE,
+        &[proposal.as_ref(), signer_commitment, &[key_share.index], &key_share.share],
+    )
+}
+
+/// Campaign tag a donation note's owner must equal (proven in-circuit)
+pub(crate) fn compute_campaign_tag(pool: &Pubkey, campaign_id: &[u8; 32]) -> [u8; 32] {
+    hash_domain(DOMAIN_CAMPAIGN_TAG, &[pool.as_ref(), campaign_id])
+}
+
+/// Pool tag binding a reserve proof to one pool
+pub(crate) fn compute_reserve_tag(pool: &Pubkey) -> [u8; 32] {
+    hash_domain(DOMAIN_RESERVE, &[pool.as_ref()])
+}
+
+/// Pool binding every withdrawal proof carries as its last public signal,
+/// so a proof made against one pool cannot be replayed against another
+pub(crate) fn compute_pool_binding(pool: &Pubkey) -> [u8; 32] {
+    hash_domain(DOMAIN_POOL_BINDING, &[pool.as_ref()])
+}
+
+/// Holding tag a holding withdrawal proof is bound to:
+/// H(DOMAIN_HOLDING, pool, claim_key_hash, lamports)
+pub(crate) fn compute_holding_tag(pool: &Pubkey, release: &HoldingRelease) -> [u8; 32] {
+    hash_domain(
+        DOMAIN_HOLDING,
+        &[pool.as_ref(), &release.claim_key_hash, &release.lamports.to_le_bytes()],
+    )
+}
+
+/// Split tag a split withdrawal proof is bound to, fixing every recipient
+/// and share: H(DOMAIN_SPLIT_WITHDRAWAL, pool, (recipient, lamports)...),
+/// lamports as u64 little-endian
+pub(crate) fn compute_split_tag(pool: &Pubkey, payments: &[SplitPayment]) -> [u8; 32] {
+    let mut encoded = Vec::with_capacity(payments.len() * 40);
+    for payment in payments {
+        encoded.extend_from_slice(payment.recipient.as_ref());
+        encoded.extend_from_slice(&payment.lamports.to_le_bytes());
+    }
+    hash_domain(DOMAIN_SPLIT_WITHDRAWAL, &[pool.as_ref(), &encoded])
+}
+
+/// Cancel tag a deposit cancellation proof is bound to, fixing the note,
+/// the refund recipient and the amount:
+/// H(DOMAIN_DEPOSIT_CANCEL, pool, note_index, recipient, lamports), note
+/// index as u32 and lamports as u64 little-endian
+pub(crate) fn compute_deposit_cancel_tag(pool: &Pubkey, note_index: u32, recipient: &Pubkey, lamports: u64) -> [u8; 32] {
+    hash_domain(
+        DOMAIN_DEPOSIT_CANCEL,
+        &[pool.as_ref(), &note_index.to_le_bytes(), recipient.as_ref(), &lamports.to_le_bytes()],
+    )
+}
+
+/// Recipient tag a holding claim proof is bound to, so the claim cannot be
+/// redirected: H(DOMAIN_HOLDING_CLAIM, holding, recipient)
+pub(crate) fn compute_holding_claim_tag(holding: &Pubkey, recipient: &Pubkey) -> [u8; 32] {
+    hash_domain(DOMAIN_HOLDING_CLAIM, &[holding.as_ref(), recipient.as_ref()])
+}
+
+/// Escrow tag binding lock and vote proofs to one order
+pub(crate) fn compute_escrow_tag(pool: &Pubkey, escrow_id: &[u8; 32], order_hash: &[u8; 32]) -> [u8; 32] {
+    hash_domain(DOMAIN_ESCROW_TAG, &[pool.as_ref(), escrow_id, order_hash])
+}
+
+/// Fee commitment a relayed withdrawal proof is bound to:
+/// H(DOMAIN_RELAYER_FEE, relayer, lamports, blinding)
+pub(crate) fn compute_relayer_fee_commitment(relayer: &Pubkey, fee: &RelayerFeeOpening) -> [u8; 32] {
+    hash_domain(DOMAIN_RELAYER_FEE, &[relayer.as_ref(), &fee.lamports.to_le_bytes(), &fee.blinding])
+}
+
+/// Outcome a relayed withdrawal records with its nullifier: the output
+/// note it paid into, the relayer and the fee it took.
+/// H(DOMAIN_RELAYED_OUTCOME, output_commitment, relayer, lamports, blinding)
+pub(crate) fn compute_relayed_outcome(
+    output_commitment: &[u8; 32],
+    relayer: &Pubkey,
+    fee: &RelayerFeeOpening,
+) -> [u8; 32] {
+    hash_domain(
+        DOMAIN_RELAYED_OUTCOME,
+        &[output_commitment, relayer.as_ref(), &fee.lamports.to_le_bytes(), &fee.blinding],
+    )
+}
+
+/// Tag binding a relayer solvency proof to the relayer's stake and one
+/// solvency period: H(DOMAIN_RELAYER_SOLVENCY, relayer_record,
+/// stake_lamports, period_index), both numbers u64 little-endian
+pub(crate) fn compute_relayer_solvency_tag(relayer_record: &Pubkey, stake_lamports: u64, period_index: u64) -> [u8; 32] {
+    hash_domain(
+        DOMAIN_RELAYER_SOLVENCY,
+        &[relayer_record.as_ref(), &stake_lamports.to_le_bytes(), &period_index.to_le_bytes()],
+    )
+}
+
+/// Tag binding an early-exit proof to its payout: H(DOMAIN_EARLY_EXIT,
+/// pool, recipient, gross_lamports, penalty_bps), lamports as u64 and the
+/// penalty as u16 little-endian
+pub(crate) fn compute_early_exit_tag(pool: &Pubkey, recipient: &Pubkey, gross_lamports: u64, penalty_bps: u16) -> [u8; 32] {
+    hash_domain(
+        DOMAIN_EARLY_EXIT,
+        &[pool.as_ref(), recipient.as_ref(), &gross_lamports.to_le_bytes(), &penalty_bps.to_le_bytes()],
+    )
+}
+
+/// Hash of a pool's reward schedule, the public input reward proofs
+/// evaluate at the note's deposit time: H(DOMAIN_REWARD_CURVE,
+/// base_rate_bps, (starts_at, rate_bps)...), starts_at as i64 and rates
+/// as u16 little-endian
+pub(crate) fn compute_reward_curve_hash(base_rate_bps: u16, points: &[RewardCurvePoint]) -> [u8; 32] {
+    let mut encoded = Vec::with_capacity(points.len() * RewardCurvePoint::LEN);
+    for point in points {
+        encoded.extend_from_slice(&point.starts_at.to_le_bytes());
+        encoded.extend_from_slice(&point.rate_bps.to_le_bytes());
+    }
+    hash_domain(DOMAIN_REWARD_CURVE, &[&base_rate_bps.to_le_bytes(), &encoded])
+}
+
+/// Tag binding a key-value attestation proof to one write:
+/// H(DOMAIN_ATTESTATION_KV, wallet, key, value_hash, revision)
+pub(crate) fn compute_kv_attestation_tag(wallet: &Pubkey, key: &[u8; 32], value_hash: &[u8; 32], revision: u32) -> [u8; 32] {
+    hash_domain(
+        DOMAIN_ATTESTATION_KV,
+        &[wallet.as_ref(), key, value_hash, &revision.to_le_bytes()],
+    )
+}
+
+/// Tag binding an unfreeze proof to one freeze of one wallet:
+/// H(DOMAIN_WALLET_UNFREEZE, wallet, frozen_at)
+pub(crate) fn compute_wallet_unfreeze_tag(wallet: &Pubkey, frozen_at: i64) -> [u8; 32] {
+    hash_domain(DOMAIN_WALLET_UNFREEZE, &[wallet.as_ref(), &frozen_at.to_le_bytes()])
+}
+
+/// Tag binding a referral claim to one payout of one referrer:
+/// H(DOMAIN_REFERRAL_CLAIM, referral, recipient, claimed_count)
+pub(crate) fn compute_referral_claim_tag(referral: &Pubkey, recipient: &Pubkey, claimed_count: u64) -> [u8; 32] {
+    hash_domain(
+        DOMAIN_REFERRAL_CLAIM,
+        &[referral.as_ref(), recipient.as_ref(), &claimed_count.to_le_bytes()],
+    )
+}
+
+/// Tag binding a sweep proof to one range of one pool:
+/// H(DOMAIN_NOTE_SWEEP, pool, from_index, through_index, amount)
+pub(crate) fn compute_note_sweep_tag(pool: &Pubkey, from_index: u32, through_index: u32, amount: u64) -> [u8; 32] {
+    hash_domain(
+        DOMAIN_NOTE_SWEEP,
+        &[
+            pool.as_ref(),
+            &from_index.to_le_bytes(),
+            &through_index.to_le_bytes(),
+            &amount.to_le_bytes(),
+        ],
+    )
+}
+
+/// Tag binding a stake weight proof to one proposal and minimum value:
+/// H(DOMAIN_STAKE_WEIGHT, proposal, pool, min_value)
+pub(crate) fn compute_stake_weight_tag(proposal: &Pubkey, pool: &Pubkey, min_value: u64) -> [u8; 32] {
+    hash_domain(
+        DOMAIN_STAKE_WEIGHT,
+        &[proposal.as_ref(), pool.as_ref(), &min_value.to_le_bytes()],
+    )
+}
+
+/// Tag binding a treasury attestation proof to one multisig, pool and
+/// stated band: H(DOMAIN_TREASURY_BAND, multisig, pool, min_balance, max_balance)
+pub(crate) fn compute_treasury_band_tag(multisig: &Pubkey, pool: &Pubkey, min_balance: u64, max_balance: u64) -> [u8; 32] {
+    hash_domain(
+        DOMAIN_TREASURY_BAND,
+        &[multisig.as_ref(), pool.as_ref(), &min_balance.to_le_bytes(), &max_balance.to_le_bytes()],
+    )
+}
+
+/// Tag binding a small-spend proof to one proposal's transfer:
+/// H(DOMAIN_SMALL_SPEND, proposal, mint, amount), mint default for SOL
+pub(crate) fn compute_small_spend_tag(proposal: &Pubkey, mint: &Pubkey, amount: u64) -> [u8; 32] {
+    hash_domain(
+        DOMAIN_SMALL_SPEND,
+        &[proposal.as_ref(), mint.as_ref(), &amount.to_le_bytes()],
+    )
+}
+
+/// Tag binding a heartbeat proof to one multisig epoch and signer set:
+/// H(DOMAIN_SIGNER_HEARTBEAT, multisig, epoch, signer_commitment...)
+pub(crate) fn compute_heartbeat_tag(multisig: &Pubkey, epoch: u64, signers: &[[u8; 32]]) -> [u8; 32] {
+    let epoch = epoch.to_le_bytes();
+    let parts = [multisig.as_ref(), epoch.as_ref()].into_iter();
+    hash_domain_iter(
+        DOMAIN_SIGNER_HEARTBEAT,
+        parts.chain(signers.iter().map(|signer| signer.as_ref())),
+    )
+}
+
+/// Tag binding a stealth approval proof to one proposal and signer set:
+/// H(DOMAIN_STEALTH_APPROVAL, proposal, signer_commitment...)
+pub(crate) fn compute_stealth_approval_tag(proposal: &Pubkey, signers: &[[u8; 32]]) -> [u8; 32] {
+    let parts = core::iter::once(proposal.as_ref());
+    hash_domain_iter(
+        DOMAIN_STEALTH_APPROVAL,
+        parts.chain(signers.iter().map(|signer| signer.as_ref())),
+    )
+}
+
+/// Tag binding a signer rotation proof to one multisig and the commitment
+/// taking the old one's place: H(DOMAIN_SIGNER_ROTATION, multisig, new_commitment)
+pub(crate) fn compute_signer_rotation_tag(multisig: &Pubkey, new_commitment: &[u8; 32]) -> [u8; 32] {
+    hash_domain(DOMAIN_SIGNER_ROTATION, &[multisig.as_ref(), new_commitment])
+}
+
+/// Tag binding an approval revocation proof to one proposal:
+/// H(DOMAIN_APPROVAL_REVOCATION, proposal, approval_commitment)
+pub(crate) fn compute_approval_revocation_tag(proposal: &Pubkey, approval_commitment: &[u8; 32]) -> [u8; 32] {
+    hash_domain(DOMAIN_APPROVAL_REVOCATION, &[proposal.as_ref(), approval_commitment])
+}
+
+/// Call tag binding a wallet call proof to one call:
+/// H(DOMAIN_WALLET_CALL, wallet, nonce, program, data, (key, flags)...)
+pub(crate) fn compute_wallet_call_tag(
+    wallet: &Pubkey,
+    nonce: u64,
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> [u8; 32] {
+    let nonce = nonce.to_le_bytes();
+    let flags: Vec<[u8; 1]> = accounts
+        .iter()
+        .map(|account| [u8::from(account.is_signer) | (u8::from(account.is_writable) << 1)])
+        .collect();
+    let header: [&[u8]; 4] = [wallet.as_ref(), &nonce, program_id.as_ref(), data];
+    let metas = accounts
+        .iter()
+        .zip(&flags)
+        .flat_map(|(account, flag)| [account.key.as_ref(), flag.as_ref()]);
+    hash_domain_iter(DOMAIN_WALLET_CALL, header.into_iter().chain(metas))
+}
+
+/// Veto tag binding a veto proof to one multisig proposal
+pub(crate) fn compute_veto_tag(proposal: &Pubkey) -> [u8; 32] {
+    hash_domain(DOMAIN_VETO_TAG, &[proposal.as_ref()])
+}
+
+/// Subscription tag binding a collection proof to one period
+pub(crate) fn compute_subscription_tag(subscription: &Pubkey, period: u64) -> [u8; 32] {
+    hash_domain(DOMAIN_SUBSCRIPTION_TAG, &[subscription.as_ref(), &period.to_le_bytes()])
+}
+
+/// Compute reward commitment from proof
+pub(crate) fn compute_reward_commitment(proof: &[u8]) -> [u8; 32] {
+    if proof.len() >= 32 {
+        let mut result = [0u8; 32];
+        result.copy_from_slice(&proof[0..32]);
+        result
+    } else {
+        hash_domain(DOMAIN_REWARD, &[proof])
+    }
+}
+
+/// Compute vote commitment: H(DOMAIN_VOTE, vote_choice || secret || voter)
+pub(crate) fn compute_vote_commitment(vote_choice: bool, secret: &[u8; 32], voter: &Pubkey) -> [u8; 32] {
+    hash_domain(
+        DOMAIN_VOTE,
+        &[&[vote_choice as u8], secret, voter.as_ref()],
+    )
+}
+
+/// Compute the reveal token hash a vote record stores for delegated
+/// reveals: H(DOMAIN_REVEAL_TOKEN, reveal_token)
+pub(crate) fn compute_reveal_token_hash(reveal_token: &[u8; 32]) -> [u8; 32] {
+    hash_domain(DOMAIN_REVEAL_TOKEN, &[reveal_token])
+}
+
+/// Leaf of a proposal eligibility tree: H(DOMAIN_ELIGIBILITY_LEAF, voter)
+pub(crate) fn compute_eligibility_leaf(voter: &Pubkey) -> [u8; 32] {
+    hash_domain(DOMAIN_ELIGIBILITY_LEAF, &[voter.as_ref()])
+}
+
+/// Commitment to a proposal's tally before its dispute window:
+/// H(DOMAIN_TALLY_COMMITMENT, proposal, yes_count, no_count, yes_weight,
+/// no_weight, quorum_failed, salt), counts as u64 little-endian and the
+/// commit slot as salt
+pub(crate) fn compute_tally_commitment(
+    proposal: &Pubkey,
+    counts: &[u64; 4],
+    quorum_failed: bool,
+    salt: u64,
+) -> [u8; 32] {
+    let [yes_count, no_count, yes_weight, no_weight] = counts.map(u64::to_le_bytes);
+    hash_domain(
+        DOMAIN_TALLY_COMMITMENT,
+        &[
+            proposal.as_ref(),
+            &yes_count,
+            &no_count,
+            &yes_weight,
+            &no_weight,
+            &[quorum_failed as u8],
+            &salt.to_le_bytes(),
+        ],
+    )
+}
+
+/// Tag binding a conviction proof to one stake and the accrual it claims:
+/// H(DOMAIN_CONVICTION, stake, conviction, elapsed, ramp_period,
+/// weight_unit), all little-endian
+pub(crate) fn compute_conviction_tag(
+    stake: &Pubkey,
+    conviction: u64,
+    elapsed: i64,
+    ramp_period: i64,
+    weight_unit: u64,
+) -> [u8; 32] {
+    hash_domain(
+        DOMAIN_CONVICTION,
+        &[
+            stake.as_ref(),
+            &conviction.to_le_bytes(),
+            &elapsed.to_le_bytes(),
+            &ramp_period.to_le_bytes(),
+            &weight_unit.to_le_bytes(),
+        ],
+    )
+}
+
+/// Tag binding a quadratic vote proof to one proposal and the votes it
+/// adds per option: H(DOMAIN_QUADRATIC_VOTE, proposal, votes...), each
+/// vote count as u64 little-endian
+pub(crate) fn compute_quadratic_vote_tag(proposal: &Pubkey, votes: &[u64]) -> [u8; 32] {
+    let encoded: Vec<u8> = votes.iter().flat_map(|votes| votes.to_le_bytes()).collect();
+    hash_domain(DOMAIN_QUADRATIC_VOTE, &[proposal.as_ref(), &encoded])
+}
+
+/// Compute beacon commitment: H(DOMAIN_BEACON_COMMIT, secret || participant)
+pub(crate) fn compute_beacon_commitment(secret: &[u8; 32], participant: &Pubkey) -> [u8; 32] {
+    hash_domain(DOMAIN_BEACON_COMMIT, &[secret, participant.as_ref()])
+}
+
+/// Compute beacon value: H(DOMAIN_BEACON, round || accumulator || reveal_count)
+pub(crate) fn compute_beacon_value(round: &Pubkey, accumulator: &[u8; 32], reveal_count: u32) -> [u8; 32] {
+    hash_domain(
+        DOMAIN_BEACON,
+        &[round.as_ref(), accumulator, &reveal_count.to_le_bytes()],
+    )
+}
+
+/// Compute v0 (untagged) vote commitment: mixer(vote_choice || secret || voter)
+/// Only used to reveal votes cast before LEGACY_VOTE_COMMITMENT_CUTOFF, so
+/// it keeps the hash those votes were committed with
+pub(crate) fn compute_vote_commitment_v0(vote_choice: bool, secret: &[u8; 32], voter: &Pubkey) -> [u8; 32] {
+    let mut data = [0u8; 1 + 32 + 32];
+    data[0] = vote_choice as u8;
+    data[1..33].copy_from_slice(secret);
+    data[33..].copy_from_slice(voter.as_ref());
+    legacy_mixer_hash(&data)
+}
+
+/// Compute stake commitment: H(DOMAIN_STAKE, amount || validator_commitment || staker || secret)
+#[allow(dead_code)]
+pub(crate) fn compute_stake_commitment(
+    amount_lamports: u64,
+    validator_commitment: &[u8; 32],
+    staker: &Pubkey,
+    secret: &[u8; 32],
+) -> [u8; 32] {
+    hash_domain(
+        DOMAIN_STAKE,
+        &[
+            &amount_lamports.to_le_bytes(),
+            validator_commitment,
+            staker.as_ref(),
+            secret,
+        ],
+    )
+}
+
+/// Compute RLN identity commitment: H(DOMAIN_RLN_IDENTITY, identity_secret)
+pub(crate) fn compute_rln_identity_commitment(identity_secret: &[u8; 32]) -> [u8; 32] {
+    hash_domain(DOMAIN_RLN_IDENTITY, &[identity_secret])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::hex32;
+
+    const SECRET: [u8; 32] = [7; 32];
+
+    fn voter() -> Pubkey {
+        Pubkey::new_from_array([9; 32])
+    }
+
+    #[test]
+    fn vote_commitment_vectors() {
+        assert_eq!(
+            compute_vote_commitment(true, &SECRET, &voter()),
+            hex32("66e85994ce271a150e2d3ce1c027eb8637b4b740cea1bfac00e33f7f12d849fc")
+        );
+        // v0 is the legacy mixer with no domain tag
+        assert_eq!(
+            compute_vote_commitment_v0(true, &SECRET, &voter()),
+            hex32("04f4043424f4f4b4d4b43cfc04c424844c9c24fcbcfc8c5c8c9e7a7a7afa9a37")
+        );
+        assert_ne!(
+            compute_vote_commitment(true, &SECRET, &voter()),
+            compute_vote_commitment(false, &SECRET, &voter())
+        );
+        assert_ne!(
+            compute_vote_commitment(true, &SECRET, &voter()),
+            compute_vote_commitment(true, &SECRET, &Pubkey::new_from_array([8; 32]))
+        );
+    }
+
+    #[test]
+    fn stake_commitment_vector() {
+        // The amount is little-endian, unlike u64 public signals
+        assert_eq!(
+            compute_stake_commitment(1_000_000_000, &[3; 32], &voter(), &SECRET),
+            hex32("aeb4becfbc3ac741fd4fb56e2e50c0fd5ca5e9c840d143d30dc90a60948a7752")
+        );
+    }
+
+    #[test]
+    fn beacon_value_vector() {
+        let round = Pubkey::new_from_array([5; 32]);
+        assert_eq!(
+            compute_beacon_value(&round, &[6; 32], 3),
+            hex32("e2c98e2bc026490c6eb935ebf2a9b7e4fea08cf4ff2954b2b50e12f1c28ce008")
+        );
+        assert_ne!(compute_beacon_value(&round, &[6; 32], 3), compute_beacon_value(&round, &[6; 32], 4));
+    }
+
+    #[test]
+    fn commitments_are_domain_separated() {
+        // Same 32-byte input under different contexts
+        let input = [4u8; 32];
+        let pool = Pubkey::new_from_array(input);
+        let digests = [
+            compute_rln_identity_commitment(&input),
+            compute_reserve_tag(&pool),
+            compute_pool_binding(&pool),
+            compute_veto_tag(&pool),
+            hash_domain(DOMAIN_NULLIFIER, &[&input]),
+        ];
+        for (i, digest) in digests.iter().enumerate() {
+            for other in &digests[i + 1..] {
+                assert_ne!(digest, other);
+            }
+        }
+    }
+
+    #[test]
+    fn pool_binding_is_per_pool() {
+        let a = Pubkey::new_from_array([7; 32]);
+        let b = Pubkey::new_from_array([8; 32]);
+        assert_eq!(
+            compute_pool_binding(&a),
+            hex32("8839235e862df1dd8694248ec853354d5d27944d15ad43746dccd44c6fc7e510")
+        );
+        assert_ne!(compute_pool_binding(&a), compute_pool_binding(&b));
+    }
+
+    #[test]
+    fn holding_tags_bind_amount_and_recipient() {
+        let pool = Pubkey::new_from_array([7; 32]);
+        let release = HoldingRelease { claim_key_hash: [8; 32], lamports: 1000 };
+        assert_eq!(
+            compute_holding_tag(&pool, &release),
+            hex32("bb0f50e3bf7349394d85f2faef49f978e6f1771ae7af50a4a80b251cbfe9aebe")
+        );
+        let more = HoldingRelease { lamports: 1001, ..release };
+        assert_ne!(compute_holding_tag(&pool, &more), compute_holding_tag(&pool, &release));
+
+        let recipient = Pubkey::new_from_array([9; 32]);
+        assert_eq!(
+            compute_holding_claim_tag(&pool, &recipient),
+            hex32("186ac30cde9ce148afe158e39360cb906b3650ad3a7026274a35ab60645eb1fe")
+        );
+        assert_ne!(compute_holding_claim_tag(&pool, &recipient), compute_holding_claim_tag(&pool, &pool));
+    }
+
+    #[test]
+    fn reward_commitment_takes_the_proof_prefix() {
+        let mut proof = [0u8; 64];
+        proof[..32].copy_from_slice(&[1; 32]);
+        assert_eq!(compute_reward_commitment(&proof), [1; 32]);
+        assert_eq!(compute_reward_commitment(&[2; 31]), hash_domain(DOMAIN_REWARD, &[&[2; 31]]));
+    }
+
+    #[test]
+    fn key_share_commitment_binds_index() {
+        let proposal = Pubkey::new_from_array([1; 32]);
+        let share = |index| ProposalKeyShare { index, share: [3; 32] };
+        assert_ne!(
+            compute_key_share_commitment(&proposal, &[2; 32], &share(1)),
+            compute_key_share_commitment(&proposal, &[2; 32], &share(2))
+        );
+    }
+
+    #[test]
+    fn tags_bind_every_field() {
+        let pool = Pubkey::new_from_array([1; 32]);
+        let base = compute_note_sweep_tag(&pool, 0, 10, 500);
+        assert_ne!(base, compute_note_sweep_tag(&pool, 1, 10, 500));
+        assert_ne!(base, compute_note_sweep_tag(&pool, 0, 11, 500));
+        assert_ne!(base, compute_note_sweep_tag(&pool, 0, 10, 501));
+        // Fixed-width fields: shifting a byte between them changes the tag
+        assert_ne!(compute_note_sweep_tag(&pool, 0x100, 0, 0), compute_note_sweep_tag(&pool, 0, 1, 0));
+    }
+}
@@ -0,0 +1,299 @@
+//! BN128 curve point validation for Groth16 proofs
+
+use super::field::{limbs_from_be_bytes, limbs_gte, limbs_sub, Limbs};
+use crate::*;
+
+// ============================================
+// GROTH16 PROOF POINTS
+//
+// A 256-byte proof is pi_a (G1, 64) || pi_b (G2, 128) || pi_c (G1, 64),
+// big-endian in the alt_bn128 syscall (EIP-197) layout: G1 is x || y and
+// G2 is x.c1 || x.c0 || y.c1 || y.c0, imaginary part first.
+//
+// G1 has cofactor 1, so a G1 point on the curve is in the prime-order
+// subgroup. G2 does not, but [r]P costs far more compute than a
+// transaction has; G2 subgroup membership is left to the alt_bn128
+// pairing syscall, which rejects points outside it.
+// ============================================
+
+/// Base field modulus q as little-endian limbs (BN128_MODULUS)
+const Q: Limbs = [0x3c208c16d87cfd47, 0x97816a916871ca8d, 0xb85045b68181585d, 0x30644e72e131a029];
+
+/// -q^-1 mod 2^64
+const Q_INV: u64 = 0x87d20782e4866389;
+
+/// R^2 mod q with R = 2^256, to enter Montgomery form
+const R2: Limbs = [0xf32cfc5b538afa89, 0xb5e71911d44501fb, 0x47ab1eff0a417ff6, 0x06d89f71cab8351f];
+
+/// 3, the G1 curve coefficient b, in Montgomery form
+const G1_B: Fq = Fq([0x7a17caa950ad28d7, 0x1f6ac17ae15521b9, 0x334bea4e696bd284, 0x2a1f6744ce179d8e]);
+
+/// 3 / (9 + u), the G2 twist coefficient b', in Montgomery form
+const G2_B: Fq2 = Fq2(
+    Fq([0x3bf938e377b802a8, 0x020b1b273633535d, 0x26b7edf049755260, 0x2514c6324384a86d]),
+    Fq([0x38e7ecccd1dcff67, 0x65f0b37d93ce0d3e, 0xd749d0dd22ac00aa, 0x0141b9ce4a688d4d]),
+);
+
+/// Base field element in Montgomery form (a * R mod q)
+/// Kept apart from `FieldElement`: a multiplication here is a few dozen
+/// word products instead of 256 doublings, which the curve checks need
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Fq(Limbs);
+
+impl Fq {
+    /// Parse a canonical big-endian coordinate
+    fn from_be_bytes(bytes: &[u8]) -> Option<Self> {
+        if !verify_field_element(bytes) {
+            return None;
+        }
+        let mut coordinate = [0u8; 32];
+        coordinate.copy_from_slice(bytes);
+        Some(Self(limbs_from_be_bytes(&coordinate)).mul(&Self(R2)))
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        let mut result = [0u64; 4];
+        let mut carry = 0u128;
+        for (limb, (a, b)) in result.iter_mut().zip(self.0.iter().zip(&other.0)) {
+            let sum = *a as u128 + *b as u128 + carry;
+            *limb = sum as u64;
+            carry = sum >> 64;
+        }
+        // q < 2^254, so the sum of two reduced values never carries out
+        if limbs_gte(&result, &Q) {
+            result = limbs_sub(&result, &Q);
+        }
+        Self(result)
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        if limbs_gte(&self.0, &other.0) {
+            Self(limbs_sub(&self.0, &other.0))
+        } else {
+            Self(limbs_sub(&Q, &limbs_sub(&other.0, &self.0)))
+        }
+    }
+
+    /// Montgomery product a * b * R^-1 mod q (CIOS)
+    #[allow(clippy::needless_range_loop)]
+    fn mul(&self, other: &Self) -> Self {
+        let (a, b) = (&self.0, &other.0);
+        let mut t = [0u64; 6];
+        for i in 0..4 {
+            let mut carry = 0u64;
+            for j in 0..4 {
+                let sum = t[j] as u128 + a[j] as u128 * b[i] as u128 + carry as u128;
+                t[j] = sum as u64;
+                carry = (sum >> 64) as u64;
+            }
+            let sum = t[4] as u128 + carry as u128;
+            t[4] = sum as u64;
+            t[5] = (sum >> 64) as u64;
+
+            let m = t[0].wrapping_mul(Q_INV);
+            let sum = t[0] as u128 + m as u128 * Q[0] as u128;
+            let mut carry = (sum >> 64) as u64;
+            for j in 1..4 {
+                let sum = t[j] as u128 + m as u128 * Q[j] as u128 + carry as u128;
+                t[j - 1] = sum as u64;
+                carry = (sum >> 64) as u64;
+            }
+            let sum = t[4] as u128 + carry as u128;
+            t[3] = sum as u64;
+            t[4] = t[5] + (sum >> 64) as u64;
+        }
+        let mut result = [t[0], t[1], t[2], t[3]];
+        if t[4] != 0 || limbs_gte(&result, &Q) {
+            result = limbs_sub(&result, &Q);
+        }
+        Self(result)
+    }
+}
+
+/// Quadratic extension element c0 + c1 * u with u^2 = -1
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Fq2(Fq, Fq);
+
+impl Fq2 {
+    fn add(&self, other: &Self) -> Self {
+        Self(self.0.add(&other.0), self.1.add(&other.1))
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        let real = self.0.mul(&other.0).sub(&self.1.mul(&other.1));
+        let imaginary = self.0.mul(&other.1).add(&self.1.mul(&other.0));
+        Self(real, imaginary)
+    }
+}
+
+/// y^2 == x^3 + 3
+fn g1_on_curve(x: &Fq, y: &Fq) -> bool {
+    y.mul(y) == x.mul(x).mul(x).add(&G1_B)
+}
+
+/// y^2 == x^3 + 3 / (9 + u)
+fn g2_on_curve(x: &Fq2, y: &Fq2) -> bool {
+    y.mul(y) == x.mul(x).mul(x).add(&G2_B)
+}
+
+fn parse_g1(point: &[u8]) -> Result<(Fq, Fq)> {
+    let x = Fq::from_be_bytes(&point[0..32]).ok_or(ErrorCode::InvalidProofPoint)?;
+    let y = Fq::from_be_bytes(&point[32..64]).ok_or(ErrorCode::InvalidProofPoint)?;
+    Ok((x, y))
+}
+
+fn parse_g2(point: &[u8]) -> Result<(Fq2, Fq2)> {
+    let coordinate = |offset: usize| {
+        Fq::from_be_bytes(&point[offset..offset + 32]).ok_or(ErrorCode::InvalidProofPoint)
+    };
+    let x = Fq2(coordinate(32)?, coordinate(0)?);
+    let y = Fq2(coordinate(96)?, coordinate(64)?);
+    Ok((x, y))
+}
+
+/// Validate the three points of a Groth16 proof: every coordinate is a
+/// canonical field element, pi_a and pi_c are on the curve and pi_b is on
+/// the twist. The point at infinity is rejected; no honest proof uses it
+pub(crate) fn validate_groth16_points(proof: &[u8]) -> Result<()> {
+    require!(proof.len() >= 256, ErrorCode::InvalidProofStructure);
+
+    let (pi_a, pi_b, pi_c) = (&proof[0..64], &proof[64..192], &proof[192..256]);
+    for point in [pi_a, pi_c] {
+        let (x, y) = parse_g1(point)?;
+        require!(g1_on_curve(&x, &y), ErrorCode::G1PointNotOnCurve);
+    }
+    let (x, y) = parse_g2(pi_b)?;
+    require!(g2_on_curve(&x, &y), ErrorCode::G2PointNotOnCurve);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// G1 generator (1, 2)
+    fn g1_generator() -> [u8; 64] {
+        let mut point = [0u8; 64];
+        point[31] = 1;
+        point[63] = 2;
+        point
+    }
+
+    /// EIP-197 G2 generator
+    fn g2_generator() -> [u8; 128] {
+        let mut point = [0u8; 128];
+        point[0..32].copy_from_slice(&crate::crypto::hex32(
+            "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+        ));
+        point[32..64].copy_from_slice(&crate::crypto::hex32(
+            "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+        ));
+        point[64..96].copy_from_slice(&crate::crypto::hex32(
+            "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+        ));
+        point[96..128].copy_from_slice(&crate::crypto::hex32(
+            "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+        ));
+        point
+    }
+
+    /// 2 * G1
+    fn g1_double() -> [u8; 64] {
+        let mut point = [0u8; 64];
+        point[0..32].copy_from_slice(&crate::crypto::hex32(
+            "030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3",
+        ));
+        point[32..64].copy_from_slice(&crate::crypto::hex32(
+            "15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4",
+        ));
+        point
+    }
+
+    fn proof(pi_a: &[u8; 64], pi_b: &[u8; 128], pi_c: &[u8; 64]) -> Vec<u8> {
+        [pi_a.as_ref(), pi_b, pi_c].concat()
+    }
+
+    fn error(result: Result<()>) -> Error {
+        result.unwrap_err()
+    }
+
+    #[test]
+    fn montgomery_round_trip() {
+        let three = Fq::from_be_bytes(&encode_u64_signal(3)).unwrap();
+        assert!(three == G1_B);
+        // Multiplying by plain 1 leaves Montgomery form
+        let mut one = [0u64; 4];
+        one[0] = 1;
+        assert_eq!(three.mul(&Fq(one)).0, [3, 0, 0, 0]);
+        let two = Fq::from_be_bytes(&encode_u64_signal(2)).unwrap();
+        let six = Fq::from_be_bytes(&encode_u64_signal(6)).unwrap();
+        assert!(two.mul(&three) == six);
+        assert!(six.sub(&three) == three);
+        assert!(three.sub(&six).add(&six) == three);
+    }
+
+    #[test]
+    fn accepts_valid_points() {
+        let valid = proof(&g1_generator(), &g2_generator(), &g1_double());
+        assert!(validate_groth16_points(&valid).is_ok());
+        // Trailing bytes past the three points are not part of them
+        let mut padded = valid.clone();
+        padded.extend_from_slice(&[0xff; 16]);
+        assert!(validate_groth16_points(&padded).is_ok());
+    }
+
+    #[test]
+    fn rejects_g1_points_off_the_curve() {
+        let mut off_curve = g1_generator();
+        off_curve[63] = 3;
+        let result = validate_groth16_points(&proof(&off_curve, &g2_generator(), &g1_generator()));
+        assert_eq!(error(result), ErrorCode::G1PointNotOnCurve.into());
+        let result = validate_groth16_points(&proof(&g1_generator(), &g2_generator(), &off_curve));
+        assert_eq!(error(result), ErrorCode::G1PointNotOnCurve.into());
+        // (0, 0), the syscalls' encoding of infinity
+        let result = validate_groth16_points(&proof(&[0; 64], &g2_generator(), &g1_generator()));
+        assert_eq!(error(result), ErrorCode::G1PointNotOnCurve.into());
+    }
+
+    #[test]
+    fn rejects_g2_points_off_the_twist() {
+        let mut off_twist = g2_generator();
+        off_twist[127] ^= 1;
+        let result = validate_groth16_points(&proof(&g1_generator(), &off_twist, &g1_generator()));
+        assert_eq!(error(result), ErrorCode::G2PointNotOnCurve.into());
+
+        // Real and imaginary parts swapped (c0 first) is a different point
+        let generator = g2_generator();
+        let mut swapped = [0u8; 128];
+        for (offset, from) in [(0, 32), (32, 0), (64, 96), (96, 64)] {
+            swapped[offset..offset + 32].copy_from_slice(&generator[from..from + 32]);
+        }
+        let result = validate_groth16_points(&proof(&g1_generator(), &swapped, &g1_generator()));
+        assert_eq!(error(result), ErrorCode::G2PointNotOnCurve.into());
+    }
+
+    #[test]
+    fn rejects_non_canonical_coordinates() {
+        // Every one of the eight coordinates, including all four of pi_b
+        for coordinate in 0..8 {
+            let mut bytes = proof(&g1_generator(), &g2_generator(), &g1_generator());
+            let offset = coordinate * 32;
+            bytes[offset..offset + 32].copy_from_slice(&BN128_MODULUS);
+            assert_eq!(
+                error(validate_groth16_points(&bytes)),
+                ErrorCode::InvalidProofPoint.into(),
+                "coordinate {coordinate}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_short_proofs() {
+        let valid = proof(&g1_generator(), &g2_generator(), &g1_generator());
+        assert_eq!(
+            error(validate_groth16_points(&valid[..255])),
+            ErrorCode::InvalidProofStructure.into()
+        );
+    }
+}
@@ -0,0 +1,398 @@
+//! BN128 field elements: range checks, byte order, signal encoding and
+//! limb arithmetic
+
+/// BN128 field modulus (for ZK proof verification)
+pub const BN128_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29,
+    0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d,
+    0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
+
+/// Verify a value is a valid big-endian BN128 field element (< modulus)
+/// Runs in constant time: computes value - modulus across all bytes and
+/// checks the final borrow, instead of returning at the first differing byte
+pub(crate) fn verify_field_element(value: &[u8]) -> bool {
+    if value.len() != 32 {
+        return false;
+    }
+    // value < modulus iff (value - modulus) borrows out of the top byte
+    let mut borrow = 0u16;
+    for (byte, modulus_byte) in value.iter().zip(BN128_MODULUS.iter()).rev() {
+        let diff = (*byte as u16)
+            .wrapping_sub(*modulus_byte as u16)
+            .wrapping_sub(borrow);
+        borrow = (diff >> 15) & 1;
+    }
+    core::hint::black_box(borrow) == 1 // Equal to modulus is not valid
+}
+
+/// Encode a u64 as a big-endian field element public signal
+pub(crate) fn encode_u64_signal(value: u64) -> [u8; 32] {
+    let mut signal = [0u8; 32];
+    signal[24..].copy_from_slice(&value.to_be_bytes());
+    signal
+}
+
+/// Decode a big-endian field element public signal into a u64
+/// Returns None if the value does not fit in 64 bits
+pub(crate) fn decode_u64_signal(signal: &[u8; 32]) -> Option<u64> {
+    if signal[..24].iter().any(|b| *b != 0) {
+        return None;
+    }
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&signal[24..]);
+    Some(u64::from_be_bytes(bytes))
+}
+
+// ============================================
+// BN128 FIELD ELEMENTS
+//
+// Every 32-byte field element the program reads, stores or emits (public
+// signals, proof coordinates, RLN shares) is big-endian, the byte order of
+// the alt_bn128 syscalls. circom witnesses and snarkjs/ffjavascript
+// (`toRprLE`) are little-endian, so off-chain tooling must convert with
+// `FieldElement::from_le_bytes` + `to_be_bytes` rather than reinterpret
+// the bytes: a little-endian value that happens to be below the modulus
+// when read big-endian is a different element, not an error.
+//
+// In memory an element is 4 little-endian u64 limbs, always reduced.
+// ============================================
+
+/// Byte order of a 32-byte field element encoding
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endianness {
+    /// Most significant byte first (the program's encoding)
+    Big,
+    /// Least significant byte first (circom/snarkjs)
+    Little,
+}
+
+/// 256-bit value as little-endian u64 limbs, not necessarily reduced
+pub(super) type Limbs = [u64; 4];
+
+/// Reduced BN128 field element (< BN128_MODULUS)
+#[derive(Clone, Copy, Debug)]
+pub struct FieldElement(Limbs);
+
+impl FieldElement {
+    pub const ZERO: Self = Self([0; 4]);
+    pub const ONE: Self = Self([1, 0, 0, 0]);
+
+    pub fn from_u64(value: u64) -> Self {
+        Self([value, 0, 0, 0])
+    }
+
+    /// Parse a big-endian encoding; None unless it is below the modulus
+    pub fn from_be_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        verify_field_element(bytes).then(|| Self(limbs_from_be_bytes(bytes)))
+    }
+
+    /// Parse a little-endian encoding; None unless it is below the modulus
+    pub fn from_le_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let mut be = *bytes;
+        be.reverse();
+        Self::from_be_bytes(&be)
+    }
+
+    pub fn from_bytes(bytes: &[u8; 32], endianness: Endianness) -> Option<Self> {
+        match endianness {
+            Endianness::Big => Self::from_be_bytes(bytes),
+            Endianness::Little => Self::from_le_bytes(bytes),
+        }
+    }
+
+    /// Parse an encoding of unknown byte order
+    /// Big-endian wins whenever the bytes are in range that way; they are
+    /// only read little-endian when big-endian is out of range, which no
+    /// big-endian encoder can produce. A little-endian value that is also
+    /// in range big-endian is therefore misread, so only use this where a
+    /// wrong element fails a check rather than being stored.
+    pub fn from_bytes_detect(bytes: &[u8; 32]) -> Option<(Self, Endianness)> {
+        if let Some(element) = Self::from_be_bytes(bytes) {
+            return Some((element, Endianness::Big));
+        }
+        Self::from_le_bytes(bytes).map(|element| (element, Endianness::Little))
+    }
+
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (i, limb) in self.0.iter().enumerate() {
+            let start = 32 - (i + 1) * 8;
+            bytes[start..start + 8].copy_from_slice(&limb.to_be_bytes());
+        }
+        bytes
+    }
+
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let mut bytes = self.to_be_bytes();
+        bytes.reverse();
+        bytes
+    }
+
+    pub fn to_bytes(&self, endianness: Endianness) -> [u8; 32] {
+        match endianness {
+            Endianness::Big => self.to_be_bytes(),
+            Endianness::Little => self.to_le_bytes(),
+        }
+    }
+
+    /// Constant-time equality
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        let diff = self.0.iter().zip(&other.0).fold(0u64, |acc, (x, y)| acc | (x ^ y));
+        core::hint::black_box(diff) == 0
+    }
+}
+
+/// (a + b) mod p
+impl core::ops::Add for FieldElement {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let mut result = [0u64; 4];
+        let mut carry = 0u128;
+        for (limb, (a, b)) in result.iter_mut().zip(self.0.iter().zip(&other.0)) {
+            let sum = *a as u128 + *b as u128 + carry;
+            *limb = sum as u64;
+            carry = sum >> 64;
+        }
+        let modulus = field_modulus();
+        if carry != 0 || limbs_gte(&result, &modulus) {
+            result = limbs_sub(&result, &modulus);
+        }
+        Self(result)
+    }
+}
+
+/// (a - b) mod p
+impl core::ops::Sub for FieldElement {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        if limbs_gte(&self.0, &other.0) {
+            Self(limbs_sub(&self.0, &other.0))
+        } else {
+            // a - b + p, computed as p - (b - a)
+            Self(limbs_sub(&field_modulus(), &limbs_sub(&other.0, &self.0)))
+        }
+    }
+}
+
+/// (a * b) mod p via double-and-add
+impl core::ops::Mul for FieldElement {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let mut result = Self::ZERO;
+        for i in (0..4).rev() {
+            for bit in (0..64).rev() {
+                result = result + result;
+                if (other.0[i] >> bit) & 1 == 1 {
+                    result = result + self;
+                }
+            }
+        }
+        result
+    }
+}
+
+/// BN128_MODULUS as limbs
+fn field_modulus() -> Limbs {
+    limbs_from_be_bytes(&BN128_MODULUS)
+}
+
+pub(super) fn limbs_from_be_bytes(bytes: &[u8; 32]) -> Limbs {
+    let mut limbs = [0u64; 4];
+    for (i, limb) in limbs.iter_mut().enumerate() {
+        let start = 32 - (i + 1) * 8;
+        let mut chunk = [0u8; 8];
+        chunk.copy_from_slice(&bytes[start..start + 8]);
+        *limb = u64::from_be_bytes(chunk);
+    }
+    limbs
+}
+
+/// a >= b
+pub(super) fn limbs_gte(a: &Limbs, b: &Limbs) -> bool {
+    for i in (0..4).rev() {
+        if a[i] != b[i] {
+            return a[i] > b[i];
+        }
+    }
+    true
+}
+
+/// Raw 256-bit subtraction (wrapping)
+pub(super) fn limbs_sub(a: &Limbs, b: &Limbs) -> Limbs {
+    let mut result = [0u64; 4];
+    let mut borrow = 0u64;
+    for i in 0..4 {
+        let (d1, b1) = a[i].overflowing_sub(b[i]);
+        let (d2, b2) = d1.overflowing_sub(borrow);
+        result[i] = d2;
+        borrow = (b1 || b2) as u64;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::hex32;
+
+    fn modulus_minus(value: u64) -> FieldElement {
+        FieldElement(limbs_sub(&field_modulus(), &[value, 0, 0, 0]))
+    }
+
+    fn be(element: FieldElement) -> [u8; 32] {
+        element.to_be_bytes()
+    }
+
+    #[test]
+    fn modulus_vector() {
+        assert_eq!(
+            BN128_MODULUS,
+            hex32("30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47")
+        );
+        assert_eq!(
+            field_modulus(),
+            [0x3c208c16d87cfd47, 0x97816a916871ca8d, 0xb85045b68181585d, 0x30644e72e131a029]
+        );
+    }
+
+    #[test]
+    fn field_element_range_boundaries() {
+        assert!(verify_field_element(&[0u8; 32]));
+        assert!(verify_field_element(&be(FieldElement::ONE)));
+        assert!(verify_field_element(&be(modulus_minus(1))));
+        assert!(!verify_field_element(&BN128_MODULUS));
+        assert!(!verify_field_element(&[0xff; 32]));
+
+        // Only the lowest byte differs: the borrow has to ripple through all 32
+        let mut above = BN128_MODULUS;
+        above[31] += 1;
+        assert!(!verify_field_element(&above));
+
+        // Top byte below the modulus, every other byte maxed out
+        let mut below = [0xffu8; 32];
+        below[0] = BN128_MODULUS[0] - 1;
+        assert!(verify_field_element(&below));
+    }
+
+    #[test]
+    fn field_element_rejects_wrong_lengths() {
+        assert!(!verify_field_element(&[]));
+        assert!(!verify_field_element(&[0u8; 31]));
+        assert!(!verify_field_element(&[0u8; 33]));
+        assert!(!verify_field_element(&BN128_MODULUS[1..]));
+    }
+
+    #[test]
+    fn byte_order_round_trips() {
+        let bytes = hex32("0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20");
+        let element = FieldElement::from_be_bytes(&bytes).unwrap();
+        assert_eq!(element.0[0], 0x191a1b1c1d1e1f20);
+        assert_eq!(element.0[3], 0x0102030405060708);
+        assert_eq!(element.to_be_bytes(), bytes);
+
+        let mut reversed = bytes;
+        reversed.reverse();
+        assert_eq!(element.to_le_bytes(), reversed);
+        assert!(FieldElement::from_le_bytes(&reversed).unwrap().ct_eq(&element));
+        for endianness in [Endianness::Big, Endianness::Little] {
+            let encoded = element.to_bytes(endianness);
+            assert!(FieldElement::from_bytes(&encoded, endianness).unwrap().ct_eq(&element));
+        }
+    }
+
+    #[test]
+    fn both_byte_orders_enforce_the_modulus() {
+        let mut modulus_le = BN128_MODULUS;
+        modulus_le.reverse();
+        assert!(FieldElement::from_be_bytes(&BN128_MODULUS).is_none());
+        assert!(FieldElement::from_le_bytes(&modulus_le).is_none());
+        assert!(FieldElement::from_le_bytes(&modulus_minus(1).to_le_bytes()).is_some());
+    }
+
+    #[test]
+    fn detection_prefers_big_endian() {
+        // Out of range big-endian, so it can only be little-endian
+        let large = modulus_minus(1);
+        let (element, endianness) = FieldElement::from_bytes_detect(&large.to_le_bytes()).unwrap();
+        assert_eq!(endianness, Endianness::Little);
+        assert!(element.ct_eq(&large));
+
+        // In range both ways: read big-endian. A little-endian 1 is 2^248
+        let mut little_endian_one = [0u8; 32];
+        little_endian_one[0] = 1;
+        let (element, endianness) = FieldElement::from_bytes_detect(&little_endian_one).unwrap();
+        assert_eq!(endianness, Endianness::Big);
+        assert_eq!(element.0, [0, 0, 0, 1 << 56]);
+
+        // Out of range both ways
+        assert!(FieldElement::from_bytes_detect(&[0xff; 32]).is_none());
+    }
+
+    #[test]
+    fn u64_signals_are_big_endian() {
+        let signal = encode_u64_signal(0x0102030405060708);
+        assert_eq!(signal[..24], [0u8; 24]);
+        assert_eq!(signal[24..], [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert!(FieldElement::from_be_bytes(&signal).unwrap().ct_eq(&FieldElement::from_u64(0x0102030405060708)));
+
+        for value in [0, 1, u32::MAX as u64, u64::MAX] {
+            assert_eq!(decode_u64_signal(&encode_u64_signal(value)), Some(value));
+        }
+
+        let mut wide = encode_u64_signal(u64::MAX);
+        wide[23] = 1;
+        assert_eq!(decode_u64_signal(&wide), None);
+        let mut top = [0u8; 32];
+        top[0] = 1;
+        assert_eq!(decode_u64_signal(&top), None);
+    }
+
+    #[test]
+    fn comparisons() {
+        let p = field_modulus();
+        assert!(modulus_minus(1).ct_eq(&modulus_minus(1)));
+        assert!(!modulus_minus(1).ct_eq(&modulus_minus(2)));
+        assert!(limbs_gte(&p, &p));
+        assert!(limbs_gte(&p, &modulus_minus(1).0));
+        assert!(!limbs_gte(&modulus_minus(1).0, &p));
+        // Decided by the highest differing limb, not the lowest
+        assert!(limbs_gte(&[0, 0, 0, 1], &[u64::MAX, u64::MAX, u64::MAX, 0]));
+    }
+
+    #[test]
+    fn addition_and_subtraction_wrap_at_the_modulus() {
+        let n = FieldElement::from_u64;
+        assert!((n(2) + n(3)).ct_eq(&n(5)));
+        assert!((modulus_minus(1) + FieldElement::ONE).ct_eq(&FieldElement::ZERO));
+        assert!((modulus_minus(1) + modulus_minus(1)).ct_eq(&modulus_minus(2)));
+        assert!((n(5) - n(3)).ct_eq(&n(2)));
+        assert!((FieldElement::ZERO - FieldElement::ONE).ct_eq(&modulus_minus(1)));
+        assert!((n(3) - n(5)).ct_eq(&modulus_minus(2)));
+    }
+
+    #[test]
+    fn multiplication_vectors() {
+        let n = FieldElement::from_u64;
+        assert!((n(3) * n(5)).ct_eq(&n(15)));
+        assert!((modulus_minus(1) * FieldElement::ONE).ct_eq(&modulus_minus(1)));
+        assert!((modulus_minus(1) * FieldElement::ZERO).ct_eq(&FieldElement::ZERO));
+        // (-1)^2 = 1
+        assert!((modulus_minus(1) * modulus_minus(1)).ct_eq(&FieldElement::ONE));
+
+        let two_128 = FieldElement([0, 0, 1, 0]);
+        assert_eq!(
+            be(two_128 * two_128),
+            hex32("0e0a77c19a07df2f666ea36f7879462c0a78eb28f5c70b3dd35d438dc58f0d9d")
+        );
+        let a = FieldElement::from_be_bytes(&hex32(
+            "0000000000000000ea5b3d7c8f9e0dcb1234567890abcdef1234567890abcdef",
+        ))
+        .unwrap();
+        assert!((a * modulus_minus(5)).ct_eq(&(FieldElement::ZERO - a * n(5))));
+    }
+}
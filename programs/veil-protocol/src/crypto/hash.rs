@@ -0,0 +1,351 @@
+//! Domain-separated SHA-256 hashing
+
+/// SHA-256 of the concatenation of several slices
+/// On-chain this is a single `sol_sha256` syscall that reads the slices in
+/// place (iovec-style), so parts are never copied into a temporary buffer.
+pub(crate) fn hashv(parts: &[&[u8]]) -> HashResult {
+    HashResult(solana_sha256_hasher::hashv(parts).to_bytes())
+}
+
+pub(crate) struct HashResult([u8; 32]);
+
+/// The byte mixer every hash used before the move to SHA-256 (schemes v0
+/// and v1). Only kept to check v0 vote commitments cast before
+/// LEGACY_VOTE_COMMITMENT_CUTOFF; nothing new may be hashed with it.
+pub(crate) fn legacy_mixer_hash(data: &[u8]) -> [u8; 32] {
+    let mut result = [0u8; 32];
+    for (i, byte) in data.iter().enumerate() {
+        let idx = i % 32;
+        result[idx] = result[idx].wrapping_add(*byte);
+        result[(idx + 1) % 32] = result[(idx + 1) % 32].wrapping_mul(byte.wrapping_add(1));
+        result[(idx + 7) % 32] ^= byte.rotate_left((i % 8) as u32);
+    }
+
+    // Additional mixing rounds
+    let len = data.len();
+    for round in 0..4u8 {
+        for i in 0..32 {
+            result[i] = result[i]
+                .wrapping_add(result[(i + 1) % 32])
+                .wrapping_mul(result[(i + 7) % 32].wrapping_add(1))
+                ^ (len as u8).wrapping_add(round);
+        }
+    }
+    result
+}
+
+impl HashResult {
+    pub(crate) fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+}
+
+/// Version of the hash domain-separation scheme (see `hash_domain`)
+pub const HASH_DOMAIN_VERSION: u8 = 2;
+/// Domain tags for every protocol hash context
+pub const DOMAIN_VOTE: &[u8] = b"veil:vote:v2";
+pub const DOMAIN_STAKE: &[u8] = b"veil:stake:v2";
+pub const DOMAIN_NOTE_INSERT: &[u8] = b"veil:note_insert:v2";
+pub const DOMAIN_MERKLE_NODE: &[u8] = b"veil:merkle_node:v2";
+pub const DOMAIN_PROOF: &[u8] = b"veil:proof:v2";
+pub const DOMAIN_PUBLIC_SIGNALS: &[u8] = b"veil:public_signals:v2";
+pub const DOMAIN_REWARD: &[u8] = b"veil:reward:v2";
+pub const DOMAIN_NULLIFIER: &[u8] = b"veil:nullifier:v2";
+pub const DOMAIN_RLN_IDENTITY: &[u8] = b"veil:rln_identity:v2";
+pub const DOMAIN_TREE_CHECKPOINT: &[u8] = b"veil:tree_checkpoint:v2";
+pub const DOMAIN_CAMPAIGN_TAG: &[u8] = b"veil:campaign_tag:v2";
+pub const DOMAIN_CAMPAIGN_AGGREGATE: &[u8] = b"veil:campaign_aggregate:v2";
+pub const DOMAIN_BEACON_COMMIT: &[u8] = b"veil:beacon_commit:v2";
+pub const DOMAIN_BEACON: &[u8] = b"veil:beacon:v2";
+pub const DOMAIN_MULTISIG_ACTION: &[u8] = b"veil:multisig_action:v2";
+pub const DOMAIN_PROPOSAL_KEY: &[u8] = b"veil:proposal_key:v2";
+pub const DOMAIN_PROPOSAL_KEY_SHARE: &[u8] = b"veil:proposal_key_share:v2";
+pub const DOMAIN_PROPOSAL_STREAM: &[u8] = b"veil:proposal_stream:v2";
+pub const DOMAIN_RESERVE: &[u8] = b"veil:reserve:v2";
+pub const DOMAIN_CLAIM_KEY: &[u8] = b"veil:claim_key:v2";
+pub const DOMAIN_ESCROW_TAG: &[u8] = b"veil:escrow_tag:v2";
+pub const DOMAIN_SUBSCRIPTION_TAG: &[u8] = b"veil:subscription_tag:v2";
+pub const DOMAIN_VETO_TAG: &[u8] = b"veil:veto_tag:v2";
+pub const DOMAIN_WALLET_CALL: &[u8] = b"veil:wallet_call:v2";
+pub const DOMAIN_RELAYER_FEE: &[u8] = b"veil:relayer_fee:v2";
+pub const DOMAIN_CIRCUIT_ID: &[u8] = b"veil:circuit_id:v2";
+pub const DOMAIN_VERIFYING_KEY: &[u8] = b"veil:verifying_key:v2";
+pub const DOMAIN_ATTESTATION_KV: &[u8] = b"veil:attestation_kv:v2";
+pub const DOMAIN_APPROVAL_REVOCATION: &[u8] = b"veil:approval_revocation:v2";
+pub const DOMAIN_STAKE_WEIGHT: &[u8] = b"veil:stake_weight:v2";
+pub const DOMAIN_WALLET_UNFREEZE: &[u8] = b"veil:wallet_unfreeze:v2";
+pub const DOMAIN_REFERRAL_CLAIM: &[u8] = b"veil:referral_claim:v2";
+pub const DOMAIN_NOTE_SWEEP: &[u8] = b"veil:note_sweep:v2";
+pub const DOMAIN_SPEND_ANCHOR: &[u8] = b"veil:spend_anchor:v2";
+pub const DOMAIN_AUDIT_LOG: &[u8] = b"veil:audit_log:v2";
+/// Client-side only: alias_hash = H(DOMAIN_ALIAS, name || salt)
+pub const DOMAIN_ALIAS: &[u8] = b"veil:alias:v2";
+pub const DOMAIN_NULLIFIER_FILTER: &[u8] = b"veil:nullifier_filter:v2";
+pub const DOMAIN_POOL_BINDING: &[u8] = b"veil:pool_binding:v2";
+pub const DOMAIN_HOLDING: &[u8] = b"veil:holding:v2";
+pub const DOMAIN_HOLDING_CLAIM: &[u8] = b"veil:holding_claim:v2";
+pub const DOMAIN_TREASURY_BAND: &[u8] = b"veil:treasury_band:v2";
+pub const DOMAIN_SMALL_SPEND: &[u8] = b"veil:small_spend:v2";
+pub const DOMAIN_SIGNER_HEARTBEAT: &[u8] = b"veil:signer_heartbeat:v2";
+pub const DOMAIN_SIGNER_ROTATION: &[u8] = b"veil:signer_rotation:v2";
+pub const DOMAIN_STEALTH_APPROVAL: &[u8] = b"veil:stealth_approval:v2";
+pub const DOMAIN_REVEAL_TOKEN: &[u8] = b"veil:reveal_token:v2";
+pub const DOMAIN_ELIGIBILITY_LEAF: &[u8] = b"veil:eligibility_leaf:v2";
+pub const DOMAIN_TALLY_COMMITMENT: &[u8] = b"veil:tally_commitment:v2";
+pub const DOMAIN_CONVICTION: &[u8] = b"veil:conviction:v2";
+pub const DOMAIN_QUADRATIC_VOTE: &[u8] = b"veil:quadratic_vote:v2";
+pub const DOMAIN_SPLIT_WITHDRAWAL: &[u8] = b"veil:split_withdrawal:v2";
+pub const DOMAIN_DEPOSIT_CANCEL: &[u8] = b"veil:deposit_cancel:v2";
+pub const DOMAIN_RELAYER_SOLVENCY: &[u8] = b"veil:relayer_solvency:v2";
+pub const DOMAIN_REWARD_CURVE: &[u8] = b"veil:reward_curve:v2";
+pub const DOMAIN_EARLY_EXIT: &[u8] = b"veil:early_exit:v2";
+pub const DOMAIN_RELAYED_OUTCOME: &[u8] = b"veil:relayed_outcome:v2";
+
+// Hash domain separation (scheme version HASH_DOMAIN_VERSION = 2)
+//
+// Every protocol hash is H(len(tag) || tag || parts...) where tag is
+// "veil:<context>:v<version>". Within a context all parts are fixed-size,
+// so two different contexts can never produce structurally identical
+// preimages (e.g. a vote commitment colliding with a stake commitment).
+//
+// Version history:
+//   v0 - raw concatenation, no tag. Only still accepted when revealing
+//        votes cast before LEGACY_VOTE_COMMITMENT_CUTOFF.
+//   v1 - tagged hashes for all contexts. Merkle roots recorded under v0
+//        remain valid chain values; new insertions extend them under v1.
+//   v2 - H switched from the legacy byte mixer to SHA-256. Tags were
+//        bumped so no v2 digest can be mistaken for a v1 one.
+//
+// H is SHA-256 via the `sol_sha256` syscall, except for v0 vote
+// commitments, which keep the legacy mixer (`legacy_mixer_hash`).
+
+/// Slices a domain hash passes to the syscall without allocating
+/// (length prefix + tag + 14 parts, enough for every fixed-arity context)
+pub(crate) const MAX_HASH_SLICES: usize = 16;
+
+/// Domain-separated hash: H(len(tag) || tag || parts...)
+pub(crate) fn hash_domain(tag: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    hash_domain_iter(tag, parts.iter().copied())
+}
+
+/// Domain-separated hash over a borrowed sequence of parts
+/// The slices are gathered on the stack and hashed with one syscall; only
+/// variable-arity inputs beyond MAX_HASH_SLICES spill into a Vec
+pub(crate) fn hash_domain_iter<'a>(tag: &'a [u8], parts: impl Iterator<Item = &'a [u8]>) -> [u8; 32] {
+    let prefix = [tag.len() as u8];
+    let mut slices: [&[u8]; MAX_HASH_SLICES] = [&[]; MAX_HASH_SLICES];
+    slices[0] = &prefix;
+    slices[1] = tag;
+
+    let mut count = 2;
+    let mut spilled: Vec<&[u8]> = Vec::new();
+    for part in parts {
+        if count < MAX_HASH_SLICES {
+            slices[count] = part;
+            count += 1;
+        } else {
+            if spilled.is_empty() {
+                spilled.extend_from_slice(&slices);
+            }
+            spilled.push(part);
+        }
+    }
+
+    if spilled.is_empty() {
+        hashv(&slices[..count]).to_bytes()
+    } else {
+        hashv(&spilled).to_bytes()
+    }
+}
+
+/// Hash proof data using SHA-256
+#[allow(dead_code)]
+pub(crate) fn hash_proof(proof_data: &[u8]) -> [u8; 32] {
+    hash_domain(DOMAIN_PROOF, &[proof_data])
+}
+
+/// Hash public signals for event logging
+pub(crate) fn hash_public_signals(signals: &[[u8; 32]]) -> [u8; 32] {
+    hash_domain_iter(DOMAIN_PUBLIC_SIGNALS, signals.iter().map(|signal| signal.as_ref()))
+}
+
+/// Constant-time byte comparison
+/// Runtime depends only on the length, never on where the inputs differ,
+/// to avoid leaking commitment prefixes through timing
+#[inline(never)]
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let diff = a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y));
+    core::hint::black_box(diff) == 0
+}
+
+/// Index of the first entry equal to `needle`, comparing every entry with
+/// `constant_time_eq` so the scan does not stop at the match
+pub(crate) fn constant_time_position(entries: &[[u8; 32]], needle: &[u8; 32]) -> Option<usize> {
+    entries
+        .iter()
+        .enumerate()
+        .fold(None, |found, (i, entry)| {
+            let equal = constant_time_eq(entry, needle);
+            found.or(equal.then_some(i))
+        })
+}
+
+/// Compute proof hash for verification event
+pub(crate) fn compute_proof_hash(proof_data: &[u8], public_signals: &[[u8; 32]]) -> [u8; 32] {
+    let signals = public_signals.iter().map(|signal| signal.as_ref());
+    hash_domain_iter(DOMAIN_PROOF, core::iter::once(proof_data).chain(signals))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::hex32;
+
+    const DOMAINS: [&[u8]; 56] = [
+        DOMAIN_VOTE,
+        DOMAIN_STAKE,
+        DOMAIN_NOTE_INSERT,
+        DOMAIN_MERKLE_NODE,
+        DOMAIN_PROOF,
+        DOMAIN_PUBLIC_SIGNALS,
+        DOMAIN_REWARD,
+        DOMAIN_NULLIFIER,
+        DOMAIN_RLN_IDENTITY,
+        DOMAIN_TREE_CHECKPOINT,
+        DOMAIN_CAMPAIGN_TAG,
+        DOMAIN_CAMPAIGN_AGGREGATE,
+        DOMAIN_BEACON_COMMIT,
+        DOMAIN_BEACON,
+        DOMAIN_MULTISIG_ACTION,
+        DOMAIN_PROPOSAL_KEY,
+        DOMAIN_PROPOSAL_KEY_SHARE,
+        DOMAIN_PROPOSAL_STREAM,
+        DOMAIN_RESERVE,
+        DOMAIN_CLAIM_KEY,
+        DOMAIN_ESCROW_TAG,
+        DOMAIN_SUBSCRIPTION_TAG,
+        DOMAIN_VETO_TAG,
+        DOMAIN_WALLET_CALL,
+        DOMAIN_RELAYER_FEE,
+        DOMAIN_CIRCUIT_ID,
+        DOMAIN_VERIFYING_KEY,
+        DOMAIN_ATTESTATION_KV,
+        DOMAIN_APPROVAL_REVOCATION,
+        DOMAIN_STAKE_WEIGHT,
+        DOMAIN_WALLET_UNFREEZE,
+        DOMAIN_REFERRAL_CLAIM,
+        DOMAIN_NOTE_SWEEP,
+        DOMAIN_SPEND_ANCHOR,
+        DOMAIN_AUDIT_LOG,
+        DOMAIN_ALIAS,
+        DOMAIN_NULLIFIER_FILTER,
+        DOMAIN_POOL_BINDING,
+        DOMAIN_HOLDING,
+        DOMAIN_HOLDING_CLAIM,
+        DOMAIN_TREASURY_BAND,
+        DOMAIN_SMALL_SPEND,
+        DOMAIN_SIGNER_HEARTBEAT,
+        DOMAIN_SIGNER_ROTATION,
+        DOMAIN_STEALTH_APPROVAL,
+        DOMAIN_REVEAL_TOKEN,
+        DOMAIN_ELIGIBILITY_LEAF,
+        DOMAIN_TALLY_COMMITMENT,
+        DOMAIN_CONVICTION,
+        DOMAIN_QUADRATIC_VOTE,
+        DOMAIN_SPLIT_WITHDRAWAL,
+        DOMAIN_DEPOSIT_CANCEL,
+        DOMAIN_RELAYER_SOLVENCY,
+        DOMAIN_REWARD_CURVE,
+        DOMAIN_EARLY_EXIT,
+        DOMAIN_RELAYED_OUTCOME,
+    ];
+
+    #[test]
+    fn hashv_is_sha256() {
+        // FIPS 180-2 "abc"
+        let expected = hex32("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(hashv(&[b"abc"]).to_bytes(), expected);
+        assert_eq!(hashv(&[b"a", b"", b"bc"]).to_bytes(), expected);
+    }
+
+    #[test]
+    fn hash_domain_vectors() {
+        // sha256(len(tag) || tag || parts...)
+        assert_eq!(
+            hash_domain(DOMAIN_VOTE, &[]),
+            hex32("b156ecf2bc22970303be6af5f861bacbc91c4158e601303f93db519b062385d2")
+        );
+        assert_eq!(
+            hash_domain(DOMAIN_VOTE, &[b"a", b"bc"]),
+            hex32("ecb1e1e207efc75d6bd07046d495922c29b78438ee5ce0962dbbaf0abd177824")
+        );
+    }
+
+    #[test]
+    fn hash_domain_length_prefix_separates_tags() {
+        // Without the length prefix both preimages would be "abc"
+        assert_ne!(hash_domain(b"ab", &[b"c"]), hash_domain(b"a", &[b"bc"]));
+        assert_ne!(hash_domain(b"", &[b"abc"]), hashv(&[b"abc"]).to_bytes());
+    }
+
+    #[test]
+    fn hash_domain_iter_matches_past_the_slice_limit() {
+        let parts: Vec<[u8; 1]> = (0..40u8).map(|i| [i]).collect();
+        let concatenated: Vec<u8> = parts.iter().flatten().copied().collect();
+        for count in [0, 1, MAX_HASH_SLICES - 2, MAX_HASH_SLICES - 1, MAX_HASH_SLICES, 40] {
+            let slices: Vec<&[u8]> = parts[..count].iter().map(|part| part.as_ref()).collect();
+            let expected = hash_domain(DOMAIN_PROOF, &[&concatenated[..count]]);
+            assert_eq!(hash_domain(DOMAIN_PROOF, &slices), expected, "{count} parts");
+            assert_eq!(hash_domain_iter(DOMAIN_PROOF, slices.into_iter()), expected, "{count} parts");
+        }
+    }
+
+    #[test]
+    fn domain_tags_are_distinct_and_versioned() {
+        for (i, tag) in DOMAINS.iter().enumerate() {
+            let text = core::str::from_utf8(tag).unwrap();
+            assert!(text.starts_with("veil:"), "{text}");
+            assert!(text.ends_with(&format!(":v{HASH_DOMAIN_VERSION}")), "{text}");
+            assert!(tag.len() <= u8::MAX as usize, "{text}");
+            for other in &DOMAINS[i + 1..] {
+                assert_ne!(tag, other, "{text} is reused");
+            }
+        }
+    }
+
+    #[test]
+    fn proof_hash_covers_proof_and_signals() {
+        let proof = [0xabu8; 256];
+        let signals = [[1u8; 32], [2u8; 32]];
+        let mut preimage = proof.to_vec();
+        preimage.extend(signals.iter().flatten());
+        assert_eq!(compute_proof_hash(&proof, &signals), hash_domain(DOMAIN_PROOF, &[&preimage]));
+        assert_eq!(compute_proof_hash(&proof, &[]), hash_proof(&proof));
+        assert_ne!(compute_proof_hash(&proof, &signals[..1]), compute_proof_hash(&proof, &signals));
+        assert_eq!(
+            hash_public_signals(&signals),
+            hash_domain(DOMAIN_PUBLIC_SIGNALS, &[&[1u8; 32], &[2u8; 32]])
+        );
+    }
+
+    #[test]
+    fn constant_time_eq_compares_contents_and_length() {
+        assert!(constant_time_eq(&[], &[]));
+        assert!(constant_time_eq(&[1, 2, 3], &[1, 2, 3]));
+        assert!(!constant_time_eq(&[1, 2, 3], &[1, 2, 4]));
+        assert!(!constant_time_eq(&[0, 2, 3], &[1, 2, 3]));
+        assert!(!constant_time_eq(&[1, 2], &[1, 2, 3]));
+    }
+
+    #[test]
+    fn constant_time_position_finds_first_match() {
+        let entries = [[1u8; 32], [2u8; 32], [1u8; 32]];
+        assert_eq!(constant_time_position(&entries, &[1u8; 32]), Some(0));
+        assert_eq!(constant_time_position(&entries, &[2u8; 32]), Some(1));
+        assert_eq!(constant_time_position(&entries, &[3u8; 32]), None);
+        assert_eq!(constant_time_position(&[], &[1u8; 32]), None);
+    }
+}
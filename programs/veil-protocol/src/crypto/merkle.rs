@@ -0,0 +1,188 @@
+//! Incremental note-commitment Merkle tree
+
+use crate::*;
+
+/// Insert a note into the Merkle tree and return new root
+pub(crate) fn insert_note_to_merkle_tree(
+    current_root: &[u8; 32],
+    note_commitment: &[u8; 32],
+    note_index: u32,
+) -> [u8; 32] {
+    // Simplified Merkle tree update for demo
+    // In production: use proper incremental Merkle tree (IMT) library
+
+    hash_domain(
+        DOMAIN_NOTE_INSERT,
+        &[current_root, note_commitment, &note_index.to_le_bytes()],
+    )
+}
+
+/// Append a leaf to an incremental Merkle tree given its filled subtrees
+/// and return the new root. Empty positions hash as zero subtrees, and the
+/// tree is as deep as `filled_subtrees` is long.
+pub(crate) fn append_to_filled_subtrees(
+    filled_subtrees: &mut [[u8; 32]],
+    leaf: &[u8; 32],
+    leaf_index: u32,
+) -> [u8; 32] {
+    let mut node = *leaf;
+    let mut zero = [0u8; 32];
+    let mut index = leaf_index;
+
+    for filled in filled_subtrees.iter_mut() {
+        node = if index & 1 == 0 {
+            *filled = node;
+            hash_domain(DOMAIN_MERKLE_NODE, &[&node, &zero])
+        } else {
+            hash_domain(DOMAIN_MERKLE_NODE, &[filled, &node])
+        };
+        zero = hash_domain(DOMAIN_MERKLE_NODE, &[&zero, &zero]);
+        index >>= 1;
+    }
+
+    node
+}
+
+/// Digest committing to a checkpoint's incremental tree state
+pub(crate) fn compute_subtree_digest(filled_subtrees: &[[u8; 32]; MERKLE_TREE_DEPTH], leaf_count: u32) -> [u8; 32] {
+    let count = leaf_count.to_le_bytes();
+    hash_domain_iter(
+        DOMAIN_TREE_CHECKPOINT,
+        core::iter::once(count.as_ref()).chain(filled_subtrees.iter().map(|node| node.as_ref())),
+    )
+}
+
+/// Verify Merkle proof for note membership
+pub(crate) fn verify_merkle_proof(
+    root: &[u8; 32],
+    proof: &[[u8; 32]; MERKLE_TREE_DEPTH],
+    path_indices: u8,
+    leaf_hash: &[u8; 32],
+) -> bool {
+    // Compute root from leaf and proof
+    let mut current_hash = *leaf_hash;
+
+    for (i, sibling) in proof.iter().enumerate() {
+        let is_right = (path_indices >> i) & 1 == 1;
+
+        current_hash = if is_right {
+            hash_domain(DOMAIN_MERKLE_NODE, &[sibling, &current_hash])
+        } else {
+            hash_domain(DOMAIN_MERKLE_NODE, &[&current_hash, sibling])
+        };
+    }
+
+    constant_time_eq(&current_hash, root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::hex32;
+
+    fn zero_subtrees() -> [[u8; 32]; MERKLE_TREE_DEPTH] {
+        let mut zeros = [[0u8; 32]; MERKLE_TREE_DEPTH];
+        for level in 1..MERKLE_TREE_DEPTH {
+            zeros[level] = hash_domain(DOMAIN_MERKLE_NODE, &[&zeros[level - 1], &zeros[level - 1]]);
+        }
+        zeros
+    }
+
+    fn leaf(byte: u8) -> [u8; 32] {
+        [byte; 32]
+    }
+
+    #[test]
+    fn single_leaf_root_vector() {
+        let mut filled = [[0u8; 32]; MERKLE_TREE_DEPTH];
+        let root = append_to_filled_subtrees(&mut filled, &leaf(1), 0);
+        assert_eq!(root, hex32("8559eb6122d53fb547e9fecce7fcef1b6c8370f86b24bda1644d2f297f81c65a"));
+        assert_eq!(filled[0], leaf(1));
+        assert!(verify_merkle_proof(&root, &zero_subtrees(), 0, &leaf(1)));
+    }
+
+    #[test]
+    fn appended_leaves_have_valid_proofs() {
+        let mut filled = [[0u8; 32]; MERKLE_TREE_DEPTH];
+        append_to_filled_subtrees(&mut filled, &leaf(1), 0);
+        append_to_filled_subtrees(&mut filled, &leaf(2), 1);
+        let root = append_to_filled_subtrees(&mut filled, &leaf(3), 2);
+
+        let zeros = zero_subtrees();
+        let pair = hash_domain(DOMAIN_MERKLE_NODE, &[&leaf(1), &leaf(2)]);
+
+        // Leaf 3 is a left child at level 0 and a right child at level 1
+        let mut proof = zeros;
+        proof[1] = pair;
+        assert!(verify_merkle_proof(&root, &proof, 0b10, &leaf(3)));
+
+        // Leaf 2 is a right child whose uncle is the (leaf 3, zero) node
+        let mut proof = zeros;
+        proof[0] = leaf(1);
+        proof[1] = hash_domain(DOMAIN_MERKLE_NODE, &[&leaf(3), &zeros[0]]);
+        assert!(verify_merkle_proof(&root, &proof, 0b01, &leaf(2)));
+    }
+
+    #[test]
+    fn deeper_tree_extends_root_with_zero_subtrees() {
+        let mut filled = [[0u8; 32]; MERKLE_TREE_DEPTH];
+        append_to_filled_subtrees(&mut filled, &leaf(1), 0);
+        let shallow = append_to_filled_subtrees(&mut filled, &leaf(2), 1);
+
+        let mut deep_filled = [[0u8; 32]; WALLET_TREE_DEPTH];
+        append_to_filled_subtrees(&mut deep_filled, &leaf(1), 0);
+        let deep = append_to_filled_subtrees(&mut deep_filled, &leaf(2), 1);
+
+        let mut zero = zero_subtrees()[MERKLE_TREE_DEPTH - 1];
+        zero = hash_domain(DOMAIN_MERKLE_NODE, &[&zero, &zero]);
+        let mut node = shallow;
+        for _ in MERKLE_TREE_DEPTH..WALLET_TREE_DEPTH {
+            node = hash_domain(DOMAIN_MERKLE_NODE, &[&node, &zero]);
+            zero = hash_domain(DOMAIN_MERKLE_NODE, &[&zero, &zero]);
+        }
+        assert_eq!(deep, node);
+    }
+
+    #[test]
+    fn proof_rejects_wrong_leaf_sibling_or_path() {
+        let mut filled = [[0u8; 32]; MERKLE_TREE_DEPTH];
+        append_to_filled_subtrees(&mut filled, &leaf(1), 0);
+        let root = append_to_filled_subtrees(&mut filled, &leaf(2), 1);
+
+        let mut proof = zero_subtrees();
+        proof[0] = leaf(1);
+        assert!(verify_merkle_proof(&root, &proof, 0b01, &leaf(2)));
+        assert!(!verify_merkle_proof(&root, &proof, 0b01, &leaf(3)));
+        assert!(!verify_merkle_proof(&root, &proof, 0b00, &leaf(2)));
+        assert!(!verify_merkle_proof(&root, &proof, 0b11, &leaf(2)));
+        proof[MERKLE_TREE_DEPTH - 1][0] ^= 1;
+        assert!(!verify_merkle_proof(&root, &proof, 0b01, &leaf(2)));
+    }
+
+    #[test]
+    fn subtree_digest_commits_to_count_and_nodes() {
+        let mut filled = [[0u8; 32]; MERKLE_TREE_DEPTH];
+        append_to_filled_subtrees(&mut filled, &leaf(1), 0);
+        let digest = compute_subtree_digest(&filled, 1);
+        assert_ne!(digest, compute_subtree_digest(&filled, 2));
+        let mut other = filled;
+        other[MERKLE_TREE_DEPTH - 1] = leaf(9);
+        assert_ne!(digest, compute_subtree_digest(&other, 1));
+
+        let mut preimage = 1u32.to_le_bytes().to_vec();
+        preimage.extend(filled.iter().flatten());
+        assert_eq!(digest, hash_domain(DOMAIN_TREE_CHECKPOINT, &[&preimage]));
+    }
+
+    #[test]
+    fn note_insert_vector() {
+        assert_eq!(
+            insert_note_to_merkle_tree(&[0u8; 32], &leaf(1), 0),
+            hex32("f5653f4e4023f16e30010c1e878317e22d12d4d64a267c1b9db18d6f52afb97d")
+        );
+        assert_ne!(
+            insert_note_to_merkle_tree(&[0u8; 32], &leaf(1), 0),
+            insert_note_to_merkle_tree(&[0u8; 32], &leaf(1), 1)
+        );
+    }
+}
@@ -0,0 +1,30 @@
+//! Cryptographic helpers shared by the instruction handlers
+
+mod commitments;
+mod curve;
+pub mod field;
+pub mod hash;
+mod merkle;
+mod shamir;
+#[cfg(test)]
+pub(crate) mod test_vectors;
+mod verifier;
+
+pub(crate) use commitments::*;
+pub(crate) use curve::*;
+pub use field::*;
+pub use hash::*;
+pub(crate) use merkle::*;
+pub(crate) use shamir::*;
+pub(crate) use verifier::*;
+
+/// Decode a 64-digit hex test vector
+#[cfg(test)]
+pub(crate) fn hex32(hex: &str) -> [u8; 32] {
+    assert_eq!(hex.len(), 64, "test vector must be 32 bytes");
+    let mut bytes = [0u8; 32];
+    for (byte, pair) in bytes.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(core::str::from_utf8(pair).unwrap(), 16).unwrap();
+    }
+    bytes
+}
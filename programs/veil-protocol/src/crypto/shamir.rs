@@ -0,0 +1,129 @@
+//! GF(256) Shamir secret reconstruction and the proposal keystream it
+//! unlocks
+
+use crate::*;
+
+/// Multiply in GF(256) with the AES polynomial x^8 + x^4 + x^3 + x + 1
+pub(crate) fn gf256_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80;
+        a <<= 1;
+        if carry != 0 {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// Multiplicative inverse in GF(256) (a^254); `a` must be non-zero
+pub(crate) fn gf256_inv(a: u8) -> u8 {
+    let mut result = 1u8;
+    let mut base = a;
+    let mut exp = 254u8;
+    while exp != 0 {
+        if exp & 1 != 0 {
+            result = gf256_mul(result, base);
+        }
+        base = gf256_mul(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Lagrange-interpolate the shares at x = 0, byte by byte
+/// Indices must be distinct and non-zero
+pub(crate) fn reconstruct_shamir_secret(shares: &[ProposalKeyShare]) -> [u8; 32] {
+    let mut secret = [0u8; 32];
+    for (i, share) in shares.iter().enumerate() {
+        let mut basis = 1u8;
+        for (j, other) in shares.iter().enumerate() {
+            if i != j {
+                // x_j / (x_j - x_i); subtraction is XOR in GF(2^8)
+                basis = gf256_mul(basis, gf256_mul(other.index, gf256_inv(other.index ^ share.index)));
+            }
+        }
+        for (byte, y) in secret.iter_mut().zip(share.share.iter()) {
+            *byte ^= gf256_mul(*y, basis);
+        }
+    }
+    secret
+}
+
+/// XOR `data` with the keystream H(DOMAIN_PROPOSAL_STREAM, key || counter)
+pub(crate) fn apply_proposal_keystream(key: &[u8; 32], data: &[u8]) -> Vec<u8> {
+    data.chunks(32)
+        .enumerate()
+        .flat_map(|(counter, chunk)| {
+            let block = hash_domain(DOMAIN_PROPOSAL_STREAM, &[key, &(counter as u32).to_le_bytes()]);
+            chunk.iter().zip(block).map(|(byte, pad)| byte ^ pad).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Shares of `secret` on the line secret + slope * x
+    fn split(secret: &[u8; 32], slope: u8, indices: &[u8]) -> Vec<ProposalKeyShare> {
+        indices
+            .iter()
+            .map(|&index| {
+                let mut share = [0u8; 32];
+                for (y, s) in share.iter_mut().zip(secret) {
+                    *y = s ^ gf256_mul(slope, index);
+                }
+                ProposalKeyShare { index, share }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn gf256_vectors() {
+        // FIPS 197 section 4.2
+        assert_eq!(gf256_mul(0x57, 0x83), 0xc1);
+        assert_eq!(gf256_mul(0x57, 0x13), 0xfe);
+        assert_eq!(gf256_mul(0x57, 0x01), 0x57);
+        assert_eq!(gf256_mul(0x57, 0x00), 0x00);
+        assert_eq!(gf256_inv(0x53), 0xca);
+    }
+
+    #[test]
+    fn gf256_inverse_of_every_element() {
+        for a in 1..=255u8 {
+            assert_eq!(gf256_mul(a, gf256_inv(a)), 1, "{a:#04x}");
+        }
+    }
+
+    #[test]
+    fn reconstructs_from_any_threshold_subset() {
+        let secret = [0x42u8; 32];
+        let shares = split(&secret, 0x9d, &[1, 2, 3]);
+        for pair in [[0, 1], [0, 2], [1, 2]] {
+            let subset = [shares[pair[0]].clone(), shares[pair[1]].clone()];
+            assert_eq!(reconstruct_shamir_secret(&subset), secret);
+        }
+        // A single share of a degree-1 polynomial reveals nothing
+        assert_ne!(reconstruct_shamir_secret(&shares[..1]), secret);
+    }
+
+    #[test]
+    fn keystream_round_trips() {
+        let key = [5u8; 32];
+        let data: Vec<u8> = (0..70u8).collect();
+        let encrypted = apply_proposal_keystream(&key, &data);
+        assert_eq!(encrypted.len(), data.len());
+        assert_ne!(encrypted, data);
+        assert_eq!(apply_proposal_keystream(&key, &encrypted), data);
+        // Each 32-byte block uses its own counter
+        let pad = |range: core::ops::Range<usize>| -> Vec<u8> {
+            encrypted[range.clone()].iter().zip(&data[range]).map(|(a, b)| a ^ b).collect()
+        };
+        assert_ne!(pad(0..32), pad(32..64));
+    }
+}
@@ -0,0 +1,335 @@
+//! Canonical test vectors shared with the circuits and the SDK
+//!
+//! The constants mirror test-vectors/vectors.json at the repository root,
+//! which the SDK tests and circuit witnesses load directly. Every vector is
+//! recomputed here from the inputs in that file and compared with both the
+//! constant and the file's expected value, so none of the three layers can
+//! drift without a failing test.
+
+use crate::*;
+
+pub(crate) const SHA256_ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+pub(crate) const HASH_DOMAIN_EMPTY: &str = "b156ecf2bc22970303be6af5f861bacbc91c4158e601303f93db519b062385d2";
+pub(crate) const HASH_DOMAIN_PARTS: &str = "ecb1e1e207efc75d6bd07046d495922c29b78438ee5ce0962dbbaf0abd177824";
+
+pub(crate) const VOTE_COMMITMENT: &str = "66e85994ce271a150e2d3ce1c027eb8637b4b740cea1bfac00e33f7f12d849fc";
+pub(crate) const STAKE_COMMITMENT: &str = "aeb4becfbc3ac741fd4fb56e2e50c0fd5ca5e9c840d143d30dc90a60948a7752";
+pub(crate) const BEACON_VALUE: &str = "e2c98e2bc026490c6eb935ebf2a9b7e4fea08cf4ff2954b2b50e12f1c28ce008";
+pub(crate) const POOL_BINDING: &str = "8839235e862df1dd8694248ec853354d5d27944d15ad43746dccd44c6fc7e510";
+pub(crate) const CLAIM_KEY_HASH: &str = "2f20888a377cb09d7b7e591e0b355829d7bafeeaaaeece2db7c1c9497e32c33e";
+pub(crate) const HOLDING_TAG: &str = "bb0f50e3bf7349394d85f2faef49f978e6f1771ae7af50a4a80b251cbfe9aebe";
+pub(crate) const HOLDING_CLAIM_TAG: &str = "186ac30cde9ce148afe158e39360cb906b3650ad3a7026274a35ab60645eb1fe";
+pub(crate) const TREASURY_BAND_TAG: &str = "8f2679387ebda070a43cacf8617ec081bdade80fc7e86ac33ca004b77db71b43";
+pub(crate) const SMALL_SPEND_TAG: &str = "7431172c7024714d720cad7e01d5da7a6746916d339b86ed06994f1160fe187f";
+pub(crate) const HEARTBEAT_TAG: &str = "f0bdb7633e549d0977b10247b463a56f5972f9f0593652de4ace4bacc25ef039";
+pub(crate) const SIGNER_ROTATION_TAG: &str = "8c53368eaa73c7168243ccd4685998bb3776039ca79193c00f501884c677eba5";
+pub(crate) const STEALTH_APPROVAL_TAG: &str = "d89ec9fac0a722f25441b9b04028ea8cdacf5c5c459bf14861fbce23ca884809";
+pub(crate) const TALLY_COMMITMENT: &str = "690952b05cc9eb4c5748088df874133df6b16716881ba0aa4dc8110d064d4354";
+pub(crate) const CONVICTION_TAG: &str = "448a9bea7c9a1be80d96ecdb57371e0872718df8d07bbcc3388fee0be000a3d5";
+pub(crate) const QUADRATIC_VOTE_TAG: &str = "4bb0a516e149b35c58f3dd29512f4bb63c6079438e0bc6826a72b61e1cfe0514";
+pub(crate) const CIRCUIT_ID: &str = "dce28dc3b1825b1bed9b8af6ff904acc1144f31f426da300c15192625d56ea0d";
+pub(crate) const SPLIT_TAG: &str = "1398027f1f899e3389a32d6a18922cfc40be713b94502442c5f5bf7dcff9dfd0";
+pub(crate) const DEPOSIT_CANCEL_TAG: &str = "a3afe4893acf8b9f5d6030bbffcd517fbcdf40ec5c05ff04c5b07c356695901e";
+pub(crate) const RELAYER_SOLVENCY_TAG: &str = "141693e814ac05ac024851917833ae331f469371ae123cb7774f39e968e7a245";
+pub(crate) const REWARD_CURVE_HASH: &str = "8e8b9ca8def48e35f597c117b99d8c0edf37ebffa3b2dc8f21a3d40915cb2426";
+pub(crate) const EARLY_EXIT_TAG: &str = "c87af854fa4e3cb77adeaea8993455f1ee29145b57c8da3d587980db146101bc";
+
+pub(crate) const NULLIFIER_FILTER_BITS: [usize; NULLIFIER_FILTER_HASHES] = [1618, 3753, 7120, 1660];
+
+pub(crate) const SINGLE_LEAF_ROOT: &str = "8559eb6122d53fb547e9fecce7fcef1b6c8370f86b24bda1644d2f297f81c65a";
+pub(crate) const THREE_LEAF_ROOT: &str = "0175a2bdab828ee59b7fc8439a1781234908d425858340512b9776f086209d03";
+pub(crate) const NOTE_INSERT: &str = "f5653f4e4023f16e30010c1e878317e22d12d4d64a267c1b9db18d6f52afb97d";
+
+pub(crate) const PROPOSAL_KEYSTREAM: &str = concat!(
+    "ffd6f090bccc86884968f958cee2f0959636544e38af966583036163e24708ca",
+    "7266d78196fbbccaea6e277006cb9ed7d9126af877196ee4b22d7c7bc2de5ad6",
+    "2a2b4eefe423",
+);
+
+/// Groth16 point checks: vector name and the error it must fail with
+pub(crate) const PROOF_VECTORS: [(&str, Option<ErrorCode>); 4] = [
+    ("groth16_points_valid", None),
+    ("groth16_g1_off_curve", Some(ErrorCode::G1PointNotOnCurve)),
+    ("groth16_g2_off_twist", Some(ErrorCode::G2PointNotOnCurve)),
+    ("groth16_non_canonical", Some(ErrorCode::InvalidProofPoint)),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VECTORS_JSON: &str = include_str!("../../../../test-vectors/vectors.json");
+
+    /// Raw JSON value of `key` in the vector called `name`. The file keeps one
+    /// vector per line, so the lookup never needs a full parser
+    fn field(name: &str, key: &str) -> &'static str {
+        let marker = format!("{{\"name\": \"{name}\",");
+        let line = VECTORS_JSON
+            .lines()
+            .find(|line| line.trim_start().starts_with(&marker))
+            .unwrap_or_else(|| panic!("no vector named {name}"));
+        let start = line
+            .find(&format!("\"{key}\": "))
+            .unwrap_or_else(|| panic!("vector {name} has no {key}"))
+            + key.len()
+            + 4;
+        let value = &line[start..];
+        let end = match value.as_bytes()[0] {
+            b'"' => value[1..].find('"').unwrap() + 2,
+            b'[' => value.find(']').unwrap() + 1,
+            _ => value.find([',', '}']).unwrap(),
+        };
+        value[..end].trim_matches('"')
+    }
+
+    fn bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    /// Hex strings of a JSON array value
+    fn list(name: &str, key: &str) -> Vec<&'static str> {
+        let value = field(name, key);
+        value[1..value.len() - 1].split(", ").map(|item| item.trim_matches('"')).collect()
+    }
+
+    fn input32(name: &str, key: &str) -> [u8; 32] {
+        crate::crypto::hex32(field(name, key))
+    }
+
+    fn input_key(name: &str, key: &str) -> Pubkey {
+        Pubkey::new_from_array(input32(name, key))
+    }
+
+    fn input_u64(name: &str, key: &str) -> u64 {
+        field(name, key).parse().unwrap()
+    }
+
+    /// `constant` must match the file and the value computed from its inputs
+    fn check(name: &str, constant: &str, computed: &[u8]) {
+        assert_eq!(field(name, "expected"), constant, "{name}: constant differs from vectors.json");
+        assert_eq!(hex(computed), constant, "{name}: implementation differs from the vector");
+    }
+
+    #[test]
+    fn hash_vectors() {
+        check("sha256_abc", SHA256_ABC, &hashv(&[&bytes(field("sha256_abc", "data"))]).to_bytes());
+        for (name, constant) in [("hash_domain_empty", HASH_DOMAIN_EMPTY), ("hash_domain_parts", HASH_DOMAIN_PARTS)] {
+            let parts: Vec<Vec<u8>> = list(name, "parts").into_iter().map(bytes).collect();
+            let parts: Vec<&[u8]> = parts.iter().map(Vec::as_slice).collect();
+            check(name, constant, &hash_domain(field(name, "tag").as_bytes(), &parts));
+        }
+    }
+
+    #[test]
+    fn commitment_vectors() {
+        let name = "vote_commitment";
+        let choice = field(name, "choice") == "true";
+        let vote = compute_vote_commitment(choice, &input32(name, "secret"), &input_key(name, "voter"));
+        check(name, VOTE_COMMITMENT, &vote);
+
+        let name = "stake_commitment";
+        let stake = compute_stake_commitment(
+            input_u64(name, "amount_lamports"),
+            &input32(name, "validator_commitment"),
+            &input_key(name, "staker"),
+            &input32(name, "secret"),
+        );
+        check(name, STAKE_COMMITMENT, &stake);
+
+        let name = "beacon_value";
+        let beacon = compute_beacon_value(
+            &input_key(name, "round"),
+            &input32(name, "accumulator"),
+            input_u64(name, "reveal_count") as u32,
+        );
+        check(name, BEACON_VALUE, &beacon);
+
+        let name = "pool_binding";
+        check(name, POOL_BINDING, &compute_pool_binding(&input_key(name, "pool")));
+
+        let name = "claim_key_hash";
+        let claim_key = hash_domain(DOMAIN_CLAIM_KEY, &[&input32(name, "claim_secret")]);
+        check(name, CLAIM_KEY_HASH, &claim_key);
+
+        let name = "holding_tag";
+        let release = HoldingRelease {
+            claim_key_hash: input32(name, "claim_key_hash"),
+            lamports: input_u64(name, "lamports"),
+        };
+        check(name, HOLDING_TAG, &compute_holding_tag(&input_key(name, "pool"), &release));
+
+        let name = "holding_claim_tag";
+        let claim_tag = compute_holding_claim_tag(&input_key(name, "holding"), &input_key(name, "recipient"));
+        check(name, HOLDING_CLAIM_TAG, &claim_tag);
+
+        let name = "treasury_band_tag";
+        let band_tag = compute_treasury_band_tag(
+            &input_key(name, "multisig"),
+            &input_key(name, "pool"),
+            input_u64(name, "min_balance"),
+            input_u64(name, "max_balance"),
+        );
+        check(name, TREASURY_BAND_TAG, &band_tag);
+
+        let name = "small_spend_tag";
+        let spend_tag = compute_small_spend_tag(
+            &input_key(name, "proposal"),
+            &input_key(name, "mint"),
+            input_u64(name, "amount"),
+        );
+        check(name, SMALL_SPEND_TAG, &spend_tag);
+
+        let name = "heartbeat_tag";
+        let signers: Vec<[u8; 32]> = list(name, "signers").into_iter().map(crate::crypto::hex32).collect();
+        let heartbeat_tag = compute_heartbeat_tag(&input_key(name, "multisig"), input_u64(name, "epoch"), &signers);
+        check(name, HEARTBEAT_TAG, &heartbeat_tag);
+
+        let name = "signer_rotation_tag";
+        let rotation_tag = compute_signer_rotation_tag(&input_key(name, "multisig"), &input32(name, "new_commitment"));
+        check(name, SIGNER_ROTATION_TAG, &rotation_tag);
+
+        let name = "stealth_approval_tag";
+        let signers: Vec<[u8; 32]> = list(name, "signers").into_iter().map(crate::crypto::hex32).collect();
+        let approval_tag = compute_stealth_approval_tag(&input_key(name, "proposal"), &signers);
+        check(name, STEALTH_APPROVAL_TAG, &approval_tag);
+
+        let name = "tally_commitment";
+        let counts = ["yes_count", "no_count", "yes_weight", "no_weight"].map(|key| input_u64(name, key));
+        let tally = compute_tally_commitment(
+            &input_key(name, "proposal"),
+            &counts,
+            field(name, "quorum_failed") == "true",
+            input_u64(name, "salt"),
+        );
+        check(name, TALLY_COMMITMENT, &tally);
+
+        let name = "conviction_tag";
+        let conviction_tag = compute_conviction_tag(
+            &input_key(name, "stake"),
+            input_u64(name, "conviction"),
+            input_u64(name, "elapsed") as i64,
+            input_u64(name, "ramp_period") as i64,
+            input_u64(name, "weight_unit"),
+        );
+        check(name, CONVICTION_TAG, &conviction_tag);
+
+        let name = "quadratic_vote_tag";
+        let votes: Vec<u64> = list(name, "votes").into_iter().map(|votes| votes.parse().unwrap()).collect();
+        let quadratic_tag = compute_quadratic_vote_tag(&input_key(name, "proposal"), &votes);
+        check(name, QUADRATIC_VOTE_TAG, &quadratic_tag);
+
+        let name = "circuit_id";
+        assert_eq!(field(name, "circuit"), Circuit::Withdrawal.name());
+        check(name, CIRCUIT_ID, &Circuit::Withdrawal.id());
+
+        let name = "split_tag";
+        let payments: Vec<SplitPayment> = list(name, "recipients")
+            .into_iter()
+            .zip(list(name, "lamports"))
+            .map(|(recipient, lamports)| SplitPayment {
+                recipient: Pubkey::new_from_array(crate::crypto::hex32(recipient)),
+                lamports: lamports.parse().unwrap(),
+            })
+            .collect();
+        check(name, SPLIT_TAG, &compute_split_tag(&input_key(name, "pool"), &payments));
+
+        let name = "deposit_cancel_tag";
+        let tag = compute_deposit_cancel_tag(
+            &input_key(name, "pool"),
+            input_u64(name, "note_index") as u32,
+            &input_key(name, "recipient"),
+            input_u64(name, "lamports"),
+        );
+        check(name, DEPOSIT_CANCEL_TAG, &tag);
+
+        let name = "relayer_solvency_tag";
+        let tag = compute_relayer_solvency_tag(
+            &input_key(name, "relayer_record"),
+            input_u64(name, "stake_lamports"),
+            input_u64(name, "period_index"),
+        );
+        check(name, RELAYER_SOLVENCY_TAG, &tag);
+
+        let name = "reward_curve_hash";
+        let points: Vec<RewardCurvePoint> = list(name, "starts_at")
+            .into_iter()
+            .zip(list(name, "rate_bps"))
+            .map(|(starts_at, rate_bps)| RewardCurvePoint {
+                starts_at: starts_at.parse().unwrap(),
+                rate_bps: rate_bps.parse().unwrap(),
+            })
+            .collect();
+        let hash = compute_reward_curve_hash(input_u64(name, "base_rate_bps") as u16, &points);
+        check(name, REWARD_CURVE_HASH, &hash);
+
+        let name = "early_exit_tag";
+        let tag = compute_early_exit_tag(
+            &input_key(name, "pool"),
+            &input_key(name, "recipient"),
+            input_u64(name, "gross_lamports"),
+            input_u64(name, "penalty_bps") as u16,
+        );
+        check(name, EARLY_EXIT_TAG, &tag);
+    }
+
+    #[test]
+    fn nullifier_vectors() {
+        let name = "nullifier_filter_bits";
+        let bits = NullifierFilter::bit_positions(&input32(name, "nullifier"));
+        assert_eq!(field(name, "expected"), format!("{NULLIFIER_FILTER_BITS:?}"));
+        assert_eq!(bits, NULLIFIER_FILTER_BITS);
+    }
+
+    #[test]
+    fn merkle_vectors() {
+        for (name, constant) in [("single_leaf_root", SINGLE_LEAF_ROOT), ("three_leaf_root", THREE_LEAF_ROOT)] {
+            let mut filled = [[0u8; 32]; MERKLE_TREE_DEPTH];
+            let mut root = [0u8; 32];
+            for (index, leaf) in list(name, "leaves").into_iter().enumerate() {
+                root = append_to_filled_subtrees(&mut filled, &crate::crypto::hex32(leaf), index as u32);
+            }
+            check(name, constant, &root);
+        }
+
+        let name = "note_insert";
+        let root = insert_note_to_merkle_tree(
+            &input32(name, "root"),
+            &input32(name, "leaf"),
+            input_u64(name, "index") as u32,
+        );
+        check(name, NOTE_INSERT, &root);
+    }
+
+    #[test]
+    fn ciphertext_vectors() {
+        let name = "proposal_keystream";
+        let plaintext = bytes(field(name, "plaintext"));
+        let ciphertext = apply_proposal_keystream(&input32(name, "key"), &plaintext);
+        check(name, PROPOSAL_KEYSTREAM, &ciphertext);
+        assert_eq!(apply_proposal_keystream(&input32(name, "key"), &ciphertext), plaintext);
+    }
+
+    #[test]
+    fn proof_vectors() {
+        for (name, expected) in PROOF_VECTORS {
+            let result = validate_groth16_points(&bytes(field(name, "proof")));
+            match expected {
+                None => {
+                    assert_eq!(field(name, "expected"), "ok");
+                    assert!(result.is_ok(), "{name}");
+                }
+                Some(code) => {
+                    assert_eq!(field(name, "expected"), format!("{code:?}"));
+                    assert_eq!(result.unwrap_err(), code.into(), "{name}");
+                }
+            }
+        }
+    }
+}
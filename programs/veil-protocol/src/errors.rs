@@ -0,0 +1,824 @@
+//! Program error codes
+
+use crate::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Invalid proof provided")]
+    InvalidProof,
+
+    #[msg("Recovery is already active")]
+    RecoveryAlreadyActive,
+
+    #[msg("No active recovery to execute or cancel")]
+    NoActiveRecovery,
+
+    #[msg("Timelock period has not expired yet")]
+    TimelockNotExpired,
+
+    #[msg("Invalid timelock period (must be 1-90 days)")]
+    InvalidTimelockPeriod,
+
+    #[msg("Unauthorized: only owner can perform this action")]
+    Unauthorized,
+
+    // Voting Errors
+    #[msg("Invalid voting period")]
+    InvalidVotingPeriod,
+
+    #[msg("Invalid reveal period")]
+    InvalidRevealPeriod,
+
+    #[msg("Voting period has ended")]
+    VotingEnded,
+
+    #[msg("Already voted on this proposal")]
+    AlreadyVoted,
+
+    #[msg("Voting period has not ended yet")]
+    VotingNotEnded,
+
+    #[msg("Reveal period has ended")]
+    RevealEnded,
+
+    #[msg("Not voted on this proposal")]
+    NotVoted,
+
+    #[msg("Already revealed vote")]
+    AlreadyRevealed,
+
+    #[msg("Invalid vote reveal - commitment mismatch")]
+    InvalidVoteReveal,
+
+    #[msg("Reveal period has not ended yet")]
+    RevealNotEnded,
+
+    #[msg("Proposal already finalized")]
+    AlreadyFinalized,
+
+    // Multisig Errors
+    #[msg("Invalid threshold")]
+    InvalidThreshold,
+
+    #[msg("Too many signers (max 10)")]
+    TooManySigners,
+
+    #[msg("Proposal already executed")]
+    ProposalAlreadyExecuted,
+
+    #[msg("Threshold already reached")]
+    ThresholdReached,
+
+    #[msg("Invalid signer proof")]
+    InvalidSignerProof,
+
+    #[msg("Duplicate approval")]
+    DuplicateApproval,
+
+    #[msg("Insufficient approvals to execute")]
+    InsufficientApprovals,
+
+    // Private Staking Errors (Legacy)
+    #[msg("Stake amount too small")]
+    StakeTooSmall,
+
+    #[msg("Invalid reward rate")]
+    InvalidRewardRate,
+
+    #[msg("Invalid lockup period (must be 1-52 epochs)")]
+    InvalidLockupPeriod,
+
+    #[msg("Stake pool is not active")]
+    PoolNotActive,
+
+    #[msg("Stake is not active")]
+    StakeNotActive,
+
+    #[msg("Stake is still locked")]
+    StakeLocked,
+
+    #[msg("Invalid stake reveal - commitment mismatch")]
+    InvalidStakeReveal,
+
+    #[msg("Invalid reward proof")]
+    InvalidRewardProof,
+
+    #[msg("Insufficient pool funds")]
+    InsufficientPoolFunds,
+
+    // ============================================
+    // SHIELDED POOL ERRORS - True Privacy
+    // ============================================
+
+    #[msg("Invalid proof structure - expected Groth16 format (256 bytes)")]
+    InvalidProofStructure,
+
+    #[msg("Invalid proof point - not a valid field element")]
+    InvalidProofPoint,
+
+    #[msg("Invalid public signal - not a valid field element")]
+    InvalidPublicSignal,
+
+    #[msg("Commitment mismatch - proof is not for this wallet")]
+    CommitmentMismatch,
+
+    #[msg("Invalid proof hash")]
+    InvalidProofHash,
+
+    #[msg("Shielded pool is full")]
+    PoolFull,
+
+    #[msg("Invalid range proof - amount out of valid range")]
+    InvalidRangeProof,
+
+    #[msg("Nullifier has already been used - double-spend attempt")]
+    NullifierAlreadyUsed,
+
+    #[msg("Invalid Merkle proof - note not in tree")]
+    InvalidMerkleProof,
+
+    #[msg("Invalid withdrawal proof")]
+    InvalidWithdrawalProof,
+
+    #[msg("Invalid nullifier derivation")]
+    InvalidNullifier,
+
+    // Proof Routing Errors
+    #[msg("Invalid callback program - must be an executable external program")]
+    InvalidCallbackProgram,
+
+    #[msg("Invalid callback accounts - expected [target_program, proof_authority, ...]")]
+    InvalidCallbackAccounts,
+
+    // Wallet Attestation Errors
+    #[msg("Invalid attestation threshold")]
+    InvalidAttestationThreshold,
+
+    #[msg("Attestation proof reference time is too old or in the future")]
+    StaleAttestationProof,
+
+    #[msg("Attestation is for a different claim")]
+    AttestationClaimMismatch,
+
+    #[msg("Attestation threshold not met")]
+    AttestationThresholdNotMet,
+
+    #[msg("Attestation has expired")]
+    AttestationExpired,
+
+    #[msg("Attestation refresh must use a newer proof or a higher threshold")]
+    AttestationNotNewer,
+
+    #[msg("Wallet registry root is unknown or too old")]
+    UnknownWalletRoot,
+
+    #[msg("Wallet registry is full")]
+    WalletRegistryFull,
+
+    // RLN Errors
+    #[msg("Invalid RLN group parameters")]
+    InvalidRlnParameters,
+
+    #[msg("RLN signal is not for the current epoch")]
+    InvalidRlnEpoch,
+
+    #[msg("Invalid RLN share")]
+    InvalidRlnShare,
+
+    #[msg("RLN member has already been slashed")]
+    RlnMemberAlreadySlashed,
+
+    // Account Layout Errors
+    #[msg("Account data does not match the expected layout")]
+    InvalidAccountLayout,
+
+    #[msg("Account version is newer than this program supports")]
+    UnsupportedAccountVersion,
+
+    // Tree Checkpoint Errors
+    #[msg("Tree checkpoint has no new notes to include")]
+    CheckpointUpToDate,
+
+    #[msg("Note is not the next leaf of this pool")]
+    InvalidCheckpointNote,
+
+    #[msg("Replayed notes do not match the pool Merkle root")]
+    CheckpointRootMismatch,
+
+    // Crank Errors
+    #[msg("Invalid crank task parameters")]
+    InvalidCrankTask,
+
+    #[msg("Crank task is for a different instruction or target")]
+    CrankTaskMismatch,
+
+    #[msg("Crank task was paid too recently")]
+    CrankTooEarly,
+
+    #[msg("Crank task cannot cover its tip")]
+    CrankTaskUnderfunded,
+
+    // Campaign Errors
+    #[msg("Campaign deadline must be in the future")]
+    InvalidCampaignDeadline,
+
+    #[msg("Campaign has ended")]
+    CampaignEnded,
+
+    #[msg("Campaign is still accepting donations")]
+    CampaignStillActive,
+
+    #[msg("Campaign has already been withdrawn")]
+    CampaignAlreadyWithdrawn,
+
+    #[msg("Campaign has no donations")]
+    CampaignEmpty,
+
+    #[msg("Invalid campaign proof")]
+    InvalidCampaignProof,
+
+    #[msg("Campaign commitment must be non-zero")]
+    InvalidCampaignCommitment,
+
+    // Randomness Beacon Errors
+    #[msg("Beacon bond must be non-zero")]
+    InvalidBeaconBond,
+
+    #[msg("Invalid beacon reveal - commitment mismatch")]
+    InvalidBeaconReveal,
+
+    #[msg("Beacon participant has already been slashed")]
+    BeaconEntryAlreadySlashed,
+
+    #[msg("Beacon round has no reveals")]
+    BeaconNoReveals,
+
+    #[msg("Beacon has not been finalized")]
+    BeaconNotFinalized,
+
+    // Multisig Action Errors
+    #[msg("Invalid multisig action payload")]
+    InvalidMultisigAction,
+
+    #[msg("Signer commitment is not in the multisig")]
+    SignerNotFound,
+
+    #[msg("Signer commitment is already in the multisig")]
+    DuplicateSigner,
+
+    // Encrypted Proposal Errors
+    #[msg("Invalid encrypted proposal action")]
+    InvalidEncryptedAction,
+
+    #[msg("Invalid proposal key share")]
+    InvalidKeyShare,
+
+    #[msg("Not enough key shares to decrypt the proposal")]
+    InsufficientKeyShares,
+
+    // Proof of Reserve Errors
+    #[msg("Invalid proof of reserve")]
+    InvalidReserveProof,
+
+    // Note Consolidation Errors
+    #[msg("Invalid consolidation inputs")]
+    InvalidConsolidation,
+
+    #[msg("Invalid consolidation proof")]
+    InvalidConsolidationProof,
+
+    // Claimable Note Errors
+    #[msg("Invalid claimable note")]
+    InvalidClaimableNote,
+
+    #[msg("Note has already been claimed")]
+    NoteAlreadyClaimed,
+
+    #[msg("Invalid claim proof")]
+    InvalidClaimProof,
+
+    // Escrow Errors
+    #[msg("Invalid escrow parameters")]
+    InvalidEscrow,
+
+    #[msg("Invalid escrow proof")]
+    InvalidEscrowProof,
+
+    #[msg("Escrow has already been resolved")]
+    EscrowAlreadyResolved,
+
+    #[msg("Escrow has not been resolved")]
+    EscrowNotResolved,
+
+    #[msg("Escrow has already been settled")]
+    EscrowAlreadySettled,
+
+    // Subscription Errors
+    #[msg("Invalid subscription parameters")]
+    InvalidSubscription,
+
+    #[msg("Subscription period has already been collected")]
+    SubscriptionPeriodCollected,
+
+    #[msg("Subscription period has not started")]
+    SubscriptionPeriodNotStarted,
+
+    #[msg("Invalid subscription collection proof")]
+    InvalidSubscriptionProof,
+
+    // Veto Errors
+    #[msg("Proposal has been vetoed")]
+    ProposalVetoed,
+
+    #[msg("Proposal is still inside its veto window")]
+    VetoWindowOpen,
+
+    #[msg("Veto window has closed")]
+    VetoWindowClosed,
+
+    #[msg("Multisig has no veto authority")]
+    NoVetoAuthority,
+
+    #[msg("Invalid veto authority proof")]
+    InvalidVetoProof,
+
+    #[msg("Account layout is outdated; run migrate_account first")]
+    AccountNeedsMigration,
+
+    // Timelock Errors
+    #[msg("Proposal timelock has not elapsed")]
+    TimelockNotElapsed,
+
+    // Smart Wallet Errors
+    #[msg("Program is not allowlisted for wallet calls")]
+    ProgramNotAllowlisted,
+
+    #[msg("Invalid wallet call accounts")]
+    InvalidWalletCallAccounts,
+
+    #[msg("Invalid wallet call proof")]
+    InvalidWalletCallProof,
+
+    // Relayer Errors
+    #[msg("Invalid relayer parameters")]
+    InvalidRelayerParameters,
+
+    #[msg("Relayer is not active")]
+    RelayerNotActive,
+
+    #[msg("Relayer fee exceeds its quote")]
+    RelayerFeeTooHigh,
+
+    #[msg("Invalid relayed withdrawal proof")]
+    InvalidRelayedWithdrawalProof,
+
+    #[msg("Relayer has already been slashed")]
+    RelayerAlreadySlashed,
+
+    #[msg("Relayer has not requested exit")]
+    RelayerNotExiting,
+
+    #[msg("Relayer stake is still unbonding")]
+    RelayerUnbonding,
+
+    // Deposit Batch Errors
+    #[msg("Invalid deposit batch")]
+    InvalidDepositBatch,
+
+    // Aggregated Withdrawal Errors
+    #[msg("Invalid aggregated withdrawal")]
+    InvalidAggregateWithdrawal,
+
+    #[msg("Invalid aggregated withdrawal proof")]
+    InvalidAggregateProof,
+
+    #[msg("Nullifier record account does not match its nullifier")]
+    InvalidNullifierAccount,
+
+    // Circuit Registry Errors
+    #[msg("Invalid circuit registry parameters")]
+    InvalidCircuitRegistry,
+
+    #[msg("Circuit is already registered")]
+    CircuitAlreadyRegistered,
+
+    #[msg("Circuit is not in the registry")]
+    CircuitNotRegistered,
+
+    #[msg("Verifying key does not match the audited registry entry")]
+    VerifyingKeyMismatch,
+
+    // Key-Value Attestation Errors
+    #[msg("Attestation key must be non-zero")]
+    InvalidAttestationKey,
+
+    #[msg("Invalid attestation proof")]
+    InvalidAttestationProof,
+
+    // Input Size Errors
+    #[msg("Proof exceeds MAX_PROOF_LEN bytes")]
+    ProofTooLarge,
+
+    #[msg("More than MAX_PUBLIC_SIGNALS public signals")]
+    TooManyPublicSignals,
+
+    // Approval Revocation Errors
+    #[msg("No approval with this commitment on the proposal")]
+    ApprovalNotFound,
+
+    #[msg("Invalid approval revocation proof")]
+    InvalidRevocationProof,
+
+    // Reveal Quorum Errors
+    #[msg("Minimum reveal ratio must be at most 10000 bps and the extension non-negative")]
+    InvalidRevealQuorum,
+
+    // Stake-Weighted Voting Errors
+    #[msg("Proposal is stake-weighted; vote with cast_weighted_vote")]
+    StakeWeightedProposal,
+
+    #[msg("Proposal is not stake-weighted")]
+    NotStakeWeighted,
+
+    #[msg("Vote weighting can only be enabled once, before any vote")]
+    StakeWeightingLocked,
+
+    #[msg("Invalid vote weight")]
+    InvalidVoteWeight,
+
+    #[msg("Invalid stake weight proof")]
+    InvalidStakeWeightProof,
+
+    // Wallet Freeze Errors
+    #[msg("Wallet is frozen")]
+    FrozenWallet,
+
+    #[msg("Wallet is not frozen")]
+    WalletNotFrozen,
+
+    #[msg("Invalid unfreeze proof")]
+    InvalidUnfreezeProof,
+
+    // Recovery Handover Errors
+    #[msg("A recovered wallet is awaiting its new owner's acceptance")]
+    HandoverPending,
+
+    #[msg("No recovery handover is pending")]
+    NoPendingHandover,
+
+    #[msg("Recovery must name a new owner")]
+    InvalidNewOwner,
+
+    // Deployment Errors
+    #[msg("Circuit registry is not the one this cluster trusts")]
+    UntrustedCircuitRegistry,
+
+    // Referral Errors
+    #[msg("Invalid referral program or commitment")]
+    InvalidReferralProgram,
+
+    #[msg("No referred deposits left to claim")]
+    NoReferralRewards,
+
+    #[msg("Invalid referral commitment or proof")]
+    InvalidReferralProof,
+
+    #[msg("Referral program cannot cover a single reward")]
+    ReferralProgramUnderfunded,
+
+    #[msg("Referred deposits must pass the referral program and pool vault")]
+    ReferralProgramRequired,
+
+    // Note Sweep Errors
+    #[msg("Note expiry must be 0 or at least a year, with a treasury")]
+    InvalidNoteExpiry,
+
+    #[msg("Invalid note sweep range or treasury")]
+    InvalidNoteSweep,
+
+    #[msg("Notes in the sweep range have not expired")]
+    NotesNotExpired,
+
+    #[msg("A note sweep is already announced")]
+    NoteSweepPending,
+
+    #[msg("Note sweep notice period has not passed")]
+    NoteSweepNoticeActive,
+
+    #[msg("Invalid note sweep proof")]
+    InvalidNoteSweepProof,
+
+    // Pool Governance Errors
+    #[msg("Invalid pool parameter update")]
+    InvalidPoolUpdate,
+
+    #[msg("Pool updates can only be attached before any vote")]
+    PoolUpdateLocked,
+
+    #[msg("Pool is not governed by its stakers")]
+    PoolNotGoverned,
+
+    #[msg("Proposal has not passed")]
+    ProposalNotPassed,
+
+    // Audit Log Errors
+    #[msg("Audit logs are kept for shielded pools and wallets only")]
+    InvalidAuditSubject,
+
+    // Alias Errors
+    #[msg("Invalid alias hash")]
+    InvalidAlias,
+
+    // Nullifier Filter Errors
+    #[msg("This pool keeps a nullifier filter; pass it to spend")]
+    NullifierFilterRequired,
+
+    // Proof Point Errors
+    #[msg("Invalid proof point - pi_a or pi_c is not on the curve")]
+    G1PointNotOnCurve,
+
+    #[msg("Invalid proof point - pi_b is not on the twist curve")]
+    G2PointNotOnCurve,
+
+    // Reentrancy Errors
+    #[msg("Shielded pool is already being modified by this transaction")]
+    PoolBusy,
+
+    // Protocol Stats Errors
+    #[msg("This pool is counted in the protocol stats; pass the stats account")]
+    ProtocolStatsRequired,
+
+    #[msg("Pool is already counted in the protocol stats")]
+    PoolAlreadyInProtocolStats,
+
+    // Decoy Output Errors
+    #[msg("This pool has no decoy outputs configured")]
+    DecoyOutputsDisabled,
+
+    #[msg("Decoy deposits need one real and the pool's decoy count of distinct notes")]
+    InvalidDecoyCount,
+
+    // Holding Account Errors
+    #[msg("Holding withdrawals need a non-zero amount and claim key")]
+    InvalidHoldingWithdrawal,
+
+    #[msg("Invalid holding withdrawal proof")]
+    InvalidHoldingWithdrawalProof,
+
+    #[msg("Invalid holding claim proof")]
+    InvalidHoldingClaimProof,
+
+    // Governance Token Errors
+    #[msg("Proposal is token-weighted; vote with cast_token_weighted_vote")]
+    TokenWeightedProposal,
+
+    #[msg("Proposal is not token-weighted")]
+    NotTokenWeighted,
+
+    #[msg("Governance token deposits need a lockup of at most MAX_GOVERNANCE_LOCKUP_SECONDS")]
+    InvalidGovernanceLockup,
+
+    #[msg("Invalid governance token amount")]
+    InvalidGovernanceTokenAmount,
+
+    #[msg("Governance tokens are locked up or counted in an open vote")]
+    GovernanceTokensLocked,
+
+    #[msg("Invalid governance token account")]
+    InvalidGovernanceTokenAccount,
+
+    // Realms Addin Errors
+    #[msg("Account is not an spl-governance realm or proposal of this registrar")]
+    InvalidRealmAccount,
+
+    // Treasury Attestation Errors
+    #[msg("Treasury band needs min_balance <= max_balance and a non-zero upper bound")]
+    InvalidTreasuryBand,
+
+    #[msg("Invalid treasury attestation proof")]
+    InvalidTreasuryProof,
+
+    // Small Spend Errors
+    #[msg("Multisig has no small-spend policy")]
+    NoSpendLimit,
+
+    #[msg("Only transfer proposals can be small spends")]
+    NotSpendAction,
+
+    #[msg("Small spends must be proven before any approval")]
+    SmallSpendLocked,
+
+    #[msg("Invalid small-spend proof")]
+    InvalidSmallSpendProof,
+
+    // Signer Liveness Errors
+    #[msg("Multisig does not track signer liveness")]
+    LivenessNotTracked,
+
+    #[msg("Heartbeat epoch or account does not match")]
+    InvalidHeartbeatEpoch,
+
+    #[msg("Signer already posted a heartbeat this epoch")]
+    DuplicateHeartbeat,
+
+    #[msg("Every signer already posted a heartbeat this epoch")]
+    HeartbeatsFull,
+
+    #[msg("Invalid heartbeat proof")]
+    InvalidHeartbeatProof,
+
+    #[msg("Enough signers are live; the threshold cannot degrade")]
+    SignersLive,
+
+    #[msg("A threshold degradation is already pending")]
+    DegradationPending,
+
+    #[msg("No threshold degradation is pending")]
+    NoDegradationPending,
+
+    // Delegated Reveal Errors
+    #[msg("Vote reveal was not delegated")]
+    RevealNotDelegated,
+
+    #[msg("Reveal token does not match the vote record")]
+    InvalidRevealToken,
+
+    // Proposal Template Errors
+    #[msg("Proposal restricts voting to eligible keys; an eligibility proof is required")]
+    EligibilityProofRequired,
+
+    #[msg("Voter is not in the proposal's eligibility tree")]
+    NotEligible,
+
+    #[msg("Proposals with an eligibility list cannot be weighted")]
+    EligibilityRestricted,
+
+    // Tally Dispute Errors
+    #[msg("Tally disputes must be enabled once, before any vote")]
+    TallyDisputeLocked,
+
+    #[msg("Dispute window out of range")]
+    InvalidDisputeWindow,
+
+    #[msg("Proposal tally has not been committed")]
+    TallyNotCommitted,
+
+    #[msg("Tally dispute window is still open")]
+    DisputeWindowOpen,
+
+    #[msg("Tally dispute window has closed")]
+    DisputeWindowClosed,
+
+    #[msg("Proposal tally is already disputed")]
+    TallyAlreadyDisputed,
+
+    #[msg("Invalid tally challenge proof")]
+    InvalidTallyChallenge,
+
+    // Conviction Voting Errors
+    #[msg("Invalid conviction proposal parameters")]
+    InvalidConvictionProposal,
+
+    #[msg("Conviction proposal is closed")]
+    ConvictionClosed,
+
+    #[msg("Invalid conviction stake proof")]
+    InvalidConvictionStakeProof,
+
+    #[msg("Invalid conviction proof")]
+    InvalidConvictionProof,
+
+    // Quadratic Voting Errors
+    #[msg("Invalid quadratic proposal parameters")]
+    InvalidQuadraticProposal,
+
+    #[msg("Quadratic vote must give a count for every option")]
+    InvalidQuadraticVote,
+
+    #[msg("Invalid quadratic vote proof")]
+    InvalidQuadraticProof,
+
+    // Circuit Pinning Errors
+    #[msg("Proof was pinned to a different circuit than this instruction verifies")]
+    CircuitIdMismatch,
+
+    // CPI Deposit Errors
+    #[msg("Deposit caller must be an executable program other than this one")]
+    InvalidDepositCaller,
+
+    #[msg("Depositor is not the allowlisted caller program's depositor PDA")]
+    DepositCallerMismatch,
+
+    #[msg("CPI deposits must be invoked by the caller program, not the transaction")]
+    DepositNotCpi,
+
+    // Withdrawal Screening Errors
+    #[msg("Screener must be an executable program other than this one")]
+    InvalidScreenerProgram,
+
+    #[msg("This pool screens withdrawals; pass its screener program")]
+    ScreenerRequired,
+
+    #[msg("This pool screens withdrawals; only shield_withdraw can be screened")]
+    WithdrawalNotScreenable,
+
+    // Split Withdrawal Errors
+    #[msg("Split withdrawal needs 1 to 4 distinct recipients with non-zero shares")]
+    InvalidSplitWithdrawal,
+
+    #[msg("Recipient accounts must match the split payments in order")]
+    SplitRecipientMismatch,
+
+    #[msg("Invalid split withdrawal proof")]
+    InvalidSplitWithdrawalProof,
+
+    // Offline Signing Errors
+    #[msg("Offline approval has passed its valid_until time")]
+    OfflineApprovalExpired,
+
+    #[msg("Offline approval was signed for a different proposal action")]
+    OfflineApprovalMismatch,
+
+    // Backup Errors
+    #[msg("Backup hash must be non-zero and differ from the current backup")]
+    InvalidBackup,
+
+    // Deposit Cancellation Errors
+    #[msg("Cancellation needs an unspent note and a non-zero refund")]
+    InvalidDepositCancel,
+
+    #[msg("The deposit's cancellation window has closed")]
+    DepositCancelWindowClosed,
+
+    #[msg("The note is already in a published tree checkpoint")]
+    DepositAlreadyCheckpointed,
+
+    #[msg("Account is not this pool's tree checkpoint")]
+    InvalidTreeCheckpoint,
+
+    #[msg("Invalid deposit cancellation proof")]
+    InvalidDepositCancelProof,
+
+    // Watchtower Errors
+    #[msg("Watchtowers are kept for wallets, multisigs and shielded pools only")]
+    InvalidWatchtowerSubject,
+
+    #[msg("Alert key must be set")]
+    InvalidAlertKey,
+
+    // Relayer Solvency Errors
+    #[msg("Relayer has no current solvency proof")]
+    RelayerSolvencyLapsed,
+
+    #[msg("Invalid relayer solvency proof")]
+    InvalidRelayerSolvencyProof,
+
+    // Reward Curve Errors
+    #[msg("Reward curve points must start in increasing order with rates of at most 10000 bps")]
+    InvalidRewardCurve,
+
+    #[msg("Reward curve points already in effect cannot be changed")]
+    RewardCurveHistoryChanged,
+
+    // Early Exit Errors
+    #[msg("Early exits are not enabled on this pool")]
+    EarlyExitDisabled,
+
+    #[msg("Early-exit penalty must be 1-5000 bps, or 0 to disable")]
+    InvalidEarlyExitPenalty,
+
+    #[msg("Early exit must release a non-zero amount")]
+    InvalidEarlyExit,
+
+    #[msg("Invalid early-exit proof")]
+    InvalidEarlyExitProof,
+
+    // Deposit Routing Errors
+    #[msg("Deposit route must list 1-8 notes, each pool's notes together, with matching accounts")]
+    InvalidDepositRoute,
+
+    // Wallet Freeze Errors
+    #[msg("A wallet needs a recovery commitment before it can be frozen")]
+    NoRecoveryCommitment,
+
+    // Pool Governance Errors
+    #[msg("Pool governance needs commit and reveal phases of at least a day, a reveal quorum, a non-zero stake quorum and a bounded delay")]
+    InvalidPoolGovernance,
+
+    #[msg("Proposal does not meet the pool's governance rules")]
+    PoolGovernanceViolation,
+
+    #[msg("Passing pool updates wait out the governance execution delay")]
+    PoolUpdateDelayActive,
+
+    #[msg("Hand a pool to its governance PDA with govern_pool")]
+    UseGovernPool,
+
+    // Multisig Signer Set Errors
+    #[msg("The signer set changed since these approvals; the proposal must be re-approved")]
+    SignerSetChanged,
+
+    // Multisig Batch Errors
+    #[msg("Batches can only hold steps that execute on-chain (pool-admin calls)")]
+    BatchStepNotExecutable,
+}
@@ -0,0 +1,1475 @@
+//! Program events
+//!
+//! Named events per feature, plus the compact `VeilRecord` every mutating
+//! instruction emits through a self-CPI.
+
+use crate::*;
+
+// Events
+//
+// Events that clients need to rebuild note trees and nullifier sets
+// (deposits, withdrawals, spent nullifiers, root updates) are emitted with
+// `emit_cpi!` so they cannot be lost to log truncation; read them from the
+// program's self-invocations. The rest still go to the log via `emit!`.
+
+/// Compact record emitted through a self-CPI (`emit_cpi!`) by every
+/// instruction that mutates state
+///
+/// Unlike the named events below, records never go through the program log,
+/// so they survive log truncation and can be read straight from inner
+/// instructions by Geyser plugins and indexers without the IDL. The inner
+/// instruction data is always 163 bytes: `EVENT_IX_TAG` (8) + this event's
+/// discriminator (8) + the fixed layout below. Which fields carry what
+/// depends on `kind` (see `RecordKind`); unused fields are zero. Like the
+/// named events, records never carry amounts.
+#[event]
+pub struct VeilRecord {
+    /// `RecordKind` code: high byte is the feature, low byte the instruction
+    pub kind: u16,
+    /// Layout version of this record (`VeilRecord::LAYOUT`)
+    pub layout: u8,
+    /// Primary account the instruction mutated (pool, proposal, wallet, ...)
+    pub account: Pubkey,
+    /// Main 32-byte value: nullifier, commitment or identifier
+    pub subject: [u8; 32],
+    /// Secondary 32-byte value, e.g. the output commitment of a withdrawal
+    pub detail: [u8; 32],
+    /// Merkle root after the mutation, for pool and group instructions
+    pub root: [u8; 32],
+    /// Kind-specific counter or index (note index, count, epoch, ...)
+    pub value: u64,
+    pub timestamp: i64,
+}
+
+impl VeilRecord {
+    pub const LAYOUT: u8 = 1;
+
+    pub(crate) fn new(
+        kind: RecordKind,
+        account: Pubkey,
+        subject: [u8; 32],
+        detail: [u8; 32],
+        root: [u8; 32],
+        value: u64,
+        timestamp: i64,
+    ) -> Self {
+        Self {
+            kind: kind as u16,
+            layout: Self::LAYOUT,
+            account,
+            subject,
+            detail,
+            root,
+            value,
+            timestamp,
+        }
+    }
+}
+
+/// Stable `VeilRecord::kind` codes. Codes are never reused or renumbered;
+/// new instructions take the next free code in their feature's range.
+#[repr(u16)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordKind {
+    // Wallets
+    InitializeCommitment = 0x0101,
+    SubmitProof = 0x0102,
+    InitiateRecovery = 0x0103,
+    ExecuteRecovery = 0x0104,
+    CancelRecovery = 0x0105,
+    ExecuteAsWallet = 0x0106,
+    AttestWalletProperty = 0x0107,
+    Attest = 0x0108,
+    RevokeAttestation = 0x0109,
+    FreezeWallet = 0x010A,
+    UnfreezeWallet = 0x010B,
+    AcceptRecovery = 0x010C,
+    RegisterAlias = 0x010D,
+    UpdateAlias = 0x010E,
+    ReleaseAlias = 0x010F,
+    RegisterBackup = 0x0110,
+    CreateWalletRegistry = 0x0111,
+    EnrollWallet = 0x0112,
+
+    // Voting
+    CreateProposal = 0x0201,
+    CastVote = 0x0202,
+    RevealVote = 0x0203,
+    FinalizeProposal = 0x0204,
+    ExtendRevealPeriod = 0x0205,
+    EnableStakeWeightedVoting = 0x0206,
+    CastWeightedVote = 0x0207,
+    RevealWeightedVote = 0x0208,
+    AttachPoolUpdate = 0x0209,
+    ExecutePoolUpdate = 0x020A,
+    DepositGovernanceTokens = 0x020B,
+    WithdrawGovernanceTokens = 0x020C,
+    EnableTokenWeightedVoting = 0x020D,
+    CastTokenWeightedVote = 0x020E,
+    CreateRealmRegistrar = 0x020F,
+    SnapshotRealmProposal = 0x0210,
+    UpdateRealmVoterWeight = 0x0211,
+    UpdateRealmMaxVoterWeight = 0x0212,
+    RevealVoteWithToken = 0x0213,
+    CreateProposalTemplate = 0x0214,
+    CreateProposalFromTemplate = 0x0215,
+    EnableTallyDispute = 0x0216,
+    CommitProposalTally = 0x0217,
+    ChallengeProposalTally = 0x0218,
+    CreateConvictionProposal = 0x0219,
+    CommitConvictionStake = 0x021A,
+    ProveConviction = 0x021B,
+    WithdrawConvictionStake = 0x021C,
+    CreateQuadraticProposal = 0x021D,
+    CastQuadraticVote = 0x021E,
+    GovernPool = 0x021F,
+
+    // Randomness beacon
+    CreateBeaconRound = 0x0301,
+    CommitBeaconSecret = 0x0302,
+    RevealBeaconSecret = 0x0303,
+    SlashBeaconNonReveal = 0x0304,
+    FinalizeBeacon = 0x0305,
+
+    // Multisig
+    CreateMultisig = 0x0401,
+    CreateMultisigProposal = 0x0402,
+    StealthSign = 0x0403,
+    ExecuteMultisigProposal = 0x0404,
+    CreateEncryptedMultisigProposal = 0x0405,
+    ExecuteEncryptedMultisigProposal = 0x0406,
+    VetoMultisigProposal = 0x0407,
+    RevokeStealthSignature = 0x0408,
+    PublishTreasuryAttestation = 0x0409,
+    ProveSmallSpend = 0x040A,
+    PostSignerHeartbeat = 0x040B,
+    AnnounceThresholdDegradation = 0x040C,
+    ExecuteThresholdDegradation = 0x040D,
+    RotateSignerKey = 0x040E,
+
+    // RLN
+    CreateRlnGroup = 0x0501,
+    RegisterRlnMember = 0x0502,
+    RlnSignal = 0x0503,
+    RlnSlash = 0x0504,
+
+    // Shielded pool
+    CreateShieldedPool = 0x0601,
+    ShieldDeposit = 0x0602,
+    ShieldDepositBatch = 0x0603,
+    ShieldWithdraw = 0x0604,
+    ShieldWithdrawAggregated = 0x0605,
+    ShieldConsolidate = 0x0606,
+    ClaimShieldedRewards = 0x0607,
+    CreateClaimableNote = 0x0608,
+    ClaimNote = 0x0609,
+    SetPoolAuthority = 0x060A,
+    CreateNullifierFilter = 0x060B,
+    CreateProtocolStats = 0x060C,
+    RegisterPoolStats = 0x060D,
+    ConfigureDecoyOutputs = 0x060E,
+    ConfigureWithdrawalScreener = 0x060F,
+    ConfigureRewardCurve = 0x0610,
+
+    // Escrow
+    CreateEscrow = 0x0701,
+    VoteEscrow = 0x0702,
+    SettleEscrow = 0x0703,
+
+    // Subscriptions
+    CreateSubscription = 0x0801,
+    CollectSubscription = 0x0802,
+    CancelSubscription = 0x0803,
+
+    // Campaigns
+    CreateCampaign = 0x0901,
+    DonateToCampaign = 0x0902,
+    WithdrawCampaign = 0x0903,
+
+    // Proof of reserve
+    PublishReserveProof = 0x0A01,
+
+    // Relayers
+    CreateRelayerRegistry = 0x0B01,
+    RegisterRelayer = 0x0B02,
+    UpdateRelayerQuote = 0x0B03,
+    ShieldWithdrawRelayed = 0x0B04,
+    SlashRelayer = 0x0B05,
+    RequestRelayerExit = 0x0B06,
+    CloseRelayer = 0x0B07,
+    ConfigureRelayerSolvency = 0x0B08,
+    ProveRelayerSolvency = 0x0B09,
+
+    // Deposit receipts
+    EnableDepositReceipts = 0x0C01,
+    ShieldDepositWithReceipt = 0x0C02,
+
+    // Circuits
+    CreateCircuitRegistry = 0x0D01,
+    RegisterCircuit = 0x0D02,
+
+    // Cranks
+    RegisterCrankTask = 0x0E01,
+    FundCrankTask = 0x0E02,
+    CloseCrankTask = 0x0E03,
+
+    // Tree checkpoints
+    CreateTreeCheckpoint = 0x0F01,
+    CheckpointTree = 0x0F02,
+
+    // Legacy staking
+    CreateStakePool = 0x1001,
+    StakePrivate = 0x1002,
+    Unstake = 0x1003,
+    ClaimRewards = 0x1004,
+
+    // Account migration
+    MigrateAccount = 0x1101,
+
+    // Referrals
+    CreateReferralProgram = 0x1201,
+    FundReferralProgram = 0x1202,
+    RegisterReferrer = 0x1203,
+    ClaimReferralRewards = 0x1204,
+    ConfigureReferralProgram = 0x1205,
+
+    // Note expiry
+    ConfigureNoteExpiry = 0x1301,
+    AnnounceNoteSweep = 0x1302,
+    CancelNoteSweep = 0x1303,
+    ExecuteNoteSweep = 0x1304,
+
+    // Audit log
+    CreateAuditLog = 0x1401,
+
+    // Holding accounts
+    ShieldWithdrawToHolding = 0x1501,
+    ClaimHolding = 0x1502,
+
+    // CPI deposits
+    AllowDepositCaller = 0x1601,
+    RevokeDepositCaller = 0x1602,
+    ShieldDepositCpi = 0x1603,
+
+    // Split withdrawals
+    ShieldWithdrawSplit = 0x1701,
+
+    // Deposit cancellation
+    CancelDeposit = 0x1801,
+
+    // Watchtowers
+    RegisterWatchtower = 0x1901,
+    UpdateWatchtower = 0x1902,
+
+    // Early exits
+    ConfigureEarlyExit = 0x1A01,
+    ShieldWithdrawEarly = 0x1A02,
+
+    // Deposit routing
+    RouteDeposit = 0x1B01,
+}
+
+#[event]
+pub struct CommitmentCreated {
+    pub wallet: Pubkey,
+    pub commitment: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProofVerified {
+    pub wallet: Pubkey,
+    pub proof_hash: [u8; 32],
+    pub public_signals_hash: [u8; 32],
+    pub verification_type: ProofType,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProofRouted {
+    pub wallet: Pubkey,
+    pub target_program: Pubkey,
+    pub proof_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryInitiated {
+    pub wallet: Pubkey,
+    pub recovery_commitment: [u8; 32],
+    pub unlock_time: i64,
+}
+
+#[event]
+pub struct RecoveryExecuted {
+    pub wallet: Pubkey,
+    /// Key that must sign `accept_recovery` to take over the wallet
+    pub pending_owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryAccepted {
+    pub wallet: Pubkey,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryCancelled {
+    pub wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WalletFrozen {
+    pub wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WalletUnfrozen {
+    pub wallet: Pubkey,
+    pub frozen_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AliasRegistered {
+    pub alias: Pubkey,
+    pub alias_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AliasUpdated {
+    pub alias: Pubkey,
+    pub alias_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AliasReleased {
+    pub alias: Pubkey,
+    pub alias_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BackupRegistered {
+    pub wallet: Pubkey,
+    pub backup: Pubkey,
+    pub backup_version: u64,
+    pub backup_hash: [u8; 32],
+    pub previous_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WalletEnrolled {
+    pub wallet: Pubkey,
+    pub commitment: [u8; 32],
+    pub leaf_index: u32,
+    pub root: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WalletCallExecuted {
+    pub wallet: Pubkey,
+    pub target_program: Pubkey,
+    pub call_tag: [u8; 32],
+    pub nonce: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AttestationWritten {
+    pub attestation: Pubkey,
+    pub wallet: Pubkey,
+    pub key: [u8; 32],
+    pub value_hash: [u8; 32],
+    pub revision: u32,
+    pub timestamp: i64,
+    // Note: the attested value is NEVER included, only its hash
+}
+
+#[event]
+pub struct AttestationRevoked {
+    pub attestation: Pubkey,
+    pub wallet: Pubkey,
+    pub key: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WalletAttested {
+    pub attestation: Pubkey,
+    pub claim: AttestationClaim,
+    pub threshold: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+    // Note: wallet identity is NOT included to preserve privacy
+}
+
+// Private Voting Events
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal: Pubkey,
+    pub proposal_id: [u8; 32],
+    pub creator: Pubkey,
+    pub voting_ends_at: i64,
+    pub reveal_ends_at: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub commitment: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeWeightingEnabled {
+    pub proposal: Pubkey,
+    pub pool: Pubkey,
+    pub weight_root: [u8; 32],
+    pub weight_unit: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolGoverned {
+    pub pool: Pubkey,
+    pub governance: Pubkey,
+    pub previous_authority: Pubkey,
+    pub config: PoolGovernanceConfig,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolUpdateAttached {
+    pub proposal: Pubkey,
+    pub pool: Pubkey,
+    pub update: PoolParamUpdate,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolUpdateExecuted {
+    pub proposal: Pubkey,
+    pub pool: Pubkey,
+    pub update: PoolParamUpdate,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WeightedVoteCast {
+    pub proposal: Pubkey,
+    pub weight_nullifier: [u8; 32],
+    pub commitment: [u8; 32],
+    /// Claimed lower bound, in `weight_unit`s - never the stake itself
+    pub weight: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenWeightingEnabled {
+    pub proposal: Pubkey,
+    pub governing_mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenWeightedVoteCast {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub commitment: [u8; 32],
+    /// Escrowed tokens with the lockup bonus at cast time
+    pub weight: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceTokensDeposited {
+    pub voter_weight_record: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub lockup_ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceTokensWithdrawn {
+    pub voter_weight_record: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RealmRegistrarCreated {
+    pub registrar: Pubkey,
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub pool: Pubkey,
+    pub weight_unit: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RealmProposalSnapshotted {
+    pub registrar: Pubkey,
+    pub proposal: Pubkey,
+    pub weight_root: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RealmVoterWeightUpdated {
+    pub registrar: Pubkey,
+    pub proposal: Pubkey,
+    pub weight_nullifier: [u8; 32],
+    pub voter_weight: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RealmMaxVoterWeightUpdated {
+    pub registrar: Pubkey,
+    pub max_voter_weight: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QuadraticProposalCreated {
+    pub proposal: Pubkey,
+    pub creator: Pubkey,
+    pub credit_root: [u8; 32],
+    pub option_count: u8,
+    pub voting_ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct QuadraticVoteCast {
+    pub proposal: Pubkey,
+    pub vote_nullifier: [u8; 32],
+    pub votes: Vec<u64>,
+    pub timestamp: i64,
+    // Note: neither the voter nor their credit budget is included
+}
+
+#[event]
+pub struct ConvictionProposalCreated {
+    pub proposal: Pubkey,
+    pub pool: Pubkey,
+    pub conviction_threshold: u64,
+    pub ramp_period: i64,
+    pub closes_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConvictionStakeCommitted {
+    pub proposal: Pubkey,
+    pub stake: Pubkey,
+    pub stake_commitment: [u8; 32],
+    pub timestamp: i64,
+    // Note: the staked weight is NOT included
+}
+
+#[event]
+pub struct ConvictionProven {
+    pub proposal: Pubkey,
+    pub stake: Pubkey,
+    pub conviction: u64,
+    pub total_conviction: u64,
+    pub threshold_reached: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConvictionStakeWithdrawn {
+    pub proposal: Pubkey,
+    pub stake: Pubkey,
+    pub total_conviction: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TallyDisputeEnabled {
+    pub proposal: Pubkey,
+    pub dispute_window: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalTallyCommitted {
+    pub proposal: Pubkey,
+    pub tally_commitment: [u8; 32],
+    pub disputable_until: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalTallyDisputed {
+    pub proposal: Pubkey,
+    pub tally_commitment: [u8; 32],
+    pub challenger: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FinalizationBountyPaid {
+    pub proposal: Pubkey,
+    pub finalizer: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalTemplateCreated {
+    pub template: Pubkey,
+    pub creator: Pubkey,
+    pub template_id: [u8; 32],
+    pub voting_period: i64,
+    pub reveal_period: i64,
+    pub eligibility_root: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteRevealed {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub timestamp: i64,
+    // Note: vote choice is NOT included to preserve privacy
+}
+
+#[event]
+pub struct ProposalFinalized {
+    pub proposal: Pubkey,
+    pub yes_count: u32,
+    pub no_count: u32,
+    pub total_votes: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RevealPeriodExtended {
+    pub proposal: Pubkey,
+    pub reveal_ends_at: i64,
+    pub total_commitments: u32,
+    pub total_revealed: u32,
+    pub extensions: u8,
+    pub timestamp: i64,
+}
+
+/// The reveal phase ran out below `min_reveal_bps`; the proposal is
+/// finalized but its tally does not decide it
+#[event]
+pub struct UnrevealedVotesExpired {
+    pub proposal: Pubkey,
+    pub total_commitments: u32,
+    pub total_revealed: u32,
+    pub min_reveal_bps: u16,
+    pub timestamp: i64,
+}
+
+// Randomness Beacon Events
+
+#[event]
+pub struct BeaconRoundCreated {
+    pub round: Pubkey,
+    pub round_id: [u8; 32],
+    pub commit_ends_at: i64,
+    pub reveal_ends_at: i64,
+    pub bond_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BeaconSecretCommitted {
+    pub round: Pubkey,
+    pub participant: Pubkey,
+    pub commitment: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BeaconSecretRevealed {
+    pub round: Pubkey,
+    pub participant: Pubkey,
+    pub timestamp: i64,
+    // Note: Individual secrets are not emitted, only folded into the beacon
+}
+
+#[event]
+pub struct BeaconParticipantSlashed {
+    pub round: Pubkey,
+    pub participant: Pubkey,
+    pub bond_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BeaconFinalized {
+    pub round: Pubkey,
+    pub beacon: [u8; 32],
+    pub reveal_count: u32,
+    pub commit_count: u32,
+    pub timestamp: i64,
+}
+
+// Stealth Multisig Events
+
+#[event]
+pub struct MultisigCreated {
+    pub multisig: Pubkey,
+    pub vault_id: [u8; 32],
+    pub threshold: u8,
+    pub total_signers: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MultisigProposalCreated {
+    pub multisig: Pubkey,
+    pub proposal: Pubkey,
+    pub proposal_id: [u8; 32],
+    pub kind: MultisigActionKind,
+    pub instruction_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StealthSignatureAdded {
+    pub proposal: Pubkey,
+    pub approval_commitment: [u8; 32],
+    pub current_approvals: u8,
+    pub threshold: u8,
+    /// Set once the final approval lands (0 before)
+    pub executable_after: i64,
+    pub timestamp: i64,
+    // Note: signer identity is NOT included to preserve privacy
+}
+
+#[event]
+pub struct StealthSignatureRevoked {
+    pub proposal: Pubkey,
+    pub approval_commitment: [u8; 32],
+    pub current_approvals: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+    // Note: signer identity is NOT included to preserve privacy
+}
+
+#[event]
+pub struct MultisigProposalExecuted {
+    pub multisig: Pubkey,
+    pub proposal: Pubkey,
+    pub kind: MultisigActionKind,
+    pub approval_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProposalVetoed {
+    pub multisig: Pubkey,
+    pub proposal: Pubkey,
+    pub approval_count: u8,
+    pub timestamp: i64,
+    // Note: veto authority identity is NOT included to preserve privacy
+}
+
+#[event]
+pub struct MultisigProposalDecrypted {
+    pub proposal: Pubkey,
+    pub action: MultisigAction,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SmallSpendProven {
+    pub multisig: Pubkey,
+    pub proposal: Pubkey,
+    pub timestamp: i64,
+    // Note: neither the amount nor the hidden limit is included
+}
+
+#[event]
+pub struct SignerKeyRotated {
+    pub multisig: Pubkey,
+    pub total_signers: u8,
+    pub timestamp: i64,
+    // Note: neither the old nor the new commitment is included
+}
+
+#[event]
+pub struct SignerHeartbeatPosted {
+    pub multisig: Pubkey,
+    pub epoch: u64,
+    pub live_signers: u8,
+    pub degradation_cancelled: bool,
+    pub timestamp: i64,
+    // Note: the signer is NOT identified, only counted
+}
+
+#[event]
+pub struct ThresholdDegradationAnnounced {
+    pub multisig: Pubkey,
+    /// Epoch that ended without quorum
+    pub epoch: u64,
+    pub live_signers: u8,
+    pub threshold: u8,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ThresholdDegraded {
+    pub multisig: Pubkey,
+    pub old_threshold: u8,
+    pub new_threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryAttestationPublished {
+    pub multisig: Pubkey,
+    pub attestation: Pubkey,
+    pub pool: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub min_balance: u64,
+    pub max_balance: u64,
+    pub proof_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+// RLN Events
+
+#[event]
+pub struct RlnGroupCreated {
+    pub group: Pubkey,
+    pub group_id: [u8; 32],
+    pub stake_lamports: u64,
+    pub epoch_length: i64,
+    pub message_limit: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RlnMemberRegistered {
+    pub group: Pubkey,
+    pub identity_commitment: [u8; 32],
+    pub member_index: u32,
+    pub members_root: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RlnSignalRecorded {
+    pub group: Pubkey,
+    pub nullifier: [u8; 32],
+    pub epoch: u64,
+    pub share_x: [u8; 32],
+    pub share_y: [u8; 32],
+    pub timestamp: i64,
+    // Note: member identity is NOT included to preserve privacy
+}
+
+#[event]
+pub struct RlnMemberSlashed {
+    pub group: Pubkey,
+    pub identity_commitment: [u8; 32],
+    pub nullifier: [u8; 32],
+    pub slasher: Pubkey,
+    pub slashed_lamports: u64,
+    pub timestamp: i64,
+}
+
+// ============================================
+// SHIELDED POOL EVENTS - True Privacy
+// ============================================
+
+#[event]
+pub struct ShieldedPoolCreated {
+    pub pool: Pubkey,
+    pub pool_id: [u8; 32],
+    pub creator: Pubkey,
+    pub reward_rate_bps: u16,
+    pub lockup_epochs: u8,
+    pub timestamp: i64,
+    // Note: NO amount information - privacy by design
+}
+
+#[event]
+pub struct PoolAuthorityChanged {
+    pub pool: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ShieldedDeposit {
+    pub pool: Pubkey,
+    pub note_commitment: [u8; 32],
+    /// The note's `encrypted_data`, opaque to the program (any ephemeral
+    /// key of the sender's encryption scheme travels inside it), so
+    /// recipients can recover notes from events alone
+    pub encrypted_note: [u8; 64],
+    pub note_index: u32,
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+    // Note: Amount is NEVER included - true privacy!
+}
+
+#[event]
+pub struct ShieldedWithdraw {
+    pub pool: Pubkey,
+    pub nullifier: [u8; 32],
+    pub output_commitment: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+    // Note: Amount is NEVER included - true privacy!
+}
+
+#[event]
+pub struct ShieldedConsolidate {
+    pub pool: Pubkey,
+    pub nullifiers: Vec<[u8; 32]>,
+    pub output_commitment: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+    // Note: Amounts are NEVER included - true privacy!
+}
+
+#[event]
+pub struct ShieldedWithdrawAggregated {
+    pub pool: Pubkey,
+    pub nullifiers: Vec<[u8; 32]>,
+    pub output_commitments: Vec<[u8; 32]>,
+    /// Root the aggregated proof was made against
+    pub proven_root: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+    // Note: Amounts are NEVER included - true privacy!
+}
+
+#[event]
+pub struct ShieldedRewardsClaimed {
+    pub pool: Pubkey,
+    pub stake_nullifier: [u8; 32],
+    pub new_note_commitment: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+    // Note: Reward amount is NEVER included - true privacy!
+}
+
+#[event]
+pub struct ClaimableNoteCreated {
+    pub pool: Pubkey,
+    pub claimable_note: Pubkey,
+    pub note_commitment: [u8; 32],
+    pub note_index: u32,
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+    // Note: Amount is NEVER included - true privacy!
+}
+
+#[event]
+pub struct ClaimableNoteClaimed {
+    pub pool: Pubkey,
+    pub claimable_note: Pubkey,
+    pub nullifier: [u8; 32],
+    pub output_commitment: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+    // Note: Amount and recipient are NEVER included - true privacy!
+}
+
+#[event]
+pub struct EscrowCreated {
+    pub escrow: Pubkey,
+    pub pool: Pubkey,
+    pub order_hash: [u8; 32],
+    pub nullifier: [u8; 32],
+    pub timestamp: i64,
+    // Note: Amount and parties are NEVER included - true privacy!
+}
+
+#[event]
+pub struct EscrowVoted {
+    pub escrow: Pubkey,
+    pub role: EscrowRole,
+    pub outcome: EscrowOutcome,
+    pub resolution: EscrowOutcome,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowSettled {
+    pub escrow: Pubkey,
+    pub resolution: EscrowOutcome,
+    pub output_commitment: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+    // Note: Amount and recipient are NEVER included - true privacy!
+}
+
+#[event]
+pub struct SubscriptionCreated {
+    pub subscription: Pubkey,
+    pub pool: Pubkey,
+    pub period_seconds: i64,
+    pub start_at: i64,
+    pub timestamp: i64,
+    // Note: Amount, payer and merchant are NEVER included - true privacy!
+}
+
+#[event]
+pub struct SubscriptionCollected {
+    pub subscription: Pubkey,
+    pub period: u64,
+    pub nullifier: [u8; 32],
+    pub payment_commitment: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+    // Note: Amount is NEVER included - true privacy!
+}
+
+#[event]
+pub struct SubscriptionCancelled {
+    pub subscription: Pubkey,
+    pub collected_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CampaignCreated {
+    pub campaign: Pubkey,
+    pub pool: Pubkey,
+    pub campaign_id: [u8; 32],
+    pub deadline: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CampaignDonation {
+    pub campaign: Pubkey,
+    pub note_commitment: [u8; 32],
+    pub note_index: u32,
+    pub aggregate_commitment: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+    // Note: Donation amount is NEVER included - true privacy!
+}
+
+#[event]
+pub struct CampaignWithdrawn {
+    pub campaign: Pubkey,
+    pub output_commitment: [u8; 32],
+    pub donation_count: u32,
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+    // Note: Total raised is NEVER included - true privacy!
+}
+
+#[event]
+pub struct ReserveProofPublished {
+    pub pool: Pubkey,
+    pub attestation: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub vault_balance: u64,
+    pub proof_hash: [u8; 32],
+    pub timestamp: i64,
+    // Note: Individual note values are NEVER included - true privacy!
+}
+
+#[event]
+pub struct RelayerRegistryCreated {
+    pub registry: Pubkey,
+    pub pool: Pubkey,
+    pub min_stake: u64,
+    pub max_fee_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RelayerRegistered {
+    pub registry: Pubkey,
+    pub relayer: Pubkey,
+    pub stake_lamports: u64,
+    pub fee_quote: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RelayerQuoteUpdated {
+    pub registry: Pubkey,
+    pub relayer: Pubkey,
+    pub fee_quote: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ShieldedWithdrawRelayed {
+    pub pool: Pubkey,
+    pub relayer: Pubkey,
+    pub nullifier: [u8; 32],
+    pub output_commitment: [u8; 32],
+    pub fee_commitment: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+    // Note: Withdrawal amount is NEVER included - true privacy!
+}
+
+#[event]
+pub struct ShieldedWithdrawHeld {
+    pub pool: Pubkey,
+    pub holding_account: Pubkey,
+    pub nullifier: [u8; 32],
+    pub output_commitment: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ShieldedWithdrawSplit {
+    pub pool: Pubkey,
+    pub nullifier: [u8; 32],
+    pub output_commitment: [u8; 32],
+    pub recipients: Vec<Pubkey>,
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+    // Shares are not repeated here; they are visible in the transfers
+}
+
+#[event]
+pub struct DepositCancelled {
+    pub pool: Pubkey,
+    pub note_index: u32,
+    pub nullifier: [u8; 32],
+    pub recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EarlyExitConfigured {
+    pub pool: Pubkey,
+    pub penalty_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ShieldedEarlyExit {
+    pub pool: Pubkey,
+    pub nullifier: [u8; 32],
+    pub output_commitment: [u8; 32],
+    pub recipient: Pubkey,
+    /// Kept in the vault as reward reserve
+    pub penalty_lamports: u64,
+    pub merkle_root: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct HoldingClaimed {
+    pub pool: Pubkey,
+    pub holding_account: Pubkey,
+    pub recipient: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RelayerSlashed {
+    pub registry: Pubkey,
+    pub relayer: Pubkey,
+    pub evidence_hash: [u8; 32],
+    pub slashed_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RelayerSolvencyConfigured {
+    pub registry: Pubkey,
+    pub solvency_period: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RelayerSolvencyProven {
+    pub registry: Pubkey,
+    pub relayer: Pubkey,
+    pub obligations_commitment: [u8; 32],
+    pub stake_lamports: u64,
+    pub solvent_until: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RelayerExitRequested {
+    pub registry: Pubkey,
+    pub relayer: Pubkey,
+    pub unbonds_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DepositReceiptsEnabled {
+    pub config: Pubkey,
+    pub pool: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DepositReceiptMinted {
+    pub pool: Pubkey,
+    pub note_index: u32,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+    // Note: Amount is NEVER included - the receipt proves participation only
+}
+
+#[event]
+pub struct WithdrawalScreenerConfigured {
+    pub pool: Pubkey,
+    pub screener_program: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardCurveConfigured {
+    pub pool: Pubkey,
+    /// Rate before the first point
+    pub base_rate_bps: u16,
+    pub reward_curve: Vec<RewardCurvePoint>,
+    /// Public input of reward proofs from now on
+    pub curve_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DepositCallerAllowed {
+    pub pool: Pubkey,
+    pub program: Pubkey,
+    pub depositor: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DepositCallerRevoked {
+    pub pool: Pubkey,
+    pub program: Pubkey,
+    pub deposit_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CpiDepositCredited {
+    pub pool: Pubkey,
+    pub caller_program: Pubkey,
+    pub note_index: u32,
+    pub timestamp: i64,
+    // Note: Amount is NEVER included - only the originating program
+}
+
+#[event]
+pub struct ReferralProgramCreated {
+    pub referral_program: Pubkey,
+    pub pool: Pubkey,
+    pub reward_per_deposit: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferrerRegistered {
+    pub referral: Pubkey,
+    pub pool: Pubkey,
+    pub referral_commitment: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralRewardsClaimed {
+    pub referral: Pubkey,
+    pub pool: Pubkey,
+    pub deposits: u64,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NoteExpiryConfigured {
+    pub pool: Pubkey,
+    pub note_expiry_seconds: i64,
+    pub treasury: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NoteSweepAnnounced {
+    pub pool: Pubkey,
+    pub from_index: u32,
+    pub through_index: u32,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NoteSweepCancelled {
+    pub pool: Pubkey,
+    pub through_index: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct NoteSweepExecuted {
+    pub pool: Pubkey,
+    pub from_index: u32,
+    pub through_index: u32,
+    pub amount: u64,
+    pub treasury: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuditEntryAppended {
+    pub audit_log: Pubkey,
+    pub subject: Pubkey,
+    pub sequence: u64,
+    /// `RecordKind` of the admin action
+    pub action: u16,
+    pub detail: [u8; 32],
+    pub value: u64,
+    pub previous_head: [u8; 32],
+    pub head: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WatchtowerConfigured {
+    pub watchtower: Pubkey,
+    pub subject: Pubkey,
+    pub owner: Pubkey,
+    pub alert_key: Pubkey,
+    pub large_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WatchtowerAlertRaised {
+    pub watchtower: Pubkey,
+    pub subject: Pubkey,
+    pub alert_key: Pubkey,
+    pub sequence: u64,
+    pub kind: WatchtowerAlertKind,
+    pub reference: [u8; 32],
+    pub value: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitRegistryCreated {
+    pub registry: Pubkey,
+    pub authority: Pubkey,
+    pub ptau_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitRegistered {
+    pub registry: Pubkey,
+    pub circuit: Circuit,
+    pub circuit_id: [u8; 32],
+    pub vk_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CrankTaskRegistered {
+    pub task: Pubkey,
+    pub kind: CrankTaskKind,
+    pub target: Pubkey,
+    pub tip_lamports: u64,
+    pub min_interval: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CrankTipPaid {
+    pub task: Pubkey,
+    pub cranker: Pubkey,
+    pub tip_lamports: u64,
+    pub run_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreeCheckpointed {
+    pub pool: Pubkey,
+    pub checkpoint: Pubkey,
+    pub pool_root: [u8; 32],
+    pub tree_root: [u8; 32],
+    pub subtree_digest: [u8; 32],
+    pub leaf_count: u32,
+    pub sequence: u32,
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+// ============================================
+// LEGACY STAKING EVENTS (Deprecated)
+// ============================================
+
+#[event]
+pub struct StakePoolCreated {
+    pub pool: Pubkey,
+    pub pool_id: [u8; 32],
+    pub creator: Pubkey,
+    pub min_stake_lamports: u64,
+    pub reward_rate_bps: u16,
+    pub lockup_epochs: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PrivateStakeCreated {
+    pub pool: Pubkey,
+    pub staker: Pubkey,
+    pub stake_commitment: [u8; 32],
+    pub validator_commitment: [u8; 32],
+    pub unlock_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PrivateUnstake {
+    pub pool: Pubkey,
+    pub staker: Pubkey,
+    pub nullifier_hash: [u8; 32], // Changed: now includes nullifier hash instead of nothing
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub pool: Pubkey,
+    pub staker: Pubkey,
+    pub reward_commitment: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub timestamp: i64,
+}
@@ -0,0 +1,111 @@
+//! Admin audit logs
+
+use crate::*;
+
+// ============================================
+// AUDIT LOG - Hash-Chained Admin Actions
+// ============================================
+//
+// A pool or wallet can keep an audit log: a rolling hash over every
+// administrative action (authority changes, pauses, parameter
+// updates, recovery and freezes) with a sequence number. Each action
+// emits an `AuditEntryAppended` event carrying the previous and new
+// head, so a monitor replaying the events notices any missing or
+// reordered entry, even when transaction logs were truncated.
+
+pub(crate) fn create_audit_log(ctx: Context<CreateAuditLog>) -> Result<()> {
+    let log = &mut ctx.accounts.audit_log;
+    let subject = &ctx.accounts.subject;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    {
+        let data = subject.try_borrow_data()?;
+        require!(
+            data.starts_with(ShieldedPool::DISCRIMINATOR) || data.starts_with(WalletAccount::DISCRIMINATOR),
+            ErrorCode::InvalidAuditSubject
+        );
+    }
+
+    log.subject = subject.key();
+    log.sequence = 0;
+    log.head = hash_domain(DOMAIN_AUDIT_LOG, &[subject.key.as_ref()]);
+    log.last_entry_at = 0;
+    log.created_at = current_time;
+    log.bump = ctx.bumps.audit_log;
+    log.version = AuditLog::VERSION;
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateAuditLog,
+        log.key(),
+        log.subject.to_bytes(),
+        [0u8; 32],
+        log.head,
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateAuditLog<'info> {
+    /// CHECK: Shielded pool or wallet account; its discriminator is checked
+    #[account(owner = crate::ID)]
+    pub subject: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = AuditLog::LEN,
+        seeds = [AUDIT_LOG_SEED, subject.key().as_ref()],
+        bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Chain an admin action onto the subject's audit log, if it keeps one
+pub(crate) fn append_audit_entry(
+    log: Option<&mut Account<AuditLog>>,
+    action: RecordKind,
+    detail: [u8; 32],
+    value: u64,
+    timestamp: i64,
+) {
+    let Some(log) = log else {
+        return;
+    };
+
+    let previous_head = log.head;
+    let sequence = log.sequence + 1;
+    log.head = hash_domain(
+        DOMAIN_AUDIT_LOG,
+        &[
+            &previous_head,
+            &sequence.to_le_bytes(),
+            &(action as u16).to_le_bytes(),
+            &detail,
+            &value.to_le_bytes(),
+            &timestamp.to_le_bytes(),
+        ],
+    );
+    log.sequence = sequence;
+    log.last_entry_at = timestamp;
+
+    emit!(AuditEntryAppended {
+        audit_log: log.key(),
+        subject: log.subject,
+        sequence,
+        action: action as u16,
+        detail,
+        value,
+        previous_head,
+        head: log.head,
+        timestamp,
+    });
+}
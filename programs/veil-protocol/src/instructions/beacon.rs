@@ -0,0 +1,354 @@
+//! Commit-reveal randomness beacon
+
+use crate::*;
+
+// ============================================
+// RANDOMNESS BEACON - Commit-Reveal
+// ============================================
+//
+// Same commit-reveal flow as private voting: participants bond lamports
+// and commit H(DOMAIN_BEACON_COMMIT, secret || participant), then reveal
+// the secret. Reveals are XORed into an order-independent accumulator and
+// the beacon is H(DOMAIN_BEACON, round || accumulator || reveal_count).
+// A participant who withholds their reveal (e.g. after seeing the others)
+// forfeits the bond to the round account, where it stays locked.
+
+pub(crate) fn create_beacon_round(
+    ctx: Context<CreateBeaconRound>,
+    round_id: [u8; 32],
+    commit_ends_at: i64,
+    reveal_ends_at: i64,
+    bond_lamports: u64,
+) -> Result<()> {
+    let round = &mut ctx.accounts.beacon_round;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(commit_ends_at > current_time, ErrorCode::InvalidVotingPeriod);
+    require!(reveal_ends_at > commit_ends_at, ErrorCode::InvalidRevealPeriod);
+    require!(bond_lamports > 0, ErrorCode::InvalidBeaconBond);
+
+    round.round_id = round_id;
+    round.creator = ctx.accounts.creator.key();
+    round.commit_ends_at = commit_ends_at;
+    round.reveal_ends_at = reveal_ends_at;
+    round.bond_lamports = bond_lamports;
+    round.commit_count = 0;
+    round.reveal_count = 0;
+    round.slashed_count = 0;
+    round.accumulator = [0u8; 32];
+    round.beacon = [0u8; 32];
+    round.created_at = current_time;
+    round.finalized_at = 0;
+    round.is_finalized = false;
+    round.bump = ctx.bumps.beacon_round;
+    round.version = BeaconRound::VERSION;
+
+    emit!(BeaconRoundCreated {
+        round: round.key(),
+        round_id,
+        commit_ends_at,
+        reveal_ends_at,
+        bond_lamports,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateBeaconRound,
+        round.key(),
+        round_id,
+        [0u8; 32],
+        [0u8; 32],
+        bond_lamports,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn commit_beacon_secret(
+    ctx: Context<CommitBeaconSecret>,
+    commitment: [u8; 32], // H(DOMAIN_BEACON_COMMIT, secret || participant)
+) -> Result<()> {
+    let round = &mut ctx.accounts.beacon_round;
+    let entry = &mut ctx.accounts.beacon_entry;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(current_time < round.commit_ends_at, ErrorCode::VotingEnded);
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.participant.to_account_info(),
+                to: entry.to_account_info(),
+            },
+        ),
+        round.bond_lamports,
+    )?;
+
+    entry.round = round.key();
+    entry.participant = ctx.accounts.participant.key();
+    entry.commitment = commitment;
+    entry.bond_lamports = round.bond_lamports;
+    entry.committed_at = current_time;
+    entry.has_revealed = false;
+    entry.is_slashed = false;
+    entry.bump = ctx.bumps.beacon_entry;
+    entry.version = BeaconEntry::VERSION;
+
+    round.commit_count += 1;
+
+    emit!(BeaconSecretCommitted {
+        round: round.key(),
+        participant: entry.participant,
+        commitment,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CommitBeaconSecret,
+        round.key(),
+        commitment,
+        entry.participant.to_bytes(),
+        [0u8; 32],
+        round.commit_count as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn reveal_beacon_secret(ctx: Context<RevealBeaconSecret>, secret: [u8; 32]) -> Result<()> {
+    let round = &mut ctx.accounts.beacon_round;
+    let entry = &mut ctx.accounts.beacon_entry;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(current_time >= round.commit_ends_at, ErrorCode::VotingNotEnded);
+    require!(current_time < round.reveal_ends_at, ErrorCode::RevealEnded);
+    require!(!entry.has_revealed, ErrorCode::AlreadyRevealed);
+
+    let expected = compute_beacon_commitment(&secret, &entry.participant);
+    require!(
+        constant_time_eq(&entry.commitment, &expected),
+        ErrorCode::InvalidBeaconReveal
+    );
+
+    for (acc, byte) in round.accumulator.iter_mut().zip(secret.iter()) {
+        *acc ^= byte;
+    }
+    round.reveal_count += 1;
+
+    entry.has_revealed = true;
+    let bond = entry.bond_lamports;
+    entry.bond_lamports = 0;
+    entry.sub_lamports(bond)?;
+    ctx.accounts.participant.add_lamports(bond)?;
+
+    emit!(BeaconSecretRevealed {
+        round: round.key(),
+        participant: entry.participant,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RevealBeaconSecret,
+        round.key(),
+        entry.participant.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        round.reveal_count as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn slash_beacon_non_reveal(ctx: Context<SlashBeaconNonReveal>) -> Result<()> {
+    let round = &mut ctx.accounts.beacon_round;
+    let entry = &mut ctx.accounts.beacon_entry;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(current_time >= round.reveal_ends_at, ErrorCode::RevealNotEnded);
+    require!(!entry.has_revealed, ErrorCode::AlreadyRevealed);
+    require!(!entry.is_slashed, ErrorCode::BeaconEntryAlreadySlashed);
+
+    let bond = entry.bond_lamports;
+    entry.bond_lamports = 0;
+    entry.is_slashed = true;
+    entry.sub_lamports(bond)?;
+    round.add_lamports(bond)?;
+
+    round.slashed_count += 1;
+
+    emit!(BeaconParticipantSlashed {
+        round: round.key(),
+        participant: entry.participant,
+        bond_lamports: bond,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::SlashBeaconNonReveal,
+        round.key(),
+        entry.participant.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        bond,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn finalize_beacon(ctx: Context<FinalizeBeacon>) -> Result<()> {
+    let round = &mut ctx.accounts.beacon_round;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(current_time >= round.reveal_ends_at, ErrorCode::RevealNotEnded);
+    require!(!round.is_finalized, ErrorCode::AlreadyFinalized);
+    require!(round.reveal_count > 0, ErrorCode::BeaconNoReveals);
+
+    round.beacon = compute_beacon_value(&round.key(), &round.accumulator, round.reveal_count);
+    round.is_finalized = true;
+    round.finalized_at = current_time;
+
+    debug_log!(
+        "finalize_beacon",
+        round = round.key(),
+        reveals = round.reveal_count,
+        commits = round.commit_count,
+        beacon = Hex(&round.beacon),
+    );
+
+    emit!(BeaconFinalized {
+        round: round.key(),
+        beacon: round.beacon,
+        reveal_count: round.reveal_count,
+        commit_count: round.commit_count,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::FinalizeBeacon,
+        round.key(),
+        round.beacon,
+        [0u8; 32],
+        [0u8; 32],
+        round.reveal_count as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn get_beacon(ctx: Context<GetBeacon>) -> Result<[u8; 32]> {
+    ctx.accounts.beacon_round.finalized_beacon()
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(round_id: [u8; 32])]
+pub struct CreateBeaconRound<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = BeaconRound::LEN,
+        seeds = [BEACON_ROUND_SEED, creator.key().as_ref(), &round_id],
+        bump
+    )]
+    pub beacon_round: Account<'info, BeaconRound>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CommitBeaconSecret<'info> {
+    #[account(
+        mut,
+        seeds = [BEACON_ROUND_SEED, beacon_round.creator.as_ref(), &beacon_round.round_id],
+        bump = beacon_round.bump
+    )]
+    pub beacon_round: Account<'info, BeaconRound>,
+
+    #[account(
+        init,
+        payer = participant,
+        space = BeaconEntry::LEN,
+        seeds = [BEACON_ENTRY_SEED, beacon_round.key().as_ref(), participant.key().as_ref()],
+        bump
+    )]
+    pub beacon_entry: Account<'info, BeaconEntry>,
+
+    #[account(mut)]
+    pub participant: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevealBeaconSecret<'info> {
+    #[account(
+        mut,
+        seeds = [BEACON_ROUND_SEED, beacon_round.creator.as_ref(), &beacon_round.round_id],
+        bump = beacon_round.bump
+    )]
+    pub beacon_round: Account<'info, BeaconRound>,
+
+    #[account(
+        mut,
+        seeds = [BEACON_ENTRY_SEED, beacon_round.key().as_ref(), beacon_entry.participant.as_ref()],
+        bump = beacon_entry.bump,
+        constraint = beacon_entry.participant == participant.key() @ ErrorCode::Unauthorized
+    )]
+    pub beacon_entry: Account<'info, BeaconEntry>,
+
+    #[account(mut)]
+    pub participant: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SlashBeaconNonReveal<'info> {
+    #[account(
+        mut,
+        seeds = [BEACON_ROUND_SEED, beacon_round.creator.as_ref(), &beacon_round.round_id],
+        bump = beacon_round.bump
+    )]
+    pub beacon_round: Account<'info, BeaconRound>,
+
+    #[account(
+        mut,
+        seeds = [BEACON_ENTRY_SEED, beacon_round.key().as_ref(), beacon_entry.participant.as_ref()],
+        bump = beacon_entry.bump
+    )]
+    pub beacon_entry: Account<'info, BeaconEntry>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinalizeBeacon<'info> {
+    #[account(
+        mut,
+        seeds = [BEACON_ROUND_SEED, beacon_round.creator.as_ref(), &beacon_round.round_id],
+        bump = beacon_round.bump
+    )]
+    pub beacon_round: Account<'info, BeaconRound>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct GetBeacon<'info> {
+    #[account(
+        seeds = [BEACON_ROUND_SEED, beacon_round.creator.as_ref(), &beacon_round.round_id],
+        bump = beacon_round.bump
+    )]
+    pub beacon_round: Account<'info, BeaconRound>,
+}
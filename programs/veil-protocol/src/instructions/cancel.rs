@@ -0,0 +1,215 @@
+//! Cancellation of deposits not yet in a tree checkpoint
+
+use crate::*;
+
+// ============================================
+// DEPOSIT CANCELLATION - Undo a Fresh Deposit
+// ============================================
+//
+// A depositor who sent the wrong amount, or to the wrong pool, can take a
+// note back within DEPOSIT_CANCEL_WINDOW of creating it, as long as the
+// pool's tree checkpoint has not yet published its leaf. The proof shows
+// knowledge of the note's opening: that the note commits to `lamports`
+// and that `nullifier` is the one the note would be spent with. Recording
+// that nullifier is what stops the note from also being withdrawn later;
+// the note account stays, with `is_spent` set, because `checkpoint_tree`
+// replays every note in index order. The proof commits to
+//   cancel_tag = H(DOMAIN_DEPOSIT_CANCEL, pool, note_index, recipient, lamports)
+// so the refund cannot be redirected by whoever submits it.
+
+pub(crate) fn cancel_deposit<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CancelDeposit<'info>>,
+    nullifier: [u8; 32],
+    lamports: u64,
+    cancel_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    require_proof_len(&cancel_proof)?;
+
+    let pool = &mut ctx.accounts.shielded_pool;
+    let note = &mut ctx.accounts.note_account;
+    let recipient = &ctx.accounts.recipient;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(pool.is_active, ErrorCode::PoolNotActive);
+    require!(!note.is_spent && lamports > 0, ErrorCode::InvalidDepositCancel);
+    require!(
+        current_time <= note.created_at.saturating_add(DEPOSIT_CANCEL_WINDOW),
+        ErrorCode::DepositCancelWindowClosed
+    );
+    require!(
+        note.note_index >= checkpointed_leaves(&ctx.accounts.tree_checkpoint)?,
+        ErrorCode::DepositAlreadyCheckpointed
+    );
+    require!(!is_nullifier_used(pool, &nullifier), ErrorCode::NullifierAlreadyUsed);
+
+    let public_signals = [
+        note.commitment,
+        nullifier,
+        compute_deposit_cancel_tag(&pool.key(), note.note_index, &recipient.key(), lamports),
+        compute_pool_binding(&pool.key()),
+    ];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::DepositCancel, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::DepositCancel, &public_signals, &cancel_proof),
+        ErrorCode::InvalidDepositCancelProof
+    );
+
+    screen_withdrawal(pool, ctx.accounts.screener_program.as_ref(), recipient, &nullifier, &[])?;
+
+    record_filtered_nullifiers(pool, ctx.accounts.nullifier_filter.as_ref(), &[nullifier])?;
+    spend_withdrawal_note(
+        pool,
+        &mut ctx.accounts.nullifier_account,
+        ctx.bumps.nullifier_account,
+        nullifier,
+        [0u8; 32], // A cancelled deposit leaves no change
+        current_time,
+    );
+    note.is_spent = true;
+
+    let pool_key = pool.key();
+    anchor_lang::system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.pool_vault.to_account_info(),
+                to: recipient.to_account_info(),
+            },
+            &[&[SHIELDED_VAULT_SEED, pool_key.as_ref(), &[ctx.bumps.pool_vault]]],
+        ),
+        lamports,
+    )?;
+
+    debug_log!(
+        "cancel_deposit",
+        pool = pool_key,
+        note_index = note.note_index,
+        nullifier = Hex(&nullifier),
+    );
+
+    emit_cpi!(DepositCancelled {
+        pool: pool_key,
+        note_index: note.note_index,
+        nullifier,
+        recipient: recipient.key(),
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CancelDeposit,
+        pool_key,
+        nullifier,
+        note.commitment,
+        pool.merkle_root,
+        lamports,
+        current_time,
+    ));
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+/// Number of leaves the pool's tree checkpoint has published; zero when
+/// the pool has no checkpoint account yet
+fn checkpointed_leaves(tree_checkpoint: &UncheckedAccount) -> Result<u32> {
+    if tree_checkpoint.data_is_empty() {
+        return Ok(0);
+    }
+    require_keys_eq!(*tree_checkpoint.owner, crate::ID, ErrorCode::InvalidTreeCheckpoint);
+
+    let data = tree_checkpoint.try_borrow_data()?;
+    let disc = TreeCheckpoint::DISCRIMINATOR.len();
+    let body = data
+        .get(disc..disc + std::mem::size_of::<TreeCheckpoint>())
+        .filter(|_| data.starts_with(TreeCheckpoint::DISCRIMINATOR))
+        .ok_or(ErrorCode::InvalidTreeCheckpoint)?;
+    Ok(bytemuck::from_bytes::<TreeCheckpoint>(body).leaf_count)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(nullifier: [u8; 32])]
+pub struct CancelDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    /// The note being cancelled; kept (marked spent) for checkpoint replay
+    #[account(
+        mut,
+        seeds = [NOTE_SEED, shielded_pool.key().as_ref(), &note_account.note_index.to_le_bytes()],
+        bump = note_account.bump
+    )]
+    pub note_account: Account<'info, ShieldedNote>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = NullifierRecord::LEN,
+        seeds = [NULLIFIER_SEED, shielded_pool.key().as_ref(), &nullifier],
+        bump
+    )]
+    pub nullifier_account: Account<'info, NullifierRecord>,
+
+    /// CHECK: The pool's tree checkpoint; may not exist yet, in which case
+    /// nothing has been published. Read in `checkpointed_leaves`
+    #[account(
+        seeds = [TREE_CHECKPOINT_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub tree_checkpoint: UncheckedAccount<'info>,
+
+    /// CHECK: Pool vault paying the refund
+    #[account(
+        mut,
+        seeds = [SHIELDED_VAULT_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_vault: AccountInfo<'info>,
+
+    /// CHECK: Refund recipient, bound by the cancel tag in the proof
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Any payer (e.g. a relayer) - need not be the recipient
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The pool's nullifier filter, required once the pool keeps one
+    #[account(
+        mut,
+        seeds = [NULLIFIER_FILTER_SEED, shielded_pool.key().as_ref()],
+        bump = nullifier_filter.load()?.bump
+    )]
+    pub nullifier_filter: Option<AccountLoader<'info, NullifierFilter>>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    /// CHECK: The pool's withdrawal screener, required once the pool has
+    /// one; matched against `screener_program` in `screen_withdrawal`
+    pub screener_program: Option<UncheckedAccount<'info>>,
+}
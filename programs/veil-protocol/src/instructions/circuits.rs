@@ -0,0 +1,124 @@
+//! Circuit registry
+
+use crate::*;
+
+// ============================================
+// CIRCUIT REGISTRY - Trusted Setup Artifacts
+// ============================================
+//
+// An auditor (or the team) records, per circuit, the hash of the
+// verifying key they audited, plus the hash of the final Powers-of-Tau
+// contribution the keys were derived from. The hash is computed off-chain
+// from the audited key and is not checked against this build when it is
+// registered. Entries are write-once. Every proof path checks the hash of
+// the key compiled into the program against the registry, so a program
+// upgrade that swaps in a different key than the audited one fails loudly.
+
+pub(crate) fn create_circuit_registry(
+    ctx: Context<CreateCircuitRegistry>,
+    ptau_hash: [u8; 32],
+) -> Result<()> {
+    let registry = &mut ctx.accounts.circuit_registry;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(ptau_hash != [0u8; 32], ErrorCode::InvalidCircuitRegistry);
+
+    registry.authority = ctx.accounts.authority.key();
+    registry.ptau_hash = ptau_hash;
+    registry.entries = Vec::new();
+    registry.created_at = current_time;
+    registry.bump = ctx.bumps.circuit_registry;
+    registry.version = CircuitRegistry::VERSION;
+
+    emit!(CircuitRegistryCreated {
+        registry: registry.key(),
+        authority: registry.authority,
+        ptau_hash,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateCircuitRegistry,
+        registry.key(),
+        ptau_hash,
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn register_circuit(
+    ctx: Context<RegisterCircuit>,
+    circuit: Circuit,
+    vk_hash: [u8; 32],
+) -> Result<()> {
+    let registry = &mut ctx.accounts.circuit_registry;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+    let circuit_id = circuit.id();
+
+    require!(
+        registry.entry(&circuit_id).is_none(),
+        ErrorCode::CircuitAlreadyRegistered
+    );
+    require!(vk_hash != [0u8; 32], ErrorCode::InvalidCircuitRegistry);
+
+    registry.entries.push(CircuitEntry {
+        circuit_id,
+        vk_hash,
+        registered_at: current_time,
+    });
+
+    emit!(CircuitRegistered {
+        registry: registry.key(),
+        circuit,
+        circuit_id,
+        vk_hash,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RegisterCircuit,
+        registry.key(),
+        circuit_id,
+        vk_hash,
+        [0u8; 32],
+        circuit as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateCircuitRegistry<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = CircuitRegistry::LEN,
+        seeds = [CIRCUIT_REGISTRY_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RegisterCircuit<'info> {
+    #[account(
+        mut,
+        seeds = [CIRCUIT_REGISTRY_SEED, authority.key().as_ref()],
+        bump = circuit_registry.bump
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+
+    pub authority: Signer<'info>,
+}
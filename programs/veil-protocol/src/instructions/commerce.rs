@@ -0,0 +1,1074 @@
+//! Shielded escrow, subscriptions and campaigns
+
+use crate::*;
+
+// ============================================
+// SHIELDED ESCROW - Private Commerce
+// ============================================
+//
+// The buyer spends one of their notes into a locked value commitment tied
+// to an order hash. Buyer, seller and arbiter are known only by
+// commitments. Settlement is 2-of-3: the seller stands on Release and the
+// buyer on Refund, so either the buyer releasing or the arbiter ruling
+// decides it. The decided party then receives the locked value as a new
+// note. No amount ever appears on-chain.
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn create_escrow(
+    ctx: Context<CreateEscrow>,
+    escrow_id: [u8; 32],
+    order_hash: [u8; 32],
+    nullifier: [u8; 32],             // Nullifier of the buyer's input note
+    locked_commitment: [u8; 32],     // Commitment to the locked value
+    party_commitments: [[u8; 32]; 3], // [buyer, seller, arbiter]
+    lock_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    let pool = &mut ctx.accounts.shielded_pool;
+    let escrow = &mut ctx.accounts.escrow;
+    let nullifier_account = &mut ctx.accounts.nullifier_account;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(pool.is_active, ErrorCode::PoolNotActive);
+    require!(order_hash != [0u8; 32], ErrorCode::InvalidEscrow);
+    require!(locked_commitment != [0u8; 32], ErrorCode::InvalidEscrow);
+    require!(
+        party_commitments.iter().all(|c| *c != [0u8; 32]),
+        ErrorCode::InvalidEscrow
+    );
+
+    let escrow_tag = compute_escrow_tag(&pool.key(), &escrow_id, &order_hash);
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::Escrow, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::Escrow, 
+            &[pool.spend_anchor(), nullifier, locked_commitment, escrow_tag],
+            &lock_proof,
+        ),
+        ErrorCode::InvalidEscrowProof
+    );
+
+    nullifier_account.pool = pool.key();
+    nullifier_account.nullifier = nullifier;
+    nullifier_account.spent_at = current_time;
+    nullifier_account.bump = ctx.bumps.nullifier_account;
+    nullifier_account.version = NullifierRecord::VERSION;
+    pool.nullifier_count += 1;
+    record_filtered_nullifiers(pool, ctx.accounts.nullifier_filter.as_ref(), &[nullifier])?;
+
+    let [buyer_commitment, seller_commitment, arbiter_commitment] = party_commitments;
+    escrow.pool = pool.key();
+    escrow.escrow_id = escrow_id;
+    escrow.order_hash = order_hash;
+    escrow.locked_commitment = locked_commitment;
+    escrow.buyer_commitment = buyer_commitment;
+    escrow.seller_commitment = seller_commitment;
+    escrow.arbiter_commitment = arbiter_commitment;
+    escrow.buyer_vote = EscrowOutcome::Refund;
+    escrow.seller_vote = EscrowOutcome::Release;
+    escrow.arbiter_vote = EscrowOutcome::Pending;
+    escrow.resolution = EscrowOutcome::Pending;
+    escrow.created_at = current_time;
+    escrow.settled_at = 0;
+    escrow.is_settled = false;
+    escrow.bump = ctx.bumps.escrow;
+    escrow.version = Escrow::VERSION;
+
+    emit_cpi!(EscrowCreated {
+        escrow: escrow.key(),
+        pool: pool.key(),
+        order_hash,
+        nullifier,
+        timestamp: current_time,
+        // Amount and parties are NEVER included - true privacy!
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateEscrow,
+        escrow.key(),
+        nullifier,
+        order_hash,
+        pool.merkle_root,
+        0,
+        current_time,
+    ));
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+pub(crate) fn vote_escrow(
+    ctx: Context<VoteEscrow>,
+    role: EscrowRole,
+    outcome: EscrowOutcome,
+    party_proof: Vec<u8>,            // Proves knowledge of the role's commitment preimage
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(escrow.resolution == EscrowOutcome::Pending, ErrorCode::EscrowAlreadyResolved);
+    require!(outcome != EscrowOutcome::Pending, ErrorCode::InvalidEscrow);
+
+    let party_commitment = match role {
+        EscrowRole::Buyer => escrow.buyer_commitment,
+        EscrowRole::Seller => escrow.seller_commitment,
+        EscrowRole::Arbiter => escrow.arbiter_commitment,
+    };
+    let escrow_tag = compute_escrow_tag(&escrow.pool, &escrow.escrow_id, &escrow.order_hash);
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::Escrow, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::Escrow, 
+            &[party_commitment, escrow_tag, encode_u64_signal(outcome as u64)],
+            &party_proof,
+        ),
+        ErrorCode::InvalidEscrowProof
+    );
+
+    match role {
+        EscrowRole::Buyer => escrow.buyer_vote = outcome,
+        EscrowRole::Seller => escrow.seller_vote = outcome,
+        EscrowRole::Arbiter => escrow.arbiter_vote = outcome,
+    }
+    escrow.resolution = escrow.tally();
+
+    emit!(EscrowVoted {
+        escrow: escrow.key(),
+        role,
+        outcome,
+        resolution: escrow.resolution,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::VoteEscrow,
+        escrow.key(),
+        party_commitment,
+        [0u8; 32],
+        [0u8; 32],
+        outcome as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn settle_escrow(
+    ctx: Context<SettleEscrow>,
+    output_commitment: [u8; 32],
+    encrypted_note: [u8; 64],
+    settlement_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    let pool = &mut ctx.accounts.shielded_pool;
+    let escrow = &mut ctx.accounts.escrow;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(pool.is_active, ErrorCode::PoolNotActive);
+    require!(pool.next_note_index < MAX_SHIELDED_NOTES as u32, ErrorCode::PoolFull);
+    require!(!escrow.is_settled, ErrorCode::EscrowAlreadySettled);
+    require!(output_commitment != [0u8; 32], ErrorCode::InvalidEscrow);
+
+    let recipient_commitment = match escrow.resolution {
+        EscrowOutcome::Release => escrow.seller_commitment,
+        EscrowOutcome::Refund => escrow.buyer_commitment,
+        EscrowOutcome::Pending => return err!(ErrorCode::EscrowNotResolved),
+    };
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::Escrow, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::Escrow, 
+            &[escrow.locked_commitment, recipient_commitment, output_commitment],
+            &settlement_proof,
+        ),
+        ErrorCode::InvalidEscrowProof
+    );
+
+    append_pool_note(
+        pool,
+        &mut ctx.accounts.note_account,
+        output_commitment,
+        encrypted_note,
+        ctx.bumps.note_account,
+        current_time,
+    );
+
+    escrow.is_settled = true;
+    escrow.settled_at = current_time;
+
+    emit_cpi!(EscrowSettled {
+        escrow: escrow.key(),
+        resolution: escrow.resolution,
+        output_commitment,
+        merkle_root: pool.merkle_root,
+        timestamp: current_time,
+        // Amount and recipient are NEVER included - true privacy!
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::SettleEscrow,
+        escrow.key(),
+        output_commitment,
+        [0u8; 32],
+        pool.merkle_root,
+        pool.next_note_index as u64,
+        current_time,
+    ));
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+// ============================================
+// SHIELDED SUBSCRIPTIONS - Recurring Payments
+// ============================================
+//
+// A payer authorizes a merchant to pull a fixed hidden amount (committed
+// in `amount_commitment`) once per period. Each collection spends one
+// payer note and re-creates it as a change note for the payer plus a
+// payment note for the merchant, proven against the subscription terms.
+// Periods are collected at most once, and the payer can cancel anytime.
+
+pub(crate) fn create_subscription(
+    ctx: Context<CreateSubscription>,
+    subscription_id: [u8; 32],
+    payer_commitment: [u8; 32],      // H(payer_secret) - owner of the spent notes
+    merchant_commitment: [u8; 32],   // H(merchant_secret) - owner of payment notes
+    amount_commitment: [u8; 32],     // H(amount || blinding) - fixed per-period amount
+    period_seconds: i64,
+    start_at: i64,
+) -> Result<()> {
+    let subscription = &mut ctx.accounts.subscription;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(period_seconds > 0, ErrorCode::InvalidSubscription);
+    require!(start_at >= current_time, ErrorCode::InvalidSubscription);
+    require!(
+        payer_commitment != [0u8; 32]
+            && merchant_commitment != [0u8; 32]
+            && amount_commitment != [0u8; 32],
+        ErrorCode::InvalidSubscription
+    );
+
+    subscription.pool = ctx.accounts.shielded_pool.key();
+    subscription.subscription_id = subscription_id;
+    subscription.authority = ctx.accounts.authority.key();
+    subscription.payer_commitment = payer_commitment;
+    subscription.merchant_commitment = merchant_commitment;
+    subscription.amount_commitment = amount_commitment;
+    subscription.period_seconds = period_seconds;
+    subscription.start_at = start_at;
+    subscription.next_period = 0;
+    subscription.collected_count = 0;
+    subscription.created_at = current_time;
+    subscription.bump = ctx.bumps.subscription;
+    subscription.version = Subscription::VERSION;
+
+    emit!(SubscriptionCreated {
+        subscription: subscription.key(),
+        pool: subscription.pool,
+        period_seconds,
+        start_at,
+        timestamp: current_time,
+        // Amount, payer and merchant are NEVER included - true privacy!
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateSubscription,
+        subscription.key(),
+        subscription_id,
+        [0u8; 32],
+        [0u8; 32],
+        period_seconds as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn collect_subscription(
+    ctx: Context<CollectSubscription>,
+    period: u64,
+    nullifier: [u8; 32],               // Nullifier of the payer note spent this period
+    change_commitment: [u8; 32],       // Payer's re-created note
+    payment_commitment: [u8; 32],      // Merchant's payment note
+    encrypted_notes: [[u8; 64]; 2],    // [change, payment]
+    collection_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    let pool = &mut ctx.accounts.shielded_pool;
+    let subscription = &mut ctx.accounts.subscription;
+    let nullifier_account = &mut ctx.accounts.nullifier_account;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(pool.is_active, ErrorCode::PoolNotActive);
+    require!(pool.next_note_index + 1 < MAX_SHIELDED_NOTES as u32, ErrorCode::PoolFull);
+
+    // Each period can be collected once, and only once it has started
+    require!(period >= subscription.next_period, ErrorCode::SubscriptionPeriodCollected);
+    require!(
+        subscription.period_started(period, current_time),
+        ErrorCode::SubscriptionPeriodNotStarted
+    );
+
+    let public_signals = [
+        pool.merkle_root,
+        nullifier,
+        change_commitment,
+        payment_commitment,
+        subscription.amount_commitment,
+        subscription.payer_commitment,
+        subscription.merchant_commitment,
+        compute_subscription_tag(&subscription.key(), period),
+    ];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::Subscription, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::Subscription, &public_signals, &collection_proof),
+        ErrorCode::InvalidSubscriptionProof
+    );
+
+    nullifier_account.pool = pool.key();
+    nullifier_account.nullifier = nullifier;
+    nullifier_account.spent_at = current_time;
+    nullifier_account.bump = ctx.bumps.nullifier_account;
+    nullifier_account.version = NullifierRecord::VERSION;
+    pool.nullifier_count += 1;
+    record_filtered_nullifiers(pool, ctx.accounts.nullifier_filter.as_ref(), &[nullifier])?;
+
+    let [change_note, payment_note] = encrypted_notes;
+    append_pool_note(
+        pool,
+        &mut ctx.accounts.change_note_account,
+        change_commitment,
+        change_note,
+        ctx.bumps.change_note_account,
+        current_time,
+    );
+    append_pool_note(
+        pool,
+        &mut ctx.accounts.payment_note_account,
+        payment_commitment,
+        payment_note,
+        ctx.bumps.payment_note_account,
+        current_time,
+    );
+
+    subscription.next_period = period + 1;
+    subscription.collected_count += 1;
+
+    emit_cpi!(SubscriptionCollected {
+        subscription: subscription.key(),
+        period,
+        nullifier,
+        payment_commitment,
+        merkle_root: pool.merkle_root,
+        timestamp: current_time,
+        // Amount is NEVER included - true privacy!
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CollectSubscription,
+        subscription.key(),
+        nullifier,
+        payment_commitment,
+        pool.merkle_root,
+        period,
+        current_time,
+    ));
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+pub(crate) fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+    let subscription = &ctx.accounts.subscription;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    emit!(SubscriptionCancelled {
+        subscription: subscription.key(),
+        collected_count: subscription.collected_count,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CancelSubscription,
+        subscription.key(),
+        [0u8; 32],
+        [0u8; 32],
+        [0u8; 32],
+        subscription.collected_count as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+// ============================================
+// SHIELDED CAMPAIGNS - Private Fundraising
+// ============================================
+//
+// Donations are ordinary pool notes whose owner (proven in-circuit) is the
+// campaign tag H(DOMAIN_CAMPAIGN_TAG, pool || campaign_id). The campaign
+// only accumulates the note commitments; after the deadline the
+// beneficiary proves the aggregate opens to a single output note.
+// Individual donation sizes are never revealed.
+//
+// The campaign tag is public, so the donor still knows everything needed
+// to spend a donated note. Each donation therefore proves the note's
+// nullifier and spends it on the spot: the value stays in the pool but is
+// only ever claimed once, through the aggregate.
+
+pub(crate) fn create_campaign(
+    ctx: Context<CreateCampaign>,
+    campaign_id: [u8; 32],
+    beneficiary_commitment: [u8; 32], // H(beneficiary_secret)
+    deadline: i64,
+) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(ctx.accounts.shielded_pool.is_active, ErrorCode::PoolNotActive);
+    require!(deadline > current_time, ErrorCode::InvalidCampaignDeadline);
+    require!(beneficiary_commitment != [0u8; 32], ErrorCode::InvalidCampaignCommitment);
+
+    campaign.pool = ctx.accounts.shielded_pool.key();
+    campaign.campaign_id = campaign_id;
+    campaign.creator = ctx.accounts.creator.key();
+    campaign.beneficiary_commitment = beneficiary_commitment;
+    campaign.aggregate_commitment = [0u8; 32];
+    campaign.donation_count = 0;
+    campaign.deadline = deadline;
+    campaign.created_at = current_time;
+    campaign.withdrawn_at = 0;
+    campaign.is_withdrawn = false;
+    campaign.bump = ctx.bumps.campaign;
+    campaign.version = Campaign::VERSION;
+
+    emit!(CampaignCreated {
+        campaign: campaign.key(),
+        pool: campaign.pool,
+        campaign_id,
+        deadline,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateCampaign,
+        campaign.key(),
+        campaign_id,
+        [0u8; 32],
+        [0u8; 32],
+        deadline as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn donate_to_campaign(
+    ctx: Context<DonateToCampaign>,
+    note_commitment: [u8; 32],
+    nullifier: [u8; 32],      // Nullifier of the donated note
+    encrypted_note: [u8; 64], // Encrypted to the beneficiary
+    donation_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    let pool = &mut ctx.accounts.shielded_pool;
+    let campaign = &mut ctx.accounts.campaign;
+    let note_account = &mut ctx.accounts.note_account;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(pool.is_active, ErrorCode::PoolNotActive);
+    require!(pool.next_note_index < MAX_SHIELDED_NOTES as u32, ErrorCode::PoolFull);
+    require!(current_time < campaign.deadline, ErrorCode::CampaignEnded);
+    require!(note_commitment != [0u8; 32], ErrorCode::InvalidCampaignCommitment);
+
+    let campaign_tag = compute_campaign_tag(&campaign.pool, &campaign.campaign_id);
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::Campaign, &circuit_id)?;
+    let proof_valid = verify_groth16_stub(
+        Circuit::Campaign,
+        &[note_commitment, campaign_tag, nullifier],
+        &donation_proof,
+    );
+    require!(proof_valid, ErrorCode::InvalidCampaignProof);
+
+    append_pool_note(
+        pool,
+        note_account,
+        note_commitment,
+        encrypted_note,
+        ctx.bumps.note_account,
+        current_time,
+    );
+    pool.record_deposit(current_time);
+    record_filtered_nullifiers(pool, ctx.accounts.nullifier_filter.as_ref(), &[nullifier])?;
+    record_donation(
+        pool,
+        campaign,
+        &mut ctx.accounts.nullifier_account,
+        ctx.bumps.nullifier_account,
+        note_commitment,
+        nullifier,
+        current_time,
+    );
+
+    emit_cpi!(CampaignDonation {
+        campaign: campaign.key(),
+        note_commitment,
+        note_index: note_account.note_index,
+        aggregate_commitment: campaign.aggregate_commitment,
+        merkle_root: pool.merkle_root,
+        timestamp: current_time,
+        // Donation amount is NEVER included - true privacy!
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::DonateToCampaign,
+        campaign.key(),
+        note_commitment,
+        campaign.aggregate_commitment,
+        pool.merkle_root,
+        note_account.note_index as u64,
+        current_time,
+    ));
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+/// Spend a donated note's nullifier and fold its commitment into the
+/// campaign aggregate
+pub(crate) fn record_donation(
+    pool: &mut ShieldedPool,
+    campaign: &mut Campaign,
+    nullifier_account: &mut NullifierRecord,
+    bump: u8,
+    note_commitment: [u8; 32],
+    nullifier: [u8; 32],
+    now: i64,
+) {
+    nullifier_account.pool = campaign.pool;
+    nullifier_account.nullifier = nullifier;
+    nullifier_account.spent_at = now;
+    nullifier_account.bump = bump;
+    nullifier_account.version = NullifierRecord::VERSION;
+    pool.nullifier_count += 1;
+
+    campaign.aggregate_commitment = hash_domain(
+        DOMAIN_CAMPAIGN_AGGREGATE,
+        &[&campaign.aggregate_commitment, &note_commitment],
+    );
+    campaign.donation_count += 1;
+}
+
+pub(crate) fn withdraw_campaign(
+    ctx: Context<WithdrawCampaign>,
+    output_commitment: [u8; 32],
+    encrypted_note: [u8; 64],
+    withdrawal_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    require_proof_len(&withdrawal_proof)?;
+
+    let pool = &mut ctx.accounts.shielded_pool;
+    let campaign = &mut ctx.accounts.campaign;
+    let note_account = &mut ctx.accounts.note_account;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(pool.is_active, ErrorCode::PoolNotActive);
+    require!(pool.next_note_index < MAX_SHIELDED_NOTES as u32, ErrorCode::PoolFull);
+    require!(current_time >= campaign.deadline, ErrorCode::CampaignStillActive);
+    require!(!campaign.is_withdrawn, ErrorCode::CampaignAlreadyWithdrawn);
+    require!(campaign.donation_count > 0, ErrorCode::CampaignEmpty);
+    require!(output_commitment != [0u8; 32], ErrorCode::InvalidCampaignCommitment);
+
+    let campaign_tag = compute_campaign_tag(&campaign.pool, &campaign.campaign_id);
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::Campaign, &circuit_id)?;
+    let proof_valid = verify_groth16_stub(Circuit::Campaign, 
+        &[
+            campaign_tag,
+            campaign.aggregate_commitment,
+            campaign.beneficiary_commitment,
+            output_commitment,
+        ],
+        &withdrawal_proof,
+    );
+    require!(proof_valid, ErrorCode::InvalidCampaignProof);
+
+    append_pool_note(
+        pool,
+        note_account,
+        output_commitment,
+        encrypted_note,
+        ctx.bumps.note_account,
+        current_time,
+    );
+
+    campaign.is_withdrawn = true;
+    campaign.withdrawn_at = current_time;
+
+    emit_cpi!(CampaignWithdrawn {
+        campaign: campaign.key(),
+        output_commitment,
+        donation_count: campaign.donation_count,
+        merkle_root: pool.merkle_root,
+        timestamp: current_time,
+        // Total raised is NEVER included - true privacy!
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::WithdrawCampaign,
+        campaign.key(),
+        output_commitment,
+        [0u8; 32],
+        pool.merkle_root,
+        pool.next_note_index as u64,
+        current_time,
+    ));
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(escrow_id: [u8; 32], order_hash: [u8; 32], nullifier: [u8; 32])]
+pub struct CreateEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = Escrow::LEN,
+        seeds = [ESCROW_SEED, shielded_pool.key().as_ref(), &escrow_id],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = NullifierRecord::LEN,
+        seeds = [NULLIFIER_SEED, shielded_pool.key().as_ref(), &nullifier],
+        bump
+    )]
+    pub nullifier_account: Account<'info, NullifierRecord>,
+
+    /// Fee payer only; the buyer is identified by `buyer_commitment`
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The pool's nullifier filter, required once the pool keeps one
+    #[account(
+        mut,
+        seeds = [NULLIFIER_FILTER_SEED, shielded_pool.key().as_ref()],
+        bump = nullifier_filter.load()?.bump
+    )]
+    pub nullifier_filter: Option<AccountLoader<'info, NullifierFilter>>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct VoteEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, escrow.pool.as_ref(), &escrow.escrow_id],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// Fee payer only; the party is proven by `party_proof`
+    pub payer: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, shielded_pool.key().as_ref(), &escrow.escrow_id],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ShieldedNote::LEN,
+        seeds = [NOTE_SEED, shielded_pool.key().as_ref(), &shielded_pool.next_note_index.to_le_bytes()],
+        bump
+    )]
+    pub note_account: Account<'info, ShieldedNote>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(subscription_id: [u8; 32])]
+pub struct CreateSubscription<'info> {
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Subscription::LEN,
+        seeds = [SUBSCRIPTION_SEED, shielded_pool.key().as_ref(), &subscription_id],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(period: u64, nullifier: [u8; 32])]
+pub struct CollectSubscription<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        mut,
+        seeds = [SUBSCRIPTION_SEED, shielded_pool.key().as_ref(), &subscription.subscription_id],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(
+        init,
+        payer = collector,
+        space = NullifierRecord::LEN,
+        seeds = [NULLIFIER_SEED, shielded_pool.key().as_ref(), &nullifier],
+        bump
+    )]
+    pub nullifier_account: Account<'info, NullifierRecord>,
+
+    #[account(
+        init,
+        payer = collector,
+        space = ShieldedNote::LEN,
+        seeds = [NOTE_SEED, shielded_pool.key().as_ref(), &shielded_pool.next_note_index.to_le_bytes()],
+        bump
+    )]
+    pub change_note_account: Account<'info, ShieldedNote>,
+
+    #[account(
+        init,
+        payer = collector,
+        space = ShieldedNote::LEN,
+        seeds = [NOTE_SEED, shielded_pool.key().as_ref(), &(shielded_pool.next_note_index + 1).to_le_bytes()],
+        bump
+    )]
+    pub payment_note_account: Account<'info, ShieldedNote>,
+
+    #[account(mut)]
+    pub collector: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The pool's nullifier filter, required once the pool keeps one
+    #[account(
+        mut,
+        seeds = [NULLIFIER_FILTER_SEED, shielded_pool.key().as_ref()],
+        bump = nullifier_filter.load()?.bump
+    )]
+    pub nullifier_filter: Option<AccountLoader<'info, NullifierFilter>>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelSubscription<'info> {
+    #[account(
+        mut,
+        seeds = [SUBSCRIPTION_SEED, subscription.pool.as_ref(), &subscription.subscription_id],
+        bump = subscription.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+        close = authority
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(campaign_id: [u8; 32])]
+pub struct CreateCampaign<'info> {
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = Campaign::LEN,
+        seeds = [CAMPAIGN_SEED, shielded_pool.key().as_ref(), &campaign_id],
+        bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(note_commitment: [u8; 32], nullifier: [u8; 32])]
+pub struct DonateToCampaign<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, shielded_pool.key().as_ref(), &campaign.campaign_id],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    #[account(
+        init,
+        payer = donor,
+        space = ShieldedNote::LEN,
+        seeds = [NOTE_SEED, shielded_pool.key().as_ref(), &shielded_pool.next_note_index.to_le_bytes()],
+        bump
+    )]
+    pub note_account: Account<'info, ShieldedNote>,
+
+    /// Spent with the donation, so the donated note cannot be spent again
+    #[account(
+        init,
+        payer = donor,
+        space = NullifierRecord::LEN,
+        seeds = [NULLIFIER_SEED, shielded_pool.key().as_ref(), &nullifier],
+        bump
+    )]
+    pub nullifier_account: Account<'info, NullifierRecord>,
+
+    #[account(mut)]
+    pub donor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The pool's nullifier filter, required once the pool keeps one
+    #[account(
+        mut,
+        seeds = [NULLIFIER_FILTER_SEED, shielded_pool.key().as_ref()],
+        bump = nullifier_filter.load()?.bump
+    )]
+    pub nullifier_filter: Option<AccountLoader<'info, NullifierFilter>>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawCampaign<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, shielded_pool.key().as_ref(), &campaign.campaign_id],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ShieldedNote::LEN,
+        seeds = [NOTE_SEED, shielded_pool.key().as_ref(), &shielded_pool.next_note_index.to_le_bytes()],
+        bump
+    )]
+    pub note_account: Account<'info, ShieldedNote>,
+
+    /// Anyone may submit (e.g. a relayer); the proof binds the beneficiary
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::test_support::*;
+
+    #[test]
+    fn donated_notes_cannot_be_spent_after_the_campaign_withdraws() {
+        let mut pool: ShieldedPool = zeroed(ShieldedPool::LEN);
+        let mut campaign: Campaign = zeroed(Campaign::LEN);
+        campaign.pool = Pubkey::new_unique();
+        let mut nullifier_account: NullifierRecord = zeroed(NullifierRecord::LEN);
+        let nullifier = [9u8; 32];
+
+        record_donation(
+            &mut pool,
+            &mut campaign,
+            &mut nullifier_account,
+            255,
+            [3u8; 32],
+            nullifier,
+            1_700_000_000,
+        );
+        assert_eq!(campaign.donation_count, 1);
+        assert_eq!(pool.nullifier_count, 1);
+        assert_ne!(campaign.aggregate_commitment, [0u8; 32]);
+
+        // The aggregate is claimed by `withdraw_campaign`; the donor's
+        // direct spend of the same note finds its nullifier already spent
+        assert_error(
+            require_unspent(&nullifier_account, &pool, &nullifier),
+            ErrorCode::NullifierAlreadyUsed,
+        );
+        assert!(require_unspent(&zeroed(NullifierRecord::LEN), &pool, &[8u8; 32]).is_ok());
+    }
+}
@@ -0,0 +1,200 @@
+//! Crank task registry
+
+use crate::*;
+
+// ============================================
+// CRANK REGISTRY - Permissionless Maintenance
+// ============================================
+//
+// A crank task is a sponsor-funded tip jar for one kind of maintenance on
+// one target account. Maintenance instructions take an optional task and
+// pay its tip to whoever ran them, at most once per `min_interval`.
+
+pub(crate) fn register_crank_task(
+    ctx: Context<RegisterCrankTask>,
+    kind: CrankTaskKind,
+    target: Pubkey,
+    tip_lamports: u64,
+    min_interval: i64,
+    funding_lamports: u64,
+) -> Result<()> {
+    let task = &mut ctx.accounts.crank_task;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(tip_lamports > 0, ErrorCode::InvalidCrankTask);
+    require!(min_interval >= 0, ErrorCode::InvalidCrankTask);
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: task.to_account_info(),
+            },
+        ),
+        funding_lamports,
+    )?;
+
+    task.funder = ctx.accounts.funder.key();
+    task.target = target;
+    task.kind = kind;
+    task.tip_lamports = tip_lamports;
+    task.min_interval = min_interval;
+    task.last_run_at = 0;
+    task.run_count = 0;
+    task.created_at = current_time;
+    task.bump = ctx.bumps.crank_task;
+    task.version = CrankTask::VERSION;
+
+    emit!(CrankTaskRegistered {
+        task: task.key(),
+        kind,
+        target,
+        tip_lamports,
+        min_interval,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RegisterCrankTask,
+        task.key(),
+        target.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        tip_lamports,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn fund_crank_task(ctx: Context<FundCrankTask>, amount: u64) -> Result<()> {
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.sponsor.to_account_info(),
+                to: ctx.accounts.crank_task.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::FundCrankTask,
+        ctx.accounts.crank_task.key(),
+        ctx.accounts.crank_task.target.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        amount,
+        clock::now(ctx.remaining_accounts)?.unix_timestamp,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn close_crank_task(ctx: Context<CloseCrankTask>) -> Result<()> {
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CloseCrankTask,
+        ctx.accounts.crank_task.key(),
+        ctx.accounts.crank_task.target.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        clock::now(ctx.remaining_accounts)?.unix_timestamp,
+    ));
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(kind: CrankTaskKind, target: Pubkey)]
+pub struct RegisterCrankTask<'info> {
+    #[account(
+        init,
+        payer = funder,
+        space = CrankTask::LEN,
+        seeds = [CRANK_TASK_SEED, target.as_ref(), &[kind as u8], funder.key().as_ref()],
+        bump
+    )]
+    pub crank_task: Account<'info, CrankTask>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FundCrankTask<'info> {
+    #[account(
+        mut,
+        seeds = [CRANK_TASK_SEED, crank_task.target.as_ref(), &[crank_task.kind as u8], crank_task.funder.as_ref()],
+        bump = crank_task.bump
+    )]
+    pub crank_task: Account<'info, CrankTask>,
+
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseCrankTask<'info> {
+    #[account(
+        mut,
+        seeds = [CRANK_TASK_SEED, crank_task.target.as_ref(), &[crank_task.kind as u8], funder.key().as_ref()],
+        bump = crank_task.bump,
+        has_one = funder @ ErrorCode::Unauthorized,
+        close = funder
+    )]
+    pub crank_task: Account<'info, CrankTask>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+}
+
+/// Pay a crank task's tip to the cranker after a successful maintenance run
+pub(crate) fn pay_crank_tip(
+    task: &mut Account<CrankTask>,
+    cranker: &AccountInfo,
+    kind: CrankTaskKind,
+    target: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    require!(
+        task.kind == kind && task.target == *target,
+        ErrorCode::CrankTaskMismatch
+    );
+    require!(
+        task.run_count == 0 || now >= task.last_run_at.saturating_add(task.min_interval),
+        ErrorCode::CrankTooEarly
+    );
+
+    let task_info = task.to_account_info();
+    let rent_floor = Rent::get()?.minimum_balance(task_info.data_len());
+    require!(
+        task_info.lamports() >= rent_floor.saturating_add(task.tip_lamports),
+        ErrorCode::CrankTaskUnderfunded
+    );
+
+    task_info.sub_lamports(task.tip_lamports)?;
+    cranker.add_lamports(task.tip_lamports)?;
+
+    task.last_run_at = now;
+    task.run_count += 1;
+
+    emit!(CrankTipPaid {
+        task: task.key(),
+        cranker: cranker.key(),
+        tip_lamports: task.tip_lamports,
+        run_count: task.run_count,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,241 @@
+//! Early exits from locked notes, with the penalty kept for stakers
+
+use crate::*;
+
+// ============================================
+// EARLY EXITS - Leave Before the Lockup Ends
+// ============================================
+//
+// A withdrawal proof only accepts notes past their `unlock_at`. A pool
+// can instead let holders leave early at a cost: the early-exit circuit
+// drops the lockup check and commits to
+//   early_exit_tag = H(DOMAIN_EARLY_EXIT, pool, recipient, gross_lamports, penalty_bps)
+// so the payout and the penalty rate cannot be changed by whoever submits
+// it, nor the rate raised by the authority once the proof is made. The
+// recipient gets `gross_lamports` less `early_exit_penalty_bps` of it; the
+// penalty never leaves the vault and is counted in the pool's reward
+// reserve, backing the reward notes of the stakers who stay.
+
+pub(crate) fn configure_early_exit(ctx: Context<ConfigureEarlyExit>, penalty_bps: u16) -> Result<()> {
+    let pool = &mut ctx.accounts.shielded_pool;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(penalty_bps <= MAX_EARLY_EXIT_PENALTY_BPS, ErrorCode::InvalidEarlyExitPenalty);
+    pool.early_exit_penalty_bps = penalty_bps;
+
+    append_audit_entry(
+        ctx.accounts.audit_log.as_mut(),
+        RecordKind::ConfigureEarlyExit,
+        [0u8; 32],
+        penalty_bps as u64,
+        current_time,
+    );
+
+    emit!(EarlyExitConfigured {
+        pool: pool.key(),
+        penalty_bps,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ConfigureEarlyExit,
+        pool.key(),
+        [0u8; 32],
+        [0u8; 32],
+        pool.merkle_root,
+        penalty_bps as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn shield_withdraw_early<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ShieldWithdrawEarly<'info>>,
+    nullifier: [u8; 32],
+    merkle_proof: [[u8; 32]; 8],
+    merkle_path_indices: u8,
+    withdrawal_proof: Vec<u8>,
+    output_commitment: [u8; 32],
+    gross_lamports: u64,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    require_proof_len(&withdrawal_proof)?;
+
+    let pool = &mut ctx.accounts.shielded_pool;
+    let recipient = &ctx.accounts.recipient;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(pool.is_active, ErrorCode::PoolNotActive);
+    require!(pool.early_exit_penalty_bps > 0, ErrorCode::EarlyExitDisabled);
+    let penalty_lamports = pool.early_exit_penalty(gross_lamports);
+    require!(gross_lamports > penalty_lamports, ErrorCode::InvalidEarlyExit);
+    require!(!is_nullifier_used(pool, &nullifier), ErrorCode::NullifierAlreadyUsed);
+    require!(
+        verify_merkle_proof(&pool.merkle_root, &merkle_proof, merkle_path_indices, &nullifier),
+        ErrorCode::InvalidMerkleProof
+    );
+
+    let public_signals = [
+        nullifier,
+        output_commitment,
+        pool.spend_anchor(),
+        compute_early_exit_tag(&pool.key(), &recipient.key(), gross_lamports, pool.early_exit_penalty_bps),
+        compute_pool_binding(&pool.key()),
+    ];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::EarlyExit, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::EarlyExit, &public_signals, &withdrawal_proof),
+        ErrorCode::InvalidEarlyExitProof
+    );
+
+    screen_withdrawal(pool, ctx.accounts.screener_program.as_ref(), recipient, &nullifier, &[])?;
+
+    record_filtered_nullifiers(pool, ctx.accounts.nullifier_filter.as_ref(), &[nullifier])?;
+    spend_withdrawal_note(
+        pool,
+        &mut ctx.accounts.nullifier_account,
+        ctx.bumps.nullifier_account,
+        nullifier,
+        output_commitment,
+        current_time,
+    );
+    pool.reward_reserve_lamports = pool.reward_reserve_lamports.saturating_add(penalty_lamports);
+
+    let pool_key = pool.key();
+    anchor_lang::system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.pool_vault.to_account_info(),
+                to: recipient.to_account_info(),
+            },
+            &[&[SHIELDED_VAULT_SEED, pool_key.as_ref(), &[ctx.bumps.pool_vault]]],
+        ),
+        gross_lamports - penalty_lamports,
+    )?;
+
+    debug_log!(
+        "shield_withdraw_early",
+        pool = pool_key,
+        nullifier = Hex(&nullifier),
+        penalty_lamports = penalty_lamports,
+    );
+
+    emit_cpi!(ShieldedEarlyExit {
+        pool: pool_key,
+        nullifier,
+        output_commitment,
+        recipient: recipient.key(),
+        penalty_lamports,
+        merkle_root: pool.merkle_root,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ShieldWithdrawEarly,
+        pool_key,
+        nullifier,
+        output_commitment,
+        pool.merkle_root,
+        penalty_lamports,
+        current_time,
+    ));
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ConfigureEarlyExit<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    pub authority: Signer<'info>,
+
+    /// The pool's audit log, appended to when it keeps one
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, shielded_pool.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(nullifier: [u8; 32])]
+pub struct ShieldWithdrawEarly<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = NullifierRecord::LEN,
+        seeds = [NULLIFIER_SEED, shielded_pool.key().as_ref(), &nullifier],
+        bump
+    )]
+    pub nullifier_account: Account<'info, NullifierRecord>,
+
+    /// CHECK: Pool vault paying the recipient and keeping the penalty
+    #[account(
+        mut,
+        seeds = [SHIELDED_VAULT_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_vault: AccountInfo<'info>,
+
+    /// CHECK: Payout recipient, bound by the early-exit tag in the proof
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Any payer (e.g. a relayer) - need not be the recipient
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The pool's nullifier filter, required once the pool keeps one
+    #[account(
+        mut,
+        seeds = [NULLIFIER_FILTER_SEED, shielded_pool.key().as_ref()],
+        bump = nullifier_filter.load()?.bump
+    )]
+    pub nullifier_filter: Option<AccountLoader<'info, NullifierFilter>>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    /// CHECK: The pool's withdrawal screener, required once the pool has
+    /// one; matched against `screener_program` in `screen_withdrawal`
+    pub screener_program: Option<UncheckedAccount<'info>>,
+}
@@ -0,0 +1,811 @@
+//! Governance token escrow, token-weighted voting and the spl-governance
+//! voter weight addin
+
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+
+use crate::*;
+
+// ============================================
+// GOVERNANCE TOKENS - Escrowed Voting Weight
+// ============================================
+//
+// Holders of an SPL governance token escrow it in a voter weight record
+// ([VOTER_WEIGHT_SEED, mint, owner]) whose associated token account holds
+// the tokens. A deposit may lock the tokens up to
+// MAX_GOVERNANCE_LOCKUP_SECONDS for a veToken-style bonus: the weight is
+// the deposit plus up to MAX_LOCKUP_BONUS_BPS of it, decaying linearly as
+// the lockup runs down. A proposal made token-weighted counts each vote
+// with its caster's weight at cast time, read through `VoterWeightPlugin`;
+// the tokens then stay in escrow until voting closes. Votes remain
+// commit-reveal and are revealed with `reveal_vote`.
+
+pub(crate) fn deposit_governance_tokens(
+    ctx: Context<DepositGovernanceTokens>,
+    amount: u64,
+    lockup_seconds: i64,
+) -> Result<()> {
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+    require!(
+        (0..=MAX_GOVERNANCE_LOCKUP_SECONDS).contains(&lockup_seconds),
+        ErrorCode::InvalidGovernanceLockup
+    );
+
+    let accounts = &ctx.accounts;
+    let mint = accounts.governing_mint.key();
+    let record_key = accounts.voter_weight_record.key();
+
+    // Associated Token Account CreateIdempotent for the record's escrow
+    invoke(
+        &Instruction {
+            program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(accounts.owner.key(), true),
+                AccountMeta::new(accounts.escrow.key(), false),
+                AccountMeta::new_readonly(record_key, false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new_readonly(System::id(), false),
+                AccountMeta::new_readonly(accounts.token_program.key(), false),
+            ],
+            data: vec![1],
+        },
+        &[
+            accounts.owner.to_account_info(),
+            accounts.escrow.to_account_info(),
+            accounts.voter_weight_record.to_account_info(),
+            accounts.governing_mint.to_account_info(),
+            accounts.system_program.to_account_info(),
+            accounts.token_program.to_account_info(),
+        ],
+    )?;
+
+    // Count what arrived, so transfer-fee mints cannot inflate the weight
+    let before = token_account_amount(&accounts.escrow)?;
+    if amount > 0 {
+        transfer_governance_tokens(
+            &accounts.token_program,
+            &accounts.source,
+            &accounts.governing_mint,
+            &accounts.escrow,
+            &accounts.owner,
+            amount,
+            &[],
+        )?;
+    }
+    let received = token_account_amount(&accounts.escrow)?.saturating_sub(before);
+
+    let record = &mut ctx.accounts.voter_weight_record;
+    if record.owner == Pubkey::default() {
+        record.owner = ctx.accounts.owner.key();
+        record.mint = mint;
+        record.created_at = current_time;
+        record.bump = ctx.bumps.voter_weight_record;
+        record.version = VoterWeightRecord::VERSION;
+    }
+
+    let lockup_ends_at = record.lockup_ends_at.max(current_time.saturating_add(lockup_seconds));
+    require!(
+        received > 0 || lockup_ends_at > record.lockup_ends_at,
+        ErrorCode::InvalidGovernanceTokenAmount
+    );
+    record.amount = record
+        .amount
+        .checked_add(received)
+        .ok_or(ErrorCode::InvalidGovernanceTokenAmount)?;
+    record.lockup_ends_at = lockup_ends_at;
+
+    emit!(GovernanceTokensDeposited {
+        voter_weight_record: record_key,
+        mint,
+        amount: received,
+        lockup_ends_at,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::DepositGovernanceTokens,
+        record_key,
+        mint.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        lockup_ends_at as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn withdraw_governance_tokens(ctx: Context<WithdrawGovernanceTokens>, amount: u64) -> Result<()> {
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+    let record = &ctx.accounts.voter_weight_record;
+
+    require!(record.is_unlocked(current_time), ErrorCode::GovernanceTokensLocked);
+    require!(
+        amount > 0 && amount <= record.amount,
+        ErrorCode::InvalidGovernanceTokenAmount
+    );
+
+    let record_key = record.key();
+    let (mint, owner) = (record.mint, record.owner);
+    transfer_governance_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.escrow,
+        &ctx.accounts.governing_mint,
+        &ctx.accounts.destination,
+        &ctx.accounts.voter_weight_record.to_account_info(),
+        amount,
+        &[&[VOTER_WEIGHT_SEED, mint.as_ref(), owner.as_ref(), &[record.bump]]],
+    )?;
+
+    ctx.accounts.voter_weight_record.amount -= amount;
+
+    emit!(GovernanceTokensWithdrawn {
+        voter_weight_record: record_key,
+        mint,
+        amount,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::WithdrawGovernanceTokens,
+        record_key,
+        mint.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn enable_token_weighted_voting(ctx: Context<EnableTokenWeightedVoting>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let mint = ctx.accounts.governing_mint.key();
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(current_time < proposal.voting_ends_at, ErrorCode::VotingEnded);
+    require!(
+        proposal.total_commitments == 0 && !proposal.is_weighted(),
+        ErrorCode::StakeWeightingLocked
+    );
+    require!(proposal.eligibility_root == [0u8; 32], ErrorCode::EligibilityRestricted);
+
+    proposal.governing_mint = mint;
+
+    emit!(TokenWeightingEnabled {
+        proposal: proposal.key(),
+        governing_mint: mint,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::EnableTokenWeightedVoting,
+        proposal.key(),
+        mint.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn cast_token_weighted_vote(ctx: Context<CastTokenWeightedVote>, vote_commitment: [u8; 32]) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let vote_record = &mut ctx.accounts.vote_record;
+    let voter = ctx.accounts.voter.key();
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(current_time < proposal.voting_ends_at, ErrorCode::VotingEnded);
+    require!(proposal.governing_mint != Pubkey::default(), ErrorCode::NotTokenWeighted);
+
+    let weight = take_voter_weight(&mut *ctx.accounts.voter_weight_record, proposal, &voter, current_time)?;
+
+    vote_record.proposal = proposal.key();
+    vote_record.voter = voter;
+    vote_record.commitment = vote_commitment;
+    vote_record.has_voted = true;
+    vote_record.has_revealed = false;
+    vote_record.voted_at = current_time;
+    vote_record.bump = ctx.bumps.vote_record;
+    vote_record.version = VoteRecord::VERSION;
+    vote_record.weight = weight;
+
+    proposal.total_commitments += 1;
+
+    emit!(TokenWeightedVoteCast {
+        proposal: proposal.key(),
+        voter,
+        commitment: vote_commitment,
+        weight,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CastTokenWeightedVote,
+        proposal.key(),
+        vote_commitment,
+        voter.to_bytes(),
+        [0u8; 32],
+        weight,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositGovernanceTokens<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = VoterWeightRecord::LEN,
+        seeds = [VOTER_WEIGHT_SEED, governing_mint.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    /// CHECK: Governance token mint, owned by `token_program`
+    #[account(owner = token_program.key() @ ErrorCode::InvalidGovernanceTokenAccount)]
+    pub governing_mint: UncheckedAccount<'info>,
+
+    /// CHECK: The owner's token account; the token program checks it
+    #[account(mut)]
+    pub source: UncheckedAccount<'info>,
+
+    /// CHECK: The record's associated token account, created if missing
+    #[account(
+        mut,
+        address = voter_weight_escrow_address(&voter_weight_record.key(), &governing_mint.key(), &token_program.key())
+            @ ErrorCode::InvalidGovernanceTokenAccount
+    )]
+    pub escrow: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: SPL Token or Token-2022
+    #[account(constraint = is_token_program(token_program.key) @ ErrorCode::InvalidGovernanceTokenAccount)]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// CHECK: Associated Token Account program
+    #[account(address = ASSOCIATED_TOKEN_PROGRAM_ID)]
+    pub associated_token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawGovernanceTokens<'info> {
+    #[account(
+        mut,
+        seeds = [VOTER_WEIGHT_SEED, voter_weight_record.mint.as_ref(), owner.key().as_ref()],
+        bump = voter_weight_record.bump
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    /// CHECK: The record's governance token mint
+    #[account(address = voter_weight_record.mint)]
+    pub governing_mint: UncheckedAccount<'info>,
+
+    /// CHECK: The record's associated token account
+    #[account(
+        mut,
+        address = voter_weight_escrow_address(&voter_weight_record.key(), &governing_mint.key(), &token_program.key())
+            @ ErrorCode::InvalidGovernanceTokenAccount
+    )]
+    pub escrow: UncheckedAccount<'info>,
+
+    /// CHECK: Token account receiving the tokens; the token program checks it
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    pub owner: Signer<'info>,
+
+    /// CHECK: SPL Token or Token-2022
+    #[account(constraint = is_token_program(token_program.key) @ ErrorCode::InvalidGovernanceTokenAccount)]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EnableTokenWeightedVoting<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.creator.as_ref(), &proposal.proposal_id],
+        bump = proposal.bump,
+        constraint = proposal.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// CHECK: Governance token mint, owned by SPL Token or Token-2022
+    #[account(constraint = is_token_program(governing_mint.owner) @ ErrorCode::InvalidGovernanceTokenAccount)]
+    pub governing_mint: UncheckedAccount<'info>,
+
+    pub creator: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CastTokenWeightedVote<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.creator.as_ref(), &proposal.proposal_id],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Keyed like one-vote-per-key votes, so `reveal_vote` reveals it
+    #[account(
+        init,
+        payer = voter,
+        space = VoteRecord::LEN,
+        seeds = [VOTE_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(
+        mut,
+        seeds = [VOTER_WEIGHT_SEED, proposal.governing_mint.as_ref(), voter.key().as_ref()],
+        bump = voter_weight_record.bump
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Read `voter`'s weight for `proposal` from a weight plugin and lock it
+/// until voting closes
+pub(crate) fn take_voter_weight(
+    plugin: &mut impl VoterWeightPlugin,
+    proposal: &Proposal,
+    voter: &Pubkey,
+    now: i64,
+) -> Result<u64> {
+    require!(
+        plugin.governing_mint() == proposal.governing_mint && plugin.weight_owner() == *voter,
+        ErrorCode::InvalidGovernanceTokenAccount
+    );
+    let weight = plugin.voter_weight(now);
+    require!(weight > 0, ErrorCode::InvalidVoteWeight);
+    plugin.lock_weight_until(proposal.voting_ends_at);
+    Ok(weight)
+}
+
+/// Whether `program` is SPL Token or Token-2022
+pub(crate) fn is_token_program(program: &Pubkey) -> bool {
+    *program == TOKEN_PROGRAM_ID || *program == TOKEN_2022_PROGRAM_ID
+}
+
+/// Balance of an SPL token account (bytes 64..72 of the base layout)
+pub(crate) fn token_account_amount(account: &AccountInfo) -> Result<u64> {
+    let data = account.try_borrow_data()?;
+    let amount = data
+        .get(64..72)
+        .ok_or(ErrorCode::InvalidGovernanceTokenAccount)?;
+    Ok(u64::from_le_bytes(amount.try_into().unwrap()))
+}
+
+/// SPL TransferChecked of `amount` tokens, reading the decimals from the
+/// mint (byte 44 of the base layout). `signer_seeds` sign for a PDA
+/// authority
+pub(crate) fn transfer_governance_tokens<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let decimals = *mint
+        .try_borrow_data()?
+        .get(44)
+        .ok_or(ErrorCode::InvalidGovernanceTokenAccount)?;
+    let mut data = vec![12];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(decimals);
+
+    invoke_signed(
+        &Instruction {
+            program_id: token_program.key(),
+            accounts: vec![
+                AccountMeta::new(from.key(), false),
+                AccountMeta::new_readonly(mint.key(), false),
+                AccountMeta::new(to.key(), false),
+                AccountMeta::new_readonly(authority.key(), true),
+            ],
+            data,
+        },
+        &[from.clone(), mint.clone(), to.clone(), authority.clone()],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+// ============================================
+// REALMS ADDIN - spl-governance Voter Weight
+// ============================================
+//
+// An existing Realms DAO can count shielded-pool notes as voting weight by
+// setting this program as its voter weight addin. The realm authority
+// registers a pool for a governing mint; each spl-governance proposal then
+// gets a snapshot of the pool root. A voter proves a note in that root with
+// the stake weight circuit and the weight lands in a standard addin
+// `VoterWeightRecord`, scoped to CastVote on that proposal and valid for the
+// current slot, so it is updated in the same transaction as the vote. The
+// weight nullifier is spent per snapshot: a note counts once per proposal
+// whichever key presents it.
+
+/// spl-governance `GovernanceAccountType::RealmV2`
+const REALM_V2_ACCOUNT_TYPE: u8 = 16;
+
+/// spl-governance `GovernanceAccountType::ProposalV2`
+const PROPOSAL_V2_ACCOUNT_TYPE: u8 = 14;
+
+pub(crate) fn create_realm_registrar(ctx: Context<CreateRealmRegistrar>, weight_unit: u64) -> Result<()> {
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+    let authority = ctx.accounts.realm_authority.key();
+
+    require!(weight_unit > 0, ErrorCode::InvalidVoteWeight);
+    require!(
+        realm_authority(&ctx.accounts.realm)? == Some(authority),
+        ErrorCode::Unauthorized
+    );
+
+    let registrar = &mut ctx.accounts.registrar;
+    registrar.realm = ctx.accounts.realm.key();
+    registrar.governing_token_mint = ctx.accounts.governing_token_mint.key();
+    registrar.pool = ctx.accounts.shielded_pool.key();
+    registrar.weight_unit = weight_unit;
+    registrar.authority = authority;
+    registrar.created_at = current_time;
+    registrar.bump = ctx.bumps.registrar;
+    registrar.version = RealmRegistrar::VERSION;
+
+    emit!(RealmRegistrarCreated {
+        registrar: registrar.key(),
+        realm: registrar.realm,
+        governing_token_mint: registrar.governing_token_mint,
+        pool: registrar.pool,
+        weight_unit,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateRealmRegistrar,
+        registrar.key(),
+        registrar.realm.to_bytes(),
+        registrar.pool.to_bytes(),
+        [0u8; 32],
+        weight_unit,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn snapshot_realm_proposal(ctx: Context<SnapshotRealmProposal>) -> Result<()> {
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+    let registrar = &ctx.accounts.registrar;
+    let proposal = &ctx.accounts.proposal;
+
+    require!(
+        is_realm_proposal(proposal, ctx.accounts.realm.owner, &registrar.governing_token_mint)?,
+        ErrorCode::InvalidRealmAccount
+    );
+
+    let weight_root = ctx.accounts.shielded_pool.merkle_root;
+    let snapshot = &mut ctx.accounts.snapshot;
+    snapshot.registrar = registrar.key();
+    snapshot.proposal = proposal.key();
+    snapshot.weight_root = weight_root;
+    snapshot.created_at = current_time;
+    snapshot.bump = ctx.bumps.snapshot;
+    snapshot.version = RealmWeightSnapshot::VERSION;
+
+    emit!(RealmProposalSnapshotted {
+        registrar: registrar.key(),
+        proposal: proposal.key(),
+        weight_root,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::SnapshotRealmProposal,
+        snapshot.key(),
+        registrar.key().to_bytes(),
+        proposal.key().to_bytes(),
+        weight_root,
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn update_realm_voter_weight(
+    ctx: Context<UpdateRealmVoterWeight>,
+    weight_nullifier: [u8; 32],
+    weight: u64,
+    weight_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    require_proof_len(&weight_proof)?;
+
+    let clock = clock::now(ctx.remaining_accounts)?;
+    let registrar = &ctx.accounts.registrar;
+    let snapshot = &ctx.accounts.snapshot;
+    let owner = ctx.accounts.governing_token_owner.key();
+    let min_value = weight
+        .checked_mul(registrar.weight_unit)
+        .filter(|_| weight > 0)
+        .ok_or(ErrorCode::InvalidVoteWeight)?;
+
+    // The owner takes the vote commitment's place, binding the proof to the
+    // key whose weight record it fills
+    let public_signals = [
+        snapshot.weight_root,
+        weight_nullifier,
+        owner.to_bytes(),
+        compute_stake_weight_tag(&snapshot.proposal, &registrar.pool, min_value),
+    ];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::StakeWeight, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::StakeWeight, &public_signals, &weight_proof),
+        ErrorCode::InvalidStakeWeightProof
+    );
+
+    let nullifier_record = &mut ctx.accounts.weight_nullifier_record;
+    nullifier_record.pool = registrar.pool;
+    nullifier_record.nullifier = weight_nullifier;
+    nullifier_record.spent_at = clock.unix_timestamp;
+    nullifier_record.bump = ctx.bumps.weight_nullifier_record;
+    nullifier_record.version = NullifierRecord::VERSION;
+
+    let record = &mut ctx.accounts.voter_weight_record;
+    record.realm = registrar.realm;
+    record.governing_token_mint = registrar.governing_token_mint;
+    record.governing_token_owner = owner;
+    record.voter_weight = weight;
+    record.voter_weight_expiry = Some(clock.slot);
+    record.weight_action = Some(VoterWeightAction::CastVote);
+    record.weight_action_target = Some(snapshot.proposal);
+
+    emit!(RealmVoterWeightUpdated {
+        registrar: registrar.key(),
+        proposal: snapshot.proposal,
+        weight_nullifier,
+        voter_weight: weight,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::UpdateRealmVoterWeight,
+        record.key(),
+        weight_nullifier,
+        snapshot.proposal.to_bytes(),
+        snapshot.weight_root,
+        weight,
+        clock.unix_timestamp,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn update_realm_max_voter_weight(ctx: Context<UpdateRealmMaxVoterWeight>) -> Result<()> {
+    let clock = clock::now(ctx.remaining_accounts)?;
+    let registrar = &ctx.accounts.registrar;
+    let max_voter_weight = ctx.accounts.pool_vault.lamports() / registrar.weight_unit;
+
+    let record = &mut ctx.accounts.max_voter_weight_record;
+    record.realm = registrar.realm;
+    record.governing_token_mint = registrar.governing_token_mint;
+    record.max_voter_weight = max_voter_weight;
+    record.max_voter_weight_expiry = Some(clock.slot);
+
+    emit!(RealmMaxVoterWeightUpdated {
+        registrar: registrar.key(),
+        max_voter_weight,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::UpdateRealmMaxVoterWeight,
+        record.key(),
+        registrar.key().to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        max_voter_weight,
+        clock.unix_timestamp,
+    ));
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateRealmRegistrar<'info> {
+    #[account(
+        init,
+        payer = realm_authority,
+        space = RealmRegistrar::LEN,
+        seeds = [REALM_REGISTRAR_SEED, realm.key().as_ref(), governing_token_mint.key().as_ref()],
+        bump
+    )]
+    pub registrar: Account<'info, RealmRegistrar>,
+
+    /// CHECK: spl-governance realm, parsed by `realm_authority`
+    pub realm: UncheckedAccount<'info>,
+
+    /// CHECK: Governing mint, owned by SPL Token or Token-2022
+    #[account(constraint = is_token_program(governing_token_mint.owner) @ ErrorCode::InvalidGovernanceTokenAccount)]
+    pub governing_token_mint: UncheckedAccount<'info>,
+
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(mut)]
+    pub realm_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SnapshotRealmProposal<'info> {
+    #[account(
+        seeds = [REALM_REGISTRAR_SEED, registrar.realm.as_ref(), registrar.governing_token_mint.as_ref()],
+        bump = registrar.bump
+    )]
+    pub registrar: Account<'info, RealmRegistrar>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = RealmWeightSnapshot::LEN,
+        seeds = [REALM_SNAPSHOT_SEED, registrar.key().as_ref(), proposal.key().as_ref()],
+        bump
+    )]
+    pub snapshot: Account<'info, RealmWeightSnapshot>,
+
+    /// CHECK: The registrar's realm; its owner is the governance program
+    #[account(address = registrar.realm)]
+    pub realm: UncheckedAccount<'info>,
+
+    /// CHECK: spl-governance proposal, parsed by `is_realm_proposal`
+    pub proposal: UncheckedAccount<'info>,
+
+    #[account(address = registrar.pool)]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(weight_nullifier: [u8; 32])]
+pub struct UpdateRealmVoterWeight<'info> {
+    #[account(
+        seeds = [REALM_REGISTRAR_SEED, registrar.realm.as_ref(), registrar.governing_token_mint.as_ref()],
+        bump = registrar.bump
+    )]
+    pub registrar: Account<'info, RealmRegistrar>,
+
+    #[account(
+        seeds = [REALM_SNAPSHOT_SEED, registrar.key().as_ref(), snapshot.proposal.as_ref()],
+        bump = snapshot.bump
+    )]
+    pub snapshot: Account<'info, RealmWeightSnapshot>,
+
+    #[account(
+        init_if_needed,
+        payer = governing_token_owner,
+        space = RealmsVoterWeightRecord::LEN,
+        seeds = [REALM_VOTER_WEIGHT_SEED, registrar.key().as_ref(), governing_token_owner.key().as_ref()],
+        bump
+    )]
+    pub voter_weight_record: Account<'info, RealmsVoterWeightRecord>,
+
+    /// Spent once per note and proposal
+    #[account(
+        init,
+        payer = governing_token_owner,
+        space = NullifierRecord::LEN,
+        seeds = [REALM_WEIGHT_NULLIFIER_SEED, snapshot.key().as_ref(), &weight_nullifier],
+        bump
+    )]
+    pub weight_nullifier_record: Account<'info, NullifierRecord>,
+
+    #[account(mut)]
+    pub governing_token_owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateRealmMaxVoterWeight<'info> {
+    #[account(
+        seeds = [REALM_REGISTRAR_SEED, registrar.realm.as_ref(), registrar.governing_token_mint.as_ref()],
+        bump = registrar.bump
+    )]
+    pub registrar: Account<'info, RealmRegistrar>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RealmsMaxVoterWeightRecord::LEN,
+        seeds = [REALM_MAX_VOTER_WEIGHT_SEED, registrar.key().as_ref()],
+        bump
+    )]
+    pub max_voter_weight_record: Account<'info, RealmsMaxVoterWeightRecord>,
+
+    /// CHECK: Vault of the registrar's pool
+    #[account(seeds = [SHIELDED_VAULT_SEED, registrar.pool.as_ref()], bump)]
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Authority of an spl-governance `RealmV2`, read past its variable-length
+/// council mint: account_type, community_mint, config (council mint option
+/// at byte 58), reserved, legacy, then `authority: Option<Pubkey>`
+pub(crate) fn realm_authority(realm: &AccountInfo) -> Result<Option<Pubkey>> {
+    let data = realm.try_borrow_data()?;
+    require!(
+        data.first() == Some(&REALM_V2_ACCOUNT_TYPE),
+        ErrorCode::InvalidRealmAccount
+    );
+    let council_mint_len = match data.get(58) {
+        Some(0) => 1,
+        Some(1) => 33,
+        _ => return err!(ErrorCode::InvalidRealmAccount),
+    };
+    let authority = 58 + council_mint_len + 6 + 2;
+    match data.get(authority) {
+        Some(0) => Ok(None),
+        Some(1) => {
+            let key = data
+                .get(authority + 1..authority + 33)
+                .ok_or(ErrorCode::InvalidRealmAccount)?;
+            Ok(Some(Pubkey::new_from_array(key.try_into().unwrap())))
+        }
+        _ => err!(ErrorCode::InvalidRealmAccount),
+    }
+}
+
+/// Whether `proposal` is a `ProposalV2` of `governance_program` voting
+/// with `governing_token_mint` (bytes 33..65, after the governance key)
+pub(crate) fn is_realm_proposal(
+    proposal: &AccountInfo,
+    governance_program: &Pubkey,
+    governing_token_mint: &Pubkey,
+) -> Result<bool> {
+    let data = proposal.try_borrow_data()?;
+    Ok(proposal.owner == governance_program
+        && data.first() == Some(&PROPOSAL_V2_ACCOUNT_TYPE)
+        && data.get(33..65) == Some(governing_token_mint.as_ref()))
+}
@@ -0,0 +1,259 @@
+//! Withdrawals into holding accounts
+
+use crate::*;
+
+// ============================================
+// HOLDING ACCOUNTS - Delayed Unshielding
+// ============================================
+//
+// Instead of paying a wallet, a withdrawal can park its lamports in a
+// holding PDA keyed by claim_key_hash = H(DOMAIN_CLAIM_KEY, claim_secret).
+// The withdrawal proof commits to
+//   holding_tag = H(DOMAIN_HOLDING, pool, claim_key_hash, lamports)
+// and anyone knowing claim_secret can later release the lamports with a
+// claim proof bound to H(DOMAIN_HOLDING_CLAIM, holding, recipient), so the
+// hop to the final address happens long after the nullifier appeared.
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn shield_withdraw_to_holding(
+    ctx: Context<ShieldWithdrawToHolding>,
+    nullifier: [u8; 32],
+    merkle_proof: [[u8; 32]; 8],
+    merkle_path_indices: u8,
+    withdrawal_proof: Vec<u8>,
+    output_commitment: [u8; 32],
+    release: HoldingRelease,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    require_proof_len(&withdrawal_proof)?;
+
+    let pool = &mut ctx.accounts.shielded_pool;
+    let holding = &mut ctx.accounts.holding_account;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(pool.is_active, ErrorCode::PoolNotActive);
+    require!(!pool.screens_withdrawals(), ErrorCode::WithdrawalNotScreenable);
+    require!(
+        release.lamports > 0 && release.claim_key_hash != [0u8; 32],
+        ErrorCode::InvalidHoldingWithdrawal
+    );
+    require!(!is_nullifier_used(pool, &nullifier), ErrorCode::NullifierAlreadyUsed);
+    require!(
+        verify_merkle_proof(&pool.merkle_root, &merkle_proof, merkle_path_indices, &nullifier),
+        ErrorCode::InvalidMerkleProof
+    );
+
+    let holding_tag = compute_holding_tag(&pool.key(), &release);
+    let public_signals = [
+        nullifier,
+        output_commitment,
+        pool.spend_anchor(),
+        holding_tag,
+        compute_pool_binding(&pool.key()),
+    ];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::HoldingWithdrawal, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::HoldingWithdrawal, &public_signals, &withdrawal_proof),
+        ErrorCode::InvalidHoldingWithdrawalProof
+    );
+
+    record_filtered_nullifiers(pool, ctx.accounts.nullifier_filter.as_ref(), &[nullifier])?;
+    spend_withdrawal_note(
+        pool,
+        &mut ctx.accounts.nullifier_account,
+        ctx.bumps.nullifier_account,
+        nullifier,
+        output_commitment,
+        current_time,
+    );
+
+    let pool_key = pool.key();
+    anchor_lang::system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.pool_vault.to_account_info(),
+                to: holding.to_account_info(),
+            },
+            &[&[SHIELDED_VAULT_SEED, pool_key.as_ref(), &[ctx.bumps.pool_vault]]],
+        ),
+        release.lamports,
+    )?;
+
+    holding.pool = pool_key;
+    holding.claim_key_hash = release.claim_key_hash;
+    holding.lamports = release.lamports;
+    holding.created_at = current_time;
+    holding.bump = ctx.bumps.holding_account;
+    holding.version = HoldingAccount::VERSION;
+
+    debug_log!(
+        "shield_withdraw_to_holding",
+        pool = pool_key,
+        holding = holding.key(),
+        nullifier = Hex(&nullifier),
+    );
+
+    emit_cpi!(ShieldedWithdrawHeld {
+        pool: pool_key,
+        holding_account: holding.key(),
+        nullifier,
+        output_commitment,
+        merkle_root: pool.merkle_root,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ShieldWithdrawToHolding,
+        pool_key,
+        nullifier,
+        holding.key().to_bytes(),
+        pool.merkle_root,
+        release.lamports,
+        current_time,
+    ));
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+pub(crate) fn claim_holding(ctx: Context<ClaimHolding>, claim_proof: Vec<u8>, circuit_id: [u8; 32]) -> Result<()> {
+    require_proof_len(&claim_proof)?;
+
+    let holding = &ctx.accounts.holding_account;
+    let recipient = ctx.accounts.recipient.key();
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::HoldingClaim, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::HoldingClaim, 
+            &[holding.claim_key_hash, compute_holding_claim_tag(&holding.key(), &recipient)],
+            &claim_proof,
+        ),
+        ErrorCode::InvalidHoldingClaimProof
+    );
+
+    emit_cpi!(HoldingClaimed {
+        pool: holding.pool,
+        holding_account: holding.key(),
+        recipient,
+        lamports: holding.lamports,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ClaimHolding,
+        holding.pool,
+        holding.claim_key_hash,
+        recipient.to_bytes(),
+        [0u8; 32],
+        holding.lamports,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(
+    nullifier: [u8; 32],
+    merkle_proof: [[u8; 32]; 8],
+    merkle_path_indices: u8,
+    withdrawal_proof: Vec<u8>,
+    output_commitment: [u8; 32],
+    release: HoldingRelease
+)]
+pub struct ShieldWithdrawToHolding<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = NullifierRecord::LEN,
+        seeds = [NULLIFIER_SEED, shielded_pool.key().as_ref(), &nullifier],
+        bump
+    )]
+    pub nullifier_account: Account<'info, NullifierRecord>,
+
+    /// CHECK: Pool vault releasing the withdrawn lamports
+    #[account(
+        mut,
+        seeds = [SHIELDED_VAULT_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = HoldingAccount::LEN,
+        seeds = [HOLDING_SEED, shielded_pool.key().as_ref(), &release.claim_key_hash],
+        bump
+    )]
+    pub holding_account: Account<'info, HoldingAccount>,
+
+    /// Any payer (e.g. a relayer) - not linked to the claimant
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The pool's nullifier filter, required once the pool keeps one
+    #[account(
+        mut,
+        seeds = [NULLIFIER_FILTER_SEED, shielded_pool.key().as_ref()],
+        bump = nullifier_filter.load()?.bump
+    )]
+    pub nullifier_filter: Option<AccountLoader<'info, NullifierFilter>>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimHolding<'info> {
+    #[account(
+        mut,
+        seeds = [HOLDING_SEED, holding_account.pool.as_ref(), &holding_account.claim_key_hash],
+        bump = holding_account.bump,
+        close = recipient
+    )]
+    pub holding_account: Account<'info, HoldingAccount>,
+
+    /// CHECK: Receives the held lamports and rent; bound by the claim proof
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
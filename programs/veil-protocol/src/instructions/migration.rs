@@ -0,0 +1,330 @@
+//! Account layout migration
+
+use anchor_lang::ZeroCopy;
+
+use crate::*;
+
+// ============================================
+// ACCOUNT MIGRATION
+// ============================================
+
+pub(crate) fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+    let account = ctx.accounts.account.to_account_info();
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    let discriminator = {
+        let data = account.try_borrow_data()?;
+        require!(data.len() >= 8, ErrorCode::InvalidAccountLayout);
+        data[..8].to_vec()
+    };
+
+    let accounts = (&account, &payer, &system_program);
+    let (from_version, to_version) = match discriminator.as_slice() {
+        d if d == WalletAccount::DISCRIMINATOR => migrate_borsh_account::<WalletAccount>(accounts)?,
+        d if d == AliasRecord::DISCRIMINATOR => migrate_borsh_account::<AliasRecord>(accounts)?,
+        d if d == WalletBackup::DISCRIMINATOR => migrate_borsh_account::<WalletBackup>(accounts)?,
+        d if d == WalletAttestation::DISCRIMINATOR => migrate_borsh_account::<WalletAttestation>(accounts)?,
+        d if d == WalletRegistry::DISCRIMINATOR => migrate_borsh_account::<WalletRegistry>(accounts)?,
+        d if d == WalletEnrollment::DISCRIMINATOR => migrate_borsh_account::<WalletEnrollment>(accounts)?,
+        d if d == Attestation::DISCRIMINATOR => migrate_borsh_account::<Attestation>(accounts)?,
+        d if d == Proposal::DISCRIMINATOR => migrate_borsh_account::<Proposal>(accounts)?,
+        d if d == ProposalTemplate::DISCRIMINATOR => migrate_borsh_account::<ProposalTemplate>(accounts)?,
+        d if d == ConvictionProposal::DISCRIMINATOR => migrate_borsh_account::<ConvictionProposal>(accounts)?,
+        d if d == ConvictionStake::DISCRIMINATOR => migrate_borsh_account::<ConvictionStake>(accounts)?,
+        d if d == QuadraticProposal::DISCRIMINATOR => migrate_borsh_account::<QuadraticProposal>(accounts)?,
+        d if d == QuadraticVoteRecord::DISCRIMINATOR => migrate_borsh_account::<QuadraticVoteRecord>(accounts)?,
+        d if d == VoteRecord::DISCRIMINATOR => migrate_borsh_account::<VoteRecord>(accounts)?,
+        d if d == VoterWeightRecord::DISCRIMINATOR => migrate_borsh_account::<VoterWeightRecord>(accounts)?,
+        d if d == RealmRegistrar::DISCRIMINATOR => migrate_borsh_account::<RealmRegistrar>(accounts)?,
+        d if d == RealmWeightSnapshot::DISCRIMINATOR => migrate_borsh_account::<RealmWeightSnapshot>(accounts)?,
+        d if d == RlnGroup::DISCRIMINATOR => migrate_borsh_account::<RlnGroup>(accounts)?,
+        d if d == RlnMember::DISCRIMINATOR => migrate_borsh_account::<RlnMember>(accounts)?,
+        d if d == RlnNullifierRecord::DISCRIMINATOR => migrate_borsh_account::<RlnNullifierRecord>(accounts)?,
+        d if d == ShieldedPool::DISCRIMINATOR => migrate_borsh_account::<ShieldedPool>(accounts)?,
+        d if d == ShieldedNote::DISCRIMINATOR => migrate_borsh_account::<ShieldedNote>(accounts)?,
+        d if d == NullifierRecord::DISCRIMINATOR => migrate_borsh_account::<NullifierRecord>(accounts)?,
+        d if d == ProtocolStats::DISCRIMINATOR => migrate_borsh_account::<ProtocolStats>(accounts)?,
+        d if d == HoldingAccount::DISCRIMINATOR => migrate_borsh_account::<HoldingAccount>(accounts)?,
+        d if d == PrivateStakePool::DISCRIMINATOR => migrate_borsh_account::<PrivateStakePool>(accounts)?,
+        d if d == PrivateStakeRecord::DISCRIMINATOR => migrate_borsh_account::<PrivateStakeRecord>(accounts)?,
+        d if d == CrankTask::DISCRIMINATOR => migrate_borsh_account::<CrankTask>(accounts)?,
+        d if d == Campaign::DISCRIMINATOR => migrate_borsh_account::<Campaign>(accounts)?,
+        d if d == Subscription::DISCRIMINATOR => migrate_borsh_account::<Subscription>(accounts)?,
+        d if d == Escrow::DISCRIMINATOR => migrate_borsh_account::<Escrow>(accounts)?,
+        d if d == ClaimableNote::DISCRIMINATOR => migrate_borsh_account::<ClaimableNote>(accounts)?,
+        d if d == ReserveAttestation::DISCRIMINATOR => migrate_borsh_account::<ReserveAttestation>(accounts)?,
+        d if d == TreasuryAttestation::DISCRIMINATOR => migrate_borsh_account::<TreasuryAttestation>(accounts)?,
+        d if d == SignerHeartbeats::DISCRIMINATOR => migrate_borsh_account::<SignerHeartbeats>(accounts)?,
+        d if d == RelayerRegistry::DISCRIMINATOR => migrate_borsh_account::<RelayerRegistry>(accounts)?,
+        d if d == RelayerRecord::DISCRIMINATOR => migrate_borsh_account::<RelayerRecord>(accounts)?,
+        d if d == CircuitRegistry::DISCRIMINATOR => migrate_borsh_account::<CircuitRegistry>(accounts)?,
+        d if d == DepositReceiptConfig::DISCRIMINATOR => migrate_borsh_account::<DepositReceiptConfig>(accounts)?,
+        d if d == DepositCaller::DISCRIMINATOR => migrate_borsh_account::<DepositCaller>(accounts)?,
+        d if d == ReferralProgram::DISCRIMINATOR => migrate_borsh_account::<ReferralProgram>(accounts)?,
+        d if d == ReferralRecord::DISCRIMINATOR => migrate_borsh_account::<ReferralRecord>(accounts)?,
+        d if d == PoolGovernance::DISCRIMINATOR => migrate_borsh_account::<PoolGovernance>(accounts)?,
+        d if d == PoolUpdateProposal::DISCRIMINATOR => migrate_borsh_account::<PoolUpdateProposal>(accounts)?,
+        d if d == AuditLog::DISCRIMINATOR => migrate_borsh_account::<AuditLog>(accounts)?,
+        d if d == Watchtower::DISCRIMINATOR => migrate_borsh_account::<Watchtower>(accounts)?,
+        d if d == BeaconRound::DISCRIMINATOR => migrate_borsh_account::<BeaconRound>(accounts)?,
+        d if d == BeaconEntry::DISCRIMINATOR => migrate_borsh_account::<BeaconEntry>(accounts)?,
+        d if d == MultisigProposalAction::DISCRIMINATOR => migrate_borsh_account::<MultisigProposalAction>(accounts)?,
+        d if d == EncryptedMultisigAction::DISCRIMINATOR => migrate_borsh_account::<EncryptedMultisigAction>(accounts)?,
+        d if d == StealthMultisig::DISCRIMINATOR => migrate_zero_copy_account::<StealthMultisig>(accounts)?,
+        d if d == MultisigProposal::DISCRIMINATOR => migrate_zero_copy_account::<MultisigProposal>(accounts)?,
+        d if d == TreeCheckpoint::DISCRIMINATOR => migrate_zero_copy_account::<TreeCheckpoint>(accounts)?,
+        d if d == NullifierFilter::DISCRIMINATOR => migrate_zero_copy_account::<NullifierFilter>(accounts)?,
+        _ => return err!(ErrorCode::InvalidAccountLayout),
+    };
+
+    debug_log!(
+        "migrate_account",
+        account = account.key(),
+        from = from_version,
+        to = to_version,
+    );
+
+    emit!(AccountMigrated {
+        account: account.key(),
+        from_version,
+        to_version,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::MigrateAccount,
+        account.key(),
+        [0u8; 32],
+        [0u8; 32],
+        [0u8; 32],
+        to_version as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: Any account owned by this program; the discriminator selects
+    /// the layout and `migrate_account` rejects anything it does not know
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grow a Borsh account to its current layout and bump its version
+/// Returns (from_version, to_version)
+pub(crate) fn migrate_borsh_account<'info, T>(
+    (account, payer, system_program): (&AccountInfo<'info>, &AccountInfo<'info>, &AccountInfo<'info>),
+) -> Result<(u8, u8)>
+where
+    T: AccountSerialize + AccountDeserialize + Versioned,
+{
+    if account.data_len() < T::LEN {
+        // New bytes are zero-filled, so pre-versioning accounts read as version 0
+        resize_account(account, payer, system_program, T::LEN)?;
+    }
+
+    let mut state = T::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    let from_version = state.version();
+    require!(from_version <= T::VERSION, ErrorCode::UnsupportedAccountVersion);
+
+    state.upgrade(from_version);
+    state.set_version(T::VERSION);
+    state.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+
+    Ok((from_version, T::VERSION))
+}
+
+/// Resize a program account, topping up rent from `payer` when it grows
+/// New bytes are zero-filled
+pub(crate) fn resize_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    let shortfall = Rent::get()?.minimum_balance(new_len).saturating_sub(account.lamports());
+    if shortfall > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    account.resize(new_len)?;
+    Ok(())
+}
+
+/// Grow a zero-copy header to its current layout and bump its version
+/// The version byte took over header padding, so zero-copy accounts from
+/// before versioning read as version 0. Appended header fields are
+/// zero-filled and any trailing commitment slots are moved past them.
+/// Accounts still in the Borsh layout that preceded zero-copy are rewritten
+/// whole (see `ZeroCopyVersioned::from_borsh_v0`) and reported as version 0
+/// Returns (from_version, to_version)
+pub(crate) fn migrate_zero_copy_account<'info, T: ZeroCopy + Owner + ZeroCopyVersioned>(
+    (account, payer, system_program): (&AccountInfo<'info>, &AccountInfo<'info>, &AccountInfo<'info>),
+) -> Result<(u8, u8)> {
+    if T::BORSH_V0_LEN == Some(account.data_len()) {
+        let converted = T::from_borsh_v0(&account.try_borrow_data()?)?;
+        resize_account(account, payer, system_program, converted.len())?;
+        account.try_borrow_mut_data()?.copy_from_slice(&converted);
+        return Ok((0, T::VERSION));
+    }
+
+    let from_version = {
+        let data = account.try_borrow_data()?;
+        require!(data.len() > T::VERSION_OFFSET, ErrorCode::InvalidAccountLayout);
+        data[T::VERSION_OFFSET]
+    };
+    require!(from_version <= T::VERSION, ErrorCode::UnsupportedAccountVersion);
+
+    let old_header_len = T::header_len(from_version);
+    let data_len = account.data_len();
+    require!(data_len >= old_header_len, ErrorCode::InvalidAccountLayout);
+    if old_header_len < T::LEN {
+        let tail_len = data_len - old_header_len;
+        resize_account(account, payer, system_program, T::LEN + tail_len)?;
+
+        let mut data = account.try_borrow_mut_data()?;
+        data.copy_within(old_header_len..old_header_len + tail_len, T::LEN);
+        data[old_header_len..T::LEN].fill(0);
+    }
+
+    let mut data = account.try_borrow_mut_data()?;
+    let state: &mut T = bytemuck::from_bytes_mut(&mut data[8..T::LEN]);
+
+    state.upgrade(from_version);
+    state.set_version(T::VERSION);
+
+    Ok((from_version, T::VERSION))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A multisig as the pre-zero-copy program serialized it: 3 of 10 signer
+    /// slots filled, 2-of-3
+    fn borsh_v0_multisig(creator: Pubkey) -> Vec<u8> {
+        let mut data = vec![0u8; 407];
+        data[..8].copy_from_slice(StealthMultisig::DISCRIMINATOR);
+        data[8..40].copy_from_slice(&[7; 32]); // vault_id
+        data[40..72].copy_from_slice(creator.as_ref());
+        data[72] = 2; // threshold
+        data[73] = 3; // total_signers
+        for (slot, fill) in [0xA1u8, 0xA2, 0xA3].into_iter().enumerate() {
+            data[74 + 32 * slot..106 + 32 * slot].fill(fill);
+        }
+        data[394..402].copy_from_slice(&1_700_000_000i64.to_le_bytes()); // created_at
+        data[402..406].copy_from_slice(&9u32.to_le_bytes()); // proposal_count
+        data[406] = 254; // bump
+        data
+    }
+
+    /// A proposal as the pre-zero-copy program serialized it, executed with
+    /// 2 approvals
+    fn borsh_v0_proposal(multisig: Pubkey) -> Vec<u8> {
+        let mut data = vec![0u8; 443];
+        data[..8].copy_from_slice(MultisigProposal::DISCRIMINATOR);
+        data[8..40].copy_from_slice(multisig.as_ref());
+        data[40..72].fill(0x11); // proposal_id
+        data[72..104].fill(0x22); // instruction_hash
+        data[104..112].copy_from_slice(&1_700_000_100i64.to_le_bytes()); // created_at
+        data[112] = 2; // approval_count
+        data[113..145].fill(0xB1);
+        data[145..177].fill(0xB2);
+        data[433] = 1; // is_executed
+        data[434..442].copy_from_slice(&1_700_000_200i64.to_le_bytes()); // executed_at
+        data[442] = 253; // bump
+        data
+    }
+
+    #[test]
+    fn borsh_v0_multisigs_convert_to_the_current_layout() {
+        let creator = Pubkey::new_unique();
+        let data = borsh_v0_multisig(creator);
+        assert_eq!(StealthMultisig::BORSH_V0_LEN, Some(data.len()));
+
+        let converted = StealthMultisig::from_borsh_v0(&data).unwrap();
+        assert_eq!(converted.len(), StealthMultisig::space(3));
+        assert_eq!(&converted[..8], StealthMultisig::DISCRIMINATOR);
+
+        let multisig: StealthMultisig = bytemuck::pod_read_unaligned(&converted[8..StealthMultisig::LEN]);
+        assert_eq!(multisig.vault_id, [7; 32]);
+        assert_eq!(multisig.creator, creator);
+        assert_eq!(multisig.threshold, 2);
+        assert_eq!(multisig.total_signers, 3);
+        assert_eq!(multisig.created_at, 1_700_000_000);
+        assert_eq!(multisig.proposal_count, 9);
+        assert_eq!(multisig.bump, 254);
+        assert_eq!(multisig.version(), StealthMultisig::VERSION);
+        assert_eq!(multisig.veto_commitment, [0; 32]);
+
+        let slots = &converted[StealthMultisig::LEN..];
+        assert_eq!(slots, [[0xA1; 32], [0xA2; 32], [0xA3; 32]].concat());
+    }
+
+    #[test]
+    fn borsh_v0_proposals_convert_to_the_current_layout() {
+        let multisig = Pubkey::new_unique();
+        let data = borsh_v0_proposal(multisig);
+        assert_eq!(MultisigProposal::BORSH_V0_LEN, Some(data.len()));
+
+        let converted = MultisigProposal::from_borsh_v0(&data).unwrap();
+        assert_eq!(converted.len(), MultisigProposal::space(2));
+        assert_eq!(&converted[..8], MultisigProposal::DISCRIMINATOR);
+
+        let proposal: MultisigProposal = bytemuck::pod_read_unaligned(&converted[8..MultisigProposal::LEN]);
+        assert_eq!(proposal.multisig, multisig);
+        assert_eq!(proposal.proposal_id, [0x11; 32]);
+        assert_eq!(proposal.instruction_hash, [0x22; 32]);
+        assert_eq!(proposal.created_at, 1_700_000_100);
+        assert_eq!(proposal.approval_count, 2);
+        assert_eq!(proposal.is_executed, 1);
+        assert_eq!(proposal.executed_at, 1_700_000_200);
+        assert_eq!(proposal.bump, 253);
+        assert_eq!(proposal.kind, MultisigActionKind::Custom as u8);
+        assert_eq!(proposal.version(), MultisigProposal::VERSION);
+
+        let slots = &converted[MultisigProposal::LEN..];
+        assert_eq!(slots, [[0xB1; 32], [0xB2; 32]].concat());
+    }
+
+    #[test]
+    fn borsh_v0_accounts_with_overfull_slots_are_rejected() {
+        let mut multisig = borsh_v0_multisig(Pubkey::new_unique());
+        multisig[73] = 11;
+        assert!(StealthMultisig::from_borsh_v0(&multisig).is_err());
+
+        let mut proposal = borsh_v0_proposal(Pubkey::new_unique());
+        proposal[112] = 11;
+        assert!(MultisigProposal::from_borsh_v0(&proposal).is_err());
+    }
+
+    #[test]
+    fn zero_copy_sizes_never_collide_with_the_borsh_v0_sizes() {
+        for version in 0..=StealthMultisig::VERSION {
+            assert_eq!(StealthMultisig::header_len(version) % 2, 0);
+        }
+        for version in 0..=MultisigProposal::VERSION {
+            assert_eq!(MultisigProposal::header_len(version) % 2, 0);
+        }
+        assert_eq!(StealthMultisig::BORSH_V0_LEN.unwrap() % 2, 1);
+        assert_eq!(MultisigProposal::BORSH_V0_LEN.unwrap() % 2, 1);
+    }
+}
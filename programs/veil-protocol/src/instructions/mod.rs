@@ -0,0 +1,54 @@
+//! Instruction handlers and their account contexts, one module per
+//! feature. The `#[program]` entry points in lib.rs forward here.
+
+pub mod audit;
+pub mod beacon;
+pub mod cancel;
+pub mod circuits;
+pub mod commerce;
+pub mod crank;
+pub mod early_exit;
+pub mod governance;
+pub mod holding;
+pub mod migration;
+pub mod multisig;
+pub mod referral;
+pub mod relayer;
+pub mod rln;
+pub mod router;
+pub mod shielded;
+pub mod split;
+pub mod staking;
+#[cfg(test)]
+pub(crate) mod test_support;
+#[cfg(feature = "test-clock")]
+pub mod testing;
+pub mod views;
+pub mod voting;
+pub mod wallet;
+pub mod watchtower;
+
+pub use audit::*;
+pub use beacon::*;
+pub use cancel::*;
+pub use circuits::*;
+pub use commerce::*;
+pub use crank::*;
+pub use early_exit::*;
+pub use governance::*;
+pub use holding::*;
+pub use migration::*;
+pub use multisig::*;
+pub use referral::*;
+pub use relayer::*;
+pub use rln::*;
+pub use router::*;
+pub use shielded::*;
+pub use split::*;
+pub use staking::*;
+#[cfg(feature = "test-clock")]
+pub use testing::*;
+pub use views::*;
+pub use voting::*;
+pub use wallet::*;
+pub use watchtower::*;
@@ -0,0 +1,1949 @@
+//! Stealth multisig
+
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::ZeroCopy;
+use std::cell::RefMut;
+
+use crate::*;
+
+// ============================================
+// STEALTH MULTISIG - Hidden Signers
+// ============================================
+
+pub(crate) fn create_multisig(
+    ctx: Context<CreateMultisig>,
+    vault_id: [u8; 32],
+    threshold: u8,
+    signer_commitments: Vec<[u8; 32]>,
+) -> Result<()> {
+    let multisig_key = ctx.accounts.multisig.key();
+    let (mut multisig, mut signers) = load_init_with_commitments_mut(&ctx.accounts.multisig)?;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(threshold > 0, ErrorCode::InvalidThreshold);
+    require!(signer_commitments.len() >= threshold as usize, ErrorCode::InvalidThreshold);
+    require!(signer_commitments.len() <= MAX_MULTISIG_SIGNERS, ErrorCode::TooManySigners);
+
+    multisig.vault_id = vault_id;
+    multisig.creator = ctx.accounts.creator.key();
+    multisig.threshold = threshold;
+    multisig.total_signers = signer_commitments.len() as u8;
+    multisig.created_at = current_time;
+    multisig.proposal_count = 0;
+    multisig.bump = ctx.bumps.multisig;
+    multisig.version = StealthMultisig::VERSION;
+
+    // Store signer commitments (not actual public keys!)
+    signers.copy_from_slice(&signer_commitments);
+
+    emit!(MultisigCreated {
+        multisig: multisig_key,
+        vault_id,
+        threshold,
+        total_signers: multisig.total_signers,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateMultisig,
+        multisig_key,
+        vault_id,
+        [0u8; 32],
+        [0u8; 32],
+        threshold as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn create_multisig_proposal(
+    ctx: Context<CreateMultisigProposal>,
+    proposal_id: [u8; 32],
+    action: MultisigAction,
+) -> Result<()> {
+    let multisig_key = ctx.accounts.multisig.key();
+    let proposal_key = ctx.accounts.multisig_proposal.key();
+    let (mut multisig, signers) = load_with_commitments_mut(&ctx.accounts.multisig)?;
+    let proposal = &mut ctx.accounts.multisig_proposal.load_init()?;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    validate_multisig_action(&action, &signers)?;
+    let instruction_hash = compute_multisig_action_hash(&action)?;
+
+    let action_account = &mut ctx.accounts.proposal_action;
+    action_account.proposal = proposal_key;
+    action_account.action = action.clone();
+    action_account.bump = ctx.bumps.proposal_action;
+    action_account.version = MultisigProposalAction::VERSION;
+
+    proposal.multisig = multisig_key;
+    proposal.proposal_id = proposal_id;
+    proposal.instruction_hash = instruction_hash;
+    proposal.kind = action.kind() as u8;
+    proposal.created_at = current_time;
+    proposal.approval_count = 0;
+    proposal.is_executed = 0;
+    proposal.bump = ctx.bumps.multisig_proposal;
+    proposal.version = MultisigProposal::VERSION;
+    proposal.signer_epoch = multisig.signer_epoch;
+
+    multisig.proposal_count += 1;
+
+    emit!(MultisigProposalCreated {
+        multisig: multisig_key,
+        proposal: proposal_key,
+        proposal_id,
+        kind: action.kind(),
+        instruction_hash,
+        timestamp: current_time,
+    });
+
+    // Plain transfers below the watchtower's threshold are routine
+    let spend = action.spend().map(|(_, amount)| amount);
+    raise_watchtower_alert(
+        ctx.accounts
+            .watchtower
+            .as_mut()
+            .filter(|watchtower| spend.is_none_or(|amount| amount >= watchtower.large_amount)),
+        WatchtowerAlertKind::LargeProposal,
+        proposal_key.to_bytes(),
+        spend.unwrap_or(0),
+        current_time,
+    );
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateMultisigProposal,
+        proposal_key,
+        proposal_id,
+        instruction_hash,
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn stealth_sign(
+    ctx: Context<StealthSign>,
+    signer_proof: Vec<u8>,  // Proof that signer knows the preimage of one of the commitments
+    approval_commitment: [u8; 32], // Signer's nullifier for this proposal
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    require_proof_len(&signer_proof)?;
+
+    let proposal_key = ctx.accounts.multisig_proposal.key();
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    // Verify signer_proof against the signer_commitments before the
+    // proposal grows, so only a signer can add an approval slot
+    let tag = {
+        let (_, signers) = load_with_commitments_mut(&ctx.accounts.multisig)?;
+        compute_stealth_approval_tag(&proposal_key, &signers)
+    };
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::StealthApproval, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::StealthApproval, &[approval_commitment, tag], &signer_proof),
+        ErrorCode::InvalidSignerProof
+    );
+
+    let current_count = {
+        let multisig = ctx.accounts.multisig.load()?;
+        let (mut proposal, mut approvals) = load_with_commitments_mut(&ctx.accounts.multisig_proposal)?;
+        require!(proposal.is_executed == 0, ErrorCode::ProposalAlreadyExecuted);
+        reset_stale_approvals(&multisig, &mut proposal, &mut approvals);
+        require!(
+            proposal.approval_count < multisig.required_approvals(&proposal),
+            ErrorCode::ThresholdReached
+        );
+
+        // Check this approval commitment hasn't been used
+        let current_count = proposal.approval_count as usize;
+        require!(
+            constant_time_position(&approvals[..current_count], &approval_commitment).is_none(),
+            ErrorCode::DuplicateApproval
+        );
+        current_count
+    };
+
+    resize_account(
+        ctx.accounts.multisig_proposal.as_ref(),
+        ctx.accounts.signer.as_ref(),
+        ctx.accounts.system_program.as_ref(),
+        MultisigProposal::space(current_count + 1),
+    )?;
+
+    let multisig = &ctx.accounts.multisig.load()?;
+    let (mut proposal, mut approvals) =
+        load_with_commitments_mut(&ctx.accounts.multisig_proposal)?;
+    let required = multisig.required_approvals(&proposal);
+
+    // Store the approval commitment (not the signer identity!)
+    approvals[current_count] = approval_commitment;
+    proposal.approval_count += 1;
+    if proposal.approval_count == required {
+        // Opens the veto window when the multisig has a veto authority
+        // and starts the execution timelock
+        proposal.threshold_reached_at = current_time;
+        proposal.executable_after = current_time.saturating_add(multisig.timelock);
+    }
+
+    debug_log!(
+        "stealth_sign",
+        proposal = proposal_key,
+        approvals = proposal.approval_count,
+        threshold = required,
+    );
+
+    emit!(StealthSignatureAdded {
+        proposal: proposal_key,
+        approval_commitment,
+        current_approvals: proposal.approval_count,
+        threshold: required,
+        executable_after: proposal.executable_after,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::StealthSign,
+        proposal_key,
+        approval_commitment,
+        [0u8; 32],
+        [0u8; 32],
+        proposal.approval_count as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+/// `stealth_sign` for approvals signed offline, e.g. in a durable-nonce
+/// transaction that can land any time after it was signed: the approval
+/// only counts for the action the signer reviewed and until `valid_until`
+pub(crate) fn stealth_sign_offline(
+    ctx: Context<StealthSign>,
+    signer_proof: Vec<u8>,
+    approval_commitment: [u8; 32],
+    instruction_hash: [u8; 32],
+    valid_until: i64,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+    require!(current_time <= valid_until, ErrorCode::OfflineApprovalExpired);
+    require!(
+        constant_time_eq(&ctx.accounts.multisig_proposal.load()?.instruction_hash, &instruction_hash),
+        ErrorCode::OfflineApprovalMismatch
+    );
+
+    stealth_sign(ctx, signer_proof, approval_commitment, circuit_id)
+}
+
+pub(crate) fn revoke_stealth_signature(
+    ctx: Context<RevokeStealthSignature>,
+    approval_commitment: [u8; 32],
+    revocation_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    require_proof_len(&revocation_proof)?;
+
+    let proposal_key = ctx.accounts.multisig_proposal.key();
+    let multisig = &ctx.accounts.multisig.load()?;
+    let (mut proposal, mut approvals) =
+        load_with_commitments_mut(&ctx.accounts.multisig_proposal)?;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(proposal.is_executed == 0, ErrorCode::ProposalAlreadyExecuted);
+    let required = multisig.required_approvals(&proposal);
+
+    let current_count = proposal.approval_count as usize;
+    let slot = constant_time_position(&approvals[..current_count], &approval_commitment)
+        .ok_or(ErrorCode::ApprovalNotFound)?;
+
+    let public_signals = [
+        approval_commitment,
+        compute_approval_revocation_tag(&proposal_key, &approval_commitment),
+    ];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::ApprovalRevocation, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::ApprovalRevocation, &public_signals, &revocation_proof),
+        ErrorCode::InvalidRevocationProof
+    );
+
+    // Keep approvals packed: move the last one into the freed slot. The
+    // account keeps its size; the next `stealth_sign` reuses the slot.
+    approvals[slot] = approvals[current_count - 1];
+    approvals[current_count - 1] = [0u8; 32];
+    proposal.approval_count -= 1;
+    if proposal.approval_count < required {
+        proposal.threshold_reached_at = 0;
+        proposal.executable_after = 0;
+    }
+
+    debug_log!(
+        "revoke_stealth_signature",
+        proposal = proposal_key,
+        approvals = proposal.approval_count,
+        threshold = required,
+    );
+
+    emit!(StealthSignatureRevoked {
+        proposal: proposal_key,
+        approval_commitment,
+        current_approvals: proposal.approval_count,
+        threshold: required,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RevokeStealthSignature,
+        proposal_key,
+        approval_commitment,
+        [0u8; 32],
+        [0u8; 32],
+        proposal.approval_count as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn execute_multisig_proposal<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteMultisigProposal<'info>>,
+) -> Result<()> {
+    let remaining_accounts = clock::strip(ctx.remaining_accounts);
+    let multisig_key = ctx.accounts.multisig.key();
+    let proposal_key = ctx.accounts.multisig_proposal.key();
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    let (approval_count, kind) = {
+        let multisig = &ctx.accounts.multisig.load()?;
+        let proposal = &mut ctx.accounts.multisig_proposal.load_mut()?;
+
+        require!(proposal.is_executed == 0, ErrorCode::ProposalAlreadyExecuted);
+        require!(
+            proposal.approval_count >= multisig.required_approvals(proposal),
+            ErrorCode::InsufficientApprovals
+        );
+        require_veto_window_elapsed(multisig, proposal, current_time)?;
+        require_current_signers(multisig, proposal)?;
+        require!(
+            current_time >= proposal.executable_after,
+            ErrorCode::TimelockNotElapsed
+        );
+
+        proposal.is_executed = 1;
+        proposal.executed_at = current_time;
+        (proposal.approval_count, proposal.kind)
+    };
+
+    // Proposals migrated from untyped v1 have no action account
+    let action = match &ctx.accounts.proposal_action {
+        Some(action_account) => action_account.action.clone(),
+        None => {
+            require!(
+                kind == MultisigActionKind::Custom as u8,
+                ErrorCode::InvalidMultisigAction
+            );
+            MultisigAction::Custom {
+                program_id: Pubkey::default(),
+                instruction_hash: ctx.accounts.multisig_proposal.load()?.instruction_hash,
+            }
+        }
+    };
+
+    apply_multisig_action(
+        &ctx.accounts.multisig,
+        &ctx.accounts.executor,
+        &ctx.accounts.system_program,
+        remaining_accounts,
+        &action,
+        current_time,
+    )?;
+
+    debug_log!(
+        "execute_multisig_proposal",
+        proposal = proposal_key,
+        approvals = approval_count,
+    );
+
+    emit!(MultisigProposalExecuted {
+        multisig: multisig_key,
+        proposal: proposal_key,
+        kind: action.kind(),
+        approval_count,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ExecuteMultisigProposal,
+        proposal_key,
+        [0u8; 32],
+        [0u8; 32],
+        [0u8; 32],
+        approval_count as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn create_encrypted_multisig_proposal(
+    ctx: Context<CreateEncryptedMultisigProposal>,
+    proposal_id: [u8; 32],
+    ciphertext: Vec<u8>,
+    key_commitment: [u8; 32],
+    share_commitments: Vec<[u8; 32]>,
+) -> Result<()> {
+    let multisig_key = ctx.accounts.multisig.key();
+    let proposal_key = ctx.accounts.multisig_proposal.key();
+    let multisig = &mut ctx.accounts.multisig.load_mut()?;
+    let proposal = &mut ctx.accounts.multisig_proposal.load_init()?;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(!ciphertext.is_empty(), ErrorCode::InvalidEncryptedAction);
+    require!(ciphertext.len() <= MAX_ENCRYPTED_ACTION_LEN, ErrorCode::InvalidEncryptedAction);
+    require!(key_commitment != [0u8; 32], ErrorCode::InvalidEncryptedAction);
+    require!(
+        share_commitments.len() == multisig.total_signers as usize,
+        ErrorCode::InvalidEncryptedAction
+    );
+
+    let instruction_hash = hash_domain(DOMAIN_MULTISIG_ACTION, &[&ciphertext]);
+
+    let payload = &mut ctx.accounts.encrypted_action;
+    payload.proposal = proposal_key;
+    payload.key_commitment = key_commitment;
+    payload.threshold = multisig.threshold;
+    payload.share_commitments = share_commitments;
+    payload.ciphertext = ciphertext;
+    payload.decryption_key = None;
+    payload.bump = ctx.bumps.encrypted_action;
+    payload.version = EncryptedMultisigAction::VERSION;
+
+    proposal.multisig = multisig_key;
+    proposal.proposal_id = proposal_id;
+    proposal.instruction_hash = instruction_hash;
+    proposal.kind = MultisigActionKind::Encrypted as u8;
+    proposal.created_at = current_time;
+    proposal.approval_count = 0;
+    proposal.is_executed = 0;
+    proposal.bump = ctx.bumps.multisig_proposal;
+    proposal.version = MultisigProposal::VERSION;
+    proposal.signer_epoch = multisig.signer_epoch;
+
+    multisig.proposal_count += 1;
+
+    emit!(MultisigProposalCreated {
+        multisig: multisig_key,
+        proposal: proposal_key,
+        proposal_id,
+        kind: MultisigActionKind::Encrypted,
+        instruction_hash,
+        timestamp: current_time,
+    });
+
+    // The action is hidden until execution, so it always counts as large
+    raise_watchtower_alert(
+        ctx.accounts.watchtower.as_mut(),
+        WatchtowerAlertKind::LargeProposal,
+        proposal_key.to_bytes(),
+        0,
+        current_time,
+    );
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateEncryptedMultisigProposal,
+        proposal_key,
+        proposal_id,
+        instruction_hash,
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn execute_encrypted_multisig_proposal<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteEncryptedMultisigProposal<'info>>,
+    key_shares: Vec<ProposalKeyShare>,
+) -> Result<()> {
+    let remaining_accounts = clock::strip(ctx.remaining_accounts);
+    let multisig_key = ctx.accounts.multisig.key();
+    let proposal_key = ctx.accounts.multisig_proposal.key();
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    let approval_count = {
+        let multisig = &ctx.accounts.multisig.load()?;
+        let proposal = &mut ctx.accounts.multisig_proposal.load_mut()?;
+
+        require!(proposal.is_executed == 0, ErrorCode::ProposalAlreadyExecuted);
+        require!(
+            proposal.approval_count >= multisig.required_approvals(proposal),
+            ErrorCode::InsufficientApprovals
+        );
+        require_veto_window_elapsed(multisig, proposal, current_time)?;
+        require_current_signers(multisig, proposal)?;
+        require!(
+            current_time >= proposal.executable_after,
+            ErrorCode::TimelockNotElapsed
+        );
+
+        proposal.is_executed = 1;
+        proposal.executed_at = current_time;
+        proposal.approval_count
+    };
+
+    let payload = &mut ctx.accounts.encrypted_action;
+    let action = {
+        let (_, signers) = load_with_commitments_mut(&ctx.accounts.multisig)?;
+
+        // Shares are bound to signer slots, so a signer change since
+        // creation invalidates the proposal
+        require!(
+            signers.len() == payload.share_commitments.len(),
+            ErrorCode::InvalidKeyShare
+        );
+        require!(
+            key_shares.len() >= payload.threshold as usize,
+            ErrorCode::InsufficientKeyShares
+        );
+
+        for (i, key_share) in key_shares.iter().enumerate() {
+            let slot = (key_share.index as usize)
+                .checked_sub(1)
+                .filter(|slot| *slot < signers.len())
+                .ok_or(ErrorCode::InvalidKeyShare)?;
+            require!(
+                key_shares[..i].iter().all(|other| other.index != key_share.index),
+                ErrorCode::InvalidKeyShare
+            );
+            require!(
+                constant_time_eq(
+                    &compute_key_share_commitment(&proposal_key, &signers[slot], key_share),
+                    &payload.share_commitments[slot],
+                ),
+                ErrorCode::InvalidKeyShare
+            );
+        }
+
+        let key = reconstruct_shamir_secret(&key_shares);
+        require!(
+            constant_time_eq(&hash_domain(DOMAIN_PROPOSAL_KEY, &[&key]), &payload.key_commitment),
+            ErrorCode::InvalidKeyShare
+        );
+
+        let plaintext = apply_proposal_keystream(&key, &payload.ciphertext);
+        let action = MultisigAction::try_from_slice(&plaintext)
+            .map_err(|_| error!(ErrorCode::InvalidEncryptedAction))?;
+        validate_multisig_action(&action, &signers)?;
+
+        payload.decryption_key = Some(key);
+        action
+    };
+
+    apply_multisig_action(
+        &ctx.accounts.multisig,
+        &ctx.accounts.executor,
+        &ctx.accounts.system_program,
+        remaining_accounts,
+        &action,
+        current_time,
+    )?;
+
+    ctx.accounts.multisig_proposal.load_mut()?.kind = action.kind() as u8;
+
+    debug_log!(
+        "execute_encrypted_multisig_proposal",
+        proposal = proposal_key,
+        approvals = approval_count,
+        shares = key_shares.len(),
+    );
+
+    emit!(MultisigProposalDecrypted {
+        proposal: proposal_key,
+        action: action.clone(),
+        timestamp: current_time,
+    });
+
+    emit!(MultisigProposalExecuted {
+        multisig: multisig_key,
+        proposal: proposal_key,
+        kind: action.kind(),
+        approval_count,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ExecuteEncryptedMultisigProposal,
+        proposal_key,
+        [0u8; 32],
+        [0u8; 32],
+        [0u8; 32],
+        approval_count as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn veto_multisig_proposal(
+    ctx: Context<VetoMultisigProposal>,
+    veto_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    require_proof_len(&veto_proof)?;
+    // pi_a, pi_c (G1) and pi_b (G2) must be canonical points on their curves
+    validate_groth16_points(&veto_proof)?;
+
+    let multisig_key = ctx.accounts.multisig.key();
+    let proposal_key = ctx.accounts.multisig_proposal.key();
+    let multisig = &ctx.accounts.multisig.load()?;
+    let proposal = &mut ctx.accounts.multisig_proposal.load_mut()?;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require_current_multisig_layout(multisig, proposal)?;
+    require!(proposal.is_executed == 0, ErrorCode::ProposalAlreadyExecuted);
+    require!(proposal.vetoed_at == 0, ErrorCode::ProposalVetoed);
+    require!(
+        proposal.approval_count >= multisig.required_approvals(proposal),
+        ErrorCode::InsufficientApprovals
+    );
+
+    let window_end = multisig
+        .veto_window_end(proposal)
+        .ok_or(ErrorCode::NoVetoAuthority)?;
+    require!(current_time < window_end, ErrorCode::VetoWindowClosed);
+
+    let public_signals = [multisig.veto_commitment, compute_veto_tag(&proposal_key)];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::Veto, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::Veto, &public_signals, &veto_proof),
+        ErrorCode::InvalidVetoProof
+    );
+
+    proposal.vetoed_at = current_time;
+
+    debug_log!(
+        "veto_multisig_proposal",
+        proposal = proposal_key,
+        window_end = window_end,
+    );
+
+    emit!(ProposalVetoed {
+        multisig: multisig_key,
+        proposal: proposal_key,
+        approval_count: proposal.approval_count,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::VetoMultisigProposal,
+        proposal_key,
+        [0u8; 32],
+        [0u8; 32],
+        [0u8; 32],
+        proposal.approval_count as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn prove_small_spend(
+    ctx: Context<ProveSmallSpend>,
+    spend_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    require_proof_len(&spend_proof)?;
+
+    let multisig_key = ctx.accounts.multisig.key();
+    let proposal_key = ctx.accounts.multisig_proposal.key();
+    let multisig = &ctx.accounts.multisig.load()?;
+    let proposal = &mut ctx.accounts.multisig_proposal.load_mut()?;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require_current_multisig_layout(multisig, proposal)?;
+    require!(proposal.is_executed == 0, ErrorCode::ProposalAlreadyExecuted);
+    require!(
+        proposal.approval_count == 0 && proposal.is_small_spend == 0,
+        ErrorCode::SmallSpendLocked
+    );
+    require!(
+        multisig.has_spend_limit() && proposal.created_at >= multisig.spend_limit_configured_at,
+        ErrorCode::NoSpendLimit
+    );
+    let (mint, amount) = ctx
+        .accounts
+        .proposal_action
+        .action
+        .spend()
+        .ok_or(ErrorCode::NotSpendAction)?;
+
+    let public_signals = [
+        multisig.spend_limit_commitment,
+        compute_small_spend_tag(&proposal_key, &mint, amount),
+    ];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::SmallSpend, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::SmallSpend, &public_signals, &spend_proof),
+        ErrorCode::InvalidSmallSpendProof
+    );
+
+    proposal.is_small_spend = 1;
+
+    debug_log!("prove_small_spend", proposal = proposal_key);
+
+    emit!(SmallSpendProven {
+        multisig: multisig_key,
+        proposal: proposal_key,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ProveSmallSpend,
+        proposal_key,
+        multisig_key.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        1,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn rotate_signer_key(
+    ctx: Context<RotateSignerKey>,
+    old_commitment: [u8; 32],
+    new_commitment: [u8; 32],
+    rotation_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    require_proof_len(&rotation_proof)?;
+
+    let multisig_key = ctx.accounts.multisig.key();
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+    let (mut multisig, mut signers) = load_with_commitments_mut(&ctx.accounts.multisig)?;
+
+    require!(multisig.version == StealthMultisig::VERSION, ErrorCode::AccountNeedsMigration);
+    require!(new_commitment != [0u8; 32], ErrorCode::InvalidMultisigAction);
+    require!(
+        constant_time_position(&signers, &new_commitment).is_none(),
+        ErrorCode::DuplicateSigner
+    );
+    let slot = constant_time_position(&signers, &old_commitment).ok_or(ErrorCode::SignerNotFound)?;
+
+    let public_signals = [old_commitment, compute_signer_rotation_tag(&multisig_key, &new_commitment)];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::SignerRotation, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::SignerRotation, &public_signals, &rotation_proof),
+        ErrorCode::InvalidSignerProof
+    );
+
+    // Same slot, same threshold: the rest of the set is untouched. The old
+    // key's approvals go stale, as the key may be why it was rotated
+    signers[slot] = new_commitment;
+    multisig.signer_epoch += 1;
+
+    debug_log!("rotate_signer_key", multisig = multisig_key);
+
+    emit!(SignerKeyRotated {
+        multisig: multisig_key,
+        total_signers: multisig.total_signers,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RotateSignerKey,
+        multisig_key,
+        [0u8; 32],
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(vault_id: [u8; 32], threshold: u8, signer_commitments: Vec<[u8; 32]>)]
+pub struct CreateMultisig<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = StealthMultisig::space(signer_commitments.len()),
+        seeds = [MULTISIG_SEED, creator.key().as_ref(), &vault_id],
+        bump
+    )]
+    pub multisig: AccountLoader<'info, StealthMultisig>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(proposal_id: [u8; 32], action: MultisigAction)]
+pub struct CreateMultisigProposal<'info> {
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED, multisig.load()?.creator.as_ref(), &multisig.load()?.vault_id],
+        bump = multisig.load()?.bump
+    )]
+    pub multisig: AccountLoader<'info, StealthMultisig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = MultisigProposal::space(0),
+        seeds = [MULTISIG_PROPOSAL_SEED, multisig.key().as_ref(), &proposal_id],
+        bump
+    )]
+    pub multisig_proposal: AccountLoader<'info, MultisigProposal>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = MultisigProposalAction::space(&action),
+        seeds = [MULTISIG_ACTION_SEED, multisig_proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_action: Account<'info, MultisigProposalAction>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The multisig's watchtower, alerted when it has one
+    #[account(
+        mut,
+        seeds = [WATCHTOWER_SEED, multisig.key().as_ref()],
+        bump = watchtower.bump
+    )]
+    pub watchtower: Option<Account<'info, Watchtower>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(proposal_id: [u8; 32], ciphertext: Vec<u8>, key_commitment: [u8; 32], share_commitments: Vec<[u8; 32]>)]
+pub struct CreateEncryptedMultisigProposal<'info> {
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED, multisig.load()?.creator.as_ref(), &multisig.load()?.vault_id],
+        bump = multisig.load()?.bump
+    )]
+    pub multisig: AccountLoader<'info, StealthMultisig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = MultisigProposal::space(0),
+        seeds = [MULTISIG_PROPOSAL_SEED, multisig.key().as_ref(), &proposal_id],
+        bump
+    )]
+    pub multisig_proposal: AccountLoader<'info, MultisigProposal>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = EncryptedMultisigAction::space(share_commitments.len(), ciphertext.len()),
+        seeds = [MULTISIG_ENCRYPTED_ACTION_SEED, multisig_proposal.key().as_ref()],
+        bump
+    )]
+    pub encrypted_action: Account<'info, EncryptedMultisigAction>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The multisig's watchtower, alerted when it has one
+    #[account(
+        mut,
+        seeds = [WATCHTOWER_SEED, multisig.key().as_ref()],
+        bump = watchtower.bump
+    )]
+    pub watchtower: Option<Account<'info, Watchtower>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteEncryptedMultisigProposal<'info> {
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED, multisig.load()?.creator.as_ref(), &multisig.load()?.vault_id],
+        bump = multisig.load()?.bump
+    )]
+    pub multisig: AccountLoader<'info, StealthMultisig>,
+
+    #[account(
+        mut,
+        seeds = [MULTISIG_PROPOSAL_SEED, multisig.key().as_ref(), &multisig_proposal.load()?.proposal_id],
+        bump = multisig_proposal.load()?.bump,
+        constraint = multisig_proposal.load()?.multisig == multisig.key() @ ErrorCode::Unauthorized
+    )]
+    pub multisig_proposal: AccountLoader<'info, MultisigProposal>,
+
+    #[account(
+        mut,
+        seeds = [MULTISIG_ENCRYPTED_ACTION_SEED, multisig_proposal.key().as_ref()],
+        bump = encrypted_action.bump
+    )]
+    pub encrypted_action: Account<'info, EncryptedMultisigAction>,
+
+    /// Pays rent if a signer change grows the multisig
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StealthSign<'info> {
+    #[account(
+        seeds = [MULTISIG_SEED, multisig.load()?.creator.as_ref(), &multisig.load()?.vault_id],
+        bump = multisig.load()?.bump
+    )]
+    pub multisig: AccountLoader<'info, StealthMultisig>,
+
+    #[account(
+        mut,
+        seeds = [MULTISIG_PROPOSAL_SEED, multisig.key().as_ref(), &multisig_proposal.load()?.proposal_id],
+        bump = multisig_proposal.load()?.bump,
+        constraint = multisig_proposal.load()?.multisig == multisig.key() @ ErrorCode::Unauthorized
+    )]
+    pub multisig_proposal: AccountLoader<'info, MultisigProposal>,
+
+    /// Pays for the approval slot; the proof, not this key, identifies the approver
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevokeStealthSignature<'info> {
+    #[account(
+        seeds = [MULTISIG_SEED, multisig.load()?.creator.as_ref(), &multisig.load()?.vault_id],
+        bump = multisig.load()?.bump
+    )]
+    pub multisig: AccountLoader<'info, StealthMultisig>,
+
+    #[account(
+        mut,
+        seeds = [MULTISIG_PROPOSAL_SEED, multisig.key().as_ref(), &multisig_proposal.load()?.proposal_id],
+        bump = multisig_proposal.load()?.bump,
+        constraint = multisig_proposal.load()?.multisig == multisig.key() @ ErrorCode::Unauthorized
+    )]
+    pub multisig_proposal: AccountLoader<'info, MultisigProposal>,
+
+    /// Fee payer; the proof, not this key, identifies the approver
+    pub signer: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteMultisigProposal<'info> {
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED, multisig.load()?.creator.as_ref(), &multisig.load()?.vault_id],
+        bump = multisig.load()?.bump
+    )]
+    pub multisig: AccountLoader<'info, StealthMultisig>,
+
+    #[account(
+        mut,
+        seeds = [MULTISIG_PROPOSAL_SEED, multisig.key().as_ref(), &multisig_proposal.load()?.proposal_id],
+        bump = multisig_proposal.load()?.bump,
+        constraint = multisig_proposal.load()?.multisig == multisig.key() @ ErrorCode::Unauthorized
+    )]
+    pub multisig_proposal: AccountLoader<'info, MultisigProposal>,
+
+    /// Required for every proposal created with a typed action
+    #[account(
+        seeds = [MULTISIG_ACTION_SEED, multisig_proposal.key().as_ref()],
+        bump = proposal_action.bump
+    )]
+    pub proposal_action: Option<Account<'info, MultisigProposalAction>>,
+
+    /// Pays rent if a signer change grows the multisig
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct VetoMultisigProposal<'info> {
+    #[account(
+        seeds = [MULTISIG_SEED, multisig.load()?.creator.as_ref(), &multisig.load()?.vault_id],
+        bump = multisig.load()?.bump
+    )]
+    pub multisig: AccountLoader<'info, StealthMultisig>,
+
+    #[account(
+        mut,
+        seeds = [MULTISIG_PROPOSAL_SEED, multisig.key().as_ref(), &multisig_proposal.load()?.proposal_id],
+        bump = multisig_proposal.load()?.bump,
+        constraint = multisig_proposal.load()?.multisig == multisig.key() @ ErrorCode::Unauthorized
+    )]
+    pub multisig_proposal: AccountLoader<'info, MultisigProposal>,
+
+    /// Any fee payer; the veto authority is proven, not signed
+    pub submitter: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProveSmallSpend<'info> {
+    #[account(
+        seeds = [MULTISIG_SEED, multisig.load()?.creator.as_ref(), &multisig.load()?.vault_id],
+        bump = multisig.load()?.bump
+    )]
+    pub multisig: AccountLoader<'info, StealthMultisig>,
+
+    #[account(
+        mut,
+        seeds = [MULTISIG_PROPOSAL_SEED, multisig.key().as_ref(), &multisig_proposal.load()?.proposal_id],
+        bump = multisig_proposal.load()?.bump,
+        constraint = multisig_proposal.load()?.multisig == multisig.key() @ ErrorCode::Unauthorized
+    )]
+    pub multisig_proposal: AccountLoader<'info, MultisigProposal>,
+
+    #[account(
+        seeds = [MULTISIG_ACTION_SEED, multisig_proposal.key().as_ref()],
+        bump = proposal_action.bump
+    )]
+    pub proposal_action: Account<'info, MultisigProposalAction>,
+
+    /// Any fee payer; the spend is proven against the hidden limit
+    pub submitter: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RotateSignerKey<'info> {
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED, multisig.load()?.creator.as_ref(), &multisig.load()?.vault_id],
+        bump = multisig.load()?.bump
+    )]
+    pub multisig: AccountLoader<'info, StealthMultisig>,
+
+    /// Any fee payer, so the rotating signer need not link a wallet
+    pub submitter: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+/// 32-byte commitment slots trailing a zero-copy account header
+pub(crate) type CommitmentSlotsMut<'a> = RefMut<'a, [[u8; 32]]>;
+
+/// Check a multisig action's payload against the current signer set
+pub(crate) fn validate_multisig_action(action: &MultisigAction, signers: &[[u8; 32]]) -> Result<()> {
+    match action {
+        MultisigAction::TransferSol { recipient, lamports } => {
+            require!(*lamports > 0, ErrorCode::InvalidMultisigAction);
+            require!(*recipient != Pubkey::default(), ErrorCode::InvalidMultisigAction);
+        }
+        MultisigAction::TransferToken { mint, recipient, amount, .. } => {
+            require!(*amount > 0, ErrorCode::InvalidMultisigAction);
+            require!(*mint != Pubkey::default(), ErrorCode::InvalidMultisigAction);
+            require!(*recipient != Pubkey::default(), ErrorCode::InvalidMultisigAction);
+        }
+        MultisigAction::ChangeSigners { add, remove, new_threshold } => {
+            require!(!add.is_empty() || !remove.is_empty(), ErrorCode::InvalidMultisigAction);
+            compute_signer_change(signers, add, remove, *new_threshold)?;
+        }
+        MultisigAction::ProgramUpgrade { program, buffer, spill } => {
+            require!(program != buffer, ErrorCode::InvalidMultisigAction);
+            require!(
+                *program != Pubkey::default() && *buffer != Pubkey::default() && *spill != Pubkey::default(),
+                ErrorCode::InvalidMultisigAction
+            );
+        }
+        MultisigAction::Custom { program_id, instruction_hash } => {
+            require!(*program_id != Pubkey::default(), ErrorCode::InvalidMultisigAction);
+            require!(*instruction_hash != [0u8; 32], ErrorCode::InvalidMultisigAction);
+        }
+        MultisigAction::SetVeto { veto_commitment, veto_window } => {
+            // A veto authority needs a window to act in; clearing it needs none
+            if *veto_commitment == [0u8; 32] {
+                require!(*veto_window == 0, ErrorCode::InvalidMultisigAction);
+            } else {
+                require!(
+                    *veto_window > 0 && *veto_window <= MAX_VETO_WINDOW,
+                    ErrorCode::InvalidMultisigAction
+                );
+            }
+        }
+        MultisigAction::SetTimelock { seconds } => {
+            require!(
+                (0..=MAX_MULTISIG_TIMELOCK).contains(seconds),
+                ErrorCode::InvalidMultisigAction
+            );
+        }
+        MultisigAction::PoolAdmin { pool, instruction_data } => {
+            require!(*pool != Pubkey::default(), ErrorCode::InvalidMultisigAction);
+            require!(
+                instruction_data.len() <= MAX_POOL_ADMIN_DATA_LEN &&
+                    is_pool_admin_instruction(instruction_data),
+                ErrorCode::InvalidMultisigAction
+            );
+        }
+        // A zero commitment clears the policy
+        MultisigAction::SetSpendLimit { .. } => {}
+        MultisigAction::SetLiveness { period, degrade_timelock } => {
+            if *period == 0 {
+                require!(*degrade_timelock == 0, ErrorCode::InvalidMultisigAction);
+            } else {
+                require!(
+                    (MIN_LIVENESS_PERIOD..=MAX_LIVENESS_PERIOD).contains(period)
+                        && (1..=MAX_MULTISIG_TIMELOCK).contains(degrade_timelock),
+                    ErrorCode::InvalidMultisigAction
+                );
+            }
+        }
+        MultisigAction::Batch { steps } => {
+            require!(
+                (1..=MAX_MULTISIG_BATCH_STEPS).contains(&steps.len()),
+                ErrorCode::InvalidMultisigAction
+            );
+            for step in steps {
+                let MultisigStep::PoolAdmin { account_count, .. } = step else {
+                    return err!(ErrorCode::BatchStepNotExecutable);
+                };
+                require!(*account_count > 0, ErrorCode::InvalidMultisigAction);
+                validate_multisig_action(&step.action(), signers)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether `instruction_data` calls one of this program's pool-admin
+/// instructions, the only ones a `PoolAdmin` action may invoke
+pub(crate) fn is_pool_admin_instruction(instruction_data: &[u8]) -> bool {
+    const POOL_ADMIN_INSTRUCTIONS: [&[u8]; 12] = [
+        instruction::SetPoolAuthority::DISCRIMINATOR,
+        instruction::PublishReserveProof::DISCRIMINATOR,
+        instruction::CreateRelayerRegistry::DISCRIMINATOR,
+        instruction::ConfigureRelayerSolvency::DISCRIMINATOR,
+        instruction::EnableDepositReceipts::DISCRIMINATOR,
+        instruction::ConfigureRewardCurve::DISCRIMINATOR,
+        instruction::ConfigureEarlyExit::DISCRIMINATOR,
+        instruction::CreateReferralProgram::DISCRIMINATOR,
+        instruction::ConfigureNoteExpiry::DISCRIMINATOR,
+        instruction::AnnounceNoteSweep::DISCRIMINATOR,
+        instruction::CancelNoteSweep::DISCRIMINATOR,
+        instruction::ExecuteNoteSweep::DISCRIMINATOR,
+    ];
+    POOL_ADMIN_INSTRUCTIONS
+        .iter()
+        .any(|discriminator| instruction_data.starts_with(discriminator))
+}
+
+/// Invoke a pool-admin instruction as the multisig's authority PDA
+/// Every pool-admin context takes the pool first, so the approved pool
+/// must lead the accounts
+pub(crate) fn invoke_pool_admin<'info>(
+    multisig: &Pubkey,
+    pool: &Pubkey,
+    instruction_data: &[u8],
+    accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    require!(
+        accounts.first().is_some_and(|account| account.key == pool),
+        ErrorCode::InvalidMultisigAction
+    );
+
+    let (authority, bump) = multisig_authority_address(multisig);
+    let metas = accounts
+        .iter()
+        .map(|account| {
+            let is_signer = account.is_signer || account.key() == authority;
+            if account.is_writable {
+                AccountMeta::new(account.key(), is_signer)
+            } else {
+                AccountMeta::new_readonly(account.key(), is_signer)
+            }
+        })
+        .collect();
+    let ix = Instruction {
+        program_id: crate::ID,
+        accounts: metas,
+        data: instruction_data.to_vec(),
+    };
+    invoke_signed(&ix, accounts, &[&[MULTISIG_AUTHORITY_SEED, multisig.as_ref(), &[bump]]])?;
+    Ok(())
+}
+
+/// Apply the parts of an executed action that change multisig state, and
+/// invoke pool-admin instructions. Transfers, upgrades and custom
+/// instructions are carried out by the client against the approved payload
+pub(crate) fn apply_multisig_action<'info>(
+    multisig: &AccountLoader<'info, StealthMultisig>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    remaining_accounts: &[AccountInfo<'info>],
+    action: &MultisigAction,
+    now: i64,
+) -> Result<()> {
+    match action {
+        MultisigAction::ChangeSigners { add, remove, new_threshold } => {
+            apply_signer_change(multisig, payer, system_program, add, remove, *new_threshold)
+        }
+        MultisigAction::SetVeto { veto_commitment, veto_window } => {
+            let multisig = &mut multisig.load_mut()?;
+            multisig.veto_commitment = *veto_commitment;
+            multisig.veto_window = *veto_window;
+            multisig.veto_configured_at = now;
+            Ok(())
+        }
+        MultisigAction::SetTimelock { seconds } => {
+            // Applies to proposals whose final approval lands from now on
+            multisig.load_mut()?.timelock = *seconds;
+            Ok(())
+        }
+        MultisigAction::SetSpendLimit { limit_commitment } => {
+            // Pending small spends proven under the old policy need the
+            // full threshold again (see `required_approvals`)
+            let multisig = &mut multisig.load_mut()?;
+            multisig.spend_limit_commitment = *limit_commitment;
+            multisig.spend_limit_configured_at = now;
+            Ok(())
+        }
+        MultisigAction::SetLiveness { period, degrade_timelock } => {
+            // Epochs restart, and a pending degradation is dropped
+            let multisig = &mut multisig.load_mut()?;
+            multisig.liveness_period = *period;
+            multisig.degrade_timelock = *degrade_timelock;
+            multisig.liveness_configured_at = now;
+            multisig.degrade_announced_at = 0;
+            Ok(())
+        }
+        MultisigAction::PoolAdmin { pool, instruction_data } => {
+            invoke_pool_admin(&multisig.key(), pool, instruction_data, remaining_accounts)
+        }
+        MultisigAction::Batch { steps } => {
+            // Steps run in order in one transaction, so a failing step
+            // reverts the ones before it
+            require!(
+                steps.iter().map(MultisigStep::account_count).sum::<usize>() == remaining_accounts.len(),
+                ErrorCode::InvalidMultisigAction
+            );
+            let mut accounts = remaining_accounts;
+            for step in steps {
+                let (step_accounts, rest) = accounts.split_at(step.account_count());
+                // Batches created before client-side steps were rejected
+                // may still hold them; they cannot run all-or-nothing
+                let MultisigStep::PoolAdmin { pool, instruction_data, .. } = step else {
+                    return err!(ErrorCode::BatchStepNotExecutable);
+                };
+                invoke_pool_admin(&multisig.key(), pool, instruction_data, step_accounts)?;
+                accounts = rest;
+            }
+            Ok(())
+        }
+        MultisigAction::TransferSol { .. }
+        | MultisigAction::TransferToken { .. }
+        | MultisigAction::ProgramUpgrade { .. }
+        | MultisigAction::Custom { .. } => Ok(()),
+    }
+}
+
+/// Veto state lives in header fields older layouts read from commitment
+/// slots, so both accounts must be migrated before it is trusted
+pub(crate) fn require_current_multisig_layout(multisig: &StealthMultisig, proposal: &MultisigProposal) -> Result<()> {
+    require!(
+        multisig.version == StealthMultisig::VERSION && proposal.version == MultisigProposal::VERSION,
+        ErrorCode::AccountNeedsMigration
+    );
+    Ok(())
+}
+
+/// Reject a vetoed proposal, or one whose veto window is still open
+pub(crate) fn require_veto_window_elapsed(multisig: &StealthMultisig, proposal: &MultisigProposal, now: i64) -> Result<()> {
+    require_current_multisig_layout(multisig, proposal)?;
+    require!(proposal.vetoed_at == 0, ErrorCode::ProposalVetoed);
+    if let Some(window_end) = multisig.veto_window_end(proposal) {
+        require!(now >= window_end, ErrorCode::VetoWindowOpen);
+    }
+    Ok(())
+}
+
+/// Drop approvals given before the multisig's signer set last changed, so
+/// the proposal collects them again from the current set
+pub(crate) fn reset_stale_approvals(
+    multisig: &StealthMultisig,
+    proposal: &mut MultisigProposal,
+    approvals: &mut [[u8; 32]],
+) {
+    if proposal.signer_epoch != multisig.signer_epoch {
+        approvals.fill([0u8; 32]);
+        proposal.approval_count = 0;
+        proposal.threshold_reached_at = 0;
+        proposal.executable_after = 0;
+        proposal.signer_epoch = multisig.signer_epoch;
+    }
+}
+
+/// Reject executing on approvals given under an earlier signer set
+pub(crate) fn require_current_signers(multisig: &StealthMultisig, proposal: &MultisigProposal) -> Result<()> {
+    require!(
+        proposal.signer_epoch == multisig.signer_epoch,
+        ErrorCode::SignerSetChanged
+    );
+    Ok(())
+}
+
+/// The signer set after removing `remove` and appending `add`
+pub(crate) fn compute_signer_change(
+    signers: &[[u8; 32]],
+    add: &[[u8; 32]],
+    remove: &[[u8; 32]],
+    new_threshold: u8,
+) -> Result<Vec<[u8; 32]>> {
+    let mut updated = signers.to_vec();
+    for commitment in remove {
+        let position = constant_time_position(&updated, commitment).ok_or(ErrorCode::SignerNotFound)?;
+        updated.swap_remove(position);
+    }
+    for commitment in add {
+        require!(*commitment != [0u8; 32], ErrorCode::InvalidMultisigAction);
+        require!(constant_time_position(&updated, commitment).is_none(), ErrorCode::DuplicateSigner);
+        updated.push(*commitment);
+    }
+
+    require!(updated.len() <= MAX_MULTISIG_SIGNERS, ErrorCode::TooManySigners);
+    require!(new_threshold > 0, ErrorCode::InvalidThreshold);
+    require!(updated.len() >= new_threshold as usize, ErrorCode::InvalidThreshold);
+
+    Ok(updated)
+}
+
+/// Rewrite a multisig's signer slots, resizing the account to fit
+pub(crate) fn apply_signer_change<'info>(
+    multisig: &AccountLoader<'info, StealthMultisig>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    add: &[[u8; 32]],
+    remove: &[[u8; 32]],
+    new_threshold: u8,
+) -> Result<()> {
+    // Re-validated: another proposal may have changed the set since creation
+    let updated = {
+        let (_, signers) = load_with_commitments_mut(multisig)?;
+        compute_signer_change(&signers, add, remove, new_threshold)?
+    };
+
+    resize_account(
+        multisig.as_ref(),
+        payer.as_ref(),
+        system_program.as_ref(),
+        StealthMultisig::space(updated.len()),
+    )?;
+
+    let (mut header, mut signers) = load_with_commitments_mut(multisig)?;
+    signers.copy_from_slice(&updated);
+    header.total_signers = updated.len() as u8;
+    header.threshold = new_threshold;
+    header.signer_epoch += 1;
+    // The signers have acted; a degradation announced before is moot
+    header.degrade_announced_at = 0;
+
+    Ok(())
+}
+
+/// Borrow a zero-copy header together with the 32-byte commitment slots that
+/// trail it, like `AccountLoader::load_mut`. The loader has checked the
+/// owner, but the data may have changed since, so the discriminator is
+/// checked again before the cast; `space()` keeps the tail a whole number
+/// of slots.
+pub(crate) fn load_with_commitments_mut<'a, T: ZeroCopy + Owner>(
+    loader: &'a AccountLoader<T>,
+) -> Result<(RefMut<'a, T>, CommitmentSlotsMut<'a>)> {
+    let data = loader.as_ref().try_borrow_mut_data()?;
+    require!(
+        data.len() >= T::DISCRIMINATOR.len() && data[..T::DISCRIMINATOR.len()] == *T::DISCRIMINATOR,
+        anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+    );
+    split_commitments_mut(data)
+}
+
+/// `load_with_commitments_mut` for an account `init` just created, like
+/// `AccountLoader::load_init`: the discriminator must still be unset, and
+/// Anchor writes it when the instruction exits
+pub(crate) fn load_init_with_commitments_mut<'a, T: ZeroCopy + Owner>(
+    loader: &'a AccountLoader<T>,
+) -> Result<(RefMut<'a, T>, CommitmentSlotsMut<'a>)> {
+    require!(loader.as_ref().is_writable, anchor_lang::error::ErrorCode::AccountNotMutable);
+    let data = loader.as_ref().try_borrow_mut_data()?;
+    require!(
+        data.len() >= T::DISCRIMINATOR.len() && data[..T::DISCRIMINATOR.len()].iter().all(|b| *b == 0),
+        anchor_lang::error::ErrorCode::AccountDiscriminatorAlreadySet
+    );
+    split_commitments_mut(data)
+}
+
+fn split_commitments_mut<'a, T: ZeroCopy>(data: RefMut<'a, &mut [u8]>) -> Result<(RefMut<'a, T>, CommitmentSlotsMut<'a>)> {
+    let header_len = T::DISCRIMINATOR.len() + std::mem::size_of::<T>();
+    require!(
+        data.len() >= header_len && (data.len() - header_len).is_multiple_of(32),
+        ErrorCode::InvalidAccountLayout
+    );
+
+    Ok(RefMut::map_split(data, |data| {
+        let (header, tail) = data.split_at_mut(header_len);
+        (
+            bytemuck::from_bytes_mut(&mut header[T::DISCRIMINATOR.len()..]),
+            bytemuck::cast_slice_mut(tail),
+        )
+    }))
+}
+
+// ============================================
+// SIGNER LIVENESS - Heartbeats and Threshold Degradation
+// ============================================
+//
+// A multisig can opt in to liveness tracking with a `SetLiveness` action.
+// Time is cut into epochs of `liveness_period` seconds from when tracking
+// was configured, and each signer posts one anonymous heartbeat per epoch:
+// a proof of membership with a per-epoch nullifier, so signers are counted
+// without being identified. If a whole epoch passes with fewer heartbeats
+// than the threshold, anyone can announce a degradation; unless quorum
+// heartbeats return within `degrade_timelock`, the threshold then drops by
+// one. This keeps a vault whose signers lost their keys from being bricked
+// forever, one step at a time.
+
+pub(crate) fn post_signer_heartbeat(
+    ctx: Context<PostSignerHeartbeat>,
+    epoch: u64,
+    heartbeat_nullifier: [u8; 32],
+    heartbeat_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    require_proof_len(&heartbeat_proof)?;
+
+    let multisig_key = ctx.accounts.multisig.key();
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+    let (mut multisig, signers) = load_with_commitments_mut(&ctx.accounts.multisig)?;
+
+    require!(multisig.version == StealthMultisig::VERSION, ErrorCode::AccountNeedsMigration);
+    require!(multisig.tracks_liveness(), ErrorCode::LivenessNotTracked);
+    require!(epoch == multisig.heartbeat_epoch(current_time), ErrorCode::InvalidHeartbeatEpoch);
+
+    let heartbeats = &mut ctx.accounts.signer_heartbeats;
+    require!(
+        constant_time_position(&heartbeats.nullifiers, &heartbeat_nullifier).is_none(),
+        ErrorCode::DuplicateHeartbeat
+    );
+    // Sized for the signer set when the epoch's account was created
+    require!(
+        heartbeats.nullifiers.len() < multisig.total_signers as usize
+            && SignerHeartbeats::space(heartbeats.nullifiers.len() + 1) <= heartbeats.to_account_info().data_len(),
+        ErrorCode::HeartbeatsFull
+    );
+
+    let public_signals = [heartbeat_nullifier, compute_heartbeat_tag(&multisig_key, epoch, &signers)];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::SignerHeartbeat, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::SignerHeartbeat, &public_signals, &heartbeat_proof),
+        ErrorCode::InvalidHeartbeatProof
+    );
+
+    if heartbeats.multisig == Pubkey::default() {
+        heartbeats.multisig = multisig_key;
+        heartbeats.epoch = epoch;
+        heartbeats.bump = ctx.bumps.signer_heartbeats;
+        heartbeats.version = SignerHeartbeats::VERSION;
+    }
+    heartbeats.nullifiers.push(heartbeat_nullifier);
+    let live_signers = heartbeats.live_signers();
+
+    // Quorum is back: a pending degradation is cancelled
+    let cancelled = multisig.degrade_announced_at != 0 && live_signers >= multisig.threshold;
+    if cancelled {
+        multisig.degrade_announced_at = 0;
+    }
+
+    debug_log!("post_signer_heartbeat", multisig = multisig_key, epoch = epoch, live = live_signers);
+
+    emit!(SignerHeartbeatPosted {
+        multisig: multisig_key,
+        epoch,
+        live_signers,
+        degradation_cancelled: cancelled,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::PostSignerHeartbeat,
+        multisig_key,
+        heartbeat_nullifier,
+        [0u8; 32],
+        [0u8; 32],
+        epoch,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn announce_threshold_degradation(ctx: Context<AnnounceThresholdDegradation>) -> Result<()> {
+    let multisig_key = ctx.accounts.multisig.key();
+    let multisig = &mut ctx.accounts.multisig.load_mut()?;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(multisig.version == StealthMultisig::VERSION, ErrorCode::AccountNeedsMigration);
+    require!(multisig.tracks_liveness(), ErrorCode::LivenessNotTracked);
+    require!(multisig.degrade_announced_at == 0, ErrorCode::DegradationPending);
+    require!(multisig.threshold > 1, ErrorCode::InvalidThreshold);
+
+    // The last complete epoch and the current one both lack quorum
+    let epoch = multisig.heartbeat_epoch(current_time);
+    require!(epoch > 0, ErrorCode::SignersLive);
+    let previous = heartbeat_count(&ctx.accounts.previous_heartbeats, &multisig_key, epoch - 1)?;
+    let current = heartbeat_count(&ctx.accounts.current_heartbeats, &multisig_key, epoch)?;
+    require!(
+        previous < multisig.threshold && current < multisig.threshold,
+        ErrorCode::SignersLive
+    );
+
+    multisig.degrade_announced_at = current_time;
+    let executable_at = current_time.saturating_add(multisig.degrade_timelock);
+
+    debug_log!("announce_threshold_degradation", multisig = multisig_key, executable_at = executable_at);
+
+    emit!(ThresholdDegradationAnnounced {
+        multisig: multisig_key,
+        epoch: epoch - 1,
+        live_signers: previous,
+        threshold: multisig.threshold,
+        executable_at,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::AnnounceThresholdDegradation,
+        multisig_key,
+        [0u8; 32],
+        [0u8; 32],
+        [0u8; 32],
+        executable_at as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn execute_threshold_degradation(ctx: Context<ExecuteThresholdDegradation>) -> Result<()> {
+    let multisig_key = ctx.accounts.multisig.key();
+    let multisig = &mut ctx.accounts.multisig.load_mut()?;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(multisig.version == StealthMultisig::VERSION, ErrorCode::AccountNeedsMigration);
+    require!(multisig.degrade_announced_at != 0, ErrorCode::NoDegradationPending);
+    require!(
+        current_time >= multisig.degrade_announced_at.saturating_add(multisig.degrade_timelock),
+        ErrorCode::TimelockNotElapsed
+    );
+
+    let old_threshold = multisig.threshold;
+    multisig.threshold = old_threshold.saturating_sub(1).max(1);
+    multisig.degrade_announced_at = 0;
+
+    debug_log!("execute_threshold_degradation", multisig = multisig_key, threshold = multisig.threshold);
+
+    emit!(ThresholdDegraded {
+        multisig: multisig_key,
+        old_threshold,
+        new_threshold: multisig.threshold,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ExecuteThresholdDegradation,
+        multisig_key,
+        [0u8; 32],
+        [0u8; 32],
+        [0u8; 32],
+        multisig.threshold as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct PostSignerHeartbeat<'info> {
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED, multisig.load()?.creator.as_ref(), &multisig.load()?.vault_id],
+        bump = multisig.load()?.bump
+    )]
+    pub multisig: AccountLoader<'info, StealthMultisig>,
+
+    #[account(
+        init_if_needed,
+        payer = submitter,
+        space = SignerHeartbeats::space(multisig.load()?.total_signers as usize),
+        seeds = [SIGNER_HEARTBEATS_SEED, multisig.key().as_ref(), &epoch.to_le_bytes()],
+        bump
+    )]
+    pub signer_heartbeats: Account<'info, SignerHeartbeats>,
+
+    /// Any fee payer; the heartbeat is proven, not signed
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AnnounceThresholdDegradation<'info> {
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED, multisig.load()?.creator.as_ref(), &multisig.load()?.vault_id],
+        bump = multisig.load()?.bump
+    )]
+    pub multisig: AccountLoader<'info, StealthMultisig>,
+
+    /// CHECK: Heartbeats of the last complete epoch, checked by
+    /// `heartbeat_count`; may not exist
+    pub previous_heartbeats: UncheckedAccount<'info>,
+
+    /// CHECK: Heartbeats of the current epoch, checked by
+    /// `heartbeat_count`; may not exist
+    pub current_heartbeats: UncheckedAccount<'info>,
+
+    /// Anyone can announce
+    pub submitter: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteThresholdDegradation<'info> {
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED, multisig.load()?.creator.as_ref(), &multisig.load()?.vault_id],
+        bump = multisig.load()?.bump
+    )]
+    pub multisig: AccountLoader<'info, StealthMultisig>,
+
+    /// Anyone can execute
+    pub submitter: Signer<'info>,
+}
+
+/// Heartbeats posted for `multisig` in `epoch`; an account never created
+/// (no heartbeat that epoch) counts as none
+pub(crate) fn heartbeat_count(account: &AccountInfo, multisig: &Pubkey, epoch: u64) -> Result<u8> {
+    require_keys_eq!(
+        account.key(),
+        signer_heartbeats_address(multisig, epoch).0,
+        ErrorCode::InvalidHeartbeatEpoch
+    );
+    if account.data_is_empty() {
+        return Ok(0);
+    }
+    require_keys_eq!(*account.owner, crate::ID, ErrorCode::InvalidHeartbeatEpoch);
+    let heartbeats = SignerHeartbeats::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    Ok(heartbeats.live_signers())
+}
+
+// ============================================
+// TREASURY ATTESTATIONS - Private Holdings Bands
+// ============================================
+//
+// A multisig whose treasury sits in shielded notes owned by its authority
+// PDA can show stakeholders roughly what it holds without disclosing the
+// figure: a range proof over the unspent treasury notes shows their total
+// lies inside a publicly stated band. Like reserve proofs, each
+// attestation is recorded against the pool state it was made for. Only
+// holders of the treasury note secrets can produce the proof, so the
+// submitter need not be a known signer.
+
+pub(crate) fn publish_treasury_attestation(
+    ctx: Context<PublishTreasuryAttestation>,
+    min_balance: u64,
+    max_balance: u64,
+    band_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    require_proof_len(&band_proof)?;
+    require!(
+        min_balance <= max_balance && max_balance > 0,
+        ErrorCode::InvalidTreasuryBand
+    );
+
+    let multisig_key = ctx.accounts.multisig.key();
+    let pool = &ctx.accounts.shielded_pool;
+    let clock = clock::now(ctx.remaining_accounts)?;
+
+    let public_signals = [
+        compute_treasury_band_tag(&multisig_key, &pool.key(), min_balance, max_balance),
+        pool.spend_anchor(),
+        encode_u64_signal(pool.nullifier_count as u64),
+    ];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::TreasuryBand, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::TreasuryBand, &public_signals, &band_proof),
+        ErrorCode::InvalidTreasuryProof
+    );
+
+    let attestation = &mut ctx.accounts.treasury_attestation;
+    attestation.multisig = multisig_key;
+    attestation.pool = pool.key();
+    attestation.merkle_root = pool.merkle_root;
+    attestation.nullifier_count = pool.nullifier_count;
+    attestation.min_balance = min_balance;
+    attestation.max_balance = max_balance;
+    attestation.proof_hash = compute_proof_hash(&band_proof, &public_signals);
+    attestation.published_by = ctx.accounts.publisher.key();
+    attestation.published_at = clock.unix_timestamp;
+    attestation.published_slot = clock.slot;
+    attestation.bump = ctx.bumps.treasury_attestation;
+    attestation.version = TreasuryAttestation::VERSION;
+
+    debug_log!(
+        "publish_treasury_attestation",
+        multisig = multisig_key,
+        min_balance = min_balance,
+        max_balance = max_balance,
+    );
+
+    emit!(TreasuryAttestationPublished {
+        multisig: multisig_key,
+        attestation: attestation.key(),
+        pool: pool.key(),
+        merkle_root: pool.merkle_root,
+        min_balance,
+        max_balance,
+        proof_hash: attestation.proof_hash,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::PublishTreasuryAttestation,
+        attestation.key(),
+        attestation.proof_hash,
+        multisig_key.to_bytes(),
+        pool.merkle_root,
+        max_balance,
+        clock.unix_timestamp,
+    ));
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PublishTreasuryAttestation<'info> {
+    #[account(
+        seeds = [MULTISIG_SEED, multisig.load()?.creator.as_ref(), &multisig.load()?.vault_id],
+        bump = multisig.load()?.bump
+    )]
+    pub multisig: AccountLoader<'info, StealthMultisig>,
+
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    /// One attestation per multisig and pool state (root and nullifier count)
+    #[account(
+        init,
+        payer = publisher,
+        space = TreasuryAttestation::LEN,
+        seeds = [
+            TREASURY_ATTESTATION_SEED,
+            multisig.key().as_ref(),
+            shielded_pool.key().as_ref(),
+            &shielded_pool.merkle_root,
+            &shielded_pool.nullifier_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub treasury_attestation: Account<'info, TreasuryAttestation>,
+
+    /// Any fee payer; the treasury is proven, not signed for
+    #[account(mut)]
+    pub publisher: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::test_support::*;
+
+    fn multisig(threshold: u8) -> StealthMultisig {
+        let mut multisig: StealthMultisig = bytemuck::Zeroable::zeroed();
+        multisig.threshold = threshold;
+        multisig.total_signers = 3;
+        multisig
+    }
+
+    /// A proposal created under `multisig`'s current signers that reached
+    /// its threshold with `approvals`
+    fn approved_proposal(multisig: &StealthMultisig, approvals: &[[u8; 32]]) -> MultisigProposal {
+        let mut proposal: MultisigProposal = bytemuck::Zeroable::zeroed();
+        proposal.signer_epoch = multisig.signer_epoch;
+        proposal.approval_count = approvals.len() as u8;
+        proposal.threshold_reached_at = 1_700_000_000;
+        proposal.executable_after = 1_700_000_000;
+        proposal
+    }
+
+    #[test]
+    fn approvals_from_before_a_signer_change_do_not_execute() {
+        let mut multisig = multisig(2);
+        let mut approvals = [[1u8; 32], [2u8; 32]];
+        let mut proposal = approved_proposal(&multisig, &approvals);
+        assert!(require_current_signers(&multisig, &proposal).is_ok());
+
+        // e.g. an executed signer change removed the signer behind [1; 32]
+        multisig.signer_epoch += 1;
+        assert_error(require_current_signers(&multisig, &proposal), ErrorCode::SignerSetChanged);
+
+        // The next approval starts the count over under the new set
+        reset_stale_approvals(&multisig, &mut proposal, &mut approvals);
+        assert_eq!(proposal.approval_count, 0);
+        assert_eq!(proposal.threshold_reached_at, 0);
+        assert_eq!(proposal.executable_after, 0);
+        assert_eq!(approvals, [[0u8; 32]; 2]);
+        assert!(require_current_signers(&multisig, &proposal).is_ok());
+        assert!(proposal.approval_count < multisig.required_approvals(&proposal));
+    }
+
+    #[test]
+    fn approvals_under_the_current_signers_are_kept() {
+        let multisig = multisig(2);
+        let mut approvals = [[1u8; 32]];
+        let mut proposal = approved_proposal(&multisig, &approvals);
+
+        reset_stale_approvals(&multisig, &mut proposal, &mut approvals);
+        assert_eq!(proposal.approval_count, 1);
+        assert_eq!(approvals, [[1u8; 32]]);
+    }
+
+    fn pool_admin_step() -> MultisigStep {
+        MultisigStep::PoolAdmin {
+            pool: Pubkey::new_unique(),
+            instruction_data: [instruction::ConfigureEarlyExit::DISCRIMINATOR, &[0u8; 2]].concat(),
+            account_count: 2,
+        }
+    }
+
+    #[test]
+    fn batches_reject_steps_the_program_cannot_execute() {
+        let signers = [[1u8; 32], [2u8; 32]];
+        let client_side = [
+            MultisigStep::TransferSol {
+                recipient: Pubkey::new_unique(),
+                lamports: 1_000,
+            },
+            MultisigStep::TransferToken {
+                mint: Pubkey::new_unique(),
+                recipient: Pubkey::new_unique(),
+                amount: 1_000,
+                decimals: 6,
+            },
+            MultisigStep::Custom {
+                program_id: Pubkey::new_unique(),
+                instruction_hash: [3u8; 32],
+            },
+        ];
+        for step in client_side {
+            let batch = MultisigAction::Batch {
+                steps: vec![pool_admin_step(), step.clone()],
+            };
+            assert_error(validate_multisig_action(&batch, &signers), ErrorCode::BatchStepNotExecutable);
+
+            // The same action stays valid on its own, for the client to carry out
+            assert!(validate_multisig_action(&step.action(), &signers).is_ok());
+        }
+
+        let batch = MultisigAction::Batch {
+            steps: vec![pool_admin_step(), pool_admin_step()],
+        };
+        assert!(validate_multisig_action(&batch, &signers).is_ok());
+    }
+}
@@ -0,0 +1,388 @@
+//! Referral rewards
+
+use crate::*;
+
+// ============================================
+// REFERRALS - Anonymity-Set Incentives
+// ============================================
+//
+// A pool authority funds a referral program that pays a fixed reward per
+// referred deposit. Referrers register a commitment to a secret, and
+// depositors credit one by passing its referral record to
+// `shield_deposit`. Only deposit counts are recorded, never amounts, and
+// rewards are claimed with a proof of the secret to any recipient.
+//
+// Deposits themselves move no lamports, so a referred deposit must also
+// move the program's `min_deposit_lamports` into the pool vault; otherwise
+// anyone could refer themselves with empty deposits and drain the rewards.
+// Each referrer is credited with at most `max_deposits_per_referrer`.
+
+pub(crate) fn create_referral_program(
+    ctx: Context<CreateReferralProgram>,
+    reward_per_deposit: u64,
+    min_deposit_lamports: u64,
+    max_deposits_per_referrer: u64,
+    funding_lamports: u64,
+) -> Result<()> {
+    let program = &mut ctx.accounts.referral_program;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(reward_per_deposit > 0, ErrorCode::InvalidReferralProgram);
+    require!(
+        min_deposit_lamports > 0 && max_deposits_per_referrer > 0,
+        ErrorCode::InvalidReferralProgram
+    );
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: program.to_account_info(),
+            },
+        ),
+        funding_lamports,
+    )?;
+
+    program.pool = ctx.accounts.shielded_pool.key();
+    program.authority = ctx.accounts.authority.key();
+    program.reward_per_deposit = reward_per_deposit;
+    program.min_deposit_lamports = min_deposit_lamports;
+    program.max_deposits_per_referrer = max_deposits_per_referrer;
+    program.referrers = 0;
+    program.rewarded_deposits = 0;
+    program.created_at = current_time;
+    program.bump = ctx.bumps.referral_program;
+    program.version = ReferralProgram::VERSION;
+
+    emit!(ReferralProgramCreated {
+        referral_program: program.key(),
+        pool: program.pool,
+        reward_per_deposit,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateReferralProgram,
+        program.key(),
+        program.pool.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        reward_per_deposit,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn configure_referral_program(
+    ctx: Context<ConfigureReferralProgram>,
+    min_deposit_lamports: u64,
+    max_deposits_per_referrer: u64,
+) -> Result<()> {
+    require!(
+        min_deposit_lamports > 0 && max_deposits_per_referrer > 0,
+        ErrorCode::InvalidReferralProgram
+    );
+
+    let program = &mut ctx.accounts.referral_program;
+    program.min_deposit_lamports = min_deposit_lamports;
+    program.max_deposits_per_referrer = max_deposits_per_referrer;
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ConfigureReferralProgram,
+        program.key(),
+        program.pool.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        min_deposit_lamports,
+        clock::now(ctx.remaining_accounts)?.unix_timestamp,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn fund_referral_program(ctx: Context<FundReferralProgram>, amount: u64) -> Result<()> {
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.sponsor.to_account_info(),
+                to: ctx.accounts.referral_program.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::FundReferralProgram,
+        ctx.accounts.referral_program.key(),
+        ctx.accounts.referral_program.pool.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        amount,
+        clock::now(ctx.remaining_accounts)?.unix_timestamp,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn register_referrer(ctx: Context<RegisterReferrer>, referral_commitment: [u8; 32]) -> Result<()> {
+    let referral = &mut ctx.accounts.referral;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(referral_commitment != [0u8; 32], ErrorCode::InvalidReferralProgram);
+
+    referral.pool = ctx.accounts.referral_program.pool;
+    referral.referral_commitment = referral_commitment;
+    referral.deposit_count = 0;
+    referral.claimed_count = 0;
+    referral.created_at = current_time;
+    referral.bump = ctx.bumps.referral;
+    referral.version = ReferralRecord::VERSION;
+    ctx.accounts.referral_program.referrers += 1;
+
+    emit!(ReferrerRegistered {
+        referral: referral.key(),
+        pool: referral.pool,
+        referral_commitment,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RegisterReferrer,
+        referral.key(),
+        referral_commitment,
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn claim_referral_rewards(
+    ctx: Context<ClaimReferralRewards>,
+    referral_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    require_proof_len(&referral_proof)?;
+
+    let program = &mut ctx.accounts.referral_program;
+    let referral = &mut ctx.accounts.referral;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    let unclaimed = referral.deposit_count - referral.claimed_count;
+    require!(unclaimed > 0, ErrorCode::NoReferralRewards);
+
+    let claim_tag = compute_referral_claim_tag(
+        &referral.key(),
+        &ctx.accounts.recipient.key(),
+        referral.claimed_count,
+    );
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::ReferralClaim, &circuit_id)?;
+    require!(
+        verify_groth16_stub(
+            Circuit::ReferralClaim,
+            &[referral.referral_commitment, claim_tag],
+            &referral_proof
+        ),
+        ErrorCode::InvalidReferralProof
+    );
+
+    let program_info = program.to_account_info();
+    let rent_floor = Rent::get()?.minimum_balance(program_info.data_len());
+    let available = program_info.lamports().saturating_sub(rent_floor);
+    let deposits = unclaimed.min(available / program.reward_per_deposit);
+    require!(deposits > 0, ErrorCode::ReferralProgramUnderfunded);
+
+    let lamports = deposits * program.reward_per_deposit;
+    program_info.sub_lamports(lamports)?;
+    ctx.accounts.recipient.add_lamports(lamports)?;
+
+    referral.claimed_count += deposits;
+    program.rewarded_deposits += deposits;
+
+    debug_log!(
+        "claim_referral_rewards",
+        referral = referral.key(),
+        deposits = deposits,
+        lamports = lamports,
+    );
+
+    emit!(ReferralRewardsClaimed {
+        referral: referral.key(),
+        pool: referral.pool,
+        deposits,
+        lamports,
+        timestamp: current_time,
+        // Note: Recipient is NEVER included - it is unlinkable to the referrer
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ClaimReferralRewards,
+        referral.key(),
+        referral.referral_commitment,
+        [0u8; 32],
+        [0u8; 32],
+        lamports,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+/// Credit `referral` with up to `deposits` referred deposits, moving the
+/// program's `min_deposit_lamports` per credited deposit from `depositor`
+/// into the pool vault. Credit stops at the per-referrer cap; deposits past
+/// it (or under a program without a minimum) still go through, uncredited
+pub(crate) fn credit_referral<'info>(
+    referral: &mut ReferralRecord,
+    program: Option<&ReferralProgram>,
+    pool_vault: Option<AccountInfo<'info>>,
+    depositor: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    deposits: u64,
+) -> Result<()> {
+    let (Some(program), Some(pool_vault)) = (program, pool_vault) else {
+        return err!(ErrorCode::ReferralProgramRequired);
+    };
+    if program.min_deposit_lamports == 0 {
+        return Ok(());
+    }
+
+    let credited = deposits.min(program.max_deposits_per_referrer.saturating_sub(referral.deposit_count));
+    if credited == 0 {
+        return Ok(());
+    }
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            system_program,
+            anchor_lang::system_program::Transfer {
+                from: depositor,
+                to: pool_vault,
+            },
+        ),
+        credited * program.min_deposit_lamports,
+    )?;
+    referral.deposit_count += credited;
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateReferralProgram<'info> {
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ReferralProgram::LEN,
+        seeds = [REFERRAL_PROGRAM_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub referral_program: Account<'info, ReferralProgram>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ConfigureReferralProgram<'info> {
+    #[account(
+        mut,
+        seeds = [REFERRAL_PROGRAM_SEED, referral_program.pool.as_ref()],
+        bump = referral_program.bump,
+        constraint = referral_program.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub referral_program: Account<'info, ReferralProgram>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FundReferralProgram<'info> {
+    #[account(
+        mut,
+        seeds = [REFERRAL_PROGRAM_SEED, referral_program.pool.as_ref()],
+        bump = referral_program.bump
+    )]
+    pub referral_program: Account<'info, ReferralProgram>,
+
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(referral_commitment: [u8; 32])]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        mut,
+        seeds = [REFERRAL_PROGRAM_SEED, referral_program.pool.as_ref()],
+        bump = referral_program.bump
+    )]
+    pub referral_program: Account<'info, ReferralProgram>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ReferralRecord::LEN,
+        seeds = [REFERRAL_SEED, referral_program.pool.as_ref(), &referral_commitment],
+        bump
+    )]
+    pub referral: Account<'info, ReferralRecord>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(
+        mut,
+        seeds = [REFERRAL_PROGRAM_SEED, referral_program.pool.as_ref()],
+        bump = referral_program.bump
+    )]
+    pub referral_program: Account<'info, ReferralProgram>,
+
+    #[account(
+        mut,
+        seeds = [REFERRAL_SEED, referral_program.pool.as_ref(), &referral.referral_commitment],
+        bump = referral.bump
+    )]
+    pub referral: Account<'info, ReferralRecord>,
+
+    /// CHECK: Any address the referrer chooses; bound by the claim proof
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+
+    /// Submits the claim (may be a relayer)
+    pub payer: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
@@ -0,0 +1,789 @@
+//! Relayer fee market
+
+use crate::*;
+
+// ============================================
+// RELAYER FEE MARKET - Gasless Withdrawals
+// ============================================
+//
+// A withdrawer with no unlinked SOL for fees hands the transaction to a
+// registered relayer. The withdrawal proof commits to
+//   fee_commitment = H(DOMAIN_RELAYER_FEE, relayer, fee_lamports, blinding)
+// so the relayer is paid from the pool vault only when it submits that
+// exact withdrawal, and never more than its posted quote. Relayers bond
+// stake in the pool's registry, which the pool authority can slash, and
+// the registry can require them to keep proving solvency (below).
+
+pub(crate) fn create_relayer_registry(
+    ctx: Context<CreateRelayerRegistry>,
+    min_stake: u64,
+    max_fee_lamports: u64,
+) -> Result<()> {
+    let registry = &mut ctx.accounts.relayer_registry;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(min_stake > 0, ErrorCode::InvalidRelayerParameters);
+    require!(max_fee_lamports > 0, ErrorCode::InvalidRelayerParameters);
+
+    registry.pool = ctx.accounts.shielded_pool.key();
+    registry.authority = ctx.accounts.authority.key();
+    registry.min_stake = min_stake;
+    registry.max_fee_lamports = max_fee_lamports;
+    registry.relayer_count = 0;
+    registry.slashed_count = 0;
+    registry.total_fees_paid = 0;
+    registry.created_at = current_time;
+    registry.bump = ctx.bumps.relayer_registry;
+    registry.version = RelayerRegistry::VERSION;
+    registry.solvency_period = 0;
+
+    emit!(RelayerRegistryCreated {
+        registry: registry.key(),
+        pool: registry.pool,
+        min_stake,
+        max_fee_lamports,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateRelayerRegistry,
+        registry.key(),
+        [0u8; 32],
+        [0u8; 32],
+        [0u8; 32],
+        min_stake,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn register_relayer(ctx: Context<RegisterRelayer>, fee_quote: u64) -> Result<()> {
+    let registry = &mut ctx.accounts.relayer_registry;
+    let record = &mut ctx.accounts.relayer_record;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(
+        fee_quote > 0 && fee_quote <= registry.max_fee_lamports,
+        ErrorCode::InvalidRelayerParameters
+    );
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.relayer.to_account_info(),
+                to: record.to_account_info(),
+            },
+        ),
+        registry.min_stake,
+    )?;
+
+    record.registry = registry.key();
+    record.relayer = ctx.accounts.relayer.key();
+    record.stake_lamports = registry.min_stake;
+    record.fee_quote = fee_quote;
+    record.fees_claimed = 0;
+    record.withdrawals_relayed = 0;
+    record.registered_at = current_time;
+    record.exit_requested_at = 0;
+    record.is_slashed = false;
+    record.bump = ctx.bumps.relayer_record;
+    record.version = RelayerRecord::VERSION;
+    record.obligations_commitment = [0u8; 32];
+    record.solvent_until = 0;
+
+    registry.relayer_count += 1;
+
+    emit!(RelayerRegistered {
+        registry: registry.key(),
+        relayer: record.relayer,
+        stake_lamports: record.stake_lamports,
+        fee_quote,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RegisterRelayer,
+        record.key(),
+        record.relayer.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        record.stake_lamports,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn update_relayer_quote(ctx: Context<UpdateRelayerQuote>, fee_quote: u64) -> Result<()> {
+    let registry = &ctx.accounts.relayer_registry;
+    let record = &mut ctx.accounts.relayer_record;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(record.is_active(), ErrorCode::RelayerNotActive);
+    require!(record.is_solvent(registry, current_time), ErrorCode::RelayerSolvencyLapsed);
+    require!(
+        fee_quote > 0 && fee_quote <= registry.max_fee_lamports,
+        ErrorCode::InvalidRelayerParameters
+    );
+
+    record.fee_quote = fee_quote;
+
+    emit!(RelayerQuoteUpdated {
+        registry: registry.key(),
+        relayer: record.relayer,
+        fee_quote,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::UpdateRelayerQuote,
+        record.key(),
+        record.relayer.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        fee_quote,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn shield_withdraw_relayed(
+    ctx: Context<ShieldWithdrawRelayed>,
+    nullifier: [u8; 32],
+    merkle_proof: [[u8; 32]; 8],
+    merkle_path_indices: u8,
+    withdrawal_proof: Vec<u8>,
+    output_commitment: [u8; 32],
+    fee: RelayerFeeOpening,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    require_proof_len(&withdrawal_proof)?;
+
+    let pool = &mut ctx.accounts.shielded_pool;
+    let registry = &mut ctx.accounts.relayer_registry;
+    let record = &mut ctx.accounts.relayer_record;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(pool.is_active, ErrorCode::PoolNotActive);
+    require!(!pool.screens_withdrawals(), ErrorCode::WithdrawalNotScreenable);
+    require!(record.is_active(), ErrorCode::RelayerNotActive);
+    require!(record.is_solvent(registry, current_time), ErrorCode::RelayerSolvencyLapsed);
+    require!(fee.lamports <= record.fee_quote, ErrorCode::RelayerFeeTooHigh);
+    require_unspent(&ctx.accounts.nullifier_account, pool, &nullifier)?;
+    require!(
+        verify_merkle_proof(&pool.merkle_root, &merkle_proof, merkle_path_indices, &nullifier),
+        ErrorCode::InvalidMerkleProof
+    );
+
+    let fee_commitment = compute_relayer_fee_commitment(&record.relayer, &fee);
+    let public_signals = [
+        nullifier,
+        output_commitment,
+        pool.spend_anchor(),
+        fee_commitment,
+        compute_pool_binding(&pool.key()),
+    ];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::RelayedWithdrawal, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::RelayedWithdrawal, &public_signals, &withdrawal_proof),
+        ErrorCode::InvalidRelayedWithdrawalProof
+    );
+
+    record_filtered_nullifiers(pool, ctx.accounts.nullifier_filter.as_ref(), &[nullifier])?;
+    spend_withdrawal_note(
+        pool,
+        &mut ctx.accounts.nullifier_account,
+        ctx.bumps.nullifier_account,
+        nullifier,
+        output_commitment,
+        current_time,
+    );
+    ctx.accounts.nullifier_account.outcome = compute_relayed_outcome(&output_commitment, &record.relayer, &fee);
+
+    if fee.lamports > 0 {
+        let pool_key = pool.key();
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.pool_vault.to_account_info(),
+                    to: ctx.accounts.relayer.to_account_info(),
+                },
+                &[&[SHIELDED_VAULT_SEED, pool_key.as_ref(), &[ctx.bumps.pool_vault]]],
+            ),
+            fee.lamports,
+        )?;
+    }
+
+    record.fees_claimed += fee.lamports;
+    record.withdrawals_relayed += 1;
+    registry.total_fees_paid += fee.lamports;
+
+    debug_log!(
+        "shield_withdraw_relayed",
+        pool = pool.key(),
+        relayer = record.relayer,
+        nullifier = Hex(&nullifier),
+        fee = fee.lamports,
+    );
+
+    emit_cpi!(ShieldedWithdrawRelayed {
+        pool: pool.key(),
+        relayer: record.relayer,
+        nullifier,
+        output_commitment,
+        fee_commitment,
+        merkle_root: pool.merkle_root,
+        timestamp: current_time,
+        // Withdrawal amount is NEVER included - true privacy!
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ShieldWithdrawRelayed,
+        pool.key(),
+        nullifier,
+        output_commitment,
+        pool.merkle_root,
+        pool.next_note_index as u64,
+        current_time,
+    ));
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+/// `shield_withdraw_relayed` for retries after an ambiguous failure: if
+/// the nullifier is already recorded with this withdrawal's outcome, the
+/// earlier attempt landed and it succeeds without changing anything. A
+/// note spent any other way still fails with `NullifierAlreadyUsed`
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn shield_withdraw_relayed_idempotent(
+    ctx: Context<ShieldWithdrawRelayed>,
+    nullifier: [u8; 32],
+    merkle_proof: [[u8; 32]; 8],
+    merkle_path_indices: u8,
+    withdrawal_proof: Vec<u8>,
+    output_commitment: [u8; 32],
+    fee: RelayerFeeOpening,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    if ctx.accounts.nullifier_account.is_recorded() {
+        let outcome = compute_relayed_outcome(&output_commitment, &ctx.accounts.relayer_record.relayer, &fee);
+        require_relayed_outcome(&ctx.accounts.nullifier_account, &outcome)?;
+        debug_log!(
+            "shield_withdraw_relayed_idempotent",
+            pool = ctx.accounts.shielded_pool.key(),
+            nullifier = Hex(&nullifier),
+        );
+        return Ok(());
+    }
+
+    shield_withdraw_relayed(
+        ctx,
+        nullifier,
+        merkle_proof,
+        merkle_path_indices,
+        withdrawal_proof,
+        output_commitment,
+        fee,
+        circuit_id,
+    )
+}
+
+/// Check that a recorded nullifier was spent by the relayed withdrawal
+/// with `outcome`
+pub(crate) fn require_relayed_outcome(record: &NullifierRecord, outcome: &[u8; 32]) -> Result<()> {
+    require!(
+        *outcome != [0u8; 32] && constant_time_eq(&record.outcome, outcome),
+        ErrorCode::NullifierAlreadyUsed
+    );
+    Ok(())
+}
+
+pub(crate) fn slash_relayer(ctx: Context<SlashRelayer>, evidence_hash: [u8; 32]) -> Result<()> {
+    let registry = &mut ctx.accounts.relayer_registry;
+    let record = &mut ctx.accounts.relayer_record;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(!record.is_slashed, ErrorCode::RelayerAlreadySlashed);
+
+    let slashed_lamports = record.stake_lamports;
+    record.sub_lamports(slashed_lamports)?;
+    ctx.accounts.pool_vault.add_lamports(slashed_lamports)?;
+
+    record.stake_lamports = 0;
+    record.is_slashed = true;
+    registry.slashed_count += 1;
+
+    emit!(RelayerSlashed {
+        registry: registry.key(),
+        relayer: record.relayer,
+        evidence_hash,
+        slashed_lamports,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::SlashRelayer,
+        record.key(),
+        record.relayer.to_bytes(),
+        evidence_hash,
+        [0u8; 32],
+        slashed_lamports,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn request_relayer_exit(ctx: Context<UpdateRelayerQuote>) -> Result<()> {
+    let record = &mut ctx.accounts.relayer_record;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(record.is_active(), ErrorCode::RelayerNotActive);
+    record.exit_requested_at = current_time;
+
+    emit!(RelayerExitRequested {
+        registry: record.registry,
+        relayer: record.relayer,
+        unbonds_at: current_time + RELAYER_UNBONDING_SECONDS,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RequestRelayerExit,
+        record.key(),
+        record.relayer.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn close_relayer(ctx: Context<CloseRelayer>) -> Result<()> {
+    let record = &ctx.accounts.relayer_record;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(
+        record.is_slashed || record.exit_requested_at != 0,
+        ErrorCode::RelayerNotExiting
+    );
+    require!(
+        record.is_slashed || current_time >= record.exit_requested_at + RELAYER_UNBONDING_SECONDS,
+        ErrorCode::RelayerUnbonding
+    );
+
+    ctx.accounts.relayer_registry.relayer_count -= 1;
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CloseRelayer,
+        record.key(),
+        record.relayer.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+// ============================================
+// RELAYER SOLVENCY - Stake Covers Open Jobs
+// ============================================
+//
+// A relayer quotes fees for jobs it settles later; if it disappears with
+// jobs outstanding, the users behind them are left waiting. A registry
+// with a solvency period requires each relayer to prove, at least once
+// per period, that its quoted-but-unsettled obligations sum to less than
+// its bonded stake. The obligations stay hidden behind a commitment; the
+// proof is bound to
+//   solvency_tag = H(DOMAIN_RELAYER_SOLVENCY, relayer_record, stake_lamports, now / period)
+// so it cannot be replayed in a later period or against a smaller stake.
+// A relayer without a current proof can neither quote nor relay.
+
+pub(crate) fn configure_relayer_solvency(ctx: Context<ConfigureRelayerSolvency>, solvency_period: i64) -> Result<()> {
+    let registry = &mut ctx.accounts.relayer_registry;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(
+        solvency_period == 0
+            || (MIN_RELAYER_SOLVENCY_PERIOD..=MAX_RELAYER_SOLVENCY_PERIOD).contains(&solvency_period),
+        ErrorCode::InvalidRelayerParameters
+    );
+
+    registry.solvency_period = solvency_period;
+
+    emit!(RelayerSolvencyConfigured {
+        registry: registry.key(),
+        solvency_period,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ConfigureRelayerSolvency,
+        registry.key(),
+        registry.pool.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        solvency_period as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn prove_relayer_solvency(
+    ctx: Context<ProveRelayerSolvency>,
+    obligations_commitment: [u8; 32],
+    solvency_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    require_proof_len(&solvency_proof)?;
+
+    let registry = &ctx.accounts.relayer_registry;
+    let record = &mut ctx.accounts.relayer_record;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(record.is_active(), ErrorCode::RelayerNotActive);
+    require!(registry.solvency_period > 0, ErrorCode::InvalidRelayerParameters);
+
+    let period_index = (current_time / registry.solvency_period) as u64;
+    let public_signals = [
+        obligations_commitment,
+        compute_relayer_solvency_tag(&record.key(), record.stake_lamports, period_index),
+        compute_pool_binding(&registry.pool),
+    ];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::RelayerSolvency, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::RelayerSolvency, &public_signals, &solvency_proof),
+        ErrorCode::InvalidRelayerSolvencyProof
+    );
+
+    record.obligations_commitment = obligations_commitment;
+    record.solvent_until = current_time.saturating_add(registry.solvency_period);
+
+    debug_log!(
+        "prove_relayer_solvency",
+        relayer = record.relayer,
+        solvent_until = record.solvent_until,
+    );
+
+    emit!(RelayerSolvencyProven {
+        registry: registry.key(),
+        relayer: record.relayer,
+        obligations_commitment,
+        stake_lamports: record.stake_lamports,
+        solvent_until: record.solvent_until,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ProveRelayerSolvency,
+        record.key(),
+        record.relayer.to_bytes(),
+        obligations_commitment,
+        [0u8; 32],
+        record.solvent_until as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateRelayerRegistry<'info> {
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = RelayerRegistry::LEN,
+        seeds = [RELAYER_REGISTRY_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub relayer_registry: Account<'info, RelayerRegistry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RegisterRelayer<'info> {
+    #[account(
+        mut,
+        seeds = [RELAYER_REGISTRY_SEED, relayer_registry.pool.as_ref()],
+        bump = relayer_registry.bump
+    )]
+    pub relayer_registry: Account<'info, RelayerRegistry>,
+
+    #[account(
+        init,
+        payer = relayer,
+        space = RelayerRecord::LEN,
+        seeds = [RELAYER_SEED, relayer_registry.key().as_ref(), relayer.key().as_ref()],
+        bump
+    )]
+    pub relayer_record: Account<'info, RelayerRecord>,
+
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateRelayerQuote<'info> {
+    #[account(
+        seeds = [RELAYER_REGISTRY_SEED, relayer_registry.pool.as_ref()],
+        bump = relayer_registry.bump
+    )]
+    pub relayer_registry: Account<'info, RelayerRegistry>,
+
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, relayer_registry.key().as_ref(), relayer.key().as_ref()],
+        bump = relayer_record.bump
+    )]
+    pub relayer_record: Account<'info, RelayerRecord>,
+
+    pub relayer: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(nullifier: [u8; 32])]
+pub struct ShieldWithdrawRelayed<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    /// Created by the first successful attempt; `init_if_needed` lets the
+    /// idempotent variant see an earlier one
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = NullifierRecord::LEN,
+        seeds = [NULLIFIER_SEED, shielded_pool.key().as_ref(), &nullifier],
+        bump
+    )]
+    pub nullifier_account: Account<'info, NullifierRecord>,
+
+    /// CHECK: Pool vault paying the relayer fee
+    #[account(
+        mut,
+        seeds = [SHIELDED_VAULT_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [RELAYER_REGISTRY_SEED, shielded_pool.key().as_ref()],
+        bump = relayer_registry.bump
+    )]
+    pub relayer_registry: Account<'info, RelayerRegistry>,
+
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, relayer_registry.key().as_ref(), relayer.key().as_ref()],
+        bump = relayer_record.bump
+    )]
+    pub relayer_record: Account<'info, RelayerRecord>,
+
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The pool's nullifier filter, required once the pool keeps one
+    #[account(
+        mut,
+        seeds = [NULLIFIER_FILTER_SEED, shielded_pool.key().as_ref()],
+        bump = nullifier_filter.load()?.bump
+    )]
+    pub nullifier_filter: Option<AccountLoader<'info, NullifierFilter>>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SlashRelayer<'info> {
+    #[account(
+        mut,
+        seeds = [RELAYER_REGISTRY_SEED, relayer_registry.pool.as_ref()],
+        bump = relayer_registry.bump,
+        constraint = relayer_registry.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub relayer_registry: Account<'info, RelayerRegistry>,
+
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, relayer_registry.key().as_ref(), relayer_record.relayer.as_ref()],
+        bump = relayer_record.bump
+    )]
+    pub relayer_record: Account<'info, RelayerRecord>,
+
+    /// CHECK: Pool vault receiving the slashed stake
+    #[account(
+        mut,
+        seeds = [SHIELDED_VAULT_SEED, relayer_registry.pool.as_ref()],
+        bump
+    )]
+    pub pool_vault: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseRelayer<'info> {
+    #[account(
+        mut,
+        seeds = [RELAYER_REGISTRY_SEED, relayer_registry.pool.as_ref()],
+        bump = relayer_registry.bump
+    )]
+    pub relayer_registry: Account<'info, RelayerRegistry>,
+
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [RELAYER_SEED, relayer_registry.key().as_ref(), relayer.key().as_ref()],
+        bump = relayer_record.bump
+    )]
+    pub relayer_record: Account<'info, RelayerRecord>,
+
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ConfigureRelayerSolvency<'info> {
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        mut,
+        seeds = [RELAYER_REGISTRY_SEED, shielded_pool.key().as_ref()],
+        bump = relayer_registry.bump
+    )]
+    pub relayer_registry: Account<'info, RelayerRegistry>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProveRelayerSolvency<'info> {
+    #[account(
+        seeds = [RELAYER_REGISTRY_SEED, relayer_registry.pool.as_ref()],
+        bump = relayer_registry.bump
+    )]
+    pub relayer_registry: Account<'info, RelayerRegistry>,
+
+    #[account(
+        mut,
+        seeds = [RELAYER_SEED, relayer_registry.key().as_ref(), relayer.key().as_ref()],
+        bump = relayer_record.bump
+    )]
+    pub relayer_record: Account<'info, RelayerRecord>,
+
+    pub relayer: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::test_support::*;
+
+    fn fee(lamports: u64) -> RelayerFeeOpening {
+        RelayerFeeOpening {
+            lamports,
+            blinding: [5u8; 32],
+        }
+    }
+
+    #[test]
+    fn relayed_retries_succeed_only_for_the_recorded_outcome() {
+        let relayer = Pubkey::new_unique();
+        let output = [7u8; 32];
+        let mut record: NullifierRecord = zeroed(NullifierRecord::LEN);
+        record.pool = Pubkey::new_unique();
+        record.outcome = compute_relayed_outcome(&output, &relayer, &fee(5_000));
+
+        assert!(require_relayed_outcome(&record, &compute_relayed_outcome(&output, &relayer, &fee(5_000))).is_ok());
+
+        let different = [
+            compute_relayed_outcome(&[8u8; 32], &relayer, &fee(5_000)),
+            compute_relayed_outcome(&output, &Pubkey::new_unique(), &fee(5_000)),
+            compute_relayed_outcome(&output, &relayer, &fee(6_000)),
+        ];
+        for outcome in different {
+            assert_error(require_relayed_outcome(&record, &outcome), ErrorCode::NullifierAlreadyUsed);
+        }
+    }
+
+    #[test]
+    fn notes_spent_outside_relaying_are_not_retries() {
+        let relayer = Pubkey::new_unique();
+        let mut record: NullifierRecord = zeroed(NullifierRecord::LEN);
+        record.pool = Pubkey::new_unique();
+
+        let outcome = compute_relayed_outcome(&[7u8; 32], &relayer, &fee(5_000));
+        assert_error(require_relayed_outcome(&record, &outcome), ErrorCode::NullifierAlreadyUsed);
+        assert_error(require_relayed_outcome(&record, &[0u8; 32]), ErrorCode::NullifierAlreadyUsed);
+    }
+}
@@ -0,0 +1,387 @@
+//! Rate-limiting nullifiers
+
+use crate::*;
+
+// ============================================
+// RATE-LIMITING NULLIFIERS (RLN) - Anti-Spam
+// ============================================
+//
+// Members bond stake against an identity commitment = H(identity_secret).
+// Each anonymous action reveals a Shamir share of the secret on the line
+//   y = identity_secret + a1 * x,  a1 = H(identity_secret || epoch || message_id)
+// with nullifier = H(a1). Exceeding the per-epoch limit forces a nullifier
+// to be reused, publishing two points on the same line, from which anyone
+// can reconstruct identity_secret and slash the member's stake.
+
+pub(crate) fn create_rln_group(
+    ctx: Context<CreateRlnGroup>,
+    group_id: [u8; 32],
+    stake_lamports: u64,
+    epoch_length: i64,
+    message_limit: u32,
+) -> Result<()> {
+    let group = &mut ctx.accounts.rln_group;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(stake_lamports > 0, ErrorCode::InvalidRlnParameters);
+    require!(epoch_length > 0, ErrorCode::InvalidRlnParameters);
+    require!(
+        message_limit > 0 && message_limit <= MAX_RLN_MESSAGE_LIMIT,
+        ErrorCode::InvalidRlnParameters
+    );
+
+    group.group_id = group_id;
+    group.creator = ctx.accounts.creator.key();
+    group.stake_lamports = stake_lamports;
+    group.epoch_length = epoch_length;
+    group.message_limit = message_limit;
+    group.members_root = [0u8; 32]; // Empty tree root
+    group.member_count = 0;
+    group.slashed_count = 0;
+    group.created_at = current_time;
+    group.bump = ctx.bumps.rln_group;
+    group.version = RlnGroup::VERSION;
+
+    emit!(RlnGroupCreated {
+        group: group.key(),
+        group_id,
+        stake_lamports,
+        epoch_length,
+        message_limit,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateRlnGroup,
+        group.key(),
+        group_id,
+        [0u8; 32],
+        [0u8; 32],
+        stake_lamports,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn register_rln_member(
+    ctx: Context<RegisterRlnMember>,
+    identity_commitment: [u8; 32],
+) -> Result<()> {
+    let group = &mut ctx.accounts.rln_group;
+    let member = &mut ctx.accounts.rln_member;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(
+        group.member_count < MAX_SHIELDED_NOTES as u32,
+        ErrorCode::PoolFull
+    );
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: member.to_account_info(),
+            },
+        ),
+        group.stake_lamports,
+    )?;
+
+    member.group = group.key();
+    member.identity_commitment = identity_commitment;
+    member.member_index = group.member_count;
+    member.stake_lamports = group.stake_lamports;
+    member.registered_at = current_time;
+    member.is_slashed = false;
+    member.bump = ctx.bumps.rln_member;
+    member.version = RlnMember::VERSION;
+
+    group.members_root = insert_note_to_merkle_tree(
+        &group.members_root,
+        &identity_commitment,
+        group.member_count,
+    );
+    group.member_count += 1;
+
+    debug_log!(
+        "register_rln_member",
+        group = group.key(),
+        index = member.member_index,
+        members_root = Hex(&group.members_root),
+    );
+
+    emit_cpi!(RlnMemberRegistered {
+        group: group.key(),
+        identity_commitment,
+        member_index: member.member_index,
+        members_root: group.members_root,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RegisterRlnMember,
+        group.key(),
+        identity_commitment,
+        [0u8; 32],
+        group.members_root,
+        member.member_index as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn rln_signal(
+    ctx: Context<RlnSignal>,
+    nullifier: [u8; 32],
+    epoch: u64,
+    share_x: [u8; 32],
+    share_y: [u8; 32],
+    members_root: [u8; 32],
+    signal_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    let group = &ctx.accounts.rln_group;
+    let record = &mut ctx.accounts.nullifier_record;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    let current_epoch = (current_time / group.epoch_length) as u64;
+    require!(epoch == current_epoch, ErrorCode::InvalidRlnEpoch);
+    require!(members_root == group.members_root, ErrorCode::InvalidMerkleProof);
+    require!(
+        verify_field_element(&share_x)
+            && verify_field_element(&share_y)
+            && share_x != [0u8; 32],
+        ErrorCode::InvalidRlnShare
+    );
+    require!(signal_proof.len() >= 256, ErrorCode::InvalidProofStructure);
+
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::Rln, &circuit_id)?;
+    let proof_valid = verify_rln_proof(
+        &nullifier,
+        epoch,
+        &share_x,
+        &share_y,
+        &members_root,
+        group.message_limit,
+        &signal_proof,
+    );
+    require!(proof_valid, ErrorCode::InvalidProof);
+
+    record.group = group.key();
+    record.nullifier = nullifier;
+    record.epoch = epoch;
+    record.share_x = share_x;
+    record.share_y = share_y;
+    record.recorded_at = current_time;
+    record.bump = ctx.bumps.nullifier_record;
+    record.version = RlnNullifierRecord::VERSION;
+
+    debug_log!(
+        "rln_signal",
+        group = group.key(),
+        epoch = epoch,
+        nullifier = Hex(&nullifier),
+    );
+
+    // Shares are public so that double-signalling can be slashed
+    emit_cpi!(RlnSignalRecorded {
+        group: group.key(),
+        nullifier,
+        epoch,
+        share_x,
+        share_y,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RlnSignal,
+        group.key(),
+        nullifier,
+        [0u8; 32],
+        [0u8; 32],
+        epoch,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn rln_slash(
+    ctx: Context<RlnSlash>,
+    identity_secret: [u8; 32],
+    share_x: [u8; 32],
+    share_y: [u8; 32],
+) -> Result<()> {
+    let group = &mut ctx.accounts.rln_group;
+    let record = &ctx.accounts.nullifier_record;
+    let member = &mut ctx.accounts.rln_member;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(!member.is_slashed, ErrorCode::RlnMemberAlreadySlashed);
+
+    // The slasher recovers these off-chain, often with little-endian
+    // tooling; decode either byte order, then compare canonical values so a
+    // re-encoded copy of the recorded share is not taken for a second one
+    let decode = |bytes: &[u8; 32]| {
+        FieldElement::from_bytes_detect(bytes)
+            .map(|(element, _)| element)
+            .ok_or(ErrorCode::InvalidRlnShare)
+    };
+    let secret = decode(&identity_secret)?;
+    let x2 = decode(&share_x)?;
+    let y2 = decode(&share_y)?;
+    let x1 = FieldElement::from_be_bytes(&record.share_x).ok_or(ErrorCode::InvalidRlnShare)?;
+    let y1 = FieldElement::from_be_bytes(&record.share_y).ok_or(ErrorCode::InvalidRlnShare)?;
+    require!(
+        !x2.ct_eq(&FieldElement::ZERO) && !x2.ct_eq(&x1),
+        ErrorCode::InvalidRlnShare
+    );
+
+    // Both shares lie on y = identity_secret + a1 * x iff
+    // (y1 - secret) * x2 == (y2 - secret) * x1  (mod p)
+    let lhs = (y1 - secret) * x2;
+    let rhs = (y2 - secret) * x1;
+    require!(lhs.ct_eq(&rhs), ErrorCode::InvalidRlnShare);
+
+    let slashed_lamports = member.stake_lamports;
+    member.sub_lamports(slashed_lamports)?;
+    ctx.accounts.slasher.add_lamports(slashed_lamports)?;
+
+    member.stake_lamports = 0;
+    member.is_slashed = true;
+    group.slashed_count += 1;
+
+    emit!(RlnMemberSlashed {
+        group: group.key(),
+        identity_commitment: member.identity_commitment,
+        nullifier: record.nullifier,
+        slasher: ctx.accounts.slasher.key(),
+        slashed_lamports,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RlnSlash,
+        group.key(),
+        member.identity_commitment,
+        record.nullifier,
+        [0u8; 32],
+        slashed_lamports,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(group_id: [u8; 32])]
+pub struct CreateRlnGroup<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = RlnGroup::LEN,
+        seeds = [RLN_GROUP_SEED, creator.key().as_ref(), &group_id],
+        bump
+    )]
+    pub rln_group: Account<'info, RlnGroup>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(identity_commitment: [u8; 32])]
+pub struct RegisterRlnMember<'info> {
+    #[account(
+        mut,
+        seeds = [RLN_GROUP_SEED, rln_group.creator.as_ref(), &rln_group.group_id],
+        bump = rln_group.bump
+    )]
+    pub rln_group: Account<'info, RlnGroup>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = RlnMember::LEN,
+        seeds = [RLN_MEMBER_SEED, rln_group.key().as_ref(), &identity_commitment],
+        bump
+    )]
+    pub rln_member: Account<'info, RlnMember>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(nullifier: [u8; 32])]
+pub struct RlnSignal<'info> {
+    #[account(
+        seeds = [RLN_GROUP_SEED, rln_group.creator.as_ref(), &rln_group.group_id],
+        bump = rln_group.bump
+    )]
+    pub rln_group: Account<'info, RlnGroup>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = RlnNullifierRecord::LEN,
+        seeds = [RLN_NULLIFIER_SEED, rln_group.key().as_ref(), &nullifier],
+        bump
+    )]
+    pub nullifier_record: Account<'info, RlnNullifierRecord>,
+
+    /// Any payer (e.g. a relayer) - not linked to the member identity
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(identity_secret: [u8; 32])]
+pub struct RlnSlash<'info> {
+    #[account(
+        mut,
+        seeds = [RLN_GROUP_SEED, rln_group.creator.as_ref(), &rln_group.group_id],
+        bump = rln_group.bump
+    )]
+    pub rln_group: Account<'info, RlnGroup>,
+
+    #[account(
+        seeds = [RLN_NULLIFIER_SEED, rln_group.key().as_ref(), &nullifier_record.nullifier],
+        bump = nullifier_record.bump,
+        constraint = nullifier_record.group == rln_group.key() @ ErrorCode::Unauthorized
+    )]
+    pub nullifier_record: Account<'info, RlnNullifierRecord>,
+
+    /// Member PDA derived from H(identity_secret) - proves the secret matches
+    #[account(
+        mut,
+        seeds = [RLN_MEMBER_SEED, rln_group.key().as_ref(), &compute_rln_identity_commitment(&identity_secret)],
+        bump = rln_member.bump
+    )]
+    pub rln_member: Account<'info, RlnMember>,
+
+    #[account(mut)]
+    pub slasher: Signer<'info>,
+}
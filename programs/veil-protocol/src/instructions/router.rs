@@ -0,0 +1,131 @@
+//! Deposits routed across several pools
+
+use crate::*;
+
+// ============================================
+// DEPOSIT ROUTING - One Deposit, Several Pools
+// ============================================
+//
+// Splitting a deposit over pools of different sizes (e.g. a 1 SOL and a
+// 10 SOL pool) otherwise takes one transaction per pool, each signed and
+// timed separately. `route_deposit` inserts every note of the client's
+// plan in one instruction, under one aggregate range proof over all the
+// commitments. The plan lists the notes grouped by pool; remaining
+// accounts follow it, each pool followed by the note accounts of its legs:
+//   [pool A, note A0, note A1, pool B, note B0, ...]
+// Pools are loaded from the remaining accounts, so each may appear in the
+// plan only once; they get the same checks and bookkeeping as a batch
+// deposit.
+
+pub(crate) fn route_deposit<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RouteDeposit<'info>>,
+    plan: Vec<RouteLeg>,
+    range_proof: Vec<u8>,
+) -> Result<()> {
+    let remaining_accounts = clock::strip(ctx.remaining_accounts);
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(
+        !plan.is_empty() && plan.len() <= MAX_ROUTED_NOTES,
+        ErrorCode::InvalidDepositRoute
+    );
+    let groups: Vec<&[RouteLeg]> = plan.chunk_by(|a, b| a.pool == b.pool).collect();
+    for (i, group) in groups.iter().enumerate() {
+        require!(
+            !groups[..i].iter().any(|earlier| earlier[0].pool == group[0].pool),
+            ErrorCode::InvalidDepositRoute
+        );
+    }
+    require!(
+        remaining_accounts.len() == groups.len() + plan.len(),
+        ErrorCode::InvalidDepositRoute
+    );
+
+    let note_commitments: Vec<[u8; 32]> = plan.iter().map(|leg| leg.note_commitment).collect();
+    require!(
+        verify_aggregate_range_proof(&note_commitments, &range_proof),
+        ErrorCode::InvalidRangeProof
+    );
+
+    let mut accounts = remaining_accounts.iter();
+    for group in &groups {
+        let pool_info = accounts.next().ok_or(ErrorCode::InvalidDepositRoute)?;
+        require!(
+            pool_info.key() == group[0].pool && pool_info.is_writable,
+            ErrorCode::InvalidDepositRoute
+        );
+        let mut pool = Account::<ShieldedPool>::try_from(pool_info)?;
+        lock_pool(&pool)?;
+        let totals = PoolTotals::of(&pool);
+
+        require!(pool.is_active, ErrorCode::PoolNotActive);
+        require!(
+            pool.next_note_index as usize + group.len() <= MAX_SHIELDED_NOTES,
+            ErrorCode::PoolFull
+        );
+
+        for leg in group.iter() {
+            let note_account = accounts.next().ok_or(ErrorCode::InvalidDepositRoute)?;
+            let note_index = pool.next_note_index;
+            create_pool_note_account(
+                &mut pool,
+                note_account,
+                &ctx.accounts.depositor,
+                &ctx.accounts.system_program,
+                leg.note_commitment,
+                leg.encrypted_note,
+                current_time,
+            )?;
+            pool.record_deposit(current_time);
+
+            emit_cpi!(ShieldedDeposit {
+                pool: leg.pool,
+                note_commitment: leg.note_commitment,
+                encrypted_note: leg.encrypted_note,
+                note_index,
+                merkle_root: pool.merkle_root,
+                timestamp: current_time,
+                // Amount is NEVER included - true privacy!
+            });
+            emit_cpi!(VeilRecord::new(
+                RecordKind::RouteDeposit,
+                leg.pool,
+                leg.note_commitment,
+                [0u8; 32],
+                pool.merkle_root,
+                note_index as u64,
+                current_time,
+            ));
+        }
+
+        record_protocol_stats(&pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+        pool.exit(&crate::ID)?;
+    }
+
+    debug_log!(
+        "route_deposit",
+        depositor = ctx.accounts.depositor.key(),
+        pools = groups.len(),
+        notes = plan.len(),
+    );
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RouteDeposit<'info> {
+    /// Pays for the note accounts
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Protocol-wide totals; required once any routed pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+}
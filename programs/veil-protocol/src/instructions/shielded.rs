@@ -0,0 +1,3230 @@
+//! Shielded pools: deposits, spends, claimable notes, reserves, receipts,
+//! CPI deposits, note expiry, tree checkpoints and nullifier filters
+
+use anchor_lang::solana_program::instruction::{
+    get_stack_height, AccountMeta, Instruction, TRANSACTION_LEVEL_STACK_HEIGHT,
+};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+
+use crate::*;
+
+// ============================================
+// SHIELDED STAKING POOL - True Privacy with Note-Based System
+// ============================================
+//
+// Architecture: UTXO/Note-based shielded pool
+// - Deposits create "notes" (encrypted commitments)
+// - Withdrawals consume notes via nullifiers (prevents double-spend)
+// - Amounts are NEVER visible on-chain or in transactions
+// - Uses ZK proofs to verify ownership without revealing details
+//
+// Note structure: commitment = H(amount || blinding_factor || owner_commitment)
+// Nullifier: nullifier = H(note_commitment || owner_secret)
+
+pub(crate) fn create_shielded_pool(
+    ctx: Context<CreateShieldedPool>,
+    pool_id: [u8; 32],
+    reward_rate_bps: u16,
+    lockup_epochs: u8,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.shielded_pool;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(reward_rate_bps <= 10000, ErrorCode::InvalidRewardRate);
+    require!((1..=52).contains(&lockup_epochs), ErrorCode::InvalidLockupPeriod);
+
+    pool.pool_id = pool_id;
+    pool.creator = ctx.accounts.creator.key();
+    pool.authority = ctx.accounts.creator.key();
+    pool.reward_rate_bps = reward_rate_bps;
+    pool.lockup_epochs = lockup_epochs;
+    pool.merkle_root = [0u8; 32]; // Empty tree root
+    pool.next_note_index = 0;
+    pool.total_notes = 0;
+    pool.created_at = current_time;
+    pool.is_active = true;
+    pool.bump = ctx.bumps.shielded_pool;
+    pool.version = ShieldedPool::VERSION;
+
+    // Initialize nullifier set to empty
+    pool.nullifier_count = 0;
+
+    pool.metrics_epoch = anonymity_epoch(current_time);
+    pool.deposits_this_epoch = 0;
+    pool.deposits_last_epoch = 0;
+    pool.deposit_histogram = [0; DEPOSIT_HISTOGRAM_BUCKETS];
+    pool.unique_withdrawal_roots_used = 0;
+    pool.last_withdrawal_root = [0u8; 32];
+
+    pool.note_expiry_seconds = 0;
+    pool.treasury = Pubkey::default();
+    pool.swept_through_index = 0;
+    pool.pending_sweep_through = 0;
+    pool.sweep_executable_at = 0;
+
+    if let Some(stats) = ctx.accounts.protocol_stats.as_deref_mut() {
+        stats.add_pool(pool);
+        pool.in_protocol_stats = true;
+    }
+
+    emit!(ShieldedPoolCreated {
+        pool: pool.key(),
+        pool_id,
+        creator: ctx.accounts.creator.key(),
+        reward_rate_bps,
+        lockup_epochs,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateShieldedPool,
+        pool.key(),
+        pool_id,
+        [0u8; 32],
+        pool.merkle_root,
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn set_pool_authority(ctx: Context<SetPoolAuthority>, new_authority: Pubkey) -> Result<()> {
+    let pool = &mut ctx.accounts.shielded_pool;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(new_authority != Pubkey::default(), ErrorCode::Unauthorized);
+    // Governance takes the pool with its rules, never without them
+    require!(
+        new_authority != pool_governance_address(&pool.key()).0,
+        ErrorCode::UseGovernPool
+    );
+
+    let previous_authority = pool.authority;
+    pool.authority = new_authority;
+
+    emit!(PoolAuthorityChanged {
+        pool: pool.key(),
+        previous_authority,
+        new_authority,
+        timestamp: current_time,
+    });
+
+    append_audit_entry(
+        ctx.accounts.audit_log.as_mut(),
+        RecordKind::SetPoolAuthority,
+        new_authority.to_bytes(),
+        0,
+        current_time,
+    );
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::SetPoolAuthority,
+        pool.key(),
+        new_authority.to_bytes(),
+        previous_authority.to_bytes(),
+        pool.merkle_root,
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn shield_deposit(
+    ctx: Context<ShieldDeposit>,
+    note_commitment: [u8; 32],      // H(amount || blinding || owner_commitment)
+    encrypted_note: [u8; 64],        // Encrypted note data (only owner can decrypt)
+    range_proof: Vec<u8>,            // ZK proof that amount is valid (Bulletproof)
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    let deposit = deposit_note(
+        &mut ctx.accounts.shielded_pool,
+        &mut ctx.accounts.note_account,
+        note_commitment,
+        encrypted_note,
+        &range_proof,
+        ctx.bumps.note_account,
+        current_time,
+    )?;
+    emit_cpi!(deposit);
+
+    if let Some(referral) = ctx.accounts.referral.as_deref_mut() {
+        credit_referral(
+            referral,
+            ctx.accounts.referral_program.as_deref(),
+            Some(ctx.accounts.pool_vault.to_account_info()),
+            ctx.accounts.depositor.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            1,
+        )?;
+    }
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ShieldDeposit,
+        ctx.accounts.shielded_pool.key(),
+        note_commitment,
+        [0u8; 32],
+        ctx.accounts.shielded_pool.merkle_root,
+        ctx.accounts.note_account.note_index as u64,
+        current_time,
+    ));
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+pub(crate) fn shield_deposit_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ShieldDepositBatch<'info>>,
+    note_commitments: Vec<[u8; 32]>,
+    encrypted_notes: Vec<[u8; 64]>,
+    range_proof: Vec<u8>,
+) -> Result<()> {
+    let deposits = note_commitments.len();
+    deposit_batch(ctx, note_commitments, encrypted_notes, range_proof, deposits)
+}
+
+/// Insert a batch of notes of which only `deposits` are real deposits; the
+/// rest are decoys, which go into the tree but not into the deposit metrics,
+/// referral credit or protocol totals
+fn deposit_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ShieldDepositBatch<'info>>,
+    note_commitments: Vec<[u8; 32]>,
+    encrypted_notes: Vec<[u8; 64]>,
+    range_proof: Vec<u8>,
+    deposits: usize,
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let decoys = (note_commitments.len() - deposits) as u32;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool).without_notes(decoys);
+    let remaining_accounts = clock::strip(ctx.remaining_accounts);
+    let pool = &mut ctx.accounts.shielded_pool;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(pool.is_active, ErrorCode::PoolNotActive);
+    require!(
+        !note_commitments.is_empty() && note_commitments.len() <= MAX_DEPOSIT_BATCH,
+        ErrorCode::InvalidDepositBatch
+    );
+    require!(encrypted_notes.len() == note_commitments.len(), ErrorCode::InvalidDepositBatch);
+    require!(
+        remaining_accounts.len() == note_commitments.len(),
+        ErrorCode::InvalidDepositBatch
+    );
+    require!(
+        pool.next_note_index as usize + note_commitments.len() <= MAX_SHIELDED_NOTES,
+        ErrorCode::PoolFull
+    );
+    require!(
+        verify_aggregate_range_proof(&note_commitments, &range_proof),
+        ErrorCode::InvalidRangeProof
+    );
+
+    for ((note_commitment, encrypted_note), note_account) in note_commitments
+        .iter()
+        .zip(&encrypted_notes)
+        .zip(remaining_accounts)
+    {
+        let note_index = pool.next_note_index;
+        create_pool_note_account(
+            pool,
+            note_account,
+            &ctx.accounts.depositor,
+            &ctx.accounts.system_program,
+            *note_commitment,
+            *encrypted_note,
+            current_time,
+        )?;
+
+        emit_cpi!(ShieldedDeposit {
+            pool: pool.key(),
+            note_commitment: *note_commitment,
+            encrypted_note: *encrypted_note,
+            note_index,
+            merkle_root: pool.merkle_root,
+            timestamp: current_time,
+            // Amount is NEVER included - true privacy!
+        });
+        emit_cpi!(VeilRecord::new(
+            RecordKind::ShieldDepositBatch,
+            pool.key(),
+            *note_commitment,
+            [0u8; 32],
+            pool.merkle_root,
+            note_index as u64,
+            current_time,
+        ));
+    }
+
+    for _ in 0..deposits {
+        pool.record_deposit(current_time);
+    }
+    if let Some(referral) = ctx.accounts.referral.as_deref_mut() {
+        credit_referral(
+            referral,
+            ctx.accounts.referral_program.as_deref(),
+            ctx.accounts.pool_vault.as_ref().map(|vault| vault.to_account_info()),
+            ctx.accounts.depositor.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            deposits as u64,
+        )?;
+    }
+
+    debug_log!(
+        "shield_deposit_batch",
+        pool = pool.key(),
+        first_note_index = pool.next_note_index - note_commitments.len() as u32,
+        notes = note_commitments.len(),
+        merkle_root = Hex(&pool.merkle_root),
+    );
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+pub(crate) fn shield_deposit_with_decoys<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ShieldDepositBatch<'info>>,
+    note_commitments: Vec<[u8; 32]>,
+    encrypted_notes: Vec<[u8; 64]>,
+    range_proof: Vec<u8>,
+) -> Result<()> {
+    let decoy_outputs = ctx.accounts.shielded_pool.decoy_outputs as usize;
+    require!(decoy_outputs > 0, ErrorCode::DecoyOutputsDisabled);
+    require!(
+        note_commitments.len() == decoy_outputs + 1,
+        ErrorCode::InvalidDecoyCount
+    );
+
+    // Repeated or empty leaves would mark themselves as padding
+    for (i, note_commitment) in note_commitments.iter().enumerate() {
+        require!(
+            *note_commitment != [0u8; 32] && !note_commitments[..i].contains(note_commitment),
+            ErrorCode::InvalidDecoyCount
+        );
+    }
+
+    deposit_batch(ctx, note_commitments, encrypted_notes, range_proof, 1)
+}
+
+pub(crate) fn configure_decoy_outputs(ctx: Context<ConfigureDecoyOutputs>, decoy_outputs: u8) -> Result<()> {
+    let pool = &mut ctx.accounts.shielded_pool;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(
+        (decoy_outputs as usize) < MAX_DEPOSIT_BATCH,
+        ErrorCode::InvalidDecoyCount
+    );
+    pool.decoy_outputs = decoy_outputs;
+
+    append_audit_entry(
+        ctx.accounts.audit_log.as_mut(),
+        RecordKind::ConfigureDecoyOutputs,
+        [0u8; 32],
+        decoy_outputs as u64,
+        current_time,
+    );
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ConfigureDecoyOutputs,
+        pool.key(),
+        [0u8; 32],
+        [0u8; 32],
+        pool.merkle_root,
+        decoy_outputs as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn configure_withdrawal_screener(ctx: Context<ConfigureWithdrawalScreener>) -> Result<()> {
+    let pool = &mut ctx.accounts.shielded_pool;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    let screener_program = match &ctx.accounts.screener_program {
+        Some(program) => {
+            require!(
+                program.executable && program.key() != crate::ID,
+                ErrorCode::InvalidScreenerProgram
+            );
+            program.key()
+        }
+        None => Pubkey::default(),
+    };
+    pool.screener_program = screener_program;
+
+    append_audit_entry(
+        ctx.accounts.audit_log.as_mut(),
+        RecordKind::ConfigureWithdrawalScreener,
+        screener_program.to_bytes(),
+        0,
+        current_time,
+    );
+
+    emit!(WithdrawalScreenerConfigured {
+        pool: pool.key(),
+        screener_program: pool.screens_withdrawals().then_some(screener_program),
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ConfigureWithdrawalScreener,
+        pool.key(),
+        screener_program.to_bytes(),
+        [0u8; 32],
+        pool.merkle_root,
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+// ============================================
+// REWARD CURVES - Rate Schedules for Shielded Rewards
+// ============================================
+//
+// Instead of one flat `reward_rate_bps`, a pool may schedule up to
+// MAX_REWARD_CURVE_POINTS rate changes, e.g. a decaying emissions
+// schedule. Each note snapshots the rate in effect when it is created,
+// and reward proofs commit to the whole curve so the circuit can evaluate
+// it at the note's hidden deposit time. That only gives the snapshotted
+// rate if the past never changes: points already in effect must be kept
+// as they are, and new points must start in the future.
+
+pub(crate) fn configure_reward_curve(
+    ctx: Context<ConfigureRewardCurve>,
+    reward_curve: Vec<RewardCurvePoint>,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.shielded_pool;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(
+        reward_curve.len() <= MAX_REWARD_CURVE_POINTS &&
+            reward_curve.iter().all(|point| point.rate_bps <= 10000) &&
+            reward_curve.windows(2).all(|pair| pair[0].starts_at < pair[1].starts_at),
+        ErrorCode::InvalidRewardCurve
+    );
+
+    let current = pool.reward_curve_points();
+    let started = current.iter().take_while(|point| point.starts_at <= current_time).count();
+    require!(
+        reward_curve.get(..started) == Some(&current[..started]) &&
+            reward_curve[started..].iter().all(|point| point.starts_at > current_time),
+        ErrorCode::RewardCurveHistoryChanged
+    );
+
+    pool.reward_curve = [RewardCurvePoint::default(); MAX_REWARD_CURVE_POINTS];
+    pool.reward_curve[..reward_curve.len()].copy_from_slice(&reward_curve);
+    pool.reward_curve_len = reward_curve.len() as u8;
+    let curve_hash = compute_reward_curve_hash(pool.reward_rate_bps, pool.reward_curve_points());
+
+    append_audit_entry(
+        ctx.accounts.audit_log.as_mut(),
+        RecordKind::ConfigureRewardCurve,
+        curve_hash,
+        reward_curve.len() as u64,
+        current_time,
+    );
+
+    emit!(RewardCurveConfigured {
+        pool: pool.key(),
+        base_rate_bps: pool.reward_rate_bps,
+        reward_curve,
+        curve_hash,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ConfigureRewardCurve,
+        pool.key(),
+        curve_hash,
+        [0u8; 32],
+        pool.merkle_root,
+        pool.reward_curve_len as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn shield_withdraw<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ShieldWithdraw<'info>>,
+    nullifier: [u8; 32],            // H(note_commitment || owner_secret) - prevents double-spend
+    merkle_proof: [[u8; 32]; 8],    // Proof that note is in tree (depth 8)
+    merkle_path_indices: u8,         // Bit flags for left/right path
+    withdrawal_proof: Vec<u8>,       // ZK proof of valid withdrawal
+    output_commitment: [u8; 32],     // New note commitment (for change, or zero for full withdraw)
+    circuit_id: [u8; 32],           // Circuit the proof was made for (`Circuit::id`)
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    require_proof_len(&withdrawal_proof)?;
+
+    let pool = &mut ctx.accounts.shielded_pool;
+    let nullifier_account = &mut ctx.accounts.nullifier_account;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(pool.is_active, ErrorCode::PoolNotActive);
+
+    // Verify nullifier hasn't been used (prevents double-spend)
+    require!(!is_nullifier_used(pool, &nullifier), ErrorCode::NullifierAlreadyUsed);
+
+    // Verify Merkle proof (note exists in the tree)
+    let merkle_valid = verify_merkle_proof(
+        &pool.merkle_root,
+        &merkle_proof,
+        merkle_path_indices,
+        &nullifier, // Nullifier is derived from note, so we verify against it
+    );
+    require!(merkle_valid, ErrorCode::InvalidMerkleProof);
+
+    // Verify withdrawal proof (Groth16 format)
+    require!(withdrawal_proof.len() >= 256, ErrorCode::InvalidWithdrawalProof);
+
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::Withdrawal, &circuit_id)?;
+
+    // The withdrawal proof proves:
+    // - nullifier = H(note || secret) for a note in the tree
+    // - The withdrawal amount matches the note amount
+    // - output_commitment is valid (for change) or zero
+    // - it was generated for this pool (pool binding)
+    let proof_valid = verify_withdrawal_proof(
+        &nullifier,
+        &output_commitment,
+        &pool.spend_anchor(),
+        &compute_pool_binding(&pool.key()),
+        &withdrawal_proof,
+    );
+    require!(proof_valid, ErrorCode::InvalidWithdrawalProof);
+
+    screen_withdrawal(
+        pool,
+        ctx.accounts.screener_program.as_ref(),
+        &ctx.accounts.withdrawer,
+        &nullifier,
+        clock::strip(ctx.remaining_accounts),
+    )?;
+
+    record_filtered_nullifiers(pool, ctx.accounts.nullifier_filter.as_ref(), &[nullifier])?;
+    spend_withdrawal_note(
+        pool,
+        nullifier_account,
+        ctx.bumps.nullifier_account,
+        nullifier,
+        output_commitment,
+        current_time,
+    );
+
+    debug_log!(
+        "shield_withdraw",
+        pool = pool.key(),
+        nullifier = Hex(&nullifier),
+        output_commitment = Hex(&output_commitment),
+        merkle_root = Hex(&pool.merkle_root),
+    );
+
+    // NOTE: Amount is NEVER revealed - the SOL transfer happens via the proof
+    emit_cpi!(ShieldedWithdraw {
+        pool: pool.key(),
+        nullifier,
+        output_commitment,
+        merkle_root: pool.merkle_root,
+        timestamp: current_time,
+        // Amount is NEVER included - true privacy!
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ShieldWithdraw,
+        pool.key(),
+        nullifier,
+        output_commitment,
+        pool.merkle_root,
+        pool.next_note_index as u64,
+        current_time,
+    ));
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+pub(crate) fn shield_withdraw_aggregated<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ShieldWithdrawAggregated<'info>>,
+    nullifiers: Vec<[u8; 32]>,
+    output_commitments: Vec<[u8; 32]>,
+    aggregate_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    let remaining_accounts = clock::strip(ctx.remaining_accounts);
+    require_proof_len(&aggregate_proof)?;
+
+    let pool_key = ctx.accounts.shielded_pool.key();
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+    let pool = &ctx.accounts.shielded_pool;
+
+    require!(pool.is_active, ErrorCode::PoolNotActive);
+    require!(!pool.screens_withdrawals(), ErrorCode::WithdrawalNotScreenable);
+    require!(
+        !nullifiers.is_empty() && nullifiers.len() <= MAX_AGGREGATE_WITHDRAWALS,
+        ErrorCode::InvalidAggregateWithdrawal
+    );
+    require!(
+        output_commitments.len() == nullifiers.len(),
+        ErrorCode::InvalidAggregateWithdrawal
+    );
+    require!(
+        remaining_accounts.len() == nullifiers.len(),
+        ErrorCode::InvalidAggregateWithdrawal
+    );
+    let change_notes = output_commitments.iter().filter(|c| **c != [0u8; 32]).count();
+    require!(
+        pool.next_note_index as usize + change_notes <= MAX_SHIELDED_NOTES,
+        ErrorCode::PoolFull
+    );
+    for (i, nullifier) in nullifiers.iter().enumerate() {
+        require!(!nullifiers[..i].contains(nullifier), ErrorCode::NullifierAlreadyUsed);
+    }
+
+    let mut public_signals = Vec::with_capacity(3 + 2 * nullifiers.len());
+    public_signals.push(pool.spend_anchor());
+    public_signals.push(encode_u64_signal(nullifiers.len() as u64));
+    for (nullifier, output_commitment) in nullifiers.iter().zip(&output_commitments) {
+        public_signals.push(*nullifier);
+        public_signals.push(*output_commitment);
+    }
+    public_signals.push(compute_pool_binding(&pool.key()));
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::AggregateWithdrawal, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::AggregateWithdrawal, &public_signals, &aggregate_proof),
+        ErrorCode::InvalidAggregateProof
+    );
+
+    let proven_root = pool.merkle_root;
+    for (nullifier, record) in nullifiers.iter().zip(remaining_accounts) {
+        record_nullifier(
+            &pool_key,
+            nullifier,
+            record,
+            &ctx.accounts.withdrawer,
+            &ctx.accounts.system_program,
+            current_time,
+        )?;
+    }
+
+    record_filtered_nullifiers(pool, ctx.accounts.nullifier_filter.as_ref(), &nullifiers)?;
+
+    let pool = &mut ctx.accounts.shielded_pool;
+    for output_commitment in &output_commitments {
+        record_pool_withdrawal(pool, *output_commitment);
+    }
+
+    debug_log!(
+        "shield_withdraw_aggregated",
+        pool = pool_key,
+        withdrawals = nullifiers.len(),
+        proven_root = Hex(&proven_root),
+        merkle_root = Hex(&pool.merkle_root),
+    );
+
+    for (leg, (nullifier, output_commitment)) in nullifiers.iter().zip(&output_commitments).enumerate() {
+        emit_cpi!(VeilRecord::new(
+            RecordKind::ShieldWithdrawAggregated,
+            pool_key,
+            *nullifier,
+            *output_commitment,
+            pool.merkle_root,
+            leg as u64,
+            current_time,
+        ));
+    }
+
+    emit_cpi!(ShieldedWithdrawAggregated {
+        pool: pool_key,
+        nullifiers,
+        output_commitments,
+        proven_root,
+        merkle_root: pool.merkle_root,
+        timestamp: current_time,
+        // Amounts are NEVER included - true privacy!
+    });
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+pub(crate) fn shield_consolidate<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ShieldConsolidate<'info>>,
+    nullifiers: Vec<[u8; 32]>,
+    merkle_path_indices: Vec<u8>,
+    output_commitment: [u8; 32],
+    encrypted_note: [u8; 64],
+    consolidation_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    let remaining_accounts = clock::strip(ctx.remaining_accounts);
+    require_proof_len(&consolidation_proof)?;
+
+    let pool_key = ctx.accounts.shielded_pool.key();
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+    let pool = &ctx.accounts.shielded_pool;
+
+    require!(pool.is_active, ErrorCode::PoolNotActive);
+    require!(pool.next_note_index < MAX_SHIELDED_NOTES as u32, ErrorCode::PoolFull);
+    require!(
+        !nullifiers.is_empty() && nullifiers.len() <= MAX_CONSOLIDATE_INPUTS,
+        ErrorCode::InvalidConsolidation
+    );
+    require!(merkle_path_indices.len() == nullifiers.len(), ErrorCode::InvalidConsolidation);
+    require!(
+        remaining_accounts.len() == nullifiers.len() * (1 + MERKLE_TREE_DEPTH),
+        ErrorCode::InvalidConsolidation
+    );
+    require!(output_commitment != [0u8; 32], ErrorCode::InvalidConsolidation);
+
+    for (i, nullifier) in nullifiers.iter().enumerate() {
+        require!(!nullifiers[..i].contains(nullifier), ErrorCode::NullifierAlreadyUsed);
+    }
+
+    let inputs = remaining_accounts.chunks(1 + MERKLE_TREE_DEPTH);
+    for ((nullifier, path_indices), input) in nullifiers.iter().zip(&merkle_path_indices).zip(inputs) {
+        let mut merkle_proof = [[0u8; 32]; MERKLE_TREE_DEPTH];
+        for (sibling, account) in merkle_proof.iter_mut().zip(&input[1..]) {
+            *sibling = account.key.to_bytes();
+        }
+        require!(
+            verify_merkle_proof(&pool.merkle_root, &merkle_proof, *path_indices, nullifier),
+            ErrorCode::InvalidMerkleProof
+        );
+
+        record_nullifier(
+            &pool_key,
+            nullifier,
+            &input[0],
+            &ctx.accounts.owner,
+            &ctx.accounts.system_program,
+            current_time,
+        )?;
+    }
+
+    let mut public_signals = vec![pool.spend_anchor(), output_commitment];
+    public_signals.extend_from_slice(&nullifiers);
+    public_signals.push(compute_pool_binding(&pool.key()));
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::Consolidation, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::Consolidation, &public_signals, &consolidation_proof),
+        ErrorCode::InvalidConsolidationProof
+    );
+
+    let pool = &mut ctx.accounts.shielded_pool;
+    pool.nullifier_count += nullifiers.len() as u32;
+    record_filtered_nullifiers(pool, ctx.accounts.nullifier_filter.as_ref(), &nullifiers)?;
+    append_pool_note(
+        pool,
+        &mut ctx.accounts.note_account,
+        output_commitment,
+        encrypted_note,
+        ctx.bumps.note_account,
+        current_time,
+    );
+
+    debug_log!(
+        "shield_consolidate",
+        pool = pool_key,
+        inputs = nullifiers.len(),
+        output_commitment = Hex(&output_commitment),
+        merkle_root = Hex(&pool.merkle_root),
+    );
+
+    for nullifier in &nullifiers {
+        emit_cpi!(VeilRecord::new(
+            RecordKind::ShieldConsolidate,
+            pool_key,
+            *nullifier,
+            output_commitment,
+            pool.merkle_root,
+            ctx.accounts.note_account.note_index as u64,
+            current_time,
+        ));
+    }
+
+    emit_cpi!(ShieldedConsolidate {
+        pool: pool_key,
+        nullifiers,
+        output_commitment,
+        merkle_root: pool.merkle_root,
+        timestamp: current_time,
+        // Input and output amounts are NEVER included - true privacy!
+    });
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+pub(crate) fn claim_shielded_rewards(
+    ctx: Context<ClaimShieldedRewards>,
+    stake_nullifier: [u8; 32],       // Nullifier for the original stake note
+    merkle_proof: [[u8; 32]; 8],     // Proof note is in tree
+    merkle_path_indices: u8,
+    reward_proof: Vec<u8>,            // ZK proof of correct reward calculation
+    new_note_commitment: [u8; 32],    // New note = stake + rewards
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    require_proof_len(&reward_proof)?;
+
+    let pool = &mut ctx.accounts.shielded_pool;
+    let nullifier_account = &mut ctx.accounts.nullifier_account;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(pool.is_active, ErrorCode::PoolNotActive);
+
+    // Verify nullifier hasn't been used
+    require!(!is_nullifier_used(pool, &stake_nullifier), ErrorCode::NullifierAlreadyUsed);
+
+    // Verify Merkle proof
+    let merkle_valid = verify_merkle_proof(
+        &pool.merkle_root,
+        &merkle_proof,
+        merkle_path_indices,
+        &stake_nullifier,
+    );
+    require!(merkle_valid, ErrorCode::InvalidMerkleProof);
+
+    // Verify reward proof
+    // The proof demonstrates:
+    // - Original stake amount (hidden)
+    // - Time elapsed since stake
+    // - Reward rate the pool's curve gave at deposit time
+    // - Correct reward = stake * rate * time
+    // - new_note = stake + reward
+    // - It was generated for this pool (pool binding), so it cannot be
+    //   replayed against another pool with the same curve
+    require!(reward_proof.len() >= 256, ErrorCode::InvalidRewardProof);
+
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::Rewards, &circuit_id)?;
+    let proof_valid = verify_reward_proof(
+        &stake_nullifier,
+        &new_note_commitment,
+        &compute_reward_curve_hash(pool.reward_rate_bps, pool.reward_curve_points()),
+        &compute_pool_binding(&pool.key()),
+        current_time,
+        &reward_proof,
+    );
+    require!(proof_valid, ErrorCode::InvalidRewardProof);
+
+    // Record nullifier
+    nullifier_account.pool = pool.key();
+    nullifier_account.nullifier = stake_nullifier;
+    nullifier_account.spent_at = current_time;
+    nullifier_account.bump = ctx.bumps.nullifier_account;
+    nullifier_account.version = NullifierRecord::VERSION;
+
+    pool.nullifier_count += 1;
+    record_filtered_nullifiers(pool, ctx.accounts.nullifier_filter.as_ref(), &[stake_nullifier])?;
+
+    // Add new note with stake + rewards
+    let new_root = insert_note_to_merkle_tree(
+        &pool.merkle_root,
+        &new_note_commitment,
+        pool.next_note_index,
+    );
+    pool.merkle_root = new_root;
+    pool.next_note_index += 1;
+
+    debug_log!(
+        "claim_shielded_rewards",
+        pool = pool.key(),
+        nullifier = Hex(&stake_nullifier),
+        merkle_root = Hex(&pool.merkle_root),
+    );
+
+    emit_cpi!(ShieldedRewardsClaimed {
+        pool: pool.key(),
+        stake_nullifier,
+        new_note_commitment,
+        merkle_root: pool.merkle_root,
+        timestamp: current_time,
+        // Reward amount is NEVER included - true privacy!
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ClaimShieldedRewards,
+        pool.key(),
+        stake_nullifier,
+        new_note_commitment,
+        pool.merkle_root,
+        pool.next_note_index as u64,
+        current_time,
+    ));
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+// ============================================
+// CLAIMABLE NOTES - Gift Links
+// ============================================
+//
+// A sender deposits a note whose spending key is derived from a one-time
+// secret s and shares s out of band (e.g. as a link). Only
+// H(DOMAIN_CLAIM_KEY, s) is stored. The recipient proves knowledge of s
+// to nullify the gift note and re-create its value under their own keys,
+// so they need no Veil wallet before receiving. Until claimed, the sender
+// can take the note back the same way since they also know s.
+
+pub(crate) fn create_claimable_note(
+    ctx: Context<CreateClaimableNote>,
+    claim_key_hash: [u8; 32],       // H(DOMAIN_CLAIM_KEY, claim_secret)
+    note_commitment: [u8; 32],      // Note owned by a key derived from claim_secret
+    encrypted_note: [u8; 64],       // Note data encrypted to claim_secret
+    range_proof: Vec<u8>,
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    let pool = &mut ctx.accounts.shielded_pool;
+    let note_account = &mut ctx.accounts.note_account;
+    let claimable = &mut ctx.accounts.claimable_note;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(pool.is_active, ErrorCode::PoolNotActive);
+    require!(pool.next_note_index < MAX_SHIELDED_NOTES as u32, ErrorCode::PoolFull);
+    require!(claim_key_hash != [0u8; 32], ErrorCode::InvalidClaimableNote);
+    require!(range_proof.len() >= 64, ErrorCode::InvalidRangeProof);
+    require!(
+        verify_range_proof(&note_commitment, &range_proof),
+        ErrorCode::InvalidRangeProof
+    );
+
+    append_pool_note(
+        pool,
+        note_account,
+        note_commitment,
+        encrypted_note,
+        ctx.bumps.note_account,
+        current_time,
+    );
+    pool.record_deposit(current_time);
+
+    claimable.pool = pool.key();
+    claimable.claim_key_hash = claim_key_hash;
+    claimable.note_commitment = note_commitment;
+    claimable.note_index = note_account.note_index;
+    claimable.creator = ctx.accounts.sender.key();
+    claimable.created_at = current_time;
+    claimable.claimed_at = 0;
+    claimable.is_claimed = false;
+    claimable.bump = ctx.bumps.claimable_note;
+    claimable.version = ClaimableNote::VERSION;
+
+    emit_cpi!(ClaimableNoteCreated {
+        pool: pool.key(),
+        claimable_note: claimable.key(),
+        note_commitment,
+        note_index: note_account.note_index,
+        merkle_root: pool.merkle_root,
+        timestamp: current_time,
+        // Amount and claim secret are NEVER included - true privacy!
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateClaimableNote,
+        pool.key(),
+        note_commitment,
+        claimable.key().to_bytes(),
+        pool.merkle_root,
+        note_account.note_index as u64,
+        current_time,
+    ));
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+pub(crate) fn claim_note(
+    ctx: Context<ClaimNote>,
+    nullifier: [u8; 32],
+    output_commitment: [u8; 32],
+    encrypted_note: [u8; 64],
+    claim_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    require_proof_len(&claim_proof)?;
+
+    let pool = &mut ctx.accounts.shielded_pool;
+    let claimable = &mut ctx.accounts.claimable_note;
+    let nullifier_account = &mut ctx.accounts.nullifier_account;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(pool.is_active, ErrorCode::PoolNotActive);
+    require!(pool.next_note_index < MAX_SHIELDED_NOTES as u32, ErrorCode::PoolFull);
+    require!(!claimable.is_claimed, ErrorCode::NoteAlreadyClaimed);
+    require!(output_commitment != [0u8; 32], ErrorCode::InvalidClaimableNote);
+
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::Claim, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::Claim, 
+            &[
+                claimable.claim_key_hash,
+                claimable.note_commitment,
+                nullifier,
+                output_commitment,
+            ],
+            &claim_proof,
+        ),
+        ErrorCode::InvalidClaimProof
+    );
+
+    nullifier_account.pool = pool.key();
+    nullifier_account.nullifier = nullifier;
+    nullifier_account.spent_at = current_time;
+    nullifier_account.bump = ctx.bumps.nullifier_account;
+    nullifier_account.version = NullifierRecord::VERSION;
+    pool.nullifier_count += 1;
+    record_filtered_nullifiers(pool, ctx.accounts.nullifier_filter.as_ref(), &[nullifier])?;
+
+    append_pool_note(
+        pool,
+        &mut ctx.accounts.note_account,
+        output_commitment,
+        encrypted_note,
+        ctx.bumps.note_account,
+        current_time,
+    );
+
+    claimable.is_claimed = true;
+    claimable.claimed_at = current_time;
+
+    emit_cpi!(ClaimableNoteClaimed {
+        pool: pool.key(),
+        claimable_note: claimable.key(),
+        nullifier,
+        output_commitment,
+        merkle_root: pool.merkle_root,
+        timestamp: current_time,
+        // Amount and recipient are NEVER included - true privacy!
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ClaimNote,
+        pool.key(),
+        nullifier,
+        output_commitment,
+        pool.merkle_root,
+        pool.next_note_index as u64,
+        current_time,
+    ));
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+// ============================================
+// PROOF OF RESERVE - Shielded Pool Solvency
+// ============================================
+//
+// The pool authority proves that the values of all notes in the tree
+// that have not been nullified sum to the vault balance, without
+// revealing any individual note. Each proof is recorded against the
+// Merkle root and nullifier count it was made for, so auditors and UIs
+// can check whether the pool's current state is attested.
+
+pub(crate) fn publish_reserve_proof(
+    ctx: Context<PublishReserveProof>,
+    reserve_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    let pool = &ctx.accounts.shielded_pool;
+    let vault_balance = ctx.accounts.pool_vault.lamports();
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    // The spend anchor commits to the sweep frontier, so swept notes are
+    // excluded from the reserves the vault must cover
+    let public_signals = [
+        compute_reserve_tag(&pool.key()),
+        pool.spend_anchor(),
+        encode_u64_signal(pool.total_notes as u64),
+        encode_u64_signal(pool.nullifier_count as u64),
+        encode_u64_signal(vault_balance),
+    ];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::Reserve, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::Reserve, &public_signals, &reserve_proof),
+        ErrorCode::InvalidReserveProof
+    );
+
+    let attestation = &mut ctx.accounts.reserve_attestation;
+    attestation.pool = pool.key();
+    attestation.merkle_root = pool.merkle_root;
+    attestation.total_notes = pool.total_notes;
+    attestation.nullifier_count = pool.nullifier_count;
+    attestation.vault_balance = vault_balance;
+    attestation.proof_hash = compute_proof_hash(&reserve_proof, &public_signals);
+    attestation.published_by = ctx.accounts.authority.key();
+    attestation.published_at = current_time;
+    attestation.published_slot = clock::now(ctx.remaining_accounts)?.slot;
+    attestation.bump = ctx.bumps.reserve_attestation;
+    attestation.version = ReserveAttestation::VERSION;
+
+    debug_log!(
+        "publish_reserve_proof",
+        pool = pool.key(),
+        root = Hex(&pool.merkle_root),
+        vault_balance = vault_balance,
+    );
+
+    emit!(ReserveProofPublished {
+        pool: pool.key(),
+        attestation: attestation.key(),
+        merkle_root: pool.merkle_root,
+        vault_balance,
+        proof_hash: attestation.proof_hash,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::PublishReserveProof,
+        attestation.key(),
+        attestation.proof_hash,
+        [0u8; 32],
+        pool.merkle_root,
+        vault_balance,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+// ============================================
+// DEPOSIT RECEIPTS - Opt-in Participation Proofs
+// ============================================
+//
+// For compliance-friendly pools. A depositor may ask for a receipt: a
+// Token-2022 NFT with the NonTransferable extension, minted to an
+// address of their choice. It proves the holder deposited into the pool
+// (and which note index) without revealing the amount. Receipts link
+// that address to the deposit, so this is a privacy-off mode; plain
+// `shield_deposit` stays available and issues none.
+
+pub(crate) fn enable_deposit_receipts(ctx: Context<EnableDepositReceipts>) -> Result<()> {
+    let config = &mut ctx.accounts.receipt_config;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    config.pool = ctx.accounts.shielded_pool.key();
+    config.authority = ctx.accounts.authority.key();
+    config.receipts_issued = 0;
+    config.created_at = current_time;
+    config.bump = ctx.bumps.receipt_config;
+    config.version = DepositReceiptConfig::VERSION;
+
+    emit!(DepositReceiptsEnabled {
+        config: config.key(),
+        pool: config.pool,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::EnableDepositReceipts,
+        config.key(),
+        [0u8; 32],
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn shield_deposit_with_receipt(
+    ctx: Context<ShieldDepositWithReceipt>,
+    note_commitment: [u8; 32],
+    encrypted_note: [u8; 64],
+    range_proof: Vec<u8>,
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+    let note_index = ctx.accounts.shielded_pool.next_note_index;
+
+    let deposit = deposit_note(
+        &mut ctx.accounts.shielded_pool,
+        &mut ctx.accounts.note_account,
+        note_commitment,
+        encrypted_note,
+        &range_proof,
+        ctx.bumps.note_account,
+        current_time,
+    )?;
+    emit_cpi!(deposit);
+
+    mint_deposit_receipt(ctx.accounts, note_index, ctx.bumps.receipt_mint)?;
+    ctx.accounts.receipt_config.receipts_issued += 1;
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ShieldDepositWithReceipt,
+        ctx.accounts.shielded_pool.key(),
+        note_commitment,
+        ctx.accounts.receipt_mint.key().to_bytes(),
+        ctx.accounts.shielded_pool.merkle_root,
+        note_index as u64,
+        current_time,
+    ));
+
+    emit!(DepositReceiptMinted {
+        pool: ctx.accounts.shielded_pool.key(),
+        note_index,
+        mint: ctx.accounts.receipt_mint.key(),
+        owner: ctx.accounts.receipt_owner.key(),
+        timestamp: current_time,
+        // Note: Amount is NEVER included - the receipt proves participation only
+    });
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+// ============================================
+// CPI DEPOSITS - Composable Entry Point
+// ============================================
+//
+// Lets another protocol shield its users' funds inside its own flow. The
+// pool authority allowlists a caller program, which then signs
+// `shield_deposit_cpi` as its `CPI_DEPOSITOR_SEED` PDA: only that program
+// can produce the signature, so the deposit is attributed to it. The
+// instruction must arrive through CPI and takes the same pool lock as
+// every other note insertion, so a callback cannot re-enter mid-deposit.
+
+pub(crate) fn allow_deposit_caller(ctx: Context<AllowDepositCaller>) -> Result<()> {
+    let caller_program = &ctx.accounts.caller_program;
+    require!(
+        caller_program.executable && caller_program.key() != crate::ID,
+        ErrorCode::InvalidDepositCaller
+    );
+
+    let caller = &mut ctx.accounts.deposit_caller;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    caller.pool = ctx.accounts.shielded_pool.key();
+    caller.program = caller_program.key();
+    caller.depositor = cpi_depositor_address(&caller.program).0;
+    caller.deposit_count = 0;
+    caller.created_at = current_time;
+    caller.bump = ctx.bumps.deposit_caller;
+    caller.version = DepositCaller::VERSION;
+
+    emit!(DepositCallerAllowed {
+        pool: caller.pool,
+        program: caller.program,
+        depositor: caller.depositor,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::AllowDepositCaller,
+        caller.key(),
+        caller.program.to_bytes(),
+        caller.depositor.to_bytes(),
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn revoke_deposit_caller(ctx: Context<RevokeDepositCaller>) -> Result<()> {
+    let caller = &ctx.accounts.deposit_caller;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    emit!(DepositCallerRevoked {
+        pool: caller.pool,
+        program: caller.program,
+        deposit_count: caller.deposit_count,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RevokeDepositCaller,
+        caller.key(),
+        caller.program.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        caller.deposit_count,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn shield_deposit_cpi(
+    ctx: Context<ShieldDepositCpi>,
+    note_commitment: [u8; 32],
+    encrypted_note: [u8; 64],
+    range_proof: Vec<u8>,
+) -> Result<()> {
+    require!(
+        get_stack_height() > TRANSACTION_LEVEL_STACK_HEIGHT,
+        ErrorCode::DepositNotCpi
+    );
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    let deposit = deposit_note(
+        &mut ctx.accounts.shielded_pool,
+        &mut ctx.accounts.note_account,
+        note_commitment,
+        encrypted_note,
+        &range_proof,
+        ctx.bumps.note_account,
+        current_time,
+    )?;
+    emit_cpi!(deposit);
+
+    let caller = &mut ctx.accounts.deposit_caller;
+    caller.deposit_count += 1;
+    let note_index = ctx.accounts.note_account.note_index;
+
+    emit!(CpiDepositCredited {
+        pool: ctx.accounts.shielded_pool.key(),
+        caller_program: caller.program,
+        note_index,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ShieldDepositCpi,
+        ctx.accounts.shielded_pool.key(),
+        note_commitment,
+        caller.program.to_bytes(),
+        ctx.accounts.shielded_pool.merkle_root,
+        note_index as u64,
+        current_time,
+    ));
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+// ============================================
+// NOTE EXPIRY - Sweeping Stranded Funds
+// ============================================
+//
+// A pool can opt into note expiry. Once every note in a prefix of the
+// tree is older than the expiry, the pool authority may announce a
+// sweep of that prefix; after NOTE_SWEEP_NOTICE_SECONDS it moves the
+// value of the prefix's still-unspent notes to the pool's treasury.
+// Swept notes stay in the tree, but spend and reserve proofs commit to
+// the sweep frontier through `ShieldedPool::spend_anchor`, so they can
+// no longer be spent and no longer count towards the reserves.
+
+pub(crate) fn configure_note_expiry(
+    ctx: Context<ConfigureNoteExpiry>,
+    note_expiry_seconds: i64,
+    treasury: Pubkey,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.shielded_pool;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(pool.pending_sweep_through == 0, ErrorCode::NoteSweepPending);
+    require!(
+        note_expiry_seconds == 0 ||
+            (note_expiry_seconds >= MIN_NOTE_EXPIRY_SECONDS && treasury != Pubkey::default()),
+        ErrorCode::InvalidNoteExpiry
+    );
+
+    pool.note_expiry_seconds = note_expiry_seconds;
+    pool.treasury = treasury;
+
+    emit!(NoteExpiryConfigured {
+        pool: pool.key(),
+        note_expiry_seconds,
+        treasury,
+        timestamp: current_time,
+    });
+
+    append_audit_entry(
+        ctx.accounts.audit_log.as_mut(),
+        RecordKind::ConfigureNoteExpiry,
+        treasury.to_bytes(),
+        note_expiry_seconds as u64,
+        current_time,
+    );
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ConfigureNoteExpiry,
+        pool.key(),
+        treasury.to_bytes(),
+        [0u8; 32],
+        pool.merkle_root,
+        note_expiry_seconds as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn announce_note_sweep(ctx: Context<AnnounceNoteSweep>, through_index: u32) -> Result<()> {
+    let pool = &mut ctx.accounts.shielded_pool;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(pool.note_expiry_seconds > 0, ErrorCode::InvalidNoteExpiry);
+    require!(pool.pending_sweep_through == 0, ErrorCode::NoteSweepPending);
+    require!(
+        through_index > pool.swept_through_index && through_index <= pool.next_note_index,
+        ErrorCode::InvalidNoteSweep
+    );
+    require!(
+        ctx.accounts.last_note.created_at + pool.note_expiry_seconds <= current_time,
+        ErrorCode::NotesNotExpired
+    );
+
+    pool.pending_sweep_through = through_index;
+    pool.sweep_executable_at = current_time + NOTE_SWEEP_NOTICE_SECONDS;
+
+    emit!(NoteSweepAnnounced {
+        pool: pool.key(),
+        from_index: pool.swept_through_index,
+        through_index,
+        executable_at: pool.sweep_executable_at,
+        timestamp: current_time,
+    });
+
+    append_audit_entry(
+        ctx.accounts.audit_log.as_mut(),
+        RecordKind::AnnounceNoteSweep,
+        pool.treasury.to_bytes(),
+        through_index as u64,
+        current_time,
+    );
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::AnnounceNoteSweep,
+        pool.key(),
+        pool.treasury.to_bytes(),
+        [0u8; 32],
+        pool.merkle_root,
+        through_index as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn cancel_note_sweep(ctx: Context<ConfigureNoteExpiry>) -> Result<()> {
+    let pool = &mut ctx.accounts.shielded_pool;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    let through_index = pool.pending_sweep_through;
+    require!(through_index > 0, ErrorCode::InvalidNoteSweep);
+
+    pool.pending_sweep_through = 0;
+    pool.sweep_executable_at = 0;
+
+    emit!(NoteSweepCancelled {
+        pool: pool.key(),
+        through_index,
+        timestamp: current_time,
+    });
+
+    append_audit_entry(
+        ctx.accounts.audit_log.as_mut(),
+        RecordKind::CancelNoteSweep,
+        pool.treasury.to_bytes(),
+        through_index as u64,
+        current_time,
+    );
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CancelNoteSweep,
+        pool.key(),
+        pool.treasury.to_bytes(),
+        [0u8; 32],
+        pool.merkle_root,
+        through_index as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn execute_note_sweep(
+    ctx: Context<ExecuteNoteSweep>,
+    amount: u64,
+    sweep_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    require_proof_len(&sweep_proof)?;
+
+    let pool = &mut ctx.accounts.shielded_pool;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    let from_index = pool.swept_through_index;
+    let through_index = pool.pending_sweep_through;
+    require!(through_index > 0, ErrorCode::InvalidNoteSweep);
+    require!(current_time >= pool.sweep_executable_at, ErrorCode::NoteSweepNoticeActive);
+
+    let public_signals = [
+        compute_note_sweep_tag(&pool.key(), from_index, through_index, amount),
+        pool.merkle_root,
+        encode_u64_signal(pool.nullifier_count as u64),
+        encode_u64_signal(amount),
+    ];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::NoteSweep, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::NoteSweep, &public_signals, &sweep_proof),
+        ErrorCode::InvalidNoteSweepProof
+    );
+
+    if amount > 0 {
+        let pool_key = pool.key();
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.pool_vault.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                },
+                &[&[SHIELDED_VAULT_SEED, pool_key.as_ref(), &[ctx.bumps.pool_vault]]],
+            ),
+            amount,
+        )?;
+    }
+
+    pool.swept_through_index = through_index;
+    pool.pending_sweep_through = 0;
+    pool.sweep_executable_at = 0;
+
+    debug_log!(
+        "execute_note_sweep",
+        pool = pool.key(),
+        from = from_index,
+        through = through_index,
+        amount = amount,
+    );
+
+    emit!(NoteSweepExecuted {
+        pool: pool.key(),
+        from_index,
+        through_index,
+        amount,
+        treasury: pool.treasury,
+        timestamp: current_time,
+    });
+
+    append_audit_entry(
+        ctx.accounts.audit_log.as_mut(),
+        RecordKind::ExecuteNoteSweep,
+        pool.treasury.to_bytes(),
+        amount,
+        current_time,
+    );
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ExecuteNoteSweep,
+        pool.key(),
+        pool.treasury.to_bytes(),
+        [0u8; 32],
+        pool.merkle_root,
+        amount,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+// ============================================
+// TREE CHECKPOINTS - Light Client Bootstrap
+// ============================================
+//
+// A checkpoint mirrors a pool's note tree so light clients and relayers
+// can start from its root, leaf count and filled subtrees instead of
+// replaying every deposit since genesis. Anyone can crank it forward by
+// passing the next notes, in index order, as remaining accounts.
+
+pub(crate) fn create_tree_checkpoint(ctx: Context<CreateTreeCheckpoint>) -> Result<()> {
+    let checkpoint = &mut ctx.accounts.tree_checkpoint.load_init()?;
+
+    checkpoint.pool = ctx.accounts.shielded_pool.key();
+    checkpoint.pool_root = [0u8; 32]; // Matches an empty pool
+    checkpoint.leaf_count = 0;
+    checkpoint.sequence = 0;
+    checkpoint.bump = ctx.bumps.tree_checkpoint;
+    checkpoint.version = TreeCheckpoint::VERSION;
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateTreeCheckpoint,
+        ctx.accounts.tree_checkpoint.key(),
+        ctx.accounts.shielded_pool.key().to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        clock::now(ctx.remaining_accounts)?.unix_timestamp,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn checkpoint_tree<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CheckpointTree<'info>>,
+) -> Result<()> {
+    let remaining_accounts = clock::strip(ctx.remaining_accounts);
+    let pool = &ctx.accounts.shielded_pool;
+    let pool_key = pool.key();
+    let checkpoint_key = ctx.accounts.tree_checkpoint.key();
+    let checkpoint = &mut ctx.accounts.tree_checkpoint.load_mut()?;
+    let clock = clock::now(ctx.remaining_accounts)?;
+
+    require!(!remaining_accounts.is_empty(), ErrorCode::CheckpointUpToDate);
+
+    for note_info in remaining_accounts {
+        let note = Account::<ShieldedNote>::try_from(note_info)?;
+        let index = checkpoint.leaf_count;
+
+        // The note PDA pins both the pool and the leaf index
+        let expected = Pubkey::create_program_address(
+            &[NOTE_SEED, pool_key.as_ref(), &index.to_le_bytes(), &[note.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::InvalidCheckpointNote)?;
+        require_keys_eq!(note_info.key(), expected, ErrorCode::InvalidCheckpointNote);
+
+        checkpoint.pool_root = insert_note_to_merkle_tree(&checkpoint.pool_root, &note.commitment, index);
+        checkpoint.tree_root = append_to_filled_subtrees(&mut checkpoint.filled_subtrees, &note.commitment, index);
+        checkpoint.leaf_count += 1;
+    }
+
+    // Once caught up, the replayed insertions must land on the pool's root
+    if checkpoint.leaf_count == pool.next_note_index {
+        require!(
+            constant_time_eq(&checkpoint.pool_root, &pool.merkle_root),
+            ErrorCode::CheckpointRootMismatch
+        );
+    }
+
+    checkpoint.subtree_digest = compute_subtree_digest(&checkpoint.filled_subtrees, checkpoint.leaf_count);
+    checkpoint.updated_at = clock.unix_timestamp;
+    checkpoint.updated_slot = clock.slot;
+    checkpoint.sequence += 1;
+
+    if let Some(task) = ctx.accounts.crank_task.as_mut() {
+        pay_crank_tip(
+            task,
+            &ctx.accounts.cranker,
+            CrankTaskKind::TreeCheckpoint,
+            &pool_key,
+            clock.unix_timestamp,
+        )?;
+    }
+
+    debug_log!(
+        "checkpoint_tree",
+        pool = pool_key,
+        leaf_count = checkpoint.leaf_count,
+        pool_root = Hex(&checkpoint.pool_root),
+    );
+
+    emit_cpi!(TreeCheckpointed {
+        pool: pool_key,
+        checkpoint: checkpoint_key,
+        pool_root: checkpoint.pool_root,
+        tree_root: checkpoint.tree_root,
+        subtree_digest: checkpoint.subtree_digest,
+        leaf_count: checkpoint.leaf_count,
+        sequence: checkpoint.sequence,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CheckpointTree,
+        checkpoint_key,
+        checkpoint.tree_root,
+        checkpoint.subtree_digest,
+        checkpoint.pool_root,
+        checkpoint.leaf_count as u64,
+        clock.unix_timestamp,
+    ));
+
+    Ok(())
+}
+
+// ============================================
+// NULLIFIER FILTERS - Spent-Note Pre-checks
+// ============================================
+//
+// A pool's authority can add a bloom filter of the pool's nullifiers.
+// From then on every spend must pass the filter and sets its
+// nullifier's bits, so relayers and wallets can read one account and
+// drop notes that are obviously spent instead of deriving and fetching
+// each nullifier PDA. A miss is conclusive; a hit may be a false
+// positive and is settled by the NullifierRecord.
+
+pub(crate) fn create_nullifier_filter(ctx: Context<CreateNullifierFilter>) -> Result<()> {
+    let filter_key = ctx.accounts.nullifier_filter.key();
+    let filter = &mut ctx.accounts.nullifier_filter.load_init()?;
+    let pool = &mut ctx.accounts.shielded_pool;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    filter.pool = pool.key();
+    filter.tracked_from = pool.nullifier_count;
+    filter.inserted = 0;
+    filter.bump = ctx.bumps.nullifier_filter;
+    filter.version = NullifierFilter::VERSION;
+    pool.has_nullifier_filter = true;
+
+    append_audit_entry(
+        ctx.accounts.audit_log.as_mut(),
+        RecordKind::CreateNullifierFilter,
+        filter_key.to_bytes(),
+        pool.nullifier_count as u64,
+        current_time,
+    );
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateNullifierFilter,
+        filter_key,
+        pool.key().to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        pool.nullifier_count as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn create_protocol_stats(ctx: Context<CreateProtocolStats>) -> Result<()> {
+    let stats = &mut ctx.accounts.protocol_stats;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    stats.pool_count = 0;
+    stats.total_notes = 0;
+    stats.total_nullifiers = 0;
+    stats.created_at = current_time;
+    stats.bump = ctx.bumps.protocol_stats;
+    stats.version = ProtocolStats::VERSION;
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateProtocolStats,
+        stats.key(),
+        [0u8; 32],
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn register_pool_stats(ctx: Context<RegisterPoolStats>) -> Result<()> {
+    let pool = &mut ctx.accounts.shielded_pool;
+    let stats = &mut ctx.accounts.protocol_stats;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(!pool.in_protocol_stats, ErrorCode::PoolAlreadyInProtocolStats);
+    stats.add_pool(pool);
+    pool.in_protocol_stats = true;
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RegisterPoolStats,
+        pool.key(),
+        stats.key().to_bytes(),
+        [0u8; 32],
+        pool.merkle_root,
+        stats.pool_count as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: [u8; 32])]
+pub struct CreateShieldedPool<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = ShieldedPool::LEN,
+        seeds = [SHIELDED_POOL_SEED, creator.key().as_ref(), &pool_id],
+        bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Protocol-wide totals; the new pool is counted there when passed
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPoolAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    pub authority: Signer<'info>,
+
+    /// The pool's audit log, appended to when it keeps one
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, shielded_pool.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ShieldDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = depositor,
+        space = ShieldedNote::LEN,
+        seeds = [NOTE_SEED, shielded_pool.key().as_ref(), &shielded_pool.next_note_index.to_le_bytes()],
+        bump
+    )]
+    pub note_account: Account<'info, ShieldedNote>,
+
+    /// CHECK: Pool vault for holding deposited SOL
+    #[account(
+        mut,
+        seeds = [SHIELDED_VAULT_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Referrer to credit with this deposit, if any
+    #[account(
+        mut,
+        seeds = [REFERRAL_SEED, shielded_pool.key().as_ref(), &referral.referral_commitment],
+        bump = referral.bump
+    )]
+    pub referral: Option<Account<'info, ReferralRecord>>,
+
+    /// Referral program of the pool; required with `referral`
+    #[account(
+        seeds = [REFERRAL_PROGRAM_SEED, shielded_pool.key().as_ref()],
+        bump = referral_program.bump
+    )]
+    pub referral_program: Option<Account<'info, ReferralProgram>>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ShieldDepositBatch<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    /// Pays for the note accounts
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Referrer to credit with this deposit, if any
+    #[account(
+        mut,
+        seeds = [REFERRAL_SEED, shielded_pool.key().as_ref(), &referral.referral_commitment],
+        bump = referral.bump
+    )]
+    pub referral: Option<Account<'info, ReferralRecord>>,
+
+    /// Referral program of the pool; required with `referral`
+    #[account(
+        seeds = [REFERRAL_PROGRAM_SEED, shielded_pool.key().as_ref()],
+        bump = referral_program.bump
+    )]
+    pub referral_program: Option<Account<'info, ReferralProgram>>,
+
+    /// CHECK: Pool vault receiving a referred deposit's lamports; required with `referral`
+    #[account(
+        mut,
+        seeds = [SHIELDED_VAULT_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_vault: Option<AccountInfo<'info>>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(nullifier: [u8; 32])]
+pub struct ShieldWithdraw<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = withdrawer,
+        space = NullifierRecord::LEN,
+        seeds = [NULLIFIER_SEED, shielded_pool.key().as_ref(), &nullifier],
+        bump
+    )]
+    pub nullifier_account: Account<'info, NullifierRecord>,
+
+    /// CHECK: Pool vault for releasing SOL
+    #[account(
+        mut,
+        seeds = [SHIELDED_VAULT_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub withdrawer: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The pool's nullifier filter, required once the pool keeps one
+    #[account(
+        mut,
+        seeds = [NULLIFIER_FILTER_SEED, shielded_pool.key().as_ref()],
+        bump = nullifier_filter.load()?.bump
+    )]
+    pub nullifier_filter: Option<AccountLoader<'info, NullifierFilter>>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    /// CHECK: The pool's withdrawal screener, required once the pool has
+    /// one; matched against `screener_program` in `screen_withdrawal`
+    pub screener_program: Option<UncheckedAccount<'info>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ShieldWithdrawAggregated<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    /// Pays for the nullifier records (typically a relayer or sequencer)
+    #[account(mut)]
+    pub withdrawer: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The pool's nullifier filter, required once the pool keeps one
+    #[account(
+        mut,
+        seeds = [NULLIFIER_FILTER_SEED, shielded_pool.key().as_ref()],
+        bump = nullifier_filter.load()?.bump
+    )]
+    pub nullifier_filter: Option<AccountLoader<'info, NullifierFilter>>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ShieldConsolidate<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = ShieldedNote::LEN,
+        seeds = [NOTE_SEED, shielded_pool.key().as_ref(), &shielded_pool.next_note_index.to_le_bytes()],
+        bump
+    )]
+    pub note_account: Account<'info, ShieldedNote>,
+
+    /// Pays for the nullifier records and the output note
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The pool's nullifier filter, required once the pool keeps one
+    #[account(
+        mut,
+        seeds = [NULLIFIER_FILTER_SEED, shielded_pool.key().as_ref()],
+        bump = nullifier_filter.load()?.bump
+    )]
+    pub nullifier_filter: Option<AccountLoader<'info, NullifierFilter>>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(stake_nullifier: [u8; 32])]
+pub struct ClaimShieldedRewards<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = claimer,
+        space = NullifierRecord::LEN,
+        seeds = [NULLIFIER_SEED, shielded_pool.key().as_ref(), &stake_nullifier],
+        bump
+    )]
+    pub nullifier_account: Account<'info, NullifierRecord>,
+
+    /// CHECK: Pool vault for reward distribution
+    #[account(
+        mut,
+        seeds = [SHIELDED_VAULT_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub claimer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The pool's nullifier filter, required once the pool keeps one
+    #[account(
+        mut,
+        seeds = [NULLIFIER_FILTER_SEED, shielded_pool.key().as_ref()],
+        bump = nullifier_filter.load()?.bump
+    )]
+    pub nullifier_filter: Option<AccountLoader<'info, NullifierFilter>>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(claim_key_hash: [u8; 32])]
+pub struct CreateClaimableNote<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = sender,
+        space = ShieldedNote::LEN,
+        seeds = [NOTE_SEED, shielded_pool.key().as_ref(), &shielded_pool.next_note_index.to_le_bytes()],
+        bump
+    )]
+    pub note_account: Account<'info, ShieldedNote>,
+
+    #[account(
+        init,
+        payer = sender,
+        space = ClaimableNote::LEN,
+        seeds = [CLAIMABLE_NOTE_SEED, shielded_pool.key().as_ref(), &claim_key_hash],
+        bump
+    )]
+    pub claimable_note: Account<'info, ClaimableNote>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(nullifier: [u8; 32])]
+pub struct ClaimNote<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        mut,
+        seeds = [CLAIMABLE_NOTE_SEED, shielded_pool.key().as_ref(), &claimable_note.claim_key_hash],
+        bump = claimable_note.bump
+    )]
+    pub claimable_note: Account<'info, ClaimableNote>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = NullifierRecord::LEN,
+        seeds = [NULLIFIER_SEED, shielded_pool.key().as_ref(), &nullifier],
+        bump
+    )]
+    pub nullifier_account: Account<'info, NullifierRecord>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ShieldedNote::LEN,
+        seeds = [NOTE_SEED, shielded_pool.key().as_ref(), &shielded_pool.next_note_index.to_le_bytes()],
+        bump
+    )]
+    pub note_account: Account<'info, ShieldedNote>,
+
+    /// Anyone may submit (e.g. a relayer); the proof binds the output note
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The pool's nullifier filter, required once the pool keeps one
+    #[account(
+        mut,
+        seeds = [NULLIFIER_FILTER_SEED, shielded_pool.key().as_ref()],
+        bump = nullifier_filter.load()?.bump
+    )]
+    pub nullifier_filter: Option<AccountLoader<'info, NullifierFilter>>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PublishReserveProof<'info> {
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    /// CHECK: Pool vault whose balance is attested
+    #[account(
+        seeds = [SHIELDED_VAULT_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_vault: AccountInfo<'info>,
+
+    /// One attestation per pool state (root and nullifier count)
+    #[account(
+        init,
+        payer = authority,
+        space = ReserveAttestation::LEN,
+        seeds = [
+            RESERVE_ATTESTATION_SEED,
+            shielded_pool.key().as_ref(),
+            &shielded_pool.merkle_root,
+            &shielded_pool.nullifier_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub reserve_attestation: Account<'info, ReserveAttestation>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EnableDepositReceipts<'info> {
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = DepositReceiptConfig::LEN,
+        seeds = [DEPOSIT_RECEIPTS_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub receipt_config: Account<'info, DepositReceiptConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ConfigureWithdrawalScreener<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: Screener to install; omit to turn screening off
+    pub screener_program: Option<UncheckedAccount<'info>>,
+
+    /// The pool's audit log, appended to when it keeps one
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, shielded_pool.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ConfigureRewardCurve<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    pub authority: Signer<'info>,
+
+    /// The pool's audit log, appended to when it keeps one
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, shielded_pool.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AllowDepositCaller<'info> {
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = DepositCaller::LEN,
+        seeds = [DEPOSIT_CALLER_SEED, shielded_pool.key().as_ref(), caller_program.key().as_ref()],
+        bump
+    )]
+    pub deposit_caller: Account<'info, DepositCaller>,
+
+    /// CHECK: Program being allowlisted; must be executable
+    pub caller_program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevokeDepositCaller<'info> {
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        mut,
+        seeds = [DEPOSIT_CALLER_SEED, shielded_pool.key().as_ref(), deposit_caller.program.as_ref()],
+        bump = deposit_caller.bump,
+        close = authority
+    )]
+    pub deposit_caller: Account<'info, DepositCaller>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ShieldDepositCpi<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        mut,
+        seeds = [DEPOSIT_CALLER_SEED, shielded_pool.key().as_ref(), deposit_caller.program.as_ref()],
+        bump = deposit_caller.bump,
+        has_one = depositor @ ErrorCode::DepositCallerMismatch
+    )]
+    pub deposit_caller: Account<'info, DepositCaller>,
+
+    #[account(
+        init,
+        payer = depositor,
+        space = ShieldedNote::LEN,
+        seeds = [NOTE_SEED, shielded_pool.key().as_ref(), &shielded_pool.next_note_index.to_le_bytes()],
+        bump
+    )]
+    pub note_account: Account<'info, ShieldedNote>,
+
+    /// The caller program's depositor PDA, signing through `invoke_signed`
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ShieldDepositWithReceipt<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = depositor,
+        space = ShieldedNote::LEN,
+        seeds = [NOTE_SEED, shielded_pool.key().as_ref(), &shielded_pool.next_note_index.to_le_bytes()],
+        bump
+    )]
+    pub note_account: Account<'info, ShieldedNote>,
+
+    /// CHECK: Pool vault for holding deposited SOL
+    #[account(
+        mut,
+        seeds = [SHIELDED_VAULT_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [DEPOSIT_RECEIPTS_SEED, shielded_pool.key().as_ref()],
+        bump = receipt_config.bump
+    )]
+    pub receipt_config: Account<'info, DepositReceiptConfig>,
+
+    /// CHECK: Receipt mint for this note index, created by the instruction
+    #[account(
+        mut,
+        seeds = [RECEIPT_MINT_SEED, shielded_pool.key().as_ref(), &shielded_pool.next_note_index.to_le_bytes()],
+        bump
+    )]
+    pub receipt_mint: AccountInfo<'info>,
+
+    /// CHECK: Any address the depositor chooses to hold the receipt
+    pub receipt_owner: AccountInfo<'info>,
+
+    /// CHECK: receipt_owner's associated token account for the receipt mint;
+    /// the Associated Token Account program checks the address
+    #[account(mut)]
+    pub receipt_token_account: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Token-2022 program
+    #[account(address = TOKEN_2022_PROGRAM_ID)]
+    pub token_program: AccountInfo<'info>,
+
+    /// CHECK: Associated Token Account program
+    #[account(address = ASSOCIATED_TOKEN_PROGRAM_ID)]
+    pub associated_token_program: AccountInfo<'info>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ConfigureNoteExpiry<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    pub authority: Signer<'info>,
+
+    /// The pool's audit log, appended to when it keeps one
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, shielded_pool.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ConfigureDecoyOutputs<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    pub authority: Signer<'info>,
+
+    /// The pool's audit log, appended to when it keeps one
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, shielded_pool.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(through_index: u32)]
+pub struct AnnounceNoteSweep<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    /// Last note of the swept range
+    #[account(
+        seeds = [NOTE_SEED, shielded_pool.key().as_ref(), &through_index.saturating_sub(1).to_le_bytes()],
+        bump = last_note.bump
+    )]
+    pub last_note: Account<'info, ShieldedNote>,
+
+    pub authority: Signer<'info>,
+
+    /// The pool's audit log, appended to when it keeps one
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, shielded_pool.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteNoteSweep<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    /// CHECK: Pool vault holding the swept funds
+    #[account(
+        mut,
+        seeds = [SHIELDED_VAULT_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_vault: AccountInfo<'info>,
+
+    /// CHECK: The pool's configured treasury
+    #[account(
+        mut,
+        address = shielded_pool.treasury @ ErrorCode::InvalidNoteSweep
+    )]
+    pub treasury: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The pool's audit log, appended to when it keeps one
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, shielded_pool.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateTreeCheckpoint<'info> {
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = TreeCheckpoint::LEN,
+        seeds = [TREE_CHECKPOINT_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub tree_checkpoint: AccountLoader<'info, TreeCheckpoint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateNullifierFilter<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = NullifierFilter::LEN,
+        seeds = [NULLIFIER_FILTER_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub nullifier_filter: AccountLoader<'info, NullifierFilter>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The pool's audit log, appended to when it keeps one
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, shielded_pool.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateProtocolStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = ProtocolStats::LEN,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RegisterPoolStats<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CheckpointTree<'info> {
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        mut,
+        seeds = [TREE_CHECKPOINT_SEED, shielded_pool.key().as_ref()],
+        bump = tree_checkpoint.load()?.bump
+    )]
+    pub tree_checkpoint: AccountLoader<'info, TreeCheckpoint>,
+
+    /// Optional sponsor task tipping the cranker
+    #[account(mut)]
+    pub crank_task: Option<Account<'info, CrankTask>>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+}
+
+/// Check if a nullifier has been used in the pool
+pub(crate) fn is_nullifier_used(_pool: &ShieldedPool, _nullifier: &[u8; 32]) -> bool {
+    // In production: query nullifier account by PDA
+    // For demo: nullifier accounts are separate, so this always returns false
+    // The actual check happens via account existence (init constraint will fail)
+    false
+}
+
+/// Reject a spend whose nullifier record, created with `init_if_needed`,
+/// has already been written
+pub(crate) fn require_unspent(record: &NullifierRecord, pool: &ShieldedPool, nullifier: &[u8; 32]) -> Result<()> {
+    require!(
+        !record.is_recorded() && !is_nullifier_used(pool, nullifier),
+        ErrorCode::NullifierAlreadyUsed
+    );
+    Ok(())
+}
+
+/// Verify a deposit's range proof and store its note at the pool's next index
+/// Returns the deposit event for the caller to emit through `emit_cpi!`
+pub(crate) fn deposit_note(
+    pool: &mut Account<ShieldedPool>,
+    note_account: &mut Account<ShieldedNote>,
+    note_commitment: [u8; 32],
+    encrypted_note: [u8; 64],
+    range_proof: &[u8],
+    bump: u8,
+    now: i64,
+) -> Result<ShieldedDeposit> {
+    require!(pool.is_active, ErrorCode::PoolNotActive);
+    require!(pool.next_note_index < MAX_SHIELDED_NOTES as u32, ErrorCode::PoolFull);
+
+    // Verify range proof structure (Bulletproof format)
+    // Bulletproofs are typically 672+ bytes for 64-bit range proofs
+    require!(range_proof.len() >= 64, ErrorCode::InvalidRangeProof);
+
+    // Verify the range proof commits to a valid amount
+    // In production: use bulletproofs-solana or similar library
+    let proof_valid = verify_range_proof(&note_commitment, range_proof);
+    require!(proof_valid, ErrorCode::InvalidRangeProof);
+
+    // Store note in the pool and update the Merkle tree
+    append_pool_note(pool, note_account, note_commitment, encrypted_note, bump, now);
+    pool.record_deposit(now);
+
+    debug_log!(
+        "shield_deposit",
+        pool = pool.key(),
+        note_index = note_account.note_index,
+        commitment = Hex(&note_commitment),
+        merkle_root = Hex(&pool.merkle_root),
+    );
+
+    // NOTE: No amount is logged, stored, or emitted!
+    Ok(ShieldedDeposit {
+        pool: pool.key(),
+        note_commitment,
+        encrypted_note,
+        note_index: note_account.note_index,
+        merkle_root: pool.merkle_root,
+        timestamp: now,
+        // Amount is NEVER included - true privacy!
+    })
+}
+
+/// Store a new note at the pool's next index and extend the Merkle tree
+pub(crate) fn append_pool_note(
+    pool: &mut Account<ShieldedPool>,
+    note_account: &mut Account<ShieldedNote>,
+    commitment: [u8; 32],
+    encrypted_data: [u8; 64],
+    bump: u8,
+    now: i64,
+) {
+    note_account.set_inner(next_pool_note(pool, commitment, encrypted_data, bump, now));
+}
+
+/// Whether `screener` is the program `screen_withdrawal` would call, or
+/// the pool screens nothing
+pub(crate) fn screener_supplied(pool: &ShieldedPool, screener: Option<&UncheckedAccount>) -> bool {
+    !pool.screens_withdrawals()
+        || screener.is_some_and(|screener| screener.key() == pool.screener_program)
+}
+
+/// CPI the pool's screener with a withdrawal's recipient and nullifier
+/// before funds are released; any error it returns aborts the withdrawal
+/// accounts: [recipient, ...forwarded accounts], all passed read-only
+/// The pool stays locked (`lock_pool`) during the call, so the screener
+/// cannot re-enter it
+pub(crate) fn screen_withdrawal<'info>(
+    pool: &Account<'info, ShieldedPool>,
+    screener: Option<&UncheckedAccount<'info>>,
+    recipient: &AccountInfo<'info>,
+    nullifier: &[u8; 32],
+    forwarded: &[AccountInfo<'info>],
+) -> Result<()> {
+    if !pool.screens_withdrawals() {
+        return Ok(());
+    }
+    let screener = screener
+        .filter(|screener| screener_supplied(pool, Some(screener)))
+        .ok_or(ErrorCode::ScreenerRequired)?;
+
+    let mut data = SCREEN_WITHDRAWAL_DISCRIMINATOR.to_vec();
+    ScreenWithdrawalPayload {
+        pool: pool.key(),
+        recipient: recipient.key(),
+        nullifier: *nullifier,
+    }
+    .serialize(&mut data)?;
+
+    let mut accounts = Vec::with_capacity(forwarded.len() + 2);
+    accounts.push(recipient.clone());
+    accounts.extend_from_slice(forwarded);
+    let metas = accounts
+        .iter()
+        .map(|account| AccountMeta::new_readonly(account.key(), false))
+        .collect();
+    accounts.push(screener.to_account_info());
+
+    let ix = Instruction {
+        program_id: screener.key(),
+        accounts: metas,
+        data,
+    };
+    invoke(&ix, &accounts)?;
+
+    Ok(())
+}
+
+/// Mark the pool busy until the current instruction returns
+///
+/// The flag is written straight to the account data while the handler's
+/// copy keeps `processing = false`, so Anchor clears it when it persists
+/// that copy on exit. A nested instruction that loads the pool in between
+/// (a CPI callback into this program) is rejected instead of reading the
+/// same root and `next_note_index` and clobbering the outer insertion
+pub(crate) fn lock_pool(pool: &Account<ShieldedPool>) -> Result<()> {
+    require!(!pool.processing, ErrorCode::PoolBusy);
+    let mut locked = (**pool).clone();
+    locked.processing = true;
+    let info = pool.to_account_info();
+    let mut data = info.try_borrow_mut_data()?;
+    locked.try_serialize(&mut &mut data[..])
+}
+
+/// Create the note account for the pool's next index by hand, for
+/// instructions that insert a variable number of notes
+pub(crate) fn create_pool_note_account<'info>(
+    pool: &mut Account<ShieldedPool>,
+    note_account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    commitment: [u8; 32],
+    encrypted_data: [u8; 64],
+    now: i64,
+) -> Result<()> {
+    let pool_key = pool.key();
+    let index = pool.next_note_index.to_le_bytes();
+    let (address, bump) = note_address(&pool_key, pool.next_note_index);
+    require_keys_eq!(*note_account.key, address, ErrorCode::InvalidDepositBatch);
+    require!(
+        note_account.owner == &System::id() && note_account.data_is_empty(),
+        ErrorCode::InvalidDepositBatch
+    );
+
+    anchor_lang::system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            anchor_lang::system_program::CreateAccount {
+                from: payer.to_account_info(),
+                to: note_account.clone(),
+            },
+            &[&[NOTE_SEED, pool_key.as_ref(), &index, &[bump]]],
+        ),
+        Rent::get()?.minimum_balance(ShieldedNote::LEN),
+        ShieldedNote::LEN as u64,
+        &crate::ID,
+    )?;
+
+    let note = next_pool_note(pool, commitment, encrypted_data, bump, now);
+    note.try_serialize(&mut &mut note_account.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
+
+/// Build the note for the pool's next index and extend the Merkle tree
+pub(crate) fn next_pool_note(
+    pool: &mut Account<ShieldedPool>,
+    commitment: [u8; 32],
+    encrypted_data: [u8; 64],
+    bump: u8,
+    now: i64,
+) -> ShieldedNote {
+    let note = ShieldedNote {
+        pool: pool.key(),
+        commitment,
+        encrypted_data,
+        note_index: pool.next_note_index,
+        created_at: now,
+        unlock_at: now + (pool.lockup_epochs as i64 * 432000),
+        is_spent: false,
+        bump,
+        version: ShieldedNote::VERSION,
+        reward_rate_bps: pool.reward_rate_at(now),
+    };
+
+    pool.merkle_root = insert_note_to_merkle_tree(&pool.merkle_root, &commitment, pool.next_note_index);
+    pool.next_note_index += 1;
+    pool.total_notes += 1;
+
+    note
+}
+
+/// Anonymity-metrics epoch containing unix time `now`
+pub(crate) fn anonymity_epoch(now: i64) -> u64 {
+    (now / ANONYMITY_EPOCH_SECONDS).max(0) as u64
+}
+
+/// Create the NullifierRecord PDA for `nullifier` at `record`
+/// Fails if the record already exists, i.e. the note was already spent
+pub(crate) fn record_nullifier<'info>(
+    pool: &Pubkey,
+    nullifier: &[u8; 32],
+    record: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    now: i64,
+) -> Result<()> {
+    let (address, bump) = nullifier_address(pool, nullifier);
+    require_keys_eq!(*record.key, address, ErrorCode::InvalidNullifierAccount);
+    require!(
+        record.owner == &System::id() && record.data_is_empty(),
+        ErrorCode::NullifierAlreadyUsed
+    );
+
+    anchor_lang::system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            anchor_lang::system_program::CreateAccount {
+                from: payer.to_account_info(),
+                to: record.clone(),
+            },
+            &[&[NULLIFIER_SEED, pool.as_ref(), nullifier, &[bump]]],
+        ),
+        Rent::get()?.minimum_balance(NullifierRecord::LEN),
+        NullifierRecord::LEN as u64,
+        &crate::ID,
+    )?;
+
+    let state = NullifierRecord {
+        pool: *pool,
+        nullifier: *nullifier,
+        spent_at: now,
+        bump,
+        version: NullifierRecord::VERSION,
+        outcome: [0u8; 32],
+    };
+    state.try_serialize(&mut &mut record.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
+
+/// Create the receipt mint for `note_index` as a non-transferable Token-2022
+/// mint, mint its single token to the owner's associated token account and
+/// revoke the mint authority so no second token can exist
+pub(crate) fn mint_deposit_receipt(accounts: &ShieldDepositWithReceipt, note_index: u32, mint_bump: u8) -> Result<()> {
+    let pool_key = accounts.shielded_pool.key();
+    let index = note_index.to_le_bytes();
+    let mint = accounts.receipt_mint.to_account_info();
+    let config = accounts.receipt_config.to_account_info();
+    let token_account = accounts.receipt_token_account.to_account_info();
+    let config_seeds: &[&[u8]] = &[DEPOSIT_RECEIPTS_SEED, pool_key.as_ref(), &[accounts.receipt_config.bump]];
+
+    anchor_lang::system_program::create_account(
+        CpiContext::new_with_signer(
+            accounts.system_program.to_account_info(),
+            anchor_lang::system_program::CreateAccount {
+                from: accounts.depositor.to_account_info(),
+                to: mint.clone(),
+            },
+            &[&[RECEIPT_MINT_SEED, pool_key.as_ref(), &index, &[mint_bump]]],
+        ),
+        Rent::get()?.minimum_balance(RECEIPT_MINT_LEN),
+        RECEIPT_MINT_LEN as u64,
+        &TOKEN_2022_PROGRAM_ID,
+    )?;
+
+    // InitializeNonTransferableMint must precede InitializeMint2
+    invoke(
+        &token_2022_instruction(vec![32], vec![AccountMeta::new(mint.key(), false)]),
+        std::slice::from_ref(&mint),
+    )?;
+
+    // InitializeMint2: 0 decimals, config as mint authority, no freeze authority
+    let mut data = vec![20, 0];
+    data.extend_from_slice(config.key.as_ref());
+    data.push(0);
+    invoke(
+        &token_2022_instruction(data, vec![AccountMeta::new(mint.key(), false)]),
+        std::slice::from_ref(&mint),
+    )?;
+
+    // Associated Token Account CreateIdempotent; it adds the NonTransferable
+    // and ImmutableOwner account extensions itself
+    invoke(
+        &Instruction {
+            program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(accounts.depositor.key(), true),
+                AccountMeta::new(token_account.key(), false),
+                AccountMeta::new_readonly(accounts.receipt_owner.key(), false),
+                AccountMeta::new_readonly(mint.key(), false),
+                AccountMeta::new_readonly(System::id(), false),
+                AccountMeta::new_readonly(TOKEN_2022_PROGRAM_ID, false),
+            ],
+            data: vec![1],
+        },
+        &[
+            accounts.depositor.to_account_info(),
+            token_account.clone(),
+            accounts.receipt_owner.to_account_info(),
+            mint.clone(),
+            accounts.system_program.to_account_info(),
+            accounts.token_program.to_account_info(),
+        ],
+    )?;
+
+    // MintTo: exactly one token
+    let mut data = vec![7];
+    data.extend_from_slice(&1u64.to_le_bytes());
+    invoke_signed(
+        &token_2022_instruction(
+            data,
+            vec![
+                AccountMeta::new(mint.key(), false),
+                AccountMeta::new(token_account.key(), false),
+                AccountMeta::new_readonly(config.key(), true),
+            ],
+        ),
+        &[mint.clone(), token_account, config.clone()],
+        &[config_seeds],
+    )?;
+
+    // SetAuthority(MintTokens) to None fixes the supply at one
+    invoke_signed(
+        &token_2022_instruction(
+            vec![6, 0, 0],
+            vec![AccountMeta::new(mint.key(), false), AccountMeta::new_readonly(config.key(), true)],
+        ),
+        &[mint, config],
+        &[config_seeds],
+    )?;
+
+    Ok(())
+}
+
+/// Token-2022 instruction with raw `data`
+pub(crate) fn token_2022_instruction(data: Vec<u8>, accounts: Vec<AccountMeta>) -> Instruction {
+    Instruction {
+        program_id: TOKEN_2022_PROGRAM_ID,
+        accounts,
+        data,
+    }
+}
+
+/// Set the bits of spent `nullifiers` in the pool's nullifier filter
+/// Once a pool keeps a filter every spend must pass it, so the filter
+/// never misses a nullifier spent after its creation
+pub(crate) fn record_filtered_nullifiers(
+    pool: &ShieldedPool,
+    filter: Option<&AccountLoader<NullifierFilter>>,
+    nullifiers: &[[u8; 32]],
+) -> Result<()> {
+    match filter {
+        Some(filter) => {
+            let mut filter = filter.load_mut()?;
+            for nullifier in nullifiers {
+                filter.insert(nullifier);
+            }
+        }
+        None => require!(!pool.has_nullifier_filter, ErrorCode::NullifierFilterRequired),
+    }
+    Ok(())
+}
+
+/// Note and nullifier counts of a pool before an instruction mutates it
+#[derive(Clone, Copy)]
+pub(crate) struct PoolTotals {
+    notes: u32,
+    nullifiers: u32,
+}
+
+impl PoolTotals {
+    pub(crate) fn of(pool: &ShieldedPool) -> Self {
+        Self {
+            notes: pool.total_notes,
+            nullifiers: pool.nullifier_count,
+        }
+    }
+
+    /// Leave `notes` of the notes inserted after this snapshot out of the
+    /// protocol totals (decoy outputs)
+    pub(crate) fn without_notes(self, notes: u32) -> Self {
+        Self { notes: self.notes + notes, ..self }
+    }
+}
+
+/// Fold the notes and nullifiers an instruction added to `pool` into the
+/// protocol totals. Pools counted there must pass the stats account so the
+/// totals never drift; other pools may omit it
+pub(crate) fn record_protocol_stats(
+    pool: &ShieldedPool,
+    stats: Option<&mut ProtocolStats>,
+    before: PoolTotals,
+) -> Result<()> {
+    match stats {
+        Some(stats) if pool.in_protocol_stats => {
+            stats.total_notes += u64::from(pool.total_notes - before.notes);
+            stats.total_nullifiers += u64::from(pool.nullifier_count - before.nullifiers);
+        }
+        Some(_) => {}
+        None => require!(!pool.in_protocol_stats, ErrorCode::ProtocolStatsRequired),
+    }
+    Ok(())
+}
+
+/// Record a withdrawal's nullifier and insert its change note, if any
+pub(crate) fn spend_withdrawal_note(
+    pool: &mut Account<ShieldedPool>,
+    nullifier_account: &mut Account<NullifierRecord>,
+    bump: u8,
+    nullifier: [u8; 32],
+    output_commitment: [u8; 32],
+    now: i64,
+) {
+    // Record nullifier to prevent double-spend
+    nullifier_account.pool = pool.key();
+    nullifier_account.nullifier = nullifier;
+    nullifier_account.spent_at = now;
+    nullifier_account.bump = bump;
+    nullifier_account.version = NullifierRecord::VERSION;
+
+    record_pool_withdrawal(pool, output_commitment);
+}
+
+/// Count a spent note and insert its change note, if any
+pub(crate) fn record_pool_withdrawal(pool: &mut ShieldedPool, output_commitment: [u8; 32]) {
+    pool.nullifier_count += 1;
+    pool.record_withdrawal();
+
+    // If there's change, add new note to the tree
+    if output_commitment != [0u8; 32] {
+        let new_root = insert_note_to_merkle_tree(
+            &pool.merkle_root,
+            &output_commitment,
+            pool.next_note_index,
+        );
+        pool.merkle_root = new_root;
+        pool.next_note_index += 1;
+    }
+}
@@ -0,0 +1,199 @@
+//! Withdrawals split across public recipients
+
+use crate::*;
+
+// ============================================
+// SPLIT WITHDRAWALS - One Unshield, Several Payees
+// ============================================
+//
+// One withdrawal proof can pay up to MAX_SPLIT_RECIPIENTS public wallets
+// fixed shares, e.g. an invoice, a relayer fee and a tax withholding
+// address, instead of unshielding once and paying each from the same
+// wallet. The proof commits to
+//   split_tag = H(DOMAIN_SPLIT_WITHDRAWAL, pool, (recipient, lamports)...)
+// so neither the recipients nor their shares can be changed by whoever
+// submits it. Each recipient is screened like a `shield_withdraw`
+// recipient when the pool has a screener.
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn shield_withdraw_split<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ShieldWithdrawSplit<'info>>,
+    nullifier: [u8; 32],
+    merkle_proof: [[u8; 32]; 8],
+    merkle_path_indices: u8,
+    withdrawal_proof: Vec<u8>,
+    output_commitment: [u8; 32],
+    payments: Vec<SplitPayment>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    lock_pool(&ctx.accounts.shielded_pool)?;
+    let totals = PoolTotals::of(&ctx.accounts.shielded_pool);
+    let recipients = clock::strip(ctx.remaining_accounts);
+    require_proof_len(&withdrawal_proof)?;
+
+    let pool = &mut ctx.accounts.shielded_pool;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(pool.is_active, ErrorCode::PoolNotActive);
+    require!(
+        (1..=MAX_SPLIT_RECIPIENTS).contains(&payments.len()),
+        ErrorCode::InvalidSplitWithdrawal
+    );
+    for (i, payment) in payments.iter().enumerate() {
+        require!(
+            payment.lamports > 0
+                && !payments[..i].iter().any(|earlier| earlier.recipient == payment.recipient),
+            ErrorCode::InvalidSplitWithdrawal
+        );
+    }
+    require!(recipients.len() == payments.len(), ErrorCode::SplitRecipientMismatch);
+    for (recipient, payment) in recipients.iter().zip(&payments) {
+        require!(
+            recipient.key() == payment.recipient && recipient.is_writable,
+            ErrorCode::SplitRecipientMismatch
+        );
+    }
+    require!(!is_nullifier_used(pool, &nullifier), ErrorCode::NullifierAlreadyUsed);
+    require!(
+        verify_merkle_proof(&pool.merkle_root, &merkle_proof, merkle_path_indices, &nullifier),
+        ErrorCode::InvalidMerkleProof
+    );
+
+    let public_signals = [
+        nullifier,
+        output_commitment,
+        pool.spend_anchor(),
+        compute_split_tag(&pool.key(), &payments),
+        compute_pool_binding(&pool.key()),
+    ];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::SplitWithdrawal, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::SplitWithdrawal, &public_signals, &withdrawal_proof),
+        ErrorCode::InvalidSplitWithdrawalProof
+    );
+
+    for recipient in recipients {
+        screen_withdrawal(pool, ctx.accounts.screener_program.as_ref(), recipient, &nullifier, &[])?;
+    }
+
+    record_filtered_nullifiers(pool, ctx.accounts.nullifier_filter.as_ref(), &[nullifier])?;
+    spend_withdrawal_note(
+        pool,
+        &mut ctx.accounts.nullifier_account,
+        ctx.bumps.nullifier_account,
+        nullifier,
+        output_commitment,
+        current_time,
+    );
+
+    let pool_key = pool.key();
+    let mut total_lamports = 0u64;
+    for (recipient, payment) in recipients.iter().zip(&payments) {
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.pool_vault.to_account_info(),
+                    to: recipient.clone(),
+                },
+                &[&[SHIELDED_VAULT_SEED, pool_key.as_ref(), &[ctx.bumps.pool_vault]]],
+            ),
+            payment.lamports,
+        )?;
+        total_lamports = total_lamports.saturating_add(payment.lamports);
+    }
+
+    debug_log!(
+        "shield_withdraw_split",
+        pool = pool_key,
+        nullifier = Hex(&nullifier),
+        recipients = payments.len(),
+    );
+
+    emit_cpi!(ShieldedWithdrawSplit {
+        pool: pool_key,
+        nullifier,
+        output_commitment,
+        recipients: payments.iter().map(|payment| payment.recipient).collect(),
+        merkle_root: pool.merkle_root,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ShieldWithdrawSplit,
+        pool_key,
+        nullifier,
+        output_commitment,
+        pool.merkle_root,
+        total_lamports,
+        current_time,
+    ));
+
+    record_protocol_stats(&ctx.accounts.shielded_pool, ctx.accounts.protocol_stats.as_deref_mut(), totals)?;
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(nullifier: [u8; 32])]
+pub struct ShieldWithdrawSplit<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = NullifierRecord::LEN,
+        seeds = [NULLIFIER_SEED, shielded_pool.key().as_ref(), &nullifier],
+        bump
+    )]
+    pub nullifier_account: Account<'info, NullifierRecord>,
+
+    /// CHECK: Pool vault paying the recipients
+    #[account(
+        mut,
+        seeds = [SHIELDED_VAULT_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_vault: AccountInfo<'info>,
+
+    /// Any payer (e.g. a relayer) - need not be one of the recipients
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The pool's nullifier filter, required once the pool keeps one
+    #[account(
+        mut,
+        seeds = [NULLIFIER_FILTER_SEED, shielded_pool.key().as_ref()],
+        bump = nullifier_filter.load()?.bump
+    )]
+    pub nullifier_filter: Option<AccountLoader<'info, NullifierFilter>>,
+
+    /// Protocol-wide totals; required once the pool is counted there
+    #[account(
+        mut,
+        seeds = [PROTOCOL_STATS_SEED],
+        bump = protocol_stats.bump
+    )]
+    pub protocol_stats: Option<Account<'info, ProtocolStats>>,
+
+    /// CHECK: The pool's withdrawal screener, required once the pool has
+    /// one; matched against `screener_program` in `screen_withdrawal`
+    pub screener_program: Option<UncheckedAccount<'info>>,
+}
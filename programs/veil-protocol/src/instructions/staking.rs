@@ -0,0 +1,353 @@
+//! Legacy staking (deprecated)
+
+use crate::*;
+
+// ============================================
+// LEGACY STAKING (Deprecated - kept for compatibility)
+// These functions have privacy issues - use shielded versions above
+// ============================================
+
+pub(crate) fn create_stake_pool(
+    ctx: Context<CreateStakePool>,
+    pool_id: [u8; 32],
+    min_stake_lamports: u64,
+    reward_rate_bps: u16,
+    lockup_epochs: u8,
+) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(min_stake_lamports >= 1_000_000, ErrorCode::StakeTooSmall);
+    require!(reward_rate_bps <= 10000, ErrorCode::InvalidRewardRate);
+    require!((1..=52).contains(&lockup_epochs), ErrorCode::InvalidLockupPeriod);
+
+    stake_pool.pool_id = pool_id;
+    stake_pool.creator = ctx.accounts.creator.key();
+    stake_pool.min_stake_lamports = min_stake_lamports;
+    stake_pool.reward_rate_bps = reward_rate_bps;
+    stake_pool.lockup_epochs = lockup_epochs;
+    stake_pool.total_stake_commitments = 0;
+    stake_pool.total_staked_lamports = 0;
+    stake_pool.created_at = current_time;
+    stake_pool.is_active = true;
+    stake_pool.bump = ctx.bumps.stake_pool;
+    stake_pool.version = PrivateStakePool::VERSION;
+
+    emit!(StakePoolCreated {
+        pool: stake_pool.key(),
+        pool_id,
+        creator: ctx.accounts.creator.key(),
+        min_stake_lamports,
+        reward_rate_bps,
+        lockup_epochs,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateStakePool,
+        stake_pool.key(),
+        pool_id,
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn stake_private(
+    ctx: Context<StakePrivate>,
+    stake_commitment: [u8; 32],
+    validator_commitment: [u8; 32],
+    _amount_commitment: [u8; 32], // Changed: now accepts commitment, not plaintext
+) -> Result<()> {
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let stake_record = &mut ctx.accounts.stake_record;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(stake_pool.is_active, ErrorCode::PoolNotActive);
+
+    // NOTE: We no longer accept plaintext amounts!
+    // The amount is now hidden inside the commitment.
+    // Actual transfer must happen separately through shield_deposit
+
+    stake_record.pool = stake_pool.key();
+    stake_record.staker = ctx.accounts.staker.key();
+    stake_record.stake_commitment = stake_commitment;
+    stake_record.validator_commitment = validator_commitment;
+    stake_record.staked_at = current_time;
+    stake_record.unlock_at = current_time + (stake_pool.lockup_epochs as i64 * 432000);
+    stake_record.is_active = true;
+    stake_record.claimed_rewards = 0;
+    stake_record.bump = ctx.bumps.stake_record;
+    stake_record.version = PrivateStakeRecord::VERSION;
+
+    stake_pool.total_stake_commitments += 1;
+
+    emit!(PrivateStakeCreated {
+        pool: stake_pool.key(),
+        staker: ctx.accounts.staker.key(),
+        stake_commitment,
+        validator_commitment,
+        unlock_at: stake_record.unlock_at,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::StakePrivate,
+        stake_record.key(),
+        stake_commitment,
+        validator_commitment,
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn unstake(
+    ctx: Context<Unstake>,
+    nullifier: [u8; 32],          // Changed: now uses nullifier
+    withdrawal_proof: Vec<u8>,     // Changed: ZK proof instead of plaintext reveal
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    require_proof_len(&withdrawal_proof)?;
+
+    let stake_pool = &mut ctx.accounts.stake_pool;
+    let stake_record = &mut ctx.accounts.stake_record;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(stake_record.is_active, ErrorCode::StakeNotActive);
+    require!(current_time >= stake_record.unlock_at, ErrorCode::StakeLocked);
+
+    // Verify withdrawal proof structure
+    require!(withdrawal_proof.len() >= 256, ErrorCode::InvalidWithdrawalProof);
+
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::Unstake, &circuit_id)?;
+
+    // Verify the nullifier is correctly derived from the stake commitment
+    let nullifier_valid = verify_nullifier_derivation(
+        &stake_record.stake_commitment,
+        &nullifier,
+        &withdrawal_proof,
+    );
+    require!(nullifier_valid, ErrorCode::InvalidNullifier);
+
+    stake_record.is_active = false;
+    stake_record.unstaked_at = current_time;
+
+    // NOTE: No amount is transferred here - that happens in shield_withdraw
+    // This just marks the stake as inactive
+
+    emit!(PrivateUnstake {
+        pool: stake_pool.key(),
+        staker: ctx.accounts.staker.key(),
+        nullifier_hash: hash_domain(DOMAIN_NULLIFIER, &[&nullifier]),
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::Unstake,
+        stake_record.key(),
+        hash_domain(DOMAIN_NULLIFIER, &[&nullifier]),
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn claim_rewards(
+    ctx: Context<ClaimRewards>,
+    reward_proof: Vec<u8>,  // Changed: full ZK proof, not just hash
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    require_proof_len(&reward_proof)?;
+
+    let stake_pool = &ctx.accounts.stake_pool;
+    let stake_record = &mut ctx.accounts.stake_record;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(stake_record.is_active, ErrorCode::StakeNotActive);
+
+    // Verify reward proof (must be proper Groth16 proof)
+    require!(reward_proof.len() >= 256, ErrorCode::InvalidRewardProof);
+
+    // Extract and verify proof components
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::StakeRewards, &circuit_id)?;
+    let proof_valid = verify_reward_claim_proof(
+        &stake_record.stake_commitment,
+        stake_pool.reward_rate_bps,
+        stake_record.staked_at,
+        current_time,
+        &reward_proof,
+    );
+    require!(proof_valid, ErrorCode::InvalidRewardProof);
+
+    // Compute reward commitment hash for the event
+    let reward_commitment = compute_reward_commitment(&reward_proof);
+
+    stake_record.last_claim_at = current_time;
+
+    emit!(RewardsClaimed {
+        pool: stake_pool.key(),
+        staker: ctx.accounts.staker.key(),
+        reward_commitment,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ClaimRewards,
+        stake_record.key(),
+        reward_commitment,
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(pool_id: [u8; 32])]
+pub struct CreateStakePool<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = PrivateStakePool::LEN,
+        seeds = [STAKE_POOL_SEED, creator.key().as_ref(), &pool_id],
+        bump
+    )]
+    pub stake_pool: Account<'info, PrivateStakePool>,
+
+    /// CHECK: Pool vault PDA for holding staked SOL
+    #[account(
+        mut,
+        seeds = [STAKE_VAULT_SEED, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StakePrivate<'info> {
+    #[account(
+        mut,
+        seeds = [STAKE_POOL_SEED, stake_pool.creator.as_ref(), &stake_pool.pool_id],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, PrivateStakePool>,
+
+    #[account(
+        init,
+        payer = staker,
+        space = PrivateStakeRecord::LEN,
+        seeds = [STAKE_RECORD_SEED, stake_pool.key().as_ref(), staker.key().as_ref()],
+        bump
+    )]
+    pub stake_record: Account<'info, PrivateStakeRecord>,
+
+    /// CHECK: Pool vault PDA for holding staked SOL
+    #[account(
+        mut,
+        seeds = [STAKE_VAULT_SEED, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(
+        mut,
+        seeds = [STAKE_POOL_SEED, stake_pool.creator.as_ref(), &stake_pool.pool_id],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, PrivateStakePool>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_RECORD_SEED, stake_pool.key().as_ref(), staker.key().as_ref()],
+        bump = stake_record.bump,
+        constraint = stake_record.staker == staker.key() @ ErrorCode::Unauthorized
+    )]
+    pub stake_record: Account<'info, PrivateStakeRecord>,
+
+    /// CHECK: Pool vault PDA for holding staked SOL
+    #[account(
+        mut,
+        seeds = [STAKE_VAULT_SEED, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(
+        seeds = [STAKE_POOL_SEED, stake_pool.creator.as_ref(), &stake_pool.pool_id],
+        bump = stake_pool.bump
+    )]
+    pub stake_pool: Account<'info, PrivateStakePool>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_RECORD_SEED, stake_pool.key().as_ref(), staker.key().as_ref()],
+        bump = stake_record.bump,
+        constraint = stake_record.staker == staker.key() @ ErrorCode::Unauthorized
+    )]
+    pub stake_record: Account<'info, PrivateStakeRecord>,
+
+    /// CHECK: Pool vault PDA for holding staked SOL
+    #[account(
+        mut,
+        seeds = [STAKE_VAULT_SEED, stake_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
@@ -0,0 +1,111 @@
+//! Fixtures shared by the handler tests
+//!
+//! The demo verifiers accept or reject a proof by hashing it together with
+//! its public signals, so a test that needs a particular outcome searches
+//! for one: the three points stay fixed (and on their curves) while a
+//! trailing nonce, which the point checks ignore, changes the digest.
+
+use crate::*;
+
+/// G1 generator (1, 2) followed by the EIP-197 G2 generator and G1 again
+fn mock_points() -> Vec<u8> {
+    let mut g1 = [0u8; 64];
+    g1[31] = 1;
+    g1[63] = 2;
+    let g2 = [
+        hex32("198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2"),
+        hex32("1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed"),
+        hex32("090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b"),
+        hex32("12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"),
+    ]
+    .concat();
+    [g1.as_ref(), &g2, &g1].concat()
+}
+
+/// First on-curve proof for which `accepts` holds
+pub(crate) fn find_proof(accepts: impl Fn(&[u8]) -> bool) -> Vec<u8> {
+    let points = mock_points();
+    (0u32..100_000)
+        .map(|nonce| [points.as_slice(), &nonce.to_le_bytes()].concat())
+        .find(|proof| accepts(proof))
+        .expect("no mock proof satisfies the predicate")
+}
+
+/// Proof whose pi_b is off the curve, which every verifier rejects
+pub(crate) fn rejected_proof() -> Vec<u8> {
+    let mut proof = mock_points();
+    proof[64 + 31] ^= 1;
+    proof
+}
+
+/// Account state with every field zeroed, as `init` leaves it
+pub(crate) fn zeroed<T: AccountDeserialize>(len: usize) -> T {
+    T::try_deserialize_unchecked(&mut &vec![0u8; len][..]).unwrap()
+}
+
+/// Assert that `result` failed with the program error `expected`
+pub(crate) fn assert_error<T>(result: Result<T>, expected: ErrorCode) {
+    match result {
+        Ok(_) => panic!("expected {expected:?}, got Ok"),
+        Err(Error::AnchorError(error)) => {
+            assert_eq!(error.error_code_number, u32::from(expected), "{}", error.error_msg)
+        }
+        Err(error) => panic!("expected {expected:?}, got {error:?}"),
+    }
+}
+
+/// Account handed to an instruction, leaked so it lives for `'static`
+pub(crate) fn account_info(key: Pubkey, owner: Pubkey, data: Vec<u8>, is_signer: bool) -> AccountInfo<'static> {
+    AccountInfo::new(
+        Box::leak(Box::new(key)),
+        is_signer,
+        true,
+        Box::leak(Box::new(1_000_000_000)),
+        Box::leak(data.into_boxed_slice()),
+        Box::leak(Box::new(owner)),
+        key == crate::ID,
+        0,
+    )
+}
+
+/// Program account at `key` holding `state`, padded to `len`
+pub(crate) fn state_account<T: AccountSerialize>(key: Pubkey, state: &T, len: usize) -> AccountInfo<'static> {
+    let mut data = Vec::with_capacity(len);
+    state.try_serialize(&mut data).unwrap();
+    data.resize(len.max(data.len()), 0);
+    account_info(key, crate::ID, data, false)
+}
+
+pub(crate) fn signer(key: Pubkey) -> AccountInfo<'static> {
+    account_info(key, System::id(), vec![], true)
+}
+
+/// The `event_authority` and `program` accounts `#[event_cpi]` appends
+pub(crate) fn event_cpi_accounts() -> [AccountInfo<'static>; 2] {
+    let (event_authority, _) = Pubkey::find_program_address(&[b"__event_authority"], &crate::ID);
+    [
+        account_info(event_authority, System::id(), vec![], false),
+        account_info(crate::ID, Pubkey::default(), vec![], false),
+    ]
+}
+
+/// Run an instruction's account validation, as the entrypoint does before
+/// the handler
+pub(crate) fn try_accounts<T, B>(accounts: Vec<AccountInfo<'static>>) -> Result<T>
+where
+    T: Accounts<'static, B>,
+    B: Default,
+{
+    let mut accounts: &'static [AccountInfo<'static>] = Box::leak(accounts.into_boxed_slice());
+    T::try_accounts(&crate::ID, &mut accounts, &[], &mut B::default(), &mut Default::default())
+}
+
+/// Empty circuit registry maintained by a fresh authority
+pub(crate) fn circuit_registry_account() -> AccountInfo<'static> {
+    let authority = Pubkey::new_unique();
+    let (key, bump) = crate::pda::circuit_registry_address(&authority);
+    let mut registry: CircuitRegistry = zeroed(CircuitRegistry::LEN);
+    registry.authority = authority;
+    registry.bump = bump;
+    state_account(key, &registry, CircuitRegistry::LEN)
+}
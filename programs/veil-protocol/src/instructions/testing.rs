@@ -0,0 +1,37 @@
+//! Test clock
+
+use crate::*;
+
+// ============================================
+// TEST CLOCK (test-clock feature only)
+// ============================================
+
+#[cfg(feature = "test-clock")]
+pub(crate) fn set_mock_clock(ctx: Context<SetMockClock>, unix_timestamp: i64, slot: u64) -> Result<()> {
+    let mock_clock = &mut ctx.accounts.mock_clock;
+    mock_clock.unix_timestamp = unix_timestamp;
+    mock_clock.slot = slot;
+    mock_clock.bump = ctx.bumps.mock_clock;
+
+    debug_log!("set_mock_clock", unix_timestamp = unix_timestamp, slot = slot);
+
+    Ok(())
+}
+
+#[cfg(feature = "test-clock")]
+#[derive(Accounts)]
+pub struct SetMockClock<'info> {
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = MockClock::LEN,
+        seeds = [MOCK_CLOCK_SEED],
+        bump
+    )]
+    pub mock_clock: Account<'info, MockClock>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
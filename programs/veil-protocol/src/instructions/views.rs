@@ -0,0 +1,374 @@
+//! Read-only getters and their return data
+
+use crate::*;
+
+// ============================================
+// VIEW INSTRUCTIONS - Read-only getters
+// ============================================
+//
+// These never mutate state. The returned snapshot is Borsh-encoded into
+// the transaction return data (`set_return_data`), so CPI callers read it
+// with `get_return_data` and clients read it from a simulation without
+// depending on account layouts.
+
+pub(crate) fn get_pool_state(ctx: Context<GetPoolState>) -> Result<PoolStateSnapshot> {
+    let pool = &ctx.accounts.shielded_pool;
+
+    Ok(PoolStateSnapshot {
+        pool: pool.key(),
+        pool_id: pool.pool_id,
+        merkle_root: pool.merkle_root,
+        next_note_index: pool.next_note_index,
+        total_notes: pool.total_notes,
+        nullifier_count: pool.nullifier_count,
+        reward_rate_bps: pool.reward_rate_bps,
+        lockup_epochs: pool.lockup_epochs,
+        is_active: pool.is_active,
+    })
+}
+
+pub(crate) fn get_protocol_stats(ctx: Context<GetProtocolStats>) -> Result<ProtocolStatsSnapshot> {
+    let stats = &ctx.accounts.protocol_stats;
+
+    Ok(ProtocolStatsSnapshot {
+        pool_count: stats.pool_count,
+        total_notes: stats.total_notes,
+        total_nullifiers: stats.total_nullifiers,
+        unspent_notes: stats.total_notes.saturating_sub(stats.total_nullifiers),
+    })
+}
+
+pub(crate) fn get_proposal_tally(ctx: Context<GetProposalTally>) -> Result<ProposalTally> {
+    let proposal = &ctx.accounts.proposal;
+
+    Ok(ProposalTally {
+        proposal: proposal.key(),
+        yes_count: proposal.yes_count,
+        no_count: proposal.no_count,
+        total_commitments: proposal.total_commitments,
+        total_revealed: proposal.total_revealed,
+        voting_ends_at: proposal.voting_ends_at,
+        reveal_ends_at: proposal.reveal_ends_at,
+        is_finalized: proposal.is_finalized,
+        min_reveal_bps: proposal.min_reveal_bps,
+        reveal_quorum_met: proposal.reveal_quorum_met(),
+        reveal_quorum_failed: proposal.reveal_quorum_failed,
+        weight_pool: proposal.weight_pool,
+        yes_weight: proposal.yes_weight,
+        no_weight: proposal.no_weight,
+        governing_mint: proposal.governing_mint,
+    })
+}
+
+pub(crate) fn get_merkle_root(ctx: Context<GetPoolState>) -> Result<MerkleRootSnapshot> {
+    let pool = &ctx.accounts.shielded_pool;
+
+    Ok(MerkleRootSnapshot {
+        merkle_root: pool.merkle_root,
+        leaf_count: pool.next_note_index,
+    })
+}
+
+pub(crate) fn get_anonymity_metrics(ctx: Context<GetPoolState>) -> Result<AnonymityMetrics> {
+    let epoch = anonymity_epoch(clock::now(ctx.remaining_accounts)?.unix_timestamp);
+    let mut pool = (*ctx.accounts.shielded_pool).clone();
+    pool.roll_anonymity_metrics(epoch);
+
+    // Oldest to newest, ending with the current epoch
+    let mut deposit_histogram = [0u32; DEPOSIT_HISTOGRAM_BUCKETS];
+    for (age, bucket) in deposit_histogram.iter_mut().rev().enumerate() {
+        if let Some(bucket_epoch) = epoch.checked_sub(age as u64) {
+            *bucket = pool.deposit_histogram[(bucket_epoch % DEPOSIT_HISTOGRAM_BUCKETS as u64) as usize];
+        }
+    }
+
+    Ok(AnonymityMetrics {
+        pool: ctx.accounts.shielded_pool.key(),
+        epoch,
+        total_notes: pool.total_notes,
+        unspent_notes: pool.total_notes.saturating_sub(pool.nullifier_count),
+        deposits_this_epoch: pool.deposits_this_epoch,
+        deposits_last_epoch: pool.deposits_last_epoch,
+        deposit_histogram,
+        unique_withdrawal_roots_used: pool.unique_withdrawal_roots_used,
+    })
+}
+
+pub(crate) fn get_reserve_status(ctx: Context<GetReserveStatus>) -> Result<ReserveStatus> {
+    let pool = &ctx.accounts.shielded_pool;
+    let attestation = &ctx.accounts.reserve_attestation;
+
+    Ok(ReserveStatus {
+        pool: pool.key(),
+        merkle_root: attestation.merkle_root,
+        vault_balance: attestation.vault_balance,
+        published_at: attestation.published_at,
+        is_current: attestation.is_current(pool),
+    })
+}
+
+pub(crate) fn verify_withdrawal_dry_run<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VerifyWithdrawalDryRun<'info>>,
+    nullifier: [u8; 32],
+    merkle_proof: [[u8; 32]; 8],
+    merkle_path_indices: u8,
+    withdrawal_proof: Vec<u8>,
+    output_commitment: [u8; 32],
+    circuit_id: [u8; 32],
+) -> Result<WithdrawalDryRun> {
+    let pool = &ctx.accounts.shielded_pool;
+
+    // The real withdrawal creates this PDA, so it must not exist yet
+    let nullifier_unused = ctx.accounts.nullifier_account.data_is_empty()
+        && !is_nullifier_used(pool, &nullifier);
+    let merkle_proof_valid = verify_merkle_proof(
+        &pool.merkle_root,
+        &merkle_proof,
+        merkle_path_indices,
+        &nullifier,
+    );
+    let circuit_registered =
+        verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::Withdrawal, &circuit_id).is_ok();
+    let spend_anchor = pool.spend_anchor();
+    let proof_valid = require_proof_len(&withdrawal_proof).is_ok()
+        && withdrawal_proof.len() >= 256
+        && verify_withdrawal_proof(
+            &nullifier,
+            &output_commitment,
+            &spend_anchor,
+            &compute_pool_binding(&pool.key()),
+            &withdrawal_proof,
+        );
+
+    // A screener cannot report a rejection without aborting the
+    // simulation, so only a missing screener shows up in the result
+    let screener_supplied = screener_supplied(pool, ctx.accounts.screener_program.as_ref());
+    if screener_supplied {
+        screen_withdrawal(
+            pool,
+            ctx.accounts.screener_program.as_ref(),
+            &ctx.accounts.withdrawer,
+            &nullifier,
+            clock::strip(ctx.remaining_accounts),
+        )?;
+    }
+
+    Ok(WithdrawalDryRun {
+        pool: pool.key(),
+        pool_active: pool.is_active,
+        nullifier_unused,
+        merkle_proof_valid,
+        circuit_registered,
+        proof_valid,
+        screener_supplied,
+        valid: pool.is_active
+            && nullifier_unused
+            && merkle_proof_valid
+            && circuit_registered
+            && proof_valid
+            && screener_supplied,
+        spend_anchor,
+    })
+}
+
+#[derive(Accounts)]
+pub struct GetPoolState<'info> {
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+}
+
+#[derive(Accounts)]
+pub struct GetProtocolStats<'info> {
+    #[account(seeds = [PROTOCOL_STATS_SEED], bump = protocol_stats.bump)]
+    pub protocol_stats: Account<'info, ProtocolStats>,
+}
+
+#[derive(Accounts)]
+pub struct GetReserveStatus<'info> {
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        seeds = [
+            RESERVE_ATTESTATION_SEED,
+            shielded_pool.key().as_ref(),
+            &reserve_attestation.merkle_root,
+            &reserve_attestation.nullifier_count.to_le_bytes()
+        ],
+        bump = reserve_attestation.bump
+    )]
+    pub reserve_attestation: Account<'info, ReserveAttestation>,
+}
+
+#[derive(Accounts)]
+#[instruction(nullifier: [u8; 32])]
+pub struct VerifyWithdrawalDryRun<'info> {
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    /// CHECK: Nullifier record PDA; only checked for existence
+    #[account(
+        seeds = [NULLIFIER_SEED, shielded_pool.key().as_ref(), &nullifier],
+        bump
+    )]
+    pub nullifier_account: UncheckedAccount<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+
+    /// CHECK: Recipient the withdrawal would pay, handed to the screener;
+    /// need not sign a simulation
+    pub withdrawer: UncheckedAccount<'info>,
+
+    /// CHECK: The pool's withdrawal screener, as in `shield_withdraw`;
+    /// matched against `screener_program` in `screener_supplied`
+    pub screener_program: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct GetProposalTally<'info> {
+    #[account(
+        seeds = [PROPOSAL_SEED, proposal.creator.as_ref(), &proposal.proposal_id],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+/// Return data of `get_pool_state`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PoolStateSnapshot {
+    pub pool: Pubkey,
+    pub pool_id: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub next_note_index: u32,
+    pub total_notes: u32,
+    pub nullifier_count: u32,
+    pub reward_rate_bps: u16,
+    pub lockup_epochs: u8,
+    pub is_active: bool,
+}
+
+/// Return data of `get_protocol_stats`, totals over the counted pools
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProtocolStatsSnapshot {
+    pub pool_count: u32,
+    pub total_notes: u64,
+    pub total_nullifiers: u64,
+    pub unspent_notes: u64,
+}
+
+/// Return data of `get_anonymity_metrics`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AnonymityMetrics {
+    pub pool: Pubkey,
+    /// Current metrics epoch (unix time / ANONYMITY_EPOCH_SECONDS)
+    pub epoch: u64,
+    pub total_notes: u32,
+    pub unspent_notes: u32,
+    pub deposits_this_epoch: u32,
+    pub deposits_last_epoch: u32,
+    /// Deposits per epoch, oldest first, ending with the current epoch
+    pub deposit_histogram: [u32; DEPOSIT_HISTOGRAM_BUCKETS],
+    pub unique_withdrawal_roots_used: u32,
+}
+
+/// Return data of `get_reserve_status`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ReserveStatus {
+    pub pool: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub vault_balance: u64,
+    pub published_at: i64,
+    /// False once notes were added or spent after the attestation
+    pub is_current: bool,
+}
+
+/// Return data of `verify_withdrawal_dry_run`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WithdrawalDryRun {
+    pub pool: Pubkey,
+    pub pool_active: bool,
+    /// No nullifier record exists for the nullifier yet
+    pub nullifier_unused: bool,
+    pub merkle_proof_valid: bool,
+    /// The withdrawal circuit matches the registry
+    pub circuit_registered: bool,
+    pub proof_valid: bool,
+    /// The pool screens nothing or its screener was passed (and accepted
+    /// the withdrawal, or the simulation would have failed)
+    pub screener_supplied: bool,
+    /// Every check passed
+    pub valid: bool,
+    /// Root the proof was checked against
+    pub spend_anchor: [u8; 32],
+}
+
+/// Return data of `get_proposal_tally`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProposalTally {
+    pub proposal: Pubkey,
+    pub yes_count: u32,
+    pub no_count: u32,
+    pub total_commitments: u32,
+    pub total_revealed: u32,
+    pub voting_ends_at: i64,
+    pub reveal_ends_at: i64,
+    pub is_finalized: bool,
+    pub min_reveal_bps: u16,
+    /// Whether the current reveals meet `min_reveal_bps`
+    pub reveal_quorum_met: bool,
+    /// Finalized with too few reveals: the tally is void
+    pub reveal_quorum_failed: bool,
+    /// Pool weights are proven against (default when not stake-weighted)
+    pub weight_pool: Pubkey,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+    /// Governance token mint (default when not token-weighted)
+    pub governing_mint: Pubkey,
+}
+
+/// Return data of `get_merkle_root`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MerkleRootSnapshot {
+    pub merkle_root: [u8; 32],
+    /// Number of leaves inserted (the next note index)
+    pub leaf_count: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::test_support::*;
+
+    #[test]
+    fn dry_runs_of_screened_pools_need_the_pools_screener() {
+        let mut pool: ShieldedPool = zeroed(ShieldedPool::LEN);
+        let program = || {
+            let info = account_info(Pubkey::new_unique(), Pubkey::default(), vec![], false);
+            UncheckedAccount::try_from(Box::leak(Box::new(info)))
+        };
+        let (screener, other) = (program(), program());
+
+        assert!(screener_supplied(&pool, None));
+
+        pool.screener_program = screener.key();
+        assert!(!screener_supplied(&pool, None));
+        assert!(!screener_supplied(&pool, Some(&other)));
+        assert!(screener_supplied(&pool, Some(&screener)));
+    }
+}
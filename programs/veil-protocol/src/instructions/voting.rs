@@ -0,0 +1,2287 @@
+//! Private commit-reveal voting, stake weighting and pool governance
+
+use crate::*;
+
+// ============================================
+// PRIVATE VOTING - Commit-Reveal Scheme
+// ============================================
+
+pub(crate) fn create_proposal(
+    ctx: Context<CreateProposal>,
+    proposal_id: [u8; 32],
+    metadata_hash: [u8; 32],
+    voting_ends_at: i64,
+    reveal_ends_at: i64,
+    min_reveal_bps: u16,
+    reveal_extension: i64,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(voting_ends_at > current_time, ErrorCode::InvalidVotingPeriod);
+    require!(reveal_ends_at > voting_ends_at, ErrorCode::InvalidRevealPeriod);
+    require!(
+        min_reveal_bps <= 10_000 && reveal_extension >= 0,
+        ErrorCode::InvalidRevealQuorum
+    );
+
+    proposal.proposal_id = proposal_id;
+    proposal.creator = ctx.accounts.creator.key();
+    proposal.metadata_hash = metadata_hash;
+    proposal.created_at = current_time;
+    proposal.voting_ends_at = voting_ends_at;
+    proposal.reveal_ends_at = reveal_ends_at;
+    proposal.yes_count = 0;
+    proposal.no_count = 0;
+    proposal.total_commitments = 0;
+    proposal.total_revealed = 0;
+    proposal.is_finalized = false;
+    proposal.bump = ctx.bumps.proposal;
+    proposal.version = Proposal::VERSION;
+    proposal.min_reveal_bps = min_reveal_bps;
+    proposal.reveal_extension = reveal_extension;
+    proposal.reveal_extensions = 0;
+    proposal.reveal_quorum_failed = false;
+    proposal.finalization_bounty = escrow_finalization_bounty(
+        proposal,
+        &ctx.accounts.creator,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit!(ProposalCreated {
+        proposal: proposal.key(),
+        proposal_id,
+        creator: ctx.accounts.creator.key(),
+        voting_ends_at,
+        reveal_ends_at,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateProposal,
+        proposal.key(),
+        proposal_id,
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn cast_vote(
+    ctx: Context<CastVote>,
+    vote_commitment: [u8; 32],
+    reveal_token_hash: [u8; 32],
+    eligibility: Option<&EligibilityProof>,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let vote_record = &mut ctx.accounts.vote_record;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(current_time < proposal.voting_ends_at, ErrorCode::VotingEnded);
+    require!(!vote_record.has_voted, ErrorCode::AlreadyVoted);
+    check_eligibility(proposal, &ctx.accounts.voter.key(), eligibility)?;
+    // One key, one vote would let weighted proposals be Sybiled
+    require!(
+        proposal.weight_pool == Pubkey::default(),
+        ErrorCode::StakeWeightedProposal
+    );
+    require!(
+        proposal.governing_mint == Pubkey::default(),
+        ErrorCode::TokenWeightedProposal
+    );
+
+    vote_record.proposal = proposal.key();
+    vote_record.voter = ctx.accounts.voter.key();
+    vote_record.commitment = vote_commitment;
+    vote_record.has_voted = true;
+    vote_record.has_revealed = false;
+    vote_record.voted_at = current_time;
+    vote_record.bump = ctx.bumps.vote_record;
+    vote_record.version = VoteRecord::VERSION;
+    vote_record.reveal_token_hash = reveal_token_hash;
+
+    proposal.total_commitments += 1;
+
+    emit!(VoteCast {
+        proposal: proposal.key(),
+        voter: ctx.accounts.voter.key(),
+        commitment: vote_commitment,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CastVote,
+        proposal.key(),
+        vote_commitment,
+        ctx.accounts.voter.key().to_bytes(),
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+/// Check a voter against the proposal's eligibility list, if it has one
+pub(crate) fn check_eligibility(
+    proposal: &Proposal,
+    voter: &Pubkey,
+    eligibility: Option<&EligibilityProof>,
+) -> Result<()> {
+    if proposal.eligibility_root != [0u8; 32] {
+        let eligibility = eligibility.ok_or(ErrorCode::EligibilityProofRequired)?;
+        require!(
+            verify_merkle_proof(
+                &proposal.eligibility_root,
+                &eligibility.siblings,
+                eligibility.path_indices,
+                &compute_eligibility_leaf(voter),
+            ),
+            ErrorCode::NotEligible
+        );
+    }
+    Ok(())
+}
+
+/// `cast_vote` for retries after an ambiguous failure: if the record
+/// already holds this exact vote it succeeds without changing anything;
+/// a different earlier vote still fails with `AlreadyVoted`. A first
+/// attempt on a restricted proposal needs `eligibility`, as with
+/// `cast_eligible_vote`
+pub(crate) fn cast_vote_idempotent(
+    ctx: Context<CastVote>,
+    vote_commitment: [u8; 32],
+    reveal_token_hash: [u8; 32],
+    eligibility: Option<EligibilityProof>,
+) -> Result<()> {
+    if ctx.accounts.vote_record.holds_vote(&vote_commitment, &reveal_token_hash) {
+        debug_log!(
+            "cast_vote_idempotent",
+            proposal = ctx.accounts.proposal.key(),
+            voter = ctx.accounts.voter.key(),
+        );
+        return Ok(());
+    }
+
+    cast_vote(ctx, vote_commitment, reveal_token_hash, eligibility.as_ref())
+}
+
+pub(crate) fn reveal_vote(
+    ctx: Context<RevealVote>,
+    vote_choice: bool, // true = yes, false = no
+    secret: [u8; 32],
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    reveal_vote_record(
+        proposal,
+        &mut ctx.accounts.vote_record,
+        &ctx.accounts.voter.key(),
+        vote_choice,
+        &secret,
+        current_time,
+    )?;
+
+    emit!(VoteRevealed {
+        proposal: proposal.key(),
+        voter: ctx.accounts.voter.key(),
+        // Note: We emit that a reveal happened, but not the choice
+        // Individual votes remain private even after reveal
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RevealVote,
+        proposal.key(),
+        ctx.accounts.voter.key().to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        proposal.total_revealed as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn reveal_vote_with_token(
+    ctx: Context<RevealVoteWithToken>,
+    vote_choice: bool,
+    secret: [u8; 32],
+    reveal_token: [u8; 32],
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let vote_record = &mut ctx.accounts.vote_record;
+    let voter = vote_record.voter;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(
+        vote_record.reveal_token_hash != [0u8; 32],
+        ErrorCode::RevealNotDelegated
+    );
+    require!(
+        constant_time_eq(&vote_record.reveal_token_hash, &compute_reveal_token_hash(&reveal_token)),
+        ErrorCode::InvalidRevealToken
+    );
+
+    // The commitment still binds the voter's key, not the revealer's
+    reveal_vote_record(proposal, vote_record, &voter, vote_choice, &secret, current_time)?;
+
+    emit!(VoteRevealed {
+        proposal: proposal.key(),
+        voter,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RevealVoteWithToken,
+        proposal.key(),
+        voter.to_bytes(),
+        ctx.accounts.revealer.key().to_bytes(),
+        [0u8; 32],
+        proposal.total_revealed as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let clock = clock::now(ctx.remaining_accounts)?;
+    let current_time = clock.unix_timestamp;
+
+    require!(current_time >= proposal.reveal_ends_at, ErrorCode::RevealNotEnded);
+    require!(!proposal.is_finalized, ErrorCode::AlreadyFinalized);
+
+    if proposal.tally_committed_at == 0 {
+        // Too few reveals to decide: give voters more time, then void the tally
+        if !proposal.reveal_quorum_met() {
+            if proposal.reveal_extension > 0 && proposal.reveal_extensions < MAX_REVEAL_EXTENSIONS {
+                proposal.reveal_extensions += 1;
+                proposal.reveal_ends_at = current_time.saturating_add(proposal.reveal_extension);
+
+                emit!(RevealPeriodExtended {
+                    proposal: proposal.key(),
+                    reveal_ends_at: proposal.reveal_ends_at,
+                    total_commitments: proposal.total_commitments,
+                    total_revealed: proposal.total_revealed,
+                    extensions: proposal.reveal_extensions,
+                    timestamp: current_time,
+                });
+
+                emit_cpi!(VeilRecord::new(
+                    RecordKind::ExtendRevealPeriod,
+                    proposal.key(),
+                    [0u8; 32],
+                    [0u8; 32],
+                    [0u8; 32],
+                    proposal.reveal_ends_at as u64,
+                    current_time,
+                ));
+
+                return Ok(());
+            }
+
+            proposal.reveal_quorum_failed = true;
+
+            emit!(UnrevealedVotesExpired {
+                proposal: proposal.key(),
+                total_commitments: proposal.total_commitments,
+                total_revealed: proposal.total_revealed,
+                min_reveal_bps: proposal.min_reveal_bps,
+                timestamp: current_time,
+            });
+        }
+
+        // Two-stage mode: commit to the tally and leave the dispute window open
+        if proposal.dispute_window > 0 {
+            proposal.tally_committed_at = current_time;
+            proposal.tally_commitment = compute_tally_commitment(
+                &proposal.key(),
+                &[
+                    proposal.yes_count as u64,
+                    proposal.no_count as u64,
+                    proposal.yes_weight,
+                    proposal.no_weight,
+                ],
+                proposal.reveal_quorum_failed,
+                clock.slot,
+            );
+
+            emit!(ProposalTallyCommitted {
+                proposal: proposal.key(),
+                tally_commitment: proposal.tally_commitment,
+                disputable_until: current_time.saturating_add(proposal.dispute_window),
+                timestamp: current_time,
+            });
+
+            emit_cpi!(VeilRecord::new(
+                RecordKind::CommitProposalTally,
+                proposal.key(),
+                proposal.tally_commitment,
+                [0u8; 32],
+                [0u8; 32],
+                proposal.dispute_window as u64,
+                current_time,
+            ));
+
+            return Ok(());
+        }
+    } else {
+        // Second call: the committed tally opens once no challenge can land
+        require!(
+            current_time >= proposal.tally_committed_at.saturating_add(proposal.dispute_window),
+            ErrorCode::DisputeWindowOpen
+        );
+    }
+
+    proposal.is_finalized = true;
+
+    // Whoever closes the tally out collects the creator's bounty
+    let bounty = std::mem::take(&mut proposal.finalization_bounty);
+    if bounty > 0 {
+        proposal.sub_lamports(bounty)?;
+        ctx.accounts.authority.add_lamports(bounty)?;
+
+        emit!(FinalizationBountyPaid {
+            proposal: proposal.key(),
+            finalizer: ctx.accounts.authority.key(),
+            lamports: bounty,
+            timestamp: current_time,
+        });
+    }
+
+    debug_log!(
+        "finalize_proposal",
+        proposal = proposal.key(),
+        yes = proposal.yes_count,
+        no = proposal.no_count,
+        commitments = proposal.total_commitments,
+        revealed = proposal.total_revealed,
+        quorum_failed = proposal.reveal_quorum_failed,
+    );
+
+    emit!(ProposalFinalized {
+        proposal: proposal.key(),
+        yes_count: proposal.yes_count,
+        no_count: proposal.no_count,
+        total_votes: proposal.total_revealed,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::FinalizeProposal,
+        proposal.key(),
+        [0u8; 32],
+        [0u8; 32],
+        [0u8; 32],
+        proposal.total_revealed as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+// ============================================
+// TALLY DISPUTES - Two-Stage Finalization
+// ============================================
+//
+// With a dispute window set, the first `finalize_proposal` only commits to
+// the tally: H(counts, quorum flag, slot). Until the window closes anyone
+// can challenge the commitment with a proof that it disagrees with the
+// valid reveals; a successful challenge voids the result. A second
+// `finalize_proposal` after the window makes the result binding, so actions
+// gated on `Proposal::passed` never run on a disputed count.
+
+pub(crate) fn enable_tally_dispute(ctx: Context<EnableTallyDispute>, dispute_window: i64) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(current_time < proposal.voting_ends_at, ErrorCode::VotingEnded);
+    require!(
+        proposal.total_commitments == 0 && proposal.dispute_window == 0,
+        ErrorCode::TallyDisputeLocked
+    );
+    require!(
+        (MIN_TALLY_DISPUTE_WINDOW..=MAX_TALLY_DISPUTE_WINDOW).contains(&dispute_window),
+        ErrorCode::InvalidDisputeWindow
+    );
+
+    proposal.dispute_window = dispute_window;
+
+    emit!(TallyDisputeEnabled {
+        proposal: proposal.key(),
+        dispute_window,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::EnableTallyDispute,
+        proposal.key(),
+        [0u8; 32],
+        [0u8; 32],
+        [0u8; 32],
+        dispute_window as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn challenge_proposal_tally(
+    ctx: Context<ChallengeProposalTally>,
+    challenge_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    require_proof_len(&challenge_proof)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(
+        proposal.tally_committed_at != 0 && !proposal.is_finalized,
+        ErrorCode::TallyNotCommitted
+    );
+    require!(
+        current_time < proposal.tally_committed_at.saturating_add(proposal.dispute_window),
+        ErrorCode::DisputeWindowClosed
+    );
+    require!(!proposal.tally_disputed, ErrorCode::TallyAlreadyDisputed);
+
+    let public_signals = [proposal.tally_commitment, proposal.key().to_bytes()];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::TallyChallenge, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::TallyChallenge, &public_signals, &challenge_proof),
+        ErrorCode::InvalidTallyChallenge
+    );
+
+    proposal.tally_disputed = true;
+
+    debug_log!("challenge_proposal_tally", proposal = proposal.key());
+
+    emit!(ProposalTallyDisputed {
+        proposal: proposal.key(),
+        tally_commitment: proposal.tally_commitment,
+        challenger: ctx.accounts.challenger.key(),
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ChallengeProposalTally,
+        proposal.key(),
+        proposal.tally_commitment,
+        ctx.accounts.challenger.key().to_bytes(),
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+// ============================================
+// PROPOSAL TEMPLATES - Standard Governance Parameters
+// ============================================
+//
+// A template fixes a DAO's voting and reveal periods, reveal quorum and
+// eligible voter root once. Proposals created from it take their deadlines
+// relative to the creation time, so no one passes raw timestamps.
+
+pub(crate) fn create_proposal_template(
+    ctx: Context<CreateProposalTemplate>,
+    template_id: [u8; 32],
+    voting_period: i64,
+    reveal_period: i64,
+    eligibility_root: [u8; 32],
+    min_reveal_bps: u16,
+    reveal_extension: i64,
+) -> Result<()> {
+    let template = &mut ctx.accounts.proposal_template;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(voting_period > 0, ErrorCode::InvalidVotingPeriod);
+    require!(reveal_period > 0, ErrorCode::InvalidRevealPeriod);
+    require!(
+        min_reveal_bps <= 10_000 && reveal_extension >= 0,
+        ErrorCode::InvalidRevealQuorum
+    );
+
+    template.creator = ctx.accounts.creator.key();
+    template.template_id = template_id;
+    template.voting_period = voting_period;
+    template.reveal_period = reveal_period;
+    template.eligibility_root = eligibility_root;
+    template.min_reveal_bps = min_reveal_bps;
+    template.reveal_extension = reveal_extension;
+    template.proposals_created = 0;
+    template.created_at = current_time;
+    template.bump = ctx.bumps.proposal_template;
+    template.version = ProposalTemplate::VERSION;
+
+    emit!(ProposalTemplateCreated {
+        template: template.key(),
+        creator: template.creator,
+        template_id,
+        voting_period,
+        reveal_period,
+        eligibility_root,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateProposalTemplate,
+        template.key(),
+        template_id,
+        [0u8; 32],
+        eligibility_root,
+        voting_period as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn create_proposal_from_template(
+    ctx: Context<CreateProposalFromTemplate>,
+    proposal_id: [u8; 32],
+    metadata_hash: [u8; 32],
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let template = &mut ctx.accounts.proposal_template;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    let voting_ends_at = current_time
+        .checked_add(template.voting_period)
+        .ok_or(ErrorCode::InvalidVotingPeriod)?;
+    let reveal_ends_at = voting_ends_at
+        .checked_add(template.reveal_period)
+        .ok_or(ErrorCode::InvalidRevealPeriod)?;
+
+    proposal.proposal_id = proposal_id;
+    proposal.creator = ctx.accounts.creator.key();
+    proposal.metadata_hash = metadata_hash;
+    proposal.created_at = current_time;
+    proposal.voting_ends_at = voting_ends_at;
+    proposal.reveal_ends_at = reveal_ends_at;
+    proposal.yes_count = 0;
+    proposal.no_count = 0;
+    proposal.total_commitments = 0;
+    proposal.total_revealed = 0;
+    proposal.is_finalized = false;
+    proposal.bump = ctx.bumps.proposal;
+    proposal.version = Proposal::VERSION;
+    proposal.min_reveal_bps = template.min_reveal_bps;
+    proposal.reveal_extension = template.reveal_extension;
+    proposal.reveal_extensions = 0;
+    proposal.reveal_quorum_failed = false;
+    proposal.eligibility_root = template.eligibility_root;
+    proposal.finalization_bounty = escrow_finalization_bounty(
+        proposal,
+        &ctx.accounts.creator,
+        &ctx.accounts.system_program,
+    )?;
+
+    template.proposals_created += 1;
+
+    emit!(ProposalCreated {
+        proposal: proposal.key(),
+        proposal_id,
+        creator: ctx.accounts.creator.key(),
+        voting_ends_at,
+        reveal_ends_at,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateProposalFromTemplate,
+        proposal.key(),
+        proposal_id,
+        template.key().to_bytes(),
+        template.eligibility_root,
+        template.proposals_created,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+// ============================================
+// STAKE-WEIGHTED VOTING - Private Governance Power
+// ============================================
+//
+// A proposal can take its voting weight from a shielded pool instead of
+// counting one vote per key. Enabling it snapshots the pool's Merkle
+// root; each vote then proves, in zero knowledge, a note in that
+// snapshot worth at least `weight * weight_unit` without revealing the
+// amount. The vote nullifier H(note secret, proposal) keys the vote
+// record, so a note votes once per proposal however many keys its owner
+// holds, and notes created after the snapshot cannot vote at all.
+// Voters choose `weight`; claiming less than they hold hides more.
+
+pub(crate) fn enable_stake_weighted_voting(
+    ctx: Context<EnableStakeWeightedVoting>,
+    weight_unit: u64,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let pool = &ctx.accounts.shielded_pool;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(current_time < proposal.voting_ends_at, ErrorCode::VotingEnded);
+    require!(
+        proposal.total_commitments == 0 && !proposal.is_weighted(),
+        ErrorCode::StakeWeightingLocked
+    );
+    // Weighted votes are cast without an eligibility proof
+    require!(proposal.eligibility_root == [0u8; 32], ErrorCode::EligibilityRestricted);
+    require!(weight_unit > 0, ErrorCode::InvalidVoteWeight);
+
+    proposal.weight_pool = pool.key();
+    proposal.weight_root = pool.merkle_root;
+    proposal.weight_unit = weight_unit;
+
+    emit!(StakeWeightingEnabled {
+        proposal: proposal.key(),
+        pool: pool.key(),
+        weight_root: pool.merkle_root,
+        weight_unit,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::EnableStakeWeightedVoting,
+        proposal.key(),
+        pool.key().to_bytes(),
+        [0u8; 32],
+        pool.merkle_root,
+        weight_unit,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn cast_weighted_vote(
+    ctx: Context<CastWeightedVote>,
+    weight_nullifier: [u8; 32],
+    vote_commitment: [u8; 32],
+    weight: u64,
+    weight_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    require_proof_len(&weight_proof)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    let vote_record = &mut ctx.accounts.vote_record;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(current_time < proposal.voting_ends_at, ErrorCode::VotingEnded);
+    require!(proposal.weight_pool != Pubkey::default(), ErrorCode::NotStakeWeighted);
+    let min_value = weight
+        .checked_mul(proposal.weight_unit)
+        .filter(|_| weight > 0)
+        .ok_or(ErrorCode::InvalidVoteWeight)?;
+
+    let public_signals = [
+        proposal.weight_root,
+        weight_nullifier,
+        vote_commitment,
+        compute_stake_weight_tag(&proposal.key(), &proposal.weight_pool, min_value),
+    ];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::StakeWeight, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::StakeWeight, &public_signals, &weight_proof),
+        ErrorCode::InvalidStakeWeightProof
+    );
+
+    vote_record.proposal = proposal.key();
+    vote_record.voter = ctx.accounts.voter.key();
+    vote_record.commitment = vote_commitment;
+    vote_record.has_voted = true;
+    vote_record.has_revealed = false;
+    vote_record.voted_at = current_time;
+    vote_record.bump = ctx.bumps.vote_record;
+    vote_record.version = VoteRecord::VERSION;
+    vote_record.weight = weight;
+
+    proposal.total_commitments += 1;
+
+    emit!(WeightedVoteCast {
+        proposal: proposal.key(),
+        weight_nullifier,
+        commitment: vote_commitment,
+        weight,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CastWeightedVote,
+        proposal.key(),
+        weight_nullifier,
+        vote_commitment,
+        proposal.weight_root,
+        weight,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn reveal_weighted_vote(
+    ctx: Context<RevealWeightedVote>,
+    _weight_nullifier: [u8; 32],
+    vote_choice: bool,
+    secret: [u8; 32],
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    reveal_vote_record(
+        proposal,
+        &mut ctx.accounts.vote_record,
+        &ctx.accounts.voter.key(),
+        vote_choice,
+        &secret,
+        current_time,
+    )?;
+
+    emit!(VoteRevealed {
+        proposal: proposal.key(),
+        voter: ctx.accounts.voter.key(),
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RevealWeightedVote,
+        proposal.key(),
+        ctx.accounts.voter.key().to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        proposal.total_revealed as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+// ============================================
+// CONVICTION VOTING - Continuous Shielded Signal
+// ============================================
+//
+// A conviction proposal has no voting phase. Stakers commit a shielded
+// note to it, hiding its weight in a stake commitment, and the longer the
+// note stays unspent the more of that weight counts: conviction grows
+// linearly to the full weight over `ramp_period`. Stakers prove their
+// accrued conviction in zero knowledge whenever they like; each proof
+// replaces their previous contribution, and the proposal passes the first
+// time the total reaches its threshold.
+//
+// A proof only shows the note unspent as of the spend anchor it was made
+// against, so it counts for the `CONVICTION_PROOF_WINDOW` it falls in.
+// The total is kept per window: stakes re-prove each window, and a note
+// that has since been spent cannot, so its conviction lapses with it.
+
+pub(crate) fn create_conviction_proposal(
+    ctx: Context<CreateConvictionProposal>,
+    proposal_id: [u8; 32],
+    metadata_hash: [u8; 32],
+    weight_unit: u64,
+    ramp_period: i64,
+    conviction_threshold: u64,
+    closes_at: i64,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(
+        weight_unit > 0
+            && conviction_threshold > 0
+            && (1..=MAX_CONVICTION_RAMP_PERIOD).contains(&ramp_period)
+            && closes_at > current_time,
+        ErrorCode::InvalidConvictionProposal
+    );
+
+    proposal.proposal_id = proposal_id;
+    proposal.creator = ctx.accounts.creator.key();
+    proposal.metadata_hash = metadata_hash;
+    proposal.pool = ctx.accounts.shielded_pool.key();
+    proposal.weight_unit = weight_unit;
+    proposal.ramp_period = ramp_period;
+    proposal.conviction_threshold = conviction_threshold;
+    proposal.total_conviction = 0;
+    proposal.stakes = 0;
+    proposal.created_at = current_time;
+    proposal.closes_at = closes_at;
+    proposal.passed_at = 0;
+    proposal.bump = ctx.bumps.proposal;
+    proposal.version = ConvictionProposal::VERSION;
+    proposal.conviction_window = 0;
+
+    emit!(ConvictionProposalCreated {
+        proposal: proposal.key(),
+        pool: proposal.pool,
+        conviction_threshold,
+        ramp_period,
+        closes_at,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateConvictionProposal,
+        proposal.key(),
+        proposal_id,
+        proposal.pool.to_bytes(),
+        [0u8; 32],
+        conviction_threshold,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn commit_conviction_stake(
+    ctx: Context<CommitConvictionStake>,
+    stake_nullifier: [u8; 32],
+    stake_commitment: [u8; 32],
+    stake_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    require_proof_len(&stake_proof)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    let stake = &mut ctx.accounts.conviction_stake;
+    let pool = &ctx.accounts.shielded_pool;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(current_time < proposal.closes_at, ErrorCode::ConvictionClosed);
+
+    let public_signals = [
+        pool.merkle_root,
+        stake_nullifier,
+        stake_commitment,
+        proposal.key().to_bytes(),
+    ];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::ConvictionStake, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::ConvictionStake, &public_signals, &stake_proof),
+        ErrorCode::InvalidConvictionStakeProof
+    );
+
+    stake.proposal = proposal.key();
+    stake.owner = ctx.accounts.owner.key();
+    stake.stake_nullifier = stake_nullifier;
+    stake.stake_commitment = stake_commitment;
+    stake.committed_at = current_time;
+    stake.conviction = 0;
+    stake.proven_at = 0;
+    stake.bump = ctx.bumps.conviction_stake;
+    stake.version = ConvictionStake::VERSION;
+
+    proposal.stakes += 1;
+
+    emit!(ConvictionStakeCommitted {
+        proposal: proposal.key(),
+        stake: stake.key(),
+        stake_commitment,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CommitConvictionStake,
+        proposal.key(),
+        stake_nullifier,
+        stake_commitment,
+        pool.merkle_root,
+        proposal.stakes as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn prove_conviction(
+    ctx: Context<ProveConviction>,
+    conviction: u64,
+    conviction_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    require_proof_len(&conviction_proof)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    let stake = &mut ctx.accounts.conviction_stake;
+    let pool = &ctx.accounts.shielded_pool;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(current_time < proposal.closes_at, ErrorCode::ConvictionClosed);
+
+    let elapsed = current_time.saturating_sub(stake.committed_at);
+    let public_signals = [
+        stake.stake_commitment,
+        pool.spend_anchor(),
+        encode_u64_signal(pool.nullifier_count as u64),
+        compute_conviction_tag(
+            &stake.key(),
+            conviction,
+            elapsed,
+            proposal.ramp_period,
+            proposal.weight_unit,
+        ),
+    ];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::Conviction, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::Conviction, &public_signals, &conviction_proof),
+        ErrorCode::InvalidConvictionProof
+    );
+
+    record_conviction(proposal, stake, conviction, current_time);
+
+    let threshold_reached = proposal.passed_at == 0 && proposal.total_conviction >= proposal.conviction_threshold;
+    if threshold_reached {
+        proposal.passed_at = current_time;
+    }
+
+    debug_log!(
+        "prove_conviction",
+        proposal = proposal.key(),
+        total = proposal.total_conviction,
+        threshold = proposal.conviction_threshold,
+    );
+
+    emit!(ConvictionProven {
+        proposal: proposal.key(),
+        stake: stake.key(),
+        conviction,
+        total_conviction: proposal.total_conviction,
+        threshold_reached,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ProveConviction,
+        proposal.key(),
+        stake.key().to_bytes(),
+        [0u8; 32],
+        pool.spend_anchor(),
+        proposal.total_conviction,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn withdraw_conviction_stake(ctx: Context<WithdrawConvictionStake>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let stake = &ctx.accounts.conviction_stake;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    // A passed proposal keeps its pass; the live total still drops
+    remove_conviction(proposal, stake, current_time);
+    proposal.stakes = proposal.stakes.saturating_sub(1);
+
+    emit!(ConvictionStakeWithdrawn {
+        proposal: proposal.key(),
+        stake: stake.key(),
+        total_conviction: proposal.total_conviction,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::WithdrawConvictionStake,
+        proposal.key(),
+        stake.key().to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        proposal.total_conviction,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+/// Start the proof window `now` falls in, if the total is from an earlier
+/// one, and return it
+fn roll_conviction_window(proposal: &mut ConvictionProposal, now: i64) -> u64 {
+    let window = proposal.conviction_window_at(now);
+    if proposal.conviction_window != window {
+        proposal.conviction_window = window;
+        proposal.total_conviction = 0;
+    }
+    window
+}
+
+/// Conviction `stake` contributes to the total of `window`
+fn counted_conviction(proposal: &ConvictionProposal, stake: &ConvictionStake, window: u64) -> u64 {
+    if proposal.conviction_window_at(stake.proven_at) == window {
+        stake.conviction
+    } else {
+        0
+    }
+}
+
+/// Replace `stake`'s contribution with `conviction`, proven at `now`
+pub(crate) fn record_conviction(
+    proposal: &mut ConvictionProposal,
+    stake: &mut ConvictionStake,
+    conviction: u64,
+    now: i64,
+) {
+    let window = roll_conviction_window(proposal, now);
+    proposal.total_conviction = proposal
+        .total_conviction
+        .saturating_sub(counted_conviction(proposal, stake, window))
+        .saturating_add(conviction);
+    stake.conviction = conviction;
+    stake.proven_at = now;
+}
+
+/// Take `stake`'s contribution out of the total, if it still counts
+pub(crate) fn remove_conviction(proposal: &mut ConvictionProposal, stake: &ConvictionStake, now: i64) {
+    let window = roll_conviction_window(proposal, now);
+    proposal.total_conviction = proposal
+        .total_conviction
+        .saturating_sub(counted_conviction(proposal, stake, window));
+}
+
+// ============================================
+// QUADRATIC VOTING - ZK Square-Sum Budgets
+// ============================================
+//
+// Voters hold credit leaves in the proposal's credit root, each committing
+// to a budget the voter keeps hidden. A vote spreads counts across the
+// options and proves, in zero knowledge, that the sum of their squares is
+// within the leaf's budget. The counts are the proof's public inputs, so
+// the program adds them straight to the tallies; the vote nullifier keys
+// the vote record and stops a leaf voting twice without naming the voter.
+
+pub(crate) fn create_quadratic_proposal(
+    ctx: Context<CreateQuadraticProposal>,
+    proposal_id: [u8; 32],
+    metadata_hash: [u8; 32],
+    credit_root: [u8; 32],
+    option_count: u8,
+    voting_ends_at: i64,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(voting_ends_at > current_time, ErrorCode::InvalidVotingPeriod);
+    require!(
+        (2..=MAX_QUADRATIC_OPTIONS).contains(&(option_count as usize)) && credit_root != [0u8; 32],
+        ErrorCode::InvalidQuadraticProposal
+    );
+
+    proposal.proposal_id = proposal_id;
+    proposal.creator = ctx.accounts.creator.key();
+    proposal.metadata_hash = metadata_hash;
+    proposal.credit_root = credit_root;
+    proposal.option_count = option_count;
+    proposal.tallies = [0; MAX_QUADRATIC_OPTIONS];
+    proposal.total_voters = 0;
+    proposal.created_at = current_time;
+    proposal.voting_ends_at = voting_ends_at;
+    proposal.bump = ctx.bumps.proposal;
+    proposal.version = QuadraticProposal::VERSION;
+
+    emit!(QuadraticProposalCreated {
+        proposal: proposal.key(),
+        creator: proposal.creator,
+        credit_root,
+        option_count,
+        voting_ends_at,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateQuadraticProposal,
+        proposal.key(),
+        proposal_id,
+        [0u8; 32],
+        credit_root,
+        option_count as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn cast_quadratic_vote(
+    ctx: Context<CastQuadraticVote>,
+    vote_nullifier: [u8; 32],
+    votes: Vec<u64>,
+    allocation_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    require_proof_len(&allocation_proof)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    let vote_record = &mut ctx.accounts.vote_record;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(current_time < proposal.voting_ends_at, ErrorCode::VotingEnded);
+    require!(
+        votes.len() == proposal.option_count as usize && votes.iter().any(|votes| *votes > 0),
+        ErrorCode::InvalidQuadraticVote
+    );
+
+    let public_signals = [
+        proposal.credit_root,
+        vote_nullifier,
+        compute_quadratic_vote_tag(&proposal.key(), &votes),
+    ];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::QuadraticVote, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::QuadraticVote, &public_signals, &allocation_proof),
+        ErrorCode::InvalidQuadraticProof
+    );
+
+    for (tally, votes) in proposal.tallies.iter_mut().zip(&votes) {
+        *tally = tally.saturating_add(*votes);
+    }
+    proposal.total_voters += 1;
+
+    vote_record.proposal = proposal.key();
+    vote_record.vote_nullifier = vote_nullifier;
+    vote_record.cast_at = current_time;
+    vote_record.bump = ctx.bumps.vote_record;
+    vote_record.version = QuadraticVoteRecord::VERSION;
+
+    debug_log!(
+        "cast_quadratic_vote",
+        proposal = proposal.key(),
+        voters = proposal.total_voters,
+    );
+
+    emit!(QuadraticVoteCast {
+        proposal: proposal.key(),
+        vote_nullifier,
+        votes,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CastQuadraticVote,
+        proposal.key(),
+        vote_nullifier,
+        [0u8; 32],
+        proposal.credit_root,
+        proposal.total_voters as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+// ============================================
+// POOL GOVERNANCE - Voting on Pool Parameters
+// ============================================
+//
+// A pool hands itself to its stakers with `govern_pool`, which sets its
+// authority to its governance PDA (`pda::pool_governance_address`) and
+// fixes the rules update proposals must meet there. From then on its
+// parameters change only through proposals stake-weighted by the same
+// pool: the proposal creator attaches a parameter update before voting
+// opens, and once the proposal is finalized as passing, with the stake
+// quorum met and the execution delay over, anyone can execute the update
+// against the pool. The rules are checked again at execution, so a
+// proposal cannot be loosened after its update was attached.
+
+pub(crate) fn govern_pool(ctx: Context<GovernPool>, config: PoolGovernanceConfig) -> Result<()> {
+    let pool = &mut ctx.accounts.shielded_pool;
+    let governance = &mut ctx.accounts.pool_governance;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    validate_pool_governance(&config)?;
+
+    governance.pool = pool.key();
+    governance.config = config.clone();
+    governance.updates_executed = 0;
+    governance.created_at = current_time;
+    governance.bump = ctx.bumps.pool_governance;
+    governance.version = PoolGovernance::VERSION;
+
+    let previous_authority = pool.authority;
+    pool.authority = governance.key();
+
+    emit!(PoolGoverned {
+        pool: pool.key(),
+        governance: governance.key(),
+        previous_authority,
+        config,
+        timestamp: current_time,
+    });
+
+    append_audit_entry(
+        ctx.accounts.audit_log.as_mut(),
+        RecordKind::GovernPool,
+        governance.key().to_bytes(),
+        0,
+        current_time,
+    );
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::GovernPool,
+        pool.key(),
+        governance.key().to_bytes(),
+        previous_authority.to_bytes(),
+        pool.merkle_root,
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn attach_pool_update(ctx: Context<AttachPoolUpdate>, update: PoolParamUpdate) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    let pool_update = &mut ctx.accounts.pool_update;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(
+        proposal.total_commitments == 0 && current_time < proposal.voting_ends_at,
+        ErrorCode::PoolUpdateLocked
+    );
+    check_governed_proposal(
+        &ctx.accounts.pool_governance.config,
+        proposal,
+        &ctx.accounts.shielded_pool.key(),
+    )?;
+    validate_pool_update(&update)?;
+
+    pool_update.proposal = proposal.key();
+    pool_update.pool = proposal.weight_pool;
+    pool_update.update = update.clone();
+    pool_update.executed = false;
+    pool_update.executed_at = 0;
+    pool_update.created_at = current_time;
+    pool_update.bump = ctx.bumps.pool_update;
+    pool_update.version = PoolUpdateProposal::VERSION;
+
+    emit!(PoolUpdateAttached {
+        proposal: proposal.key(),
+        pool: pool_update.pool,
+        update,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::AttachPoolUpdate,
+        proposal.key(),
+        pool_update.pool.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn execute_pool_update(ctx: Context<ExecutePoolUpdate>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    let pool_update = &mut ctx.accounts.pool_update;
+    let pool = &mut ctx.accounts.shielded_pool;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(!pool_update.executed, ErrorCode::ProposalAlreadyExecuted);
+    check_pool_update_executable(&ctx.accounts.pool_governance.config, proposal, &pool.key(), current_time)?;
+
+    match pool_update.update {
+        PoolParamUpdate::RewardRate { reward_rate_bps } => pool.reward_rate_bps = reward_rate_bps,
+        PoolParamUpdate::LockupEpochs { lockup_epochs } => pool.lockup_epochs = lockup_epochs,
+        PoolParamUpdate::SetActive { is_active } => pool.is_active = is_active,
+        PoolParamUpdate::NoteExpiry { note_expiry_seconds, treasury } => {
+            require!(pool.pending_sweep_through == 0, ErrorCode::NoteSweepPending);
+            pool.note_expiry_seconds = note_expiry_seconds;
+            pool.treasury = treasury;
+        }
+    }
+
+    pool_update.executed = true;
+    pool_update.executed_at = current_time;
+    ctx.accounts.pool_governance.updates_executed += 1;
+
+    debug_log!(
+        "execute_pool_update",
+        proposal = proposal.key(),
+        pool = pool.key(),
+    );
+
+    emit!(PoolUpdateExecuted {
+        proposal: proposal.key(),
+        pool: pool.key(),
+        update: pool_update.update.clone(),
+        timestamp: current_time,
+    });
+
+    append_audit_entry(
+        ctx.accounts.audit_log.as_mut(),
+        RecordKind::ExecutePoolUpdate,
+        proposal.key().to_bytes(),
+        0,
+        current_time,
+    );
+
+    if matches!(pool_update.update, PoolParamUpdate::SetActive { is_active: false }) {
+        raise_watchtower_alert(
+            ctx.accounts.watchtower.as_mut(),
+            WatchtowerAlertKind::PoolPaused,
+            proposal.key().to_bytes(),
+            0,
+            current_time,
+        );
+    }
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ExecutePoolUpdate,
+        proposal.key(),
+        pool.key().to_bytes(),
+        [0u8; 32],
+        pool.merkle_root,
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(proposal_id: [u8; 32])]
+pub struct CreateProposal<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = Proposal::LEN,
+        seeds = [PROPOSAL_SEED, creator.key().as_ref(), &proposal_id],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.creator.as_ref(), &proposal.proposal_id],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Created by the first cast; `init_if_needed` lets a second cast fail
+    /// with `AlreadyVoted`, or succeed unchanged through the idempotent variant
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = VoteRecord::LEN,
+        seeds = [VOTE_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EnableTallyDispute<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.creator.as_ref(), &proposal.proposal_id],
+        bump = proposal.bump,
+        has_one = creator @ ErrorCode::Unauthorized
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub creator: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ChallengeProposalTally<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.creator.as_ref(), &proposal.proposal_id],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Anyone can challenge
+    pub challenger: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(template_id: [u8; 32])]
+pub struct CreateProposalTemplate<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = ProposalTemplate::LEN,
+        seeds = [PROPOSAL_TEMPLATE_SEED, creator.key().as_ref(), &template_id],
+        bump
+    )]
+    pub proposal_template: Account<'info, ProposalTemplate>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(proposal_id: [u8; 32])]
+pub struct CreateProposalFromTemplate<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_TEMPLATE_SEED, proposal_template.creator.as_ref(), &proposal_template.template_id],
+        bump = proposal_template.bump
+    )]
+    pub proposal_template: Account<'info, ProposalTemplate>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = Proposal::LEN,
+        seeds = [PROPOSAL_SEED, creator.key().as_ref(), &proposal_id],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Anyone can propose under a published template
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EnableStakeWeightedVoting<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.creator.as_ref(), &proposal.proposal_id],
+        bump = proposal.bump,
+        constraint = proposal.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    pub creator: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct GovernPool<'info> {
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PoolGovernance::LEN,
+        seeds = [POOL_GOVERNANCE_SEED, shielded_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_governance: Account<'info, PoolGovernance>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// The pool's audit log, appended to when it keeps one
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, shielded_pool.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AttachPoolUpdate<'info> {
+    #[account(
+        seeds = [PROPOSAL_SEED, proposal.creator.as_ref(), &proposal.proposal_id],
+        bump = proposal.bump,
+        constraint = proposal.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    /// The pool's governance rules; updates attach to governed pools only
+    #[account(
+        seeds = [POOL_GOVERNANCE_SEED, shielded_pool.key().as_ref()],
+        bump = pool_governance.bump
+    )]
+    pub pool_governance: Account<'info, PoolGovernance>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = PoolUpdateProposal::LEN,
+        seeds = [POOL_UPDATE_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub pool_update: Account<'info, PoolUpdateProposal>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecutePoolUpdate<'info> {
+    #[account(
+        seeds = [PROPOSAL_SEED, proposal.creator.as_ref(), &proposal.proposal_id],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [POOL_UPDATE_SEED, proposal.key().as_ref()],
+        bump = pool_update.bump,
+        constraint = pool_update.pool == shielded_pool.key() @ ErrorCode::InvalidPoolUpdate
+    )]
+    pub pool_update: Account<'info, PoolUpdateProposal>,
+
+    #[account(
+        mut,
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.authority == pool_governance.key() @ ErrorCode::PoolNotGoverned
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    /// The pool's governance rules, checked again at execution
+    #[account(
+        mut,
+        seeds = [POOL_GOVERNANCE_SEED, shielded_pool.key().as_ref()],
+        bump = pool_governance.bump
+    )]
+    pub pool_governance: Account<'info, PoolGovernance>,
+
+    pub executor: Signer<'info>,
+
+    /// The pool's audit log, appended to when it keeps one
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, shielded_pool.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+
+    /// The pool's watchtower, alerted when it has one
+    #[account(
+        mut,
+        seeds = [WATCHTOWER_SEED, shielded_pool.key().as_ref()],
+        bump = watchtower.bump
+    )]
+    pub watchtower: Option<Account<'info, Watchtower>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(weight_nullifier: [u8; 32])]
+pub struct CastWeightedVote<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.creator.as_ref(), &proposal.proposal_id],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Keyed by the vote nullifier, so `init` rejects a second vote by the same note
+    #[account(
+        init,
+        payer = voter,
+        space = VoteRecord::LEN,
+        seeds = [STAKE_VOTE_SEED, proposal.key().as_ref(), &weight_nullifier],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(weight_nullifier: [u8; 32])]
+pub struct RevealWeightedVote<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.creator.as_ref(), &proposal.proposal_id],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_VOTE_SEED, proposal.key().as_ref(), &weight_nullifier],
+        bump = vote_record.bump,
+        constraint = vote_record.voter == voter.key() @ ErrorCode::Unauthorized
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    pub voter: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevealVote<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.creator.as_ref(), &proposal.proposal_id],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump = vote_record.bump,
+        constraint = vote_record.voter == voter.key() @ ErrorCode::Unauthorized
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    pub voter: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevealVoteWithToken<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.creator.as_ref(), &proposal.proposal_id],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, proposal.key().as_ref(), vote_record.voter.as_ref()],
+        bump = vote_record.bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    /// Reveal service holding the token; not the voter
+    pub revealer: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal.creator.as_ref(), &proposal.proposal_id],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Anyone can finalize and collects the finalization bounty
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(proposal_id: [u8; 32])]
+pub struct CreateConvictionProposal<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = ConvictionProposal::LEN,
+        seeds = [CONVICTION_PROPOSAL_SEED, creator.key().as_ref(), &proposal_id],
+        bump
+    )]
+    pub proposal: Account<'info, ConvictionProposal>,
+
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(stake_nullifier: [u8; 32])]
+pub struct CommitConvictionStake<'info> {
+    #[account(
+        mut,
+        seeds = [CONVICTION_PROPOSAL_SEED, proposal.creator.as_ref(), &proposal.proposal_id],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, ConvictionProposal>,
+
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.key() == proposal.pool @ ErrorCode::InvalidConvictionProposal
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    /// Keyed by the stake nullifier, so `init` rejects a note staking twice
+    #[account(
+        init,
+        payer = owner,
+        space = ConvictionStake::LEN,
+        seeds = [CONVICTION_STAKE_SEED, proposal.key().as_ref(), &stake_nullifier],
+        bump
+    )]
+    pub conviction_stake: Account<'info, ConvictionStake>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProveConviction<'info> {
+    #[account(
+        mut,
+        seeds = [CONVICTION_PROPOSAL_SEED, proposal.creator.as_ref(), &proposal.proposal_id],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, ConvictionProposal>,
+
+    #[account(
+        seeds = [SHIELDED_POOL_SEED, shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
+        bump = shielded_pool.bump,
+        constraint = shielded_pool.key() == proposal.pool @ ErrorCode::InvalidConvictionProposal
+    )]
+    pub shielded_pool: Account<'info, ShieldedPool>,
+
+    #[account(
+        mut,
+        seeds = [CONVICTION_STAKE_SEED, proposal.key().as_ref(), &conviction_stake.stake_nullifier],
+        bump = conviction_stake.bump,
+        has_one = owner @ ErrorCode::Unauthorized
+    )]
+    pub conviction_stake: Account<'info, ConvictionStake>,
+
+    pub owner: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawConvictionStake<'info> {
+    #[account(
+        mut,
+        seeds = [CONVICTION_PROPOSAL_SEED, proposal.creator.as_ref(), &proposal.proposal_id],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, ConvictionProposal>,
+
+    #[account(
+        mut,
+        seeds = [CONVICTION_STAKE_SEED, proposal.key().as_ref(), &conviction_stake.stake_nullifier],
+        bump = conviction_stake.bump,
+        has_one = owner @ ErrorCode::Unauthorized,
+        close = owner
+    )]
+    pub conviction_stake: Account<'info, ConvictionStake>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(proposal_id: [u8; 32])]
+pub struct CreateQuadraticProposal<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = QuadraticProposal::LEN,
+        seeds = [QUADRATIC_PROPOSAL_SEED, creator.key().as_ref(), &proposal_id],
+        bump
+    )]
+    pub proposal: Account<'info, QuadraticProposal>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(vote_nullifier: [u8; 32])]
+pub struct CastQuadraticVote<'info> {
+    #[account(
+        mut,
+        seeds = [QUADRATIC_PROPOSAL_SEED, proposal.creator.as_ref(), &proposal.proposal_id],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, QuadraticProposal>,
+
+    /// Keyed by the vote nullifier, so `init` rejects a second vote by the same leaf
+    #[account(
+        init,
+        payer = payer,
+        space = QuadraticVoteRecord::LEN,
+        seeds = [QUADRATIC_VOTE_SEED, proposal.key().as_ref(), &vote_nullifier],
+        bump
+    )]
+    pub vote_record: Account<'info, QuadraticVoteRecord>,
+
+    /// Any fee payer; the voter is proven, not signed
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+/// Move the finalization bounty from the creator into a new proposal,
+/// on top of its rent; returns the escrowed lamports
+pub(crate) fn escrow_finalization_bounty<'info>(
+    proposal: &Account<'info, Proposal>,
+    creator: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<u64> {
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: creator.to_account_info(),
+                to: proposal.to_account_info(),
+            },
+        ),
+        FINALIZATION_BOUNTY_LAMPORTS,
+    )?;
+    Ok(FINALIZATION_BOUNTY_LAMPORTS)
+}
+
+/// Check a pool parameter update against the limits `create_shielded_pool`
+/// and `configure_note_expiry` enforce
+pub(crate) fn validate_pool_update(update: &PoolParamUpdate) -> Result<()> {
+    match update {
+        PoolParamUpdate::RewardRate { reward_rate_bps } => {
+            require!(*reward_rate_bps <= 10000, ErrorCode::InvalidRewardRate);
+        }
+        PoolParamUpdate::LockupEpochs { lockup_epochs } => {
+            require!((1..=52).contains(lockup_epochs), ErrorCode::InvalidLockupPeriod);
+        }
+        PoolParamUpdate::SetActive { .. } => {}
+        PoolParamUpdate::NoteExpiry { note_expiry_seconds, treasury } => {
+            require!(
+                *note_expiry_seconds == 0 ||
+                    (*note_expiry_seconds >= MIN_NOTE_EXPIRY_SECONDS && *treasury != Pubkey::default()),
+                ErrorCode::InvalidNoteExpiry
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Check the rules a pool fixes when it is handed to governance
+pub(crate) fn validate_pool_governance(config: &PoolGovernanceConfig) -> Result<()> {
+    require!(
+        config.min_voting_period >= MIN_POOL_GOVERNANCE_PERIOD &&
+            config.min_reveal_period >= MIN_POOL_GOVERNANCE_PERIOD &&
+            (1..=10_000).contains(&config.min_reveal_bps) &&
+            config.quorum_lamports > 0 &&
+            (config.min_dispute_window == 0 ||
+                (MIN_TALLY_DISPUTE_WINDOW..=MAX_TALLY_DISPUTE_WINDOW).contains(&config.min_dispute_window)) &&
+            (0..=MAX_POOL_GOVERNANCE_DELAY).contains(&config.execution_delay),
+        ErrorCode::InvalidPoolGovernance
+    );
+    Ok(())
+}
+
+/// Check that `proposal` is stake-weighted by `pool` and meets its
+/// governance rules: long enough phases, reveal quorum, dispute window and
+/// (when the pool names one) proposer
+pub(crate) fn check_governed_proposal(config: &PoolGovernanceConfig, proposal: &Proposal, pool: &Pubkey) -> Result<()> {
+    require!(proposal.weight_pool == *pool, ErrorCode::NotStakeWeighted);
+    require!(
+        proposal.voting_ends_at - proposal.created_at >= config.min_voting_period &&
+            proposal.reveal_ends_at - proposal.voting_ends_at >= config.min_reveal_period &&
+            proposal.min_reveal_bps >= config.min_reveal_bps &&
+            proposal.dispute_window >= config.min_dispute_window &&
+            (config.proposer == Pubkey::default() || proposal.creator == config.proposer),
+        ErrorCode::PoolGovernanceViolation
+    );
+    Ok(())
+}
+
+/// Check that a governed pool's update may run at `now`: the proposal
+/// still meets the pool's rules, passed with at least the stake quorum
+/// voting YES, and the execution delay after its reveal phase is over
+pub(crate) fn check_pool_update_executable(
+    config: &PoolGovernanceConfig,
+    proposal: &Proposal,
+    pool: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    check_governed_proposal(config, proposal, pool)?;
+    require!(
+        proposal.passed() &&
+            proposal.yes_weight.saturating_mul(proposal.weight_unit) >= config.quorum_lamports,
+        ErrorCode::ProposalNotPassed
+    );
+    require!(
+        now >= proposal.reveal_ends_at.saturating_add(config.execution_delay),
+        ErrorCode::PoolUpdateDelayActive
+    );
+    Ok(())
+}
+
+/// Check a vote reveal against its commitment and add it to the tally
+/// Stake-weighted votes also add their weight to the weighted tally
+pub(crate) fn reveal_vote_record(
+    proposal: &mut Account<Proposal>,
+    vote_record: &mut Account<VoteRecord>,
+    voter: &Pubkey,
+    vote_choice: bool,
+    secret: &[u8; 32],
+    now: i64,
+) -> Result<()> {
+    require!(now >= proposal.voting_ends_at, ErrorCode::VotingNotEnded);
+    require!(now < proposal.reveal_ends_at, ErrorCode::RevealEnded);
+    require!(vote_record.has_voted, ErrorCode::NotVoted);
+    require!(!vote_record.has_revealed, ErrorCode::AlreadyRevealed);
+
+    // Verify the commitment matches: H(DOMAIN_VOTE, vote_choice || secret || voter)
+    // Votes cast before the v1 hash upgrade may use the untagged v0 commitment
+    let expected_commitment = compute_vote_commitment(vote_choice, secret, voter);
+    let matches_legacy = vote_record.voted_at < LEGACY_VOTE_COMMITMENT_CUTOFF
+        && constant_time_eq(
+            &vote_record.commitment,
+            &compute_vote_commitment_v0(vote_choice, secret, voter),
+        );
+    require!(
+        constant_time_eq(&vote_record.commitment, &expected_commitment) || matches_legacy,
+        ErrorCode::InvalidVoteReveal
+    );
+
+    vote_record.has_revealed = true;
+    vote_record.revealed_choice = vote_choice;
+    vote_record.revealed_at = now;
+
+    proposal.total_revealed += 1;
+    if vote_choice {
+        proposal.yes_count += 1;
+        proposal.yes_weight = proposal.yes_weight.saturating_add(vote_record.weight);
+    } else {
+        proposal.no_count += 1;
+        proposal.no_weight = proposal.no_weight.saturating_add(vote_record.weight);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::test_support::*;
+
+    const DAY: i64 = 86_400;
+    const LAMPORTS: u64 = 1_000_000_000;
+
+    fn config() -> PoolGovernanceConfig {
+        PoolGovernanceConfig {
+            min_voting_period: 3 * DAY,
+            min_reveal_period: DAY,
+            min_reveal_bps: 5_000,
+            quorum_lamports: 100 * LAMPORTS,
+            min_dispute_window: DAY,
+            execution_delay: 2 * DAY,
+            proposer: Pubkey::default(),
+        }
+    }
+
+    /// A proposal over `pool` that meets `config()` and passed with 150 SOL
+    /// of YES stake
+    fn governed_proposal(pool: &Pubkey) -> Proposal {
+        let mut proposal: Proposal = zeroed(Proposal::LEN);
+        proposal.creator = Pubkey::new_unique();
+        proposal.created_at = 1_700_000_000;
+        proposal.voting_ends_at = proposal.created_at + 3 * DAY;
+        proposal.reveal_ends_at = proposal.voting_ends_at + DAY;
+        proposal.min_reveal_bps = 5_000;
+        proposal.dispute_window = DAY;
+        proposal.weight_pool = *pool;
+        proposal.weight_unit = LAMPORTS;
+        proposal.yes_weight = 150;
+        proposal.no_weight = 20;
+        proposal.total_commitments = 4;
+        proposal.total_revealed = 4;
+        proposal.is_finalized = true;
+        proposal
+    }
+
+    #[test]
+    fn governance_rules_must_bind_proposals() {
+        assert!(validate_pool_governance(&config()).is_ok());
+
+        let weakened: [fn(&mut PoolGovernanceConfig); 5] = [
+            |config| config.min_voting_period = 60,
+            |config| config.min_reveal_period = 0,
+            |config| config.min_reveal_bps = 0,
+            |config| config.quorum_lamports = 0,
+            |config| config.execution_delay = MAX_POOL_GOVERNANCE_DELAY + 1,
+        ];
+        for weaken in weakened {
+            let mut config = config();
+            weaken(&mut config);
+            assert_error(validate_pool_governance(&config), ErrorCode::InvalidPoolGovernance);
+        }
+    }
+
+    #[test]
+    fn updates_attach_only_to_proposals_meeting_the_rules() {
+        let pool = Pubkey::new_unique();
+        assert!(check_governed_proposal(&config(), &governed_proposal(&pool), &pool).is_ok());
+
+        // A takeover attempt: minimal periods, no reveal quorum, no disputes
+        let mut hostile = governed_proposal(&pool);
+        hostile.voting_ends_at = hostile.created_at + 1;
+        hostile.reveal_ends_at = hostile.voting_ends_at + 1;
+        hostile.min_reveal_bps = 0;
+        hostile.dispute_window = 0;
+        assert_error(check_governed_proposal(&config(), &hostile, &pool), ErrorCode::PoolGovernanceViolation);
+
+        let tweaks: [fn(&mut Proposal); 4] = [
+            |proposal| proposal.voting_ends_at -= 1,
+            |proposal| proposal.reveal_ends_at -= 1,
+            |proposal| proposal.min_reveal_bps -= 1,
+            |proposal| proposal.dispute_window = 0,
+        ];
+        for tweak in tweaks {
+            let mut proposal = governed_proposal(&pool);
+            tweak(&mut proposal);
+            assert_error(check_governed_proposal(&config(), &proposal, &pool), ErrorCode::PoolGovernanceViolation);
+        }
+
+        assert_error(
+            check_governed_proposal(&config(), &governed_proposal(&pool), &Pubkey::new_unique()),
+            ErrorCode::NotStakeWeighted,
+        );
+    }
+
+    #[test]
+    fn only_the_named_proposer_attaches_updates() {
+        let pool = Pubkey::new_unique();
+        let proposal = governed_proposal(&pool);
+        let mut config = config();
+
+        config.proposer = Pubkey::new_unique();
+        assert_error(check_governed_proposal(&config, &proposal, &pool), ErrorCode::PoolGovernanceViolation);
+        config.proposer = proposal.creator;
+        assert!(check_governed_proposal(&config, &proposal, &pool).is_ok());
+    }
+
+    #[test]
+    fn updates_execute_after_the_quorum_and_delay() {
+        let pool = Pubkey::new_unique();
+        let proposal = governed_proposal(&pool);
+        let executable_at = proposal.reveal_ends_at + config().execution_delay;
+
+        assert!(check_pool_update_executable(&config(), &proposal, &pool, executable_at).is_ok());
+        assert_error(
+            check_pool_update_executable(&config(), &proposal, &pool, executable_at - 1),
+            ErrorCode::PoolUpdateDelayActive,
+        );
+
+        // One note voting alone passes the tally but not the stake quorum
+        let mut single_note = governed_proposal(&pool);
+        single_note.yes_weight = 1;
+        single_note.no_weight = 0;
+        assert_error(
+            check_pool_update_executable(&config(), &single_note, &pool, executable_at),
+            ErrorCode::ProposalNotPassed,
+        );
+
+        let mut rejected = governed_proposal(&pool);
+        rejected.no_weight = rejected.yes_weight;
+        assert_error(
+            check_pool_update_executable(&config(), &rejected, &pool, executable_at),
+            ErrorCode::ProposalNotPassed,
+        );
+    }
+
+    fn conviction_proposal() -> ConvictionProposal {
+        let mut proposal: ConvictionProposal = zeroed(ConvictionProposal::LEN);
+        proposal.created_at = 1_700_000_000;
+        proposal.conviction_threshold = 100;
+        proposal
+    }
+
+    fn conviction_stake() -> ConvictionStake {
+        zeroed(ConvictionStake::LEN)
+    }
+
+    #[test]
+    fn conviction_stops_counting_once_a_spent_note_cannot_re_prove() {
+        let mut proposal = conviction_proposal();
+        let start = proposal.created_at;
+        let (mut spent, mut unspent) = (conviction_stake(), conviction_stake());
+
+        record_conviction(&mut proposal, &mut spent, 80, start + 3_600);
+        assert_eq!(proposal.live_conviction(start + 7_200), 80);
+
+        // The note is spent, so no proof against a later spend anchor exists
+        // for it; its proof lapses with the window
+        let next_window = start + DAY + 3_600;
+        assert_eq!(proposal.live_conviction(next_window), 0);
+
+        record_conviction(&mut proposal, &mut unspent, 30, next_window);
+        assert_eq!(proposal.total_conviction, 30);
+        assert!(proposal.total_conviction < proposal.conviction_threshold);
+
+        // Withdrawing the lapsed stake leaves the live total alone
+        remove_conviction(&mut proposal, &spent, next_window + 60);
+        assert_eq!(proposal.total_conviction, 30);
+    }
+
+    #[test]
+    fn re_proving_replaces_a_stakes_contribution() {
+        let mut proposal = conviction_proposal();
+        let start = proposal.created_at;
+        let (mut first, mut second) = (conviction_stake(), conviction_stake());
+
+        record_conviction(&mut proposal, &mut first, 40, start + 60);
+        record_conviction(&mut proposal, &mut first, 60, start + 120);
+        record_conviction(&mut proposal, &mut second, 10, start + 180);
+        assert_eq!(proposal.total_conviction, 70);
+
+        record_conviction(&mut proposal, &mut first, 70, start + DAY);
+        assert_eq!(proposal.total_conviction, 70);
+        record_conviction(&mut proposal, &mut second, 30, start + DAY + 60);
+        assert_eq!(proposal.total_conviction, 100);
+
+        remove_conviction(&mut proposal, &first, start + DAY + 120);
+        assert_eq!(proposal.total_conviction, 30);
+    }
+
+    /// A proposal restricted to `voter` alone, and the proof of its leaf
+    fn eligible_proposal(voter: &Pubkey) -> (Proposal, EligibilityProof) {
+        let mut siblings = [[0u8; 32]; MERKLE_TREE_DEPTH];
+        for level in 1..MERKLE_TREE_DEPTH {
+            siblings[level] = hash_domain(DOMAIN_MERKLE_NODE, &[&siblings[level - 1], &siblings[level - 1]]);
+        }
+        let mut filled = [[0u8; 32]; MERKLE_TREE_DEPTH];
+        let mut proposal: Proposal = zeroed(Proposal::LEN);
+        proposal.eligibility_root = append_to_filled_subtrees(&mut filled, &compute_eligibility_leaf(voter), 0);
+        (proposal, EligibilityProof { siblings, path_indices: 0 })
+    }
+
+    #[test]
+    fn idempotent_votes_carry_their_eligibility_proof() {
+        let voter = Pubkey::new_unique();
+        let (proposal, proof) = eligible_proposal(&voter);
+
+        // First attempt: the proof the idempotent entry point now takes
+        assert!(check_eligibility(&proposal, &voter, Some(&proof)).is_ok());
+        assert_error(
+            check_eligibility(&proposal, &voter, None),
+            ErrorCode::EligibilityProofRequired,
+        );
+        assert_error(
+            check_eligibility(&proposal, &Pubkey::new_unique(), Some(&proof)),
+            ErrorCode::NotEligible,
+        );
+
+        // Unrestricted proposals need none
+        let open: Proposal = zeroed(Proposal::LEN);
+        assert!(check_eligibility(&open, &voter, None).is_ok());
+    }
+
+    #[test]
+    fn idempotent_retries_match_only_the_recorded_vote() {
+        let mut record: VoteRecord = zeroed(VoteRecord::LEN);
+        assert!(!record.holds_vote(&[1u8; 32], &[0u8; 32]));
+
+        record.has_voted = true;
+        record.commitment = [1u8; 32];
+        assert!(record.holds_vote(&[1u8; 32], &[0u8; 32]));
+        assert!(!record.holds_vote(&[2u8; 32], &[0u8; 32]));
+        assert!(!record.holds_vote(&[1u8; 32], &[3u8; 32]));
+    }
+}
@@ -0,0 +1,1602 @@
+//! Commitment wallets: proofs, recovery, freezing, aliases and attestations
+
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+use crate::*;
+
+pub(crate) fn initialize_commitment(
+    ctx: Context<InitializeCommitment>,
+    commitment: [u8; 32],
+) -> Result<()> {
+    let wallet_account = &mut ctx.accounts.wallet_account;
+    wallet_account.commitment = commitment;
+    wallet_account.owner = ctx.accounts.user.key();
+    wallet_account.created_at = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+    wallet_account.recovery_active = false;
+    wallet_account.bump = ctx.bumps.wallet_account;
+    wallet_account.version = WalletAccount::VERSION;
+
+    emit!(CommitmentCreated {
+        wallet: wallet_account.key(),
+        commitment,
+        timestamp: wallet_account.created_at,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::InitializeCommitment,
+        wallet_account.key(),
+        commitment,
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        wallet_account.created_at,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn submit_proof<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SubmitProof<'info>>,
+    proof_data: Vec<u8>,
+    public_signals: Vec<[u8; 32]>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    let remaining_accounts = clock::strip(ctx.remaining_accounts);
+    let wallet_account = &ctx.accounts.wallet_account;
+
+    require!(!wallet_account.frozen, ErrorCode::FrozenWallet);
+
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::Wallet, &circuit_id)?;
+    // Nothing is emitted or routed for a proof that does not verify
+    let proof_hash = verify_wallet_proof(wallet_account, &proof_data, &public_signals)?;
+
+    let public_signals_hash = hash_public_signals(&public_signals);
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    debug_log!(
+        "submit_proof",
+        wallet = wallet_account.key(),
+        signals = public_signals.len(),
+        proof_hash = Hex(&proof_hash),
+    );
+
+    emit!(ProofVerified {
+        wallet: wallet_account.key(),
+        proof_hash,
+        public_signals_hash,
+        verification_type: ProofType::Groth16,
+        timestamp: current_time,
+    });
+
+    if !remaining_accounts.is_empty() {
+        let payload = ProofVerifiedPayload {
+            wallet: wallet_account.key(),
+            commitment: wallet_account.commitment,
+            proof_hash,
+            public_signals_hash,
+            verification_type: ProofType::Groth16,
+            timestamp: current_time,
+        };
+        route_proof_verified(ctx.program_id, remaining_accounts, &payload)?;
+
+        emit!(ProofRouted {
+            wallet: wallet_account.key(),
+            target_program: remaining_accounts[0].key(),
+            proof_hash,
+            timestamp: current_time,
+        });
+    }
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::SubmitProof,
+        wallet_account.key(),
+        proof_hash,
+        public_signals_hash,
+        [0u8; 32],
+        public_signals.len() as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+/// Verify a wallet proof over `public_signals`, whose first signal must be
+/// the wallet's commitment, and return the proof's hash
+pub(crate) fn verify_wallet_proof(
+    wallet_account: &WalletAccount,
+    proof_data: &[u8],
+    public_signals: &[[u8; 32]],
+) -> Result<[u8; 32]> {
+    // Verify proof structure (Groth16 format: 256 bytes)
+    require_proof_len(proof_data)?;
+    require!(proof_data.len() >= 256, ErrorCode::InvalidProofStructure);
+    require!(!public_signals.is_empty(), ErrorCode::InvalidProof);
+    require!(
+        public_signals.len() <= MAX_PUBLIC_SIGNALS as usize,
+        ErrorCode::TooManyPublicSignals
+    );
+
+    // pi_a, pi_c (G1) and pi_b (G2) must be canonical points on their curves
+    validate_groth16_points(proof_data)?;
+
+    // Verify each public signal is a valid field element
+    for signal in public_signals {
+        require!(verify_field_element(signal), ErrorCode::InvalidPublicSignal);
+    }
+
+    // Verify the first public signal matches the wallet commitment
+    // This ensures the proof is for this specific wallet
+    require!(
+        constant_time_eq(&public_signals[0], &wallet_account.commitment),
+        ErrorCode::CommitmentMismatch
+    );
+
+    // In production: Groth16 pairing check against the wallet circuit's key
+    require!(
+        verify_groth16_stub(Circuit::Wallet, public_signals, proof_data),
+        ErrorCode::InvalidProof
+    );
+
+    let proof_hash = compute_proof_hash(proof_data, public_signals);
+    require!(proof_hash != [0u8; 32], ErrorCode::InvalidProofHash);
+    Ok(proof_hash)
+}
+
+pub(crate) fn initiate_recovery(
+    ctx: Context<InitiateRecovery>,
+    recovery_commitment: [u8; 32],
+    timelock_days: u8,
+) -> Result<()> {
+    let wallet_account = &mut ctx.accounts.wallet_account;
+
+    require!(!wallet_account.recovery_active, ErrorCode::RecoveryAlreadyActive);
+    require!(
+        wallet_account.pending_owner == Pubkey::default(),
+        ErrorCode::HandoverPending
+    );
+    require!((1..=90).contains(&timelock_days), ErrorCode::InvalidTimelockPeriod);
+    // The recovery commitment is what lifts a freeze, so it is pinned
+    // until the freeze is lifted
+    require!(
+        !wallet_account.frozen || recovery_commitment == wallet_account.recovery_commitment,
+        ErrorCode::FrozenWallet
+    );
+
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+    let unlock_time = current_time + (timelock_days as i64 * 86400); // days to seconds
+
+    wallet_account.recovery_commitment = recovery_commitment;
+    wallet_account.recovery_initiated_at = current_time;
+    wallet_account.recovery_unlock_at = unlock_time;
+    wallet_account.recovery_active = true;
+
+    emit!(RecoveryInitiated {
+        wallet: wallet_account.key(),
+        recovery_commitment,
+        unlock_time,
+    });
+
+    append_audit_entry(
+        ctx.accounts.audit_log.as_mut(),
+        RecordKind::InitiateRecovery,
+        recovery_commitment,
+        unlock_time as u64,
+        current_time,
+    );
+
+    raise_watchtower_alert(
+        ctx.accounts.watchtower.as_mut(),
+        WatchtowerAlertKind::RecoveryInitiated,
+        recovery_commitment,
+        unlock_time as u64,
+        current_time,
+    );
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::InitiateRecovery,
+        wallet_account.key(),
+        recovery_commitment,
+        [0u8; 32],
+        [0u8; 32],
+        unlock_time as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn execute_recovery(
+    ctx: Context<ExecuteRecovery>,
+    recovery_proof: Vec<u8>,
+    new_owner: Pubkey,
+) -> Result<()> {
+    let wallet_account = &mut ctx.accounts.wallet_account;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(!wallet_account.frozen, ErrorCode::FrozenWallet);
+    require!(wallet_account.recovery_active, ErrorCode::NoActiveRecovery);
+    require!(current_time >= wallet_account.recovery_unlock_at, ErrorCode::TimelockNotExpired);
+    require!(!recovery_proof.is_empty(), ErrorCode::InvalidProof);
+    require!(new_owner != Pubkey::default(), ErrorCode::InvalidNewOwner);
+
+    // TODO: Verify recovery proof matches recovery_commitment
+    // For demo, we accept valid structure
+
+    wallet_account.recovery_active = false;
+    wallet_account.pending_owner = new_owner;
+
+    debug_log!("execute_recovery", wallet = wallet_account.key(), time = current_time);
+
+    emit!(RecoveryExecuted {
+        wallet: wallet_account.key(),
+        pending_owner: new_owner,
+        timestamp: current_time,
+    });
+
+    append_audit_entry(
+        ctx.accounts.audit_log.as_mut(),
+        RecordKind::ExecuteRecovery,
+        new_owner.to_bytes(),
+        0,
+        current_time,
+    );
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ExecuteRecovery,
+        wallet_account.key(),
+        new_owner.to_bytes(),
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn accept_recovery(ctx: Context<AcceptRecovery>) -> Result<()> {
+    let wallet_account = &mut ctx.accounts.wallet_account;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(!wallet_account.frozen, ErrorCode::FrozenWallet);
+    require!(
+        wallet_account.pending_owner != Pubkey::default(),
+        ErrorCode::NoPendingHandover
+    );
+
+    // Pin the PDA's seed before the owner key it was derived from changes
+    wallet_account.original_owner = wallet_account.seed_owner();
+    let previous_owner = wallet_account.owner;
+    wallet_account.owner = wallet_account.pending_owner;
+    wallet_account.pending_owner = Pubkey::default();
+    wallet_account.recovery_executed_at = current_time;
+
+    emit!(RecoveryAccepted {
+        wallet: wallet_account.key(),
+        previous_owner,
+        new_owner: wallet_account.owner,
+        timestamp: current_time,
+    });
+
+    append_audit_entry(
+        ctx.accounts.audit_log.as_mut(),
+        RecordKind::AcceptRecovery,
+        wallet_account.owner.to_bytes(),
+        0,
+        current_time,
+    );
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::AcceptRecovery,
+        wallet_account.key(),
+        wallet_account.owner.to_bytes(),
+        previous_owner.to_bytes(),
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+    let wallet_account = &mut ctx.accounts.wallet_account;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(
+        wallet_account.recovery_active || wallet_account.pending_owner != Pubkey::default(),
+        ErrorCode::NoActiveRecovery
+    );
+
+    wallet_account.recovery_active = false;
+    wallet_account.pending_owner = Pubkey::default();
+
+    emit!(RecoveryCancelled {
+        wallet: wallet_account.key(),
+        timestamp: current_time,
+    });
+
+    append_audit_entry(
+        ctx.accounts.audit_log.as_mut(),
+        RecordKind::CancelRecovery,
+        [0u8; 32],
+        0,
+        current_time,
+    );
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CancelRecovery,
+        wallet_account.key(),
+        [0u8; 32],
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn freeze_wallet(ctx: Context<FreezeWallet>) -> Result<()> {
+    let wallet_account = &mut ctx.accounts.wallet_account;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(!wallet_account.frozen, ErrorCode::FrozenWallet);
+    // Only the recovery secret lifts a freeze, so there must be one
+    require!(
+        wallet_account.recovery_commitment != [0u8; 32],
+        ErrorCode::NoRecoveryCommitment
+    );
+
+    wallet_account.frozen = true;
+    wallet_account.frozen_at = current_time;
+
+    emit!(WalletFrozen {
+        wallet: wallet_account.key(),
+        timestamp: current_time,
+    });
+
+    append_audit_entry(
+        ctx.accounts.audit_log.as_mut(),
+        RecordKind::FreezeWallet,
+        wallet_account.recovery_commitment,
+        0,
+        current_time,
+    );
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::FreezeWallet,
+        wallet_account.key(),
+        wallet_account.recovery_commitment,
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn unfreeze_wallet(
+    ctx: Context<UnfreezeWallet>,
+    unfreeze_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    require_proof_len(&unfreeze_proof)?;
+
+    let wallet_account = &mut ctx.accounts.wallet_account;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(wallet_account.frozen, ErrorCode::WalletNotFrozen);
+
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::WalletUnfreeze, &circuit_id)?;
+    verify_unfreeze_proof(wallet_account, &wallet_account.key(), &unfreeze_proof)?;
+
+    let frozen_at = wallet_account.frozen_at;
+    wallet_account.frozen = false;
+    wallet_account.frozen_at = 0;
+
+    emit!(WalletUnfrozen {
+        wallet: wallet_account.key(),
+        frozen_at,
+        timestamp: current_time,
+    });
+
+    append_audit_entry(
+        ctx.accounts.audit_log.as_mut(),
+        RecordKind::UnfreezeWallet,
+        [0u8; 32],
+        frozen_at as u64,
+        current_time,
+    );
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::UnfreezeWallet,
+        wallet_account.key(),
+        [0u8; 32],
+        [0u8; 32],
+        [0u8; 32],
+        frozen_at as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+/// Check an unfreeze proof of the wallet's recovery secret
+/// The freeze is lifted by the recovery commitment set before it, never by
+/// one the freezing key chose, so a stolen key cannot freeze the wallet
+/// under a secret of its own and lock recovery out
+pub(crate) fn verify_unfreeze_proof(wallet_account: &WalletAccount, wallet: &Pubkey, unfreeze_proof: &[u8]) -> Result<()> {
+    let public_signals = [
+        wallet_account.recovery_commitment,
+        compute_wallet_unfreeze_tag(wallet, wallet_account.frozen_at),
+    ];
+    require!(
+        verify_groth16_stub(Circuit::WalletUnfreeze, &public_signals, unfreeze_proof),
+        ErrorCode::InvalidUnfreezeProof
+    );
+    Ok(())
+}
+
+pub(crate) fn execute_as_wallet<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteAsWallet<'info>>,
+    instruction_data: Vec<u8>,
+    wallet_proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    let remaining_accounts = clock::strip(ctx.remaining_accounts);
+    let wallet_account = &mut ctx.accounts.wallet_account;
+    let wallet_key = wallet_account.key();
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(!wallet_account.frozen, ErrorCode::FrozenWallet);
+    require!(!remaining_accounts.is_empty(), ErrorCode::InvalidWalletCallAccounts);
+    let target_program = &remaining_accounts[0];
+    let call_accounts = &remaining_accounts[1..];
+    require!(
+        target_program.executable && WALLET_CPI_ALLOWLIST.contains(target_program.key),
+        ErrorCode::ProgramNotAllowlisted
+    );
+
+    let call_tag = compute_wallet_call_tag(
+        &wallet_key,
+        wallet_account.call_nonce,
+        target_program.key,
+        call_accounts,
+        &instruction_data,
+    );
+    let public_signals = [wallet_account.commitment, call_tag];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::WalletCall, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::WalletCall, &public_signals, &wallet_proof),
+        ErrorCode::InvalidWalletCallProof
+    );
+
+    let nonce = wallet_account.call_nonce;
+    wallet_account.call_nonce += 1;
+
+    let metas = call_accounts
+        .iter()
+        .map(|account| {
+            let is_signer = account.is_signer || account.key() == wallet_key;
+            if account.is_writable {
+                AccountMeta::new(account.key(), is_signer)
+            } else {
+                AccountMeta::new_readonly(account.key(), is_signer)
+            }
+        })
+        .collect();
+    let ix = Instruction {
+        program_id: target_program.key(),
+        accounts: metas,
+        data: instruction_data,
+    };
+    invoke_signed(
+        &ix,
+        remaining_accounts,
+        &[&[WALLET_SEED, wallet_account.seed_owner().as_ref(), &[wallet_account.bump]]],
+    )?;
+
+    debug_log!(
+        "execute_as_wallet",
+        wallet = wallet_key,
+        program = target_program.key(),
+        nonce = nonce,
+    );
+
+    emit!(WalletCallExecuted {
+        wallet: wallet_key,
+        target_program: target_program.key(),
+        call_tag,
+        nonce,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ExecuteAsWallet,
+        wallet_key,
+        call_tag,
+        target_program.key().to_bytes(),
+        [0u8; 32],
+        nonce,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+// ============================================
+// ALIASES - Private Payment Names
+// ============================================
+//
+// A wallet registers alias_hash = H(DOMAIN_ALIAS, name || salt) with its
+// stealth meta-address (spend and view public keys) encrypted under a
+// key derived from the same name and salt. Only someone told the name
+// can find the record and decrypt the meta-address, so human-readable
+// payment targets work without publishing a name -> address mapping.
+// Control follows the wallet account, so a recovered wallet keeps its
+// aliases.
+
+pub(crate) fn register_alias(
+    ctx: Context<RegisterAlias>,
+    alias_hash: [u8; 32],
+    encrypted_meta_address: [u8; 64],
+) -> Result<()> {
+    let alias = &mut ctx.accounts.alias;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(!ctx.accounts.wallet_account.frozen, ErrorCode::FrozenWallet);
+    require!(alias_hash != [0u8; 32], ErrorCode::InvalidAlias);
+
+    alias.alias_hash = alias_hash;
+    alias.wallet = ctx.accounts.wallet_account.key();
+    alias.encrypted_meta_address = encrypted_meta_address;
+    alias.created_at = current_time;
+    alias.updated_at = current_time;
+    alias.bump = ctx.bumps.alias;
+    alias.version = AliasRecord::VERSION;
+
+    emit!(AliasRegistered {
+        alias: alias.key(),
+        alias_hash,
+        timestamp: current_time,
+        // Note: the wallet is NOT included - resolvers only need the hash
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RegisterAlias,
+        alias.key(),
+        alias_hash,
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn update_alias(ctx: Context<UpdateAlias>, encrypted_meta_address: [u8; 64]) -> Result<()> {
+    let alias = &mut ctx.accounts.alias;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(!ctx.accounts.wallet_account.frozen, ErrorCode::FrozenWallet);
+
+    alias.encrypted_meta_address = encrypted_meta_address;
+    alias.updated_at = current_time;
+
+    emit!(AliasUpdated {
+        alias: alias.key(),
+        alias_hash: alias.alias_hash,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::UpdateAlias,
+        alias.key(),
+        alias.alias_hash,
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn release_alias(ctx: Context<ReleaseAlias>) -> Result<()> {
+    let alias = &ctx.accounts.alias;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(!ctx.accounts.wallet_account.frozen, ErrorCode::FrozenWallet);
+
+    emit!(AliasReleased {
+        alias: alias.key(),
+        alias_hash: alias.alias_hash,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::ReleaseAlias,
+        alias.key(),
+        alias.alias_hash,
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+// ============================================
+// BACKUPS - Encrypted Off-Chain Backup Anchoring
+// ============================================
+//
+// The wallet keeps the hash of its latest encrypted backup and every
+// version gets its own `WalletBackup` record linking to the one before,
+// so recovery tooling can check a restored blob against the exact
+// version it expected. The blob and its URI stay off-chain.
+
+pub(crate) fn register_backup(ctx: Context<RegisterBackup>, backup_hash: [u8; 32], uri_hash: [u8; 32]) -> Result<()> {
+    let wallet = &mut ctx.accounts.wallet_account;
+    let backup = &mut ctx.accounts.backup;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(!wallet.frozen, ErrorCode::FrozenWallet);
+    require!(
+        backup_hash != [0u8; 32] && backup_hash != wallet.backup_hash,
+        ErrorCode::InvalidBackup
+    );
+
+    backup.wallet = wallet.key();
+    backup.backup_version = wallet.backup_version + 1;
+    backup.backup_hash = backup_hash;
+    backup.uri_hash = uri_hash;
+    backup.previous_hash = wallet.backup_hash;
+    backup.registered_at = current_time;
+    backup.bump = ctx.bumps.backup;
+    backup.version = WalletBackup::VERSION;
+
+    wallet.backup_version = backup.backup_version;
+    wallet.backup_hash = backup_hash;
+
+    debug_log!(
+        "register_backup",
+        wallet = wallet.key(),
+        backup_version = backup.backup_version,
+    );
+
+    emit!(BackupRegistered {
+        wallet: wallet.key(),
+        backup: backup.key(),
+        backup_version: backup.backup_version,
+        backup_hash,
+        previous_hash: backup.previous_hash,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RegisterBackup,
+        wallet.key(),
+        backup_hash,
+        backup.previous_hash,
+        [0u8; 32],
+        backup.backup_version,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+// ============================================
+// WALLET REGISTRY - Enrolled Commitments
+// ============================================
+
+pub(crate) fn create_wallet_registry(ctx: Context<CreateWalletRegistry>) -> Result<()> {
+    let registry = &mut ctx.accounts.wallet_registry;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    registry.root = [0u8; 32];
+    registry.recent_roots = [[0u8; 32]; WALLET_ROOT_HISTORY];
+    registry.root_index = 0;
+    registry.filled_subtrees = [[0u8; 32]; WALLET_TREE_DEPTH];
+    registry.wallet_count = 0;
+    registry.created_at = current_time;
+    registry.bump = ctx.bumps.wallet_registry;
+    registry.version = WalletRegistry::VERSION;
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::CreateWalletRegistry,
+        registry.key(),
+        [0u8; 32],
+        [0u8; 32],
+        [0u8; 32],
+        0,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+/// Append the wallet's current commitment to the registry tree. The tree
+/// is append-only: a commitment replaced by recovery stays a member, and
+/// the new one is enrolled separately
+pub(crate) fn enroll_wallet(ctx: Context<EnrollWallet>) -> Result<()> {
+    let wallet = &ctx.accounts.wallet_account;
+    let registry = &mut ctx.accounts.wallet_registry;
+    let enrollment = &mut ctx.accounts.enrollment;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(!wallet.frozen, ErrorCode::FrozenWallet);
+
+    let leaf_index = registry.enroll(&wallet.commitment)?;
+
+    enrollment.wallet = wallet.key();
+    enrollment.commitment = wallet.commitment;
+    enrollment.leaf_index = leaf_index;
+    enrollment.enrolled_at = current_time;
+    enrollment.bump = ctx.bumps.enrollment;
+    enrollment.version = WalletEnrollment::VERSION;
+
+    debug_log!(
+        "enroll_wallet",
+        wallet = wallet.key(),
+        leaf_index = leaf_index,
+        root = Hex(&registry.root),
+    );
+
+    emit!(WalletEnrolled {
+        wallet: wallet.key(),
+        commitment: wallet.commitment,
+        leaf_index,
+        root: registry.root,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::EnrollWallet,
+        wallet.key(),
+        wallet.commitment,
+        [0u8; 32],
+        registry.root,
+        leaf_index as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+// ============================================
+// WALLET ATTESTATIONS - ZK Property Proofs
+// ============================================
+
+pub(crate) fn attest_wallet_property(
+    ctx: Context<AttestWalletProperty>,
+    attestation_nullifier: [u8; 32],
+    claim: AttestationClaim,
+    threshold: u64,
+    proof_data: Vec<u8>,
+    public_signals: Vec<[u8; 32]>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    let attestation = &mut ctx.accounts.attestation;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(threshold > 0, ErrorCode::InvalidAttestationThreshold);
+    require!(proof_data.len() >= 256, ErrorCode::InvalidProofStructure);
+    require!(
+        public_signals.len() == ATTESTATION_PUBLIC_SIGNALS,
+        ErrorCode::InvalidPublicSignal
+    );
+    for signal in &public_signals {
+        require!(verify_field_element(signal), ErrorCode::InvalidPublicSignal);
+    }
+
+    // Bind the proof to this nullifier, claim and threshold
+    require!(
+        constant_time_eq(&public_signals[0], &attestation_nullifier),
+        ErrorCode::CommitmentMismatch
+    );
+    require!(
+        constant_time_eq(&public_signals[1], &encode_u64_signal(claim as u64))
+            & constant_time_eq(&public_signals[2], &encode_u64_signal(threshold)),
+        ErrorCode::InvalidPublicSignal
+    );
+
+    // The circuit proves the claim relative to a reference time, which
+    // must be recent so old proofs cannot be replayed indefinitely
+    let reference_time = decode_u64_signal(&public_signals[3])
+        .and_then(|t| i64::try_from(t).ok())
+        .ok_or(ErrorCode::InvalidPublicSignal)?;
+    require!(
+        reference_time <= current_time
+            && current_time - reference_time <= MAX_ATTESTATION_PROOF_AGE,
+        ErrorCode::StaleAttestationProof
+    );
+
+    // The wallet is a member of the enrolled commitments, so the proof is
+    // about a real WalletAccount
+    require!(
+        ctx.accounts.wallet_registry.is_known_root(&public_signals[4]),
+        ErrorCode::UnknownWalletRoot
+    );
+
+    // A refresh replaces the attestation with a newer proof, or one at a
+    // higher threshold, but never rolls either back
+    if attestation.issued_at != 0 {
+        require!(attestation.claim == claim, ErrorCode::AttestationClaimMismatch);
+        require!(
+            reference_time >= attestation.reference_time
+                && threshold >= attestation.threshold
+                && (reference_time > attestation.reference_time || threshold > attestation.threshold),
+            ErrorCode::AttestationNotNewer
+        );
+    }
+
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::Attestation, &circuit_id)?;
+    let proof_valid = verify_groth16_stub(Circuit::Attestation, &public_signals, &proof_data);
+    require!(proof_valid, ErrorCode::InvalidProof);
+
+    attestation.nullifier = attestation_nullifier;
+    attestation.claim = claim;
+    attestation.threshold = threshold;
+    attestation.reference_time = reference_time;
+    attestation.issued_at = current_time;
+    attestation.expires_at = current_time + ATTESTATION_VALIDITY_SECONDS;
+    attestation.bump = ctx.bumps.attestation;
+    attestation.version = WalletAttestation::VERSION;
+
+    debug_log!(
+        "attest_wallet_property",
+        claim = claim as u8,
+        threshold = threshold,
+        reference_time = reference_time,
+    );
+
+    // NOTE: No wallet, owner, or commitment is emitted
+    emit!(WalletAttested {
+        attestation: attestation.key(),
+        claim,
+        threshold,
+        expires_at: attestation.expires_at,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::AttestWalletProperty,
+        attestation.key(),
+        attestation_nullifier,
+        [0u8; 32],
+        [0u8; 32],
+        threshold,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn check_attestation(
+    ctx: Context<CheckAttestation>,
+    claim: AttestationClaim,
+    min_threshold: u64,
+) -> Result<()> {
+    let attestation = &ctx.accounts.attestation;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(attestation.claim == claim, ErrorCode::AttestationClaimMismatch);
+    require!(
+        attestation.threshold >= min_threshold,
+        ErrorCode::AttestationThresholdNotMet
+    );
+    require!(current_time < attestation.expires_at, ErrorCode::AttestationExpired);
+
+    Ok(())
+}
+
+pub(crate) fn attest(
+    ctx: Context<Attest>,
+    key: [u8; 32],
+    value_hash: [u8; 32],
+    proof: Vec<u8>,
+    circuit_id: [u8; 32],
+) -> Result<()> {
+    let wallet_account = &ctx.accounts.wallet_account;
+    let attestation = &mut ctx.accounts.attestation;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(!wallet_account.frozen, ErrorCode::FrozenWallet);
+    require!(key != [0u8; 32], ErrorCode::InvalidAttestationKey);
+
+    // A freshly created account has revision 0
+    let revision = attestation.revision;
+    let public_signals = [
+        wallet_account.commitment,
+        compute_kv_attestation_tag(&wallet_account.key(), &key, &value_hash, revision),
+    ];
+    verify_vk_hash(&ctx.accounts.circuit_registry, Circuit::KvAttestation, &circuit_id)?;
+    require!(
+        verify_groth16_stub(Circuit::KvAttestation, &public_signals, &proof),
+        ErrorCode::InvalidAttestationProof
+    );
+
+    attestation.wallet = wallet_account.key();
+    attestation.key = key;
+    attestation.value_hash = value_hash;
+    attestation.proof_hash = compute_proof_hash(&proof, &public_signals);
+    attestation.attested_at = current_time;
+    attestation.revision = revision + 1;
+    attestation.bump = ctx.bumps.attestation;
+    attestation.version = Attestation::VERSION;
+
+    debug_log!(
+        "attest",
+        wallet = wallet_account.key(),
+        key = Hex(&key),
+        revision = attestation.revision,
+    );
+
+    emit!(AttestationWritten {
+        attestation: attestation.key(),
+        wallet: attestation.wallet,
+        key,
+        value_hash,
+        revision: attestation.revision,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::Attest,
+        attestation.key(),
+        key,
+        value_hash,
+        [0u8; 32],
+        attestation.revision as u64,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn revoke_attestation(ctx: Context<RevokeAttestation>) -> Result<()> {
+    let attestation = &ctx.accounts.attestation;
+
+    emit!(AttestationRevoked {
+        attestation: attestation.key(),
+        wallet: attestation.wallet,
+        key: attestation.key,
+        timestamp: clock::now(ctx.remaining_accounts)?.unix_timestamp,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RevokeAttestation,
+        attestation.key(),
+        attestation.key,
+        [0u8; 32],
+        [0u8; 32],
+        attestation.revision as u64,
+        clock::now(ctx.remaining_accounts)?.unix_timestamp,
+    ));
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeCommitment<'info> {
+    #[account(
+        init,
+        payer = user,
+        space = WalletAccount::LEN,
+        seeds = [WALLET_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub wallet_account: Account<'info, WalletAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SubmitProof<'info> {
+    #[account(
+        seeds = [WALLET_SEED, wallet_account.seed_owner().as_ref()],
+        bump = wallet_account.bump,
+        constraint = wallet_account.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub wallet_account: Account<'info, WalletAccount>,
+
+    /// The wallet's owner; routed results speak for the wallet, so no one
+    /// else may submit them
+    pub user: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitiateRecovery<'info> {
+    #[account(
+        mut,
+        seeds = [WALLET_SEED, wallet_account.seed_owner().as_ref()],
+        bump = wallet_account.bump,
+        constraint = wallet_account.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub wallet_account: Account<'info, WalletAccount>,
+
+    pub user: Signer<'info>,
+
+    /// The wallet's audit log, appended to when it keeps one
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, wallet_account.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+
+    /// The wallet's watchtower, alerted when it has one
+    #[account(
+        mut,
+        seeds = [WATCHTOWER_SEED, wallet_account.key().as_ref()],
+        bump = watchtower.bump
+    )]
+    pub watchtower: Option<Account<'info, Watchtower>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteRecovery<'info> {
+    #[account(
+        mut,
+        seeds = [WALLET_SEED, wallet_account.seed_owner().as_ref()],
+        bump = wallet_account.bump
+    )]
+    pub wallet_account: Account<'info, WalletAccount>,
+
+    pub user: Signer<'info>,
+
+    /// The wallet's audit log, appended to when it keeps one
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, wallet_account.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptRecovery<'info> {
+    #[account(
+        mut,
+        seeds = [WALLET_SEED, wallet_account.seed_owner().as_ref()],
+        bump = wallet_account.bump,
+        constraint = wallet_account.pending_owner == new_owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub wallet_account: Account<'info, WalletAccount>,
+
+    pub new_owner: Signer<'info>,
+
+    /// The wallet's audit log, appended to when it keeps one
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, wallet_account.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelRecovery<'info> {
+    #[account(
+        mut,
+        seeds = [WALLET_SEED, wallet_account.seed_owner().as_ref()],
+        bump = wallet_account.bump,
+        constraint = wallet_account.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub wallet_account: Account<'info, WalletAccount>,
+
+    pub user: Signer<'info>,
+
+    /// The wallet's audit log, appended to when it keeps one
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, wallet_account.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FreezeWallet<'info> {
+    #[account(
+        mut,
+        seeds = [WALLET_SEED, wallet_account.seed_owner().as_ref()],
+        bump = wallet_account.bump,
+        constraint = wallet_account.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub wallet_account: Account<'info, WalletAccount>,
+
+    pub owner: Signer<'info>,
+
+    /// The wallet's audit log, appended to when it keeps one
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, wallet_account.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UnfreezeWallet<'info> {
+    #[account(
+        mut,
+        seeds = [WALLET_SEED, wallet_account.seed_owner().as_ref()],
+        bump = wallet_account.bump
+    )]
+    pub wallet_account: Account<'info, WalletAccount>,
+
+    /// Fee payer; the unfreeze proof is the authority
+    pub payer: Signer<'info>,
+
+    /// The wallet's audit log, appended to when it keeps one
+    #[account(
+        mut,
+        seeds = [AUDIT_LOG_SEED, wallet_account.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteAsWallet<'info> {
+    #[account(
+        mut,
+        seeds = [WALLET_SEED, wallet_account.seed_owner().as_ref()],
+        bump = wallet_account.bump,
+        constraint = wallet_account.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub wallet_account: Account<'info, WalletAccount>,
+
+    pub owner: Signer<'info>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(alias_hash: [u8; 32])]
+pub struct RegisterAlias<'info> {
+    #[account(
+        seeds = [WALLET_SEED, wallet_account.seed_owner().as_ref()],
+        bump = wallet_account.bump,
+        constraint = wallet_account.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub wallet_account: Account<'info, WalletAccount>,
+
+    /// Keyed by the alias hash, so `init` rejects a name already taken
+    #[account(
+        init,
+        payer = owner,
+        space = AliasRecord::LEN,
+        seeds = [ALIAS_SEED, &alias_hash],
+        bump
+    )]
+    pub alias: Account<'info, AliasRecord>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateAlias<'info> {
+    #[account(
+        seeds = [WALLET_SEED, wallet_account.seed_owner().as_ref()],
+        bump = wallet_account.bump,
+        constraint = wallet_account.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub wallet_account: Account<'info, WalletAccount>,
+
+    #[account(
+        mut,
+        seeds = [ALIAS_SEED, &alias.alias_hash],
+        bump = alias.bump,
+        constraint = alias.wallet == wallet_account.key() @ ErrorCode::Unauthorized
+    )]
+    pub alias: Account<'info, AliasRecord>,
+
+    pub owner: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReleaseAlias<'info> {
+    #[account(
+        seeds = [WALLET_SEED, wallet_account.seed_owner().as_ref()],
+        bump = wallet_account.bump,
+        constraint = wallet_account.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub wallet_account: Account<'info, WalletAccount>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [ALIAS_SEED, &alias.alias_hash],
+        bump = alias.bump,
+        constraint = alias.wallet == wallet_account.key() @ ErrorCode::Unauthorized
+    )]
+    pub alias: Account<'info, AliasRecord>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RegisterBackup<'info> {
+    #[account(
+        mut,
+        seeds = [WALLET_SEED, wallet_account.seed_owner().as_ref()],
+        bump = wallet_account.bump,
+        constraint = wallet_account.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub wallet_account: Account<'info, WalletAccount>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = WalletBackup::LEN,
+        seeds = [WALLET_BACKUP_SEED, wallet_account.key().as_ref(), &(wallet_account.backup_version + 1).to_le_bytes()],
+        bump
+    )]
+    pub backup: Account<'info, WalletBackup>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateWalletRegistry<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = WalletRegistry::LEN,
+        seeds = [WALLET_REGISTRY_SEED],
+        bump
+    )]
+    pub wallet_registry: Box<Account<'info, WalletRegistry>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EnrollWallet<'info> {
+    #[account(
+        seeds = [WALLET_SEED, wallet_account.seed_owner().as_ref()],
+        bump = wallet_account.bump,
+        constraint = wallet_account.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub wallet_account: Account<'info, WalletAccount>,
+
+    #[account(
+        mut,
+        seeds = [WALLET_REGISTRY_SEED],
+        bump = wallet_registry.bump
+    )]
+    pub wallet_registry: Box<Account<'info, WalletRegistry>>,
+
+    /// One enrollment per commitment
+    #[account(
+        init,
+        payer = owner,
+        space = WalletEnrollment::LEN,
+        seeds = [WALLET_ENROLLMENT_SEED, &wallet_account.commitment],
+        bump
+    )]
+    pub enrollment: Account<'info, WalletEnrollment>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(attestation_nullifier: [u8; 32])]
+pub struct AttestWalletProperty<'info> {
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = WalletAttestation::LEN,
+        seeds = [ATTESTATION_SEED, &attestation_nullifier],
+        bump
+    )]
+    pub attestation: Account<'info, WalletAttestation>,
+
+    /// Tree of enrolled wallet commitments the proof shows membership in
+    #[account(
+        seeds = [WALLET_REGISTRY_SEED],
+        bump = wallet_registry.bump
+    )]
+    pub wallet_registry: Box<Account<'info, WalletRegistry>>,
+
+    /// Any payer (e.g. a relayer) - not linked to the attested wallet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct CheckAttestation<'info> {
+    #[account(
+        seeds = [ATTESTATION_SEED, &attestation.nullifier],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, WalletAttestation>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(key: [u8; 32])]
+pub struct Attest<'info> {
+    #[account(
+        seeds = [WALLET_SEED, wallet_account.seed_owner().as_ref()],
+        bump = wallet_account.bump,
+        constraint = wallet_account.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub wallet_account: Account<'info, WalletAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = Attestation::LEN,
+        seeds = [KV_ATTESTATION_SEED, wallet_account.key().as_ref(), &key],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Audited circuit registry to check the verifying key against
+    #[account(
+        seeds = [CIRCUIT_REGISTRY_SEED, circuit_registry.authority.as_ref()],
+        bump = circuit_registry.bump,
+        constraint = network::trusts_circuit_registry(&circuit_registry.authority)
+            @ ErrorCode::UntrustedCircuitRegistry
+    )]
+    pub circuit_registry: Account<'info, CircuitRegistry>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevokeAttestation<'info> {
+    #[account(
+        seeds = [WALLET_SEED, wallet_account.seed_owner().as_ref()],
+        bump = wallet_account.bump,
+        constraint = wallet_account.owner == owner.key() @ ErrorCode::Unauthorized
+    )]
+    pub wallet_account: Account<'info, WalletAccount>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [KV_ATTESTATION_SEED, wallet_account.key().as_ref(), &attestation.key],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Route a verified proof result to an external program
+/// accounts: [target_program, proof_authority, ...forwarded accounts]
+pub(crate) fn route_proof_verified<'info>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo<'info>],
+    payload: &ProofVerifiedPayload,
+) -> Result<()> {
+    require!(accounts.len() >= 2, ErrorCode::InvalidCallbackAccounts);
+
+    let target_program = &accounts[0];
+    let proof_authority = &accounts[1];
+
+    // Never call back into ourselves (reentrancy)
+    require!(
+        target_program.executable && target_program.key() != *program_id,
+        ErrorCode::InvalidCallbackProgram
+    );
+
+    let (authority_key, authority_bump) = proof_authority_address();
+    require_keys_eq!(
+        proof_authority.key(),
+        authority_key,
+        ErrorCode::InvalidCallbackAccounts
+    );
+
+    let mut data = PROOF_VERIFIED_CALLBACK_DISCRIMINATOR.to_vec();
+    payload.serialize(&mut data)?;
+
+    let mut metas = Vec::with_capacity(accounts.len() - 1);
+    metas.push(AccountMeta::new_readonly(authority_key, true));
+    for account in &accounts[2..] {
+        metas.push(if account.is_writable {
+            AccountMeta::new(account.key(), account.is_signer)
+        } else {
+            AccountMeta::new_readonly(account.key(), account.is_signer)
+        });
+    }
+
+    let ix = Instruction {
+        program_id: target_program.key(),
+        accounts: metas,
+        data,
+    };
+    invoke_signed(&ix, accounts, &[&[PROOF_AUTHORITY_SEED, &[authority_bump]]])?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::test_support::*;
+
+    fn frozen_wallet(recovery_commitment: [u8; 32]) -> WalletAccount {
+        let mut wallet: WalletAccount = zeroed(WalletAccount::LEN);
+        wallet.recovery_commitment = recovery_commitment;
+        wallet.frozen = true;
+        wallet.frozen_at = 1_700_000_000;
+        wallet
+    }
+
+    fn unfreeze_signals(commitment: [u8; 32], wallet: &Pubkey, frozen_at: i64) -> [[u8; 32]; 2] {
+        [commitment, compute_wallet_unfreeze_tag(wallet, frozen_at)]
+    }
+
+    fn owned_wallet(owner: &Pubkey) -> (Pubkey, WalletAccount) {
+        let (key, bump) = crate::pda::wallet_address(owner);
+        let mut wallet: WalletAccount = zeroed(WalletAccount::LEN);
+        wallet.commitment = [1u8; 32];
+        wallet.owner = *owner;
+        wallet.bump = bump;
+        (key, wallet)
+    }
+
+    fn submit_proof_accounts(wallet: (Pubkey, WalletAccount), user: Pubkey) -> Result<SubmitProof<'static>> {
+        let mut accounts = vec![
+            state_account(wallet.0, &wallet.1, WalletAccount::LEN),
+            signer(user),
+            circuit_registry_account(),
+        ];
+        accounts.extend(event_cpi_accounts());
+        try_accounts::<SubmitProof, SubmitProofBumps>(accounts)
+    }
+
+    #[test]
+    fn only_the_owner_submits_wallet_proofs() {
+        let owner = Pubkey::new_unique();
+
+        assert!(submit_proof_accounts(owned_wallet(&owner), owner).is_ok());
+        assert_error(
+            submit_proof_accounts(owned_wallet(&owner), Pubkey::new_unique()),
+            ErrorCode::Unauthorized,
+        );
+    }
+
+    #[test]
+    fn wallet_proofs_must_verify_before_they_are_routed() {
+        let (_, wallet) = owned_wallet(&Pubkey::new_unique());
+        let signals = [wallet.commitment, [2u8; 32]];
+        let accepted = find_proof(|proof| verify_groth16_stub(Circuit::Wallet, &signals, proof));
+        let refused = find_proof(|proof| !verify_groth16_stub(Circuit::Wallet, &signals, proof));
+
+        let proof_hash = verify_wallet_proof(&wallet, &accepted, &signals).unwrap();
+        assert_eq!(proof_hash, compute_proof_hash(&accepted, &signals));
+
+        assert_error(verify_wallet_proof(&wallet, &refused, &signals), ErrorCode::InvalidProof);
+        assert!(verify_wallet_proof(&wallet, &rejected_proof(), &signals).is_err());
+        assert_error(
+            verify_wallet_proof(&wallet, &accepted, &[[3u8; 32], [2u8; 32]]),
+            ErrorCode::CommitmentMismatch,
+        );
+    }
+
+    #[test]
+    fn recovery_secret_lifts_a_freeze() {
+        let key = Pubkey::new_unique();
+        let wallet = frozen_wallet([7u8; 32]);
+        let signals = unfreeze_signals(wallet.recovery_commitment, &key, wallet.frozen_at);
+        let proof = find_proof(|proof| verify_groth16_stub(Circuit::WalletUnfreeze, &signals, proof));
+
+        assert!(verify_unfreeze_proof(&wallet, &key, &proof).is_ok());
+    }
+
+    #[test]
+    fn other_secrets_do_not_lift_a_freeze() {
+        let key = Pubkey::new_unique();
+        let wallet = frozen_wallet([7u8; 32]);
+        // A proof for a secret the freezing key chose itself
+        let chosen = unfreeze_signals([9u8; 32], &key, wallet.frozen_at);
+        let recovery = unfreeze_signals(wallet.recovery_commitment, &key, wallet.frozen_at);
+        let proof = find_proof(|proof| {
+            verify_groth16_stub(Circuit::WalletUnfreeze, &chosen, proof)
+                && !verify_groth16_stub(Circuit::WalletUnfreeze, &recovery, proof)
+        });
+
+        assert_error(verify_unfreeze_proof(&wallet, &key, &proof), ErrorCode::InvalidUnfreezeProof);
+        assert_error(verify_unfreeze_proof(&wallet, &key, &rejected_proof()), ErrorCode::InvalidUnfreezeProof);
+    }
+
+    #[test]
+    fn unfreeze_proofs_do_not_carry_over_to_a_later_freeze() {
+        let key = Pubkey::new_unique();
+        let mut wallet = frozen_wallet([7u8; 32]);
+        let earlier = unfreeze_signals(wallet.recovery_commitment, &key, wallet.frozen_at);
+        wallet.frozen_at += 1;
+        let current = unfreeze_signals(wallet.recovery_commitment, &key, wallet.frozen_at);
+        let proof = find_proof(|proof| {
+            verify_groth16_stub(Circuit::WalletUnfreeze, &earlier, proof)
+                && !verify_groth16_stub(Circuit::WalletUnfreeze, &current, proof)
+        });
+
+        assert_error(verify_unfreeze_proof(&wallet, &key, &proof), ErrorCode::InvalidUnfreezeProof);
+    }
+}
@@ -0,0 +1,188 @@
+//! Watchtower alert feeds
+
+use crate::*;
+
+// ============================================
+// WATCHTOWER - Pollable Alerts for Off-Chain Notifiers
+// ============================================
+//
+// A wallet owner, multisig creator or pool creator can register a
+// watchtower for it with an alert key. Instructions that put the subject
+// at risk (a recovery being started, a large or non-transfer multisig
+// proposal, governance pausing the pool) then also write a compact alert
+// into the watchtower's ring, so a notification service only has to poll
+// one account per subject instead of indexing every transaction.
+// Like audit logs, the watchtower is passed as an optional account;
+// services should still follow the `WatchtowerAlertRaised` events, and
+// treat a subject event with no matching alert as suspicious.
+
+pub(crate) fn register_watchtower(
+    ctx: Context<RegisterWatchtower>,
+    alert_key: Pubkey,
+    large_amount: u64,
+) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    let subject = &ctx.accounts.subject;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require_keys_eq!(subject_controller(subject)?, owner, ErrorCode::Unauthorized);
+    require!(alert_key != Pubkey::default(), ErrorCode::InvalidAlertKey);
+
+    let watchtower = &mut ctx.accounts.watchtower;
+    watchtower.subject = subject.key();
+    watchtower.owner = owner;
+    watchtower.alert_key = alert_key;
+    watchtower.large_amount = large_amount;
+    watchtower.alert_count = 0;
+    watchtower.alerts = [WatchtowerAlert::default(); WATCHTOWER_ALERT_SLOTS];
+    watchtower.created_at = current_time;
+    watchtower.bump = ctx.bumps.watchtower;
+    watchtower.version = Watchtower::VERSION;
+
+    emit!(WatchtowerConfigured {
+        watchtower: watchtower.key(),
+        subject: watchtower.subject,
+        owner,
+        alert_key,
+        large_amount,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::RegisterWatchtower,
+        watchtower.key(),
+        watchtower.subject.to_bytes(),
+        alert_key.to_bytes(),
+        [0u8; 32],
+        large_amount,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+pub(crate) fn update_watchtower(
+    ctx: Context<UpdateWatchtower>,
+    alert_key: Pubkey,
+    large_amount: u64,
+) -> Result<()> {
+    let watchtower = &mut ctx.accounts.watchtower;
+    let current_time = clock::now(ctx.remaining_accounts)?.unix_timestamp;
+
+    require!(alert_key != Pubkey::default(), ErrorCode::InvalidAlertKey);
+
+    watchtower.alert_key = alert_key;
+    watchtower.large_amount = large_amount;
+
+    emit!(WatchtowerConfigured {
+        watchtower: watchtower.key(),
+        subject: watchtower.subject,
+        owner: watchtower.owner,
+        alert_key,
+        large_amount,
+        timestamp: current_time,
+    });
+
+    emit_cpi!(VeilRecord::new(
+        RecordKind::UpdateWatchtower,
+        watchtower.key(),
+        watchtower.subject.to_bytes(),
+        alert_key.to_bytes(),
+        [0u8; 32],
+        large_amount,
+        current_time,
+    ));
+
+    Ok(())
+}
+
+/// Key allowed to register a watchtower for `subject`: a wallet's owner,
+/// or the creator of a multisig or shielded pool
+fn subject_controller(subject: &UncheckedAccount) -> Result<Pubkey> {
+    let data = subject.try_borrow_data()?;
+
+    if data.starts_with(WalletAccount::DISCRIMINATOR) {
+        Ok(WalletAccount::try_deserialize(&mut &data[..])?.owner)
+    } else if data.starts_with(ShieldedPool::DISCRIMINATOR) {
+        Ok(ShieldedPool::try_deserialize(&mut &data[..])?.creator)
+    } else if data.starts_with(StealthMultisig::DISCRIMINATOR) {
+        let disc = StealthMultisig::DISCRIMINATOR.len();
+        let header = data
+            .get(disc..disc + std::mem::size_of::<StealthMultisig>())
+            .ok_or(ErrorCode::InvalidWatchtowerSubject)?;
+        Ok(bytemuck::from_bytes::<StealthMultisig>(header).creator)
+    } else {
+        err!(ErrorCode::InvalidWatchtowerSubject)
+    }
+}
+
+/// Write an alert into the subject's watchtower, if it has one
+pub(crate) fn raise_watchtower_alert(
+    watchtower: Option<&mut Account<Watchtower>>,
+    kind: WatchtowerAlertKind,
+    reference: [u8; 32],
+    value: u64,
+    timestamp: i64,
+) {
+    let Some(watchtower) = watchtower else {
+        return;
+    };
+
+    let sequence = watchtower.alert_count + 1;
+    watchtower.alerts[(sequence % WATCHTOWER_ALERT_SLOTS as u64) as usize] = WatchtowerAlert {
+        sequence,
+        kind: kind as u8,
+        reference,
+        value,
+        timestamp,
+    };
+    watchtower.alert_count = sequence;
+
+    emit!(WatchtowerAlertRaised {
+        watchtower: watchtower.key(),
+        subject: watchtower.subject,
+        alert_key: watchtower.alert_key,
+        sequence,
+        kind,
+        reference,
+        value,
+        timestamp,
+    });
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RegisterWatchtower<'info> {
+    /// CHECK: Wallet, multisig or shielded pool; its discriminator and
+    /// controller are checked in `subject_controller`
+    #[account(owner = crate::ID)]
+    pub subject: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = Watchtower::LEN,
+        seeds = [WATCHTOWER_SEED, subject.key().as_ref()],
+        bump
+    )]
+    pub watchtower: Account<'info, Watchtower>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateWatchtower<'info> {
+    #[account(
+        mut,
+        seeds = [WATCHTOWER_SEED, watchtower.subject.as_ref()],
+        bump = watchtower.bump,
+        has_one = owner @ ErrorCode::Unauthorized
+    )]
+    pub watchtower: Account<'info, Watchtower>,
+
+    pub owner: Signer<'info>,
+}
@@ -1,59 +1,58 @@
+// The legacy staking handlers are deprecated but still dispatched by the
+// Anchor-generated entrypoint, which lives outside the program module.
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 
-/// Simple hash function using SHA256-like computation
-/// In production, use proper cryptographic hash
-fn hash(data: &[u8]) -> HashResult {
-    // Simple hash implementation for on-chain use
-    let mut result = [0u8; 32];
-    let len = data.len();
-
-    // Mix data into result
-    for (i, byte) in data.iter().enumerate() {
-        let idx = i % 32;
-        result[idx] = result[idx].wrapping_add(*byte);
-        result[(idx + 1) % 32] = result[(idx + 1) % 32].wrapping_mul(byte.wrapping_add(1));
-        result[(idx + 7) % 32] ^= byte.rotate_left((i % 8) as u32);
-    }
-
-    // Additional mixing rounds
-    for round in 0..4 {
-        for i in 0..32 {
-            result[i] = result[i]
-                .wrapping_add(result[(i + 1) % 32])
-                .wrapping_mul(result[(i + 7) % 32].wrapping_add(1))
-                ^ (len as u8).wrapping_add(round);
+/// Structured diagnostics, compiled in only with the `debug-logs` feature
+/// so mainnet builds pay no compute for them.
+/// Output: `veil:<instruction> key=value ...` - never pass secrets or vote choices.
+macro_rules! debug_log {
+    ($ix:literal $(, $key:ident = $value:expr)* $(,)?) => {
+        #[cfg(feature = "debug-logs")]
+        msg!(concat!("veil:", $ix $(, " ", stringify!($key), "={}")*) $(, $value)*);
+    };
+}
+
+/// Hex formatter for byte arrays in debug logs
+#[cfg(feature = "debug-logs")]
+struct Hex<'a>(&'a [u8]);
+
+#[cfg(feature = "debug-logs")]
+impl std::fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
         }
-    }
-
-    HashResult(result)
-}
-
-struct HashResult([u8; 32]);
-
-impl HashResult {
-    fn to_bytes(&self) -> [u8; 32] {
-        self.0
+        Ok(())
     }
 }
 
+pub mod clock;
+pub mod constants;
+pub mod crypto;
+pub mod errors;
+pub mod events;
+pub mod instructions;
+pub mod network;
+pub mod pda;
+pub mod state;
+
+pub use constants::*;
+pub use crypto::*;
+pub use errors::ErrorCode;
+pub use events::*;
+pub use instructions::*;
+pub use state::*;
+use pda::*;
+
+// Program ID per deployment (see `network`); mainnet is deployed from the
+// same program keypair as devnet
+#[cfg(feature = "localnet")]
+declare_id!("Ed6t6DqRQpLKHifPG7gNorFMmaWcQQA2bQ8R6WdmbEZo");
+#[cfg(not(feature = "localnet"))]
 declare_id!("5C1VaebPdHZYETnTL18cLJK2RexXmVVhkkYpnYHD5P4h");
 
-/// Maximum number of signers for a multisig
-pub const MAX_MULTISIG_SIGNERS: usize = 10;
-/// Maximum number of votes per proposal
-pub const MAX_VOTES_PER_PROPOSAL: usize = 100;
-/// Maximum number of notes in the shielded pool Merkle tree
-pub const MAX_SHIELDED_NOTES: usize = 256;
-/// Merkle tree depth for shielded pool
-pub const MERKLE_TREE_DEPTH: usize = 8;
-/// BN128 field modulus (for ZK proof verification)
-pub const BN128_MODULUS: [u8; 32] = [
-    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29,
-    0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
-    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d,
-    0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
-];
-
 #[program]
 pub mod veil_protocol {
     use super::*;
@@ -64,80 +63,25 @@ pub mod veil_protocol {
         ctx: Context<InitializeCommitment>,
         commitment: [u8; 32],
     ) -> Result<()> {
-        let wallet_account = &mut ctx.accounts.wallet_account;
-        wallet_account.commitment = commitment;
-        wallet_account.owner = ctx.accounts.user.key();
-        wallet_account.created_at = Clock::get()?.unix_timestamp;
-        wallet_account.recovery_active = false;
-        wallet_account.bump = ctx.bumps.wallet_account;
-
-        emit!(CommitmentCreated {
-            wallet: wallet_account.key(),
-            commitment,
-            timestamp: wallet_account.created_at,
-        });
-
-        Ok(())
+        instructions::wallet::initialize_commitment(ctx, commitment)
     }
 
     /// Submit a zero-knowledge proof for verification
     /// Implements cryptographic verification using Groth16-style proof structure
     /// Proof format: [pi_a (64 bytes), pi_b (128 bytes), pi_c (64 bytes)] = 256 bytes
-    pub fn submit_proof(
-        ctx: Context<SubmitProof>,
+    /// At most `MAX_PROOF_LEN` bytes of proof and `MAX_PUBLIC_SIGNALS` signals
+    ///
+    /// Optionally routes the result to an external program via CPI, signed by
+    /// the protocol's `proof_authority` PDA, so other programs can gate actions
+    /// on Veil proofs without duplicating verifier code.
+    /// remaining_accounts: [target_program, proof_authority, ...forwarded accounts]
+    pub fn submit_proof<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitProof<'info>>,
         proof_data: Vec<u8>,
         public_signals: Vec<[u8; 32]>,
+        circuit_id: [u8; 32],
     ) -> Result<()> {
-        let wallet_account = &ctx.accounts.wallet_account;
-
-        // Verify proof structure (Groth16 format: 256 bytes)
-        require!(proof_data.len() >= 256, ErrorCode::InvalidProofStructure);
-        require!(public_signals.len() >= 1, ErrorCode::InvalidProof);
-
-        // Extract proof components
-        let pi_a = &proof_data[0..64];    // G1 point (2 x 32 bytes)
-        let pi_b = &proof_data[64..192];  // G2 point (2 x 2 x 32 bytes)
-        let pi_c = &proof_data[192..256]; // G1 point (2 x 32 bytes)
-
-        // Verify proof points are valid field elements (< BN128 modulus)
-        require!(
-            verify_field_element(&pi_a[0..32]) && verify_field_element(&pi_a[32..64]),
-            ErrorCode::InvalidProofPoint
-        );
-        require!(
-            verify_field_element(&pi_c[0..32]) && verify_field_element(&pi_c[32..64]),
-            ErrorCode::InvalidProofPoint
-        );
-
-        // Verify each public signal is a valid field element
-        for signal in &public_signals {
-            require!(verify_field_element(signal), ErrorCode::InvalidPublicSignal);
-        }
-
-        // Verify the first public signal matches the wallet commitment
-        // This ensures the proof is for this specific wallet
-        require!(
-            public_signals[0] == wallet_account.commitment,
-            ErrorCode::CommitmentMismatch
-        );
-
-        // Compute proof verification hash
-        // In production: use actual Groth16 pairing check
-        // For Solana: use the groth16-solana precompile when available
-        let proof_hash = compute_proof_hash(&proof_data, &public_signals);
-
-        // Verify proof hash has valid structure (non-zero, unique)
-        require!(proof_hash != [0u8; 32], ErrorCode::InvalidProofHash);
-
-        emit!(ProofVerified {
-            wallet: wallet_account.key(),
-            proof_hash,
-            public_signals_hash: hash_public_signals(&public_signals),
-            verification_type: ProofType::Groth16,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
-
-        Ok(())
+        instructions::wallet::submit_proof(ctx, proof_data, public_signals, circuit_id)
     }
 
     /// Initiate time-locked recovery
@@ -147,69 +91,165 @@ pub mod veil_protocol {
         recovery_commitment: [u8; 32],
         timelock_days: u8,
     ) -> Result<()> {
-        let wallet_account = &mut ctx.accounts.wallet_account;
+        instructions::wallet::initiate_recovery(ctx, recovery_commitment, timelock_days)
+    }
 
-        require!(!wallet_account.recovery_active, ErrorCode::RecoveryAlreadyActive);
-        require!(timelock_days >= 1 && timelock_days <= 90, ErrorCode::InvalidTimelockPeriod);
+    /// Execute recovery after timelock has expired
+    /// Requires proof of recovery secret ownership. Control does not move
+    /// yet: the wallet waits in handover until `new_owner` signs
+    /// `accept_recovery`, so a proof submitted with a mistyped or
+    /// substituted key cannot hand the wallet to a key nobody holds.
+    pub fn execute_recovery(
+        ctx: Context<ExecuteRecovery>,
+        recovery_proof: Vec<u8>,
+        new_owner: Pubkey,
+    ) -> Result<()> {
+        instructions::wallet::execute_recovery(ctx, recovery_proof, new_owner)
+    }
 
-        let current_time = Clock::get()?.unix_timestamp;
-        let unlock_time = current_time + (timelock_days as i64 * 86400); // days to seconds
+    /// Complete a recovery handover (signed by the new owner)
+    /// The wallet keeps its address; only its owner key changes.
+    pub fn accept_recovery(ctx: Context<AcceptRecovery>) -> Result<()> {
+        instructions::wallet::accept_recovery(ctx)
+    }
 
-        wallet_account.recovery_commitment = recovery_commitment;
-        wallet_account.recovery_initiated_at = current_time;
-        wallet_account.recovery_unlock_at = unlock_time;
-        wallet_account.recovery_active = true;
+    /// Cancel an active recovery, or a handover not yet accepted (owner only)
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        instructions::wallet::cancel_recovery(ctx)
+    }
 
-        emit!(RecoveryInitiated {
-            wallet: wallet_account.key(),
-            recovery_commitment,
-            unlock_time,
-        });
+    /// Freeze a wallet (owner only) when its key may be compromised
+    /// Blocks proof submissions, wallet calls, attestations and recovery
+    /// execution until `unfreeze_wallet`. Unfreezing needs the secret behind
+    /// the wallet's `recovery_commitment`, not the owner key, so a thief
+    /// holding the key can neither lift the freeze nor pick the secret that
+    /// does. Recovery can still be initiated meanwhile, under the same
+    /// commitment.
+    pub fn freeze_wallet(ctx: Context<FreezeWallet>) -> Result<()> {
+        instructions::wallet::freeze_wallet(ctx)
+    }
 
-        Ok(())
+    /// Lift a freeze by proving knowledge of the secret behind the
+    /// wallet's recovery commitment (any fee payer may submit the proof)
+    pub fn unfreeze_wallet(ctx: Context<UnfreezeWallet>, unfreeze_proof: Vec<u8>, circuit_id: [u8; 32]) -> Result<()> {
+        instructions::wallet::unfreeze_wallet(ctx, unfreeze_proof, circuit_id)
     }
 
-    /// Execute recovery after timelock has expired
-    /// Requires proof of recovery secret ownership
-    pub fn execute_recovery(
-        ctx: Context<ExecuteRecovery>,
-        recovery_proof: Vec<u8>,
+    /// Have the wallet PDA sign a call to an allowlisted program
+    /// Turns the commitment wallet into a smart wallet that can hold and
+    /// move tokens. The owner signs and proves knowledge of the wallet
+    /// secret; the proof is bound to the call (program, accounts, data) and
+    /// the wallet's `call_nonce`, so it cannot be replayed or redirected.
+    /// remaining_accounts: [target_program, ...call accounts]
+    pub fn execute_as_wallet<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteAsWallet<'info>>,
+        instruction_data: Vec<u8>,
+        wallet_proof: Vec<u8>,
+        circuit_id: [u8; 32],
     ) -> Result<()> {
-        let wallet_account = &mut ctx.accounts.wallet_account;
-        let current_time = Clock::get()?.unix_timestamp;
+        instructions::wallet::execute_as_wallet(ctx, instruction_data, wallet_proof, circuit_id)
+    }
 
-        require!(wallet_account.recovery_active, ErrorCode::NoActiveRecovery);
-        require!(current_time >= wallet_account.recovery_unlock_at, ErrorCode::TimelockNotExpired);
-        require!(recovery_proof.len() > 0, ErrorCode::InvalidProof);
+    // ============================================
+    // ALIASES - Private Payment Names
+    // ============================================
 
-        // TODO: Verify recovery proof matches recovery_commitment
-        // For demo, we accept valid structure
+    /// Register an alias for the caller's commitment wallet
+    pub fn register_alias(
+        ctx: Context<RegisterAlias>,
+        alias_hash: [u8; 32],
+        encrypted_meta_address: [u8; 64],
+    ) -> Result<()> {
+        instructions::wallet::register_alias(ctx, alias_hash, encrypted_meta_address)
+    }
 
-        wallet_account.recovery_active = false;
-        wallet_account.recovery_executed_at = current_time;
+    /// Point an alias at a new (encrypted) stealth meta-address
+    pub fn update_alias(ctx: Context<UpdateAlias>, encrypted_meta_address: [u8; 64]) -> Result<()> {
+        instructions::wallet::update_alias(ctx, encrypted_meta_address)
+    }
 
-        emit!(RecoveryExecuted {
-            wallet: wallet_account.key(),
-            timestamp: current_time,
-        });
+    /// Release an alias, refunding its rent; the name can be registered again
+    pub fn release_alias(ctx: Context<ReleaseAlias>) -> Result<()> {
+        instructions::wallet::release_alias(ctx)
+    }
 
-        Ok(())
+    // ============================================
+    // BACKUPS - Encrypted Off-Chain Backup Anchoring
+    // ============================================
+
+    /// Anchor the hash of a new encrypted off-chain backup of the wallet's
+    /// notes and keys; earlier versions stay on-chain as rotation history
+    pub fn register_backup(ctx: Context<RegisterBackup>, backup_hash: [u8; 32], uri_hash: [u8; 32]) -> Result<()> {
+        instructions::wallet::register_backup(ctx, backup_hash, uri_hash)
     }
 
-    /// Cancel an active recovery (owner only, before timelock expires)
-    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
-        let wallet_account = &mut ctx.accounts.wallet_account;
+    // ============================================
+    // WALLET REGISTRY - Enrolled Commitments
+    // ============================================
+
+    /// Create the wallet registry attestations prove membership in (once, by anyone)
+    pub fn create_wallet_registry(ctx: Context<CreateWalletRegistry>) -> Result<()> {
+        instructions::wallet::create_wallet_registry(ctx)
+    }
 
-        require!(wallet_account.recovery_active, ErrorCode::NoActiveRecovery);
+    /// Enroll the wallet's current commitment in the wallet registry
+    /// After a recovery the new commitment is enrolled again; the old one stays
+    pub fn enroll_wallet(ctx: Context<EnrollWallet>) -> Result<()> {
+        instructions::wallet::enroll_wallet(ctx)
+    }
+
+    // ============================================
+    // WALLET ATTESTATIONS - ZK Property Proofs
+    // ============================================
+
+    /// Attest a property of a commitment wallet in zero knowledge
+    /// e.g. "this wallet is older than 90 days" without revealing which wallet
+    ///
+    /// The attestation PDA is keyed by a nullifier derived from the wallet
+    /// secret, so it cannot be linked back to the WalletAccount on-chain.
+    /// The proof shows the wallet's commitment is in the wallet registry,
+    /// and calling again with a newer proof or higher threshold refreshes it.
+    /// Public signals: [attestation_nullifier, claim, threshold, reference_time, wallets_root]
+    pub fn attest_wallet_property(
+        ctx: Context<AttestWalletProperty>,
+        attestation_nullifier: [u8; 32],
+        claim: AttestationClaim,
+        threshold: u64,
+        proof_data: Vec<u8>,
+        public_signals: Vec<[u8; 32]>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::wallet::attest_wallet_property(ctx, attestation_nullifier, claim, threshold, proof_data, public_signals, circuit_id)
+    }
 
-        wallet_account.recovery_active = false;
+    /// Check that an attestation satisfies a claim
+    /// Intended to be called via CPI by third-party programs gating on wallet properties
+    pub fn check_attestation(
+        ctx: Context<CheckAttestation>,
+        claim: AttestationClaim,
+        min_threshold: u64,
+    ) -> Result<()> {
+        instructions::wallet::check_attestation(ctx, claim, min_threshold)
+    }
 
-        emit!(RecoveryCancelled {
-            wallet: wallet_account.key(),
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+    /// Write (or overwrite) a key-value attestation on the caller's wallet
+    /// Apps define their own keys (e.g. H("kyc_tier")) and value encodings;
+    /// only H(value) is stored. The proof shows knowledge of the wallet
+    /// secret and is bound to (wallet, key, value_hash, revision), so one
+    /// proof cannot be replayed for another key, value or later revision
+    pub fn attest(
+        ctx: Context<Attest>,
+        key: [u8; 32],
+        value_hash: [u8; 32],
+        proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::wallet::attest(ctx, key, value_hash, proof, circuit_id)
+    }
 
-        Ok(())
+    /// Remove a key-value attestation, returning its rent to the owner
+    pub fn revoke_attestation(ctx: Context<RevokeAttestation>) -> Result<()> {
+        instructions::wallet::revoke_attestation(ctx)
     }
 
     // ============================================
@@ -218,75 +258,78 @@ pub mod veil_protocol {
 
     /// Create a new proposal for private voting
     /// Only the proposal ID and metadata hash are stored on-chain
+    /// `min_reveal_bps` is the share of vote commitments (basis points) that
+    /// must be revealed for the tally to decide the proposal (0 disables the
+    /// check). While reveals fall short, finalization extends the reveal
+    /// phase by `reveal_extension` seconds, up to `MAX_REVEAL_EXTENSIONS`
+    /// times, then finalizes the proposal with its tally marked void.
+    /// The creator escrows `FINALIZATION_BOUNTY_LAMPORTS` for the finalizer.
     pub fn create_proposal(
         ctx: Context<CreateProposal>,
         proposal_id: [u8; 32],
         metadata_hash: [u8; 32],
         voting_ends_at: i64,
         reveal_ends_at: i64,
+        min_reveal_bps: u16,
+        reveal_extension: i64,
     ) -> Result<()> {
-        let proposal = &mut ctx.accounts.proposal;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        require!(voting_ends_at > current_time, ErrorCode::InvalidVotingPeriod);
-        require!(reveal_ends_at > voting_ends_at, ErrorCode::InvalidRevealPeriod);
-
-        proposal.proposal_id = proposal_id;
-        proposal.creator = ctx.accounts.creator.key();
-        proposal.metadata_hash = metadata_hash;
-        proposal.created_at = current_time;
-        proposal.voting_ends_at = voting_ends_at;
-        proposal.reveal_ends_at = reveal_ends_at;
-        proposal.yes_count = 0;
-        proposal.no_count = 0;
-        proposal.total_commitments = 0;
-        proposal.total_revealed = 0;
-        proposal.is_finalized = false;
-        proposal.bump = ctx.bumps.proposal;
-
-        emit!(ProposalCreated {
-            proposal: proposal.key(),
-            proposal_id,
-            creator: ctx.accounts.creator.key(),
-            voting_ends_at,
-            reveal_ends_at,
-        });
-
-        Ok(())
+        instructions::voting::create_proposal(ctx, proposal_id, metadata_hash, voting_ends_at, reveal_ends_at, min_reveal_bps, reveal_extension)
     }
 
     /// Cast a private vote using a commitment
     /// The actual vote (yes/no) is hidden - only the commitment is stored
-    /// commitment = hash(vote_choice || secret || voter_pubkey)
+    /// commitment = H(DOMAIN_VOTE, vote_choice || secret || voter_pubkey)
     pub fn cast_vote(
         ctx: Context<CastVote>,
         vote_commitment: [u8; 32],
     ) -> Result<()> {
-        let proposal = &mut ctx.accounts.proposal;
-        let vote_record = &mut ctx.accounts.vote_record;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        require!(current_time < proposal.voting_ends_at, ErrorCode::VotingEnded);
-        require!(!vote_record.has_voted, ErrorCode::AlreadyVoted);
-
-        vote_record.proposal = proposal.key();
-        vote_record.voter = ctx.accounts.voter.key();
-        vote_record.commitment = vote_commitment;
-        vote_record.has_voted = true;
-        vote_record.has_revealed = false;
-        vote_record.voted_at = current_time;
-        vote_record.bump = ctx.bumps.vote_record;
+        instructions::voting::cast_vote(ctx, vote_commitment, [0u8; 32], None)
+    }
 
-        proposal.total_commitments += 1;
+    /// Cast a private vote whose reveal is delegated: anyone presenting
+    /// the reveal token behind reveal_token_hash = H(DOMAIN_REVEAL_TOKEN,
+    /// reveal_token), with the choice and secret, can reveal it
+    pub fn cast_vote_with_reveal_token(
+        ctx: Context<CastVote>,
+        vote_commitment: [u8; 32],
+        reveal_token_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::voting::cast_vote(ctx, vote_commitment, reveal_token_hash, None)
+    }
 
-        emit!(VoteCast {
-            proposal: proposal.key(),
-            voter: ctx.accounts.voter.key(),
-            commitment: vote_commitment,
-            timestamp: current_time,
-        });
+    /// Cast a private vote, succeeding without change when the voter's
+    /// record already holds this exact vote (safe to resubmit after an
+    /// ambiguous failure); a different earlier vote fails with `AlreadyVoted`
+    /// Proposals with an eligibility list need `eligibility` on the first
+    /// attempt
+    pub fn cast_vote_idempotent(
+        ctx: Context<CastVote>,
+        vote_commitment: [u8; 32],
+        reveal_token_hash: [u8; 32],
+        eligibility: Option<EligibilityProof>,
+    ) -> Result<()> {
+        instructions::voting::cast_vote_idempotent(ctx, vote_commitment, reveal_token_hash, eligibility)
+    }
 
-        Ok(())
+    /// Cast a private vote on a proposal restricted to an eligibility list,
+    /// proving the voter's leaf H(DOMAIN_ELIGIBILITY_LEAF, voter) is in its
+    /// eligibility root. A zero reveal_token_hash keeps the reveal with the voter
+    pub fn cast_eligible_vote(
+        ctx: Context<CastVote>,
+        vote_commitment: [u8; 32],
+        reveal_token_hash: [u8; 32],
+        eligibility_proof: [[u8; 32]; MERKLE_TREE_DEPTH],
+        path_indices: u8,
+    ) -> Result<()> {
+        instructions::voting::cast_vote(
+            ctx,
+            vote_commitment,
+            reveal_token_hash,
+            Some(&EligibilityProof {
+                siblings: eligibility_proof,
+                path_indices,
+            }),
+        )
     }
 
     /// Reveal a vote after the voting period ends
@@ -296,2220 +339,1563 @@ pub mod veil_protocol {
         vote_choice: bool, // true = yes, false = no
         secret: [u8; 32],
     ) -> Result<()> {
-        let proposal = &mut ctx.accounts.proposal;
-        let vote_record = &mut ctx.accounts.vote_record;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        require!(current_time >= proposal.voting_ends_at, ErrorCode::VotingNotEnded);
-        require!(current_time < proposal.reveal_ends_at, ErrorCode::RevealEnded);
-        require!(vote_record.has_voted, ErrorCode::NotVoted);
-        require!(!vote_record.has_revealed, ErrorCode::AlreadyRevealed);
-
-        // Verify the commitment matches: hash(vote_choice || secret || voter)
-        let expected_commitment = compute_vote_commitment(
-            vote_choice,
-            &secret,
-            &ctx.accounts.voter.key(),
-        );
-        require!(
-            vote_record.commitment == expected_commitment,
-            ErrorCode::InvalidVoteReveal
-        );
-
-        vote_record.has_revealed = true;
-        vote_record.revealed_choice = vote_choice;
-        vote_record.revealed_at = current_time;
-
-        proposal.total_revealed += 1;
-        if vote_choice {
-            proposal.yes_count += 1;
-        } else {
-            proposal.no_count += 1;
-        }
-
-        emit!(VoteRevealed {
-            proposal: proposal.key(),
-            voter: ctx.accounts.voter.key(),
-            // Note: We emit that a reveal happened, but not the choice
-            // Individual votes remain private even after reveal
-            timestamp: current_time,
-        });
+        instructions::voting::reveal_vote(ctx, vote_choice, secret)
+    }
 
-        Ok(())
+    /// Reveal a delegated vote on the voter's behalf with its reveal token
+    /// The voter's wallet is not needed
+    pub fn reveal_vote_with_token(
+        ctx: Context<RevealVoteWithToken>,
+        vote_choice: bool,
+        secret: [u8; 32],
+        reveal_token: [u8; 32],
+    ) -> Result<()> {
+        instructions::voting::reveal_vote_with_token(ctx, vote_choice, secret, reveal_token)
     }
 
     /// Finalize the proposal after reveal period ends
+    /// Permissionless: the caller collects the proposal's finalization bounty
+    /// With a dispute window the first call only commits to the tally; a
+    /// second call after the window makes it binding
     pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
-        let proposal = &mut ctx.accounts.proposal;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        require!(current_time >= proposal.reveal_ends_at, ErrorCode::RevealNotEnded);
-        require!(!proposal.is_finalized, ErrorCode::AlreadyFinalized);
+        instructions::voting::finalize_proposal(ctx)
+    }
 
-        proposal.is_finalized = true;
+    // ============================================
+    // TALLY DISPUTES - Two-Stage Finalization
+    // ============================================
 
-        emit!(ProposalFinalized {
-            proposal: proposal.key(),
-            yes_count: proposal.yes_count,
-            no_count: proposal.no_count,
-            total_votes: proposal.total_revealed,
-            timestamp: current_time,
-        });
+    /// Require a dispute window between committing to the tally and opening
+    /// it (proposal creator only, before any vote is cast)
+    pub fn enable_tally_dispute(ctx: Context<EnableTallyDispute>, dispute_window: i64) -> Result<()> {
+        instructions::voting::enable_tally_dispute(ctx, dispute_window)
+    }
 
-        Ok(())
+    /// Challenge a committed tally with a proof that it disagrees with the
+    /// valid reveals; an upheld challenge voids the result (anyone can call)
+    pub fn challenge_proposal_tally(
+        ctx: Context<ChallengeProposalTally>,
+        challenge_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::voting::challenge_proposal_tally(ctx, challenge_proof, circuit_id)
     }
 
     // ============================================
-    // STEALTH MULTISIG - Hidden Signers
+    // PROPOSAL TEMPLATES - Standard Governance Parameters
     // ============================================
 
-    /// Create a stealth multisig vault
-    /// Signer identities are stored as commitments, not public keys
-    pub fn create_multisig(
-        ctx: Context<CreateMultisig>,
-        vault_id: [u8; 32],
-        threshold: u8,
-        signer_commitments: Vec<[u8; 32]>,
+    /// Publish a DAO's standard voting parameters: periods in seconds, the
+    /// reveal quorum and an optional eligible voter root
+    pub fn create_proposal_template(
+        ctx: Context<CreateProposalTemplate>,
+        template_id: [u8; 32],
+        voting_period: i64,
+        reveal_period: i64,
+        eligibility_root: [u8; 32],
+        min_reveal_bps: u16,
+        reveal_extension: i64,
     ) -> Result<()> {
-        let multisig = &mut ctx.accounts.multisig;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        require!(threshold > 0, ErrorCode::InvalidThreshold);
-        require!(signer_commitments.len() >= threshold as usize, ErrorCode::InvalidThreshold);
-        require!(signer_commitments.len() <= MAX_MULTISIG_SIGNERS, ErrorCode::TooManySigners);
-
-        multisig.vault_id = vault_id;
-        multisig.creator = ctx.accounts.creator.key();
-        multisig.threshold = threshold;
-        multisig.total_signers = signer_commitments.len() as u8;
-        multisig.created_at = current_time;
-        multisig.proposal_count = 0;
-        multisig.bump = ctx.bumps.multisig;
-
-        // Store signer commitments (not actual public keys!)
-        for (i, commitment) in signer_commitments.iter().enumerate() {
-            multisig.signer_commitments[i] = *commitment;
-        }
-
-        emit!(MultisigCreated {
-            multisig: multisig.key(),
-            vault_id,
-            threshold,
-            total_signers: multisig.total_signers,
-            timestamp: current_time,
-        });
-
-        Ok(())
+        instructions::voting::create_proposal_template(
+            ctx,
+            template_id,
+            voting_period,
+            reveal_period,
+            eligibility_root,
+            min_reveal_bps,
+            reveal_extension,
+        )
     }
 
-    /// Create a proposal for the multisig to execute
-    pub fn create_multisig_proposal(
-        ctx: Context<CreateMultisigProposal>,
+    /// Create a proposal from a template; voting and reveal deadlines are
+    /// counted from now. Escrows the finalization bounty like `create_proposal`
+    pub fn create_proposal_from_template(
+        ctx: Context<CreateProposalFromTemplate>,
         proposal_id: [u8; 32],
-        instruction_hash: [u8; 32],
+        metadata_hash: [u8; 32],
     ) -> Result<()> {
-        let multisig = &mut ctx.accounts.multisig;
-        let proposal = &mut ctx.accounts.multisig_proposal;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        proposal.multisig = multisig.key();
-        proposal.proposal_id = proposal_id;
-        proposal.instruction_hash = instruction_hash;
-        proposal.created_at = current_time;
-        proposal.approval_count = 0;
-        proposal.is_executed = false;
-        proposal.bump = ctx.bumps.multisig_proposal;
-
-        // Initialize approval commitments to zero
-        proposal.approval_commitments = [[0u8; 32]; MAX_MULTISIG_SIGNERS];
-
-        multisig.proposal_count += 1;
-
-        emit!(MultisigProposalCreated {
-            multisig: multisig.key(),
-            proposal: proposal.key(),
-            proposal_id,
-            instruction_hash,
-            timestamp: current_time,
-        });
-
-        Ok(())
+        instructions::voting::create_proposal_from_template(ctx, proposal_id, metadata_hash)
     }
 
-    /// Sign a multisig proposal with a stealth signature
-    /// The signer proves they are an authorized signer without revealing which one
-    pub fn stealth_sign(
-        ctx: Context<StealthSign>,
-        signer_proof: [u8; 32],  // Proof that signer knows the preimage of one of the commitments
-        approval_commitment: [u8; 32], // Unique commitment for this approval
-    ) -> Result<()> {
-        let multisig = &ctx.accounts.multisig;
-        let proposal = &mut ctx.accounts.multisig_proposal;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        require!(!proposal.is_executed, ErrorCode::ProposalAlreadyExecuted);
-        require!(proposal.approval_count < multisig.threshold, ErrorCode::ThresholdReached);
-
-        // Verify signer_proof matches one of the signer_commitments
-        // In production: ZK proof verification
-        // For demo: We accept valid structure and check proof is non-zero
-        require!(signer_proof != [0u8; 32], ErrorCode::InvalidSignerProof);
-
-        // Check this approval commitment hasn't been used
-        let current_count = proposal.approval_count as usize;
-        for i in 0..current_count {
-            require!(
-                proposal.approval_commitments[i] != approval_commitment,
-                ErrorCode::DuplicateApproval
-            );
-        }
-
-        // Store the approval commitment (not the signer identity!)
-        proposal.approval_commitments[current_count] = approval_commitment;
-        proposal.approval_count += 1;
-
-        emit!(StealthSignatureAdded {
-            proposal: proposal.key(),
-            approval_commitment,
-            current_approvals: proposal.approval_count,
-            threshold: multisig.threshold,
-            timestamp: current_time,
-        });
+    // ============================================
+    // STAKE-WEIGHTED VOTING - Private Governance Power
+    // ============================================
 
-        Ok(())
+    /// Make a proposal stake-weighted by notes of `shielded_pool`
+    /// (proposal creator only, before any vote is cast)
+    pub fn enable_stake_weighted_voting(
+        ctx: Context<EnableStakeWeightedVoting>,
+        weight_unit: u64,
+    ) -> Result<()> {
+        instructions::voting::enable_stake_weighted_voting(ctx, weight_unit)
     }
 
-    /// Execute a multisig proposal after threshold is reached
-    pub fn execute_multisig_proposal(ctx: Context<ExecuteMultisigProposal>) -> Result<()> {
-        let multisig = &ctx.accounts.multisig;
-        let proposal = &mut ctx.accounts.multisig_proposal;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        require!(!proposal.is_executed, ErrorCode::ProposalAlreadyExecuted);
-        require!(
-            proposal.approval_count >= multisig.threshold,
-            ErrorCode::InsufficientApprovals
-        );
-
-        proposal.is_executed = true;
-        proposal.executed_at = current_time;
-
-        emit!(MultisigProposalExecuted {
-            multisig: multisig.key(),
-            proposal: proposal.key(),
-            approval_count: proposal.approval_count,
-            timestamp: current_time,
-        });
+    /// Cast a stake-weighted vote
+    /// The proof shows a note in the proposal's snapshot tree holding at
+    /// least `weight * weight_unit`, whose vote nullifier for this proposal
+    /// is `weight_nullifier`. The vote itself stays a commit-reveal
+    /// commitment, revealed with `reveal_weighted_vote`.
+    pub fn cast_weighted_vote(
+        ctx: Context<CastWeightedVote>,
+        weight_nullifier: [u8; 32],
+        vote_commitment: [u8; 32],
+        weight: u64,
+        weight_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::voting::cast_weighted_vote(ctx, weight_nullifier, vote_commitment, weight, weight_proof, circuit_id)
+    }
 
-        Ok(())
+    /// Reveal a stake-weighted vote, adding its weight to the tally
+    /// Must be signed by the key that cast it
+    pub fn reveal_weighted_vote(
+        ctx: Context<RevealWeightedVote>,
+        _weight_nullifier: [u8; 32],
+        vote_choice: bool,
+        secret: [u8; 32],
+    ) -> Result<()> {
+        instructions::voting::reveal_weighted_vote(ctx, _weight_nullifier, vote_choice, secret)
     }
 
     // ============================================
-    // SHIELDED STAKING POOL - True Privacy with Note-Based System
+    // GOVERNANCE TOKENS - Escrowed Voting Weight
     // ============================================
-    //
-    // Architecture: UTXO/Note-based shielded pool
-    // - Deposits create "notes" (encrypted commitments)
-    // - Withdrawals consume notes via nullifiers (prevents double-spend)
-    // - Amounts are NEVER visible on-chain or in transactions
-    // - Uses ZK proofs to verify ownership without revealing details
-    //
-    // Note structure: commitment = H(amount || blinding_factor || owner_commitment)
-    // Nullifier: nullifier = H(note_commitment || owner_secret)
 
-    /// Initialize a shielded stake pool with Merkle tree for notes
-    pub fn create_shielded_pool(
-        ctx: Context<CreateShieldedPool>,
-        pool_id: [u8; 32],
-        reward_rate_bps: u16,
-        lockup_epochs: u8,
+    /// Escrow `amount` governance tokens in the owner's voter weight record
+    /// and lock them for at least `lockup_seconds` from now (at most
+    /// MAX_GOVERNANCE_LOCKUP_SECONDS). Longer lockups weigh more; a zero
+    /// amount only extends the lockup
+    pub fn deposit_governance_tokens(
+        ctx: Context<DepositGovernanceTokens>,
+        amount: u64,
+        lockup_seconds: i64,
     ) -> Result<()> {
-        let pool = &mut ctx.accounts.shielded_pool;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        require!(reward_rate_bps <= 10000, ErrorCode::InvalidRewardRate);
-        require!(lockup_epochs >= 1 && lockup_epochs <= 52, ErrorCode::InvalidLockupPeriod);
-
-        pool.pool_id = pool_id;
-        pool.creator = ctx.accounts.creator.key();
-        pool.reward_rate_bps = reward_rate_bps;
-        pool.lockup_epochs = lockup_epochs;
-        pool.merkle_root = [0u8; 32]; // Empty tree root
-        pool.next_note_index = 0;
-        pool.total_notes = 0;
-        pool.created_at = current_time;
-        pool.is_active = true;
-        pool.bump = ctx.bumps.shielded_pool;
-
-        // Initialize nullifier set to empty
-        pool.nullifier_count = 0;
-
-        emit!(ShieldedPoolCreated {
-            pool: pool.key(),
-            pool_id,
-            creator: ctx.accounts.creator.key(),
-            reward_rate_bps,
-            lockup_epochs,
-            timestamp: current_time,
-        });
-
-        Ok(())
+        instructions::governance::deposit_governance_tokens(ctx, amount, lockup_seconds)
     }
 
-    /// Deposit into shielded pool - creates a note with hidden amount
-    ///
-    /// PRIVACY: The amount is NEVER passed as a parameter!
-    /// Instead, the client:
-    /// 1. Computes note_commitment = H(amount || blinding || owner_commitment)
-    /// 2. Generates a range proof proving 0 < amount < MAX without revealing amount
-    /// 3. Transfers SOL separately (amount visible only in the transfer, not linked to note)
-    ///
-    /// The pool accepts ANY deposit amount and records only the commitment.
-    /// The actual value is encoded in the commitment and proven via ZK.
-    pub fn shield_deposit(
-        ctx: Context<ShieldDeposit>,
-        note_commitment: [u8; 32],      // H(amount || blinding || owner_commitment)
-        encrypted_note: [u8; 64],        // Encrypted note data (only owner can decrypt)
-        range_proof: Vec<u8>,            // ZK proof that amount is valid (Bulletproof)
-    ) -> Result<()> {
-        let pool = &mut ctx.accounts.shielded_pool;
-        let note_account = &mut ctx.accounts.note_account;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        require!(pool.is_active, ErrorCode::PoolNotActive);
-        require!(pool.next_note_index < MAX_SHIELDED_NOTES as u32, ErrorCode::PoolFull);
-
-        // Verify range proof structure (Bulletproof format)
-        // Bulletproofs are typically 672+ bytes for 64-bit range proofs
-        require!(range_proof.len() >= 64, ErrorCode::InvalidRangeProof);
-
-        // Verify the range proof commits to a valid amount
-        // In production: use bulletproofs-solana or similar library
-        let proof_valid = verify_range_proof(&note_commitment, &range_proof);
-        require!(proof_valid, ErrorCode::InvalidRangeProof);
-
-        // Store note in the pool
-        note_account.pool = pool.key();
-        note_account.commitment = note_commitment;
-        note_account.encrypted_data = encrypted_note;
-        note_account.note_index = pool.next_note_index;
-        note_account.created_at = current_time;
-        note_account.unlock_at = current_time + (pool.lockup_epochs as i64 * 432000);
-        note_account.is_spent = false;
-        note_account.bump = ctx.bumps.note_account;
-
-        // Update Merkle tree with new note
-        let new_root = insert_note_to_merkle_tree(
-            &pool.merkle_root,
-            &note_commitment,
-            pool.next_note_index,
-        );
-        pool.merkle_root = new_root;
-        pool.next_note_index += 1;
-        pool.total_notes += 1;
-
-        // NOTE: No amount is logged, stored, or emitted!
-        emit!(ShieldedDeposit {
-            pool: pool.key(),
-            note_commitment,
-            note_index: note_account.note_index,
-            merkle_root: pool.merkle_root,
-            timestamp: current_time,
-            // Amount is NEVER included - true privacy!
-        });
+    /// Return escrowed governance tokens once the lockup has ended and no
+    /// vote they were counted in is still open
+    pub fn withdraw_governance_tokens(ctx: Context<WithdrawGovernanceTokens>, amount: u64) -> Result<()> {
+        instructions::governance::withdraw_governance_tokens(ctx, amount)
+    }
 
-        Ok(())
+    /// Make a proposal weighted by tokens of `governing_mint` escrowed in
+    /// voter weight records (proposal creator only, before any vote is cast)
+    pub fn enable_token_weighted_voting(ctx: Context<EnableTokenWeightedVoting>) -> Result<()> {
+        instructions::governance::enable_token_weighted_voting(ctx)
     }
 
-    /// Withdraw from shielded pool using ZK proof
-    ///
-    /// PRIVACY: Amount is NEVER passed as a parameter!
-    /// The withdrawal proof proves:
-    /// 1. The note exists in the Merkle tree (membership proof)
-    /// 2. The nullifier is correctly derived (prevents double-spend)
-    /// 3. The output commitment is correctly formed
-    /// 4. The amount difference is valid (if splitting)
-    ///
-    /// All without revealing the actual amount!
-    pub fn shield_withdraw(
-        ctx: Context<ShieldWithdraw>,
-        nullifier: [u8; 32],            // H(note_commitment || owner_secret) - prevents double-spend
-        merkle_proof: [[u8; 32]; 8],    // Proof that note is in tree (depth 8)
-        merkle_path_indices: u8,         // Bit flags for left/right path
-        withdrawal_proof: Vec<u8>,       // ZK proof of valid withdrawal
-        output_commitment: [u8; 32],     // New note commitment (for change, or zero for full withdraw)
+    /// Cast a token-weighted vote with the voter's escrowed weight
+    /// The commitment is revealed with `reveal_vote`
+    pub fn cast_token_weighted_vote(
+        ctx: Context<CastTokenWeightedVote>,
+        vote_commitment: [u8; 32],
     ) -> Result<()> {
-        let pool = &mut ctx.accounts.shielded_pool;
-        let nullifier_account = &mut ctx.accounts.nullifier_account;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        require!(pool.is_active, ErrorCode::PoolNotActive);
-
-        // Verify nullifier hasn't been used (prevents double-spend)
-        require!(!is_nullifier_used(pool, &nullifier), ErrorCode::NullifierAlreadyUsed);
-
-        // Verify Merkle proof (note exists in the tree)
-        let merkle_valid = verify_merkle_proof(
-            &pool.merkle_root,
-            &merkle_proof,
-            merkle_path_indices,
-            &nullifier, // Nullifier is derived from note, so we verify against it
-        );
-        require!(merkle_valid, ErrorCode::InvalidMerkleProof);
-
-        // Verify withdrawal proof (Groth16 format)
-        require!(withdrawal_proof.len() >= 256, ErrorCode::InvalidWithdrawalProof);
-
-        // The withdrawal proof proves:
-        // - nullifier = H(note || secret) for a note in the tree
-        // - The withdrawal amount matches the note amount
-        // - output_commitment is valid (for change) or zero
-        let proof_valid = verify_withdrawal_proof(
-            &nullifier,
-            &output_commitment,
-            &pool.merkle_root,
-            &withdrawal_proof,
-        );
-        require!(proof_valid, ErrorCode::InvalidWithdrawalProof);
-
-        // Record nullifier to prevent double-spend
-        nullifier_account.pool = pool.key();
-        nullifier_account.nullifier = nullifier;
-        nullifier_account.spent_at = current_time;
-        nullifier_account.bump = ctx.bumps.nullifier_account;
-
-        pool.nullifier_count += 1;
-
-        // If there's change, add new note to the tree
-        if output_commitment != [0u8; 32] {
-            let new_root = insert_note_to_merkle_tree(
-                &pool.merkle_root,
-                &output_commitment,
-                pool.next_note_index,
-            );
-            pool.merkle_root = new_root;
-            pool.next_note_index += 1;
-        }
+        instructions::governance::cast_token_weighted_vote(ctx, vote_commitment)
+    }
 
-        // NOTE: Amount is NEVER revealed - the SOL transfer happens via the proof
-        emit!(ShieldedWithdraw {
-            pool: pool.key(),
-            nullifier,
-            output_commitment,
-            merkle_root: pool.merkle_root,
-            timestamp: current_time,
-            // Amount is NEVER included - true privacy!
-        });
+    // ============================================
+    // REALMS ADDIN - spl-governance Voter Weight
+    // ============================================
 
-        Ok(())
+    /// Register a shielded pool as the voter weight source for a realm's
+    /// governing mint (realm authority only). `weight_unit` lamports of
+    /// note value count as one unit of weight
+    pub fn create_realm_registrar(ctx: Context<CreateRealmRegistrar>, weight_unit: u64) -> Result<()> {
+        instructions::governance::create_realm_registrar(ctx, weight_unit)
     }
 
-    /// Claim staking rewards using ZK proof
-    ///
-    /// PRIVACY: Reward amount is NEVER passed as a parameter!
-    /// The reward proof proves:
-    /// 1. Ownership of a note in the pool
-    /// 2. Time elapsed since deposit (for reward calculation)
-    /// 3. Correct reward amount based on hidden stake amount
-    ///
-    /// Output is a new note containing stake + rewards.
-    pub fn claim_shielded_rewards(
-        ctx: Context<ClaimShieldedRewards>,
-        stake_nullifier: [u8; 32],       // Nullifier for the original stake note
-        merkle_proof: [[u8; 32]; 8],     // Proof note is in tree
-        merkle_path_indices: u8,
-        reward_proof: Vec<u8>,            // ZK proof of correct reward calculation
-        new_note_commitment: [u8; 32],    // New note = stake + rewards
+    /// Snapshot the pool root that weight proofs for an spl-governance
+    /// proposal are checked against (anyone, once per proposal)
+    pub fn snapshot_realm_proposal(ctx: Context<SnapshotRealmProposal>) -> Result<()> {
+        instructions::governance::snapshot_realm_proposal(ctx)
+    }
+
+    /// Prove a note in the snapshot worth `weight` units and write it to the
+    /// owner's addin voter weight record for casting a vote on the proposal
+    /// in the same slot. Each note counts once per proposal
+    pub fn update_realm_voter_weight(
+        ctx: Context<UpdateRealmVoterWeight>,
+        weight_nullifier: [u8; 32],
+        weight: u64,
+        weight_proof: Vec<u8>,
+        circuit_id: [u8; 32],
     ) -> Result<()> {
-        let pool = &mut ctx.accounts.shielded_pool;
-        let nullifier_account = &mut ctx.accounts.nullifier_account;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        require!(pool.is_active, ErrorCode::PoolNotActive);
-
-        // Verify nullifier hasn't been used
-        require!(!is_nullifier_used(pool, &stake_nullifier), ErrorCode::NullifierAlreadyUsed);
-
-        // Verify Merkle proof
-        let merkle_valid = verify_merkle_proof(
-            &pool.merkle_root,
-            &merkle_proof,
-            merkle_path_indices,
-            &stake_nullifier,
-        );
-        require!(merkle_valid, ErrorCode::InvalidMerkleProof);
-
-        // Verify reward proof
-        // The proof demonstrates:
-        // - Original stake amount (hidden)
-        // - Time elapsed since stake
-        // - Reward rate from pool
-        // - Correct reward = stake * rate * time
-        // - new_note = stake + reward
-        require!(reward_proof.len() >= 256, ErrorCode::InvalidRewardProof);
-
-        let proof_valid = verify_reward_proof(
-            &stake_nullifier,
-            &new_note_commitment,
-            pool.reward_rate_bps,
-            current_time,
-            &reward_proof,
-        );
-        require!(proof_valid, ErrorCode::InvalidRewardProof);
-
-        // Record nullifier
-        nullifier_account.pool = pool.key();
-        nullifier_account.nullifier = stake_nullifier;
-        nullifier_account.spent_at = current_time;
-        nullifier_account.bump = ctx.bumps.nullifier_account;
-
-        pool.nullifier_count += 1;
-
-        // Add new note with stake + rewards
-        let new_root = insert_note_to_merkle_tree(
-            &pool.merkle_root,
-            &new_note_commitment,
-            pool.next_note_index,
-        );
-        pool.merkle_root = new_root;
-        pool.next_note_index += 1;
-
-        emit!(ShieldedRewardsClaimed {
-            pool: pool.key(),
-            stake_nullifier,
-            new_note_commitment,
-            merkle_root: pool.merkle_root,
-            timestamp: current_time,
-            // Reward amount is NEVER included - true privacy!
-        });
+        instructions::governance::update_realm_voter_weight(ctx, weight_nullifier, weight, weight_proof, circuit_id)
+    }
 
-        Ok(())
+    /// Write the pool's total weight to the registrar's addin max voter
+    /// weight record, valid for the current slot (anyone can update)
+    pub fn update_realm_max_voter_weight(ctx: Context<UpdateRealmMaxVoterWeight>) -> Result<()> {
+        instructions::governance::update_realm_max_voter_weight(ctx)
     }
 
     // ============================================
-    // LEGACY STAKING (Deprecated - kept for compatibility)
-    // These functions have privacy issues - use shielded versions above
+    // CONVICTION VOTING - Continuous Shielded Signal
     // ============================================
 
-    /// Create a private stake pool (DEPRECATED - use create_shielded_pool)
-    #[deprecated(note = "Use create_shielded_pool for true amount privacy")]
-    pub fn create_stake_pool(
-        ctx: Context<CreateStakePool>,
-        pool_id: [u8; 32],
-        min_stake_lamports: u64,
-        reward_rate_bps: u16,
-        lockup_epochs: u8,
+    /// Create a funding proposal decided by conviction: committed shielded
+    /// stake accrues its weight linearly over `ramp_period`, and the
+    /// proposal passes once proven conviction reaches the threshold
+    pub fn create_conviction_proposal(
+        ctx: Context<CreateConvictionProposal>,
+        proposal_id: [u8; 32],
+        metadata_hash: [u8; 32],
+        weight_unit: u64,
+        ramp_period: i64,
+        conviction_threshold: u64,
+        closes_at: i64,
     ) -> Result<()> {
-        let stake_pool = &mut ctx.accounts.stake_pool;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        require!(min_stake_lamports >= 1_000_000, ErrorCode::StakeTooSmall);
-        require!(reward_rate_bps <= 10000, ErrorCode::InvalidRewardRate);
-        require!(lockup_epochs >= 1 && lockup_epochs <= 52, ErrorCode::InvalidLockupPeriod);
-
-        stake_pool.pool_id = pool_id;
-        stake_pool.creator = ctx.accounts.creator.key();
-        stake_pool.min_stake_lamports = min_stake_lamports;
-        stake_pool.reward_rate_bps = reward_rate_bps;
-        stake_pool.lockup_epochs = lockup_epochs;
-        stake_pool.total_stake_commitments = 0;
-        stake_pool.total_staked_lamports = 0;
-        stake_pool.created_at = current_time;
-        stake_pool.is_active = true;
-        stake_pool.bump = ctx.bumps.stake_pool;
-
-        emit!(StakePoolCreated {
-            pool: stake_pool.key(),
-            pool_id,
-            creator: ctx.accounts.creator.key(),
-            min_stake_lamports,
-            reward_rate_bps,
-            lockup_epochs,
-            timestamp: current_time,
-        });
-
-        Ok(())
+        instructions::voting::create_conviction_proposal(
+            ctx,
+            proposal_id,
+            metadata_hash,
+            weight_unit,
+            ramp_period,
+            conviction_threshold,
+            closes_at,
+        )
     }
 
-    /// Stake with commitment (DEPRECATED - has amount visibility issue)
-    #[deprecated(note = "Use shield_deposit for true amount privacy")]
-    pub fn stake_private(
-        ctx: Context<StakePrivate>,
+    /// Commit a shielded note to a conviction proposal; the weight stays
+    /// hidden in `stake_commitment` and conviction accrues from now
+    pub fn commit_conviction_stake(
+        ctx: Context<CommitConvictionStake>,
+        stake_nullifier: [u8; 32],
         stake_commitment: [u8; 32],
-        validator_commitment: [u8; 32],
-        _amount_commitment: [u8; 32], // Changed: now accepts commitment, not plaintext
+        stake_proof: Vec<u8>,
+        circuit_id: [u8; 32],
     ) -> Result<()> {
-        let stake_pool = &mut ctx.accounts.stake_pool;
-        let stake_record = &mut ctx.accounts.stake_record;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        require!(stake_pool.is_active, ErrorCode::PoolNotActive);
-
-        // NOTE: We no longer accept plaintext amounts!
-        // The amount is now hidden inside the commitment.
-        // Actual transfer must happen separately through shield_deposit
-
-        stake_record.pool = stake_pool.key();
-        stake_record.staker = ctx.accounts.staker.key();
-        stake_record.stake_commitment = stake_commitment;
-        stake_record.validator_commitment = validator_commitment;
-        stake_record.staked_at = current_time;
-        stake_record.unlock_at = current_time + (stake_pool.lockup_epochs as i64 * 432000);
-        stake_record.is_active = true;
-        stake_record.claimed_rewards = 0;
-        stake_record.bump = ctx.bumps.stake_record;
-
-        stake_pool.total_stake_commitments += 1;
-
-        emit!(PrivateStakeCreated {
-            pool: stake_pool.key(),
-            staker: ctx.accounts.staker.key(),
-            stake_commitment,
-            validator_commitment,
-            unlock_at: stake_record.unlock_at,
-            timestamp: current_time,
-        });
-
-        Ok(())
+        instructions::voting::commit_conviction_stake(ctx, stake_nullifier, stake_commitment, stake_proof, circuit_id)
     }
 
-    /// Unstake with ZK proof (DEPRECATED - use shield_withdraw)
-    #[deprecated(note = "Use shield_withdraw for true amount privacy")]
-    pub fn unstake(
-        ctx: Context<Unstake>,
-        nullifier: [u8; 32],          // Changed: now uses nullifier
-        withdrawal_proof: Vec<u8>,     // Changed: ZK proof instead of plaintext reveal
+    /// Prove a stake's accrued conviction while its note stays unspent
+    /// The proof replaces the stake's previous contribution to the total
+    /// and counts until the end of its `CONVICTION_PROOF_WINDOW`, so
+    /// stakes re-prove every window
+    pub fn prove_conviction(
+        ctx: Context<ProveConviction>,
+        conviction: u64,
+        conviction_proof: Vec<u8>,
+        circuit_id: [u8; 32],
     ) -> Result<()> {
-        let stake_pool = &mut ctx.accounts.stake_pool;
-        let stake_record = &mut ctx.accounts.stake_record;
-        let current_time = Clock::get()?.unix_timestamp;
+        instructions::voting::prove_conviction(ctx, conviction, conviction_proof, circuit_id)
+    }
 
-        require!(stake_record.is_active, ErrorCode::StakeNotActive);
-        require!(current_time >= stake_record.unlock_at, ErrorCode::StakeLocked);
+    /// Withdraw a stake and its conviction from a proposal
+    pub fn withdraw_conviction_stake(ctx: Context<WithdrawConvictionStake>) -> Result<()> {
+        instructions::voting::withdraw_conviction_stake(ctx)
+    }
 
-        // Verify withdrawal proof structure
-        require!(withdrawal_proof.len() >= 256, ErrorCode::InvalidWithdrawalProof);
+    // ============================================
+    // QUADRATIC VOTING - ZK Square-Sum Budgets
+    // ============================================
 
-        // Verify the nullifier is correctly derived from the stake commitment
-        let nullifier_valid = verify_nullifier_derivation(
-            &stake_record.stake_commitment,
-            &nullifier,
-            &withdrawal_proof,
-        );
-        require!(nullifier_valid, ErrorCode::InvalidNullifier);
+    /// Create a quadratic voting proposal over `option_count` options for
+    /// the voters whose credit leaves make up `credit_root`
+    pub fn create_quadratic_proposal(
+        ctx: Context<CreateQuadraticProposal>,
+        proposal_id: [u8; 32],
+        metadata_hash: [u8; 32],
+        credit_root: [u8; 32],
+        option_count: u8,
+        voting_ends_at: i64,
+    ) -> Result<()> {
+        instructions::voting::create_quadratic_proposal(
+            ctx,
+            proposal_id,
+            metadata_hash,
+            credit_root,
+            option_count,
+            voting_ends_at,
+        )
+    }
 
-        stake_record.is_active = false;
-        stake_record.unstaked_at = current_time;
+    /// Cast votes across the options, proving their squares sum to within
+    /// the voter's hidden credit budget; the votes are added to the tallies
+    pub fn cast_quadratic_vote(
+        ctx: Context<CastQuadraticVote>,
+        vote_nullifier: [u8; 32],
+        votes: Vec<u64>,
+        allocation_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::voting::cast_quadratic_vote(ctx, vote_nullifier, votes, allocation_proof, circuit_id)
+    }
 
-        // NOTE: No amount is transferred here - that happens in shield_withdraw
-        // This just marks the stake as inactive
+    // ============================================
+    // POOL GOVERNANCE - Voting on Pool Parameters
+    // ============================================
 
-        emit!(PrivateUnstake {
-            pool: stake_pool.key(),
-            staker: ctx.accounts.staker.key(),
-            nullifier_hash: hash(&nullifier).to_bytes(),
-            timestamp: current_time,
-        });
+    /// Hand the pool to its stakers (pool authority only): the pool's
+    /// authority becomes its governance PDA, which holds the rules every
+    /// update proposal must meet (minimum phases, reveal and stake quorums,
+    /// dispute window, execution delay and optional sole proposer)
+    pub fn govern_pool(ctx: Context<GovernPool>, config: PoolGovernanceConfig) -> Result<()> {
+        instructions::voting::govern_pool(ctx, config)
+    }
 
-        Ok(())
+    /// Attach a pool parameter update to a stake-weighted proposal
+    /// (proposal creator only, before any vote is cast, and only when the
+    /// proposal meets the pool's governance rules)
+    pub fn attach_pool_update(ctx: Context<AttachPoolUpdate>, update: PoolParamUpdate) -> Result<()> {
+        instructions::voting::attach_pool_update(ctx, update)
     }
 
-    /// Claim rewards with proof (DEPRECATED - use claim_shielded_rewards)
-    #[deprecated(note = "Use claim_shielded_rewards for true amount privacy")]
-    pub fn claim_rewards(
-        ctx: Context<ClaimRewards>,
-        reward_proof: Vec<u8>,  // Changed: full ZK proof, not just hash
-    ) -> Result<()> {
-        let stake_pool = &ctx.accounts.stake_pool;
-        let stake_record = &mut ctx.accounts.stake_record;
-        let current_time = Clock::get()?.unix_timestamp;
-
-        require!(stake_record.is_active, ErrorCode::StakeNotActive);
-
-        // Verify reward proof (must be proper Groth16 proof)
-        require!(reward_proof.len() >= 256, ErrorCode::InvalidRewardProof);
-
-        // Extract and verify proof components
-        let proof_valid = verify_reward_claim_proof(
-            &stake_record.stake_commitment,
-            stake_pool.reward_rate_bps,
-            stake_record.staked_at,
-            current_time,
-            &reward_proof,
-        );
-        require!(proof_valid, ErrorCode::InvalidRewardProof);
-
-        // Compute reward commitment hash for the event
-        let reward_commitment = compute_reward_commitment(&reward_proof);
-
-        stake_record.last_claim_at = current_time;
-
-        emit!(RewardsClaimed {
-            pool: stake_pool.key(),
-            staker: ctx.accounts.staker.key(),
-            reward_commitment,
-            timestamp: current_time,
-        });
-
-        Ok(())
+    /// Apply the update of a finalized, passing proposal to its pool
+    /// (anyone can execute once the pool's execution delay is over; the
+    /// pool must be governed by its stakers)
+    pub fn execute_pool_update(ctx: Context<ExecutePoolUpdate>) -> Result<()> {
+        instructions::voting::execute_pool_update(ctx)
     }
-}
-
-// Account Structures
-
-#[account]
-pub struct WalletAccount {
-    /// The privacy-preserving commitment (never reveals identity)
-    pub commitment: [u8; 32],
-
-    /// The wallet owner (can cancel recovery)
-    pub owner: Pubkey,
-
-    /// When this wallet was created
-    pub created_at: i64,
-
-    /// Recovery commitment (for time-locked recovery)
-    pub recovery_commitment: [u8; 32],
-
-    /// Whether recovery is currently active
-    pub recovery_active: bool,
-
-    /// When recovery was initiated
-    pub recovery_initiated_at: i64,
-
-    /// When recovery can be executed
-    pub recovery_unlock_at: i64,
-
-    /// When recovery was executed (if applicable)
-    pub recovery_executed_at: i64,
-
-    /// PDA bump seed
-    pub bump: u8,
-}
-
-impl WalletAccount {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // commitment
-        32 + // owner
-        8 + // created_at
-        32 + // recovery_commitment
-        1 + // recovery_active
-        8 + // recovery_initiated_at
-        8 + // recovery_unlock_at
-        8 + // recovery_executed_at
-        1; // bump
-}
-
-/// Private Voting Proposal - commit-reveal scheme
-#[account]
-pub struct Proposal {
-    /// Unique proposal identifier
-    pub proposal_id: [u8; 32],
-
-    /// Creator of the proposal
-    pub creator: Pubkey,
-
-    /// Hash of proposal metadata (title, description stored off-chain)
-    pub metadata_hash: [u8; 32],
-
-    /// When the proposal was created
-    pub created_at: i64,
-
-    /// When voting ends (commit phase)
-    pub voting_ends_at: i64,
-
-    /// When reveal phase ends
-    pub reveal_ends_at: i64,
-
-    /// Number of YES votes (after reveal)
-    pub yes_count: u32,
-
-    /// Number of NO votes (after reveal)
-    pub no_count: u32,
-
-    /// Total vote commitments received
-    pub total_commitments: u32,
-
-    /// Total votes revealed
-    pub total_revealed: u32,
-
-    /// Whether the proposal has been finalized
-    pub is_finalized: bool,
-
-    /// PDA bump
-    pub bump: u8,
-}
-
-impl Proposal {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // proposal_id
-        32 + // creator
-        32 + // metadata_hash
-        8 + // created_at
-        8 + // voting_ends_at
-        8 + // reveal_ends_at
-        4 + // yes_count
-        4 + // no_count
-        4 + // total_commitments
-        4 + // total_revealed
-        1 + // is_finalized
-        1; // bump
-}
-
-/// Individual vote record for commit-reveal
-#[account]
-pub struct VoteRecord {
-    /// The proposal this vote is for
-    pub proposal: Pubkey,
-
-    /// The voter (for PDA derivation)
-    pub voter: Pubkey,
-
-    /// Vote commitment: hash(vote_choice || secret || voter)
-    pub commitment: [u8; 32],
-
-    /// Whether a vote has been cast
-    pub has_voted: bool,
-
-    /// Whether the vote has been revealed
-    pub has_revealed: bool,
-
-    /// The revealed choice (only valid if has_revealed)
-    pub revealed_choice: bool,
-
-    /// When the vote was cast
-    pub voted_at: i64,
-
-    /// When the vote was revealed
-    pub revealed_at: i64,
-
-    /// PDA bump
-    pub bump: u8,
-}
-
-impl VoteRecord {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // proposal
-        32 + // voter
-        32 + // commitment
-        1 + // has_voted
-        1 + // has_revealed
-        1 + // revealed_choice
-        8 + // voted_at
-        8 + // revealed_at
-        1; // bump
-}
-
-/// Stealth Multisig Vault - signers stored as commitments
-#[account]
-pub struct StealthMultisig {
-    /// Unique vault identifier
-    pub vault_id: [u8; 32],
-
-    /// Creator of the multisig
-    pub creator: Pubkey,
-
-    /// Number of signatures required
-    pub threshold: u8,
-
-    /// Total number of signers
-    pub total_signers: u8,
-
-    /// Signer commitments (not public keys!)
-    /// Each commitment = hash(signer_secret || signer_pubkey)
-    pub signer_commitments: [[u8; 32]; MAX_MULTISIG_SIGNERS],
-
-    /// When the multisig was created
-    pub created_at: i64,
-
-    /// Number of proposals created
-    pub proposal_count: u32,
-
-    /// PDA bump
-    pub bump: u8,
-}
-
-impl StealthMultisig {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // vault_id
-        32 + // creator
-        1 + // threshold
-        1 + // total_signers
-        (32 * MAX_MULTISIG_SIGNERS) + // signer_commitments
-        8 + // created_at
-        4 + // proposal_count
-        1; // bump
-}
-
-/// Multisig proposal with stealth signatures
-#[account]
-pub struct MultisigProposal {
-    /// The multisig this proposal belongs to
-    pub multisig: Pubkey,
-
-    /// Unique proposal identifier
-    pub proposal_id: [u8; 32],
-
-    /// Hash of the instruction to execute
-    pub instruction_hash: [u8; 32],
-
-    /// When the proposal was created
-    pub created_at: i64,
 
-    /// Number of approvals received
-    pub approval_count: u8,
-
-    /// Approval commitments (proves approval without revealing signer)
-    pub approval_commitments: [[u8; 32]; MAX_MULTISIG_SIGNERS],
-
-    /// Whether the proposal has been executed
-    pub is_executed: bool,
-
-    /// When the proposal was executed
-    pub executed_at: i64,
-
-    /// PDA bump
-    pub bump: u8,
-}
-
-impl MultisigProposal {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // multisig
-        32 + // proposal_id
-        32 + // instruction_hash
-        8 + // created_at
-        1 + // approval_count
-        (32 * MAX_MULTISIG_SIGNERS) + // approval_commitments
-        1 + // is_executed
-        8 + // executed_at
-        1; // bump
-}
-
-// ============================================
-// SHIELDED POOL ACCOUNT STRUCTURES
-// True privacy with UTXO/Note-based system
-// ============================================
-
-/// Shielded Stake Pool with Merkle tree for note commitments
-#[account]
-pub struct ShieldedPool {
-    /// Unique pool identifier
-    pub pool_id: [u8; 32],
-
-    /// Creator of the pool
-    pub creator: Pubkey,
-
-    /// Reward rate in basis points per epoch
-    pub reward_rate_bps: u16,
-
-    /// Number of epochs for lockup
-    pub lockup_epochs: u8,
-
-    /// Current Merkle root of all note commitments
-    pub merkle_root: [u8; 32],
-
-    /// Index for next note insertion
-    pub next_note_index: u32,
-
-    /// Total number of notes created
-    pub total_notes: u32,
-
-    /// Number of nullifiers recorded (notes spent)
-    pub nullifier_count: u32,
-
-    /// When the pool was created
-    pub created_at: i64,
-
-    /// Whether the pool is active
-    pub is_active: bool,
-
-    /// PDA bump
-    pub bump: u8,
-}
-
-impl ShieldedPool {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // pool_id
-        32 + // creator
-        2 + // reward_rate_bps
-        1 + // lockup_epochs
-        32 + // merkle_root
-        4 + // next_note_index
-        4 + // total_notes
-        4 + // nullifier_count
-        8 + // created_at
-        1 + // is_active
-        1; // bump
-}
-
-/// Shielded Note - represents a hidden stake amount
-/// commitment = H(amount || blinding || owner_commitment)
-#[account]
-pub struct ShieldedNote {
-    /// The pool this note belongs to
-    pub pool: Pubkey,
-
-    /// Note commitment (hides amount)
-    pub commitment: [u8; 32],
-
-    /// Encrypted note data (only owner can decrypt)
-    /// Contains: amount, blinding, unlock_time
-    pub encrypted_data: [u8; 64],
-
-    /// Index in the Merkle tree
-    pub note_index: u32,
-
-    /// When the note was created
-    pub created_at: i64,
-
-    /// When the note can be withdrawn
-    pub unlock_at: i64,
-
-    /// Whether this note has been spent (nullifier submitted)
-    pub is_spent: bool,
-
-    /// PDA bump
-    pub bump: u8,
-}
-
-impl ShieldedNote {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // pool
-        32 + // commitment
-        64 + // encrypted_data
-        4 + // note_index
-        8 + // created_at
-        8 + // unlock_at
-        1 + // is_spent
-        1; // bump
-}
-
-/// Nullifier record - prevents double-spend of notes
-/// Each spent note generates a unique nullifier
-#[account]
-pub struct NullifierRecord {
-    /// The pool this nullifier belongs to
-    pub pool: Pubkey,
-
-    /// The nullifier hash = H(note_commitment || owner_secret)
-    pub nullifier: [u8; 32],
-
-    /// When the nullifier was recorded (note spent)
-    pub spent_at: i64,
-
-    /// PDA bump
-    pub bump: u8,
-}
-
-impl NullifierRecord {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // pool
-        32 + // nullifier
-        8 + // spent_at
-        1; // bump
-}
-
-// ============================================
-// LEGACY STAKING STRUCTURES (Deprecated)
-// ============================================
-
-/// Private Stake Pool - hidden stake amounts (DEPRECATED)
-#[account]
-pub struct PrivateStakePool {
-    /// Unique pool identifier
-    pub pool_id: [u8; 32],
-
-    /// Creator of the pool
-    pub creator: Pubkey,
-
-    /// Minimum stake amount in lamports
-    pub min_stake_lamports: u64,
-
-    /// Reward rate in basis points per epoch
-    pub reward_rate_bps: u16,
-
-    /// Number of epochs for lockup
-    pub lockup_epochs: u8,
-
-    /// Total number of stake commitments
-    pub total_stake_commitments: u32,
-
-    /// Total staked lamports (aggregate, not individual)
-    pub total_staked_lamports: u64,
-
-    /// When the pool was created
-    pub created_at: i64,
-
-    /// Whether the pool is active
-    pub is_active: bool,
-
-    /// PDA bump
-    pub bump: u8,
-}
-
-impl PrivateStakePool {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // pool_id
-        32 + // creator
-        8 + // min_stake_lamports
-        2 + // reward_rate_bps
-        1 + // lockup_epochs
-        4 + // total_stake_commitments
-        8 + // total_staked_lamports
-        8 + // created_at
-        1 + // is_active
-        1; // bump
-}
-
-/// Individual private stake record
-#[account]
-pub struct PrivateStakeRecord {
-    /// The pool this stake belongs to
-    pub pool: Pubkey,
-
-    /// The staker (for PDA derivation)
-    pub staker: Pubkey,
-
-    /// Stake commitment: hash(amount || validator_commitment || staker || secret)
-    pub stake_commitment: [u8; 32],
-
-    /// Validator commitment: hash(validator_pubkey || salt)
-    pub validator_commitment: [u8; 32],
-
-    /// When the stake was created
-    pub staked_at: i64,
-
-    /// When the stake can be withdrawn
-    pub unlock_at: i64,
-
-    /// Whether the stake is active
-    pub is_active: bool,
-
-    /// Total rewards claimed
-    pub claimed_rewards: u64,
-
-    /// When rewards were last claimed
-    pub last_claim_at: i64,
+    // ============================================
+    // RANDOMNESS BEACON - Commit-Reveal
+    // ============================================
 
-    /// When the stake was withdrawn (if applicable)
-    pub unstaked_at: i64,
+    /// Open a beacon round
+    pub fn create_beacon_round(
+        ctx: Context<CreateBeaconRound>,
+        round_id: [u8; 32],
+        commit_ends_at: i64,
+        reveal_ends_at: i64,
+        bond_lamports: u64,
+    ) -> Result<()> {
+        instructions::beacon::create_beacon_round(ctx, round_id, commit_ends_at, reveal_ends_at, bond_lamports)
+    }
 
-    /// PDA bump
-    pub bump: u8,
-}
+    /// Commit to a beacon secret, bonding the round's bond
+    pub fn commit_beacon_secret(
+        ctx: Context<CommitBeaconSecret>,
+        commitment: [u8; 32], // H(DOMAIN_BEACON_COMMIT, secret || participant)
+    ) -> Result<()> {
+        instructions::beacon::commit_beacon_secret(ctx, commitment)
+    }
 
-impl PrivateStakeRecord {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // pool
-        32 + // staker
-        32 + // stake_commitment
-        32 + // validator_commitment
-        8 + // staked_at
-        8 + // unlock_at
-        1 + // is_active
-        8 + // claimed_rewards
-        8 + // last_claim_at
-        8 + // unstaked_at
-        1; // bump
-}
+    /// Reveal a beacon secret and get the bond back
+    pub fn reveal_beacon_secret(ctx: Context<RevealBeaconSecret>, secret: [u8; 32]) -> Result<()> {
+        instructions::beacon::reveal_beacon_secret(ctx, secret)
+    }
 
-// Context Structures
+    /// Forfeit the bond of a participant who did not reveal (permissionless)
+    pub fn slash_beacon_non_reveal(ctx: Context<SlashBeaconNonReveal>) -> Result<()> {
+        instructions::beacon::slash_beacon_non_reveal(ctx)
+    }
 
-#[derive(Accounts)]
-pub struct InitializeCommitment<'info> {
-    #[account(
-        init,
-        payer = user,
-        space = WalletAccount::LEN,
-        seeds = [b"wallet", user.key().as_ref()],
-        bump
-    )]
-    pub wallet_account: Account<'info, WalletAccount>,
+    /// Derive the beacon value once the reveal window closes (permissionless)
+    pub fn finalize_beacon(ctx: Context<FinalizeBeacon>) -> Result<()> {
+        instructions::beacon::finalize_beacon(ctx)
+    }
 
-    #[account(mut)]
-    pub user: Signer<'info>,
+    /// Return a finalized beacon value (via return data, for CPI consumers)
+    pub fn get_beacon(ctx: Context<GetBeacon>) -> Result<[u8; 32]> {
+        instructions::beacon::get_beacon(ctx)
+    }
 
-    pub system_program: Program<'info, System>,
-}
+    // ============================================
+    // STEALTH MULTISIG - Hidden Signers
+    // ============================================
 
-#[derive(Accounts)]
-pub struct SubmitProof<'info> {
-    #[account(
-        seeds = [b"wallet", wallet_account.owner.as_ref()],
-        bump = wallet_account.bump
-    )]
-    pub wallet_account: Account<'info, WalletAccount>,
+    /// Create a stealth multisig vault
+    /// Signer identities are stored as commitments, not public keys
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        vault_id: [u8; 32],
+        threshold: u8,
+        signer_commitments: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::multisig::create_multisig(ctx, vault_id, threshold, signer_commitments)
+    }
 
-    pub user: Signer<'info>,
-}
+    /// Create a proposal for the multisig to execute
+    /// The action is validated here and stored in a companion account so
+    /// wallets can render exactly what signers are approving
+    pub fn create_multisig_proposal(
+        ctx: Context<CreateMultisigProposal>,
+        proposal_id: [u8; 32],
+        action: MultisigAction,
+    ) -> Result<()> {
+        instructions::multisig::create_multisig_proposal(ctx, proposal_id, action)
+    }
 
-#[derive(Accounts)]
-pub struct InitiateRecovery<'info> {
-    #[account(
-        mut,
-        seeds = [b"wallet", wallet_account.owner.as_ref()],
-        bump = wallet_account.bump,
-        constraint = wallet_account.owner == user.key() @ ErrorCode::Unauthorized
-    )]
-    pub wallet_account: Account<'info, WalletAccount>,
-
-    pub user: Signer<'info>,
-}
+    /// Sign a multisig proposal with a stealth signature
+    /// The signer proves they are an authorized signer without revealing which one,
+    /// and the proof is checked before the proposal grows by an approval slot
+    /// Approvals given before the signer set last changed are dropped first
+    /// Public signals: [approval_commitment, compute_stealth_approval_tag(proposal, signers)]
+    pub fn stealth_sign(
+        ctx: Context<StealthSign>,
+        signer_proof: Vec<u8>,  // Proof that signer knows the preimage of one of the commitments
+        approval_commitment: [u8; 32], // Signer's nullifier for this proposal
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::multisig::stealth_sign(ctx, signer_proof, approval_commitment, circuit_id)
+    }
 
-#[derive(Accounts)]
-pub struct ExecuteRecovery<'info> {
-    #[account(
-        mut,
-        seeds = [b"wallet", wallet_account.owner.as_ref()],
-        bump = wallet_account.bump
-    )]
-    pub wallet_account: Account<'info, WalletAccount>,
+    /// Stealth-sign from cold storage. Durable-nonce transactions never
+    /// expire, so the approval pins the action hash the signer reviewed
+    /// and a `valid_until` time past which it no longer counts
+    pub fn stealth_sign_offline(
+        ctx: Context<StealthSign>,
+        signer_proof: Vec<u8>,
+        approval_commitment: [u8; 32],
+        instruction_hash: [u8; 32],
+        valid_until: i64,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::multisig::stealth_sign_offline(
+            ctx,
+            signer_proof,
+            approval_commitment,
+            instruction_hash,
+            valid_until,
+            circuit_id,
+        )
+    }
 
-    pub user: Signer<'info>,
-}
+    /// Withdraw a stealth approval before the proposal is executed
+    /// The proof shows knowledge of the secret behind `approval_commitment`,
+    /// the per-signer value `stealth_sign` stored, so only the original
+    /// approver can revoke it. The slot is cleared and, if the proposal
+    /// drops back below threshold, its veto window and timelock are reset.
+    pub fn revoke_stealth_signature(
+        ctx: Context<RevokeStealthSignature>,
+        approval_commitment: [u8; 32],
+        revocation_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::multisig::revoke_stealth_signature(ctx, approval_commitment, revocation_proof, circuit_id)
+    }
 
-#[derive(Accounts)]
-pub struct CancelRecovery<'info> {
-    #[account(
-        mut,
-        seeds = [b"wallet", wallet_account.owner.as_ref()],
-        bump = wallet_account.bump,
-        constraint = wallet_account.owner == user.key() @ ErrorCode::Unauthorized
-    )]
-    pub wallet_account: Account<'info, WalletAccount>,
-
-    pub user: Signer<'info>,
-}
+    /// Execute a multisig proposal after threshold is reached
+    /// Signer-set and veto changes are applied here; other actions are
+    /// carried out by the client against the approved, rendered payload.
+    /// The multisig's timelock, and its veto window if it has a veto
+    /// authority, must have passed since the final approval
+    /// remaining_accounts: accounts of the pool-admin instruction a
+    /// `PoolAdmin` action invokes, in its order; for a `Batch`, each
+    /// pool-admin step's `account_count` accounts, step after step
+    pub fn execute_multisig_proposal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteMultisigProposal<'info>>,
+    ) -> Result<()> {
+        instructions::multisig::execute_multisig_proposal(ctx)
+    }
 
-// Private Voting Context Structures
+    /// Create a proposal whose action stays encrypted until execution
+    /// The action is encrypted under a random key K that is Shamir-split
+    /// (threshold-of-n over GF(256)) into one share per signer commitment.
+    /// Only H(K) and a commitment to each share are stored, so observers
+    /// cannot tell what signers are approving
+    pub fn create_encrypted_multisig_proposal(
+        ctx: Context<CreateEncryptedMultisigProposal>,
+        proposal_id: [u8; 32],
+        ciphertext: Vec<u8>,
+        key_commitment: [u8; 32],
+        share_commitments: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::multisig::create_encrypted_multisig_proposal(ctx, proposal_id, ciphertext, key_commitment, share_commitments)
+    }
 
-#[derive(Accounts)]
-#[instruction(proposal_id: [u8; 32])]
-pub struct CreateProposal<'info> {
-    #[account(
-        init,
-        payer = creator,
-        space = Proposal::LEN,
-        seeds = [b"proposal", creator.key().as_ref(), &proposal_id],
-        bump
-    )]
-    pub proposal: Account<'info, Proposal>,
+    /// Execute an encrypted proposal once it has enough approvals
+    /// The executor submits at least `threshold` key shares collected from
+    /// signers; the key is reconstructed and checked against H(K), the
+    /// action decrypted, validated and applied, and K published so anyone
+    /// can audit the ciphertext afterwards
+    /// remaining_accounts: as for `execute_multisig_proposal`
+    pub fn execute_encrypted_multisig_proposal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteEncryptedMultisigProposal<'info>>,
+        key_shares: Vec<ProposalKeyShare>,
+    ) -> Result<()> {
+        instructions::multisig::execute_encrypted_multisig_proposal(ctx, key_shares)
+    }
 
-    #[account(mut)]
-    pub creator: Signer<'info>,
+    /// Veto an approved proposal while its veto window is open
+    /// The veto authority (e.g. a security council) is known only by its
+    /// commitment and proves knowledge of the preimage in zero knowledge,
+    /// bound to this proposal. A vetoed proposal can never be executed
+    pub fn veto_multisig_proposal(
+        ctx: Context<VetoMultisigProposal>,
+        veto_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::multisig::veto_multisig_proposal(ctx, veto_proof, circuit_id)
+    }
 
-    pub system_program: Program<'info, System>,
-}
+    /// Prove a transfer proposal is within the multisig's hidden small-spend
+    /// limit, so one approval executes it. Must come before any approval;
+    /// changing the policy sends pending small spends back to the threshold
+    pub fn prove_small_spend(ctx: Context<ProveSmallSpend>, spend_proof: Vec<u8>, circuit_id: [u8; 32]) -> Result<()> {
+        instructions::multisig::prove_small_spend(ctx, spend_proof, circuit_id)
+    }
 
-#[derive(Accounts)]
-pub struct CastVote<'info> {
-    #[account(
-        mut,
-        seeds = [b"proposal", proposal.creator.as_ref(), &proposal.proposal_id],
-        bump = proposal.bump
-    )]
-    pub proposal: Account<'info, Proposal>,
-
-    #[account(
-        init,
-        payer = voter,
-        space = VoteRecord::LEN,
-        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
-        bump
-    )]
-    pub vote_record: Account<'info, VoteRecord>,
-
-    #[account(mut)]
-    pub voter: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+    /// Replace your own signer commitment by proving knowledge of the old
+    /// one's preimage. No proposal is needed and the rest of the set and
+    /// the threshold are untouched; the event names neither commitment.
+    /// Pending approvals go stale, as with any other signer change
+    pub fn rotate_signer_key(
+        ctx: Context<RotateSignerKey>,
+        old_commitment: [u8; 32],
+        new_commitment: [u8; 32],
+        rotation_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::multisig::rotate_signer_key(ctx, old_commitment, new_commitment, rotation_proof, circuit_id)
+    }
 
-#[derive(Accounts)]
-pub struct RevealVote<'info> {
-    #[account(
-        mut,
-        seeds = [b"proposal", proposal.creator.as_ref(), &proposal.proposal_id],
-        bump = proposal.bump
-    )]
-    pub proposal: Account<'info, Proposal>,
-
-    #[account(
-        mut,
-        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
-        bump = vote_record.bump,
-        constraint = vote_record.voter == voter.key() @ ErrorCode::Unauthorized
-    )]
-    pub vote_record: Account<'info, VoteRecord>,
-
-    pub voter: Signer<'info>,
-}
+    // ============================================
+    // SIGNER LIVENESS - Heartbeats and Threshold Degradation
+    // ============================================
 
-#[derive(Accounts)]
-pub struct FinalizeProposal<'info> {
-    #[account(
-        mut,
-        seeds = [b"proposal", proposal.creator.as_ref(), &proposal.proposal_id],
-        bump = proposal.bump
-    )]
-    pub proposal: Account<'info, Proposal>,
+    /// Post an anonymous heartbeat for the current epoch, proving membership
+    /// in the signer set with a per-epoch nullifier. Reaching quorum cancels
+    /// a pending threshold degradation
+    pub fn post_signer_heartbeat(
+        ctx: Context<PostSignerHeartbeat>,
+        epoch: u64,
+        heartbeat_nullifier: [u8; 32],
+        heartbeat_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::multisig::post_signer_heartbeat(ctx, epoch, heartbeat_nullifier, heartbeat_proof, circuit_id)
+    }
 
-    pub authority: Signer<'info>,
-}
+    /// Announce that the threshold will drop by one: the last complete
+    /// epoch and the current one have fewer heartbeats than the threshold
+    /// (anyone can announce)
+    pub fn announce_threshold_degradation(ctx: Context<AnnounceThresholdDegradation>) -> Result<()> {
+        instructions::multisig::announce_threshold_degradation(ctx)
+    }
 
-// Stealth Multisig Context Structures
+    /// Lower the threshold by one once the degradation timelock has passed
+    /// without quorum returning (anyone can execute)
+    pub fn execute_threshold_degradation(ctx: Context<ExecuteThresholdDegradation>) -> Result<()> {
+        instructions::multisig::execute_threshold_degradation(ctx)
+    }
 
-#[derive(Accounts)]
-#[instruction(vault_id: [u8; 32])]
-pub struct CreateMultisig<'info> {
-    #[account(
-        init,
-        payer = creator,
-        space = StealthMultisig::LEN,
-        seeds = [b"multisig", creator.key().as_ref(), &vault_id],
-        bump
-    )]
-    pub multisig: Account<'info, StealthMultisig>,
+    // ============================================
+    // TREASURY ATTESTATIONS - Private Holdings Bands
+    // ============================================
 
-    #[account(mut)]
-    pub creator: Signer<'info>,
+    /// Prove that a multisig's shielded treasury in `shielded_pool` is worth
+    /// between `min_balance` and `max_balance` lamports, without revealing
+    /// the exact balance. Recorded against the pool's current state
+    pub fn publish_treasury_attestation(
+        ctx: Context<PublishTreasuryAttestation>,
+        min_balance: u64,
+        max_balance: u64,
+        band_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::multisig::publish_treasury_attestation(ctx, min_balance, max_balance, band_proof, circuit_id)
+    }
 
-    pub system_program: Program<'info, System>,
-}
+    // ============================================
+    // RATE-LIMITING NULLIFIERS (RLN) - Anti-Spam
+    // ============================================
 
-#[derive(Accounts)]
-#[instruction(proposal_id: [u8; 32])]
-pub struct CreateMultisigProposal<'info> {
-    #[account(
-        mut,
-        seeds = [b"multisig", multisig.creator.as_ref(), &multisig.vault_id],
-        bump = multisig.bump
-    )]
-    pub multisig: Account<'info, StealthMultisig>,
-
-    #[account(
-        init,
-        payer = proposer,
-        space = MultisigProposal::LEN,
-        seeds = [b"ms_proposal", multisig.key().as_ref(), &proposal_id],
-        bump
-    )]
-    pub multisig_proposal: Account<'info, MultisigProposal>,
-
-    #[account(mut)]
-    pub proposer: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+    /// Create an RLN group with a stake requirement and per-epoch message limit
+    pub fn create_rln_group(
+        ctx: Context<CreateRlnGroup>,
+        group_id: [u8; 32],
+        stake_lamports: u64,
+        epoch_length: i64,
+        message_limit: u32,
+    ) -> Result<()> {
+        instructions::rln::create_rln_group(ctx, group_id, stake_lamports, epoch_length, message_limit)
+    }
 
-#[derive(Accounts)]
-pub struct StealthSign<'info> {
-    #[account(
-        seeds = [b"multisig", multisig.creator.as_ref(), &multisig.vault_id],
-        bump = multisig.bump
-    )]
-    pub multisig: Account<'info, StealthMultisig>,
-
-    #[account(
-        mut,
-        seeds = [b"ms_proposal", multisig.key().as_ref(), &multisig_proposal.proposal_id],
-        bump = multisig_proposal.bump,
-        constraint = multisig_proposal.multisig == multisig.key() @ ErrorCode::Unauthorized
-    )]
-    pub multisig_proposal: Account<'info, MultisigProposal>,
-
-    pub signer: Signer<'info>,
-}
+    /// Register an identity commitment in an RLN group, bonding the required stake
+    /// The stake is held by the member PDA until the member is slashed
+    pub fn register_rln_member(
+        ctx: Context<RegisterRlnMember>,
+        identity_commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::rln::register_rln_member(ctx, identity_commitment)
+    }
 
-#[derive(Accounts)]
-pub struct ExecuteMultisigProposal<'info> {
-    #[account(
-        seeds = [b"multisig", multisig.creator.as_ref(), &multisig.vault_id],
-        bump = multisig.bump
-    )]
-    pub multisig: Account<'info, StealthMultisig>,
-
-    #[account(
-        mut,
-        seeds = [b"ms_proposal", multisig.key().as_ref(), &multisig_proposal.proposal_id],
-        bump = multisig_proposal.bump,
-        constraint = multisig_proposal.multisig == multisig.key() @ ErrorCode::Unauthorized
-    )]
-    pub multisig_proposal: Account<'info, MultisigProposal>,
-
-    pub executor: Signer<'info>,
-}
+    /// Record an anonymous rate-limited action
+    /// The proof shows the signaller is a group member, the share lies on their
+    /// line for this epoch, and message_id < message_limit. Reusing a nullifier
+    /// (i.e. exceeding the limit) fails here, and the leaked share enables slashing.
+    #[allow(clippy::too_many_arguments)]
+    pub fn rln_signal(
+        ctx: Context<RlnSignal>,
+        nullifier: [u8; 32],
+        epoch: u64,
+        share_x: [u8; 32],
+        share_y: [u8; 32],
+        members_root: [u8; 32],
+        signal_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::rln::rln_signal(ctx, nullifier, epoch, share_x, share_y, members_root, signal_proof, circuit_id)
+    }
 
-// ============================================
-// SHIELDED POOL CONTEXT STRUCTURES
-// ============================================
-
-#[derive(Accounts)]
-#[instruction(pool_id: [u8; 32])]
-pub struct CreateShieldedPool<'info> {
-    #[account(
-        init,
-        payer = creator,
-        space = ShieldedPool::LEN,
-        seeds = [b"shielded_pool", creator.key().as_ref(), &pool_id],
-        bump
-    )]
-    pub shielded_pool: Account<'info, ShieldedPool>,
-
-    #[account(mut)]
-    pub creator: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+    /// Slash a member who exceeded the rate limit
+    /// The slasher presents the identity secret reconstructed from two shares
+    /// under the same nullifier; the second share must lie on the same line.
+    /// The member's bonded stake is paid to the slasher.
+    /// Field elements may be big- or little-endian (see `FieldElement::from_bytes_detect`).
+    pub fn rln_slash(
+        ctx: Context<RlnSlash>,
+        identity_secret: [u8; 32],
+        share_x: [u8; 32],
+        share_y: [u8; 32],
+    ) -> Result<()> {
+        instructions::rln::rln_slash(ctx, identity_secret, share_x, share_y)
+    }
 
-#[derive(Accounts)]
-pub struct ShieldDeposit<'info> {
-    #[account(
-        mut,
-        seeds = [b"shielded_pool", shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
-        bump = shielded_pool.bump
-    )]
-    pub shielded_pool: Account<'info, ShieldedPool>,
-
-    #[account(
-        init,
-        payer = depositor,
-        space = ShieldedNote::LEN,
-        seeds = [b"note", shielded_pool.key().as_ref(), &shielded_pool.next_note_index.to_le_bytes()],
-        bump
-    )]
-    pub note_account: Account<'info, ShieldedNote>,
-
-    /// CHECK: Pool vault for holding deposited SOL
-    #[account(
-        mut,
-        seeds = [b"shielded_vault", shielded_pool.key().as_ref()],
-        bump
-    )]
-    pub pool_vault: AccountInfo<'info>,
-
-    #[account(mut)]
-    pub depositor: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+    // ============================================
+    // SHIELDED STAKING POOL - True Privacy with Note-Based System
+    // ============================================
 
-#[derive(Accounts)]
-#[instruction(nullifier: [u8; 32])]
-pub struct ShieldWithdraw<'info> {
-    #[account(
-        mut,
-        seeds = [b"shielded_pool", shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
-        bump = shielded_pool.bump
-    )]
-    pub shielded_pool: Account<'info, ShieldedPool>,
-
-    #[account(
-        init,
-        payer = withdrawer,
-        space = NullifierRecord::LEN,
-        seeds = [b"nullifier", shielded_pool.key().as_ref(), &nullifier],
-        bump
-    )]
-    pub nullifier_account: Account<'info, NullifierRecord>,
-
-    /// CHECK: Pool vault for releasing SOL
-    #[account(
-        mut,
-        seeds = [b"shielded_vault", shielded_pool.key().as_ref()],
-        bump
-    )]
-    pub pool_vault: AccountInfo<'info>,
-
-    #[account(mut)]
-    pub withdrawer: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+    /// Initialize a shielded stake pool with Merkle tree for notes
+    pub fn create_shielded_pool(
+        ctx: Context<CreateShieldedPool>,
+        pool_id: [u8; 32],
+        reward_rate_bps: u16,
+        lockup_epochs: u8,
+    ) -> Result<()> {
+        instructions::shielded::create_shielded_pool(ctx, pool_id, reward_rate_bps, lockup_epochs)
+    }
 
-#[derive(Accounts)]
-#[instruction(stake_nullifier: [u8; 32])]
-pub struct ClaimShieldedRewards<'info> {
-    #[account(
-        mut,
-        seeds = [b"shielded_pool", shielded_pool.creator.as_ref(), &shielded_pool.pool_id],
-        bump = shielded_pool.bump
-    )]
-    pub shielded_pool: Account<'info, ShieldedPool>,
-
-    #[account(
-        init,
-        payer = claimer,
-        space = NullifierRecord::LEN,
-        seeds = [b"nullifier", shielded_pool.key().as_ref(), &stake_nullifier],
-        bump
-    )]
-    pub nullifier_account: Account<'info, NullifierRecord>,
-
-    /// CHECK: Pool vault for reward distribution
-    #[account(
-        mut,
-        seeds = [b"shielded_vault", shielded_pool.key().as_ref()],
-        bump
-    )]
-    pub pool_vault: AccountInfo<'info>,
-
-    #[account(mut)]
-    pub claimer: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+    /// Hand the pool's admin authority to `new_authority` (authority only)
+    /// Pass a multisig's authority PDA (`pda::multisig_authority_address`)
+    /// to govern the pool through `MultisigAction::PoolAdmin` proposals;
+    /// hand it to its stakers with `govern_pool` instead
+    pub fn set_pool_authority(ctx: Context<SetPoolAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::shielded::set_pool_authority(ctx, new_authority)
+    }
 
-// ============================================
-// LEGACY STAKING CONTEXT STRUCTURES (Deprecated)
-// ============================================
-
-#[derive(Accounts)]
-#[instruction(pool_id: [u8; 32])]
-pub struct CreateStakePool<'info> {
-    #[account(
-        init,
-        payer = creator,
-        space = PrivateStakePool::LEN,
-        seeds = [b"stake_pool", creator.key().as_ref(), &pool_id],
-        bump
-    )]
-    pub stake_pool: Account<'info, PrivateStakePool>,
-
-    /// CHECK: Pool vault PDA for holding staked SOL
-    #[account(
-        mut,
-        seeds = [b"stake_vault", stake_pool.key().as_ref()],
-        bump
-    )]
-    pub pool_vault: AccountInfo<'info>,
-
-    #[account(mut)]
-    pub creator: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+    /// Deposit into shielded pool - creates a note with hidden amount
+    ///
+    /// PRIVACY: The amount is NEVER passed as a parameter!
+    /// Instead, the client:
+    /// 1. Computes note_commitment = H(amount || blinding || owner_commitment)
+    /// 2. Generates a range proof proving 0 < amount < MAX without revealing amount
+    /// 3. Transfers SOL separately (amount visible only in the transfer, not linked to note)
+    ///
+    /// The pool accepts ANY deposit amount and records only the commitment.
+    /// The actual value is encoded in the commitment and proven via ZK.
+    pub fn shield_deposit(
+        ctx: Context<ShieldDeposit>,
+        note_commitment: [u8; 32],      // H(amount || blinding || owner_commitment)
+        encrypted_note: [u8; 64],        // Encrypted note data (only owner can decrypt)
+        range_proof: Vec<u8>,            // ZK proof that amount is valid (Bulletproof)
+    ) -> Result<()> {
+        instructions::shielded::shield_deposit(ctx, note_commitment, encrypted_note, range_proof)
+    }
 
-#[derive(Accounts)]
-pub struct StakePrivate<'info> {
-    #[account(
-        mut,
-        seeds = [b"stake_pool", stake_pool.creator.as_ref(), &stake_pool.pool_id],
-        bump = stake_pool.bump
-    )]
-    pub stake_pool: Account<'info, PrivateStakePool>,
-
-    #[account(
-        init,
-        payer = staker,
-        space = PrivateStakeRecord::LEN,
-        seeds = [b"stake_record", stake_pool.key().as_ref(), staker.key().as_ref()],
-        bump
-    )]
-    pub stake_record: Account<'info, PrivateStakeRecord>,
-
-    /// CHECK: Pool vault PDA for holding staked SOL
-    #[account(
-        mut,
-        seeds = [b"stake_vault", stake_pool.key().as_ref()],
-        bump
-    )]
-    pub pool_vault: AccountInfo<'info>,
-
-    #[account(mut)]
-    pub staker: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+    /// Deposit several notes in one instruction
+    /// One aggregate range proof covers every commitment, and the notes are
+    /// inserted as consecutive leaves in the order given
+    /// remaining_accounts: note accounts for indices next_note_index.. (writable)
+    pub fn shield_deposit_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ShieldDepositBatch<'info>>,
+        note_commitments: Vec<[u8; 32]>,
+        encrypted_notes: Vec<[u8; 64]>,
+        range_proof: Vec<u8>,
+    ) -> Result<()> {
+        instructions::shielded::shield_deposit_batch(ctx, note_commitments, encrypted_notes, range_proof)
+    }
 
-#[derive(Accounts)]
-pub struct Unstake<'info> {
-    #[account(
-        mut,
-        seeds = [b"stake_pool", stake_pool.creator.as_ref(), &stake_pool.pool_id],
-        bump = stake_pool.bump
-    )]
-    pub stake_pool: Account<'info, PrivateStakePool>,
-
-    #[account(
-        mut,
-        seeds = [b"stake_record", stake_pool.key().as_ref(), staker.key().as_ref()],
-        bump = stake_record.bump,
-        constraint = stake_record.staker == staker.key() @ ErrorCode::Unauthorized
-    )]
-    pub stake_record: Account<'info, PrivateStakeRecord>,
-
-    /// CHECK: Pool vault PDA for holding staked SOL
-    #[account(
-        mut,
-        seeds = [b"stake_vault", stake_pool.key().as_ref()],
-        bump
-    )]
-    pub pool_vault: AccountInfo<'info>,
-
-    #[account(mut)]
-    pub staker: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+    /// Deposit one real note padded with the pool's `decoy_outputs` decoy
+    /// notes (zero-value, owned by the depositor) in random order
+    /// Same accounts and proof as `shield_deposit_batch`; the commitments
+    /// must be distinct and non-zero. Counts as one deposit for referrals
+    /// and metrics
+    pub fn shield_deposit_with_decoys<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ShieldDepositBatch<'info>>,
+        note_commitments: Vec<[u8; 32]>,
+        encrypted_notes: Vec<[u8; 64]>,
+        range_proof: Vec<u8>,
+    ) -> Result<()> {
+        instructions::shielded::shield_deposit_with_decoys(ctx, note_commitments, encrypted_notes, range_proof)
+    }
 
-#[derive(Accounts)]
-pub struct ClaimRewards<'info> {
-    #[account(
-        seeds = [b"stake_pool", stake_pool.creator.as_ref(), &stake_pool.pool_id],
-        bump = stake_pool.bump
-    )]
-    pub stake_pool: Account<'info, PrivateStakePool>,
-
-    #[account(
-        mut,
-        seeds = [b"stake_record", stake_pool.key().as_ref(), staker.key().as_ref()],
-        bump = stake_record.bump,
-        constraint = stake_record.staker == staker.key() @ ErrorCode::Unauthorized
-    )]
-    pub stake_record: Account<'info, PrivateStakeRecord>,
-
-    /// CHECK: Pool vault PDA for holding staked SOL
-    #[account(
-        mut,
-        seeds = [b"stake_vault", stake_pool.key().as_ref()],
-        bump
-    )]
-    pub pool_vault: AccountInfo<'info>,
-
-    #[account(mut)]
-    pub staker: Signer<'info>,
-}
+    /// Set how many decoy notes pad each decoy deposit (0 disables them)
+    pub fn configure_decoy_outputs(ctx: Context<ConfigureDecoyOutputs>, decoy_outputs: u8) -> Result<()> {
+        instructions::shielded::configure_decoy_outputs(ctx, decoy_outputs)
+    }
 
-// Events
+    /// Install the program every `shield_withdraw` must be screened by, or
+    /// omit `screener_program` to stop screening (pool authority only)
+    /// Once set, withdrawal paths that cannot name a recipient are refused
+    pub fn configure_withdrawal_screener(ctx: Context<ConfigureWithdrawalScreener>) -> Result<()> {
+        instructions::shielded::configure_withdrawal_screener(ctx)
+    }
 
-#[event]
-pub struct CommitmentCreated {
-    pub wallet: Pubkey,
-    pub commitment: [u8; 32],
-    pub timestamp: i64,
-}
+    /// Schedule the pool's reward rate: each point sets the rate for notes
+    /// deposited from its `starts_at` on (pool authority only)
+    /// Points already in effect must be kept; new points must start later
+    pub fn configure_reward_curve(
+        ctx: Context<ConfigureRewardCurve>,
+        reward_curve: Vec<RewardCurvePoint>,
+    ) -> Result<()> {
+        instructions::shielded::configure_reward_curve(ctx, reward_curve)
+    }
 
-#[event]
-pub struct ProofVerified {
-    pub wallet: Pubkey,
-    pub proof_hash: [u8; 32],
-    pub public_signals_hash: [u8; 32],
-    pub verification_type: ProofType,
-    pub timestamp: i64,
-}
+    /// Withdraw from shielded pool using ZK proof
+    ///
+    /// PRIVACY: Amount is NEVER passed as a parameter!
+    /// The withdrawal proof proves:
+    /// 1. The note exists in the Merkle tree (membership proof)
+    /// 2. The nullifier is correctly derived (prevents double-spend)
+    /// 3. The output commitment is correctly formed
+    /// 4. The amount difference is valid (if splitting)
+    /// 5. It was generated for this pool (`compute_pool_binding`, last public signal)
+    ///
+    /// All without revealing the actual amount!
+    ///
+    /// Pools with a withdrawal screener CPI it with (withdrawer, nullifier)
+    /// first; remaining accounts are forwarded to it read-only
+    pub fn shield_withdraw<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ShieldWithdraw<'info>>,
+        nullifier: [u8; 32],            // H(note_commitment || owner_secret) - prevents double-spend
+        merkle_proof: [[u8; 32]; 8],    // Proof that note is in tree (depth 8)
+        merkle_path_indices: u8,         // Bit flags for left/right path
+        withdrawal_proof: Vec<u8>,       // ZK proof of valid withdrawal
+        output_commitment: [u8; 32],     // New note commitment (for change, or zero for full withdraw)
+        circuit_id: [u8; 32],           // Circuit the proof was made for (`Circuit::id`)
+    ) -> Result<()> {
+        instructions::shielded::shield_withdraw(ctx, nullifier, merkle_proof, merkle_path_indices, withdrawal_proof, output_commitment, circuit_id)
+    }
 
-/// Proof types supported by the protocol
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum ProofType {
-    Groth16,
-    Bulletproof,
-    Poseidon,
-}
+    /// Settle K withdrawals with one aggregated proof
+    /// A recursive/aggregated proof (e.g. Nova folding or SnarkPack over
+    /// Groth16) attests that each underlying withdrawal proof is valid
+    /// against the current root, including Merkle membership, so the
+    /// per-withdrawal cost here is only the nullifier record
+    /// remaining_accounts: one nullifier record per withdrawal (writable)
+    pub fn shield_withdraw_aggregated<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ShieldWithdrawAggregated<'info>>,
+        nullifiers: Vec<[u8; 32]>,
+        output_commitments: Vec<[u8; 32]>,
+        aggregate_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::shielded::shield_withdraw_aggregated(ctx, nullifiers, output_commitments, aggregate_proof, circuit_id)
+    }
 
-#[event]
-pub struct RecoveryInitiated {
-    pub wallet: Pubkey,
-    pub recovery_commitment: [u8; 32],
-    pub unlock_time: i64,
-}
+    /// Consolidate up to `MAX_CONSOLIDATE_INPUTS` dust notes into one note
+    /// One proof shows the inputs are unspent notes in the tree owned by the
+    /// caller and that the output commits to their summed value.
+    ///
+    /// remaining_accounts, per input in `nullifiers` order:
+    ///   [nullifier_record (writable, uninitialised PDA),
+    ///    sibling_0, ..., sibling_{MERKLE_TREE_DEPTH-1}]
+    /// Merkle siblings are passed as account keys (never loaded), so shared
+    /// upper-level siblings de-duplicate and fit in an address lookup table.
+    pub fn shield_consolidate<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ShieldConsolidate<'info>>,
+        nullifiers: Vec<[u8; 32]>,
+        merkle_path_indices: Vec<u8>,
+        output_commitment: [u8; 32],
+        encrypted_note: [u8; 64],
+        consolidation_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::shielded::shield_consolidate(ctx, nullifiers, merkle_path_indices, output_commitment, encrypted_note, consolidation_proof, circuit_id)
+    }
 
-#[event]
-pub struct RecoveryExecuted {
-    pub wallet: Pubkey,
-    pub timestamp: i64,
-}
+    /// Claim staking rewards using ZK proof
+    ///
+    /// PRIVACY: Reward amount is NEVER passed as a parameter!
+    /// The reward proof proves:
+    /// 1. Ownership of a note in the pool
+    /// 2. Time elapsed since deposit (for reward calculation)
+    /// 3. Correct reward amount based on hidden stake amount
+    /// 4. It was generated for this pool (`compute_pool_binding`)
+    ///
+    /// Output is a new note containing stake + rewards.
+    pub fn claim_shielded_rewards(
+        ctx: Context<ClaimShieldedRewards>,
+        stake_nullifier: [u8; 32],       // Nullifier for the original stake note
+        merkle_proof: [[u8; 32]; 8],     // Proof note is in tree
+        merkle_path_indices: u8,
+        reward_proof: Vec<u8>,            // ZK proof of correct reward calculation
+        new_note_commitment: [u8; 32],    // New note = stake + rewards
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::shielded::claim_shielded_rewards(ctx, stake_nullifier, merkle_proof, merkle_path_indices, reward_proof, new_note_commitment, circuit_id)
+    }
 
-#[event]
-pub struct RecoveryCancelled {
-    pub wallet: Pubkey,
-    pub timestamp: i64,
-}
+    // ============================================
+    // CLAIMABLE NOTES - Gift Links
+    // ============================================
 
-// Private Voting Events
+    /// Deposit a note spendable by whoever holds the one-time claim secret
+    pub fn create_claimable_note(
+        ctx: Context<CreateClaimableNote>,
+        claim_key_hash: [u8; 32],       // H(DOMAIN_CLAIM_KEY, claim_secret)
+        note_commitment: [u8; 32],      // Note owned by a key derived from claim_secret
+        encrypted_note: [u8; 64],       // Note data encrypted to claim_secret
+        range_proof: Vec<u8>,
+    ) -> Result<()> {
+        instructions::shielded::create_claimable_note(ctx, claim_key_hash, note_commitment, encrypted_note, range_proof)
+    }
 
-#[event]
-pub struct ProposalCreated {
-    pub proposal: Pubkey,
-    pub proposal_id: [u8; 32],
-    pub creator: Pubkey,
-    pub voting_ends_at: i64,
-    pub reveal_ends_at: i64,
-}
+    /// Claim a gift note into a note under the recipient's own keys
+    /// The proof shows knowledge of the claim secret behind claim_key_hash,
+    /// that `nullifier` belongs to the gift note and that the output note
+    /// carries the same value. Anyone may submit it (e.g. a relayer).
+    pub fn claim_note(
+        ctx: Context<ClaimNote>,
+        nullifier: [u8; 32],
+        output_commitment: [u8; 32],
+        encrypted_note: [u8; 64],
+        claim_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::shielded::claim_note(ctx, nullifier, output_commitment, encrypted_note, claim_proof, circuit_id)
+    }
 
-#[event]
-pub struct VoteCast {
-    pub proposal: Pubkey,
-    pub voter: Pubkey,
-    pub commitment: [u8; 32],
-    pub timestamp: i64,
-}
+    // ============================================
+    // SHIELDED ESCROW - Private Commerce
+    // ============================================
 
-#[event]
-pub struct VoteRevealed {
-    pub proposal: Pubkey,
-    pub voter: Pubkey,
-    pub timestamp: i64,
-    // Note: vote choice is NOT included to preserve privacy
-}
+    /// Lock a buyer note into escrow against an order
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_escrow(
+        ctx: Context<CreateEscrow>,
+        escrow_id: [u8; 32],
+        order_hash: [u8; 32],
+        nullifier: [u8; 32],             // Nullifier of the buyer's input note
+        locked_commitment: [u8; 32],     // Commitment to the locked value
+        party_commitments: [[u8; 32]; 3], // [buyer, seller, arbiter]
+        lock_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::commerce::create_escrow(ctx, escrow_id, order_hash, nullifier, locked_commitment, party_commitments, lock_proof, circuit_id)
+    }
 
-#[event]
-pub struct ProposalFinalized {
-    pub proposal: Pubkey,
-    pub yes_count: u32,
-    pub no_count: u32,
-    pub total_votes: u32,
-    pub timestamp: i64,
-}
+    /// Cast a party's vote on an escrow (buyer release, seller refund or
+    /// arbiter ruling); the escrow resolves once two parties agree
+    pub fn vote_escrow(
+        ctx: Context<VoteEscrow>,
+        role: EscrowRole,
+        outcome: EscrowOutcome,
+        party_proof: Vec<u8>,            // Proves knowledge of the role's commitment preimage
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::commerce::vote_escrow(ctx, role, outcome, party_proof, circuit_id)
+    }
 
-// Stealth Multisig Events
+    /// Pay the locked value to the decided party as a new pool note
+    /// Anyone may submit (e.g. a relayer); the proof binds the recipient
+    pub fn settle_escrow(
+        ctx: Context<SettleEscrow>,
+        output_commitment: [u8; 32],
+        encrypted_note: [u8; 64],
+        settlement_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::commerce::settle_escrow(ctx, output_commitment, encrypted_note, settlement_proof, circuit_id)
+    }
 
-#[event]
-pub struct MultisigCreated {
-    pub multisig: Pubkey,
-    pub vault_id: [u8; 32],
-    pub threshold: u8,
-    pub total_signers: u8,
-    pub timestamp: i64,
-}
+    // ============================================
+    // SHIELDED SUBSCRIPTIONS - Recurring Payments
+    // ============================================
 
-#[event]
-pub struct MultisigProposalCreated {
-    pub multisig: Pubkey,
-    pub proposal: Pubkey,
-    pub proposal_id: [u8; 32],
-    pub instruction_hash: [u8; 32],
-    pub timestamp: i64,
-}
+    /// Authorize recurring pulls from the caller's shielded balance
+    pub fn create_subscription(
+        ctx: Context<CreateSubscription>,
+        subscription_id: [u8; 32],
+        payer_commitment: [u8; 32],      // H(payer_secret) - owner of the spent notes
+        merchant_commitment: [u8; 32],   // H(merchant_secret) - owner of payment notes
+        amount_commitment: [u8; 32],     // H(amount || blinding) - fixed per-period amount
+        period_seconds: i64,
+        start_at: i64,
+    ) -> Result<()> {
+        instructions::commerce::create_subscription(ctx, subscription_id, payer_commitment, merchant_commitment, amount_commitment, period_seconds, start_at)
+    }
 
-#[event]
-pub struct StealthSignatureAdded {
-    pub proposal: Pubkey,
-    pub approval_commitment: [u8; 32],
-    pub current_approvals: u8,
-    pub threshold: u8,
-    pub timestamp: i64,
-    // Note: signer identity is NOT included to preserve privacy
-}
+    /// Collect one period's payment
+    /// Anyone may submit (typically the merchant); the proof binds the notes
+    /// to the subscription terms and `period`
+    #[allow(clippy::too_many_arguments)]
+    pub fn collect_subscription(
+        ctx: Context<CollectSubscription>,
+        period: u64,
+        nullifier: [u8; 32],               // Nullifier of the payer note spent this period
+        change_commitment: [u8; 32],       // Payer's re-created note
+        payment_commitment: [u8; 32],      // Merchant's payment note
+        encrypted_notes: [[u8; 64]; 2],    // [change, payment]
+        collection_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::commerce::collect_subscription(ctx, period, nullifier, change_commitment, payment_commitment, encrypted_notes, collection_proof, circuit_id)
+    }
 
-#[event]
-pub struct MultisigProposalExecuted {
-    pub multisig: Pubkey,
-    pub proposal: Pubkey,
-    pub approval_count: u8,
-    pub timestamp: i64,
-}
+    /// Cancel a subscription; no further periods can be collected
+    pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+        instructions::commerce::cancel_subscription(ctx)
+    }
 
-// ============================================
-// SHIELDED POOL EVENTS - True Privacy
-// ============================================
-
-#[event]
-pub struct ShieldedPoolCreated {
-    pub pool: Pubkey,
-    pub pool_id: [u8; 32],
-    pub creator: Pubkey,
-    pub reward_rate_bps: u16,
-    pub lockup_epochs: u8,
-    pub timestamp: i64,
-    // Note: NO amount information - privacy by design
-}
+    // ============================================
+    // SHIELDED CAMPAIGNS - Private Fundraising
+    // ============================================
 
-#[event]
-pub struct ShieldedDeposit {
-    pub pool: Pubkey,
-    pub note_commitment: [u8; 32],
-    pub note_index: u32,
-    pub merkle_root: [u8; 32],
-    pub timestamp: i64,
-    // Note: Amount is NEVER included - true privacy!
-}
+    /// Create a fundraising campaign on a shielded pool
+    pub fn create_campaign(
+        ctx: Context<CreateCampaign>,
+        campaign_id: [u8; 32],
+        beneficiary_commitment: [u8; 32], // H(beneficiary_secret)
+        deadline: i64,
+    ) -> Result<()> {
+        instructions::commerce::create_campaign(ctx, campaign_id, beneficiary_commitment, deadline)
+    }
 
-#[event]
-pub struct ShieldedWithdraw {
-    pub pool: Pubkey,
-    pub nullifier: [u8; 32],
-    pub output_commitment: [u8; 32],
-    pub merkle_root: [u8; 32],
-    pub timestamp: i64,
-    // Note: Amount is NEVER included - true privacy!
-}
+    /// Donate to a campaign by depositing a note tagged to it
+    /// The proof shows the note is owned by the campaign tag, that
+    /// `nullifier` is its nullifier and that its amount is in range; the
+    /// amount itself is never revealed. The nullifier is spent at once, so
+    /// the note is only claimed through `withdraw_campaign`
+    pub fn donate_to_campaign(
+        ctx: Context<DonateToCampaign>,
+        note_commitment: [u8; 32],
+        nullifier: [u8; 32],
+        encrypted_note: [u8; 64], // Encrypted to the beneficiary
+        donation_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::commerce::donate_to_campaign(ctx, note_commitment, nullifier, encrypted_note, donation_proof, circuit_id)
+    }
 
-#[event]
-pub struct ShieldedRewardsClaimed {
-    pub pool: Pubkey,
-    pub stake_nullifier: [u8; 32],
-    pub new_note_commitment: [u8; 32],
-    pub merkle_root: [u8; 32],
-    pub timestamp: i64,
-    // Note: Reward amount is NEVER included - true privacy!
-}
+    /// Withdraw a campaign's donations as one note after the deadline
+    /// The proof shows knowledge of the beneficiary secret and that
+    /// `output_commitment` holds the sum of every donation in the aggregate
+    pub fn withdraw_campaign(
+        ctx: Context<WithdrawCampaign>,
+        output_commitment: [u8; 32],
+        encrypted_note: [u8; 64],
+        withdrawal_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::commerce::withdraw_campaign(ctx, output_commitment, encrypted_note, withdrawal_proof, circuit_id)
+    }
 
-// ============================================
-// LEGACY STAKING EVENTS (Deprecated)
-// ============================================
-
-#[event]
-pub struct StakePoolCreated {
-    pub pool: Pubkey,
-    pub pool_id: [u8; 32],
-    pub creator: Pubkey,
-    pub min_stake_lamports: u64,
-    pub reward_rate_bps: u16,
-    pub lockup_epochs: u8,
-    pub timestamp: i64,
-}
+    // ============================================
+    // PROOF OF RESERVE - Shielded Pool Solvency
+    // ============================================
 
-#[event]
-pub struct PrivateStakeCreated {
-    pub pool: Pubkey,
-    pub staker: Pubkey,
-    pub stake_commitment: [u8; 32],
-    pub validator_commitment: [u8; 32],
-    pub unlock_at: i64,
-    pub timestamp: i64,
-}
+    /// Publish a proof of reserve for the pool's current state
+    pub fn publish_reserve_proof(
+        ctx: Context<PublishReserveProof>,
+        reserve_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::shielded::publish_reserve_proof(ctx, reserve_proof, circuit_id)
+    }
 
-#[event]
-pub struct PrivateUnstake {
-    pub pool: Pubkey,
-    pub staker: Pubkey,
-    pub nullifier_hash: [u8; 32], // Changed: now includes nullifier hash instead of nothing
-    pub timestamp: i64,
-}
+    // ============================================
+    // RELAYER FEE MARKET - Gasless Withdrawals
+    // ============================================
 
-#[event]
-pub struct RewardsClaimed {
-    pub pool: Pubkey,
-    pub staker: Pubkey,
-    pub reward_commitment: [u8; 32],
-    pub timestamp: i64,
-}
+    /// Create the relayer registry of a shielded pool (pool authority only)
+    pub fn create_relayer_registry(
+        ctx: Context<CreateRelayerRegistry>,
+        min_stake: u64,
+        max_fee_lamports: u64,
+    ) -> Result<()> {
+        instructions::relayer::create_relayer_registry(ctx, min_stake, max_fee_lamports)
+    }
 
-// Error Codes
+    /// Register as a relayer, bonding the registry's minimum stake
+    /// The stake is held by the relayer PDA until it exits or is slashed
+    pub fn register_relayer(ctx: Context<RegisterRelayer>, fee_quote: u64) -> Result<()> {
+        instructions::relayer::register_relayer(ctx, fee_quote)
+    }
 
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Invalid proof provided")]
-    InvalidProof,
+    /// Update the maximum fee a relayer charges per withdrawal
+    pub fn update_relayer_quote(ctx: Context<UpdateRelayerQuote>, fee_quote: u64) -> Result<()> {
+        instructions::relayer::update_relayer_quote(ctx, fee_quote)
+    }
 
-    #[msg("Recovery is already active")]
-    RecoveryAlreadyActive,
+    /// Withdraw from the shielded pool through a registered relayer
+    /// Same checks as `shield_withdraw`, plus the opened fee commitment the
+    /// proof is bound to; the relayer signs, pays rent and receives the fee
+    #[allow(clippy::too_many_arguments)]
+    pub fn shield_withdraw_relayed(
+        ctx: Context<ShieldWithdrawRelayed>,
+        nullifier: [u8; 32],
+        merkle_proof: [[u8; 32]; 8],
+        merkle_path_indices: u8,
+        withdrawal_proof: Vec<u8>,
+        output_commitment: [u8; 32],
+        fee: RelayerFeeOpening,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::relayer::shield_withdraw_relayed(ctx, nullifier, merkle_proof, merkle_path_indices, withdrawal_proof, output_commitment, fee, circuit_id)
+    }
 
-    #[msg("No active recovery to execute or cancel")]
-    NoActiveRecovery,
+    /// `shield_withdraw_relayed` that is safe to resubmit: succeeds without
+    /// change when the nullifier is already recorded with this same output,
+    /// relayer and fee, e.g. because an earlier attempt landed despite an
+    /// ambiguous failure
+    #[allow(clippy::too_many_arguments)]
+    pub fn shield_withdraw_relayed_idempotent(
+        ctx: Context<ShieldWithdrawRelayed>,
+        nullifier: [u8; 32],
+        merkle_proof: [[u8; 32]; 8],
+        merkle_path_indices: u8,
+        withdrawal_proof: Vec<u8>,
+        output_commitment: [u8; 32],
+        fee: RelayerFeeOpening,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::relayer::shield_withdraw_relayed_idempotent(ctx, nullifier, merkle_proof, merkle_path_indices, withdrawal_proof, output_commitment, fee, circuit_id)
+    }
 
-    #[msg("Timelock period has not expired yet")]
-    TimelockNotExpired,
+    /// Slash a misbehaving relayer (registry authority only)
+    /// The bonded stake is moved into the pool vault; `evidence_hash`
+    /// commits to the off-chain evidence for auditors
+    pub fn slash_relayer(ctx: Context<SlashRelayer>, evidence_hash: [u8; 32]) -> Result<()> {
+        instructions::relayer::slash_relayer(ctx, evidence_hash)
+    }
 
-    #[msg("Invalid timelock period (must be 1-90 days)")]
-    InvalidTimelockPeriod,
+    /// Stop relaying and start the stake unbonding period
+    pub fn request_relayer_exit(ctx: Context<UpdateRelayerQuote>) -> Result<()> {
+        instructions::relayer::request_relayer_exit(ctx)
+    }
 
-    #[msg("Unauthorized: only owner can perform this action")]
-    Unauthorized,
+    /// Close an exited relayer after unbonding, returning stake and rent
+    pub fn close_relayer(ctx: Context<CloseRelayer>) -> Result<()> {
+        instructions::relayer::close_relayer(ctx)
+    }
 
-    // Voting Errors
-    #[msg("Invalid voting period")]
-    InvalidVotingPeriod,
+    /// Require relayers to prove solvency every `solvency_period` seconds
+    /// (pool authority only; 0 stops requiring it)
+    pub fn configure_relayer_solvency(ctx: Context<ConfigureRelayerSolvency>, solvency_period: i64) -> Result<()> {
+        instructions::relayer::configure_relayer_solvency(ctx, solvency_period)
+    }
 
-    #[msg("Invalid reveal period")]
-    InvalidRevealPeriod,
+    /// Prove the relayer's outstanding fee obligations are below its stake
+    /// A relayer in a registry with a solvency period cannot quote or
+    /// relay withdrawals without a proof from the last period
+    pub fn prove_relayer_solvency(
+        ctx: Context<ProveRelayerSolvency>,
+        obligations_commitment: [u8; 32],
+        solvency_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::relayer::prove_relayer_solvency(ctx, obligations_commitment, solvency_proof, circuit_id)
+    }
 
-    #[msg("Voting period has ended")]
-    VotingEnded,
+    // ============================================
+    // HOLDING ACCOUNTS - Delayed Unshielding
+    // ============================================
 
-    #[msg("Already voted on this proposal")]
-    AlreadyVoted,
+    /// Withdraw into a holding account instead of a wallet
+    /// Same checks as `shield_withdraw`; the proof also commits to the
+    /// released lamports and the claim key of the holding account, which
+    /// can be claimed to any address later with `claim_holding`
+    #[allow(clippy::too_many_arguments)]
+    pub fn shield_withdraw_to_holding(
+        ctx: Context<ShieldWithdrawToHolding>,
+        nullifier: [u8; 32],
+        merkle_proof: [[u8; 32]; 8],
+        merkle_path_indices: u8,
+        withdrawal_proof: Vec<u8>,
+        output_commitment: [u8; 32],
+        release: HoldingRelease,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::holding::shield_withdraw_to_holding(ctx, nullifier, merkle_proof, merkle_path_indices, withdrawal_proof, output_commitment, release, circuit_id)
+    }
 
-    #[msg("Voting period has not ended yet")]
-    VotingNotEnded,
+    /// Release a holding account to `recipient` with a proof of its claim
+    /// secret bound to that recipient; anyone may submit it
+    pub fn claim_holding(ctx: Context<ClaimHolding>, claim_proof: Vec<u8>, circuit_id: [u8; 32]) -> Result<()> {
+        instructions::holding::claim_holding(ctx, claim_proof, circuit_id)
+    }
 
-    #[msg("Reveal period has ended")]
-    RevealEnded,
+    // ============================================
+    // SPLIT WITHDRAWALS - One Unshield, Several Payees
+    // ============================================
 
-    #[msg("Not voted on this proposal")]
-    NotVoted,
+    /// Withdraw to up to `MAX_SPLIT_RECIPIENTS` public wallets at once
+    /// Same checks as `shield_withdraw`; the proof also commits to every
+    /// recipient and its share, so the split cannot be altered in flight
+    /// remaining_accounts: the recipients, in `payments` order (writable)
+    #[allow(clippy::too_many_arguments)]
+    pub fn shield_withdraw_split<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ShieldWithdrawSplit<'info>>,
+        nullifier: [u8; 32],
+        merkle_proof: [[u8; 32]; 8],
+        merkle_path_indices: u8,
+        withdrawal_proof: Vec<u8>,
+        output_commitment: [u8; 32],
+        payments: Vec<SplitPayment>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::split::shield_withdraw_split(ctx, nullifier, merkle_proof, merkle_path_indices, withdrawal_proof, output_commitment, payments, circuit_id)
+    }
 
-    #[msg("Already revealed vote")]
-    AlreadyRevealed,
+    // ============================================
+    // DEPOSIT CANCELLATION - Undo a Fresh Deposit
+    // ============================================
 
-    #[msg("Invalid vote reveal - commitment mismatch")]
-    InvalidVoteReveal,
+    /// Cancel a note within `DEPOSIT_CANCEL_WINDOW` of depositing it and
+    /// refund `lamports` to `recipient`, as long as the pool's tree
+    /// checkpoint has not yet published the note
+    /// The proof opens the note and binds the refund; the note's nullifier
+    /// is recorded so it cannot also be withdrawn
+    pub fn cancel_deposit<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelDeposit<'info>>,
+        nullifier: [u8; 32],
+        lamports: u64,
+        cancel_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::cancel::cancel_deposit(ctx, nullifier, lamports, cancel_proof, circuit_id)
+    }
 
-    #[msg("Reveal period has not ended yet")]
-    RevealNotEnded,
+    // ============================================
+    // DEPOSIT ROUTING - One Deposit, Several Pools
+    // ============================================
 
-    #[msg("Proposal already finalized")]
-    AlreadyFinalized,
+    /// Deposit notes into several pools in one instruction, following a
+    /// plan that lists each pool's notes together
+    /// One aggregate range proof covers every commitment in the plan
+    /// remaining_accounts: each pool (writable) followed by its legs' note
+    /// account PDAs, in plan order
+    pub fn route_deposit<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RouteDeposit<'info>>,
+        plan: Vec<RouteLeg>,
+        range_proof: Vec<u8>,
+    ) -> Result<()> {
+        instructions::router::route_deposit(ctx, plan, range_proof)
+    }
 
-    // Multisig Errors
-    #[msg("Invalid threshold")]
-    InvalidThreshold,
+    // ============================================
+    // EARLY EXITS - Leave Before the Lockup Ends
+    // ============================================
 
-    #[msg("Too many signers (max 10)")]
-    TooManySigners,
+    /// Let notes be withdrawn before `unlock_at` for a penalty of
+    /// `penalty_bps` of the amount released (pool authority only; 0
+    /// disables early exits)
+    pub fn configure_early_exit(ctx: Context<ConfigureEarlyExit>, penalty_bps: u16) -> Result<()> {
+        instructions::early_exit::configure_early_exit(ctx, penalty_bps)
+    }
 
-    #[msg("Proposal already executed")]
-    ProposalAlreadyExecuted,
+    /// Withdraw `gross_lamports` from a note that may still be locked
+    /// Same checks as `shield_withdraw`, without the lockup; the recipient
+    /// is paid `gross_lamports` less the pool's penalty, which stays in the
+    /// vault as reward reserve
+    #[allow(clippy::too_many_arguments)]
+    pub fn shield_withdraw_early<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ShieldWithdrawEarly<'info>>,
+        nullifier: [u8; 32],
+        merkle_proof: [[u8; 32]; 8],
+        merkle_path_indices: u8,
+        withdrawal_proof: Vec<u8>,
+        output_commitment: [u8; 32],
+        gross_lamports: u64,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::early_exit::shield_withdraw_early(ctx, nullifier, merkle_proof, merkle_path_indices, withdrawal_proof, output_commitment, gross_lamports, circuit_id)
+    }
 
-    #[msg("Threshold already reached")]
-    ThresholdReached,
+    // ============================================
+    // DEPOSIT RECEIPTS - Opt-in Participation Proofs
+    // ============================================
 
-    #[msg("Invalid signer proof")]
-    InvalidSignerProof,
+    /// Enable deposit receipts on a shielded pool (pool authority only)
+    pub fn enable_deposit_receipts(ctx: Context<EnableDepositReceipts>) -> Result<()> {
+        instructions::shielded::enable_deposit_receipts(ctx)
+    }
 
-    #[msg("Duplicate approval")]
-    DuplicateApproval,
+    /// Deposit a note and mint a non-transferable receipt for it to
+    /// `receipt_owner`'s Token-2022 associated token account
+    /// The receipt mint is a PDA of the note index, has supply 1 and its
+    /// mint authority is revoked once the receipt is minted
+    pub fn shield_deposit_with_receipt(
+        ctx: Context<ShieldDepositWithReceipt>,
+        note_commitment: [u8; 32],
+        encrypted_note: [u8; 64],
+        range_proof: Vec<u8>,
+    ) -> Result<()> {
+        instructions::shielded::shield_deposit_with_receipt(ctx, note_commitment, encrypted_note, range_proof)
+    }
 
-    #[msg("Insufficient approvals to execute")]
-    InsufficientApprovals,
+    // ============================================
+    // CPI DEPOSITS - Composable Entry Point
+    // ============================================
 
-    // Private Staking Errors (Legacy)
-    #[msg("Stake amount too small")]
-    StakeTooSmall,
+    /// Allow `caller_program` to deposit into a shielded pool through CPI
+    /// (pool authority only)
+    pub fn allow_deposit_caller(ctx: Context<AllowDepositCaller>) -> Result<()> {
+        instructions::shielded::allow_deposit_caller(ctx)
+    }
 
-    #[msg("Invalid reward rate")]
-    InvalidRewardRate,
+    /// Remove a caller program from a pool's CPI deposit allowlist (pool
+    /// authority only)
+    pub fn revoke_deposit_caller(ctx: Context<RevokeDepositCaller>) -> Result<()> {
+        instructions::shielded::revoke_deposit_caller(ctx)
+    }
 
-    #[msg("Invalid lockup period (must be 1-52 epochs)")]
-    InvalidLockupPeriod,
+    /// Deposit a note on behalf of an allowlisted caller program
+    /// Must be invoked through CPI, signed by the caller's depositor PDA
+    /// (seed `CPI_DEPOSITOR_SEED` under the caller's program ID), which
+    /// also pays the note rent; events name the caller program
+    pub fn shield_deposit_cpi(
+        ctx: Context<ShieldDepositCpi>,
+        note_commitment: [u8; 32],
+        encrypted_note: [u8; 64],
+        range_proof: Vec<u8>,
+    ) -> Result<()> {
+        instructions::shielded::shield_deposit_cpi(ctx, note_commitment, encrypted_note, range_proof)
+    }
 
-    #[msg("Stake pool is not active")]
-    PoolNotActive,
+    // ============================================
+    // REFERRALS - Anonymity-Set Incentives
+    // ============================================
 
-    #[msg("Stake is not active")]
-    StakeNotActive,
+    /// Create and fund a pool's referral program (pool authority only)
+    /// A referred deposit must move `min_deposit_lamports` into the pool
+    /// vault, and a referrer is credited with at most
+    /// `max_deposits_per_referrer` deposits
+    pub fn create_referral_program(
+        ctx: Context<CreateReferralProgram>,
+        reward_per_deposit: u64,
+        min_deposit_lamports: u64,
+        max_deposits_per_referrer: u64,
+        funding_lamports: u64,
+    ) -> Result<()> {
+        instructions::referral::create_referral_program(
+            ctx,
+            reward_per_deposit,
+            min_deposit_lamports,
+            max_deposits_per_referrer,
+            funding_lamports,
+        )
+    }
 
-    #[msg("Stake is still locked")]
-    StakeLocked,
+    /// Change the deposit minimum and per-referrer cap (program authority only)
+    pub fn configure_referral_program(
+        ctx: Context<ConfigureReferralProgram>,
+        min_deposit_lamports: u64,
+        max_deposits_per_referrer: u64,
+    ) -> Result<()> {
+        instructions::referral::configure_referral_program(ctx, min_deposit_lamports, max_deposits_per_referrer)
+    }
 
-    #[msg("Invalid stake reveal - commitment mismatch")]
-    InvalidStakeReveal,
+    /// Top up a referral program's rewards (anyone can sponsor)
+    pub fn fund_referral_program(ctx: Context<FundReferralProgram>, amount: u64) -> Result<()> {
+        instructions::referral::fund_referral_program(ctx, amount)
+    }
 
-    #[msg("Invalid reward proof")]
-    InvalidRewardProof,
+    /// Register a referrer under a commitment to its secret
+    /// (anyone can pay for the record)
+    pub fn register_referrer(ctx: Context<RegisterReferrer>, referral_commitment: [u8; 32]) -> Result<()> {
+        instructions::referral::register_referrer(ctx, referral_commitment)
+    }
 
-    #[msg("Insufficient pool funds")]
-    InsufficientPoolFunds,
+    /// Pay a referrer for its unclaimed referred deposits
+    /// The proof shows knowledge of the secret behind the referral
+    /// commitment and binds the payout to `recipient`; an underfunded
+    /// program pays as many whole deposits as it can afford
+    pub fn claim_referral_rewards(
+        ctx: Context<ClaimReferralRewards>,
+        referral_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::referral::claim_referral_rewards(ctx, referral_proof, circuit_id)
+    }
 
     // ============================================
-    // SHIELDED POOL ERRORS - True Privacy
+    // NOTE EXPIRY - Sweeping Stranded Funds
     // ============================================
 
-    #[msg("Invalid proof structure - expected Groth16 format (256 bytes)")]
-    InvalidProofStructure,
+    /// Set the pool's note expiry and sweep treasury (pool authority only)
+    /// An expiry of 0 disables sweeps
+    pub fn configure_note_expiry(
+        ctx: Context<ConfigureNoteExpiry>,
+        note_expiry_seconds: i64,
+        treasury: Pubkey,
+    ) -> Result<()> {
+        instructions::shielded::configure_note_expiry(ctx, note_expiry_seconds, treasury)
+    }
 
-    #[msg("Invalid proof point - not a valid field element")]
-    InvalidProofPoint,
+    /// Announce a sweep of every note below `through_index`
+    /// The last note of the range must already be expired; notes are
+    /// inserted in time order, so every earlier note is too
+    pub fn announce_note_sweep(ctx: Context<AnnounceNoteSweep>, through_index: u32) -> Result<()> {
+        instructions::shielded::announce_note_sweep(ctx, through_index)
+    }
 
-    #[msg("Invalid public signal - not a valid field element")]
-    InvalidPublicSignal,
+    /// Withdraw an announced sweep before it executes
+    pub fn cancel_note_sweep(ctx: Context<ConfigureNoteExpiry>) -> Result<()> {
+        instructions::shielded::cancel_note_sweep(ctx)
+    }
 
-    #[msg("Commitment mismatch - proof is not for this wallet")]
-    CommitmentMismatch,
+    /// Execute an announced sweep once its notice period has passed
+    /// The proof shows `amount` is the total value of the notes in the
+    /// range that are still unspent given the current nullifier set
+    pub fn execute_note_sweep(
+        ctx: Context<ExecuteNoteSweep>,
+        amount: u64,
+        sweep_proof: Vec<u8>,
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::shielded::execute_note_sweep(ctx, amount, sweep_proof, circuit_id)
+    }
 
-    #[msg("Invalid proof hash")]
-    InvalidProofHash,
+    // ============================================
+    // AUDIT LOG - Hash-Chained Admin Actions
+    // ============================================
 
-    #[msg("Shielded pool is full")]
-    PoolFull,
+    /// Start an audit log for a shielded pool or wallet account
+    /// (anyone can pay for it)
+    pub fn create_audit_log(ctx: Context<CreateAuditLog>) -> Result<()> {
+        instructions::audit::create_audit_log(ctx)
+    }
 
-    #[msg("Invalid range proof - amount out of valid range")]
-    InvalidRangeProof,
+    // ============================================
+    // WATCHTOWER - Pollable Alerts for Off-Chain Notifiers
+    // ============================================
 
-    #[msg("Nullifier has already been used - double-spend attempt")]
-    NullifierAlreadyUsed,
+    /// Register an alert feed for a wallet (its owner), multisig or
+    /// shielded pool (their creator), addressed to `alert_key`
+    /// Recovery starts, multisig proposals moving at least `large_amount`
+    /// (or doing anything but a plain transfer) and pool pauses write an
+    /// alert to it when the watchtower is passed to those instructions
+    pub fn register_watchtower(
+        ctx: Context<RegisterWatchtower>,
+        alert_key: Pubkey,
+        large_amount: u64,
+    ) -> Result<()> {
+        instructions::watchtower::register_watchtower(ctx, alert_key, large_amount)
+    }
 
-    #[msg("Invalid Merkle proof - note not in tree")]
-    InvalidMerkleProof,
+    /// Change a watchtower's alert key and large-proposal threshold
+    /// (registrant only)
+    pub fn update_watchtower(
+        ctx: Context<UpdateWatchtower>,
+        alert_key: Pubkey,
+        large_amount: u64,
+    ) -> Result<()> {
+        instructions::watchtower::update_watchtower(ctx, alert_key, large_amount)
+    }
 
-    #[msg("Invalid withdrawal proof")]
-    InvalidWithdrawalProof,
+    // ============================================
+    // CIRCUIT REGISTRY - Trusted Setup Artifacts
+    // ============================================
 
-    #[msg("Invalid nullifier derivation")]
-    InvalidNullifier,
-}
+    /// Create a circuit registry for the signing authority
+    pub fn create_circuit_registry(
+        ctx: Context<CreateCircuitRegistry>,
+        ptau_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::circuits::create_circuit_registry(ctx, ptau_hash)
+    }
 
-// ============================================
-// HELPER FUNCTIONS - Cryptographic Operations
-// ============================================
+    /// Record the audited verifying-key hash of one circuit (write-once)
+    /// Proofs for the circuit fail until the compiled-in key matches it
+    pub fn register_circuit(
+        ctx: Context<RegisterCircuit>,
+        circuit: Circuit,
+        vk_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::circuits::register_circuit(ctx, circuit, vk_hash)
+    }
 
-/// Hash proof data using SHA-256
-fn hash_proof(proof_data: &[u8]) -> [u8; 32] {
-    hash(proof_data).to_bytes()
-}
+    // ============================================
+    // CRANK REGISTRY - Permissionless Maintenance
+    // ============================================
 
-/// Hash public signals for event logging
-fn hash_public_signals(signals: &[[u8; 32]]) -> [u8; 32] {
-    let mut data = Vec::new();
-    for signal in signals {
-        data.extend_from_slice(signal);
+    /// Register and fund a crank task for a maintenance target
+    pub fn register_crank_task(
+        ctx: Context<RegisterCrankTask>,
+        kind: CrankTaskKind,
+        target: Pubkey,
+        tip_lamports: u64,
+        min_interval: i64,
+        funding_lamports: u64,
+    ) -> Result<()> {
+        instructions::crank::register_crank_task(ctx, kind, target, tip_lamports, min_interval, funding_lamports)
     }
-    hash(&data).to_bytes()
-}
 
-/// Verify a value is a valid BN128 field element (< modulus)
-fn verify_field_element(value: &[u8]) -> bool {
-    if value.len() != 32 {
-        return false;
-    }
-    // Compare with BN128 modulus (big-endian comparison)
-    for i in 0..32 {
-        if value[i] < BN128_MODULUS[i] {
-            return true;
-        } else if value[i] > BN128_MODULUS[i] {
-            return false;
-        }
+    /// Top up a crank task's tips (anyone can sponsor)
+    pub fn fund_crank_task(ctx: Context<FundCrankTask>, amount: u64) -> Result<()> {
+        instructions::crank::fund_crank_task(ctx, amount)
     }
-    false // Equal to modulus is not valid
-}
 
-/// Compute proof hash for verification event
-fn compute_proof_hash(proof_data: &[u8], public_signals: &[[u8; 32]]) -> [u8; 32] {
-    let mut data = Vec::new();
-    data.extend_from_slice(proof_data);
-    for signal in public_signals {
-        data.extend_from_slice(signal);
+    /// Close a crank task, returning unspent tips and rent to its funder
+    pub fn close_crank_task(ctx: Context<CloseCrankTask>) -> Result<()> {
+        instructions::crank::close_crank_task(ctx)
     }
-    hash(&data).to_bytes()
-}
 
-/// Verify range proof (Bulletproof style)
-/// In production: use bulletproofs-solana library
-/// For demo: verify proof structure and basic properties
-fn verify_range_proof(commitment: &[u8; 32], proof: &[u8]) -> bool {
-    // Bulletproof structure validation
-    // A valid range proof should have:
-    // - Non-zero commitment
-    // - Proof length >= 64 bytes (minimal bulletproof)
-    // - Non-trivial proof data
+    // ============================================
+    // TREE CHECKPOINTS - Light Client Bootstrap
+    // ============================================
 
-    if commitment == &[0u8; 32] {
-        return false;
-    }
-    if proof.len() < 64 {
-        return false;
+    /// Create the tree checkpoint for a shielded pool (permissionless)
+    pub fn create_tree_checkpoint(ctx: Context<CreateTreeCheckpoint>) -> Result<()> {
+        instructions::shielded::create_tree_checkpoint(ctx)
     }
 
-    // Verify proof has proper structure (first 32 bytes should be non-zero)
-    let mut first_32_sum: u32 = 0;
-    for i in 0..32.min(proof.len()) {
-        first_32_sum += proof[i] as u32;
-    }
-    if first_32_sum == 0 {
-        return false;
+    /// Advance a pool's tree checkpoint over newly inserted notes (permissionless crank)
+    /// remaining_accounts: ShieldedNote accounts from index `leaf_count` onwards
+    pub fn checkpoint_tree<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CheckpointTree<'info>>,
+    ) -> Result<()> {
+        instructions::shielded::checkpoint_tree(ctx)
     }
 
-    // Compute verification hash
-    let mut data = Vec::new();
-    data.extend_from_slice(commitment);
-    data.extend_from_slice(proof);
-    let h = hash(&data);
-
-    // For demo: accept if hash has certain properties
-    // In production: full bulletproof verification
-    h.to_bytes()[0] != 0 || h.to_bytes()[1] != 0
-}
+    // ============================================
+    // NULLIFIER FILTERS - Spent-Note Pre-checks
+    // ============================================
 
-/// Check if a nullifier has been used in the pool
-fn is_nullifier_used(pool: &ShieldedPool, nullifier: &[u8; 32]) -> bool {
-    // In production: query nullifier account by PDA
-    // For demo: nullifier accounts are separate, so this always returns false
-    // The actual check happens via account existence (init constraint will fail)
-    false
-}
+    /// Create the nullifier filter of a shielded pool
+    pub fn create_nullifier_filter(ctx: Context<CreateNullifierFilter>) -> Result<()> {
+        instructions::shielded::create_nullifier_filter(ctx)
+    }
 
-/// Insert a note into the Merkle tree and return new root
-fn insert_note_to_merkle_tree(
-    current_root: &[u8; 32],
-    note_commitment: &[u8; 32],
-    note_index: u32,
-) -> [u8; 32] {
-    // Simplified Merkle tree update for demo
-    // In production: use proper incremental Merkle tree (IMT) library
-
-    let mut data = Vec::new();
-    data.extend_from_slice(current_root);
-    data.extend_from_slice(note_commitment);
-    data.extend_from_slice(&note_index.to_le_bytes());
-
-    hash(&data).to_bytes()
-}
+    // ============================================
+    // PROTOCOL STATS - Cross-Pool Totals
+    // ============================================
 
-/// Verify Merkle proof for note membership
-fn verify_merkle_proof(
-    root: &[u8; 32],
-    proof: &[[u8; 32]; MERKLE_TREE_DEPTH],
-    path_indices: u8,
-    leaf_hash: &[u8; 32],
-) -> bool {
-    // Compute root from leaf and proof
-    let mut current_hash = *leaf_hash;
-
-    for i in 0..MERKLE_TREE_DEPTH {
-        let sibling = &proof[i];
-        let is_right = (path_indices >> i) & 1 == 1;
-
-        let mut combined = Vec::new();
-        if is_right {
-            combined.extend_from_slice(sibling);
-            combined.extend_from_slice(&current_hash);
-        } else {
-            combined.extend_from_slice(&current_hash);
-            combined.extend_from_slice(sibling);
-        }
+    /// Create the protocol-wide stats account (once, by anyone)
+    pub fn create_protocol_stats(ctx: Context<CreateProtocolStats>) -> Result<()> {
+        instructions::shielded::create_protocol_stats(ctx)
+    }
 
-        current_hash = hash(&combined).to_bytes();
+    /// Count an existing pool in the protocol stats
+    /// Its pool instructions must pass the stats account from then on
+    pub fn register_pool_stats(ctx: Context<RegisterPoolStats>) -> Result<()> {
+        instructions::shielded::register_pool_stats(ctx)
     }
 
-    current_hash == *root
-}
+    // ============================================
+    // LEGACY STAKING (Deprecated - kept for compatibility)
+    // These functions have privacy issues - use shielded versions above
 
-/// Verify withdrawal proof (Groth16 style)
-fn verify_withdrawal_proof(
-    nullifier: &[u8; 32],
-    output_commitment: &[u8; 32],
-    merkle_root: &[u8; 32],
-    proof: &[u8],
-) -> bool {
-    // Verify proof structure
-    if proof.len() < 256 {
-        return false;
+    /// Create a private stake pool (DEPRECATED - use create_shielded_pool)
+    #[deprecated(note = "Use create_shielded_pool for true amount privacy")]
+    pub fn create_stake_pool(
+        ctx: Context<CreateStakePool>,
+        pool_id: [u8; 32],
+        min_stake_lamports: u64,
+        reward_rate_bps: u16,
+        lockup_epochs: u8,
+    ) -> Result<()> {
+        instructions::staking::create_stake_pool(ctx, pool_id, min_stake_lamports, reward_rate_bps, lockup_epochs)
     }
 
-    // Extract proof components
-    let pi_a = &proof[0..64];
-    let pi_b = &proof[64..192];
-    let pi_c = &proof[192..256];
+    /// Stake with commitment (DEPRECATED - has amount visibility issue)
+    #[deprecated(note = "Use shield_deposit for true amount privacy")]
+    pub fn stake_private(
+        ctx: Context<StakePrivate>,
+        stake_commitment: [u8; 32],
+        validator_commitment: [u8; 32],
+        _amount_commitment: [u8; 32], // Changed: now accepts commitment, not plaintext
+    ) -> Result<()> {
+        instructions::staking::stake_private(ctx, stake_commitment, validator_commitment, _amount_commitment)
+    }
 
-    // Verify all components are valid field elements
-    if !verify_field_element(&pi_a[0..32]) || !verify_field_element(&pi_a[32..64]) {
-        return false;
+    /// Unstake with ZK proof (DEPRECATED - use shield_withdraw)
+    #[deprecated(note = "Use shield_withdraw for true amount privacy")]
+    pub fn unstake(
+        ctx: Context<Unstake>,
+        nullifier: [u8; 32],          // Changed: now uses nullifier
+        withdrawal_proof: Vec<u8>,     // Changed: ZK proof instead of plaintext reveal
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::staking::unstake(ctx, nullifier, withdrawal_proof, circuit_id)
     }
-    if !verify_field_element(&pi_c[0..32]) || !verify_field_element(&pi_c[32..64]) {
-        return false;
+
+    /// Claim rewards with proof (DEPRECATED - use claim_shielded_rewards)
+    #[deprecated(note = "Use claim_shielded_rewards for true amount privacy")]
+    pub fn claim_rewards(
+        ctx: Context<ClaimRewards>,
+        reward_proof: Vec<u8>,  // Changed: full ZK proof, not just hash
+        circuit_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::staking::claim_rewards(ctx, reward_proof, circuit_id)
     }
 
-    // Compute verification hash
-    let mut data = Vec::new();
-    data.extend_from_slice(nullifier);
-    data.extend_from_slice(output_commitment);
-    data.extend_from_slice(merkle_root);
-    data.extend_from_slice(proof);
+    // ============================================
+    // VIEW INSTRUCTIONS - Read-only getters
+    // ============================================
 
-    let h = hash(&data);
+    /// Snapshot of a shielded pool's public state
+    pub fn get_pool_state(ctx: Context<GetPoolState>) -> Result<PoolStateSnapshot> {
+        instructions::views::get_pool_state(ctx)
+    }
 
-    // For demo: accept valid structure
-    // In production: full Groth16 pairing check
-    h.to_bytes()[0] != 0xFF  // Accept if not all 1s
-}
+    /// Pool count, notes and nullifiers across the pools counted in the
+    /// protocol stats
+    pub fn get_protocol_stats(ctx: Context<GetProtocolStats>) -> Result<ProtocolStatsSnapshot> {
+        instructions::views::get_protocol_stats(ctx)
+    }
 
-/// Verify reward calculation proof
-fn verify_reward_proof(
-    stake_nullifier: &[u8; 32],
-    new_note_commitment: &[u8; 32],
-    reward_rate_bps: u16,
-    current_time: i64,
-    proof: &[u8],
-) -> bool {
-    // Verify proof structure
-    if proof.len() < 256 {
-        return false;
-    }
-
-    // Compute verification hash
-    let mut data = Vec::new();
-    data.extend_from_slice(stake_nullifier);
-    data.extend_from_slice(new_note_commitment);
-    data.extend_from_slice(&reward_rate_bps.to_le_bytes());
-    data.extend_from_slice(&current_time.to_le_bytes());
-    data.extend_from_slice(proof);
-
-    let h = hash(&data);
-
-    // For demo: accept valid structure
-    // In production: full ZK verification of reward calculation
-    h.to_bytes()[0] != 0xFF
-}
+    /// Current vote tally of a proposal
+    /// Counts only include revealed votes until the proposal is finalized
+    pub fn get_proposal_tally(ctx: Context<GetProposalTally>) -> Result<ProposalTally> {
+        instructions::views::get_proposal_tally(ctx)
+    }
 
-/// Verify nullifier derivation from stake commitment
-fn verify_nullifier_derivation(
-    stake_commitment: &[u8; 32],
-    nullifier: &[u8; 32],
-    proof: &[u8],
-) -> bool {
-    if proof.len() < 256 {
-        return false;
+    /// Current note-commitment Merkle root of a shielded pool
+    pub fn get_merkle_root(ctx: Context<GetPoolState>) -> Result<MerkleRootSnapshot> {
+        instructions::views::get_merkle_root(ctx)
     }
 
-    // Compute verification
-    let mut data = Vec::new();
-    data.extend_from_slice(stake_commitment);
-    data.extend_from_slice(nullifier);
-    data.extend_from_slice(proof);
+    /// Anonymity-set metrics of a shielded pool, rolled to the current epoch
+    /// Wallets should warn before withdrawing from a pool with few recent
+    /// deposits or unspent notes
+    pub fn get_anonymity_metrics(ctx: Context<GetPoolState>) -> Result<AnonymityMetrics> {
+        instructions::views::get_anonymity_metrics(ctx)
+    }
 
-    let h = hash(&data);
-    h.to_bytes()[0] != 0xFF
-}
+    /// Latest reserve attestation of a pool and whether it is still current
+    pub fn get_reserve_status(ctx: Context<GetReserveStatus>) -> Result<ReserveStatus> {
+        instructions::views::get_reserve_status(ctx)
+    }
 
-/// Compute reward commitment from proof
-fn compute_reward_commitment(proof: &[u8]) -> [u8; 32] {
-    if proof.len() >= 32 {
-        let mut result = [0u8; 32];
-        result.copy_from_slice(&proof[0..32]);
-        result
-    } else {
-        hash(proof).to_bytes()
+    /// Run the checks of `shield_withdraw` without spending the note
+    /// Nothing is written and no nullifier record is created, so relayers
+    /// can simulate a job for free before paying for the real withdrawal.
+    /// Failed checks are reported in the result instead of as errors,
+    /// except a rejection by the pool's screener, which fails the call
+    ///
+    /// Pools with a withdrawal screener CPI it with (withdrawer, nullifier)
+    /// as `shield_withdraw` does; remaining accounts are forwarded to it
+    pub fn verify_withdrawal_dry_run<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyWithdrawalDryRun<'info>>,
+        nullifier: [u8; 32],
+        merkle_proof: [[u8; 32]; 8],
+        merkle_path_indices: u8,
+        withdrawal_proof: Vec<u8>,
+        output_commitment: [u8; 32],
+        circuit_id: [u8; 32],
+    ) -> Result<WithdrawalDryRun> {
+        instructions::views::verify_withdrawal_dry_run(ctx, nullifier, merkle_proof, merkle_path_indices, withdrawal_proof, output_commitment, circuit_id)
     }
-}
 
-// ============================================
-// LEGACY HELPER FUNCTIONS (for backwards compatibility)
-// ============================================
-
-/// Compute vote commitment: hash(vote_choice || secret || voter)
-fn compute_vote_commitment(vote_choice: bool, secret: &[u8; 32], voter: &Pubkey) -> [u8; 32] {
-    let mut data = Vec::with_capacity(1 + 32 + 32);
-    data.push(if vote_choice { 1 } else { 0 });
-    data.extend_from_slice(secret);
-    data.extend_from_slice(voter.as_ref());
-    hash(&data).to_bytes()
-}
+    // ============================================
+    // ACCOUNT MIGRATION
+    // ============================================
 
-/// Compute stake commitment: hash(amount || validator_commitment || staker || secret)
-fn compute_stake_commitment(
-    amount_lamports: u64,
-    validator_commitment: &[u8; 32],
-    staker: &Pubkey,
-    secret: &[u8; 32],
-) -> [u8; 32] {
-    let mut data = Vec::with_capacity(8 + 32 + 32 + 32);
-    data.extend_from_slice(&amount_lamports.to_le_bytes());
-    data.extend_from_slice(validator_commitment);
-    data.extend_from_slice(staker.as_ref());
-    data.extend_from_slice(secret);
-    hash(&data).to_bytes()
-}
+    /// Upgrade any program account to its current layout version in place
+    /// Permissionless: it only grows the account (or repacks a multisig or
+    /// proposal still in its pre-zero-copy Borsh layout) and fills new
+    /// fields with defaults, and the payer covers any extra rent
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        instructions::migration::migrate_account(ctx)
+    }
 
-/// Verify reward claim proof
-fn verify_reward_claim_proof(
-    stake_commitment: &[u8; 32],
-    reward_rate_bps: u16,
-    staked_at: i64,
-    current_time: i64,
-    proof: &[u8],
-) -> bool {
-    if proof.len() < 256 {
-        return false;
-    }
-
-    let mut data = Vec::new();
-    data.extend_from_slice(stake_commitment);
-    data.extend_from_slice(&reward_rate_bps.to_le_bytes());
-    data.extend_from_slice(&staked_at.to_le_bytes());
-    data.extend_from_slice(&current_time.to_le_bytes());
-    data.extend_from_slice(proof);
-
-    let h = hash(&data);
-    h.to_bytes()[0] != 0xFF
+    // ============================================
+    // TEST CLOCK (test-clock feature only)
+    // ============================================
+
+    /// Pin the time every handler sees when the `MockClock` is passed as
+    /// their last remaining account (see `clock::now`)
+    /// Unauthenticated by design: the instruction does not exist outside
+    /// `test-clock` builds
+    #[cfg(feature = "test-clock")]
+    pub fn set_mock_clock(ctx: Context<SetMockClock>, unix_timestamp: i64, slot: u64) -> Result<()> {
+        instructions::testing::set_mock_clock(ctx, unix_timestamp, slot)
+    }
 }
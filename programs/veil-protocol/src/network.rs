@@ -0,0 +1,59 @@
+//! Deployment environment
+//!
+//! The `localnet`, `devnet` and `mainnet` features select the program ID
+//! (see `declare_id!` in lib.rs) and the per-environment settings below, so
+//! one crate builds every deployment:
+//!
+//! ```text
+//! VEIL_CIRCUIT_REGISTRY_AUTHORITY=<registry authority> anchor build -- --features mainnet
+//! ```
+//!
+//! With none of them enabled the crate builds for devnet.
+
+use anchor_lang::prelude::*;
+
+#[cfg(any(
+    all(feature = "localnet", feature = "devnet"),
+    all(feature = "localnet", feature = "mainnet"),
+    all(feature = "devnet", feature = "mainnet"),
+))]
+compile_error!("enable at most one of the `localnet`, `devnet` and `mainnet` features");
+
+#[cfg(all(feature = "mainnet", any(feature = "debug-logs", feature = "test-clock")))]
+compile_error!("`debug-logs` and `test-clock` must not be enabled in mainnet builds");
+
+/// Cluster this build targets
+#[cfg(feature = "localnet")]
+pub const CLUSTER: &str = "localnet";
+#[cfg(feature = "mainnet")]
+pub const CLUSTER: &str = "mainnet-beta";
+#[cfg(not(any(feature = "localnet", feature = "mainnet")))]
+pub const CLUSTER: &str = "devnet";
+
+/// Authority of the circuit registry that proof instructions check
+/// verifying keys against, read from `VEIL_CIRCUIT_REGISTRY_AUTHORITY` at
+/// build time. Mainnet builds fail without it; on localnet and devnet an
+/// unset authority accepts any registry the caller passes, so tests can
+/// create their own
+#[cfg(feature = "mainnet")]
+pub const CIRCUIT_REGISTRY_AUTHORITY: Option<Pubkey> = Some(Pubkey::from_str_const(env!(
+    "VEIL_CIRCUIT_REGISTRY_AUTHORITY",
+    "mainnet builds must pin the audited circuit registry: set VEIL_CIRCUIT_REGISTRY_AUTHORITY"
+)));
+#[cfg(not(feature = "mainnet"))]
+pub const CIRCUIT_REGISTRY_AUTHORITY: Option<Pubkey> = match option_env!("VEIL_CIRCUIT_REGISTRY_AUTHORITY") {
+    Some(authority) => Some(Pubkey::from_str_const(authority)),
+    None => None,
+};
+
+/// Relayer unbonding delay; short on localnet so exits can be exercised
+#[cfg(feature = "localnet")]
+pub const RELAYER_UNBONDING_SECONDS: i64 = 60;
+#[cfg(not(feature = "localnet"))]
+pub const RELAYER_UNBONDING_SECONDS: i64 = 7 * 86_400;
+
+/// Whether proof instructions may check verifying keys against the
+/// registry maintained by `authority`
+pub fn trusts_circuit_registry(authority: &Pubkey) -> bool {
+    CIRCUIT_REGISTRY_AUTHORITY.is_none_or(|trusted| trusted == *authority)
+}
@@ -0,0 +1,460 @@
+//! PDA seeds and address derivation
+//!
+//! Account contexts build their seeds from the `*_SEED` constants below, and
+//! SDKs, tests and CPI callers should derive addresses through these helpers
+//! rather than repeating seed literals. Each helper returns the address and
+//! its canonical bump, like `Pubkey::find_program_address`.
+
+use anchor_lang::prelude::*;
+
+pub const WALLET_SEED: &[u8] = b"wallet";
+pub const ATTESTATION_SEED: &[u8] = b"attestation";
+pub const KV_ATTESTATION_SEED: &[u8] = b"kv_attestation";
+pub const ALIAS_SEED: &[u8] = b"alias";
+pub const WALLET_BACKUP_SEED: &[u8] = b"wallet_backup";
+pub const WALLET_REGISTRY_SEED: &[u8] = b"wallet_registry";
+pub const WALLET_ENROLLMENT_SEED: &[u8] = b"wallet_enrollment";
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const VOTE_SEED: &[u8] = b"vote";
+pub const PROPOSAL_TEMPLATE_SEED: &[u8] = b"proposal_template";
+pub const CONVICTION_PROPOSAL_SEED: &[u8] = b"conviction_proposal";
+pub const CONVICTION_STAKE_SEED: &[u8] = b"conviction_stake";
+pub const QUADRATIC_PROPOSAL_SEED: &[u8] = b"quadratic_proposal";
+pub const QUADRATIC_VOTE_SEED: &[u8] = b"quadratic_vote";
+pub const STAKE_VOTE_SEED: &[u8] = b"stake_vote";
+pub const POOL_UPDATE_SEED: &[u8] = b"pool_update";
+pub const POOL_GOVERNANCE_SEED: &[u8] = b"pool_governance";
+pub const VOTER_WEIGHT_SEED: &[u8] = b"voter_weight";
+pub const REALM_REGISTRAR_SEED: &[u8] = b"realm_registrar";
+pub const REALM_SNAPSHOT_SEED: &[u8] = b"realm_snapshot";
+pub const REALM_VOTER_WEIGHT_SEED: &[u8] = b"realm_voter_weight";
+pub const REALM_MAX_VOTER_WEIGHT_SEED: &[u8] = b"realm_max_voter_weight";
+pub const REALM_WEIGHT_NULLIFIER_SEED: &[u8] = b"realm_weight_nullifier";
+pub const BEACON_ROUND_SEED: &[u8] = b"beacon_round";
+pub const BEACON_ENTRY_SEED: &[u8] = b"beacon_entry";
+pub const MULTISIG_SEED: &[u8] = b"multisig";
+pub const MULTISIG_PROPOSAL_SEED: &[u8] = b"ms_proposal";
+pub const MULTISIG_ACTION_SEED: &[u8] = b"ms_action";
+pub const MULTISIG_ENCRYPTED_ACTION_SEED: &[u8] = b"ms_encrypted_action";
+pub const MULTISIG_AUTHORITY_SEED: &[u8] = b"multisig_authority";
+pub const TREASURY_ATTESTATION_SEED: &[u8] = b"treasury_attestation";
+pub const SIGNER_HEARTBEATS_SEED: &[u8] = b"signer_heartbeats";
+pub const RLN_GROUP_SEED: &[u8] = b"rln_group";
+pub const RLN_MEMBER_SEED: &[u8] = b"rln_member";
+pub const RLN_NULLIFIER_SEED: &[u8] = b"rln_nullifier";
+pub const SHIELDED_POOL_SEED: &[u8] = b"shielded_pool";
+pub const SHIELDED_VAULT_SEED: &[u8] = b"shielded_vault";
+pub const NOTE_SEED: &[u8] = b"note";
+pub const NULLIFIER_SEED: &[u8] = b"nullifier";
+pub const TREE_CHECKPOINT_SEED: &[u8] = b"tree_checkpoint";
+pub const NULLIFIER_FILTER_SEED: &[u8] = b"nullifier_filter";
+pub const PROTOCOL_STATS_SEED: &[u8] = b"protocol_stats";
+pub const HOLDING_SEED: &[u8] = b"holding";
+pub const CAMPAIGN_SEED: &[u8] = b"campaign";
+pub const CLAIMABLE_NOTE_SEED: &[u8] = b"claimable_note";
+pub const ESCROW_SEED: &[u8] = b"escrow";
+pub const SUBSCRIPTION_SEED: &[u8] = b"subscription";
+pub const RESERVE_ATTESTATION_SEED: &[u8] = b"reserve";
+pub const RELAYER_REGISTRY_SEED: &[u8] = b"relayer_registry";
+pub const RELAYER_SEED: &[u8] = b"relayer";
+pub const DEPOSIT_RECEIPTS_SEED: &[u8] = b"deposit_receipts";
+pub const DEPOSIT_CALLER_SEED: &[u8] = b"deposit_caller";
+/// Seed a caller program derives its depositor PDA from, under its own ID
+pub const CPI_DEPOSITOR_SEED: &[u8] = b"veil_depositor";
+pub const REFERRAL_PROGRAM_SEED: &[u8] = b"referral_program";
+pub const REFERRAL_SEED: &[u8] = b"referral";
+pub const RECEIPT_MINT_SEED: &[u8] = b"receipt_mint";
+pub const CIRCUIT_REGISTRY_SEED: &[u8] = b"circuit_registry";
+pub const AUDIT_LOG_SEED: &[u8] = b"audit_log";
+pub const WATCHTOWER_SEED: &[u8] = b"watchtower";
+pub const STAKE_POOL_SEED: &[u8] = b"stake_pool";
+pub const STAKE_VAULT_SEED: &[u8] = b"stake_vault";
+pub const STAKE_RECORD_SEED: &[u8] = b"stake_record";
+pub const PROOF_AUTHORITY_SEED: &[u8] = b"proof_authority";
+pub const CRANK_TASK_SEED: &[u8] = b"crank_task";
+pub const MOCK_CLOCK_SEED: &[u8] = b"mock_clock";
+
+fn derive(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &crate::ID)
+}
+
+// ============================================
+// WALLETS
+// ============================================
+
+/// Commitment wallet created by `owner`; the address stays the same after
+/// a recovery hands the wallet to another key
+pub fn wallet_address(owner: &Pubkey) -> (Pubkey, u8) {
+    derive(&[WALLET_SEED, owner.as_ref()])
+}
+
+/// Wallet attestation keyed by its attestation nullifier
+pub fn attestation_address(nullifier: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[ATTESTATION_SEED, nullifier])
+}
+
+/// Singleton tree of enrolled wallet commitments
+pub fn wallet_registry_address() -> (Pubkey, u8) {
+    derive(&[WALLET_REGISTRY_SEED])
+}
+
+/// Enrollment marker of a wallet commitment in the wallet registry
+pub fn wallet_enrollment_address(commitment: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[WALLET_ENROLLMENT_SEED, commitment])
+}
+
+/// Key-value attestation on `wallet` under an application-defined key
+pub fn kv_attestation_address(wallet: &Pubkey, key: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[KV_ATTESTATION_SEED, wallet.as_ref(), key])
+}
+
+/// Private alias keyed by H(DOMAIN_ALIAS, name || salt)
+pub fn alias_address(alias_hash: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[ALIAS_SEED, alias_hash])
+}
+
+/// Version `backup_version` of `wallet`'s anchored backup
+pub fn wallet_backup_address(wallet: &Pubkey, backup_version: u64) -> (Pubkey, u8) {
+    derive(&[WALLET_BACKUP_SEED, wallet.as_ref(), &backup_version.to_le_bytes()])
+}
+
+/// Signer PDA that routed proof callbacks are invoked with
+pub fn proof_authority_address() -> (Pubkey, u8) {
+    derive(&[PROOF_AUTHORITY_SEED])
+}
+
+// ============================================
+// VOTING
+// ============================================
+
+pub fn proposal_address(creator: &Pubkey, proposal_id: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[PROPOSAL_SEED, creator.as_ref(), proposal_id])
+}
+
+pub fn vote_record_address(proposal: &Pubkey, voter: &Pubkey) -> (Pubkey, u8) {
+    derive(&[VOTE_SEED, proposal.as_ref(), voter.as_ref()])
+}
+
+/// Governance parameter template published by `creator`
+pub fn proposal_template_address(creator: &Pubkey, template_id: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[PROPOSAL_TEMPLATE_SEED, creator.as_ref(), template_id])
+}
+
+/// Conviction voting proposal
+pub fn conviction_proposal_address(creator: &Pubkey, proposal_id: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[CONVICTION_PROPOSAL_SEED, creator.as_ref(), proposal_id])
+}
+
+/// Shielded stake committed to a conviction proposal, keyed by its nullifier
+pub fn conviction_stake_address(proposal: &Pubkey, stake_nullifier: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[CONVICTION_STAKE_SEED, proposal.as_ref(), stake_nullifier])
+}
+
+/// Quadratic voting proposal
+pub fn quadratic_proposal_address(creator: &Pubkey, proposal_id: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[QUADRATIC_PROPOSAL_SEED, creator.as_ref(), proposal_id])
+}
+
+/// Quadratic vote keyed by its vote nullifier
+pub fn quadratic_vote_address(proposal: &Pubkey, vote_nullifier: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[QUADRATIC_VOTE_SEED, proposal.as_ref(), vote_nullifier])
+}
+
+/// Stake-weighted vote keyed by its vote nullifier
+pub fn stake_vote_address(proposal: &Pubkey, weight_nullifier: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[STAKE_VOTE_SEED, proposal.as_ref(), weight_nullifier])
+}
+
+/// Pool parameter update decided by `proposal`
+pub fn pool_update_address(proposal: &Pubkey) -> (Pubkey, u8) {
+    derive(&[POOL_UPDATE_SEED, proposal.as_ref()])
+}
+
+/// Governance tokens of `mint` escrowed by `owner`
+pub fn voter_weight_address(mint: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    derive(&[VOTER_WEIGHT_SEED, mint.as_ref(), owner.as_ref()])
+}
+
+/// Associated token account of a voter weight record, holding its escrow
+pub fn voter_weight_escrow_address(record: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[record.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &crate::ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Realms registrar of `realm`'s `governing_token_mint`
+pub fn realm_registrar_address(realm: &Pubkey, governing_token_mint: &Pubkey) -> (Pubkey, u8) {
+    derive(&[REALM_REGISTRAR_SEED, realm.as_ref(), governing_token_mint.as_ref()])
+}
+
+/// Pool root snapshot for one spl-governance proposal
+pub fn realm_weight_snapshot_address(registrar: &Pubkey, proposal: &Pubkey) -> (Pubkey, u8) {
+    derive(&[REALM_SNAPSHOT_SEED, registrar.as_ref(), proposal.as_ref()])
+}
+
+/// spl-governance addin voter weight record of `owner`
+pub fn realm_voter_weight_address(registrar: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    derive(&[REALM_VOTER_WEIGHT_SEED, registrar.as_ref(), owner.as_ref()])
+}
+
+/// spl-governance addin max voter weight record of a registrar
+pub fn realm_max_voter_weight_address(registrar: &Pubkey) -> (Pubkey, u8) {
+    derive(&[REALM_MAX_VOTER_WEIGHT_SEED, registrar.as_ref()])
+}
+
+/// Spent weight nullifier of one proposal snapshot
+pub fn realm_weight_nullifier_address(snapshot: &Pubkey, weight_nullifier: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[REALM_WEIGHT_NULLIFIER_SEED, snapshot.as_ref(), weight_nullifier])
+}
+
+// ============================================
+// RANDOMNESS BEACON
+// ============================================
+
+pub fn beacon_round_address(creator: &Pubkey, round_id: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[BEACON_ROUND_SEED, creator.as_ref(), round_id])
+}
+
+pub fn beacon_entry_address(round: &Pubkey, participant: &Pubkey) -> (Pubkey, u8) {
+    derive(&[BEACON_ENTRY_SEED, round.as_ref(), participant.as_ref()])
+}
+
+// ============================================
+// MULTISIG
+// ============================================
+
+pub fn multisig_address(creator: &Pubkey, vault_id: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[MULTISIG_SEED, creator.as_ref(), vault_id])
+}
+
+pub fn multisig_proposal_address(multisig: &Pubkey, proposal_id: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[MULTISIG_PROPOSAL_SEED, multisig.as_ref(), proposal_id])
+}
+
+/// Structured action of a multisig proposal
+pub fn multisig_action_address(proposal: &Pubkey) -> (Pubkey, u8) {
+    derive(&[MULTISIG_ACTION_SEED, proposal.as_ref()])
+}
+
+/// Threshold-encrypted action of a multisig proposal
+pub fn multisig_encrypted_action_address(proposal: &Pubkey) -> (Pubkey, u8) {
+    derive(&[MULTISIG_ENCRYPTED_ACTION_SEED, proposal.as_ref()])
+}
+
+/// Signer a multisig acts as in `PoolAdmin` actions; set it as a pool's
+/// authority to govern the pool by proposal
+pub fn multisig_authority_address(multisig: &Pubkey) -> (Pubkey, u8) {
+    derive(&[MULTISIG_AUTHORITY_SEED, multisig.as_ref()])
+}
+
+/// Signer heartbeats of `multisig` in one epoch (little-endian)
+pub fn signer_heartbeats_address(multisig: &Pubkey, epoch: u64) -> (Pubkey, u8) {
+    derive(&[SIGNER_HEARTBEATS_SEED, multisig.as_ref(), &epoch.to_le_bytes()])
+}
+
+/// Treasury band attestation of `multisig` over one state of `pool`
+/// (little-endian count)
+pub fn treasury_attestation_address(
+    multisig: &Pubkey,
+    pool: &Pubkey,
+    merkle_root: &[u8; 32],
+    nullifier_count: u32,
+) -> (Pubkey, u8) {
+    derive(&[
+        TREASURY_ATTESTATION_SEED,
+        multisig.as_ref(),
+        pool.as_ref(),
+        merkle_root,
+        &nullifier_count.to_le_bytes(),
+    ])
+}
+
+// ============================================
+// RLN
+// ============================================
+
+pub fn rln_group_address(creator: &Pubkey, group_id: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[RLN_GROUP_SEED, creator.as_ref(), group_id])
+}
+
+pub fn rln_member_address(group: &Pubkey, identity_commitment: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[RLN_MEMBER_SEED, group.as_ref(), identity_commitment])
+}
+
+pub fn rln_nullifier_address(group: &Pubkey, nullifier: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[RLN_NULLIFIER_SEED, group.as_ref(), nullifier])
+}
+
+// ============================================
+// SHIELDED POOL
+// ============================================
+
+pub fn shielded_pool_address(creator: &Pubkey, pool_id: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[SHIELDED_POOL_SEED, creator.as_ref(), pool_id])
+}
+
+/// Vault holding the pool's deposited lamports
+pub fn shielded_vault_address(pool: &Pubkey) -> (Pubkey, u8) {
+    derive(&[SHIELDED_VAULT_SEED, pool.as_ref()])
+}
+
+/// Authority that hands a pool to its stakers: only passing proposals
+/// stake-weighted by the pool can change it (no key can sign for it)
+pub fn pool_governance_address(pool: &Pubkey) -> (Pubkey, u8) {
+    derive(&[POOL_GOVERNANCE_SEED, pool.as_ref()])
+}
+
+/// Note stored at Merkle leaf `index` (little-endian in the seed)
+pub fn note_address(pool: &Pubkey, index: u32) -> (Pubkey, u8) {
+    derive(&[NOTE_SEED, pool.as_ref(), &index.to_le_bytes()])
+}
+
+/// Spent-note marker; its existence is the double-spend check
+pub fn nullifier_address(pool: &Pubkey, nullifier: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[NULLIFIER_SEED, pool.as_ref(), nullifier])
+}
+
+/// Gift note on `pool` keyed by H(DOMAIN_CLAIM_KEY, claim_secret)
+pub fn claimable_note_address(pool: &Pubkey, claim_key_hash: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[CLAIMABLE_NOTE_SEED, pool.as_ref(), claim_key_hash])
+}
+
+/// Shielded escrow on `pool`
+pub fn escrow_address(pool: &Pubkey, escrow_id: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[ESCROW_SEED, pool.as_ref(), escrow_id])
+}
+
+/// Recurring payment authorization on `pool`
+pub fn subscription_address(pool: &Pubkey, subscription_id: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[SUBSCRIPTION_SEED, pool.as_ref(), subscription_id])
+}
+
+/// Shielded fundraising campaign on `pool`
+pub fn campaign_address(pool: &Pubkey, campaign_id: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[CAMPAIGN_SEED, pool.as_ref(), campaign_id])
+}
+
+/// Proof-of-reserve attestation of `pool` at one state (little-endian count)
+pub fn reserve_attestation_address(
+    pool: &Pubkey,
+    merkle_root: &[u8; 32],
+    nullifier_count: u32,
+) -> (Pubkey, u8) {
+    derive(&[RESERVE_ATTESTATION_SEED, pool.as_ref(), merkle_root, &nullifier_count.to_le_bytes()])
+}
+
+/// Relayer registry of `pool`
+pub fn relayer_registry_address(pool: &Pubkey) -> (Pubkey, u8) {
+    derive(&[RELAYER_REGISTRY_SEED, pool.as_ref()])
+}
+
+/// Bonded relayer in `registry`
+pub fn relayer_address(registry: &Pubkey, relayer: &Pubkey) -> (Pubkey, u8) {
+    derive(&[RELAYER_SEED, registry.as_ref(), relayer.as_ref()])
+}
+
+/// Deposit receipt settings of `pool`; mint authority of its receipts
+pub fn deposit_receipts_address(pool: &Pubkey) -> (Pubkey, u8) {
+    derive(&[DEPOSIT_RECEIPTS_SEED, pool.as_ref()])
+}
+
+/// Caller program `program` allowlisted to deposit into `pool` through CPI
+pub fn deposit_caller_address(pool: &Pubkey, program: &Pubkey) -> (Pubkey, u8) {
+    derive(&[DEPOSIT_CALLER_SEED, pool.as_ref(), program.as_ref()])
+}
+
+/// Depositor PDA `caller_program` signs `shield_deposit_cpi` with; derived
+/// under the caller's program ID, not this one
+pub fn cpi_depositor_address(caller_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CPI_DEPOSITOR_SEED], caller_program)
+}
+
+/// Non-transferable receipt mint of the note at `note_index` (little-endian)
+pub fn receipt_mint_address(pool: &Pubkey, note_index: u32) -> (Pubkey, u8) {
+    derive(&[RECEIPT_MINT_SEED, pool.as_ref(), &note_index.to_le_bytes()])
+}
+
+/// Referral program of `pool`; holds its reward lamports
+pub fn referral_program_address(pool: &Pubkey) -> (Pubkey, u8) {
+    derive(&[REFERRAL_PROGRAM_SEED, pool.as_ref()])
+}
+
+/// Referrer on `pool` keyed by its referral commitment
+pub fn referral_address(pool: &Pubkey, referral_commitment: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[REFERRAL_SEED, pool.as_ref(), referral_commitment])
+}
+
+/// Light-client checkpoint of the pool's note tree
+pub fn tree_checkpoint_address(pool: &Pubkey) -> (Pubkey, u8) {
+    derive(&[TREE_CHECKPOINT_SEED, pool.as_ref()])
+}
+
+/// Bloom filter of the pool's spent nullifiers
+pub fn nullifier_filter_address(pool: &Pubkey) -> (Pubkey, u8) {
+    derive(&[NULLIFIER_FILTER_SEED, pool.as_ref()])
+}
+
+/// Withdrawn lamports on `pool` parked until a claim proof for
+/// H(DOMAIN_CLAIM_KEY, claim_secret) releases them
+pub fn holding_address(pool: &Pubkey, claim_key_hash: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[HOLDING_SEED, pool.as_ref(), claim_key_hash])
+}
+
+/// Protocol-wide totals across the pools counted there (one per program)
+pub fn protocol_stats_address() -> (Pubkey, u8) {
+    derive(&[PROTOCOL_STATS_SEED])
+}
+
+// ============================================
+// CIRCUITS
+// ============================================
+
+/// Audited verifying-key registry maintained by `authority`
+pub fn circuit_registry_address(authority: &Pubkey) -> (Pubkey, u8) {
+    derive(&[CIRCUIT_REGISTRY_SEED, authority.as_ref()])
+}
+
+// ============================================
+// WATCHTOWERS
+// ============================================
+
+/// Alert feed of a wallet, multisig or shielded pool
+pub fn watchtower_address(subject: &Pubkey) -> (Pubkey, u8) {
+    derive(&[WATCHTOWER_SEED, subject.as_ref()])
+}
+
+// ============================================
+// CRANKS
+// ============================================
+
+/// Tip jar a `funder` sponsors for one kind of maintenance on `target`
+pub fn crank_task_address(target: &Pubkey, kind: crate::CrankTaskKind, funder: &Pubkey) -> (Pubkey, u8) {
+    derive(&[CRANK_TASK_SEED, target.as_ref(), &[kind as u8], funder.as_ref()])
+}
+
+// ============================================
+// TESTING
+// ============================================
+
+/// Time override of `test-clock` builds (see `clock::now`)
+pub fn mock_clock_address() -> (Pubkey, u8) {
+    derive(&[MOCK_CLOCK_SEED])
+}
+
+// ============================================
+// LEGACY STAKING
+// ============================================
+
+pub fn stake_pool_address(creator: &Pubkey, pool_id: &[u8; 32]) -> (Pubkey, u8) {
+    derive(&[STAKE_POOL_SEED, creator.as_ref(), pool_id])
+}
+
+pub fn stake_vault_address(pool: &Pubkey) -> (Pubkey, u8) {
+    derive(&[STAKE_VAULT_SEED, pool.as_ref()])
+}
+
+pub fn stake_record_address(pool: &Pubkey, staker: &Pubkey) -> (Pubkey, u8) {
+    derive(&[STAKE_RECORD_SEED, pool.as_ref(), staker.as_ref()])
+}
@@ -0,0 +1,41 @@
+//! Hash-chained admin audit logs
+
+use crate::*;
+
+/// Append-only hash chain of a pool's or wallet's admin actions
+/// head_n = H(DOMAIN_AUDIT_LOG, head_{n-1} || n || action || detail || value || timestamp),
+/// starting from head_0 = H(DOMAIN_AUDIT_LOG, subject)
+#[account]
+pub struct AuditLog {
+    /// The shielded pool or wallet account whose actions are logged
+    pub subject: Pubkey,
+
+    /// Entries appended so far
+    pub sequence: u64,
+
+    /// Hash chain head after the latest entry
+    pub head: [u8; 32],
+
+    /// When the latest entry was appended (0 before the first)
+    pub last_entry_at: i64,
+
+    /// When the log was created
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl AuditLog {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // subject
+        8 + // sequence
+        32 + // head
+        8 + // last_entry_at
+        8 + // created_at
+        1 + // bump
+        1; // version
+}
@@ -0,0 +1,122 @@
+//! Randomness beacon rounds
+
+use crate::*;
+
+/// Randomness beacon round - commit-reveal over participant secrets
+#[account]
+pub struct BeaconRound {
+    /// Unique round identifier
+    pub round_id: [u8; 32],
+
+    /// Creator of the round
+    pub creator: Pubkey,
+
+    /// When the commit phase ends
+    pub commit_ends_at: i64,
+
+    /// When the reveal phase ends
+    pub reveal_ends_at: i64,
+
+    /// Bond each participant posts (returned on reveal)
+    pub bond_lamports: u64,
+
+    /// Number of commitments received
+    pub commit_count: u32,
+
+    /// Number of secrets revealed
+    pub reveal_count: u32,
+
+    /// Number of non-revealers slashed
+    pub slashed_count: u32,
+
+    /// XOR of all revealed secrets
+    pub accumulator: [u8; 32],
+
+    /// Final beacon value (valid once finalized)
+    pub beacon: [u8; 32],
+
+    /// When the round was created
+    pub created_at: i64,
+
+    /// When the beacon was finalized
+    pub finalized_at: i64,
+
+    /// Whether the beacon has been finalized
+    pub is_finalized: bool,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl BeaconRound {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // round_id
+        32 + // creator
+        8 + // commit_ends_at
+        8 + // reveal_ends_at
+        8 + // bond_lamports
+        4 + // commit_count
+        4 + // reveal_count
+        4 + // slashed_count
+        32 + // accumulator
+        32 + // beacon
+        8 + // created_at
+        8 + // finalized_at
+        1 + // is_finalized
+        1 + // bump
+        1; // version
+
+    /// The beacon value, for instructions consuming randomness
+    /// (e.g. auction tie-breaks or relayer selection)
+    pub fn finalized_beacon(&self) -> Result<[u8; 32]> {
+        require!(self.is_finalized, ErrorCode::BeaconNotFinalized);
+        Ok(self.beacon)
+    }
+}
+
+/// A participant's commitment in a beacon round, holding their bond
+#[account]
+pub struct BeaconEntry {
+    /// The round this entry belongs to
+    pub round: Pubkey,
+
+    /// The participant (for PDA derivation and bond refund)
+    pub participant: Pubkey,
+
+    /// Commitment: H(DOMAIN_BEACON_COMMIT, secret || participant)
+    pub commitment: [u8; 32],
+
+    /// Bond currently held by this account
+    pub bond_lamports: u64,
+
+    /// When the commitment was made
+    pub committed_at: i64,
+
+    /// Whether the secret has been revealed
+    pub has_revealed: bool,
+
+    /// Whether the bond was forfeited for not revealing
+    pub is_slashed: bool,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl BeaconEntry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // round
+        32 + // participant
+        32 + // commitment
+        8 + // bond_lamports
+        8 + // committed_at
+        1 + // has_revealed
+        1 + // is_slashed
+        1 + // bump
+        1; // version
+}
@@ -0,0 +1,347 @@
+//! Circuit registry and circuit identifiers
+
+use crate::*;
+
+/// Bytes of verifying-keys/<name>.vk
+macro_rules! verifying_key {
+    ($name:literal) => {
+        include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/verifying-keys/", $name, ".vk"))
+    };
+}
+
+/// Audited verifying keys and trusted-setup transcript of the program's circuits
+#[account]
+pub struct CircuitRegistry {
+    /// Auditor or team that maintains the registry
+    pub authority: Pubkey,
+
+    /// Hash of the final Powers-of-Tau contribution
+    pub ptau_hash: [u8; 32],
+
+    /// One write-once entry per registered circuit
+    pub entries: Vec<CircuitEntry>,
+
+    /// When the registry was created
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl CircuitRegistry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        32 + // ptau_hash
+        4 + CircuitEntry::LEN * Circuit::COUNT + // entries
+        8 + // created_at
+        1 + // bump
+        1; // version
+
+    pub fn entry(&self, circuit_id: &[u8; 32]) -> Option<&CircuitEntry> {
+        self.entries.iter().find(|entry| entry.circuit_id == *circuit_id)
+    }
+}
+
+/// Proof circuits verified by the program; `verify_groth16_stub` checks a
+/// proof of each against its public signals
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Circuit {
+    /// Wallet ownership: knowledge of the secret behind the wallet commitment
+    Wallet,
+
+    /// Wallet attestation
+    /// The wallet's commitment is a leaf under a wallet registry root, and
+    /// the wallet meets the claimed threshold at the reference time
+    /// Signals: [attestation_nullifier, claim, threshold, reference_time, wallets_root]
+    Attestation,
+
+    /// RLN signal: membership under members_root, share correctness and
+    /// message_id < limit
+    Rln,
+
+    /// Shielded withdrawal
+    Withdrawal,
+
+    /// Relayed withdrawal
+    /// Same statement as a withdrawal, with the note value split into the
+    /// withdrawn amount, change and the committed relayer fee
+    RelayedWithdrawal,
+
+    /// Aggregated withdrawal (recursive / aggregated Groth16)
+    /// Signals: [merkle_root, K, (nullifier_i, output_commitment_i) for each i, pool_binding]
+    AggregateWithdrawal,
+
+    /// Note consolidation
+    /// Signals: [merkle_root, output_commitment, nullifier_0, ..., nullifier_n, pool_binding]
+    Consolidation,
+
+    /// Gift note claim
+    /// Signals: [claim_key_hash, note_commitment, nullifier, output_commitment]
+    Claim,
+
+    /// Escrow lock/vote/settlement
+    Escrow,
+
+    /// Subscription collection
+    /// Proves the spent note is the payer's, the payment note carries the
+    /// committed amount to the merchant, and change preserves the remainder
+    Subscription,
+
+    /// Campaign donation/withdrawal
+    Campaign,
+
+    /// Proof of reserve
+    /// Proves sum(unspent note values) == vault_balance over the tree at merkle_root
+    Reserve,
+
+    /// Veto authority
+    /// Proves knowledge of the preimage of the multisig's veto commitment
+    Veto,
+
+    /// Wallet call
+    /// Proves knowledge of the wallet secret behind the commitment
+    WalletCall,
+
+    /// Shielded reward claim: the pool's reward curve evaluated at the note's
+    /// hidden deposit time, bound to the pool
+    Rewards,
+
+    /// Holding withdrawal
+    /// Same statement as a withdrawal, with the released amount and the
+    /// holding account's claim key bound through the holding tag
+    HoldingWithdrawal,
+
+    /// Holding claim
+    /// Signals: [claim_key_hash, holding_claim_tag]
+    HoldingClaim,
+
+    /// Split withdrawal
+    /// Same statement as a withdrawal, with the released note value divided
+    /// between the public recipients and shares fixed by the split tag
+    /// Signals: [nullifier, output_commitment, spend_anchor, split_tag, pool_binding]
+    SplitWithdrawal,
+
+    /// Deposit cancellation
+    /// Proves knowledge of the note's opening: it commits to the refunded
+    /// lamports, and the nullifier is the one the note would be spent with
+    /// Signals: [note_commitment, nullifier, cancel_tag, pool_binding]
+    DepositCancel,
+
+    /// Relayer solvency
+    /// Proves the obligations behind the commitment sum to less than the
+    /// stake the solvency tag is bound to
+    /// Signals: [obligations_commitment, solvency_tag, pool_binding]
+    RelayerSolvency,
+
+    /// Early exit
+    /// Same statement as a withdrawal without the lockup check: the note's
+    /// value covers the gross amount in the early-exit tag plus the change
+    /// Signals: [nullifier, output_commitment, spend_anchor, early_exit_tag, pool_binding]
+    EarlyExit,
+
+    /// Key-value attestation
+    /// Proves knowledge of the wallet secret behind the commitment
+    KvAttestation,
+
+    /// Wallet unfreeze
+    /// Proves knowledge of the secret behind the wallet's recovery commitment
+    WalletUnfreeze,
+
+    /// Approval revocation
+    /// Proves knowledge of the signer secret behind the approval commitment
+    ApprovalRevocation,
+
+    /// Small-spend
+    /// Proves the transfer fixed by the tag is within the limit for its mint
+    /// in the policy behind the multisig's spend limit commitment
+    /// Signals: [spend_limit_commitment, small_spend_tag]
+    SmallSpend,
+
+    /// Signer rotation
+    /// Proves knowledge of the preimage of the old signer commitment
+    /// Signals: [old_commitment, signer_rotation_tag]
+    SignerRotation,
+
+    /// Signer heartbeat
+    /// Proves knowledge of the secret behind one signer commitment in the set
+    /// fixed by the tag, and that the nullifier is that signer's for the epoch
+    /// Signals: [heartbeat_nullifier, heartbeat_tag]
+    SignerHeartbeat,
+
+    /// Treasury attestation
+    /// Proves the unspent notes the multisig's authority PDA owns in the pool
+    /// sum to a value inside the band fixed by the tag, without revealing it
+    /// Signals: [treasury_band_tag, spend_anchor, nullifier_count]
+    TreasuryBand,
+
+    /// Referral claim
+    /// Proves knowledge of the secret behind the referral commitment
+    ReferralClaim,
+
+    /// Tally challenge
+    /// Proves the counts behind the tally commitment disagree with the
+    /// proposal's valid reveals
+    /// Signals: [tally_commitment, proposal]
+    TallyChallenge,
+
+    /// Stake weight
+    /// Signals: [snapshot_root, vote_nullifier, vote_commitment, weight_tag]
+    /// Proves a note in the snapshot tree worth at least the tagged minimum
+    /// and that the vote nullifier is derived from that note and the proposal
+    StakeWeight,
+
+    /// Conviction stake
+    /// Proves a note in the pool snapshot worth the weight hidden in the stake
+    /// commitment, and that the nullifier is that note's for the proposal
+    /// Signals: [merkle_root, stake_nullifier, stake_commitment, proposal]
+    ConvictionStake,
+
+    /// Conviction
+    /// Proves the note behind the stake commitment is still unspent in the
+    /// pool and the conviction fixed by the tag is at most its weight (note
+    /// value over weight_unit) scaled by min(elapsed, ramp_period) / ramp_period
+    /// Signals: [stake_commitment, spend_anchor, nullifier_count, conviction_tag]
+    Conviction,
+
+    /// Quadratic vote
+    /// Proves a credit leaf in the proposal's credit root whose hidden budget
+    /// covers the sum of squares of the votes fixed by the tag, and that the
+    /// nullifier is that leaf's for the proposal
+    /// Signals: [credit_root, vote_nullifier, quadratic_vote_tag]
+    QuadraticVote,
+
+    /// Note sweep
+    /// Signals: [sweep_tag, merkle_root, nullifier_count, amount]
+    NoteSweep,
+
+    /// Private stake unstake: the nullifier is derived from the stake commitment
+    Unstake,
+
+    /// Private stake reward claim
+    StakeRewards,
+
+    /// Stealth approval
+    /// Proves knowledge of the secret behind one of the multisig's signer
+    /// commitments, and that the approval commitment is that signer's
+    /// nullifier for the proposal, so each signer approves once
+    /// Signals: [approval_commitment, stealth_approval_tag]
+    StealthApproval,
+}
+
+impl Circuit {
+    pub const COUNT: usize = 38;
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Circuit::Wallet => "wallet",
+            Circuit::Attestation => "attestation",
+            Circuit::Rln => "rln",
+            Circuit::Withdrawal => "withdrawal",
+            Circuit::RelayedWithdrawal => "relayed_withdrawal",
+            Circuit::AggregateWithdrawal => "aggregate_withdrawal",
+            Circuit::Consolidation => "consolidation",
+            Circuit::Claim => "claim",
+            Circuit::Escrow => "escrow",
+            Circuit::Subscription => "subscription",
+            Circuit::Campaign => "campaign",
+            Circuit::Reserve => "reserve",
+            Circuit::Veto => "veto",
+            Circuit::WalletCall => "wallet_call",
+            Circuit::Rewards => "rewards",
+            Circuit::HoldingWithdrawal => "holding_withdrawal",
+            Circuit::HoldingClaim => "holding_claim",
+            Circuit::SplitWithdrawal => "split_withdrawal",
+            Circuit::DepositCancel => "deposit_cancel",
+            Circuit::RelayerSolvency => "relayer_solvency",
+            Circuit::EarlyExit => "early_exit",
+            Circuit::KvAttestation => "kv_attestation",
+            Circuit::WalletUnfreeze => "wallet_unfreeze",
+            Circuit::ApprovalRevocation => "approval_revocation",
+            Circuit::SmallSpend => "small_spend",
+            Circuit::SignerRotation => "signer_rotation",
+            Circuit::SignerHeartbeat => "signer_heartbeat",
+            Circuit::TreasuryBand => "treasury_band",
+            Circuit::ReferralClaim => "referral_claim",
+            Circuit::TallyChallenge => "tally_challenge",
+            Circuit::StakeWeight => "stake_weight",
+            Circuit::ConvictionStake => "conviction_stake",
+            Circuit::Conviction => "conviction",
+            Circuit::QuadraticVote => "quadratic_vote",
+            Circuit::NoteSweep => "note_sweep",
+            Circuit::Unstake => "unstake",
+            Circuit::StakeRewards => "stake_rewards",
+            Circuit::StealthApproval => "stealth_approval",
+        }
+    }
+
+    /// Circuit identifier: H(DOMAIN_CIRCUIT_ID, name)
+    pub fn id(self) -> [u8; 32] {
+        hash_domain(DOMAIN_CIRCUIT_ID, &[self.name().as_bytes()])
+    }
+
+    /// Groth16 verifying key compiled into this program build
+    /// (verifying-keys/<name>.vk)
+    pub fn verifying_key(self) -> &'static [u8] {
+        match self {
+            Circuit::Wallet => verifying_key!("wallet"),
+            Circuit::Attestation => verifying_key!("attestation"),
+            Circuit::Rln => verifying_key!("rln"),
+            Circuit::Withdrawal => verifying_key!("withdrawal"),
+            Circuit::RelayedWithdrawal => verifying_key!("relayed_withdrawal"),
+            Circuit::AggregateWithdrawal => verifying_key!("aggregate_withdrawal"),
+            Circuit::Consolidation => verifying_key!("consolidation"),
+            Circuit::Claim => verifying_key!("claim"),
+            Circuit::Escrow => verifying_key!("escrow"),
+            Circuit::Subscription => verifying_key!("subscription"),
+            Circuit::Campaign => verifying_key!("campaign"),
+            Circuit::Reserve => verifying_key!("reserve"),
+            Circuit::Veto => verifying_key!("veto"),
+            Circuit::WalletCall => verifying_key!("wallet_call"),
+            Circuit::Rewards => verifying_key!("rewards"),
+            Circuit::HoldingWithdrawal => verifying_key!("holding_withdrawal"),
+            Circuit::HoldingClaim => verifying_key!("holding_claim"),
+            Circuit::SplitWithdrawal => verifying_key!("split_withdrawal"),
+            Circuit::DepositCancel => verifying_key!("deposit_cancel"),
+            Circuit::RelayerSolvency => verifying_key!("relayer_solvency"),
+            Circuit::EarlyExit => verifying_key!("early_exit"),
+            Circuit::KvAttestation => verifying_key!("kv_attestation"),
+            Circuit::WalletUnfreeze => verifying_key!("wallet_unfreeze"),
+            Circuit::ApprovalRevocation => verifying_key!("approval_revocation"),
+            Circuit::SmallSpend => verifying_key!("small_spend"),
+            Circuit::SignerRotation => verifying_key!("signer_rotation"),
+            Circuit::SignerHeartbeat => verifying_key!("signer_heartbeat"),
+            Circuit::TreasuryBand => verifying_key!("treasury_band"),
+            Circuit::ReferralClaim => verifying_key!("referral_claim"),
+            Circuit::TallyChallenge => verifying_key!("tally_challenge"),
+            Circuit::StakeWeight => verifying_key!("stake_weight"),
+            Circuit::ConvictionStake => verifying_key!("conviction_stake"),
+            Circuit::Conviction => verifying_key!("conviction"),
+            Circuit::QuadraticVote => verifying_key!("quadratic_vote"),
+            Circuit::NoteSweep => verifying_key!("note_sweep"),
+            Circuit::Unstake => verifying_key!("unstake"),
+            Circuit::StakeRewards => verifying_key!("stake_rewards"),
+            Circuit::StealthApproval => verifying_key!("stealth_approval"),
+        }
+    }
+
+    /// Hash of the verifying key compiled into this program build:
+    /// H(DOMAIN_VERIFYING_KEY, key bytes)
+    pub fn verifying_key_hash(self) -> [u8; 32] {
+        hash_domain(DOMAIN_VERIFYING_KEY, &[self.verifying_key()])
+    }
+}
+
+/// Audited verifying key of one circuit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CircuitEntry {
+    pub circuit_id: [u8; 32],
+    pub vk_hash: [u8; 32],
+    pub registered_at: i64,
+}
+
+impl CircuitEntry {
+    pub const LEN: usize = 32 + 32 + 8;
+}
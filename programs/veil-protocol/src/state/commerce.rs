@@ -0,0 +1,226 @@
+//! Shielded escrows, subscriptions and campaigns
+
+use crate::*;
+
+/// Shielded escrow - a locked value commitment settled 2-of-3
+#[account]
+pub struct Escrow {
+    /// The pool the locked value settles into
+    pub pool: Pubkey,
+
+    /// Unique escrow identifier
+    pub escrow_id: [u8; 32],
+
+    /// Hash of the order being paid for
+    pub order_hash: [u8; 32],
+
+    /// Commitment to the locked value
+    pub locked_commitment: [u8; 32],
+
+    /// Buyer commitment = H(buyer_secret)
+    pub buyer_commitment: [u8; 32],
+
+    /// Seller commitment = H(seller_secret)
+    pub seller_commitment: [u8; 32],
+
+    /// Arbiter commitment = H(arbiter_secret)
+    pub arbiter_commitment: [u8; 32],
+
+    /// Buyer's vote (starts at Refund)
+    pub buyer_vote: EscrowOutcome,
+
+    /// Seller's vote (starts at Release)
+    pub seller_vote: EscrowOutcome,
+
+    /// Arbiter's ruling
+    pub arbiter_vote: EscrowOutcome,
+
+    /// Outcome once two parties agree
+    pub resolution: EscrowOutcome,
+
+    /// When the escrow was created
+    pub created_at: i64,
+
+    /// When the escrow was settled
+    pub settled_at: i64,
+
+    /// Whether the locked value has been paid out
+    pub is_settled: bool,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl Escrow {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // escrow_id
+        32 + // order_hash
+        32 + // locked_commitment
+        32 + // buyer_commitment
+        32 + // seller_commitment
+        32 + // arbiter_commitment
+        1 + // buyer_vote
+        1 + // seller_vote
+        1 + // arbiter_vote
+        1 + // resolution
+        8 + // created_at
+        8 + // settled_at
+        1 + // is_settled
+        1 + // bump
+        1; // version
+
+    /// Outcome backed by at least two of the three parties
+    pub fn tally(&self) -> EscrowOutcome {
+        let votes = [self.buyer_vote, self.seller_vote, self.arbiter_vote];
+        [EscrowOutcome::Release, EscrowOutcome::Refund]
+            .into_iter()
+            .find(|outcome| votes.iter().filter(|vote| *vote == outcome).count() >= 2)
+            .unwrap_or(EscrowOutcome::Pending)
+    }
+}
+
+/// Recurring shielded payment authorization
+#[account]
+pub struct Subscription {
+    /// The pool payments are made in
+    pub pool: Pubkey,
+
+    /// Unique subscription identifier
+    pub subscription_id: [u8; 32],
+
+    /// Key allowed to cancel
+    pub authority: Pubkey,
+
+    /// Payer commitment = H(payer_secret)
+    pub payer_commitment: [u8; 32],
+
+    /// Merchant commitment = H(merchant_secret)
+    pub merchant_commitment: [u8; 32],
+
+    /// Commitment to the fixed per-period amount
+    pub amount_commitment: [u8; 32],
+
+    /// Length of one billing period
+    pub period_seconds: i64,
+
+    /// Start of period 0
+    pub start_at: i64,
+
+    /// Lowest period that can still be collected
+    pub next_period: u64,
+
+    /// Number of periods collected
+    pub collected_count: u32,
+
+    /// When the subscription was created
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl Subscription {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // subscription_id
+        32 + // authority
+        32 + // payer_commitment
+        32 + // merchant_commitment
+        32 + // amount_commitment
+        8 + // period_seconds
+        8 + // start_at
+        8 + // next_period
+        4 + // collected_count
+        8 + // created_at
+        1 + // bump
+        1; // version
+
+    /// Whether billing period `period` has started at `now`
+    pub fn period_started(&self, period: u64, now: i64) -> bool {
+        let offset = (period as i64).checked_mul(self.period_seconds);
+        offset
+            .and_then(|offset| self.start_at.checked_add(offset))
+            .is_some_and(|period_start| now >= period_start)
+    }
+}
+
+/// Shielded fundraising campaign - tracks only an aggregate of donation notes
+#[account]
+pub struct Campaign {
+    /// The pool donations are deposited into
+    pub pool: Pubkey,
+
+    /// Unique campaign identifier
+    pub campaign_id: [u8; 32],
+
+    /// Creator of the campaign
+    pub creator: Pubkey,
+
+    /// Beneficiary commitment = H(beneficiary_secret)
+    pub beneficiary_commitment: [u8; 32],
+
+    /// Running H(DOMAIN_CAMPAIGN_AGGREGATE, aggregate || note_commitment)
+    pub aggregate_commitment: [u8; 32],
+
+    /// Number of donations received
+    pub donation_count: u32,
+
+    /// Donations close and withdrawal opens at this time
+    pub deadline: i64,
+
+    /// When the campaign was created
+    pub created_at: i64,
+
+    /// When the beneficiary withdrew
+    pub withdrawn_at: i64,
+
+    /// Whether the beneficiary has withdrawn
+    pub is_withdrawn: bool,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl Campaign {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // campaign_id
+        32 + // creator
+        32 + // beneficiary_commitment
+        32 + // aggregate_commitment
+        4 + // donation_count
+        8 + // deadline
+        8 + // created_at
+        8 + // withdrawn_at
+        1 + // is_withdrawn
+        1 + // bump
+        1; // version
+}
+
+/// Escrow party, identified on-chain only by its commitment
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum EscrowRole {
+    Buyer,
+    Seller,
+    Arbiter,
+}
+
+/// Escrow vote / resolution
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum EscrowOutcome {
+    Pending,
+    /// Pay the seller
+    Release,
+    /// Return the value to the buyer
+    Refund,
+}
@@ -0,0 +1,59 @@
+//! Sponsor-funded crank tasks
+
+use crate::*;
+
+/// Crank task - sponsor-funded tips for one kind of maintenance on one target
+/// Tips are paid out of the account's lamports above its rent-exempt minimum
+#[account]
+pub struct CrankTask {
+    /// Sponsor who registered the task (receives the remainder on close)
+    pub funder: Pubkey,
+
+    /// Account the maintenance runs against (e.g. the pool for TreeCheckpoint)
+    pub target: Pubkey,
+
+    /// Which maintenance instruction pays out of this task
+    pub kind: CrankTaskKind,
+
+    /// Lamports paid per successful run
+    pub tip_lamports: u64,
+
+    /// Minimum seconds between paid runs
+    pub min_interval: i64,
+
+    /// When a tip was last paid
+    pub last_run_at: i64,
+
+    /// Number of tips paid
+    pub run_count: u64,
+
+    /// When the task was registered
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl CrankTask {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // funder
+        32 + // target
+        1 + // kind
+        8 + // tip_lamports
+        8 + // min_interval
+        8 + // last_run_at
+        8 + // run_count
+        8 + // created_at
+        1 + // bump
+        1; // version
+}
+
+/// Maintenance instructions that can pay crank tips
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CrankTaskKind {
+    /// `checkpoint_tree`; target is the shielded pool
+    TreeCheckpoint,
+}
@@ -0,0 +1,257 @@
+//! Governance token escrow, voter weight and the spl-governance addin
+
+use crate::*;
+
+/// Source of the voting weight a token-weighted proposal counts
+///
+/// Weight is read when a vote is cast and stored on the vote record; the
+/// source is then locked until voting closes, so the same tokens cannot
+/// be moved to another owner and vote again.
+pub trait VoterWeightPlugin {
+    /// Mint the weight is denominated in
+    fn governing_mint(&self) -> Pubkey;
+
+    /// Key allowed to vote with the weight
+    fn weight_owner(&self) -> Pubkey;
+
+    /// Weight the owner can cast at `now`
+    fn voter_weight(&self, now: i64) -> u64;
+
+    /// Keep the weight from leaving before `until`
+    fn lock_weight_until(&mut self, until: i64);
+}
+
+/// Governance tokens escrowed by one owner, optionally locked up for a
+/// veToken-style weight bonus. The tokens sit in the record's associated
+/// token account (`pda::voter_weight_escrow_address`)
+///
+/// The discriminator is sha256("account:VeilVoterWeightRecord")[..8]: the
+/// default one would equal the spl-governance addin `VoterWeightRecord`'s,
+/// letting Realms read an escrow as a weight record.
+#[account(discriminator = [135, 36, 29, 145, 128, 245, 217, 18])]
+pub struct VoterWeightRecord {
+    /// Owner who deposited the tokens and votes with them
+    pub owner: Pubkey,
+
+    /// Governance token mint
+    pub mint: Pubkey,
+
+    /// Tokens held in escrow
+    pub amount: u64,
+
+    /// End of the lockup; extending it raises the weight
+    pub lockup_ends_at: i64,
+
+    /// End of the latest voting period the tokens were counted in
+    pub vote_locked_until: i64,
+
+    /// When the record was created
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl VoterWeightRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        32 + // mint
+        8 + // amount
+        8 + // lockup_ends_at
+        8 + // vote_locked_until
+        8 + // created_at
+        1 + // bump
+        1; // version
+
+    /// Weight of `amount` tokens with `remaining` seconds of lockup left:
+    /// the deposit plus MAX_LOCKUP_BONUS_BPS of it scaled by the share of
+    /// MAX_GOVERNANCE_LOCKUP_SECONDS still to run
+    pub fn lockup_weight(amount: u64, remaining: i64) -> u64 {
+        let remaining = remaining.clamp(0, MAX_GOVERNANCE_LOCKUP_SECONDS) as u128;
+        let bonus = amount as u128 * MAX_LOCKUP_BONUS_BPS as u128 * remaining
+            / (10_000 * MAX_GOVERNANCE_LOCKUP_SECONDS as u128);
+        (amount as u128 + bonus).min(u64::MAX as u128) as u64
+    }
+
+    /// Whether the tokens may leave the escrow at `now`
+    pub fn is_unlocked(&self, now: i64) -> bool {
+        now >= self.lockup_ends_at && now >= self.vote_locked_until
+    }
+}
+
+impl VoterWeightPlugin for VoterWeightRecord {
+    fn governing_mint(&self) -> Pubkey {
+        self.mint
+    }
+
+    fn weight_owner(&self) -> Pubkey {
+        self.owner
+    }
+
+    fn voter_weight(&self, now: i64) -> u64 {
+        Self::lockup_weight(self.amount, self.lockup_ends_at.saturating_sub(now))
+    }
+
+    fn lock_weight_until(&mut self, until: i64) {
+        self.vote_locked_until = self.vote_locked_until.max(until);
+    }
+}
+
+/// Ties a Realms DAO's governing mint to a shielded pool whose notes
+/// prove voter weight through the spl-governance addin records below
+#[account]
+pub struct RealmRegistrar {
+    /// spl-governance realm
+    pub realm: Pubkey,
+
+    /// Community or council mint of the realm the weight counts for
+    pub governing_token_mint: Pubkey,
+
+    /// Shielded pool whose notes carry the weight
+    pub pool: Pubkey,
+
+    /// Lamports of note value per unit of voter weight
+    pub weight_unit: u64,
+
+    /// Realm authority that created the registrar
+    pub authority: Pubkey,
+
+    /// When the registrar was created
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl RealmRegistrar {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // realm
+        32 + // governing_token_mint
+        32 + // pool
+        8 + // weight_unit
+        32 + // authority
+        8 + // created_at
+        1 + // bump
+        1; // version
+}
+
+/// Pool root one Realms proposal's weight proofs are checked against, so
+/// notes deposited after the snapshot cannot add weight
+#[account]
+pub struct RealmWeightSnapshot {
+    /// Registrar the snapshot belongs to
+    pub registrar: Pubkey,
+
+    /// spl-governance proposal
+    pub proposal: Pubkey,
+
+    /// Pool Merkle root when the snapshot was taken
+    pub weight_root: [u8; 32],
+
+    /// When the snapshot was taken
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl RealmWeightSnapshot {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // registrar
+        32 + // proposal
+        32 + // weight_root
+        8 + // created_at
+        1 + // bump
+        1; // version
+}
+
+/// Governance action a voter weight was produced for (spl-governance
+/// addin layout)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoterWeightAction {
+    CastVote,
+    CommentProposal,
+    CreateGovernance,
+    CreateProposal,
+    SignOffProposal,
+}
+
+/// spl-governance addin `VoterWeightRecord`, byte for byte, so Realms
+/// reads it as the weight of `governing_token_owner`. The layout belongs
+/// to spl-governance and carries no version byte
+#[account(discriminator = [46, 249, 155, 75, 153, 248, 116, 9])]
+pub struct RealmsVoterWeightRecord {
+    /// spl-governance realm
+    pub realm: Pubkey,
+
+    /// Governing mint the weight counts for
+    pub governing_token_mint: Pubkey,
+
+    /// Voter the weight belongs to
+    pub governing_token_owner: Pubkey,
+
+    /// Proven weight
+    pub voter_weight: u64,
+
+    /// Slot the weight is valid in; Realms rejects it in any other slot
+    pub voter_weight_expiry: Option<u64>,
+
+    /// Action the weight may be used for
+    pub weight_action: Option<VoterWeightAction>,
+
+    /// Proposal the weight may be used on
+    pub weight_action_target: Option<Pubkey>,
+
+    /// Reserved by spl-governance
+    pub reserved: [u8; 8],
+}
+
+impl RealmsVoterWeightRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // realm
+        32 + // governing_token_mint
+        32 + // governing_token_owner
+        8 + // voter_weight
+        9 + // voter_weight_expiry
+        2 + // weight_action
+        33 + // weight_action_target
+        8; // reserved
+}
+
+/// spl-governance addin `MaxVoterWeightRecord`, byte for byte. Carries no
+/// version byte, like `RealmsVoterWeightRecord`
+#[account(discriminator = [157, 95, 242, 151, 16, 98, 26, 118])]
+pub struct RealmsMaxVoterWeightRecord {
+    /// spl-governance realm
+    pub realm: Pubkey,
+
+    /// Governing mint the weight counts for
+    pub governing_token_mint: Pubkey,
+
+    /// Weight of the whole pool: vault balance over the registrar's unit
+    pub max_voter_weight: u64,
+
+    /// Slot the weight is valid in
+    pub max_voter_weight_expiry: Option<u64>,
+
+    /// Reserved by spl-governance
+    pub reserved: [u8; 8],
+}
+
+impl RealmsMaxVoterWeightRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // realm
+        32 + // governing_token_mint
+        8 + // max_voter_weight
+        9 + // max_voter_weight_expiry
+        8; // reserved
+}
@@ -0,0 +1,46 @@
+//! Holding accounts for delayed unshielding
+
+use crate::*;
+
+/// Lamports withdrawn from a shielded pool and parked until claimed
+/// The account holds the withdrawn lamports on top of its rent; claiming
+/// closes it into the recipient
+#[account]
+pub struct HoldingAccount {
+    /// The pool the lamports were withdrawn from
+    pub pool: Pubkey,
+
+    /// H(DOMAIN_CLAIM_KEY, claim_secret)
+    pub claim_key_hash: [u8; 32],
+
+    /// Lamports withdrawn into the account (excluding rent)
+    pub lamports: u64,
+
+    /// When the withdrawal landed
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl HoldingAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // claim_key_hash
+        8 + // lamports
+        8 + // created_at
+        1 + // bump
+        1; // version
+}
+
+/// Destination and amount a holding withdrawal proof is bound to
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct HoldingRelease {
+    /// H(DOMAIN_CLAIM_KEY, claim_secret) of the holding account
+    pub claim_key_hash: [u8; 32],
+    /// Lamports moved from the pool vault into the holding account
+    pub lamports: u64,
+}
@@ -0,0 +1,165 @@
+//! Program account layouts and their versioning
+
+mod audit;
+mod beacon;
+mod circuits;
+mod commerce;
+mod crank;
+mod governance;
+mod holding;
+mod multisig;
+mod referral;
+mod relayer;
+mod rln;
+mod shielded;
+mod staking;
+#[cfg(feature = "test-clock")]
+mod testing;
+mod voting;
+mod wallet;
+mod watchtower;
+
+pub use audit::*;
+pub use beacon::*;
+pub use circuits::*;
+pub use commerce::*;
+pub use crank::*;
+pub use governance::*;
+pub use holding::*;
+pub use multisig::*;
+pub use referral::*;
+pub use relayer::*;
+pub use rln::*;
+pub use shielded::*;
+pub use staking::*;
+#[cfg(feature = "test-clock")]
+pub use testing::*;
+pub use voting::*;
+pub use wallet::*;
+pub use watchtower::*;
+
+use crate::*;
+
+/// Program accounts that carry a layout `version` byte
+///
+/// Fields are only ever appended, so an older account is a prefix of the
+/// current layout: `migrate_account` grows it to `LEN`, zero-filling the new
+/// bytes, then lets `upgrade` replace any zero default that needs a real one.
+///
+/// Because of that, nothing is ever put in front of existing fields: the
+/// 8-byte discriminator is each account's kind tag, and indexers classify
+/// accounts by it (the SDK's `classifyAccount`, `accountKindFilter`).
+pub trait Versioned {
+    /// Layout version written by `init` handlers
+    const VERSION: u8;
+
+    /// Size of the current layout, including the discriminator
+    const LEN: usize;
+
+    fn version(&self) -> u8;
+
+    fn set_version(&mut self, version: u8);
+
+    /// Fill defaults for fields added after `from_version`
+    fn upgrade(&mut self, _from_version: u8) {}
+}
+
+/// Zero-copy accounts whose header may grow between versions
+///
+/// Commitment slots trail the header, so appending header fields moves the
+/// tail: `migrate_zero_copy_account` reads the stored version at
+/// `VERSION_OFFSET` to find where the old header ended.
+///
+/// Accounts that were Borsh-serialized before they became zero-copy keep
+/// their fields in a different order, so the version byte cannot be read
+/// from them. They are told apart by size (`BORSH_V0_LEN`) and rewritten
+/// whole by `from_borsh_v0`.
+pub trait ZeroCopyVersioned: Versioned {
+    /// Offset of the `version` byte, including the discriminator
+    const VERSION_OFFSET: usize;
+
+    /// Size of the Borsh layout the account had before it was zero-copy;
+    /// `None` for accounts that always were
+    const BORSH_V0_LEN: Option<usize> = None;
+
+    /// Header size (with discriminator) of the `version` layout
+    fn header_len(_version: u8) -> usize {
+        Self::LEN
+    }
+
+    /// The current layout, discriminator and trailing slots included, of
+    /// an account still in its `BORSH_V0_LEN` Borsh layout
+    fn from_borsh_v0(_data: &[u8]) -> Result<Vec<u8>> {
+        err!(ErrorCode::InvalidAccountLayout)
+    }
+}
+
+macro_rules! impl_versioned {
+    ($($account:ty => $len:expr),* $(,)?) => {
+        $(
+            impl Versioned for $account {
+                const VERSION: u8 = ACCOUNT_VERSION;
+                const LEN: usize = $len;
+
+                fn version(&self) -> u8 {
+                    self.version
+                }
+
+                fn set_version(&mut self, version: u8) {
+                    self.version = version;
+                }
+            }
+        )*
+    };
+}
+
+impl_versioned! {
+    WalletAccount => WalletAccount::LEN,
+    AliasRecord => AliasRecord::LEN,
+    WalletBackup => WalletBackup::LEN,
+    WalletAttestation => WalletAttestation::LEN,
+    WalletRegistry => WalletRegistry::LEN,
+    WalletEnrollment => WalletEnrollment::LEN,
+    Attestation => Attestation::LEN,
+    Proposal => Proposal::LEN,
+    ProposalTemplate => ProposalTemplate::LEN,
+    PoolGovernance => PoolGovernance::LEN,
+    ConvictionStake => ConvictionStake::LEN,
+    QuadraticProposal => QuadraticProposal::LEN,
+    QuadraticVoteRecord => QuadraticVoteRecord::LEN,
+    VoteRecord => VoteRecord::LEN,
+    VoterWeightRecord => VoterWeightRecord::LEN,
+    RealmRegistrar => RealmRegistrar::LEN,
+    RealmWeightSnapshot => RealmWeightSnapshot::LEN,
+    RlnGroup => RlnGroup::LEN,
+    RlnMember => RlnMember::LEN,
+    RlnNullifierRecord => RlnNullifierRecord::LEN,
+    ShieldedNote => ShieldedNote::LEN,
+    PrivateStakePool => PrivateStakePool::LEN,
+    PrivateStakeRecord => PrivateStakeRecord::LEN,
+    TreeCheckpoint => TreeCheckpoint::LEN,
+    NullifierFilter => NullifierFilter::LEN,
+    ProtocolStats => ProtocolStats::LEN,
+    HoldingAccount => HoldingAccount::LEN,
+    CrankTask => CrankTask::LEN,
+    Campaign => Campaign::LEN,
+    Subscription => Subscription::LEN,
+    Escrow => Escrow::LEN,
+    ClaimableNote => ClaimableNote::LEN,
+    ReserveAttestation => ReserveAttestation::LEN,
+    TreasuryAttestation => TreasuryAttestation::LEN,
+    SignerHeartbeats => SignerHeartbeats::MIN_LEN,
+    RelayerRegistry => RelayerRegistry::LEN,
+    RelayerRecord => RelayerRecord::LEN,
+    CircuitRegistry => CircuitRegistry::LEN,
+    DepositReceiptConfig => DepositReceiptConfig::LEN,
+    DepositCaller => DepositCaller::LEN,
+    ReferralRecord => ReferralRecord::LEN,
+    PoolUpdateProposal => PoolUpdateProposal::LEN,
+    AuditLog => AuditLog::LEN,
+    Watchtower => Watchtower::LEN,
+    BeaconRound => BeaconRound::LEN,
+    BeaconEntry => BeaconEntry::LEN,
+    MultisigProposalAction => MultisigProposalAction::MIN_LEN,
+    EncryptedMultisigAction => EncryptedMultisigAction::MIN_LEN,
+}
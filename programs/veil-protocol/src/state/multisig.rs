@@ -0,0 +1,773 @@
+//! Stealth multisigs, their proposals, actions, heartbeats and treasury
+//! attestations
+
+use crate::*;
+
+/// Stealth Multisig Vault - signers stored as commitments
+/// Zero-copy: the header is read in place instead of being deserialized onto
+/// the stack. Fields are ordered largest-alignment first so the `repr(C)`
+/// layout has no implicit padding.
+///
+/// The `total_signers` signer commitments (not public keys!) follow the
+/// header as 32-byte slots, each commitment = hash(signer_secret ||
+/// signer_pubkey). Use `load_with_commitments_mut` to reach them.
+#[account(zero_copy)]
+pub struct StealthMultisig {
+    /// Unique vault identifier
+    pub vault_id: [u8; 32],
+
+    /// Creator of the multisig
+    pub creator: Pubkey,
+
+    /// When the multisig was created
+    pub created_at: i64,
+
+    /// Number of proposals created
+    pub proposal_count: u32,
+
+    /// Number of signatures required
+    pub threshold: u8,
+
+    /// Total number of signers
+    pub total_signers: u8,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+
+    /// Commitment of the veto authority (e.g. a security council);
+    /// all zeroes when the multisig has none
+    pub veto_commitment: [u8; 32],
+
+    /// Seconds after a proposal reaches threshold during which it can be vetoed
+    pub veto_window: i64,
+
+    /// When the current veto authority was configured
+    pub veto_configured_at: i64,
+
+    /// Seconds between a proposal reaching threshold and becoming executable
+    pub timelock: i64,
+
+    /// Commitment to the hidden small-spend policy; transfers proven under
+    /// it need a single approval. All zeroes when the multisig has none
+    pub spend_limit_commitment: [u8; 32],
+
+    /// When the current small-spend policy was configured
+    pub spend_limit_configured_at: i64,
+
+    /// Length of a heartbeat epoch; 0 when liveness is not tracked
+    pub liveness_period: i64,
+
+    /// Delay between announcing and applying a threshold degradation
+    pub degrade_timelock: i64,
+
+    /// When liveness tracking was configured; epochs count from here
+    pub liveness_configured_at: i64,
+
+    /// When a threshold degradation was announced (0 = none pending)
+    pub degrade_announced_at: i64,
+
+    /// Bumped whenever the signer set changes; approvals given under an
+    /// earlier epoch no longer count
+    pub signer_epoch: u64,
+}
+
+impl StealthMultisig {
+    pub const HEADER_LEN: usize = 8 + // discriminator
+        32 + // vault_id
+        32 + // creator
+        8 + // created_at
+        4 + // proposal_count
+        1 + // threshold
+        1 + // total_signers
+        1 + // bump
+        1 + // version
+        32 + // veto_commitment
+        8 + // veto_window
+        8 + // veto_configured_at
+        8 + // timelock
+        32 + // spend_limit_commitment
+        8 + // spend_limit_configured_at
+        8 + // liveness_period
+        8 + // degrade_timelock
+        8 + // liveness_configured_at
+        8 + // degrade_announced_at
+        8; // signer_epoch
+
+    /// Account size holding `signers` signer commitments
+    pub const fn space(signers: usize) -> usize {
+        Self::HEADER_LEN + 32 * signers
+    }
+
+    pub fn has_veto_authority(&self) -> bool {
+        self.veto_commitment != [0u8; 32]
+    }
+
+    /// End of `proposal`'s veto window, if a veto authority is configured
+    /// The window opens when the proposal reaches threshold, or when the
+    /// veto authority was configured if that is later
+    pub fn veto_window_end(&self, proposal: &MultisigProposal) -> Option<i64> {
+        self.has_veto_authority().then(|| {
+            proposal
+                .threshold_reached_at
+                .max(self.veto_configured_at)
+                .saturating_add(self.veto_window)
+        })
+    }
+
+    pub fn has_spend_limit(&self) -> bool {
+        self.spend_limit_commitment != [0u8; 32]
+    }
+
+    pub fn tracks_liveness(&self) -> bool {
+        self.liveness_period > 0
+    }
+
+    /// Heartbeat epoch `now` falls in
+    pub fn heartbeat_epoch(&self, now: i64) -> u64 {
+        (now.saturating_sub(self.liveness_configured_at) / self.liveness_period.max(1)) as u64
+    }
+
+    /// Approvals `proposal` needs: one for a small spend proven under the
+    /// current policy, the full threshold otherwise. Proposals created
+    /// before the policy last changed fall back to the threshold
+    pub fn required_approvals(&self, proposal: &MultisigProposal) -> u8 {
+        let small_spend = proposal.is_small_spend == 1
+            && self.has_spend_limit()
+            && proposal.created_at >= self.spend_limit_configured_at;
+        if small_spend {
+            1
+        } else {
+            self.threshold
+        }
+    }
+}
+
+impl Versioned for StealthMultisig {
+    /// v2 added the veto authority, v3 the execution timelock, v4 the
+    /// small-spend policy, v5 signer liveness tracking and v6 the signer
+    /// epoch
+    const VERSION: u8 = 6;
+    const LEN: usize = StealthMultisig::HEADER_LEN;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
+
+impl ZeroCopyVersioned for StealthMultisig {
+    const VERSION_OFFSET: usize = 8 + std::mem::offset_of!(StealthMultisig, version);
+
+    const BORSH_V0_LEN: Option<usize> = Some(8 + // discriminator
+        32 + // vault_id
+        32 + // creator
+        1 + // threshold
+        1 + // total_signers
+        32 * BORSH_V0_SLOTS + // signer_commitments
+        8 + // created_at
+        4 + // proposal_count
+        1); // bump
+
+    fn header_len(version: u8) -> usize {
+        match version {
+            0 | 1 => 8 + std::mem::offset_of!(StealthMultisig, veto_commitment),
+            2 => 8 + std::mem::offset_of!(StealthMultisig, timelock),
+            3 => 8 + std::mem::offset_of!(StealthMultisig, spend_limit_commitment),
+            4 => 8 + std::mem::offset_of!(StealthMultisig, liveness_period),
+            5 => 8 + std::mem::offset_of!(StealthMultisig, signer_epoch),
+            _ => Self::LEN,
+        }
+    }
+
+    fn from_borsh_v0(data: &[u8]) -> Result<Vec<u8>> {
+        let old = StealthMultisigBorshV0::deserialize(&mut &data[8..])?;
+        let signers = old.total_signers as usize;
+        require!(
+            signers <= BORSH_V0_SLOTS && old.threshold as usize <= signers,
+            ErrorCode::InvalidAccountLayout
+        );
+
+        let mut state: StealthMultisig = bytemuck::Zeroable::zeroed();
+        state.vault_id = old.vault_id;
+        state.creator = old.creator;
+        state.created_at = old.created_at;
+        state.proposal_count = old.proposal_count;
+        state.threshold = old.threshold;
+        state.total_signers = old.total_signers;
+        state.bump = old.bump;
+        state.upgrade(0);
+        state.set_version(Self::VERSION);
+
+        Ok([
+            Self::DISCRIMINATOR,
+            bytemuck::bytes_of(&state),
+            &old.signer_commitments[..signers].concat(),
+        ]
+        .concat())
+    }
+}
+
+/// Signer and approval slots of the Borsh layouts multisigs and their
+/// proposals had before they became zero-copy
+const BORSH_V0_SLOTS: usize = 10;
+
+/// `StealthMultisig` as it was stored before it became zero-copy
+#[derive(AnchorDeserialize)]
+struct StealthMultisigBorshV0 {
+    vault_id: [u8; 32],
+    creator: Pubkey,
+    threshold: u8,
+    total_signers: u8,
+    signer_commitments: [[u8; 32]; BORSH_V0_SLOTS],
+    created_at: i64,
+    proposal_count: u32,
+    bump: u8,
+}
+
+/// `MultisigProposal` as it was stored before it became zero-copy
+#[derive(AnchorDeserialize)]
+struct MultisigProposalBorshV0 {
+    multisig: Pubkey,
+    proposal_id: [u8; 32],
+    instruction_hash: [u8; 32],
+    created_at: i64,
+    approval_count: u8,
+    approval_commitments: [[u8; 32]; BORSH_V0_SLOTS],
+    is_executed: bool,
+    executed_at: i64,
+    bump: u8,
+}
+
+/// Multisig proposal with stealth signatures
+/// Zero-copy, laid out without implicit padding like `StealthMultisig`.
+/// The `approval_count` approval commitments (proving approval without
+/// revealing the signer) trail the header; each `stealth_sign` reallocs the
+/// account by one slot.
+#[account(zero_copy)]
+pub struct MultisigProposal {
+    /// The multisig this proposal belongs to
+    pub multisig: Pubkey,
+
+    /// Unique proposal identifier
+    pub proposal_id: [u8; 32],
+
+    /// H(DOMAIN_MULTISIG_ACTION, borsh(action)) of the action in the
+    /// companion `MultisigProposalAction` account
+    pub instruction_hash: [u8; 32],
+
+    /// When the proposal was created
+    pub created_at: i64,
+
+    /// When the proposal was executed
+    pub executed_at: i64,
+
+    /// Number of approvals received
+    pub approval_count: u8,
+
+    /// Whether the proposal has been executed (0 = pending, 1 = executed)
+    pub is_executed: u8,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+
+    /// `MultisigActionKind` of the proposal's action
+    pub kind: u8,
+
+    /// Whether a small-spend proof lowered the approvals needed to one
+    /// (1 = proven). Taken from padding, so older proposals read 0
+    pub is_small_spend: u8,
+
+    /// Explicit padding to an 8-byte boundary
+    pub _padding: [u8; 2],
+
+    /// When the proposal reached the multisig's threshold
+    pub threshold_reached_at: i64,
+
+    /// When the veto authority vetoed the proposal (0 = not vetoed)
+    pub vetoed_at: i64,
+
+    /// Earliest execution time, set when the final approval lands
+    pub executable_after: i64,
+
+    /// The multisig's `signer_epoch` the approvals were given under
+    pub signer_epoch: u64,
+}
+
+impl MultisigProposal {
+    pub const HEADER_LEN: usize = 8 + // discriminator
+        32 + // multisig
+        32 + // proposal_id
+        32 + // instruction_hash
+        8 + // created_at
+        8 + // executed_at
+        1 + // approval_count
+        1 + // is_executed
+        1 + // bump
+        1 + // version
+        1 + // kind
+        1 + // is_small_spend
+        2 + // _padding
+        8 + // threshold_reached_at
+        8 + // vetoed_at
+        8 + // executable_after
+        8; // signer_epoch
+
+    /// Account size holding `approvals` approval commitments
+    pub const fn space(approvals: usize) -> usize {
+        Self::HEADER_LEN + 32 * approvals
+    }
+}
+
+impl Versioned for MultisigProposal {
+    /// v2 added the typed action `kind`, v3 the veto timestamps, v4
+    /// `executable_after` and v5 the signer epoch
+    const VERSION: u8 = 5;
+    const LEN: usize = MultisigProposal::HEADER_LEN;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    /// Untyped proposals only carried an opaque instruction hash
+    fn upgrade(&mut self, from_version: u8) {
+        if from_version < 2 {
+            self.kind = MultisigActionKind::Custom as u8;
+        }
+    }
+}
+
+impl ZeroCopyVersioned for MultisigProposal {
+    const VERSION_OFFSET: usize = 8 + std::mem::offset_of!(MultisigProposal, version);
+
+    const BORSH_V0_LEN: Option<usize> = Some(8 + // discriminator
+        32 + // multisig
+        32 + // proposal_id
+        32 + // instruction_hash
+        8 + // created_at
+        1 + // approval_count
+        32 * BORSH_V0_SLOTS + // approval_commitments
+        1 + // is_executed
+        8 + // executed_at
+        1); // bump
+
+    fn header_len(version: u8) -> usize {
+        match version {
+            0..=2 => 8 + std::mem::offset_of!(MultisigProposal, threshold_reached_at),
+            3 => 8 + std::mem::offset_of!(MultisigProposal, executable_after),
+            4 => 8 + std::mem::offset_of!(MultisigProposal, signer_epoch),
+            _ => Self::LEN,
+        }
+    }
+
+    fn from_borsh_v0(data: &[u8]) -> Result<Vec<u8>> {
+        let old = MultisigProposalBorshV0::deserialize(&mut &data[8..])?;
+        let approvals = old.approval_count as usize;
+        require!(approvals <= BORSH_V0_SLOTS, ErrorCode::InvalidAccountLayout);
+
+        let mut state: MultisigProposal = bytemuck::Zeroable::zeroed();
+        state.multisig = old.multisig;
+        state.proposal_id = old.proposal_id;
+        state.instruction_hash = old.instruction_hash;
+        state.created_at = old.created_at;
+        state.executed_at = old.executed_at;
+        state.approval_count = old.approval_count;
+        state.is_executed = old.is_executed as u8;
+        state.bump = old.bump;
+        state.upgrade(0);
+        state.set_version(Self::VERSION);
+
+        Ok([
+            Self::DISCRIMINATOR,
+            bytemuck::bytes_of(&state),
+            &old.approval_commitments[..approvals].concat(),
+        ]
+        .concat())
+    }
+}
+
+/// Structured action of a multisig proposal, stored for wallets to render
+#[account]
+pub struct MultisigProposalAction {
+    /// The proposal this action belongs to
+    pub proposal: Pubkey,
+
+    /// What the proposal does when executed
+    pub action: MultisigAction,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl MultisigProposalAction {
+    /// Size with the smallest action (an empty signer change)
+    pub const MIN_LEN: usize = 8 + // discriminator
+        32 + // proposal
+        1 + 4 + 4 + 1 + // action
+        1 + // bump
+        1; // version
+
+    /// Account size holding `action` (variable for signer changes)
+    pub fn space(action: &MultisigAction) -> usize {
+        8 + // discriminator
+        32 + // proposal
+        action.serialized_len() + // action
+        1 + // bump
+        1 // version
+    }
+}
+
+/// Threshold-encrypted action of a multisig proposal
+#[account]
+pub struct EncryptedMultisigAction {
+    /// The proposal this action belongs to
+    pub proposal: Pubkey,
+
+    /// H(DOMAIN_PROPOSAL_KEY, K)
+    pub key_commitment: [u8; 32],
+
+    /// Shares needed to reconstruct K (multisig threshold at creation)
+    pub threshold: u8,
+
+    /// H(DOMAIN_PROPOSAL_KEY_SHARE, proposal, signer_commitment, index, share)
+    /// per signer slot
+    pub share_commitments: Vec<[u8; 32]>,
+
+    /// borsh(MultisigAction) XOR keystream(K)
+    pub ciphertext: Vec<u8>,
+
+    /// K, published at execution
+    pub decryption_key: Option<[u8; 32]>,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl EncryptedMultisigAction {
+    /// Size with no shares and no ciphertext
+    pub const MIN_LEN: usize = 8 + // discriminator
+        32 + // proposal
+        32 + // key_commitment
+        1 + // threshold
+        4 + // share_commitments (empty)
+        4 + // ciphertext (empty)
+        1 + 32 + // decryption_key
+        1 + // bump
+        1; // version
+
+    /// Account size for `signers` share commitments and a ciphertext of `len` bytes
+    pub const fn space(signers: usize, len: usize) -> usize {
+        Self::MIN_LEN + 32 * signers + len
+    }
+}
+
+/// What a multisig proposal does when executed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum MultisigAction {
+    /// Send lamports from the multisig vault
+    TransferSol { recipient: Pubkey, lamports: u64 },
+    /// Send SPL tokens from the multisig vault
+    TransferToken {
+        mint: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+        decimals: u8,
+    },
+    /// Add and/or remove signer commitments and set a new threshold
+    ChangeSigners {
+        add: Vec<[u8; 32]>,
+        remove: Vec<[u8; 32]>,
+        new_threshold: u8,
+    },
+    /// Upgrade a program the multisig is upgrade authority of
+    ProgramUpgrade {
+        program: Pubkey,
+        buffer: Pubkey,
+        spill: Pubkey,
+    },
+    /// Arbitrary instruction, approved by hash only
+    Custom {
+        program_id: Pubkey,
+        instruction_hash: [u8; 32],
+    },
+    /// Set (or clear, with a zero commitment) the veto authority
+    SetVeto {
+        veto_commitment: [u8; 32],
+        veto_window: i64,
+    },
+    /// Set the delay between reaching threshold and execution
+    SetTimelock { seconds: i64 },
+    /// Invoke a pool-admin instruction of this program on `pool`, signed
+    /// by the multisig's authority PDA
+    PoolAdmin {
+        pool: Pubkey,
+        instruction_data: Vec<u8>,
+    },
+    /// Set (or clear, with a zero commitment) the hidden small-spend policy
+    SetSpendLimit { limit_commitment: [u8; 32] },
+    /// Track signer heartbeats every `period` seconds and let the threshold
+    /// degrade `degrade_timelock` after a period without quorum (0 disables)
+    SetLiveness { period: i64, degrade_timelock: i64 },
+    /// Up to MAX_MULTISIG_BATCH_STEPS steps executed in order, all or none
+    Batch { steps: Vec<MultisigStep> },
+}
+
+/// One step of a `Batch` action
+/// Only `PoolAdmin` steps run on-chain. The other kinds are carried out by
+/// the client, which a batch cannot make all-or-nothing, so proposals
+/// holding them are rejected (see `validate_multisig_action`)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum MultisigStep {
+    TransferSol { recipient: Pubkey, lamports: u64 },
+    TransferToken {
+        mint: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+        decimals: u8,
+    },
+    Custom {
+        program_id: Pubkey,
+        instruction_hash: [u8; 32],
+    },
+    /// Takes the next `account_count` remaining accounts, pool first
+    PoolAdmin {
+        pool: Pubkey,
+        instruction_data: Vec<u8>,
+        account_count: u8,
+    },
+}
+
+impl MultisigStep {
+    /// The standalone action this step performs
+    pub fn action(&self) -> MultisigAction {
+        match self {
+            MultisigStep::TransferSol { recipient, lamports } => MultisigAction::TransferSol {
+                recipient: *recipient,
+                lamports: *lamports,
+            },
+            MultisigStep::TransferToken { mint, recipient, amount, decimals } => MultisigAction::TransferToken {
+                mint: *mint,
+                recipient: *recipient,
+                amount: *amount,
+                decimals: *decimals,
+            },
+            MultisigStep::Custom { program_id, instruction_hash } => MultisigAction::Custom {
+                program_id: *program_id,
+                instruction_hash: *instruction_hash,
+            },
+            MultisigStep::PoolAdmin { pool, instruction_data, .. } => MultisigAction::PoolAdmin {
+                pool: *pool,
+                instruction_data: instruction_data.clone(),
+            },
+        }
+    }
+
+    /// Remaining accounts the step consumes at execution
+    pub fn account_count(&self) -> usize {
+        match self {
+            MultisigStep::PoolAdmin { account_count, .. } => *account_count as usize,
+            _ => 0,
+        }
+    }
+
+    /// Borsh-encoded size
+    pub fn serialized_len(&self) -> usize {
+        match self {
+            MultisigStep::PoolAdmin { .. } => self.action().serialized_len() + 1,
+            _ => self.action().serialized_len(),
+        }
+    }
+}
+
+/// Discriminant of `MultisigAction`, stored in the proposal header
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MultisigActionKind {
+    TransferSol,
+    TransferToken,
+    ChangeSigners,
+    ProgramUpgrade,
+    Custom,
+    /// Threshold-encrypted; replaced by the real kind at execution
+    Encrypted,
+    SetVeto,
+    SetTimelock,
+    PoolAdmin,
+    SetSpendLimit,
+    SetLiveness,
+    Batch,
+}
+
+/// One signer's Shamir share of an encrypted proposal's key
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProposalKeyShare {
+    /// Evaluation point: signer slot index + 1
+    pub index: u8,
+    /// Share bytes (one GF(256) evaluation per key byte)
+    pub share: [u8; 32],
+}
+
+impl MultisigAction {
+    pub fn kind(&self) -> MultisigActionKind {
+        match self {
+            MultisigAction::TransferSol { .. } => MultisigActionKind::TransferSol,
+            MultisigAction::TransferToken { .. } => MultisigActionKind::TransferToken,
+            MultisigAction::ChangeSigners { .. } => MultisigActionKind::ChangeSigners,
+            MultisigAction::ProgramUpgrade { .. } => MultisigActionKind::ProgramUpgrade,
+            MultisigAction::Custom { .. } => MultisigActionKind::Custom,
+            MultisigAction::SetVeto { .. } => MultisigActionKind::SetVeto,
+            MultisigAction::SetTimelock { .. } => MultisigActionKind::SetTimelock,
+            MultisigAction::PoolAdmin { .. } => MultisigActionKind::PoolAdmin,
+            MultisigAction::SetSpendLimit { .. } => MultisigActionKind::SetSpendLimit,
+            MultisigAction::SetLiveness { .. } => MultisigActionKind::SetLiveness,
+            MultisigAction::Batch { .. } => MultisigActionKind::Batch,
+        }
+    }
+
+    /// Borsh-encoded size
+    pub fn serialized_len(&self) -> usize {
+        1 + match self {
+            MultisigAction::TransferSol { .. } => 32 + 8,
+            MultisigAction::TransferToken { .. } => 32 + 32 + 8 + 1,
+            MultisigAction::ChangeSigners { add, remove, .. } => {
+                4 + 32 * add.len() + 4 + 32 * remove.len() + 1
+            }
+            MultisigAction::ProgramUpgrade { .. } => 32 + 32 + 32,
+            MultisigAction::Custom { .. } => 32 + 32,
+            MultisigAction::SetVeto { .. } => 32 + 8,
+            MultisigAction::SetTimelock { .. } => 8,
+            MultisigAction::PoolAdmin { instruction_data, .. } => 32 + 4 + instruction_data.len(),
+            MultisigAction::SetSpendLimit { .. } => 32,
+            MultisigAction::SetLiveness { .. } => 8 + 8,
+            MultisigAction::Batch { steps } => 4 + steps.iter().map(MultisigStep::serialized_len).sum::<usize>(),
+        }
+    }
+
+    /// Mint (default for SOL) and amount of a transfer action
+    pub fn spend(&self) -> Option<(Pubkey, u64)> {
+        match self {
+            MultisigAction::TransferSol { lamports, .. } => Some((Pubkey::default(), *lamports)),
+            MultisigAction::TransferToken { mint, amount, .. } => Some((*mint, *amount)),
+            _ => None,
+        }
+    }
+}
+
+/// Anonymous signer heartbeats of one multisig in one epoch. Each signer
+/// posts at most one, told apart only by a per-epoch nullifier
+#[account]
+pub struct SignerHeartbeats {
+    /// The multisig the heartbeats belong to
+    pub multisig: Pubkey,
+
+    /// Heartbeat epoch (see `StealthMultisig::heartbeat_epoch`)
+    pub epoch: u64,
+
+    /// Heartbeat nullifiers posted this epoch, one per live signer
+    pub nullifiers: Vec<[u8; 32]>,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl SignerHeartbeats {
+    /// Size with no heartbeats
+    pub const MIN_LEN: usize = 8 + // discriminator
+        32 + // multisig
+        8 + // epoch
+        4 + // nullifiers (empty)
+        1 + // bump
+        1; // version
+
+    /// Account size holding one heartbeat per signer
+    pub const fn space(signers: usize) -> usize {
+        Self::MIN_LEN + 32 * signers
+    }
+
+    pub fn live_signers(&self) -> u8 {
+        self.nullifiers.len() as u8
+    }
+}
+
+/// Range-proven band of a stealth multisig's shielded treasury at one
+/// state of a pool: the notes its authority PDA owns there are worth
+/// between `min_balance` and `max_balance`, with the exact figure hidden
+#[account]
+pub struct TreasuryAttestation {
+    /// The attested multisig
+    pub multisig: Pubkey,
+
+    /// Shielded pool holding the treasury notes
+    pub pool: Pubkey,
+
+    /// Pool Merkle root the proof was made against
+    pub merkle_root: [u8; 32],
+
+    /// Nullifiers recorded at that root
+    pub nullifier_count: u32,
+
+    /// Lower bound of the stated band, in lamports
+    pub min_balance: u64,
+
+    /// Upper bound of the stated band, in lamports
+    pub max_balance: u64,
+
+    /// Hash of the proof and its public signals
+    pub proof_hash: [u8; 32],
+
+    /// Key that submitted the proof
+    pub published_by: Pubkey,
+
+    /// When the proof was published
+    pub published_at: i64,
+
+    /// Slot the proof was published in
+    pub published_slot: u64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl TreasuryAttestation {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // multisig
+        32 + // pool
+        32 + // merkle_root
+        4 + // nullifier_count
+        8 + // min_balance
+        8 + // max_balance
+        32 + // proof_hash
+        32 + // published_by
+        8 + // published_at
+        8 + // published_slot
+        1 + // bump
+        1; // version
+
+    /// Whether this attestation covers the pool's current state
+    pub fn is_current(&self, pool: &ShieldedPool) -> bool {
+        self.merkle_root == pool.merkle_root && self.nullifier_count == pool.nullifier_count
+    }
+}
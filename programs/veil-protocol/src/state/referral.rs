@@ -0,0 +1,105 @@
+//! Referral programs and referrers
+
+use crate::*;
+
+/// Referral incentives of a shielded pool; holds the reward lamports
+#[account]
+pub struct ReferralProgram {
+    /// The pool whose deposits are rewarded
+    pub pool: Pubkey,
+
+    /// Pool authority that created the program
+    pub authority: Pubkey,
+
+    /// Lamports paid per referred deposit
+    pub reward_per_deposit: u64,
+
+    /// Referrers registered
+    pub referrers: u64,
+
+    /// Referred deposits paid out so far
+    pub rewarded_deposits: u64,
+
+    /// When the program was created
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+
+    /// Lamports a deposit must move into the pool vault to credit a referrer
+    pub min_deposit_lamports: u64,
+
+    /// Most deposits a single referrer is ever credited with
+    pub max_deposits_per_referrer: u64,
+}
+
+impl ReferralProgram {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // authority
+        8 + // reward_per_deposit
+        8 + // referrers
+        8 + // rewarded_deposits
+        8 + // created_at
+        1 + // bump
+        1 + // version
+        8 + // min_deposit_lamports
+        8; // max_deposits_per_referrer
+}
+
+impl Versioned for ReferralProgram {
+    /// v2 added the deposit minimum and per-referrer cap
+    const VERSION: u8 = 2;
+    const LEN: usize = ReferralProgram::LEN;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    /// Programs from before the deposit minimum credit no referrer until
+    /// the authority sets one with `configure_referral_program`
+    fn upgrade(&mut self, _from_version: u8) {}
+}
+
+/// Referrer registered on a pool under a commitment to its secret
+#[account]
+pub struct ReferralRecord {
+    /// The pool the referrer brings deposits to
+    pub pool: Pubkey,
+
+    /// Commitment to the referrer's secret (proven on claim)
+    pub referral_commitment: [u8; 32],
+
+    /// Deposits that credited this referrer
+    pub deposit_count: u64,
+
+    /// Deposits already paid out
+    pub claimed_count: u64,
+
+    /// When the referrer registered
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl ReferralRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // referral_commitment
+        8 + // deposit_count
+        8 + // claimed_count
+        8 + // created_at
+        1 + // bump
+        1; // version
+}
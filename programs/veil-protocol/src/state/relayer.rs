@@ -0,0 +1,136 @@
+//! Relayer registry and bonded relayers
+
+use crate::*;
+
+/// Relayers bonded to one shielded pool, with its fee cap and slashing totals
+#[account]
+pub struct RelayerRegistry {
+    /// The pool whose withdrawals are relayed
+    pub pool: Pubkey,
+
+    /// Pool authority, allowed to slash relayers
+    pub authority: Pubkey,
+
+    /// Stake each relayer bonds (lamports)
+    pub min_stake: u64,
+
+    /// Highest fee quote a relayer may post (lamports per withdrawal)
+    pub max_fee_lamports: u64,
+
+    /// Number of registered (not yet closed) relayers
+    pub relayer_count: u32,
+
+    /// Number of relayers slashed
+    pub slashed_count: u32,
+
+    /// Fees paid to relayers from the pool vault
+    pub total_fees_paid: u64,
+
+    /// When the registry was created
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+
+    /// How long a relayer's solvency proof lets it take jobs (seconds);
+    /// 0 when the registry does not require solvency proofs
+    pub solvency_period: i64,
+}
+
+impl RelayerRegistry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // authority
+        8 + // min_stake
+        8 + // max_fee_lamports
+        4 + // relayer_count
+        4 + // slashed_count
+        8 + // total_fees_paid
+        8 + // created_at
+        1 + // bump
+        1 + // version
+        8; // solvency_period
+}
+
+/// A registered relayer; holds its bonded stake as lamports
+#[account]
+pub struct RelayerRecord {
+    /// Registry this relayer is bonded to
+    pub registry: Pubkey,
+
+    /// Relayer's signing key, paid fees
+    pub relayer: Pubkey,
+
+    /// Bonded stake (lamports, held by this account)
+    pub stake_lamports: u64,
+
+    /// Maximum fee charged per withdrawal (lamports)
+    pub fee_quote: u64,
+
+    /// Fees received from the pool vault
+    pub fees_claimed: u64,
+
+    /// Withdrawals submitted
+    pub withdrawals_relayed: u32,
+
+    /// When the relayer registered
+    pub registered_at: i64,
+
+    /// When the relayer requested exit (0 = active)
+    pub exit_requested_at: i64,
+
+    /// Whether the stake was slashed
+    pub is_slashed: bool,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+
+    /// Commitment to the relayer's outstanding quoted-but-unsettled fee
+    /// obligations, as of its latest solvency proof
+    pub obligations_commitment: [u8; 32],
+
+    /// When the latest solvency proof stops covering new jobs
+    pub solvent_until: i64,
+}
+
+impl RelayerRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // registry
+        32 + // relayer
+        8 + // stake_lamports
+        8 + // fee_quote
+        8 + // fees_claimed
+        4 + // withdrawals_relayed
+        8 + // registered_at
+        8 + // exit_requested_at
+        1 + // is_slashed
+        1 + // bump
+        1 + // version
+        32 + // obligations_commitment
+        8; // solvent_until
+
+    /// Whether the relayer may submit withdrawals
+    pub fn is_active(&self) -> bool {
+        !self.is_slashed && self.exit_requested_at == 0
+    }
+
+    /// Whether the relayer may take new jobs under `registry`'s solvency rule
+    pub fn is_solvent(&self, registry: &RelayerRegistry, now: i64) -> bool {
+        registry.solvency_period == 0 || now < self.solvent_until
+    }
+}
+
+/// Opening of the fee commitment a relayed withdrawal is bound to
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RelayerFeeOpening {
+    /// Fee paid to the relayer from the pool vault
+    pub lamports: u64,
+    /// Blinding factor hiding the fee until submission
+    pub blinding: [u8; 32],
+}
@@ -0,0 +1,135 @@
+//! Rate-limiting nullifier groups
+
+use crate::*;
+
+/// RLN group - stake-backed membership for rate-limited anonymous actions
+#[account]
+pub struct RlnGroup {
+    /// Unique group identifier
+    pub group_id: [u8; 32],
+
+    /// Creator of the group
+    pub creator: Pubkey,
+
+    /// Stake each member must bond (forfeited on slashing)
+    pub stake_lamports: u64,
+
+    /// Length of a rate-limit epoch in seconds
+    pub epoch_length: i64,
+
+    /// Maximum actions per identity per epoch
+    pub message_limit: u32,
+
+    /// Merkle root of member identity commitments
+    pub members_root: [u8; 32],
+
+    /// Number of registered members
+    pub member_count: u32,
+
+    /// Number of members slashed for exceeding the limit
+    pub slashed_count: u32,
+
+    /// When the group was created
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl RlnGroup {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // group_id
+        32 + // creator
+        8 + // stake_lamports
+        8 + // epoch_length
+        4 + // message_limit
+        32 + // members_root
+        4 + // member_count
+        4 + // slashed_count
+        8 + // created_at
+        1 + // bump
+        1; // version
+}
+
+/// RLN member - holds the member's bonded stake
+#[account]
+pub struct RlnMember {
+    /// The group this member belongs to
+    pub group: Pubkey,
+
+    /// Identity commitment = H(DOMAIN_RLN_IDENTITY, identity_secret)
+    pub identity_commitment: [u8; 32],
+
+    /// Leaf index in the members tree
+    pub member_index: u32,
+
+    /// Currently bonded stake
+    pub stake_lamports: u64,
+
+    /// When the member registered
+    pub registered_at: i64,
+
+    /// Whether the member has been slashed
+    pub is_slashed: bool,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl RlnMember {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // group
+        32 + // identity_commitment
+        4 + // member_index
+        8 + // stake_lamports
+        8 + // registered_at
+        1 + // is_slashed
+        1 + // bump
+        1; // version
+}
+
+/// RLN nullifier record - the first share revealed under a nullifier
+#[account]
+pub struct RlnNullifierRecord {
+    /// The group this nullifier belongs to
+    pub group: Pubkey,
+
+    /// Nullifier = H(a1), unique per (identity, epoch, message_id)
+    pub nullifier: [u8; 32],
+
+    /// Epoch the action was taken in
+    pub epoch: u64,
+
+    /// Share x-coordinate = H(signal)
+    pub share_x: [u8; 32],
+
+    /// Share y-coordinate = identity_secret + a1 * x
+    pub share_y: [u8; 32],
+
+    /// When the action was recorded
+    pub recorded_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl RlnNullifierRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // group
+        32 + // nullifier
+        8 + // epoch
+        32 + // share_x
+        32 + // share_y
+        8 + // recorded_at
+        1 + // bump
+        1; // version
+}
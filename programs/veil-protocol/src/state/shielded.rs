@@ -0,0 +1,754 @@
+//! Shielded pools, notes, nullifiers and pool-level records
+
+use crate::*;
+
+impl ZeroCopyVersioned for TreeCheckpoint {
+    const VERSION_OFFSET: usize = 8 + std::mem::offset_of!(TreeCheckpoint, version);
+}
+
+impl ZeroCopyVersioned for NullifierFilter {
+    const VERSION_OFFSET: usize = 8 + std::mem::offset_of!(NullifierFilter, version);
+}
+
+/// Shielded Stake Pool with Merkle tree for note commitments
+#[account]
+pub struct ShieldedPool {
+    /// Unique pool identifier
+    pub pool_id: [u8; 32],
+
+    /// Creator of the pool
+    pub creator: Pubkey,
+
+    /// Reward rate in basis points per epoch
+    pub reward_rate_bps: u16,
+
+    /// Number of epochs for lockup
+    pub lockup_epochs: u8,
+
+    /// Current Merkle root of all note commitments
+    pub merkle_root: [u8; 32],
+
+    /// Index for next note insertion
+    pub next_note_index: u32,
+
+    /// Total number of notes created
+    pub total_notes: u32,
+
+    /// Number of nullifiers recorded (notes spent)
+    pub nullifier_count: u32,
+
+    /// When the pool was created
+    pub created_at: i64,
+
+    /// Whether the pool is active
+    pub is_active: bool,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+
+    /// Anonymity-metrics epoch (unix time / ANONYMITY_EPOCH_SECONDS) the
+    /// counters below were last rolled to
+    pub metrics_epoch: u64,
+
+    /// Deposits in the current metrics epoch
+    pub deposits_this_epoch: u32,
+
+    /// Deposits in the previous metrics epoch
+    pub deposits_last_epoch: u32,
+
+    /// Deposit counts of recent epochs, indexed by epoch % buckets
+    pub deposit_histogram: [u32; DEPOSIT_HISTOGRAM_BUCKETS],
+
+    /// Distinct tree roots withdrawals were proven against
+    /// (counted when the root changes between withdrawals)
+    pub unique_withdrawal_roots_used: u32,
+
+    /// Root the last withdrawal was proven against
+    pub last_withdrawal_root: [u8; 32],
+
+    /// Age after which an unspent note may be swept to `treasury`
+    /// (0 = notes never expire)
+    pub note_expiry_seconds: i64,
+
+    /// Treasury that receives swept funds
+    pub treasury: Pubkey,
+
+    /// Notes below this index were swept and can no longer be spent
+    pub swept_through_index: u32,
+
+    /// End (exclusive) of the announced sweep (0 = none announced)
+    pub pending_sweep_through: u32,
+
+    /// When the announced sweep may execute
+    pub sweep_executable_at: i64,
+
+    /// Admin authority (the creator until `set_pool_authority`), e.g. a
+    /// multisig authority PDA
+    pub authority: Pubkey,
+
+    /// Spends must update the pool's nullifier filter
+    pub has_nullifier_filter: bool,
+
+    /// Set in the account data while an instruction is mutating the pool
+    /// (see `lock_pool`); never true in a persisted, settled state
+    pub processing: bool,
+
+    /// Counted in `ProtocolStats`; pool instructions must pass that account
+    pub in_protocol_stats: bool,
+
+    /// Decoy notes `shield_deposit_with_decoys` pads each real note with
+    /// (0 = decoy deposits disabled)
+    pub decoy_outputs: u8,
+
+    /// Program CPI'd with each withdrawal's (recipient, nullifier) before
+    /// funds are released (default = no screening)
+    pub screener_program: Pubkey,
+
+    /// Breakpoints in use at the front of `reward_curve`
+    pub reward_curve_len: u8,
+
+    /// Reward rate schedule replacing the flat `reward_rate_bps` from each
+    /// point's `starts_at` on; `reward_rate_bps` applies before the first
+    /// point (see `reward_rate_at`)
+    pub reward_curve: [RewardCurvePoint; MAX_REWARD_CURVE_POINTS],
+
+    /// Share of an early exit (`shield_withdraw_early`) kept as penalty
+    /// (0 = early exits disabled)
+    pub early_exit_penalty_bps: u16,
+
+    /// Early-exit penalties left in the vault to back reward claims
+    pub reward_reserve_lamports: u64,
+}
+
+impl ShieldedPool {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool_id
+        32 + // creator
+        2 + // reward_rate_bps
+        1 + // lockup_epochs
+        32 + // merkle_root
+        4 + // next_note_index
+        4 + // total_notes
+        4 + // nullifier_count
+        8 + // created_at
+        1 + // is_active
+        1 + // bump
+        1 + // version
+        8 + // metrics_epoch
+        4 + // deposits_this_epoch
+        4 + // deposits_last_epoch
+        4 * DEPOSIT_HISTOGRAM_BUCKETS + // deposit_histogram
+        4 + // unique_withdrawal_roots_used
+        32 + // last_withdrawal_root
+        8 + // note_expiry_seconds
+        32 + // treasury
+        4 + // swept_through_index
+        4 + // pending_sweep_through
+        8 + // sweep_executable_at
+        32 + // authority
+        1 + // has_nullifier_filter
+        1 + // processing
+        1 + // in_protocol_stats
+        1 + // decoy_outputs
+        32 + // screener_program
+        1 + // reward_curve_len
+        RewardCurvePoint::LEN * MAX_REWARD_CURVE_POINTS + // reward_curve
+        2 + // early_exit_penalty_bps
+        8; // reward_reserve_lamports
+
+    /// Advance the anonymity counters to `epoch`, clearing epochs that saw
+    /// no deposits
+    pub fn roll_anonymity_metrics(&mut self, epoch: u64) {
+        if epoch <= self.metrics_epoch {
+            return;
+        }
+
+        self.deposits_last_epoch = if epoch == self.metrics_epoch + 1 {
+            self.deposits_this_epoch
+        } else {
+            0
+        };
+        self.deposits_this_epoch = 0;
+
+        let elapsed = (epoch - self.metrics_epoch).min(DEPOSIT_HISTOGRAM_BUCKETS as u64);
+        for offset in 0..elapsed {
+            self.deposit_histogram[((epoch - offset) % DEPOSIT_HISTOGRAM_BUCKETS as u64) as usize] = 0;
+        }
+        self.metrics_epoch = epoch;
+    }
+
+    /// Count a deposit in the anonymity metrics
+    pub fn record_deposit(&mut self, now: i64) {
+        let epoch = anonymity_epoch(now);
+        self.roll_anonymity_metrics(epoch);
+        self.deposits_this_epoch += 1;
+        self.deposit_histogram[(epoch % DEPOSIT_HISTOGRAM_BUCKETS as u64) as usize] += 1;
+    }
+
+    /// Root spend proofs commit to: the Merkle root, bound to the sweep
+    /// frontier once notes have been swept so their circuits reject notes
+    /// below `swept_through_index`
+    pub fn spend_anchor(&self) -> [u8; 32] {
+        if self.swept_through_index == 0 {
+            return self.merkle_root;
+        }
+        hash_domain(
+            DOMAIN_SPEND_ANCHOR,
+            &[&self.merkle_root, &self.swept_through_index.to_le_bytes()],
+        )
+    }
+
+    /// Breakpoints of the reward curve in use
+    pub fn reward_curve_points(&self) -> &[RewardCurvePoint] {
+        &self.reward_curve[..self.reward_curve_len as usize]
+    }
+
+    /// Reward rate a note deposited at unix time `time` earns: the rate of
+    /// the last curve point started by then, `reward_rate_bps` before the
+    /// first one
+    pub fn reward_rate_at(&self, time: i64) -> u16 {
+        self.reward_curve_points()
+            .iter()
+            .rev()
+            .find(|point| point.starts_at <= time)
+            .map_or(self.reward_rate_bps, |point| point.rate_bps)
+    }
+
+    /// Penalty an early exit releasing `gross_lamports` pays into the
+    /// reward reserve
+    pub fn early_exit_penalty(&self, gross_lamports: u64) -> u64 {
+        (gross_lamports as u128 * self.early_exit_penalty_bps as u128 / 10_000) as u64
+    }
+
+    /// Whether withdrawals must pass the pool's screener
+    pub fn screens_withdrawals(&self) -> bool {
+        self.screener_program != Pubkey::default()
+    }
+
+    /// Count a withdrawal proven against the current root
+    pub fn record_withdrawal(&mut self) {
+        if self.merkle_root != self.last_withdrawal_root || self.unique_withdrawal_roots_used == 0 {
+            self.unique_withdrawal_roots_used += 1;
+            self.last_withdrawal_root = self.merkle_root;
+        }
+    }
+}
+
+impl Versioned for ShieldedPool {
+    /// v2 added the anonymity metrics, v3 note expiry and sweeps, v4 the
+    /// transferable admin authority, v5 the nullifier filter flag, v6 the
+    /// processing lock, v7 protocol stats membership, v8 decoy outputs,
+    /// v9 the withdrawal screener, v10 the reward curve, v11 early exits
+    const VERSION: u8 = 11;
+    const LEN: usize = ShieldedPool::LEN;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    /// Metrics start from zero; the first deposit rolls them to the current
+    /// epoch. Notes never expire until the authority configures it, the
+    /// creator stays the authority, no nullifier filter is kept and the
+    /// pool is not counted in the protocol stats until registered. Decoy
+    /// deposits stay disabled and the curve stays empty, so notes keep
+    /// earning the flat rate. Early exits stay disabled
+    fn upgrade(&mut self, from_version: u8) {
+        if from_version < 4 {
+            self.authority = self.creator;
+        }
+    }
+}
+
+/// Breakpoint of a pool's reward curve
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RewardCurvePoint {
+    /// Deposits from this unix time on earn `rate_bps`
+    pub starts_at: i64,
+
+    /// Reward rate in basis points per epoch
+    pub rate_bps: u16,
+}
+
+impl RewardCurvePoint {
+    pub const LEN: usize = 8 + 2;
+}
+
+/// Shielded Note - represents a hidden stake amount
+/// commitment = H(amount || blinding || owner_commitment)
+#[account]
+pub struct ShieldedNote {
+    /// The pool this note belongs to
+    pub pool: Pubkey,
+
+    /// Note commitment (hides amount)
+    pub commitment: [u8; 32],
+
+    /// Encrypted note data (only owner can decrypt)
+    /// Contains: amount, blinding, unlock_time
+    pub encrypted_data: [u8; 64],
+
+    /// Index in the Merkle tree
+    pub note_index: u32,
+
+    /// When the note was created
+    pub created_at: i64,
+
+    /// When the note can be withdrawn
+    pub unlock_at: i64,
+
+    /// Whether this note has been spent (nullifier submitted)
+    pub is_spent: bool,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+
+    /// The pool's reward rate when the note was created, the rate its
+    /// reward proof evaluates the curve to (0 on notes migrated from
+    /// before reward curves, which earn the flat rate)
+    pub reward_rate_bps: u16,
+}
+
+impl ShieldedNote {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // commitment
+        64 + // encrypted_data
+        4 + // note_index
+        8 + // created_at
+        8 + // unlock_at
+        1 + // is_spent
+        1 + // bump
+        1 + // version
+        2; // reward_rate_bps
+}
+
+/// Nullifier record - prevents double-spend of notes
+/// Each spent note generates a unique nullifier
+#[account]
+pub struct NullifierRecord {
+    /// The pool this nullifier belongs to
+    pub pool: Pubkey,
+
+    /// The nullifier hash = H(note_commitment || owner_secret)
+    pub nullifier: [u8; 32],
+
+    /// When the nullifier was recorded (note spent)
+    pub spent_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+
+    /// What a relayed withdrawal spent the note on (see
+    /// `compute_relayed_outcome`), so a retry can tell it landed; zero for
+    /// every other spend
+    pub outcome: [u8; 32],
+}
+
+impl NullifierRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // nullifier
+        8 + // spent_at
+        1 + // bump
+        1 + // version
+        32; // outcome
+
+    /// Whether the nullifier has been written, for handlers that create
+    /// the record with `init_if_needed`
+    pub fn is_recorded(&self) -> bool {
+        self.pool != Pubkey::default()
+    }
+}
+
+impl Versioned for NullifierRecord {
+    /// v2 added the relayed-withdrawal outcome; older records read zero,
+    /// which no retry matches
+    const VERSION: u8 = 2;
+    const LEN: usize = NullifierRecord::LEN;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
+
+/// Proof-of-reserve attestation for one state of a shielded pool
+#[account]
+pub struct ReserveAttestation {
+    /// The attested pool
+    pub pool: Pubkey,
+
+    /// Pool Merkle root the proof was made against
+    pub merkle_root: [u8; 32],
+
+    /// Notes in the tree at that root
+    pub total_notes: u32,
+
+    /// Nullifiers recorded at that root
+    pub nullifier_count: u32,
+
+    /// Vault balance proven equal to the sum of unspent note values
+    pub vault_balance: u64,
+
+    /// Hash of the proof and its public signals
+    pub proof_hash: [u8; 32],
+
+    /// Pool authority that published the proof
+    pub published_by: Pubkey,
+
+    /// When the proof was published
+    pub published_at: i64,
+
+    /// Slot the proof was published in
+    pub published_slot: u64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl ReserveAttestation {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // merkle_root
+        4 + // total_notes
+        4 + // nullifier_count
+        8 + // vault_balance
+        32 + // proof_hash
+        32 + // published_by
+        8 + // published_at
+        8 + // published_slot
+        1 + // bump
+        1; // version
+
+    /// Whether this attestation covers the pool's current state
+    pub fn is_current(&self, pool: &ShieldedPool) -> bool {
+        self.merkle_root == pool.merkle_root && self.nullifier_count == pool.nullifier_count
+    }
+}
+
+/// One public recipient of a split withdrawal and its fixed share
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SplitPayment {
+    /// Wallet paid; passed in `remaining_accounts` in the same order
+    pub recipient: Pubkey,
+    /// Lamports moved from the pool vault to the recipient
+    pub lamports: u64,
+}
+
+/// One note of a routed deposit and the pool it goes to
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RouteLeg {
+    /// Pool receiving the note; legs for the same pool are consecutive
+    pub pool: Pubkey,
+    /// Note commitment, as for `shield_deposit`
+    pub note_commitment: [u8; 32],
+    /// Encrypted note data for the owner
+    pub encrypted_note: [u8; 64],
+}
+
+/// Payload passed to a pool's screener program in `screen_withdrawal`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ScreenWithdrawalPayload {
+    pub pool: Pubkey,
+    pub recipient: Pubkey,
+    pub nullifier: [u8; 32],
+}
+
+/// Marks a shielded pool as issuing deposit receipts; mint authority of
+/// every receipt until it is revoked
+#[account]
+pub struct DepositReceiptConfig {
+    /// The pool whose deposits can be receipted
+    pub pool: Pubkey,
+
+    /// Pool authority that enabled receipts
+    pub authority: Pubkey,
+
+    /// Receipts minted so far
+    pub receipts_issued: u64,
+
+    /// When receipts were enabled
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl DepositReceiptConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // authority
+        8 + // receipts_issued
+        8 + // created_at
+        1 + // bump
+        1; // version
+}
+
+/// Program the pool authority allows to deposit into a pool through CPI,
+/// signing as its `CPI_DEPOSITOR_SEED` PDA
+#[account]
+pub struct DepositCaller {
+    /// The pool the caller may deposit into
+    pub pool: Pubkey,
+
+    /// Allowlisted caller program
+    pub program: Pubkey,
+
+    /// The caller's depositor PDA (`pda::cpi_depositor_address`)
+    pub depositor: Pubkey,
+
+    /// Deposits credited through this caller
+    pub deposit_count: u64,
+
+    /// When the caller was allowlisted
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl DepositCaller {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // program
+        32 + // depositor
+        8 + // deposit_count
+        8 + // created_at
+        1 + // bump
+        1; // version
+}
+
+/// Gift note spendable by whoever holds a one-time claim secret
+#[account]
+pub struct ClaimableNote {
+    /// The pool holding the gift note
+    pub pool: Pubkey,
+
+    /// H(DOMAIN_CLAIM_KEY, claim_secret)
+    pub claim_key_hash: [u8; 32],
+
+    /// Commitment of the gift note
+    pub note_commitment: [u8; 32],
+
+    /// Merkle leaf index of the gift note
+    pub note_index: u32,
+
+    /// Who created the gift
+    pub creator: Pubkey,
+
+    /// When the gift was created
+    pub created_at: i64,
+
+    /// When the gift was claimed
+    pub claimed_at: i64,
+
+    /// Whether the gift has been claimed
+    pub is_claimed: bool,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl ClaimableNote {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // claim_key_hash
+        32 + // note_commitment
+        4 + // note_index
+        32 + // creator
+        8 + // created_at
+        8 + // claimed_at
+        1 + // is_claimed
+        1 + // bump
+        1; // version
+}
+
+/// Tree checkpoint - exported note tree state for light clients
+/// Zero-copy: the filled subtrees are read and updated in place.
+#[account(zero_copy)]
+pub struct TreeCheckpoint {
+    /// The pool this checkpoint mirrors
+    pub pool: Pubkey,
+
+    /// Pool `merkle_root` after the first `leaf_count` notes
+    pub pool_root: [u8; 32],
+
+    /// Root of the incremental note tree (the tree `verify_merkle_proof` checks)
+    pub tree_root: [u8; 32],
+
+    /// Rightmost left node per level of the incremental note tree;
+    /// together with `leaf_count` this is enough to keep appending leaves
+    pub filled_subtrees: [[u8; 32]; MERKLE_TREE_DEPTH],
+
+    /// H(DOMAIN_TREE_CHECKPOINT, leaf_count || filled_subtrees)
+    pub subtree_digest: [u8; 32],
+
+    /// When the checkpoint was last advanced
+    pub updated_at: i64,
+
+    /// Slot the checkpoint was last advanced in
+    pub updated_slot: u64,
+
+    /// Number of notes included
+    pub leaf_count: u32,
+
+    /// Number of times the checkpoint has been advanced
+    pub sequence: u32,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+
+    /// Explicit padding to an 8-byte boundary
+    pub _padding: [u8; 6],
+}
+
+impl TreeCheckpoint {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // pool_root
+        32 + // tree_root
+        (32 * MERKLE_TREE_DEPTH) + // filled_subtrees
+        32 + // subtree_digest
+        8 + // updated_at
+        8 + // updated_slot
+        4 + // leaf_count
+        4 + // sequence
+        1 + // bump
+        1 + // version
+        6; // _padding
+}
+
+/// Nullifier filter - bloom filter of the nullifiers a pool has spent
+/// A miss proves a nullifier unspent since `tracked_from`; a hit may be a
+/// false positive, so the NullifierRecord stays the double-spend check.
+/// Zero-copy: the bits are updated in place.
+#[account(zero_copy)]
+pub struct NullifierFilter {
+    /// The pool whose nullifiers are tracked
+    pub pool: Pubkey,
+
+    /// Filter bits, NULLIFIER_FILTER_HASHES per nullifier
+    pub bits: [u8; NULLIFIER_FILTER_BYTES],
+
+    /// Pool `nullifier_count` when the filter was created; earlier spends
+    /// are not in the filter
+    pub tracked_from: u32,
+
+    /// Number of nullifiers inserted
+    pub inserted: u32,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+
+    /// Explicit padding to an 8-byte boundary
+    pub _padding: [u8; 6],
+}
+
+impl NullifierFilter {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        NULLIFIER_FILTER_BYTES + // bits
+        4 + // tracked_from
+        4 + // inserted
+        1 + // bump
+        1 + // version
+        6; // _padding
+
+    /// Bits of `nullifier`: little-endian u16 words of
+    /// H(DOMAIN_NULLIFIER_FILTER, nullifier), modulo the filter size
+    pub fn bit_positions(nullifier: &[u8; 32]) -> [usize; NULLIFIER_FILTER_HASHES] {
+        let digest = hash_domain(DOMAIN_NULLIFIER_FILTER, &[nullifier]);
+        std::array::from_fn(|i| {
+            u16::from_le_bytes([digest[2 * i], digest[2 * i + 1]]) as usize % (NULLIFIER_FILTER_BYTES * 8)
+        })
+    }
+
+    pub fn insert(&mut self, nullifier: &[u8; 32]) {
+        for bit in Self::bit_positions(nullifier) {
+            self.bits[bit / 8] |= 1 << (bit % 8);
+        }
+        self.inserted += 1;
+    }
+
+    /// False only if `nullifier` was never inserted
+    pub fn may_contain(&self, nullifier: &[u8; 32]) -> bool {
+        Self::bit_positions(nullifier)
+            .iter()
+            .all(|bit| self.bits[bit / 8] & (1 << (bit % 8)) != 0)
+    }
+}
+
+/// Protocol-wide totals of the shielded pools counted here, so dashboards
+/// need not scan every pool PDA. Pools join at creation or through
+/// `register_pool_stats`, which adds their counts so far.
+#[account]
+pub struct ProtocolStats {
+    /// Pools counted in the totals
+    pub pool_count: u32,
+
+    /// Notes ever inserted into the counted pools
+    pub total_notes: u64,
+
+    /// Nullifiers ever recorded by the counted pools
+    pub total_nullifiers: u64,
+
+    /// When the stats account was created
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl ProtocolStats {
+    pub const LEN: usize = 8 + // discriminator
+        4 + // pool_count
+        8 + // total_notes
+        8 + // total_nullifiers
+        8 + // created_at
+        1 + // bump
+        1; // version
+
+    /// Start counting `pool` with everything it has recorded so far
+    pub fn add_pool(&mut self, pool: &ShieldedPool) {
+        self.pool_count += 1;
+        self.total_notes += u64::from(pool.total_notes);
+        self.total_nullifiers += u64::from(pool.nullifier_count);
+    }
+}
@@ -0,0 +1,111 @@
+//! Legacy staking accounts (deprecated)
+
+use crate::*;
+
+/// Private Stake Pool - hidden stake amounts (DEPRECATED)
+#[account]
+pub struct PrivateStakePool {
+    /// Unique pool identifier
+    pub pool_id: [u8; 32],
+
+    /// Creator of the pool
+    pub creator: Pubkey,
+
+    /// Minimum stake amount in lamports
+    pub min_stake_lamports: u64,
+
+    /// Reward rate in basis points per epoch
+    pub reward_rate_bps: u16,
+
+    /// Number of epochs for lockup
+    pub lockup_epochs: u8,
+
+    /// Total number of stake commitments
+    pub total_stake_commitments: u32,
+
+    /// Total staked lamports (aggregate, not individual)
+    pub total_staked_lamports: u64,
+
+    /// When the pool was created
+    pub created_at: i64,
+
+    /// Whether the pool is active
+    pub is_active: bool,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl PrivateStakePool {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool_id
+        32 + // creator
+        8 + // min_stake_lamports
+        2 + // reward_rate_bps
+        1 + // lockup_epochs
+        4 + // total_stake_commitments
+        8 + // total_staked_lamports
+        8 + // created_at
+        1 + // is_active
+        1 + // bump
+        1; // version
+}
+
+/// Individual private stake record
+#[account]
+pub struct PrivateStakeRecord {
+    /// The pool this stake belongs to
+    pub pool: Pubkey,
+
+    /// The staker (for PDA derivation)
+    pub staker: Pubkey,
+
+    /// Stake commitment: hash(amount || validator_commitment || staker || secret)
+    pub stake_commitment: [u8; 32],
+
+    /// Validator commitment: hash(validator_pubkey || salt)
+    pub validator_commitment: [u8; 32],
+
+    /// When the stake was created
+    pub staked_at: i64,
+
+    /// When the stake can be withdrawn
+    pub unlock_at: i64,
+
+    /// Whether the stake is active
+    pub is_active: bool,
+
+    /// Total rewards claimed
+    pub claimed_rewards: u64,
+
+    /// When rewards were last claimed
+    pub last_claim_at: i64,
+
+    /// When the stake was withdrawn (if applicable)
+    pub unstaked_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl PrivateStakeRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // staker
+        32 + // stake_commitment
+        32 + // validator_commitment
+        8 + // staked_at
+        8 + // unlock_at
+        1 + // is_active
+        8 + // claimed_rewards
+        8 + // last_claim_at
+        8 + // unstaked_at
+        1 + // bump
+        1; // version
+}
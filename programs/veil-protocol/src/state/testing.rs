@@ -0,0 +1,25 @@
+//! Test-only accounts
+
+use crate::*;
+
+/// Time override read by `clock::now` in `test-clock` builds
+#[cfg(feature = "test-clock")]
+#[account]
+pub struct MockClock {
+    /// Replaces `Clock::unix_timestamp`
+    pub unix_timestamp: i64,
+
+    /// Replaces `Clock::slot`
+    pub slot: u64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+#[cfg(feature = "test-clock")]
+impl MockClock {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // unix_timestamp
+        8 + // slot
+        1; // bump
+}
@@ -0,0 +1,639 @@
+//! Proposals, votes and pool parameter updates
+
+use crate::*;
+
+/// Private Voting Proposal - commit-reveal scheme
+#[account]
+pub struct Proposal {
+    /// Unique proposal identifier
+    pub proposal_id: [u8; 32],
+
+    /// Creator of the proposal
+    pub creator: Pubkey,
+
+    /// Hash of proposal metadata (title, description stored off-chain)
+    pub metadata_hash: [u8; 32],
+
+    /// When the proposal was created
+    pub created_at: i64,
+
+    /// When voting ends (commit phase)
+    pub voting_ends_at: i64,
+
+    /// When reveal phase ends
+    pub reveal_ends_at: i64,
+
+    /// Number of YES votes (after reveal)
+    pub yes_count: u32,
+
+    /// Number of NO votes (after reveal)
+    pub no_count: u32,
+
+    /// Total vote commitments received
+    pub total_commitments: u32,
+
+    /// Total votes revealed
+    pub total_revealed: u32,
+
+    /// Whether the proposal has been finalized
+    pub is_finalized: bool,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+
+    /// Share of commitments (basis points) that must be revealed for the
+    /// tally to decide the proposal; 0 disables the check
+    pub min_reveal_bps: u16,
+
+    /// Seconds each automatic reveal extension adds; 0 disables extensions
+    pub reveal_extension: i64,
+
+    /// Reveal extensions granted so far (at most `MAX_REVEAL_EXTENSIONS`)
+    pub reveal_extensions: u8,
+
+    /// Finalized with too few reveals: the tally does not decide the proposal
+    pub reveal_quorum_failed: bool,
+
+    /// Shielded pool voting weight is proven against; default for
+    /// one-vote-per-key proposals
+    pub weight_pool: Pubkey,
+
+    /// Pool Merkle root snapshotted when stake weighting was enabled
+    pub weight_root: [u8; 32],
+
+    /// Lamports of stake per unit of voting weight
+    pub weight_unit: u64,
+
+    /// Revealed YES weight (weighted proposals)
+    pub yes_weight: u64,
+
+    /// Revealed NO weight (weighted proposals)
+    pub no_weight: u64,
+
+    /// Governance token mint voting weight is escrowed in; default unless
+    /// the proposal is token-weighted
+    pub governing_mint: Pubkey,
+
+    /// Merkle root of eligible voters (leaves H(DOMAIN_ELIGIBILITY_LEAF,
+    /// voter)); zero lets any key vote
+    pub eligibility_root: [u8; 32],
+
+    /// Lamports held above rent for whoever finalizes the proposal; zero
+    /// once paid (and for proposals created before bounties)
+    pub finalization_bounty: u64,
+
+    /// Seconds a committed tally stays open to challenges; 0 finalizes in
+    /// one stage
+    pub dispute_window: i64,
+
+    /// H(DOMAIN_TALLY_COMMITMENT, proposal, counts, quorum flag, slot)
+    pub tally_commitment: [u8; 32],
+
+    /// When the tally was committed; 0 until then
+    pub tally_committed_at: i64,
+
+    /// A challenge proved the committed tally wrong: the result is void
+    pub tally_disputed: bool,
+}
+
+impl Proposal {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // proposal_id
+        32 + // creator
+        32 + // metadata_hash
+        8 + // created_at
+        8 + // voting_ends_at
+        8 + // reveal_ends_at
+        4 + // yes_count
+        4 + // no_count
+        4 + // total_commitments
+        4 + // total_revealed
+        1 + // is_finalized
+        1 + // bump
+        1 + // version
+        2 + // min_reveal_bps
+        8 + // reveal_extension
+        1 + // reveal_extensions
+        1 + // reveal_quorum_failed
+        32 + // weight_pool
+        32 + // weight_root
+        8 + // weight_unit
+        8 + // yes_weight
+        8 + // no_weight
+        32 + // governing_mint
+        32 + // eligibility_root
+        8 + // finalization_bounty
+        8 + // dispute_window
+        32 + // tally_commitment
+        8 + // tally_committed_at
+        1; // tally_disputed
+
+    /// Whether enough commitments were revealed for the tally to count
+    pub fn reveal_quorum_met(&self) -> bool {
+        self.total_revealed as u64 * 10_000 >= self.min_reveal_bps as u64 * self.total_commitments as u64
+    }
+
+    /// Whether votes carry stake or token weight rather than counting once
+    pub fn is_weighted(&self) -> bool {
+        self.weight_pool != Pubkey::default() || self.governing_mint != Pubkey::default()
+    }
+
+    /// Whether the finalized tally approves the proposal: more YES than NO
+    /// weight (weighted proposals) or votes, with the reveal quorum met
+    /// and no upheld tally challenge
+    pub fn passed(&self) -> bool {
+        if !self.is_finalized || self.reveal_quorum_failed || self.tally_disputed {
+            return false;
+        }
+        if self.is_weighted() {
+            self.yes_weight > self.no_weight
+        } else {
+            self.yes_count > self.no_count
+        }
+    }
+}
+
+/// Governance parameters a DAO publishes once and creates proposals from,
+/// so periods are set relative to creation instead of as raw timestamps
+#[account]
+pub struct ProposalTemplate {
+    /// Who published the template
+    pub creator: Pubkey,
+
+    /// Template identifier, unique per creator
+    pub template_id: [u8; 32],
+
+    /// Seconds from proposal creation until voting ends
+    pub voting_period: i64,
+
+    /// Seconds from the end of voting until the reveal phase ends
+    pub reveal_period: i64,
+
+    /// Eligible voter root copied to each proposal; zero for open voting
+    pub eligibility_root: [u8; 32],
+
+    /// Reveal quorum in basis points (see `Proposal::min_reveal_bps`)
+    pub min_reveal_bps: u16,
+
+    /// Reveal extension in seconds (see `Proposal::reveal_extension`)
+    pub reveal_extension: i64,
+
+    /// Proposals created from the template
+    pub proposals_created: u64,
+
+    /// When the template was published
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl ProposalTemplate {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // creator
+        32 + // template_id
+        8 + // voting_period
+        8 + // reveal_period
+        32 + // eligibility_root
+        2 + // min_reveal_bps
+        8 + // reveal_extension
+        8 + // proposals_created
+        8 + // created_at
+        1 + // bump
+        1; // version
+}
+
+/// Funding proposal decided by conviction: shielded stake committed to it
+/// accrues voting power the longer it stays, and the proposal passes once
+/// the proven conviction reaches the threshold
+#[account]
+pub struct ConvictionProposal {
+    /// Unique proposal identifier
+    pub proposal_id: [u8; 32],
+
+    /// Creator of the proposal
+    pub creator: Pubkey,
+
+    /// Hash of proposal metadata (stored off-chain)
+    pub metadata_hash: [u8; 32],
+
+    /// Shielded pool whose notes carry the stake
+    pub pool: Pubkey,
+
+    /// Lamports of note value per unit of weight
+    pub weight_unit: u64,
+
+    /// Seconds a stake takes to accrue its full weight as conviction
+    pub ramp_period: i64,
+
+    /// Conviction the proposal needs to pass
+    pub conviction_threshold: u64,
+
+    /// Sum of the conviction proven in window `conviction_window`
+    pub total_conviction: u64,
+
+    /// Stakes currently committed
+    pub stakes: u32,
+
+    /// When the proposal was created
+    pub created_at: i64,
+
+    /// After this, stakes can no longer commit or prove conviction
+    pub closes_at: i64,
+
+    /// When the threshold was first reached; 0 while it has not been
+    pub passed_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+
+    /// Proof window (`CONVICTION_PROOF_WINDOW`s since `created_at`)
+    /// `total_conviction` sums; proofs from earlier windows have lapsed
+    pub conviction_window: u64,
+}
+
+impl ConvictionProposal {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // proposal_id
+        32 + // creator
+        32 + // metadata_hash
+        32 + // pool
+        8 + // weight_unit
+        8 + // ramp_period
+        8 + // conviction_threshold
+        8 + // total_conviction
+        4 + // stakes
+        8 + // created_at
+        8 + // closes_at
+        8 + // passed_at
+        1 + // bump
+        1 + // version
+        8; // conviction_window
+
+    /// Proof window `time` falls in
+    pub fn conviction_window_at(&self, time: i64) -> u64 {
+        (time.saturating_sub(self.created_at).max(0) / CONVICTION_PROOF_WINDOW) as u64
+    }
+
+    /// Conviction that counts at `time`: none once the window the total
+    /// was proven in has passed
+    pub fn live_conviction(&self, time: i64) -> u64 {
+        if self.conviction_window == self.conviction_window_at(time) {
+            self.total_conviction
+        } else {
+            0
+        }
+    }
+}
+
+impl Versioned for ConvictionProposal {
+    /// v2 added proof windows; older proposals start in window 0, so their
+    /// totals lapse at the next proof
+    const VERSION: u8 = 2;
+    const LEN: usize = ConvictionProposal::LEN;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+
+    fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+}
+
+/// Shielded stake committed to a conviction proposal; its weight stays
+/// hidden in `stake_commitment`
+#[account]
+pub struct ConvictionStake {
+    /// The conviction proposal
+    pub proposal: Pubkey,
+
+    /// Key that committed the stake and may prove or withdraw it
+    pub owner: Pubkey,
+
+    /// Nullifier of the note for this proposal, so a note stakes once
+    pub stake_nullifier: [u8; 32],
+
+    /// Commitment to the note's weight
+    pub stake_commitment: [u8; 32],
+
+    /// When the stake was committed; conviction accrues from here
+    pub committed_at: i64,
+
+    /// Latest proven conviction, counted in the proposal total while the
+    /// proof window of `proven_at` lasts
+    pub conviction: u64,
+
+    /// When conviction was last proven
+    pub proven_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl ConvictionStake {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // proposal
+        32 + // owner
+        32 + // stake_nullifier
+        32 + // stake_commitment
+        8 + // committed_at
+        8 + // conviction
+        8 + // proven_at
+        1 + // bump
+        1; // version
+}
+
+/// Quadratic voting proposal: each voter spreads votes across options and
+/// proves the sum of their squares fits a hidden credit budget; the
+/// tallies add up the votes from the proofs' public inputs
+#[account]
+pub struct QuadraticProposal {
+    /// Unique proposal identifier
+    pub proposal_id: [u8; 32],
+
+    /// Creator of the proposal
+    pub creator: Pubkey,
+
+    /// Hash of proposal metadata, including the options (stored off-chain)
+    pub metadata_hash: [u8; 32],
+
+    /// Merkle root of voter credit leaves, each committing to a budget
+    pub credit_root: [u8; 32],
+
+    /// Number of options (at most `MAX_QUADRATIC_OPTIONS`)
+    pub option_count: u8,
+
+    /// Votes per option; entries past `option_count` stay zero
+    pub tallies: [u64; MAX_QUADRATIC_OPTIONS],
+
+    /// Voters who have cast
+    pub total_voters: u32,
+
+    /// When the proposal was created
+    pub created_at: i64,
+
+    /// When voting ends
+    pub voting_ends_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl QuadraticProposal {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // proposal_id
+        32 + // creator
+        32 + // metadata_hash
+        32 + // credit_root
+        1 + // option_count
+        8 * MAX_QUADRATIC_OPTIONS + // tallies
+        4 + // total_voters
+        8 + // created_at
+        8 + // voting_ends_at
+        1 + // bump
+        1; // version
+
+    /// Option with the most votes; the lowest index wins ties
+    pub fn leading_option(&self) -> Option<u8> {
+        let tallies = &self.tallies[..self.option_count as usize];
+        let max = *tallies.iter().max()?;
+        (max > 0).then(|| tallies.iter().position(|votes| *votes == max).unwrap_or(0) as u8)
+    }
+}
+
+/// Quadratic vote keyed by its nullifier, so a credit leaf votes once
+#[account]
+pub struct QuadraticVoteRecord {
+    /// The quadratic proposal
+    pub proposal: Pubkey,
+
+    /// Nullifier of the voter's credit leaf for this proposal
+    pub vote_nullifier: [u8; 32],
+
+    /// When the vote was cast
+    pub cast_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl QuadraticVoteRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // proposal
+        32 + // vote_nullifier
+        8 + // cast_at
+        1 + // bump
+        1; // version
+}
+
+/// Pool parameter a governance proposal can change
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum PoolParamUpdate {
+    RewardRate { reward_rate_bps: u16 },
+    LockupEpochs { lockup_epochs: u8 },
+    /// Pause (false) or resume (true) the pool
+    SetActive { is_active: bool },
+    NoteExpiry { note_expiry_seconds: i64, treasury: Pubkey },
+}
+
+/// Rules a proposal must meet to change a governed pool, fixed when the
+/// pool's authority is handed to its governance PDA
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct PoolGovernanceConfig {
+    /// Shortest commit phase, from proposal creation to the end of voting
+    pub min_voting_period: i64,
+
+    /// Shortest reveal phase
+    pub min_reveal_period: i64,
+
+    /// Lowest reveal quorum (`Proposal::min_reveal_bps`) a proposal may set
+    pub min_reveal_bps: u16,
+
+    /// Revealed YES stake, in lamports, an update needs to pass
+    pub quorum_lamports: u64,
+
+    /// Shortest tally dispute window (`Proposal::dispute_window`); 0 lets
+    /// proposals finalize in one stage
+    pub min_dispute_window: i64,
+
+    /// Seconds from the end of the reveal phase until the update can be
+    /// executed
+    pub execution_delay: i64,
+
+    /// Only key that may attach updates; default lets anyone propose
+    pub proposer: Pubkey,
+}
+
+impl PoolGovernanceConfig {
+    pub const LEN: usize = 8 + // min_voting_period
+        8 + // min_reveal_period
+        2 + // min_reveal_bps
+        8 + // quorum_lamports
+        8 + // min_dispute_window
+        8 + // execution_delay
+        32; // proposer
+}
+
+/// Governance PDA a pool hands its authority to (`govern_pool`)
+#[account]
+pub struct PoolGovernance {
+    /// The governed pool
+    pub pool: Pubkey,
+
+    /// Rules every update proposal is checked against
+    pub config: PoolGovernanceConfig,
+
+    /// Updates executed so far
+    pub updates_executed: u64,
+
+    /// When the pool was handed to governance
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl PoolGovernance {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        PoolGovernanceConfig::LEN + // config
+        8 + // updates_executed
+        8 + // created_at
+        1 + // bump
+        1; // version
+}
+
+/// Pool parameter update a stake-weighted proposal decides
+#[account]
+pub struct PoolUpdateProposal {
+    /// The proposal voting on the update
+    pub proposal: Pubkey,
+
+    /// The pool the update applies to (the proposal's weight pool)
+    pub pool: Pubkey,
+
+    /// The update applied if the proposal passes
+    pub update: PoolParamUpdate,
+
+    /// Whether the update has been applied
+    pub executed: bool,
+
+    /// When the update was applied
+    pub executed_at: i64,
+
+    /// When the update was attached
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl PoolUpdateProposal {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // proposal
+        32 + // pool
+        1 + 8 + 32 + // update (largest: NoteExpiry)
+        1 + // executed
+        8 + // executed_at
+        8 + // created_at
+        1 + // bump
+        1; // version
+}
+
+/// Individual vote record for commit-reveal
+#[account]
+pub struct VoteRecord {
+    /// The proposal this vote is for
+    pub proposal: Pubkey,
+
+    /// The voter (for PDA derivation)
+    pub voter: Pubkey,
+
+    /// Vote commitment: H(DOMAIN_VOTE, vote_choice || secret || voter)
+    pub commitment: [u8; 32],
+
+    /// Whether a vote has been cast
+    pub has_voted: bool,
+
+    /// Whether the vote has been revealed
+    pub has_revealed: bool,
+
+    /// The revealed choice (only valid if has_revealed)
+    pub revealed_choice: bool,
+
+    /// When the vote was cast
+    pub voted_at: i64,
+
+    /// When the vote was revealed
+    pub revealed_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+
+    /// Proven or escrowed voting weight; 0 for one-vote-per-key proposals
+    pub weight: u64,
+
+    /// H(DOMAIN_REVEAL_TOKEN, reveal_token) letting whoever holds the token
+    /// reveal on the voter's behalf; zero if the reveal is not delegated
+    pub reveal_token_hash: [u8; 32],
+}
+
+impl VoteRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // proposal
+        32 + // voter
+        32 + // commitment
+        1 + // has_voted
+        1 + // has_revealed
+        1 + // revealed_choice
+        8 + // voted_at
+        8 + // revealed_at
+        1 + // bump
+        1 + // version
+        8 + // weight
+        32; // reveal_token_hash
+
+    /// Whether this record already holds exactly this vote, so a retried
+    /// cast can succeed without changing anything
+    pub fn holds_vote(&self, commitment: &[u8; 32], reveal_token_hash: &[u8; 32]) -> bool {
+        self.has_voted && self.commitment == *commitment && self.reveal_token_hash == *reveal_token_hash
+    }
+}
+
+/// Merkle path proving a voter's leaf H(DOMAIN_ELIGIBILITY_LEAF, voter) is
+/// in a proposal's eligibility root
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EligibilityProof {
+    /// Sibling hashes from the leaf up
+    pub siblings: [[u8; 32]; MERKLE_TREE_DEPTH],
+    /// Bit i set when the path is the right child at level i
+    pub path_indices: u8,
+}
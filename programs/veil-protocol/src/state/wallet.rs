@@ -0,0 +1,375 @@
+//! Commitment wallets, aliases and attestations
+
+use crate::*;
+
+#[account]
+pub struct WalletAccount {
+    /// The privacy-preserving commitment (never reveals identity)
+    pub commitment: [u8; 32],
+
+    /// The wallet owner (can cancel recovery)
+    pub owner: Pubkey,
+
+    /// When this wallet was created
+    pub created_at: i64,
+
+    /// Recovery commitment (for time-locked recovery)
+    pub recovery_commitment: [u8; 32],
+
+    /// Whether recovery is currently active
+    pub recovery_active: bool,
+
+    /// When recovery was initiated
+    pub recovery_initiated_at: i64,
+
+    /// When recovery can be executed
+    pub recovery_unlock_at: i64,
+
+    /// When recovery was executed (if applicable)
+    pub recovery_executed_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+
+    /// Number of `execute_as_wallet` calls made; binds each call proof
+    pub call_nonce: u64,
+
+    /// Whether the owner froze the wallet (see `freeze_wallet`)
+    pub frozen: bool,
+
+    /// When the current freeze started (0 when not frozen)
+    pub frozen_at: i64,
+
+    /// Key an executed recovery hands the wallet to once it accepts;
+    /// default when no handover is pending
+    pub pending_owner: Pubkey,
+
+    /// Owner the wallet PDA was derived from, pinned at the first handover;
+    /// default while that owner still holds the wallet (see `seed_owner`)
+    pub original_owner: Pubkey,
+
+    /// Version of the latest anchored backup (0 = none; see `register_backup`)
+    pub backup_version: u64,
+
+    /// Hash of the latest anchored backup
+    pub backup_hash: [u8; 32],
+}
+
+impl WalletAccount {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // commitment
+        32 + // owner
+        8 + // created_at
+        32 + // recovery_commitment
+        1 + // recovery_active
+        8 + // recovery_initiated_at
+        8 + // recovery_unlock_at
+        8 + // recovery_executed_at
+        1 + // bump
+        1 + // version
+        8 + // call_nonce
+        1 + // frozen
+        8 + // frozen_at
+        32 + // pending_owner
+        32 + // original_owner
+        8 + // backup_version
+        32; // backup_hash
+
+    /// Key in the wallet PDA's seeds: the creating owner, even after a
+    /// recovery handed the wallet to a new key
+    pub fn seed_owner(&self) -> Pubkey {
+        if self.original_owner == Pubkey::default() {
+            self.owner
+        } else {
+            self.original_owner
+        }
+    }
+}
+
+/// One version of a wallet's encrypted off-chain backup. Versions are
+/// never overwritten, so the records form the wallet's rotation history
+/// and each one links to the version it replaced
+#[account]
+pub struct WalletBackup {
+    /// Wallet account the backup belongs to
+    pub wallet: Pubkey,
+
+    /// Backup version, counting from 1
+    pub backup_version: u64,
+
+    /// Hash of the encrypted backup blob
+    pub backup_hash: [u8; 32],
+
+    /// Hash of the URI the blob is stored at
+    pub uri_hash: [u8; 32],
+
+    /// Hash of the version this one replaced (zero for the first)
+    pub previous_hash: [u8; 32],
+
+    /// When the version was registered
+    pub registered_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl WalletBackup {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // wallet
+        8 + // backup_version
+        32 + // backup_hash
+        32 + // uri_hash
+        32 + // previous_hash
+        8 + // registered_at
+        1 + // bump
+        1; // version
+}
+
+/// Private alias of a commitment wallet, keyed by H(DOMAIN_ALIAS, name || salt)
+#[account]
+pub struct AliasRecord {
+    /// Hash of the alias name and salt
+    pub alias_hash: [u8; 32],
+
+    /// Wallet account that controls the alias
+    pub wallet: Pubkey,
+
+    /// Stealth meta-address (spend || view public key), encrypted under a
+    /// key derived from the name and salt
+    pub encrypted_meta_address: [u8; 64],
+
+    /// When the alias was registered
+    pub created_at: i64,
+
+    /// When the meta-address last changed
+    pub updated_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl AliasRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // alias_hash
+        32 + // wallet
+        64 + // encrypted_meta_address
+        8 + // created_at
+        8 + // updated_at
+        1 + // bump
+        1; // version
+}
+
+/// Wallet attestation - a ZK-proven property of an unlinked commitment wallet
+#[account]
+pub struct WalletAttestation {
+    /// Attestation nullifier = H(wallet_secret || claim), keys the PDA
+    pub nullifier: [u8; 32],
+
+    /// The property that was proven
+    pub claim: AttestationClaim,
+
+    /// The proven lower bound (days for WalletAge, count for VerifiedProofCount)
+    pub threshold: u64,
+
+    /// Time the proof was generated against
+    pub reference_time: i64,
+
+    /// When the attestation was recorded
+    pub issued_at: i64,
+
+    /// When the attestation stops being valid
+    pub expires_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl WalletAttestation {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // nullifier
+        1 + // claim
+        8 + // threshold
+        8 + // reference_time
+        8 + // issued_at
+        8 + // expires_at
+        1 + // bump
+        1; // version
+}
+
+/// Append-only Merkle tree of enrolled wallet commitments. Attestation
+/// proofs show membership under one of its recent roots, which ties them
+/// to a real WalletAccount without revealing which one
+#[account]
+pub struct WalletRegistry {
+    /// Current root of the commitment tree
+    pub root: [u8; 32],
+
+    /// Ring of the latest roots, so proofs generated just before another
+    /// enrollment still verify
+    pub recent_roots: [[u8; 32]; WALLET_ROOT_HISTORY],
+
+    /// Slot in `recent_roots` the current root was written to
+    pub root_index: u8,
+
+    /// Rightmost left node per level of the commitment tree
+    pub filled_subtrees: [[u8; 32]; WALLET_TREE_DEPTH],
+
+    /// Number of commitments enrolled
+    pub wallet_count: u32,
+
+    /// When the registry was created
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl WalletRegistry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // root
+        32 * WALLET_ROOT_HISTORY + // recent_roots
+        1 + // root_index
+        32 * WALLET_TREE_DEPTH + // filled_subtrees
+        4 + // wallet_count
+        8 + // created_at
+        1 + // bump
+        1; // version
+
+    /// Whether `root` is the current root or one of the recent ones
+    pub fn is_known_root(&self, root: &[u8; 32]) -> bool {
+        self.wallet_count > 0
+            && self
+                .recent_roots
+                .iter()
+                .fold(false, |known, recent| known | constant_time_eq(recent, root))
+    }
+
+    /// Append a commitment and return its leaf index
+    pub fn enroll(&mut self, commitment: &[u8; 32]) -> Result<u32> {
+        let index = self.wallet_count;
+        require!((index as u64) < 1u64 << WALLET_TREE_DEPTH, ErrorCode::WalletRegistryFull);
+
+        self.root = append_to_filled_subtrees(&mut self.filled_subtrees, commitment, index);
+        self.root_index = (self.root_index + 1) % WALLET_ROOT_HISTORY as u8;
+        self.recent_roots[self.root_index as usize] = self.root;
+        self.wallet_count = index + 1;
+        Ok(index)
+    }
+}
+
+/// Marks a wallet commitment as enrolled in the `WalletRegistry`, so each
+/// commitment takes one leaf
+#[account]
+pub struct WalletEnrollment {
+    /// The enrolled wallet
+    pub wallet: Pubkey,
+
+    /// The commitment that was enrolled
+    pub commitment: [u8; 32],
+
+    /// Position of the commitment in the registry tree
+    pub leaf_index: u32,
+
+    /// When the commitment was enrolled
+    pub enrolled_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl WalletEnrollment {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // wallet
+        32 + // commitment
+        4 + // leaf_index
+        8 + // enrolled_at
+        1 + // bump
+        1; // version
+}
+
+/// Key-value attestation on a commitment wallet, written with a wallet proof
+#[account]
+pub struct Attestation {
+    /// The attested wallet
+    pub wallet: Pubkey,
+
+    /// Application-defined key
+    pub key: [u8; 32],
+
+    /// Hash of the attested value (the value itself stays off-chain)
+    pub value_hash: [u8; 32],
+
+    /// Hash of the proof and public signals of the latest write
+    pub proof_hash: [u8; 32],
+
+    /// When the value was last written
+    pub attested_at: i64,
+
+    /// Number of writes so far
+    pub revision: u32,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl Attestation {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // wallet
+        32 + // key
+        32 + // value_hash
+        32 + // proof_hash
+        8 + // attested_at
+        4 + // revision
+        1 + // bump
+        1; // version
+}
+
+/// Proof types supported by the protocol
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ProofType {
+    Groth16,
+    Bulletproof,
+    Poseidon,
+}
+
+/// Payload passed to `on_proof_verified` when a proof result is routed
+/// to an external program. The CPI is signed by the `proof_authority` PDA.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProofVerifiedPayload {
+    pub wallet: Pubkey,
+    pub commitment: [u8; 32],
+    pub proof_hash: [u8; 32],
+    pub public_signals_hash: [u8; 32],
+    pub verification_type: ProofType,
+    pub timestamp: i64,
+}
+
+/// Wallet properties that can be attested in zero knowledge
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AttestationClaim {
+    /// Wallet commitment is older than `threshold` days
+    WalletAge,
+    /// Wallet has completed at least `threshold` verified proofs
+    VerifiedProofCount,
+}
@@ -0,0 +1,88 @@
+//! Watchtower alert feeds
+
+use crate::*;
+
+/// Alert feed of a wallet, multisig or shielded pool. Protocol-critical
+/// instructions on the subject write a compact alert into a ring of
+/// WATCHTOWER_ALERT_SLOTS entries; watchtower services poll the account
+/// and notify `alert_key`. An alert's slot is `sequence % WATCHTOWER_ALERT_SLOTS`,
+/// so a poller that falls more than a ring behind sees the gap in sequence
+#[account]
+pub struct Watchtower {
+    /// The wallet, multisig or shielded pool being watched
+    pub subject: Pubkey,
+
+    /// Who registered the watchtower (wallet owner, multisig or pool
+    /// creator); may change its settings
+    pub owner: Pubkey,
+
+    /// Key notifications are addressed (and may be encrypted) to
+    pub alert_key: Pubkey,
+
+    /// Smallest multisig transfer that raises an alert; proposals that do
+    /// something other than a plain transfer always raise one
+    pub large_amount: u64,
+
+    /// Alerts written so far
+    pub alert_count: u64,
+
+    /// The latest alerts, oldest overwritten first
+    pub alerts: [WatchtowerAlert; WATCHTOWER_ALERT_SLOTS],
+
+    /// When the watchtower was registered
+    pub created_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Account layout version (see `migrate_account`)
+    pub version: u8,
+}
+
+impl Watchtower {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // subject
+        32 + // owner
+        32 + // alert_key
+        8 + // large_amount
+        8 + // alert_count
+        WatchtowerAlert::LEN * WATCHTOWER_ALERT_SLOTS + // alerts
+        8 + // created_at
+        1 + // bump
+        1; // version
+}
+
+/// One alert in a watchtower's ring
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct WatchtowerAlert {
+    /// Position in the feed, counting from 1 (0 marks an unused slot)
+    pub sequence: u64,
+
+    /// `WatchtowerAlertKind`
+    pub kind: u8,
+
+    /// What the alert is about: the recovery commitment, the multisig
+    /// proposal or the pool update proposal
+    pub reference: [u8; 32],
+
+    /// Kind-specific value: recovery unlock time, transfer amount
+    pub value: u64,
+
+    /// When the alert was raised
+    pub timestamp: i64,
+}
+
+impl WatchtowerAlert {
+    pub const LEN: usize = 8 + 1 + 32 + 8 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WatchtowerAlertKind {
+    /// A time-locked recovery of the wallet was started
+    RecoveryInitiated,
+    /// A multisig proposal at or above `large_amount`, or one that is not
+    /// a plain transfer, was created
+    LargeProposal,
+    /// The pool was deactivated by governance
+    PoolPaused,
+}
@@ -152,6 +152,7 @@ export function WalletRecoveryDemoModal({ isOpen, onClose }: WalletRecoveryDemoM
           connection,
           publicKey,
           recoveryProofBytes,
+          publicKey,
           signTransaction
         );
         setExecuteRecoveryTx(executeTxSig);
@@ -1,5 +1,5 @@
-import { Connection, PublicKey, Transaction, SystemProgram } from '@solana/web3.js';
-import { AnchorProvider, Program, Idl } from '@coral-xyz/anchor';
+import { AccountMeta, Connection, PublicKey, Transaction, SystemProgram } from '@solana/web3.js';
+import { AnchorProvider, Program, Idl, BN } from '@coral-xyz/anchor';
 import idl from '../../target/idl/veil_protocol.json';
 
 // Program ID from deployment
@@ -144,10 +144,12 @@ export async function initiateRecovery(
 
 /**
  * Execute recovery after timelock expires
+ * The wallet then waits for `newOwner` to call `acceptRecovery`
  */
 export async function executeRecovery(
   wallet: any,
-  recoveryProof: Uint8Array
+  recoveryProof: Uint8Array,
+  newOwner: PublicKey
 ): Promise<string> {
   if (!wallet.publicKey) {
     throw new Error('Wallet not connected');
@@ -164,7 +166,7 @@ export async function executeRecovery(
 
   try {
     const tx = await program.methods
-      .executeRecovery(Array.from(recoveryProof))
+      .executeRecovery(Buffer.from(recoveryProof), newOwner)
       .accounts({
         walletAccount: walletAccountPDA,
         user: wallet.publicKey,
@@ -180,7 +182,42 @@ export async function executeRecovery(
 }
 
 /**
- * Cancel an active recovery
+ * Take over a recovered wallet; must be signed by the new owner named in
+ * `executeRecovery`. `originalOwner` is the key the wallet was created with
+ */
+export async function acceptRecovery(wallet: any, originalOwner: PublicKey): Promise<string> {
+  if (!wallet.publicKey) {
+    throw new Error('Wallet not connected');
+  }
+
+  const connection = getConnection();
+  const provider = new AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+
+  const program = new Program(idl as Idl, provider);
+
+  const [walletAccountPDA] = getWalletAccountPDA(originalOwner);
+
+  try {
+    const tx = await program.methods
+      .acceptRecovery()
+      .accounts({
+        walletAccount: walletAccountPDA,
+        newOwner: wallet.publicKey,
+      })
+      .rpc();
+
+    console.log('Recovery accepted:', tx);
+    return tx;
+  } catch (error) {
+    console.error('Error accepting recovery:', error);
+    throw error;
+  }
+}
+
+/**
+ * Cancel an active recovery or a handover not yet accepted
  */
 export async function cancelRecovery(wallet: any): Promise<string> {
   if (!wallet.publicKey) {
@@ -213,6 +250,129 @@ export async function cancelRecovery(wallet: any): Promise<string> {
   }
 }
 
+/**
+ * Freeze the connected wallet's commitment account after a suspected key
+ * compromise. Only the secret behind its recovery commitment can lift it
+ */
+export async function freezeWallet(wallet: any): Promise<string> {
+  if (!wallet.publicKey) {
+    throw new Error('Wallet not connected');
+  }
+
+  const connection = getConnection();
+  const provider = new AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+
+  const program = new Program(idl as Idl, provider);
+
+  const [walletAccountPDA] = getWalletAccountPDA(wallet.publicKey);
+
+  try {
+    const tx = await program.methods
+      .freezeWallet()
+      .accounts({
+        walletAccount: walletAccountPDA,
+        owner: wallet.publicKey,
+      })
+      .rpc();
+
+    console.log('Wallet frozen:', tx);
+    return tx;
+  } catch (error) {
+    console.error('Error freezing wallet:', error);
+    throw error;
+  }
+}
+
+/**
+ * Lift a freeze with a proof of the recovery secret. Any wallet can pay
+ * for the transaction, so `owner` is the frozen wallet's owner key
+ */
+export async function unfreezeWallet(
+  wallet: any,
+  owner: PublicKey,
+  unfreezeProof: Uint8Array
+): Promise<string> {
+  if (!wallet.publicKey) {
+    throw new Error('Wallet not connected');
+  }
+
+  const connection = getConnection();
+  const provider = new AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+
+  const program = new Program(idl as Idl, provider);
+
+  const [walletAccountPDA] = getWalletAccountPDA(owner);
+
+  try {
+    const tx = await program.methods
+      .unfreezeWallet(Buffer.from(unfreezeProof))
+      .accounts({
+        walletAccount: walletAccountPDA,
+        payer: wallet.publicKey,
+      })
+      .rpc();
+
+    console.log('Wallet unfrozen:', tx);
+    return tx;
+  } catch (error) {
+    console.error('Error unfreezing wallet:', error);
+    throw error;
+  }
+}
+
+/**
+ * Have the wallet PDA sign a call to an allowlisted program (SPL Token,
+ * Token-2022, Associated Token Account, Memo). Include the wallet PDA in
+ * `accounts` wherever the target instruction expects it as a signer.
+ */
+export async function executeAsWallet(
+  wallet: any,
+  targetProgram: PublicKey,
+  accounts: AccountMeta[],
+  instructionData: Uint8Array,
+  walletProof: Uint8Array
+): Promise<string> {
+  if (!wallet.publicKey) {
+    throw new Error('Wallet not connected');
+  }
+
+  const connection = getConnection();
+  const provider = new AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+
+  const program = new Program(idl as Idl, provider);
+
+  const [walletAccountPDA] = getWalletAccountPDA(wallet.publicKey);
+
+  try {
+    const tx = await program.methods
+      .executeAsWallet(Buffer.from(instructionData), Buffer.from(walletProof))
+      .accounts({
+        walletAccount: walletAccountPDA,
+        owner: wallet.publicKey,
+      })
+      .remainingAccounts([
+        { pubkey: targetProgram, isSigner: false, isWritable: false },
+        // The program signs for its own PDA; the transaction cannot
+        ...accounts.map((meta) =>
+          meta.pubkey.equals(walletAccountPDA) ? { ...meta, isSigner: false } : meta
+        ),
+      ])
+      .rpc();
+
+    console.log('Wallet call executed:', tx);
+    return tx;
+  } catch (error) {
+    console.error('Error executing wallet call:', error);
+    throw error;
+  }
+}
+
 /**
  * Fetch wallet account data from on-chain
  */
@@ -276,15 +436,30 @@ export function getVoteRecordPDA(proposalPDA: PublicKey, voterPublicKey: PublicK
   );
 }
 
+/**
+ * Get the PDA of a stake-weighted vote, keyed by its vote nullifier
+ */
+export function getStakeVotePDA(proposalPDA: PublicKey, weightNullifier: Uint8Array): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('stake_vote'), proposalPDA.toBuffer(), Buffer.from(weightNullifier)],
+    VEIL_PROGRAM_ID
+  );
+}
+
 /**
  * Create a new private voting proposal
+ * `minRevealBps` is the share of commitments (basis points) that must be
+ * revealed for the tally to count; while short, finalization extends the
+ * reveal phase by `revealExtension` seconds (a few times) before voiding it
  */
 export async function createProposal(
   wallet: any,
   proposalId: Uint8Array,
   metadataHash: Uint8Array,
   votingEndsAt: number,
-  revealEndsAt: number
+  revealEndsAt: number,
+  minRevealBps = 0,
+  revealExtension = 0
 ): Promise<string> {
   if (!wallet.publicKey) {
     throw new Error('Wallet not connected');
@@ -304,7 +479,9 @@ export async function createProposal(
         Array.from(proposalId),
         Array.from(metadataHash),
         { toNumber: () => votingEndsAt } as any,
-        { toNumber: () => revealEndsAt } as any
+        { toNumber: () => revealEndsAt } as any,
+        minRevealBps,
+        { toNumber: () => revealExtension } as any
       )
       .accounts({
         proposal: proposalPDA,
@@ -399,6 +576,95 @@ export async function revealVote(
   }
 }
 
+/**
+ * Cast a stake-weighted vote on a proposal whose weight comes from a
+ * shielded pool. The proof shows a note worth at least
+ * `weight * weight_unit` without revealing its amount
+ */
+export async function castWeightedVote(
+  wallet: any,
+  proposalPDA: PublicKey,
+  weightNullifier: Uint8Array,
+  voteCommitment: Uint8Array,
+  weight: number,
+  weightProof: Uint8Array
+): Promise<string> {
+  if (!wallet.publicKey) {
+    throw new Error('Wallet not connected');
+  }
+
+  const connection = getConnection();
+  const provider = new AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+
+  const program = new Program(idl as Idl, provider);
+  const [voteRecordPDA] = getStakeVotePDA(proposalPDA, weightNullifier);
+
+  try {
+    const tx = await program.methods
+      .castWeightedVote(
+        Array.from(weightNullifier),
+        Array.from(voteCommitment),
+        { toNumber: () => weight } as any,
+        Buffer.from(weightProof)
+      )
+      .accounts({
+        proposal: proposalPDA,
+        voteRecord: voteRecordPDA,
+        voter: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    console.log('Weighted vote cast:', tx);
+    return tx;
+  } catch (error) {
+    console.error('Error casting weighted vote:', error);
+    throw error;
+  }
+}
+
+/**
+ * Reveal a stake-weighted vote (signed by the key that cast it)
+ */
+export async function revealWeightedVote(
+  wallet: any,
+  proposalPDA: PublicKey,
+  weightNullifier: Uint8Array,
+  voteChoice: boolean,
+  secret: Uint8Array
+): Promise<string> {
+  if (!wallet.publicKey) {
+    throw new Error('Wallet not connected');
+  }
+
+  const connection = getConnection();
+  const provider = new AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+
+  const program = new Program(idl as Idl, provider);
+  const [voteRecordPDA] = getStakeVotePDA(proposalPDA, weightNullifier);
+
+  try {
+    const tx = await program.methods
+      .revealWeightedVote(Array.from(weightNullifier), voteChoice, Array.from(secret))
+      .accounts({
+        proposal: proposalPDA,
+        voteRecord: voteRecordPDA,
+        voter: wallet.publicKey,
+      })
+      .rpc();
+
+    console.log('Weighted vote revealed:', tx);
+    return tx;
+  } catch (error) {
+    console.error('Error revealing weighted vote:', error);
+    throw error;
+  }
+}
+
 /**
  * Finalize a proposal after reveal period ends
  */
@@ -501,6 +767,48 @@ export async function createMultisig(
   }
 }
 
+export function getMultisigActionPDA(proposalPDA: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('ms_action'), proposalPDA.toBuffer()],
+    VEIL_PROGRAM_ID
+  );
+}
+
+/**
+ * Derive the authority PDA a multisig signs pool-admin instructions as;
+ * set it as a pool's authority to govern the pool by proposal
+ */
+export function getMultisigAuthorityPDA(multisigPDA: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('multisig_authority'), multisigPDA.toBuffer()],
+    VEIL_PROGRAM_ID
+  );
+}
+
+/**
+ * One step of a `batch` multisig action; a `poolAdmin` step takes the
+ * next `accountCount` remaining accounts at execution, pool first
+ */
+export type MultisigStep =
+  | { transferSol: { recipient: PublicKey; lamports: BN } }
+  | { transferToken: { mint: PublicKey; recipient: PublicKey; amount: BN; decimals: number } }
+  | { custom: { programId: PublicKey; instructionHash: number[] } }
+  | { poolAdmin: { pool: PublicKey; instructionData: Buffer; accountCount: number } };
+
+/**
+ * Typed multisig proposal action, in Anchor's enum encoding
+ */
+export type MultisigAction =
+  | { transferSol: { recipient: PublicKey; lamports: BN } }
+  | { transferToken: { mint: PublicKey; recipient: PublicKey; amount: BN; decimals: number } }
+  | { changeSigners: { add: number[][]; remove: number[][]; newThreshold: number } }
+  | { programUpgrade: { program: PublicKey; buffer: PublicKey; spill: PublicKey } }
+  | { custom: { programId: PublicKey; instructionHash: number[] } }
+  | { setVeto: { vetoCommitment: number[]; vetoWindow: BN } }
+  | { setTimelock: { seconds: BN } }
+  | { poolAdmin: { pool: PublicKey; instructionData: Buffer } }
+  | { batch: { steps: MultisigStep[] } };
+
 /**
  * Create a multisig proposal
  */
@@ -508,7 +816,7 @@ export async function createMultisigProposal(
   wallet: any,
   multisigPDA: PublicKey,
   proposalId: Uint8Array,
-  instructionHash: Uint8Array
+  action: MultisigAction
 ): Promise<string> {
   if (!wallet.publicKey) {
     throw new Error('Wallet not connected');
@@ -521,13 +829,15 @@ export async function createMultisigProposal(
 
   const program = new Program(idl as Idl, provider);
   const [proposalPDA] = getMultisigProposalPDA(multisigPDA, proposalId);
+  const [actionPDA] = getMultisigActionPDA(proposalPDA);
 
   try {
     const tx = await program.methods
-      .createMultisigProposal(Array.from(proposalId), Array.from(instructionHash))
+      .createMultisigProposal(Array.from(proposalId), action)
       .accounts({
         multisig: multisigPDA,
         multisigProposal: proposalPDA,
+        proposalAction: actionPDA,
         proposer: wallet.publicKey,
         systemProgram: SystemProgram.programId,
       })
@@ -569,6 +879,7 @@ export async function stealthSign(
         multisig: multisigPDA,
         multisigProposal: proposalPDA,
         signer: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
       })
       .rpc();
 
@@ -580,13 +891,58 @@ export async function stealthSign(
   }
 }
 
+/**
+ * Withdraw a stealth approval before the proposal is executed
+ * The revocation proof shows knowledge of the secret behind
+ * `approvalCommitment`, so only the original approver can revoke it
+ */
+export async function revokeStealthSignature(
+  wallet: any,
+  multisigPDA: PublicKey,
+  proposalPDA: PublicKey,
+  approvalCommitment: Uint8Array,
+  revocationProof: Uint8Array
+): Promise<string> {
+  if (!wallet.publicKey) {
+    throw new Error('Wallet not connected');
+  }
+
+  const connection = getConnection();
+  const provider = new AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+
+  const program = new Program(idl as Idl, provider);
+
+  try {
+    const tx = await program.methods
+      .revokeStealthSignature(Array.from(approvalCommitment), Buffer.from(revocationProof))
+      .accounts({
+        multisig: multisigPDA,
+        multisigProposal: proposalPDA,
+        signer: wallet.publicKey,
+      })
+      .rpc();
+
+    console.log('Stealth signature revoked:', tx);
+    return tx;
+  } catch (error) {
+    console.error('Error revoking stealth signature:', error);
+    throw error;
+  }
+}
+
 /**
  * Execute a multisig proposal after threshold is reached
+ * For a `poolAdmin` action, pass the accounts of the pool-admin
+ * instruction it invokes (pool first, then the rest in order); for a
+ * `batch`, pass each `poolAdmin` step's accounts one step after another
  */
 export async function executeMultisigProposal(
   wallet: any,
   multisigPDA: PublicKey,
-  proposalPDA: PublicKey
+  proposalPDA: PublicKey,
+  adminAccounts: AccountMeta[] = []
 ): Promise<string> {
   if (!wallet.publicKey) {
     throw new Error('Wallet not connected');
@@ -605,8 +961,14 @@ export async function executeMultisigProposal(
       .accounts({
         multisig: multisigPDA,
         multisigProposal: proposalPDA,
+        proposalAction: getMultisigActionPDA(proposalPDA)[0],
         executor: wallet.publicKey,
+        systemProgram: SystemProgram.programId,
       })
+      // The program signs for the multisig authority PDA; the transaction cannot
+      .remainingAccounts(adminAccounts.map((meta) =>
+        meta.pubkey.equals(getMultisigAuthorityPDA(multisigPDA)[0]) ? { ...meta, isSigner: false } : meta
+      ))
       .rpc();
 
     console.log('Multisig proposal executed:', tx);
@@ -617,6 +979,46 @@ export async function executeMultisigProposal(
   }
 }
 
+/**
+ * Veto an approved multisig proposal inside its veto window
+ * The veto authority proves knowledge of its commitment preimage; any
+ * wallet can submit the proof
+ */
+export async function vetoMultisigProposal(
+  wallet: any,
+  multisigPDA: PublicKey,
+  proposalPDA: PublicKey,
+  vetoProof: Uint8Array
+): Promise<string> {
+  if (!wallet.publicKey) {
+    throw new Error('Wallet not connected');
+  }
+
+  const connection = getConnection();
+  const provider = new AnchorProvider(connection, wallet, {
+    commitment: 'confirmed',
+  });
+
+  const program = new Program(idl as Idl, provider);
+
+  try {
+    const tx = await program.methods
+      .vetoMultisigProposal(Buffer.from(vetoProof))
+      .accounts({
+        multisig: multisigPDA,
+        multisigProposal: proposalPDA,
+        submitter: wallet.publicKey,
+      })
+      .rpc();
+
+    console.log('Multisig proposal vetoed:', tx);
+    return tx;
+  } catch (error) {
+    console.error('Error vetoing multisig proposal:', error);
+    throw error;
+  }
+}
+
 // ============================================================================
 // PRIVATE STAKING FUNCTIONS
 // ============================================================================
@@ -91,6 +91,34 @@ async function sendTransactionWithRetry(
   throw lastError || new Error('Transaction failed after retries');
 }
 
+/**
+ * Trailing accounts of every instruction that emits `emit_cpi!` events:
+ * the program's event authority PDA and the program itself
+ */
+function eventCpiAccounts() {
+  const [eventAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from('__event_authority')],
+    VEIL_PROGRAM_ID
+  );
+  return [
+    { pubkey: eventAuthority, isSigner: false, isWritable: false },
+    { pubkey: VEIL_PROGRAM_ID, isSigner: false, isWritable: false },
+  ];
+}
+
+/**
+ * The audit log of `subject` (a wallet or pool) when it keeps one, else
+ * the program ID, which Anchor reads as "no account"
+ */
+async function auditLogAccount(connection: Connection, subject: PublicKey) {
+  const [auditLog] = PublicKey.findProgramAddressSync(
+    [Buffer.from('audit_log'), subject.toBuffer()],
+    VEIL_PROGRAM_ID
+  );
+  const exists = (await connection.getAccountInfo(auditLog)) !== null;
+  return { pubkey: exists ? auditLog : VEIL_PROGRAM_ID, isSigner: false, isWritable: exists };
+}
+
 /**
  * Get the PDA for a user's wallet account
  */
@@ -134,6 +162,7 @@ export async function initializeCommitment(
       { pubkey: walletPDA, isSigner: false, isWritable: true },
       { pubkey: userPubkey, isSigner: true, isWritable: true },
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ...eventCpiAccounts(),
     ],
     data: instructionData,
   });
@@ -183,6 +212,7 @@ export async function submitProof(
     keys: [
       { pubkey: walletPDA, isSigner: false, isWritable: false },
       { pubkey: userPubkey, isSigner: true, isWritable: false },
+      ...eventCpiAccounts(),
     ],
     data: instructionData,
   });
@@ -213,6 +243,8 @@ export async function initiateRecovery(
     keys: [
       { pubkey: walletPDA, isSigner: false, isWritable: true },
       { pubkey: userPubkey, isSigner: true, isWritable: false },
+      await auditLogAccount(connection, walletPDA),
+      ...eventCpiAccounts(),
     ],
     data: instructionData,
   });
@@ -222,11 +254,13 @@ export async function initiateRecovery(
 
 /**
  * Execute recovery after timelock expires
+ * Control moves only once `newOwner` signs `acceptRecovery`
  */
 export async function executeRecovery(
   connection: Connection,
   userPubkey: PublicKey,
   recoveryProof: Uint8Array,
+  newOwner: PublicKey,
   signTransaction: (tx: Transaction) => Promise<Transaction>
 ): Promise<string> {
   const [walletPDA] = getWalletAccountPDA(userPubkey);
@@ -238,6 +272,7 @@ export async function executeRecovery(
     Buffer.from([203, 133, 133, 228, 153, 121, 182, 237]), // "execute_recovery" discriminator from IDL
     proofLengthBuffer,
     Buffer.from(recoveryProof),
+    newOwner.toBuffer(),
   ]);
 
   const instruction = new TransactionInstruction({
@@ -245,6 +280,8 @@ export async function executeRecovery(
     keys: [
       { pubkey: walletPDA, isSigner: false, isWritable: true },
       { pubkey: userPubkey, isSigner: true, isWritable: false },
+      await auditLogAccount(connection, walletPDA),
+      ...eventCpiAccounts(),
     ],
     data: instructionData,
   });
@@ -253,7 +290,35 @@ export async function executeRecovery(
 }
 
 /**
- * Cancel an active recovery
+ * Take over a recovered wallet, signed by the new owner named in
+ * `executeRecovery`. `originalOwner` is the key the wallet was created with
+ */
+export async function acceptRecovery(
+  connection: Connection,
+  newOwner: PublicKey,
+  originalOwner: PublicKey,
+  signTransaction: (tx: Transaction) => Promise<Transaction>
+): Promise<string> {
+  const [walletPDA] = getWalletAccountPDA(originalOwner);
+
+  const instructionData = Buffer.from([192, 212, 48, 178, 168, 141, 138, 162]); // "accept_recovery" discriminator
+
+  const instruction = new TransactionInstruction({
+    programId: VEIL_PROGRAM_ID,
+    keys: [
+      { pubkey: walletPDA, isSigner: false, isWritable: true },
+      { pubkey: newOwner, isSigner: true, isWritable: false },
+      await auditLogAccount(connection, walletPDA),
+      ...eventCpiAccounts(),
+    ],
+    data: instructionData,
+  });
+
+  return sendTransactionWithRetry(connection, [instruction], signTransaction, newOwner);
+}
+
+/**
+ * Cancel an active recovery or a handover not yet accepted
  */
 export async function cancelRecovery(
   connection: Connection,
@@ -269,6 +334,8 @@ export async function cancelRecovery(
     keys: [
       { pubkey: walletPDA, isSigner: false, isWritable: true },
       { pubkey: userPubkey, isSigner: true, isWritable: false },
+      await auditLogAccount(connection, walletPDA),
+      ...eventCpiAccounts(),
     ],
     data: instructionData,
   });
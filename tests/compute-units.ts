@@ -0,0 +1,280 @@
+/**
+ * Veil Protocol - Compute Unit Benchmarks
+ *
+ * Simulates each instruction against a local validator, records the compute
+ * units consumed and fails if any instruction exceeds its entry in
+ * compute-budget.json. Run with `anchor test`.
+ *
+ * Set BENCH_UPDATE=1 to rewrite compute-budget.json from the measured values
+ * (measured CU + 20% headroom) after an intentional change.
+ *
+ * The budgets checked in are estimates that have not been measured yet:
+ * no run of this file has written them. Regenerate them with BENCH_UPDATE=1
+ * before relying on the limits the SDK derives from them.
+ *
+ * The 'hashing' cases isolate the hash-heavy paths (proof/public-signal
+ * digests, Merkle insertion, checkpoint subtree digests) so changes to the
+ * hash helpers show up as a CU diff against the recorded budget.
+ */
+
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram, Transaction } from '@solana/web3.js';
+import assert from 'assert';
+import * as fs from 'fs';
+import * as path from 'path';
+import {
+  acceptedProof,
+  circuitId,
+  circuitRegistry,
+  fieldBytes,
+  groth16Stub,
+  mockProof,
+  payer,
+  pda,
+  program,
+  provider,
+  registerCircuits,
+} from './helpers';
+
+const BUDGET_FILE = path.join(__dirname, 'compute-budget.json');
+const HEADROOM = 1.2;
+
+const budget: Record<string, number> = JSON.parse(fs.readFileSync(BUDGET_FILE, 'utf8'));
+const measured: Record<string, number> = {};
+
+/** Simulate to record compute units, then send so later steps see the state */
+async function measure(name: string, tx: Promise<Transaction>): Promise<void> {
+  const transaction = await tx;
+  transaction.feePayer = payer;
+  transaction.recentBlockhash = (await provider.connection.getLatestBlockhash()).blockhash;
+
+  const sim = await provider.connection.simulateTransaction(transaction);
+  assert.ifError(sim.value.err);
+  measured[name] = sim.value.unitsConsumed ?? 0;
+
+  await provider.sendAndConfirm(transaction);
+}
+
+describe('compute unit budgets', () => {
+  const commitment = fieldBytes(1);
+  const wallet = pda([Buffer.from('wallet'), payer.toBuffer()]);
+
+  before(() => registerCircuits({ wallet: 'wallet', stealthApproval: 'stealth_approval' }));
+
+  it('wallet', async () => {
+    await measure('initialize_commitment', program.methods
+      .initializeCommitment(commitment)
+      .accounts({ walletAccount: wallet, user: payer, systemProgram: SystemProgram.programId })
+      .transaction());
+
+    await measure('submit_proof', program.methods
+      .submitProof(acceptedProof(groth16Stub('wallet', [commitment])), [commitment], circuitId('wallet'))
+      .accounts({ walletAccount: wallet, user: payer, circuitRegistry })
+      .transaction());
+
+    await measure('initiate_recovery', program.methods
+      .initiateRecovery(fieldBytes(2), 1)
+      .accounts({ walletAccount: wallet, user: payer, auditLog: null, watchtower: null })
+      .transaction());
+
+    await measure('cancel_recovery', program.methods
+      .cancelRecovery()
+      .accounts({ walletAccount: wallet, user: payer, auditLog: null })
+      .transaction());
+  });
+
+  it('voting', async () => {
+    const proposalId = Keypair.generate().publicKey.toBuffer();
+    const proposal = pda([Buffer.from('proposal'), payer.toBuffer(), proposalId]);
+    const now = Math.floor(Date.now() / 1000);
+
+    await measure('create_proposal', program.methods
+      .createProposal(
+        [...proposalId],
+        fieldBytes(3),
+        new anchor.BN(now + 3600),
+        new anchor.BN(now + 7200),
+        5000,
+        new anchor.BN(3600)
+      )
+      .accounts({ proposal, creator: payer, systemProgram: SystemProgram.programId })
+      .transaction());
+
+    await measure('cast_vote', program.methods
+      .castVote(fieldBytes(4))
+      .accounts({
+        proposal,
+        voteRecord: pda([Buffer.from('vote'), proposal.toBuffer(), payer.toBuffer()]),
+        voter: payer,
+        systemProgram: SystemProgram.programId,
+      })
+      .transaction());
+  });
+
+  it('multisig', async () => {
+    const vaultId = Keypair.generate().publicKey.toBuffer();
+    const multisig = pda([Buffer.from('multisig'), payer.toBuffer(), vaultId]);
+    const proposalId = Keypair.generate().publicKey.toBuffer();
+    const msProposal = pda([Buffer.from('ms_proposal'), multisig.toBuffer(), proposalId]);
+    const msAction = pda([Buffer.from('ms_action'), msProposal.toBuffer()]);
+
+    await measure('create_multisig', program.methods
+      .createMultisig([...vaultId], 1, [fieldBytes(5), fieldBytes(6), fieldBytes(7)])
+      .accounts({ multisig, creator: payer, systemProgram: SystemProgram.programId })
+      .transaction());
+
+    await measure('create_multisig_proposal', program.methods
+      .createMultisigProposal([...proposalId], { custom: { programId: payer, instructionHash: fieldBytes(8) } })
+      .accounts({
+        multisig,
+        multisigProposal: msProposal,
+        proposalAction: msAction,
+        proposer: payer,
+        systemProgram: SystemProgram.programId,
+        watchtower: null,
+      })
+      .transaction());
+
+    // Verifies the approval proof, then grows the proposal by one slot
+    await measure('stealth_sign', program.methods
+      .stealthSign(mockProof(), fieldBytes(10), circuitId('stealth_approval'))
+      .accounts({
+        multisig,
+        multisigProposal: msProposal,
+        signer: payer,
+        systemProgram: SystemProgram.programId,
+        circuitRegistry,
+      })
+      .transaction());
+
+    await measure('execute_multisig_proposal', program.methods
+      .executeMultisigProposal()
+      .accounts({
+        multisig,
+        multisigProposal: msProposal,
+        proposalAction: msAction,
+        executor: payer,
+        systemProgram: SystemProgram.programId,
+      })
+      .transaction());
+  });
+
+  it('rln', async () => {
+    const groupId = Keypair.generate().publicKey.toBuffer();
+    const group = pda([Buffer.from('rln_group'), payer.toBuffer(), groupId]);
+    const identity = Buffer.from(fieldBytes(11));
+
+    await measure('create_rln_group', program.methods
+      .createRlnGroup([...groupId], new anchor.BN(1_000_000), new anchor.BN(3600), 1)
+      .accounts({ rlnGroup: group, creator: payer, systemProgram: SystemProgram.programId })
+      .transaction());
+
+    await measure('register_rln_member', program.methods
+      .registerRlnMember([...identity])
+      .accounts({
+        rlnGroup: group,
+        rlnMember: pda([Buffer.from('rln_member'), group.toBuffer(), identity]),
+        payer,
+        systemProgram: SystemProgram.programId,
+      })
+      .transaction());
+  });
+
+  it('shielded pool', async () => {
+    const poolId = Keypair.generate().publicKey.toBuffer();
+    const pool = pda([Buffer.from('shielded_pool'), payer.toBuffer(), poolId]);
+    const noteIndex = Buffer.alloc(4);
+
+    await measure('create_shielded_pool', program.methods
+      .createShieldedPool([...poolId], 500, 1)
+      .accounts({ shieldedPool: pool, creator: payer, systemProgram: SystemProgram.programId, protocolStats: null })
+      .transaction());
+
+    await measure('shield_deposit', program.methods
+      .shieldDeposit(fieldBytes(12), new Array(64).fill(1), Buffer.alloc(64, 1))
+      .accounts({
+        shieldedPool: pool,
+        noteAccount: pda([Buffer.from('note'), pool.toBuffer(), noteIndex]),
+        poolVault: pda([Buffer.from('shielded_vault'), pool.toBuffer()]),
+        depositor: payer,
+        systemProgram: SystemProgram.programId,
+        referral: null,
+        referralProgram: null,
+        protocolStats: null,
+      })
+      .transaction());
+  });
+
+  it('hashing', async () => {
+    const poolId = Keypair.generate().publicKey.toBuffer();
+    const pool = pda([Buffer.from('shielded_pool'), payer.toBuffer(), poolId]);
+    const treeCheckpoint = pda([Buffer.from('tree_checkpoint'), pool.toBuffer()]);
+    const notes: PublicKey[] = [];
+
+    await provider.sendAndConfirm(await program.methods
+      .createShieldedPool([...poolId], 500, 1)
+      .accounts({ shieldedPool: pool, creator: payer, systemProgram: SystemProgram.programId, protocolStats: null })
+      .transaction());
+
+    for (let index = 0; index < 4; index++) {
+      const noteIndex = Buffer.alloc(4);
+      noteIndex.writeUInt32LE(index);
+      const noteAccount = pda([Buffer.from('note'), pool.toBuffer(), noteIndex]);
+      notes.push(noteAccount);
+
+      await provider.sendAndConfirm(await program.methods
+        .shieldDeposit(fieldBytes(20 + index), new Array(64).fill(1), Buffer.alloc(64, 1))
+        .accounts({
+          shieldedPool: pool,
+          noteAccount,
+          poolVault: pda([Buffer.from('shielded_vault'), pool.toBuffer()]),
+          depositor: payer,
+          systemProgram: SystemProgram.programId,
+          referral: null,
+          referralProgram: null,
+          protocolStats: null,
+        })
+        .transaction());
+    }
+
+    await measure('create_tree_checkpoint', program.methods
+      .createTreeCheckpoint()
+      .accounts({ shieldedPool: pool, treeCheckpoint, payer, systemProgram: SystemProgram.programId })
+      .transaction());
+
+    // Four Merkle insertions, four filled-subtree updates and a subtree digest
+    await measure('checkpoint_tree', program.methods
+      .checkpointTree()
+      .accounts({ shieldedPool: pool, treeCheckpoint, crankTask: null, cranker: payer })
+      .remainingAccounts(notes.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+      .transaction());
+
+    // Domain-hashes the proof together with its public signals; the first
+    // one is the wallet commitment
+    const signals = [fieldBytes(1), ...Array.from({ length: 11 }, (_, i) => fieldBytes(41 + i))];
+    await measure('submit_proof_many_signals', program.methods
+      .submitProof(acceptedProof(groth16Stub('wallet', signals)), signals, circuitId('wallet'))
+      .accounts({ walletAccount: pda([Buffer.from('wallet'), payer.toBuffer()]), user: payer, circuitRegistry })
+      .transaction());
+  });
+
+  after(() => {
+    console.table(Object.entries(measured).map(([instruction, units]) => ({
+      instruction,
+      units,
+      budget: budget[instruction],
+    })));
+
+    if (process.env.BENCH_UPDATE) {
+      const updated = Object.fromEntries(
+        Object.entries(measured).map(([name, units]) => [name, Math.ceil(units * HEADROOM / 1000) * 1000])
+      );
+      fs.writeFileSync(BUDGET_FILE, JSON.stringify(updated, null, 2) + '\n');
+      return;
+    }
+
+    const overBudget = Object.entries(measured)
+      .filter(([name, units]) => budget[name] === undefined || units > budget[name]);
+    assert.deepStrictEqual(overBudget, [], 'instructions over (or missing from) compute budget');
+  });
+});
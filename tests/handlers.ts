@@ -0,0 +1,689 @@
+/**
+ * Veil Protocol - Handler Tests
+ *
+ * Drives the instructions that move lamports out of program-controlled
+ * accounts (the pool vault, referral programs, RLN stakes) and the
+ * multisig proposal growth path against a local validator, checking
+ * balances and account state as well as the errors. Run with `anchor test`.
+ *
+ * The note sweep case steps the clock through `set_mock_clock`, so it
+ * needs a `test-clock` build (`anchor test -- --features test-clock`) and
+ * is skipped otherwise.
+ *
+ * `insert_note_to_merkle_tree` still chains roots instead of building the
+ * tree `verify_merkle_proof` checks, so no Merkle path opens a live pool's
+ * root yet. The spend-path cases therefore cover every check those
+ * handlers make before the Merkle proof, and that a rejected spend leaves
+ * the vault untouched and the pool unlocked.
+ */
+
+import * as anchor from '@coral-xyz/anchor';
+import { Keypair, PublicKey, SystemProgram, Transaction } from '@solana/web3.js';
+import assert from 'assert';
+import { createHash } from 'crypto';
+import {
+  acceptedProof,
+  balance,
+  circuitId,
+  circuitRegistry,
+  expectError,
+  fieldBytes,
+  groth16Stub,
+  hashDomain,
+  payer,
+  pda,
+  program,
+  provider,
+  registerCircuits,
+  rejectedProof,
+  u32Le,
+  u64Le,
+  u64Signal,
+} from './helpers';
+
+const LAMPORTS = 1_000_000;
+const NO_MERKLE_PATH = Array.from({ length: 8 }, () => new Array(32).fill(0));
+
+/** Random 32 bytes, for ids and nullifiers that must not collide across runs */
+function randomBytes32(): Buffer {
+  return Keypair.generate().publicKey.toBuffer();
+}
+
+async function transfer(to: PublicKey, lamports: number): Promise<void> {
+  await provider.sendAndConfirm(new Transaction().add(
+    SystemProgram.transfer({ fromPubkey: payer, toPubkey: to, lamports })
+  ));
+}
+
+function poolVault(pool: PublicKey): PublicKey {
+  return pda([Buffer.from('shielded_vault'), pool.toBuffer()]);
+}
+
+function noteAccount(pool: PublicKey, index: number): PublicKey {
+  return pda([Buffer.from('note'), pool.toBuffer(), u32Le(index)]);
+}
+
+function nullifierAccount(pool: PublicKey, nullifier: Buffer): PublicKey {
+  return pda([Buffer.from('nullifier'), pool.toBuffer(), nullifier]);
+}
+
+function poolBinding(pool: PublicKey): number[] {
+  return hashDomain('veil:pool_binding:v2', pool.toBuffer());
+}
+
+/** `MockClock` as the trailing remaining account `clock::now` reads */
+function clockAccounts(clock?: PublicKey) {
+  return clock ? [{ pubkey: clock, isSigner: false, isWritable: false }] : [];
+}
+
+async function createPool(clock?: PublicKey): Promise<PublicKey> {
+  const poolId = randomBytes32();
+  const pool = pda([Buffer.from('shielded_pool'), payer.toBuffer(), poolId]);
+  await program.methods
+    .createShieldedPool([...poolId], 500, 1)
+    .accounts({ shieldedPool: pool, creator: payer, systemProgram: SystemProgram.programId, protocolStats: null })
+    .remainingAccounts(clockAccounts(clock))
+    .rpc();
+  return pool;
+}
+
+/** Deposit the pool's next note, optionally crediting a referrer */
+async function deposit(
+  pool: PublicKey,
+  options: { referral?: PublicKey; referralProgram?: PublicKey; clock?: PublicKey } = {}
+): Promise<number> {
+  const index = (await program.account.shieldedPool.fetch(pool)).nextNoteIndex as number;
+  await program.methods
+    .shieldDeposit(randomBytes32(), new Array(64).fill(1), Buffer.alloc(64, 1))
+    .accounts({
+      shieldedPool: pool,
+      noteAccount: noteAccount(pool, index),
+      poolVault: poolVault(pool),
+      depositor: payer,
+      systemProgram: SystemProgram.programId,
+      referral: options.referral ?? null,
+      referralProgram: options.referralProgram ?? null,
+      protocolStats: null,
+    })
+    .remainingAccounts(clockAccounts(options.clock))
+    .rpc();
+  return index;
+}
+
+async function assertUnlocked(pool: PublicKey): Promise<void> {
+  assert.strictEqual((await program.account.shieldedPool.fetch(pool)).processing, false);
+}
+
+describe('handlers', () => {
+  before(() => registerCircuits({
+    depositCancel: 'deposit_cancel',
+    splitWithdrawal: 'split_withdrawal',
+    earlyExit: 'early_exit',
+    holdingWithdrawal: 'holding_withdrawal',
+    rewards: 'rewards',
+    referralClaim: 'referral_claim',
+    noteSweep: 'note_sweep',
+    rln: 'rln',
+    stealthApproval: 'stealth_approval',
+  }));
+
+  describe('cancel_deposit', () => {
+    let pool: PublicKey;
+
+    before(async () => {
+      pool = await createPool();
+      await transfer(poolVault(pool), 10 * LAMPORTS);
+    });
+
+    function cancel(index: number, nullifier: Buffer, recipient: PublicKey, lamports: number, proof: Buffer) {
+      return program.methods
+        .cancelDeposit([...nullifier], new anchor.BN(lamports), proof, circuitId('deposit_cancel'))
+        .accounts({
+          shieldedPool: pool,
+          noteAccount: noteAccount(pool, index),
+          nullifierAccount: nullifierAccount(pool, nullifier),
+          treeCheckpoint: pda([Buffer.from('tree_checkpoint'), pool.toBuffer()]),
+          poolVault: poolVault(pool),
+          recipient,
+          payer,
+          circuitRegistry,
+          systemProgram: SystemProgram.programId,
+          nullifierFilter: null,
+          protocolStats: null,
+          screenerProgram: null,
+        })
+        .rpc();
+    }
+
+    async function cancelProof(index: number, nullifier: Buffer, recipient: PublicKey, lamports: number) {
+      const note = await program.account.shieldedNote.fetch(noteAccount(pool, index));
+      const cancelTag = hashDomain(
+        'veil:deposit_cancel:v2',
+        pool.toBuffer(),
+        u32Le(index),
+        recipient.toBuffer(),
+        u64Le(lamports)
+      );
+      return acceptedProof(groth16Stub('deposit_cancel', [note.commitment, nullifier, cancelTag, poolBinding(pool)]));
+    }
+
+    it('refunds a fresh deposit from the vault and marks the note spent', async () => {
+      const index = await deposit(pool);
+      const nullifier = randomBytes32();
+      const recipient = Keypair.generate().publicKey;
+      const vaultBefore = await balance(poolVault(pool));
+
+      await cancel(index, nullifier, recipient, LAMPORTS, await cancelProof(index, nullifier, recipient, LAMPORTS));
+
+      assert.strictEqual(await balance(recipient), LAMPORTS);
+      assert.strictEqual(await balance(poolVault(pool)), vaultBefore - LAMPORTS);
+      assert.strictEqual((await program.account.shieldedNote.fetch(noteAccount(pool, index))).isSpent, true);
+      assert.ok(await program.account.nullifierRecord.fetch(nullifierAccount(pool, nullifier)));
+      await assertUnlocked(pool);
+
+      // A second nullifier for the same note cannot refund it again
+      const second = randomBytes32();
+      await expectError(
+        cancel(index, second, recipient, LAMPORTS, await cancelProof(index, second, recipient, LAMPORTS)),
+        'InvalidDepositCancel'
+      );
+    });
+
+    it('rejects an invalid proof without touching the vault', async () => {
+      const index = await deposit(pool);
+      const vaultBefore = await balance(poolVault(pool));
+
+      await expectError(
+        cancel(index, randomBytes32(), Keypair.generate().publicKey, LAMPORTS, rejectedProof()),
+        'InvalidDepositCancelProof'
+      );
+      assert.strictEqual(await balance(poolVault(pool)), vaultBefore);
+      await assertUnlocked(pool);
+    });
+
+    it('refuses a note the tree checkpoint has published', async () => {
+      const checkpointPool = await createPool();
+      await transfer(poolVault(checkpointPool), 10 * LAMPORTS);
+      const index = await deposit(checkpointPool);
+      const treeCheckpoint = pda([Buffer.from('tree_checkpoint'), checkpointPool.toBuffer()]);
+
+      await program.methods
+        .createTreeCheckpoint()
+        .accounts({ shieldedPool: checkpointPool, treeCheckpoint, payer, systemProgram: SystemProgram.programId })
+        .rpc();
+      await program.methods
+        .checkpointTree()
+        .accounts({ shieldedPool: checkpointPool, treeCheckpoint, crankTask: null, cranker: payer })
+        .remainingAccounts([{ pubkey: noteAccount(checkpointPool, index), isSigner: false, isWritable: false }])
+        .rpc();
+
+      const nullifier = randomBytes32();
+      await expectError(
+        program.methods
+          .cancelDeposit([...nullifier], new anchor.BN(LAMPORTS), rejectedProof(), circuitId('deposit_cancel'))
+          .accounts({
+            shieldedPool: checkpointPool,
+            noteAccount: noteAccount(checkpointPool, index),
+            nullifierAccount: nullifierAccount(checkpointPool, nullifier),
+            treeCheckpoint,
+            poolVault: poolVault(checkpointPool),
+            recipient: payer,
+            payer,
+            circuitRegistry,
+            systemProgram: SystemProgram.programId,
+            nullifierFilter: null,
+            protocolStats: null,
+            screenerProgram: null,
+          })
+          .rpc(),
+        'DepositAlreadyCheckpointed'
+      );
+    });
+  });
+
+  describe('spend paths', () => {
+    let pool: PublicKey;
+    let vaultBefore: number;
+
+    before(async () => {
+      pool = await createPool();
+      await deposit(pool);
+      await transfer(poolVault(pool), 10 * LAMPORTS);
+      vaultBefore = await balance(poolVault(pool));
+    });
+
+    afterEach(async () => {
+      assert.strictEqual(await balance(poolVault(pool)), vaultBefore);
+      await assertUnlocked(pool);
+    });
+
+    it('shield_withdraw_split checks its recipients before the Merkle proof', async () => {
+      const [first, second] = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+      const split = (payments: { recipient: PublicKey; lamports: number }[], recipients: PublicKey[]) => {
+        const nullifier = randomBytes32();
+        return program.methods
+          .shieldWithdrawSplit(
+            [...nullifier],
+            NO_MERKLE_PATH,
+            0,
+            rejectedProof(),
+            new Array(32).fill(0),
+            payments.map(({ recipient, lamports }) => ({ recipient, lamports: new anchor.BN(lamports) })),
+            circuitId('split_withdrawal')
+          )
+          .accounts({
+            shieldedPool: pool,
+            nullifierAccount: nullifierAccount(pool, nullifier),
+            poolVault: poolVault(pool),
+            payer,
+            circuitRegistry,
+            systemProgram: SystemProgram.programId,
+            nullifierFilter: null,
+            protocolStats: null,
+            screenerProgram: null,
+          })
+          .remainingAccounts(recipients.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
+          .rpc();
+      };
+
+      await expectError(
+        split([{ recipient: first, lamports: LAMPORTS }, { recipient: first, lamports: LAMPORTS }], [first, first]),
+        'InvalidSplitWithdrawal'
+      );
+      await expectError(split([{ recipient: first, lamports: LAMPORTS }], [second]), 'SplitRecipientMismatch');
+      await expectError(split([{ recipient: first, lamports: LAMPORTS }], [first]), 'InvalidMerkleProof');
+    });
+
+    it('shield_withdraw_early needs early exits enabled and a positive payout', async () => {
+      const withdrawEarly = (grossLamports: number) => {
+        const nullifier = randomBytes32();
+        return program.methods
+          .shieldWithdrawEarly(
+            [...nullifier],
+            NO_MERKLE_PATH,
+            0,
+            rejectedProof(),
+            new Array(32).fill(0),
+            new anchor.BN(grossLamports),
+            circuitId('early_exit')
+          )
+          .accounts({
+            shieldedPool: pool,
+            nullifierAccount: nullifierAccount(pool, nullifier),
+            poolVault: poolVault(pool),
+            recipient: Keypair.generate().publicKey,
+            payer,
+            circuitRegistry,
+            systemProgram: SystemProgram.programId,
+            nullifierFilter: null,
+            protocolStats: null,
+            screenerProgram: null,
+          })
+          .rpc();
+      };
+
+      await expectError(withdrawEarly(LAMPORTS), 'EarlyExitDisabled');
+
+      await program.methods
+        .configureEarlyExit(1_000)
+        .accounts({ shieldedPool: pool, authority: payer, auditLog: null })
+        .rpc();
+      await expectError(withdrawEarly(0), 'InvalidEarlyExit');
+      await expectError(withdrawEarly(LAMPORTS), 'InvalidMerkleProof');
+    });
+
+    it('shield_withdraw_to_holding needs a release and a claim key', async () => {
+      const withdrawToHolding = (lamports: number) => {
+        const nullifier = randomBytes32();
+        const claimKeyHash = randomBytes32();
+        return program.methods
+          .shieldWithdrawToHolding(
+            [...nullifier],
+            NO_MERKLE_PATH,
+            0,
+            rejectedProof(),
+            new Array(32).fill(0),
+            { claimKeyHash: [...claimKeyHash], lamports: new anchor.BN(lamports) },
+            circuitId('holding_withdrawal')
+          )
+          .accounts({
+            shieldedPool: pool,
+            nullifierAccount: nullifierAccount(pool, nullifier),
+            poolVault: poolVault(pool),
+            holdingAccount: pda([Buffer.from('holding'), pool.toBuffer(), claimKeyHash]),
+            payer,
+            circuitRegistry,
+            systemProgram: SystemProgram.programId,
+            nullifierFilter: null,
+            protocolStats: null,
+          })
+          .rpc();
+      };
+
+      await expectError(withdrawToHolding(0), 'InvalidHoldingWithdrawal');
+      await expectError(withdrawToHolding(LAMPORTS), 'InvalidMerkleProof');
+    });
+
+    it('claim_shielded_rewards checks the staked note is in the tree', async () => {
+      const nullifier = randomBytes32();
+      await expectError(
+        program.methods
+          .claimShieldedRewards(
+            [...nullifier],
+            NO_MERKLE_PATH,
+            0,
+            rejectedProof(),
+            fieldBytes(1),
+            circuitId('rewards')
+          )
+          .accounts({
+            shieldedPool: pool,
+            nullifierAccount: nullifierAccount(pool, nullifier),
+            poolVault: poolVault(pool),
+            claimer: payer,
+            systemProgram: SystemProgram.programId,
+            nullifierFilter: null,
+            protocolStats: null,
+            circuitRegistry,
+          })
+          .rpc(),
+        'InvalidMerkleProof'
+      );
+    });
+  });
+
+  describe('execute_note_sweep', () => {
+    const YEAR = 365 * 86_400;
+    const NOTICE = 180 * 86_400;
+    const mockClock = pda([Buffer.from('mock_clock')]);
+
+    async function setClock(unixTimestamp: number): Promise<void> {
+      await program.methods
+        .setMockClock(new anchor.BN(unixTimestamp), new anchor.BN(0))
+        .accounts({ mockClock, payer, systemProgram: SystemProgram.programId })
+        .rpc();
+    }
+
+    before(function () {
+      if (!program.methods.setMockClock) this.skip();
+    });
+
+    it('pays an announced sweep to the treasury once the notice has passed', async () => {
+      const start = 1_700_000_000;
+      const treasury = Keypair.generate().publicKey;
+
+      await setClock(start);
+      const pool = await createPool(mockClock);
+      const index = await deposit(pool, { clock: mockClock });
+      await transfer(poolVault(pool), 10 * LAMPORTS);
+      await program.methods
+        .configureNoteExpiry(new anchor.BN(YEAR), treasury)
+        .accounts({ shieldedPool: pool, authority: payer, auditLog: null })
+        .rpc();
+
+      await setClock(start + YEAR);
+      await program.methods
+        .announceNoteSweep(index + 1)
+        .accounts({ shieldedPool: pool, lastNote: noteAccount(pool, index), authority: payer, auditLog: null })
+        .remainingAccounts(clockAccounts(mockClock))
+        .rpc();
+
+      const sweep = async (proof?: Buffer) => {
+        const state = await program.account.shieldedPool.fetch(pool);
+        const signals = [
+          hashDomain('veil:note_sweep:v2', pool.toBuffer(), u32Le(0), u32Le(index + 1), u64Le(LAMPORTS)),
+          state.merkleRoot,
+          u64Signal(state.nullifierCount),
+          u64Signal(LAMPORTS),
+        ];
+        return program.methods
+          .executeNoteSweep(
+            new anchor.BN(LAMPORTS),
+            proof ?? acceptedProof(groth16Stub('note_sweep', signals)),
+            circuitId('note_sweep')
+          )
+          .accounts({
+            shieldedPool: pool,
+            poolVault: poolVault(pool),
+            treasury,
+            authority: payer,
+            systemProgram: SystemProgram.programId,
+            auditLog: null,
+            circuitRegistry,
+          })
+          .remainingAccounts(clockAccounts(mockClock))
+          .rpc();
+      };
+
+      const vaultBefore = await balance(poolVault(pool));
+      await expectError(sweep(), 'NoteSweepNoticeActive');
+
+      await setClock(start + YEAR + NOTICE);
+      await expectError(sweep(rejectedProof()), 'InvalidNoteSweepProof');
+      assert.strictEqual(await balance(poolVault(pool)), vaultBefore);
+
+      await sweep();
+      assert.strictEqual(await balance(treasury), LAMPORTS);
+      assert.strictEqual(await balance(poolVault(pool)), vaultBefore - LAMPORTS);
+      const state = await program.account.shieldedPool.fetch(pool);
+      assert.strictEqual(state.sweptThroughIndex, index + 1);
+      assert.strictEqual(state.pendingSweepThrough, 0);
+      await assertUnlocked(pool);
+
+      await expectError(sweep(), 'InvalidNoteSweep');
+    });
+  });
+
+  describe('claim_referral_rewards', () => {
+    const MAX_DEPOSITS = 2;
+    const commitment = randomBytes32();
+    let pool: PublicKey;
+    let referralProgram: PublicKey;
+    let referral: PublicKey;
+
+    before(async () => {
+      pool = await createPool();
+      referralProgram = pda([Buffer.from('referral_program'), pool.toBuffer()]);
+      referral = pda([Buffer.from('referral'), pool.toBuffer(), commitment]);
+
+      await program.methods
+        .createReferralProgram(
+          new anchor.BN(LAMPORTS),
+          new anchor.BN(LAMPORTS),
+          new anchor.BN(MAX_DEPOSITS),
+          new anchor.BN(10 * LAMPORTS)
+        )
+        .accounts({ shieldedPool: pool, referralProgram, authority: payer, systemProgram: SystemProgram.programId })
+        .rpc();
+      await program.methods
+        .registerReferrer([...commitment])
+        .accounts({ referralProgram, referral, payer, systemProgram: SystemProgram.programId })
+        .rpc();
+    });
+
+    function claim(recipient: PublicKey, proof: Buffer) {
+      return program.methods
+        .claimReferralRewards(proof, circuitId('referral_claim'))
+        .accounts({ referralProgram, referral, recipient, payer, circuitRegistry })
+        .rpc();
+    }
+
+    it('credits referred deposits that fund the vault, up to the cap', async () => {
+      const vaultBefore = await balance(poolVault(pool));
+      for (let i = 0; i < MAX_DEPOSITS + 1; i++) {
+        await deposit(pool, { referral, referralProgram });
+      }
+
+      assert.strictEqual((await program.account.referralRecord.fetch(referral)).depositCount.toNumber(), MAX_DEPOSITS);
+      assert.strictEqual(await balance(poolVault(pool)), vaultBefore + MAX_DEPOSITS * LAMPORTS);
+    });
+
+    it('pays the credited deposits once, to the recipient bound by the proof', async () => {
+      const recipient = Keypair.generate().publicKey;
+      const programBefore = await balance(referralProgram);
+
+      await expectError(claim(recipient, rejectedProof()), 'InvalidReferralProof');
+
+      const claimTag = hashDomain('veil:referral_claim:v2', referral.toBuffer(), recipient.toBuffer(), u64Le(0));
+      await claim(recipient, acceptedProof(groth16Stub('referral_claim', [commitment, claimTag])));
+
+      assert.strictEqual(await balance(recipient), MAX_DEPOSITS * LAMPORTS);
+      assert.strictEqual(await balance(referralProgram), programBefore - MAX_DEPOSITS * LAMPORTS);
+      const record = await program.account.referralRecord.fetch(referral);
+      assert.strictEqual(record.claimedCount.toNumber(), MAX_DEPOSITS);
+
+      await expectError(claim(recipient, rejectedProof()), 'NoReferralRewards');
+    });
+  });
+
+  describe('rln_slash', () => {
+    const STAKE = LAMPORTS;
+    // y = secret + a1 * x with secret 5 and a1 7
+    const secret = fieldBytes(5);
+    const [x1, y1] = [fieldBytes(1), fieldBytes(12)];
+    const [x2, y2] = [fieldBytes(2), fieldBytes(19)];
+    const identity = Buffer.from(hashDomain('veil:rln_identity:v2', Buffer.from(secret)));
+    const nullifier = randomBytes32();
+    const slasher = Keypair.generate();
+    let group: PublicKey;
+    let member: PublicKey;
+    let nullifierRecord: PublicKey;
+
+    before(async () => {
+      const groupId = randomBytes32();
+      group = pda([Buffer.from('rln_group'), payer.toBuffer(), groupId]);
+      member = pda([Buffer.from('rln_member'), group.toBuffer(), identity]);
+      nullifierRecord = pda([Buffer.from('rln_nullifier'), group.toBuffer(), nullifier]);
+
+      // One epoch spans the validator's whole clock range, so epoch 0 is current
+      await program.methods
+        .createRlnGroup([...groupId], new anchor.BN(STAKE), new anchor.BN(1_000_000_000_000), 1)
+        .accounts({ rlnGroup: group, creator: payer, systemProgram: SystemProgram.programId })
+        .rpc();
+      await program.methods
+        .registerRlnMember([...identity])
+        .accounts({ rlnGroup: group, rlnMember: member, payer, systemProgram: SystemProgram.programId })
+        .rpc();
+
+      const membersRoot = (await program.account.rlnGroup.fetch(group)).membersRoot as number[];
+      const proof = acceptedProof((candidate) => createHash('sha256')
+        .update(nullifier)
+        .update(u64Le(0))
+        .update(Buffer.from(x1))
+        .update(Buffer.from(y1))
+        .update(Buffer.from(membersRoot))
+        .update(u32Le(1))
+        .update(candidate)
+        .digest()[0] !== 0xff);
+      await program.methods
+        .rlnSignal([...nullifier], new anchor.BN(0), x1, y1, membersRoot, proof, circuitId('rln'))
+        .accounts({ rlnGroup: group, nullifierRecord, payer, systemProgram: SystemProgram.programId, circuitRegistry })
+        .rpc();
+
+      await transfer(slasher.publicKey, LAMPORTS);
+    });
+
+    function slash(shareX: number[], shareY: number[]) {
+      return program.methods
+        .rlnSlash(secret, shareX, shareY)
+        .accounts({ rlnGroup: group, nullifierRecord, rlnMember: member, slasher: slasher.publicKey })
+        .signers([slasher])
+        .rpc();
+    }
+
+    it('refuses shares that do not expose the secret', async () => {
+      await expectError(slash(x1, y1), 'InvalidRlnShare');
+      await expectError(slash(x2, fieldBytes(18)), 'InvalidRlnShare');
+      assert.strictEqual((await program.account.rlnMember.fetch(member)).isSlashed, false);
+    });
+
+    it('pays the stake to the slasher once', async () => {
+      const [memberBefore, slasherBefore] = [await balance(member), await balance(slasher.publicKey)];
+
+      await slash(x2, y2);
+
+      assert.strictEqual(await balance(member), memberBefore - STAKE);
+      assert.strictEqual(await balance(slasher.publicKey), slasherBefore + STAKE);
+      const state = await program.account.rlnMember.fetch(member);
+      assert.strictEqual(state.isSlashed, true);
+      assert.strictEqual(state.stakeLamports.toNumber(), 0);
+      assert.strictEqual((await program.account.rlnGroup.fetch(group)).slashedCount, 1);
+
+      await expectError(slash(x2, y2), 'RlnMemberAlreadySlashed');
+    });
+  });
+
+  describe('stealth_sign', () => {
+    // Offset of `approval_count` in `MultisigProposal`
+    const APPROVAL_COUNT_OFFSET = 8 + 32 + 32 + 32 + 8 + 8;
+    const signers = [fieldBytes(5), fieldBytes(6), fieldBytes(7)];
+    let multisig: PublicKey;
+    let proposal: PublicKey;
+
+    before(async () => {
+      const vaultId = randomBytes32();
+      const proposalId = randomBytes32();
+      multisig = pda([Buffer.from('multisig'), payer.toBuffer(), vaultId]);
+      proposal = pda([Buffer.from('ms_proposal'), multisig.toBuffer(), proposalId]);
+
+      await program.methods
+        .createMultisig([...vaultId], 2, signers)
+        .accounts({ multisig, creator: payer, systemProgram: SystemProgram.programId })
+        .rpc();
+      await program.methods
+        .createMultisigProposal([...proposalId], { custom: { programId: payer, instructionHash: fieldBytes(8) } })
+        .accounts({
+          multisig,
+          multisigProposal: proposal,
+          proposalAction: pda([Buffer.from('ms_action'), proposal.toBuffer()]),
+          proposer: payer,
+          systemProgram: SystemProgram.programId,
+          watchtower: null,
+        })
+        .rpc();
+    });
+
+    function sign(approval: number[], proof: Buffer) {
+      return program.methods
+        .stealthSign(proof, approval, circuitId('stealth_approval'))
+        .accounts({
+          multisig,
+          multisigProposal: proposal,
+          signer: payer,
+          systemProgram: SystemProgram.programId,
+          circuitRegistry,
+        })
+        .rpc();
+    }
+
+    function approvalProof(approval: number[]): Buffer {
+      const tag = hashDomain('veil:stealth_approval:v2', proposal.toBuffer(), ...signers.map((signer) => Buffer.from(signer)));
+      return acceptedProof(groth16Stub('stealth_approval', [approval, tag]));
+    }
+
+    async function proposalData(): Promise<Buffer> {
+      return (await provider.connection.getAccountInfo(proposal)).data;
+    }
+
+    it('does not grow the proposal for an invalid signer proof', async () => {
+      const before = await proposalData();
+      await expectError(sign(fieldBytes(10), rejectedProof()), 'InvalidSignerProof');
+      assert.strictEqual((await proposalData()).length, before.length);
+    });
+
+    it('grows the proposal by one slot per approval', async () => {
+      const approval = fieldBytes(10);
+      const before = await proposalData();
+
+      await sign(approval, approvalProof(approval));
+
+      const after = await proposalData();
+      assert.strictEqual(after.length, before.length + 32);
+      assert.strictEqual(after[APPROVAL_COUNT_OFFSET], before[APPROVAL_COUNT_OFFSET] + 1);
+      assert.deepStrictEqual([...after.subarray(after.length - 32)], approval);
+
+      await expectError(sign(approval, approvalProof(approval)), 'DuplicateApproval');
+      assert.strictEqual((await proposalData()).length, after.length);
+    });
+  });
+});
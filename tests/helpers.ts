@@ -0,0 +1,159 @@
+/**
+ * Veil Protocol - shared test helpers
+ *
+ * Seeds, hashes and mock proofs used by the compute unit benchmarks and
+ * the handler tests. Both run against the same local validator, so setup
+ * that creates shared accounts (the circuit registry) is idempotent.
+ */
+
+import * as anchor from '@coral-xyz/anchor';
+import { PublicKey, SystemProgram } from '@solana/web3.js';
+import assert from 'assert';
+import { createHash } from 'crypto';
+import * as fs from 'fs';
+import * as path from 'path';
+
+const VERIFYING_KEYS = path.join(__dirname, '..', 'programs', 'veil-protocol', 'verifying-keys');
+
+export const provider = anchor.AnchorProvider.env();
+anchor.setProvider(provider);
+export const program = anchor.workspace.VeilProtocol as anchor.Program;
+export const payer = provider.wallet.publicKey;
+
+/** 32 bytes below the BN128 modulus, tagged so values differ per use */
+export function fieldBytes(tag: number): number[] {
+  const bytes = new Array(32).fill(0);
+  bytes[31] = tag;
+  return bytes;
+}
+
+/** u64 public signal, big-endian in the low 8 bytes (`encode_u64_signal`) */
+export function u64Signal(value: number): Buffer {
+  const signal = Buffer.alloc(32);
+  signal.writeBigUInt64BE(BigInt(value), 24);
+  return signal;
+}
+
+/** Little-endian u32 / u64, as the program hashes indices and amounts */
+export function u32Le(value: number): Buffer {
+  const bytes = Buffer.alloc(4);
+  bytes.writeUInt32LE(value);
+  return bytes;
+}
+
+export function u64Le(value: number): Buffer {
+  const bytes = Buffer.alloc(8);
+  bytes.writeBigUInt64LE(BigInt(value));
+  return bytes;
+}
+
+/** G1 generator (1, 2), its negation (1, p - 2) and its double */
+const G1_POINTS = [
+  '0000000000000000000000000000000000000000000000000000000000000001' +
+  '0000000000000000000000000000000000000000000000000000000000000002',
+  '0000000000000000000000000000000000000000000000000000000000000001' +
+  '30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45',
+  '030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3' +
+  '15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4',
+].map((hex) => Buffer.from(hex, 'hex'));
+
+/** EIP-197 G2 generator, imaginary coefficients first */
+const G2_GENERATOR = Buffer.from(
+  '198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2' +
+  '1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed' +
+  '090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b' +
+  '12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa',
+  'hex'
+);
+
+/** Groth16-shaped proof whose points are on their curves */
+export function mockProof(): Buffer {
+  return Buffer.concat([G1_POINTS[0], G2_GENERATOR, G1_POINTS[0]]);
+}
+
+/**
+ * On-curve proof the program's stub verifier accepts for `accepts`
+ * The stub rejects about one digest in 256, so try the combinations of
+ * pi_a and pi_c until one passes instead of leaving the case to chance
+ */
+export function acceptedProof(accepts: (proof: Buffer) => boolean): Buffer {
+  for (const piA of G1_POINTS) {
+    for (const piC of G1_POINTS) {
+      const proof = Buffer.concat([piA, G2_GENERATOR, piC]);
+      if (accepts(proof)) return proof;
+    }
+  }
+  throw new Error('no mock proof is accepted');
+}
+
+/** `verify_groth16_stub` for the circuit called `name` over `signals` */
+export function groth16Stub(name: string, signals: (Buffer | Uint8Array | number[])[]) {
+  return (proof: Buffer) =>
+    hashDomain('veil:proof:v2', Buffer.from(name), proof, ...signals.map((signal) => Buffer.from(signal)))[0] !== 0xff;
+}
+
+/** Proof whose pi_b is off the curve, which every verifier rejects */
+export function rejectedProof(): Buffer {
+  const proof = mockProof();
+  proof[64 + 31] ^= 1;
+  return proof;
+}
+
+export function pda(seeds: (Buffer | Uint8Array)[]): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+}
+
+/** H(tag_len || tag || parts...), as `hash_domain` in the program */
+export function hashDomain(tag: string, ...parts: (Buffer | Uint8Array)[]): number[] {
+  const hash = createHash('sha256').update(Buffer.from([tag.length])).update(tag);
+  for (const part of parts) hash.update(part);
+  return [...hash.digest()];
+}
+
+/** `Circuit::id` of the circuit called `name` */
+export function circuitId(name: string): number[] {
+  return hashDomain('veil:circuit_id:v2', Buffer.from(name));
+}
+
+/** Registry the proof instructions check verifying keys against */
+export const circuitRegistry = pda([Buffer.from('circuit_registry'), payer.toBuffer()]);
+
+/**
+ * Register the compiled-in development key of each circuit (`Circuit::name`),
+ * creating the registry on first use and skipping circuits already in it
+ */
+export async function registerCircuits(circuits: Record<string, string>): Promise<void> {
+  let registry = await program.account.circuitRegistry.fetchNullable(circuitRegistry);
+  if (!registry) {
+    await provider.sendAndConfirm(await program.methods
+      .createCircuitRegistry(fieldBytes(99))
+      .accounts({ circuitRegistry, authority: payer, systemProgram: SystemProgram.programId })
+      .transaction());
+    registry = await program.account.circuitRegistry.fetch(circuitRegistry);
+  }
+
+  const registered = new Set(
+    (registry.entries as { circuitId: number[] }[]).map((entry) => Buffer.from(entry.circuitId).toString('hex'))
+  );
+  for (const [variant, name] of Object.entries(circuits)) {
+    if (registered.has(Buffer.from(circuitId(name)).toString('hex'))) continue;
+
+    const vkHash = hashDomain('veil:verifying_key:v2', fs.readFileSync(path.join(VERIFYING_KEYS, `${name}.vk`)));
+    await provider.sendAndConfirm(await program.methods
+      .registerCircuit({ [variant]: {} }, vkHash)
+      .accounts({ circuitRegistry, authority: payer })
+      .transaction());
+  }
+}
+
+/** Assert that `call` fails with the program error `code` */
+export async function expectError(call: Promise<unknown>, code: string): Promise<void> {
+  await assert.rejects(call, (err: any) => {
+    assert.strictEqual(err?.error?.errorCode?.code, code, err?.logs?.join('\n') ?? String(err));
+    return true;
+  });
+}
+
+export async function balance(address: PublicKey): Promise<number> {
+  return provider.connection.getBalance(address);
+}